@@ -13,6 +13,7 @@ use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::net::Ipv4Addr;
 use std::num::NonZeroUsize;
+use std::ops::Deref;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -42,7 +43,7 @@ use mz_ore::collections::CollectionExt;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::{to_datetime, EpochMillis, NowFn};
 use mz_pgrepr::oid::FIRST_USER_OID;
-use mz_repr::{explain_new::ExprHumanizer, Diff, GlobalId, RelationDesc, ScalarType};
+use mz_repr::{explain_new::ExprHumanizer, Diff, GlobalId, RelationDesc, ScalarType, Timestamp};
 use mz_secrets::InMemorySecretsController;
 use mz_sql::ast::display::AstDisplay;
 use mz_sql::ast::Expr;
@@ -58,13 +59,15 @@ use mz_sql::names::{
     SchemaSpecifier,
 };
 use mz_sql::plan::{
-    AlterOptionParameter, CreateConnectionPlan, CreateIndexPlan, CreateMaterializedViewPlan,
-    CreateSecretPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan,
-    CreateViewPlan, Params, Plan, PlanContext, StatementDesc,
-    StorageHostConfig as PlanStorageHostConfig,
+    AlterOptionParameter, CreateConnectionPlan, CreateFunctionPlan, CreateIndexPlan,
+    CreateMaterializedViewPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan,
+    CreateSubscriptionPlan, CreateTablePlan, CreateTypePlan, CreateViewPlan, Params, Plan,
+    PlanContext, StatementDesc, StorageHostConfig as PlanStorageHostConfig,
 };
 use mz_sql::{plan, DEFAULT_SCHEMA};
-use mz_sql_parser::ast::{CreateSinkOption, CreateSourceOption, Statement, WithOptionValue};
+use mz_sql_parser::ast::{
+    CreateSinkOption, CreateSourceOption, CreateSubscriptionOption, Statement, WithOptionValue,
+};
 use mz_ssh_util::keys::SshKeyPairSet;
 use mz_stash::{Append, Postgres, Sqlite};
 use mz_storage_client::types::hosts::{StorageHostConfig, StorageHostResourceAllocation};
@@ -256,7 +259,8 @@ impl CatalogState {
             | CatalogItem::Source(_)
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
-            | CatalogItem::Secret(_) => (),
+            | CatalogItem::Secret(_)
+            | CatalogItem::Subscription(_) => (),
         }
     }
 
@@ -273,7 +277,8 @@ impl CatalogState {
             | CatalogItem::Sink(_)
             | CatalogItem::Type(_)
             | CatalogItem::Secret(_)
-            | CatalogItem::Connection(_) => false,
+            | CatalogItem::Connection(_)
+            | CatalogItem::Subscription(_) => false,
         }
     }
 
@@ -636,8 +641,12 @@ impl CatalogState {
             &metadata.name().qualifiers.schema_spec,
             conn_id,
         );
-        schema
-            .items
+        let items = if let CatalogItem::Func(_) = metadata.item {
+            &mut schema.functions
+        } else {
+            &mut schema.items
+        };
+        items
             .remove(&metadata.name().item)
             .expect("catalog out of sync");
 
@@ -1038,14 +1047,12 @@ impl CatalogState {
                     Err(e) => return Err(e),
                 }
             }
-            None => match self
-                .get_schema(
-                    &ResolvedDatabaseSpecifier::Ambient,
-                    &SchemaSpecifier::Temporary,
-                    conn_id,
-                )
-                .items
-                .get(&name.item)
+            None => match get_schema_entries(self.get_schema(
+                &ResolvedDatabaseSpecifier::Ambient,
+                &SchemaSpecifier::Temporary,
+                conn_id,
+            ))
+            .get(&name.item)
             {
                 Some(id) => return Ok(self.get_entry(id)),
                 None => search_path.to_vec(),
@@ -1221,6 +1228,164 @@ impl CatalogState {
         tx.insert_storage_usage_event(details);
         Ok(())
     }
+
+    pub fn for_sessionless_user(&self, user: User) -> ConnCatalog {
+        ConnCatalog {
+            state: Cow::Borrowed(self),
+            conn_id: SYSTEM_CONN_ID,
+            compute_instance: "default".into(),
+            database: self
+                .resolve_database(DEFAULT_DATABASE_NAME)
+                .ok()
+                .map(|db| db.id()),
+            search_path: Vec::new(),
+            user,
+            prepared_statements: None,
+        }
+    }
+
+    // Leaving the system's search path empty allows us to catch issues
+    // where catalog object names have not been normalized correctly.
+    pub fn for_system_session(&self) -> ConnCatalog {
+        self.for_sessionless_user(SYSTEM_USER.clone())
+    }
+
+    // Parses the given SQL string into a `CatalogItem`.
+    pub(crate) fn parse_item(
+        &self,
+        create_sql: String,
+        pcx: Option<&PlanContext>,
+    ) -> Result<CatalogItem, anyhow::Error> {
+        let session_catalog = self.for_system_session();
+        let stmt = mz_sql::parse::parse(&create_sql)?.into_element();
+        let (stmt, depends_on) = mz_sql::names::resolve(&session_catalog, stmt)?;
+        let depends_on = depends_on.into_iter().collect();
+        let plan = mz_sql::plan::plan(pcx, &session_catalog, stmt, &Params::empty())?;
+        Ok(match plan {
+            Plan::CreateTable(CreateTablePlan { table, .. }) => CatalogItem::Table(Table {
+                create_sql: table.create_sql,
+                desc: table.desc,
+                defaults: table.defaults,
+                conn_id: None,
+                depends_on,
+            }),
+            Plan::CreateSource(CreateSourcePlan {
+                source,
+                timeline,
+                host_config,
+                ..
+            }) => {
+                let allow_undefined_size = true;
+                CatalogItem::Source(Source {
+                    create_sql: source.create_sql,
+                    data_source: match source.ingestion {
+                        Some(ingestion) => DataSourceDesc::Ingestion(Ingestion {
+                            desc: ingestion.desc,
+                            source_imports: ingestion.source_imports,
+                            subsource_exports: ingestion.subsource_exports,
+                            host_config: self
+                                .resolve_storage_host_config(host_config, allow_undefined_size)?,
+                        }),
+                        None => DataSourceDesc::Source,
+                    },
+                    desc: source.desc,
+                    timeline,
+                    depends_on,
+                })
+            }
+            Plan::CreateView(CreateViewPlan { view, .. }) => {
+                let mut optimizer = Optimizer::logical_optimizer();
+                let optimized_expr = optimizer.optimize(view.expr)?;
+                let desc = RelationDesc::new(optimized_expr.typ(), view.column_names);
+                CatalogItem::View(View {
+                    create_sql: view.create_sql,
+                    optimized_expr,
+                    desc,
+                    conn_id: None,
+                    depends_on,
+                })
+            }
+            Plan::CreateMaterializedView(CreateMaterializedViewPlan {
+                materialized_view, ..
+            }) => {
+                let mut optimizer = Optimizer::logical_optimizer();
+                let optimized_expr = optimizer.optimize(materialized_view.expr)?;
+                let desc = RelationDesc::new(optimized_expr.typ(), materialized_view.column_names);
+                CatalogItem::MaterializedView(MaterializedView {
+                    create_sql: materialized_view.create_sql,
+                    optimized_expr,
+                    desc,
+                    depends_on,
+                    compute_instance: materialized_view.compute_instance,
+                })
+            }
+            Plan::CreateIndex(CreateIndexPlan { index, .. }) => CatalogItem::Index(Index {
+                create_sql: index.create_sql,
+                on: index.on,
+                keys: index.keys,
+                conn_id: None,
+                depends_on,
+                compute_instance: index.compute_instance,
+            }),
+            Plan::CreateSink(CreateSinkPlan {
+                sink,
+                with_snapshot,
+                host_config,
+                ..
+            }) => {
+                let allow_undefined_size = true;
+                CatalogItem::Sink(Sink {
+                    create_sql: sink.create_sql,
+                    from: sink.from,
+                    connection: StorageSinkConnectionState::Pending(sink.connection_builder),
+                    envelope: sink.envelope,
+                    with_snapshot,
+                    depends_on,
+                    host_config: self
+                        .resolve_storage_host_config(host_config, allow_undefined_size)?,
+                })
+            }
+            Plan::CreateType(CreateTypePlan { typ, .. }) => CatalogItem::Type(Type {
+                create_sql: typ.create_sql,
+                details: CatalogTypeDetails {
+                    array_id: None,
+                    typ: typ.inner,
+                },
+                depends_on,
+            }),
+            Plan::CreateSecret(CreateSecretPlan { secret, .. }) => CatalogItem::Secret(Secret {
+                create_sql: secret.create_sql,
+            }),
+            Plan::CreateSubscription(CreateSubscriptionPlan { subscription, .. }) => {
+                CatalogItem::Subscription(Subscription {
+                    create_sql: subscription.create_sql,
+                    from: subscription.from,
+                    timestamp: subscription
+                        .timestamp
+                        .expect("persisted subscriptions record their timestamp"),
+                    snapshot: subscription.snapshot,
+                    depends_on,
+                })
+            }
+            Plan::CreateFunction(CreateFunctionPlan { function, .. }) => CatalogItem::Func(Func {
+                create_sql: Some(function.create_sql),
+                inner: FuncDefinition::User(Arc::new(mz_sql::func::Func::user_defined(
+                    function.param_types,
+                    function.returns,
+                    function.body,
+                ))),
+                depends_on,
+            }),
+            Plan::CreateConnection(CreateConnectionPlan { connection, .. }) => {
+                CatalogItem::Connection(Connection {
+                    create_sql: connection.create_sql,
+                    connection: connection.connection,
+                    depends_on,
+                })
+            }
+            _ => bail!("catalog entry generated inappropriate plan"),
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -1362,6 +1527,7 @@ pub enum CatalogItem {
     Func(Func),
     Secret(Secret),
     Connection(Connection),
+    Subscription(Subscription),
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -1506,8 +1672,31 @@ pub struct Type {
 
 #[derive(Debug, Clone, Serialize)]
 pub struct Func {
+    /// The SQL that created the function, or `None` for built-in functions.
+    pub create_sql: Option<String>,
     #[serde(skip)]
-    pub inner: &'static mz_sql::func::Func,
+    pub inner: FuncDefinition,
+    pub depends_on: Vec<GlobalId>,
+}
+
+/// The definition of a [`Func`].
+#[derive(Debug, Clone)]
+pub enum FuncDefinition {
+    /// A function provided by the system.
+    Builtin(&'static mz_sql::func::Func),
+    /// A function created with `CREATE FUNCTION`.
+    User(Arc<mz_sql::func::Func>),
+}
+
+impl Deref for FuncDefinition {
+    type Target = mz_sql::func::Func;
+
+    fn deref(&self) -> &mz_sql::func::Func {
+        match self {
+            FuncDefinition::Builtin(func) => func,
+            FuncDefinition::User(func) => func,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -1522,6 +1711,20 @@ pub struct Connection {
     pub depends_on: Vec<GlobalId>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct Subscription {
+    pub create_sql: String,
+    /// The storage collection the subscription reads.
+    pub from: GlobalId,
+    /// The timestamp the subscription resumes from. Mirrors the `TIMESTAMP`
+    /// option in `create_sql`.
+    pub timestamp: Timestamp,
+    /// Whether the snapshot at `timestamp` is emitted on the next attach.
+    /// Mirrors the `SNAPSHOT` option in `create_sql`.
+    pub snapshot: bool,
+    pub depends_on: Vec<GlobalId>,
+}
+
 pub struct TransactionResult<R> {
     pub builtin_table_updates: Vec<BuiltinTableUpdate>,
     pub audit_events: Vec<VersionedEvent>,
@@ -1544,6 +1747,7 @@ impl CatalogItem {
             CatalogItem::Func(_) => mz_sql::catalog::CatalogItemType::Func,
             CatalogItem::Secret(_) => mz_sql::catalog::CatalogItemType::Secret,
             CatalogItem::Connection(_) => mz_sql::catalog::CatalogItemType::Connection,
+            CatalogItem::Subscription(_) => mz_sql::catalog::CatalogItemType::Subscription,
         }
     }
 
@@ -1559,19 +1763,17 @@ impl CatalogItem {
             | CatalogItem::Sink(_)
             | CatalogItem::Type(_)
             | CatalogItem::Secret(_)
-            | CatalogItem::Connection(_) => Err(SqlCatalogError::InvalidDependency {
+            | CatalogItem::Connection(_)
+            | CatalogItem::Subscription(_) => Err(SqlCatalogError::InvalidDependency {
                 name: name.to_string(),
                 typ: self.typ(),
             }),
         }
     }
 
-    pub fn func(
-        &self,
-        name: &QualifiedObjectName,
-    ) -> Result<&'static mz_sql::func::Func, SqlCatalogError> {
+    pub fn func(&self, name: &QualifiedObjectName) -> Result<&mz_sql::func::Func, SqlCatalogError> {
         match &self {
-            CatalogItem::Func(func) => Ok(func.inner),
+            CatalogItem::Func(func) => Ok(&*func.inner),
             _ => Err(SqlCatalogError::UnexpectedType(
                 name.item.to_string(),
                 CatalogItemType::Func,
@@ -1599,7 +1801,7 @@ impl CatalogItem {
     /// upon.
     pub fn uses(&self) -> &[GlobalId] {
         match self {
-            CatalogItem::Func(_) => &[],
+            CatalogItem::Func(func) => &func.depends_on,
             CatalogItem::Index(idx) => &idx.depends_on,
             CatalogItem::Sink(sink) => &sink.depends_on,
             CatalogItem::Source(source) => &source.depends_on,
@@ -1610,6 +1812,7 @@ impl CatalogItem {
             CatalogItem::MaterializedView(mview) => &mview.depends_on,
             CatalogItem::Secret(_) => &[],
             CatalogItem::Connection(connection) => &connection.depends_on,
+            CatalogItem::Subscription(subscription) => &subscription.depends_on,
         }
     }
 
@@ -1626,7 +1829,8 @@ impl CatalogItem {
             | CatalogItem::View(_)
             | CatalogItem::MaterializedView(_)
             | CatalogItem::Secret(_)
-            | CatalogItem::Connection(_) => false,
+            | CatalogItem::Connection(_)
+            | CatalogItem::Subscription(_) => false,
             CatalogItem::Sink(s) => match s.connection {
                 StorageSinkConnectionState::Pending(_) => true,
                 StorageSinkConnectionState::Ready(_) => false,
@@ -1648,7 +1852,8 @@ impl CatalogItem {
             | CatalogItem::Secret(_)
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
-            | CatalogItem::Connection(_) => None,
+            | CatalogItem::Connection(_)
+            | CatalogItem::Subscription(_) => None,
         }
     }
 
@@ -1676,6 +1881,39 @@ impl CatalogItem {
             Ok(create_stmt.to_ast_string_stable())
         };
 
+        if let CatalogItem::Type(_) = self {
+            unreachable!("{}s cannot be renamed", self.typ());
+        }
+        self.rewrite_create_sql(do_rewrite)
+    }
+
+    /// Returns a clone of `self` with all references to the schemas named
+    /// `schema_a` and `schema_b` in `database` swapped, or errors if a
+    /// reference is ambiguous.
+    fn swap_schema_refs(
+        &self,
+        database: &str,
+        schema_a: &str,
+        schema_b: &str,
+    ) -> Result<CatalogItem, String> {
+        self.rewrite_create_sql(|create_sql| {
+            let mut create_stmt = mz_sql::parse::parse(&create_sql).unwrap().into_element();
+            mz_sql::ast::transform::create_stmt_swap_schemas(
+                &mut create_stmt,
+                database,
+                schema_a,
+                schema_b,
+            )?;
+            Ok(create_stmt.to_ast_string_stable())
+        })
+    }
+
+    /// Returns a clone of `self` with its `CREATE` statement replaced by the
+    /// result of applying `do_rewrite` to it.
+    fn rewrite_create_sql<F>(&self, do_rewrite: F) -> Result<CatalogItem, String>
+    where
+        F: FnOnce(String) -> Result<String, String>,
+    {
         match self {
             CatalogItem::Table(i) => {
                 let mut i = i.clone();
@@ -1713,14 +1951,27 @@ impl CatalogItem {
                 i.create_sql = do_rewrite(i.create_sql)?;
                 Ok(CatalogItem::Secret(i))
             }
-            CatalogItem::Func(_) | CatalogItem::Type(_) => {
-                unreachable!("{}s cannot be renamed", self.typ())
+            CatalogItem::Func(i) => {
+                let mut i = i.clone();
+                let create_sql = i.create_sql.expect("builtin functions cannot be renamed");
+                i.create_sql = Some(do_rewrite(create_sql)?);
+                Ok(CatalogItem::Func(i))
+            }
+            CatalogItem::Type(i) => {
+                let mut i = i.clone();
+                i.create_sql = do_rewrite(i.create_sql)?;
+                Ok(CatalogItem::Type(i))
             }
             CatalogItem::Connection(i) => {
                 let mut i = i.clone();
                 i.create_sql = do_rewrite(i.create_sql)?;
                 Ok(CatalogItem::Connection(i))
             }
+            CatalogItem::Subscription(i) => {
+                let mut i = i.clone();
+                i.create_sql = do_rewrite(i.create_sql)?;
+                Ok(CatalogItem::Subscription(i))
+            }
         }
     }
 
@@ -1736,7 +1987,8 @@ impl CatalogItem {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
-            | CatalogItem::Connection(_) => None,
+            | CatalogItem::Connection(_)
+            | CatalogItem::Subscription(_) => None,
         }
     }
 }
@@ -1748,7 +2000,7 @@ impl CatalogEntry {
     }
 
     /// Returns the [`mz_sql::func::Func`] associated with this `CatalogEntry`.
-    pub fn func(&self) -> Result<&'static mz_sql::func::Func, SqlCatalogError> {
+    pub fn func(&self) -> Result<&mz_sql::func::Func, SqlCatalogError> {
         self.item.func(self.name())
     }
 
@@ -1760,6 +2012,14 @@ impl CatalogEntry {
         }
     }
 
+    /// Returns the inner [`Table`] if this entry is a table, else `None`.
+    pub fn table(&self) -> Option<&Table> {
+        match self.item() {
+            CatalogItem::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+
     /// Returns the inner [`Source`] if this entry is a source, else `None`.
     pub fn source(&self) -> Option<&Source> {
         match self.item() {
@@ -1776,6 +2036,15 @@ impl CatalogEntry {
         }
     }
 
+    /// Returns the inner [`Subscription`] if this entry is a subscription, else
+    /// `None`.
+    pub fn subscription(&self) -> Option<&Subscription> {
+        match self.item() {
+            CatalogItem::Subscription(subscription) => Some(subscription),
+            _ => None,
+        }
+    }
+
     /// Returns the inner [`Secret`] if this entry is a secret, else `None`.
     pub fn secret(&self) -> Option<&Secret> {
         match self.item() {
@@ -2198,7 +2467,11 @@ impl<S: Append> Catalog<S> {
                         id,
                         oid,
                         name.clone(),
-                        CatalogItem::Func(Func { inner: func.inner }),
+                        CatalogItem::Func(Func {
+                            create_sql: None,
+                            inner: FuncDefinition::Builtin(func.inner),
+                            depends_on: vec![],
+                        }),
                     );
                 }
 
@@ -2745,7 +3018,8 @@ impl<S: Append> Catalog<S> {
                 CatalogItem::Type(_)
                 | CatalogItem::Func(_)
                 | CatalogItem::Secret(_)
-                | CatalogItem::Connection(_) => unreachable!(
+                | CatalogItem::Connection(_)
+                | CatalogItem::Subscription(_) => unreachable!(
                     "impossible to migrate schema for builtin {}",
                     entry.item().typ()
                 ),
@@ -2959,18 +3233,7 @@ impl<S: Append> Catalog<S> {
     }
 
     pub fn for_sessionless_user(&self, user: User) -> ConnCatalog {
-        ConnCatalog {
-            state: Cow::Borrowed(&self.state),
-            conn_id: SYSTEM_CONN_ID,
-            compute_instance: "default".into(),
-            database: self
-                .resolve_database(DEFAULT_DATABASE_NAME)
-                .ok()
-                .map(|db| db.id()),
-            search_path: Vec::new(),
-            user,
-            prepared_statements: None,
-        }
+        self.state.for_sessionless_user(user)
     }
 
     // Leaving the system's search path empty allows us to catch issues
@@ -3595,6 +3858,15 @@ impl<S: Append> Catalog<S> {
                 to_name: QualifiedObjectName,
                 to_item: CatalogItem,
             },
+            SwapSchemas {
+                database_id: DatabaseId,
+                schema_a: SchemaId,
+                schema_b: SchemaId,
+            },
+            SwapComputeInstances {
+                name_a: String,
+                name_b: String,
+            },
             UpdateComputeInstanceStatus {
                 event: ComputeInstanceEvent,
             },
@@ -3621,6 +3893,7 @@ impl<S: Append> Catalog<S> {
                 SqlCatalogItemType::Secret => ObjectType::Secret,
                 SqlCatalogItemType::Sink => ObjectType::Sink,
                 SqlCatalogItemType::Source => ObjectType::Source,
+                SqlCatalogItemType::Subscription => ObjectType::Subscription,
                 SqlCatalogItemType::Table => ObjectType::Table,
                 SqlCatalogItemType::Type => ObjectType::Type,
                 SqlCatalogItemType::View => ObjectType::View,
@@ -3863,36 +4136,190 @@ impl<S: Append> Catalog<S> {
                         )?;
                     }
                 }
-                Op::CreateDatabase {
-                    name,
-                    oid,
-                    public_schema_oid,
-                } => {
-                    let database_id = tx.insert_database(&name)?;
-                    let schema_id = tx.insert_schema(database_id, DEFAULT_SCHEMA)?;
+                Op::AlterSourceSchema { id, source } => {
+                    let entry = state.get_entry(&id);
+                    let name = entry.name().clone();
+                    let full_name = state.resolve_full_name(&name, entry.conn_id());
+
+                    if entry.id().is_system() {
+                        return Err(AdapterError::Catalog(Error::new(
+                            ErrorKind::ReadOnlySystemSchema(full_name.schema),
+                        )));
+                    }
+
+                    let old_source = match entry.item() {
+                        CatalogItem::Source(source) => source.clone(),
+                        other => {
+                            coord_bail!("ALTER SOURCE entry was not a source: {}", other.typ())
+                        }
+                    };
+                    let data_source = match (old_source.data_source, source.ingestion) {
+                        (DataSourceDesc::Ingestion(old), Some(new)) => {
+                            DataSourceDesc::Ingestion(Ingestion {
+                                desc: new.desc,
+                                source_imports: new.source_imports,
+                                subsource_exports: new.subsource_exports,
+                                host_config: old.host_config,
+                            })
+                        }
+                        _ => {
+                            coord_bail!("source {id} does not ingest data from an external system")
+                        }
+                    };
+                    let source = CatalogItem::Source(Source {
+                        create_sql: source.create_sql,
+                        data_source,
+                        desc: source.desc,
+                        ..old_source
+                    });
+
                     state.add_to_audit_log(
                         session,
                         tx,
                         builtin_table_updates,
                         audit_events,
-                        EventType::Create,
-                        ObjectType::Database,
-                        EventDetails::IdNameV1(mz_audit_log::IdNameV1 {
-                            id: database_id.to_string(),
-                            name: name.clone(),
+                        EventType::Alter,
+                        ObjectType::Source,
+                        EventDetails::IdFullNameV1(IdFullNameV1 {
+                            id: id.to_string(),
+                            name: Self::full_name_detail(&full_name),
                         }),
                     )?;
+                    tx.update_item(id, &name.item, &Self::serialize_item(&source))?;
+                    // NB: this will be re-incremented by the action below.
+                    builtin_table_updates.extend(state.pack_item_update(id, -1));
                     catalog_action(
                         state,
                         builtin_table_updates,
-                        Action::CreateDatabase {
-                            id: database_id,
-                            oid,
-                            name: name.clone(),
+                        Action::UpdateItem {
+                            id,
+                            to_name: name,
+                            to_item: source,
                         },
                     )?;
-                    state.add_to_audit_log(
-                        session,
+
+                    update_dependents(state, builtin_table_updates, id, "source")?;
+                }
+                Op::AlterTable { id, table } => {
+                    let entry = state.get_entry(&id);
+                    let name = entry.name().clone();
+                    let conn_id = entry.conn_id();
+                    let full_name = state.resolve_full_name(&name, conn_id);
+
+                    if entry.id().is_system() {
+                        return Err(AdapterError::Catalog(Error::new(
+                            ErrorKind::ReadOnlySystemSchema(full_name.schema),
+                        )));
+                    }
+
+                    let table = CatalogItem::Table(Table {
+                        // Name resolution of the altered definition may have
+                        // ordered its dependencies differently, but they are
+                        // unchanged.
+                        depends_on: entry.uses().to_vec(),
+                        conn_id,
+                        ..table
+                    });
+                    if Self::should_audit_log_item(&table) {
+                        state.add_to_audit_log(
+                            session,
+                            tx,
+                            builtin_table_updates,
+                            audit_events,
+                            EventType::Alter,
+                            ObjectType::Table,
+                            EventDetails::IdFullNameV1(IdFullNameV1 {
+                                id: id.to_string(),
+                                name: Self::full_name_detail(&full_name),
+                            }),
+                        )?;
+                    }
+                    if !table.is_temporary() {
+                        tx.update_item(id, &name.item, &Self::serialize_item(&table))?;
+                    }
+                    builtin_table_updates.extend(state.pack_item_update(id, -1));
+                    catalog_action(
+                        state,
+                        builtin_table_updates,
+                        Action::UpdateItem {
+                            id,
+                            to_name: name,
+                            to_item: table,
+                        },
+                    )?;
+
+                    update_dependents(state, builtin_table_updates, id, "table")?;
+                }
+                Op::AlterSubscription { id, timestamp } => {
+                    let entry = state.get_entry(&id);
+                    let name = entry.name().clone();
+
+                    let old_subscription = match entry.item() {
+                        CatalogItem::Subscription(subscription) => subscription.clone(),
+                        other => {
+                            coord_bail!(
+                                "ALTER SUBSCRIPTION entry was not a subscription: {}",
+                                other.typ()
+                            )
+                        }
+                    };
+
+                    // Everything up to and including the acknowledged
+                    // timestamp has been consumed, so there is no snapshot
+                    // left to emit.
+                    let create_sql =
+                        subscription_create_sql(&old_subscription.create_sql, timestamp, false)?;
+                    let subscription = CatalogItem::Subscription(Subscription {
+                        create_sql,
+                        timestamp,
+                        snapshot: false,
+                        ..old_subscription
+                    });
+
+                    tx.update_item(id, &name.item, &Self::serialize_item(&subscription))?;
+
+                    // NB: this will be re-incremented by the action below.
+                    builtin_table_updates.extend(state.pack_item_update(id, -1));
+                    catalog_action(
+                        state,
+                        builtin_table_updates,
+                        Action::UpdateItem {
+                            id,
+                            to_name: name,
+                            to_item: subscription,
+                        },
+                    )?;
+                }
+                Op::CreateDatabase {
+                    name,
+                    oid,
+                    public_schema_oid,
+                } => {
+                    let database_id = tx.insert_database(&name)?;
+                    let schema_id = tx.insert_schema(database_id, DEFAULT_SCHEMA)?;
+                    state.add_to_audit_log(
+                        session,
+                        tx,
+                        builtin_table_updates,
+                        audit_events,
+                        EventType::Create,
+                        ObjectType::Database,
+                        EventDetails::IdNameV1(mz_audit_log::IdNameV1 {
+                            id: database_id.to_string(),
+                            name: name.clone(),
+                        }),
+                    )?;
+                    catalog_action(
+                        state,
+                        builtin_table_updates,
+                        Action::CreateDatabase {
+                            id: database_id,
+                            oid,
+                            name: name.clone(),
+                        },
+                    )?;
+                    state.add_to_audit_log(
+                        session,
                         tx,
                         builtin_table_updates,
                         audit_events,
@@ -4489,6 +4916,117 @@ impl<S: Append> Catalog<S> {
                         catalog_action(state, builtin_table_updates, action)?;
                     }
                 }
+                Op::SwapSchemas {
+                    database_id,
+                    schema_a,
+                    schema_b,
+                } => {
+                    let database = &state.database_by_id[&database_id];
+                    let database_name = database.name.clone();
+                    let name_a = database.schemas_by_id[&schema_a].name.schema.clone();
+                    let name_b = database.schemas_by_id[&schema_b].name.schema.clone();
+
+                    tx.swap_schemas(&database_id, &schema_a, &schema_b)?;
+
+                    // Every item in either schema, as well as every item that
+                    // depends on one, may refer to the schemas by name.
+                    let mut ids = BTreeSet::new();
+                    for schema_id in [&schema_a, &schema_b] {
+                        let schema = &database.schemas_by_id[schema_id];
+                        for id in schema.items.values().chain(schema.functions.values()) {
+                            ids.insert(*id);
+                            ids.extend(state.get_entry(id).used_by());
+                        }
+                    }
+
+                    let mut actions = Vec::new();
+                    for id in ids {
+                        let entry = state.get_entry(&id);
+                        let to_item = entry
+                            .item
+                            .swap_schema_refs(&database_name, &name_a, &name_b)
+                            .map_err(|e| {
+                                let name = state
+                                    .resolve_full_name(&entry.name, entry.conn_id())
+                                    .to_string();
+                                Error::new(ErrorKind::from(AmbiguousRename {
+                                    depender: name.clone(),
+                                    dependee: name,
+                                    message: e,
+                                }))
+                            })?;
+                        if !to_item.is_temporary() {
+                            let serialized_item = Self::serialize_item(&to_item);
+                            tx.update_item(id, &entry.name().item, &serialized_item)?;
+                        }
+                        builtin_table_updates.extend(state.pack_item_update(id, -1));
+                        actions.push(Action::UpdateItem {
+                            id,
+                            to_name: entry.name().clone(),
+                            to_item,
+                        });
+                    }
+
+                    for (schema_id, to_name) in [(&schema_a, &name_b), (&schema_b, &name_a)] {
+                        builtin_table_updates.push(state.pack_schema_update(
+                            &ResolvedDatabaseSpecifier::Id(database_id.clone()),
+                            schema_id,
+                            -1,
+                        ));
+                        state.add_to_audit_log(
+                            session,
+                            tx,
+                            builtin_table_updates,
+                            audit_events,
+                            EventType::Alter,
+                            ObjectType::Schema,
+                            EventDetails::SchemaV1(mz_audit_log::SchemaV1 {
+                                id: schema_id.to_string(),
+                                name: to_name.clone(),
+                                database_name: database_name.clone(),
+                            }),
+                        )?;
+                    }
+
+                    actions.push(Action::SwapSchemas {
+                        database_id,
+                        schema_a,
+                        schema_b,
+                    });
+                    for action in actions {
+                        catalog_action(state, builtin_table_updates, action)?;
+                    }
+                }
+                Op::SwapComputeInstances { name_a, name_b } => {
+                    for name in [&name_a, &name_b] {
+                        if is_reserved_name(name) {
+                            return Err(AdapterError::Catalog(Error::new(
+                                ErrorKind::ReadOnlyComputeInstance(name.clone()),
+                            )));
+                        }
+                    }
+                    tx.swap_compute_instances(&name_a, &name_b)?;
+                    for (name, to_name) in [(&name_a, &name_b), (&name_b, &name_a)] {
+                        builtin_table_updates.push(state.pack_compute_instance_update(name, -1));
+                        state.add_to_audit_log(
+                            session,
+                            tx,
+                            builtin_table_updates,
+                            audit_events,
+                            EventType::Alter,
+                            ObjectType::Cluster,
+                            EventDetails::IdNameV1(mz_audit_log::IdNameV1 {
+                                id: state.compute_instances_by_name[name].to_string(),
+                                name: to_name.clone(),
+                            }),
+                        )?;
+                    }
+                    catalog_action(
+                        state,
+                        builtin_table_updates,
+                        Action::SwapComputeInstances { name_a, name_b },
+                    )?;
+                }
                 Op::UpdateComputeInstanceStatus { event } => {
                     // When we receive the first status update for a given
                     // replica process, there is no entry in the builtin table
@@ -4603,6 +5141,72 @@ impl<S: Append> Catalog<S> {
             };
         }
 
+        /// Re-plans the items that directly depend on `id` after its
+        /// definition changed.
+        ///
+        /// Items are serialized as their `CREATE` statement, which refers to
+        /// the columns of their dependencies by name. Planning those
+        /// statements against the altered item must succeed and must not
+        /// change the shape of the dependent, or the dependent would fail to
+        /// load on the next restart.
+        fn update_dependents(
+            state: &mut CatalogState,
+            builtin_table_updates: &mut Vec<BuiltinTableUpdate>,
+            id: GlobalId,
+            item_type: &'static str,
+        ) -> Result<(), AdapterError> {
+            let entry = state.get_entry(&id);
+            let full_name = state.resolve_full_name(entry.name(), entry.conn_id());
+            let mut actions = vec![];
+            for dep_id in entry.used_by() {
+                let dependent = state.get_entry(dep_id);
+                let dependency_err = |message: String| {
+                    AdapterError::Catalog(Error::new(ErrorKind::AlterDependency {
+                        item_type,
+                        item: full_name.to_string(),
+                        dependent: state
+                            .resolve_full_name(dependent.name(), dependent.conn_id())
+                            .to_string(),
+                        message,
+                    }))
+                };
+                if dependent.item().is_temporary() {
+                    return Err(dependency_err(
+                        "temporary items cannot be re-planned".into(),
+                    ));
+                }
+                let to_item = state
+                    .parse_item(
+                        dependent.create_sql().to_string(),
+                        Some(&PlanContext::zero()),
+                    )
+                    .map_err(|e| dependency_err(e.to_string()))?;
+                let to_item = match (dependent.item(), to_item) {
+                    (CatalogItem::View(old), CatalogItem::View(new)) if old.desc == new.desc => {
+                        CatalogItem::View(View {
+                            depends_on: old.depends_on.clone(),
+                            ..new
+                        })
+                    }
+                    _ => {
+                        return Err(dependency_err(
+                            "its columns would change; drop and recreate it instead".into(),
+                        ));
+                    }
+                };
+                builtin_table_updates.extend(state.pack_item_update(*dep_id, -1));
+                actions.push(Action::UpdateItem {
+                    id: *dep_id,
+                    to_name: dependent.name().clone(),
+                    to_item,
+                });
+            }
+            for action in actions {
+                catalog_action(state, builtin_table_updates, action)?;
+            }
+            Ok(())
+        }
+
         fn catalog_action(
             state: &mut CatalogState,
             builtin_table_updates: &mut Vec<BuiltinTableUpdate>,
@@ -4813,6 +5417,44 @@ impl<S: Append> Catalog<S> {
                     builtin_table_updates.extend(state.pack_item_update(id, 1));
                 }
 
+                Action::SwapSchemas {
+                    database_id,
+                    schema_a,
+                    schema_b,
+                } => {
+                    let db = state.database_by_id.get_mut(&database_id).unwrap();
+                    let name_a = db.schemas_by_id[&schema_a].name.schema.clone();
+                    let name_b = db.schemas_by_id[&schema_b].name.schema.clone();
+                    info!(
+                        "swap schemas {}.{} and {}.{}",
+                        db.name, name_a, db.name, name_b
+                    );
+                    db.schemas_by_id.get_mut(&schema_a).unwrap().name.schema = name_b.clone();
+                    db.schemas_by_id.get_mut(&schema_b).unwrap().name.schema = name_a.clone();
+                    db.schemas_by_name.insert(name_a, schema_b.clone());
+                    db.schemas_by_name.insert(name_b, schema_a.clone());
+                    for schema_id in [&schema_a, &schema_b] {
+                        builtin_table_updates.push(state.pack_schema_update(
+                            &ResolvedDatabaseSpecifier::Id(database_id.clone()),
+                            schema_id,
+                            1,
+                        ));
+                    }
+                }
+
+                Action::SwapComputeInstances { name_a, name_b } => {
+                    info!("swap clusters {} and {}", name_a, name_b);
+                    let id_a = state.compute_instances_by_name[&name_a];
+                    let id_b = state.compute_instances_by_name[&name_b];
+                    state.compute_instances_by_id.get_mut(&id_a).unwrap().name = name_b.clone();
+                    state.compute_instances_by_id.get_mut(&id_b).unwrap().name = name_a.clone();
+                    state.compute_instances_by_name.insert(name_a.clone(), id_b);
+                    state.compute_instances_by_name.insert(name_b.clone(), id_a);
+                    for name in [&name_a, &name_b] {
+                        builtin_table_updates.push(state.pack_compute_instance_update(name, 1));
+                    }
+                }
+
                 Action::UpdateComputeInstanceStatus { event } => {
                     // It is possible that we receive a status update for a
                     // replica that has already been dropped from the catalog.
@@ -4902,7 +5544,15 @@ impl<S: Append> Catalog<S> {
             CatalogItem::Connection(connection) => SerializedCatalogItem::V1 {
                 create_sql: connection.create_sql.clone(),
             },
-            CatalogItem::Func(_) => unreachable!("cannot serialize functions yet"),
+            CatalogItem::Subscription(subscription) => SerializedCatalogItem::V1 {
+                create_sql: subscription.create_sql.clone(),
+            },
+            CatalogItem::Func(func) => SerializedCatalogItem::V1 {
+                create_sql: func
+                    .create_sql
+                    .clone()
+                    .expect("cannot serialize builtin functions"),
+            },
         }
     }
 
@@ -4919,115 +5569,7 @@ impl<S: Append> Catalog<S> {
         create_sql: String,
         pcx: Option<&PlanContext>,
     ) -> Result<CatalogItem, anyhow::Error> {
-        let session_catalog = self.for_system_session();
-        let stmt = mz_sql::parse::parse(&create_sql)?.into_element();
-        let (stmt, depends_on) = mz_sql::names::resolve(&session_catalog, stmt)?;
-        let depends_on = depends_on.into_iter().collect();
-        let plan = mz_sql::plan::plan(pcx, &session_catalog, stmt, &Params::empty())?;
-        Ok(match plan {
-            Plan::CreateTable(CreateTablePlan { table, .. }) => CatalogItem::Table(Table {
-                create_sql: table.create_sql,
-                desc: table.desc,
-                defaults: table.defaults,
-                conn_id: None,
-                depends_on,
-            }),
-            Plan::CreateSource(CreateSourcePlan {
-                source,
-                timeline,
-                host_config,
-                ..
-            }) => {
-                let allow_undefined_size = true;
-                CatalogItem::Source(Source {
-                    create_sql: source.create_sql,
-                    data_source: match source.ingestion {
-                        Some(ingestion) => DataSourceDesc::Ingestion(Ingestion {
-                            desc: ingestion.desc,
-                            source_imports: ingestion.source_imports,
-                            subsource_exports: ingestion.subsource_exports,
-                            host_config: self
-                                .resolve_storage_host_config(host_config, allow_undefined_size)?,
-                        }),
-                        None => DataSourceDesc::Source,
-                    },
-                    desc: source.desc,
-                    timeline,
-                    depends_on,
-                })
-            }
-            Plan::CreateView(CreateViewPlan { view, .. }) => {
-                let mut optimizer = Optimizer::logical_optimizer();
-                let optimized_expr = optimizer.optimize(view.expr)?;
-                let desc = RelationDesc::new(optimized_expr.typ(), view.column_names);
-                CatalogItem::View(View {
-                    create_sql: view.create_sql,
-                    optimized_expr,
-                    desc,
-                    conn_id: None,
-                    depends_on,
-                })
-            }
-            Plan::CreateMaterializedView(CreateMaterializedViewPlan {
-                materialized_view, ..
-            }) => {
-                let mut optimizer = Optimizer::logical_optimizer();
-                let optimized_expr = optimizer.optimize(materialized_view.expr)?;
-                let desc = RelationDesc::new(optimized_expr.typ(), materialized_view.column_names);
-                CatalogItem::MaterializedView(MaterializedView {
-                    create_sql: materialized_view.create_sql,
-                    optimized_expr,
-                    desc,
-                    depends_on,
-                    compute_instance: materialized_view.compute_instance,
-                })
-            }
-            Plan::CreateIndex(CreateIndexPlan { index, .. }) => CatalogItem::Index(Index {
-                create_sql: index.create_sql,
-                on: index.on,
-                keys: index.keys,
-                conn_id: None,
-                depends_on,
-                compute_instance: index.compute_instance,
-            }),
-            Plan::CreateSink(CreateSinkPlan {
-                sink,
-                with_snapshot,
-                host_config,
-                ..
-            }) => {
-                let allow_undefined_size = true;
-                CatalogItem::Sink(Sink {
-                    create_sql: sink.create_sql,
-                    from: sink.from,
-                    connection: StorageSinkConnectionState::Pending(sink.connection_builder),
-                    envelope: sink.envelope,
-                    with_snapshot,
-                    depends_on,
-                    host_config: self
-                        .resolve_storage_host_config(host_config, allow_undefined_size)?,
-                })
-            }
-            Plan::CreateType(CreateTypePlan { typ, .. }) => CatalogItem::Type(Type {
-                create_sql: typ.create_sql,
-                details: CatalogTypeDetails {
-                    array_id: None,
-                    typ: typ.inner,
-                },
-                depends_on,
-            }),
-            Plan::CreateSecret(CreateSecretPlan { secret, .. }) => CatalogItem::Secret(Secret {
-                create_sql: secret.create_sql,
-            }),
-            Plan::CreateConnection(CreateConnectionPlan { connection, .. }) => {
-                CatalogItem::Connection(Connection {
-                    create_sql: connection.create_sql,
-                    connection: connection.connection,
-                    depends_on,
-                })
-            }
-            _ => bail!("catalog entry generated inappropriate plan"),
-        })
+        self.state.parse_item(create_sql, pcx)
     }
 
     pub fn uses_tables(&self, id: GlobalId) -> bool {
@@ -5220,6 +5762,37 @@ fn alter_host_config(
     }
 }
 
+/// Rewrites the `SNAPSHOT` and `TIMESTAMP` options of the `CREATE
+/// SUBSCRIPTION` statement `create_sql`.
+///
+/// The options are the durable record of how far a subscription has been
+/// acknowledged.
+pub(crate) fn subscription_create_sql(
+    create_sql: &str,
+    timestamp: Timestamp,
+    snapshot: bool,
+) -> Result<String, AdapterError> {
+    use mz_sql::ast::Value;
+    use mz_sql_parser::ast::CreateSubscriptionOptionName;
+
+    let mut stmt = mz_sql::parse::parse(create_sql).unwrap().into_element();
+    let create_stmt = match &mut stmt {
+        Statement::CreateSubscription(s) => s,
+        _ => coord_bail!("subscription was not created with a CREATE SUBSCRIPTION statement"),
+    };
+    create_stmt.with_options = vec![
+        CreateSubscriptionOption {
+            name: CreateSubscriptionOptionName::Snapshot,
+            value: Some(WithOptionValue::Value(Value::Boolean(snapshot))),
+        },
+        CreateSubscriptionOption {
+            name: CreateSubscriptionOptionName::Timestamp,
+            value: Some(WithOptionValue::Value(Value::Number(timestamp.to_string()))),
+        },
+    ];
+    Ok(stmt.to_ast_string_stable())
+}
+
 #[derive(Debug, Clone)]
 pub enum Op {
     AlterSink {
@@ -5232,6 +5805,23 @@ pub enum Op {
         size: AlterOptionParameter,
         remote: AlterOptionParameter,
     },
+    /// Replaces the definition of a source after its schema was refreshed and
+    /// re-plans every item that depends on it directly.
+    AlterSourceSchema {
+        id: GlobalId,
+        source: plan::Source,
+    },
+    /// Replaces the definition of a table and re-plans every item that
+    /// depends on it directly.
+    AlterTable {
+        id: GlobalId,
+        table: Table,
+    },
+    /// Records that a subscription has been acknowledged through `timestamp`.
+    AlterSubscription {
+        id: GlobalId,
+        timestamp: Timestamp,
+    },
     CreateDatabase {
         name: String,
         oid: u32,
@@ -5288,6 +5878,18 @@ pub enum Op {
         current_full_name: FullObjectName,
         to_name: String,
     },
+    /// Atomically exchanges the names of two schemas in the same database,
+    /// rewriting the definitions of every item that refers to either.
+    SwapSchemas {
+        database_id: DatabaseId,
+        schema_a: SchemaId,
+        schema_b: SchemaId,
+    },
+    /// Atomically exchanges the names of two compute instances.
+    SwapComputeInstances {
+        name_a: String,
+        name_b: String,
+    },
     UpdateComputeInstanceStatus {
         event: ComputeInstanceEvent,
     },
@@ -5326,7 +5928,7 @@ pub enum SerializedCatalogItem {
 ///
 /// A `None` value for `sources` or `views` indicates that we did not yet create them in the
 /// catalog. This is only used for initializing the default replica of the default compute
-/// instance.
+/// instance and for replicas imported from a catalog snapshot.
 /// To indicate the absence of sources/views, use `Some(Vec::new())`.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct SerializedComputeReplicaLogging {
@@ -5784,7 +6386,7 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
         self.desc(name)
     }
 
-    fn func(&self) -> Result<&'static mz_sql::func::Func, SqlCatalogError> {
+    fn func(&self) -> Result<&mz_sql::func::Func, SqlCatalogError> {
         self.func()
     }
 
@@ -5809,7 +6411,10 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
             CatalogItem::Type(Type { create_sql, .. }) => create_sql,
             CatalogItem::Secret(Secret { create_sql, .. }) => create_sql,
             CatalogItem::Connection(Connection { create_sql, .. }) => create_sql,
-            CatalogItem::Func(_) => "<builtin>",
+            CatalogItem::Subscription(Subscription { create_sql, .. }) => create_sql,
+            CatalogItem::Func(Func { create_sql, .. }) => {
+                create_sql.as_deref().unwrap_or("<builtin>")
+            }
             CatalogItem::Log(_) => "<builtin>",
         }
     }
@@ -5842,6 +6447,20 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
         }
     }
 
+    fn subscription_details(&self) -> Option<(GlobalId, Timestamp, bool)> {
+        if let CatalogItem::Subscription(Subscription {
+            from,
+            timestamp,
+            snapshot,
+            ..
+        }) = self.item()
+        {
+            Some((*from, *timestamp, *snapshot))
+        } else {
+            None
+        }
+    }
+
     fn uses(&self) -> &[GlobalId] {
         self.uses()
     }
@@ -5867,7 +6486,8 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
-            | CatalogItem::Connection(_) => vec![],
+            | CatalogItem::Connection(_)
+            | CatalogItem::Subscription(_) => vec![],
         }
     }
 }
@@ -5880,8 +6500,10 @@ mod tests {
     use std::error::Error;
 
     use mz_expr::{MirRelationExpr, OptimizedMirRelationExpr};
+    use mz_ore::collections::CollectionExt;
     use mz_ore::now::NOW_ZERO;
     use mz_repr::{GlobalId, RelationDesc, RelationType, ScalarType};
+    use mz_sql::ast::display::AstDisplay;
     use mz_sql::catalog::CatalogDatabase;
     use mz_sql::names::{
         ObjectQualifiers, PartialObjectName, QualifiedObjectName, ResolvedDatabaseSpecifier,
@@ -5890,9 +6512,14 @@ mod tests {
     use mz_sql::DEFAULT_SCHEMA;
     use mz_sql_parser::ast::Expr;
     use mz_stash::Sqlite;
+    use tokio::sync::mpsc;
 
-    use crate::catalog::{Catalog, CatalogItem, MaterializedView, Op, Table, SYSTEM_CONN_ID};
+    use crate::catalog::storage::{self, BootstrapArgs};
+    use crate::catalog::{
+        Catalog, CatalogItem, MaterializedView, Op, SerializedCatalogItem, Table, SYSTEM_CONN_ID,
+    };
     use crate::session::{Session, DEFAULT_DATABASE_NAME};
+    use crate::DUMMY_AVAILABILITY_ZONE;
 
     /// System sessions have an empty `search_path` so it's necessary to
     /// schema-qualify all referenced items.
@@ -5997,6 +6624,126 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_catalog_snapshot_roundtrip() -> Result<(), anyhow::Error> {
+        let catalog = Catalog::open_debug_sqlite(NOW_ZERO.clone()).await?;
+        let snapshot = {
+            let mut storage = catalog.storage().await;
+            let mut tx = storage.transaction().await?;
+            let database_id = tx.insert_database("test")?;
+            let schema_id = tx.insert_schema(database_id, "s")?;
+            tx.insert_user_role("joe")?;
+            let cluster_id = tx.insert_user_compute_instance("c", &Vec::new())?;
+            for (id, name, create_sql) in [
+                (5, "t", "CREATE TABLE test.s.t (a int4)".to_string()),
+                (
+                    7,
+                    "v",
+                    "CREATE VIEW test.s.v AS SELECT a FROM [u5 AS test.s.t]".to_string(),
+                ),
+                (
+                    9,
+                    "i",
+                    format!("CREATE INDEX i IN CLUSTER [{cluster_id}] ON [u5 AS test.s.t] (a)"),
+                ),
+            ] {
+                tx.insert_item(
+                    GlobalId::User(id),
+                    schema_id,
+                    name,
+                    SerializedCatalogItem::V1 { create_sql },
+                )?;
+            }
+            tx.commit().await?;
+            storage.export_snapshot().await?
+        };
+        assert_eq!(snapshot.version, storage::CATALOG_SNAPSHOT_VERSION);
+        assert_eq!(snapshot.items.len(), 3);
+
+        let (consolidations_tx, consolidations_rx) = mpsc::unbounded_channel();
+        std::mem::forget(consolidations_rx);
+        let mut storage = storage::Connection::open(
+            Sqlite::open(None)?,
+            &BootstrapArgs {
+                now: (NOW_ZERO)(),
+                default_cluster_replica_size: "1".into(),
+                builtin_cluster_replica_size: "1".into(),
+                default_availability_zone: DUMMY_AVAILABILITY_ZONE.into(),
+            },
+            consolidations_tx,
+        )
+        .await?;
+        // Occupy the id of the cluster in the snapshot, so that the imported
+        // cluster is assigned a different id.
+        let mut tx = storage.transaction().await?;
+        tx.insert_user_compute_instance("d", &Vec::new())?;
+        tx.commit().await?;
+        let ids = storage.import_snapshot(snapshot.clone()).await?;
+        assert_eq!(
+            ids.into_iter().collect_vec(),
+            vec![
+                (GlobalId::User(5), GlobalId::User(1)),
+                (GlobalId::User(7), GlobalId::User(2)),
+                (GlobalId::User(9), GlobalId::User(3)),
+            ]
+        );
+
+        let imported = storage.export_snapshot().await?;
+        let names = |snapshot: &storage::CatalogSnapshot| {
+            (
+                snapshot
+                    .databases
+                    .iter()
+                    .map(|d| d.name.clone())
+                    .collect_vec(),
+                snapshot
+                    .schemas
+                    .iter()
+                    .map(|s| s.name.clone())
+                    .collect_vec(),
+                snapshot.roles.clone(),
+                snapshot.items.iter().map(|i| i.name.clone()).collect_vec(),
+            )
+        };
+        assert_eq!(names(&imported), names(&snapshot));
+
+        // References to items and clusters refer to their new ids.
+        let cluster = imported.clusters.iter().find(|c| c.name == "c").unwrap();
+        assert_ne!(
+            cluster.id,
+            snapshot.clusters.iter().find(|c| c.name == "c").unwrap().id
+        );
+        let cluster_id = cluster.id;
+        let normalize = |create_sql: &str| {
+            mz_sql::parse::parse(create_sql)
+                .unwrap()
+                .into_element()
+                .to_ast_string_stable()
+        };
+        let create_sqls = imported
+            .items
+            .iter()
+            .map(|i| match &i.definition {
+                SerializedCatalogItem::V1 { create_sql } => create_sql.clone(),
+            })
+            .collect_vec();
+        assert_eq!(
+            create_sqls,
+            vec![
+                normalize("CREATE TABLE test.s.t (a int4)"),
+                normalize("CREATE VIEW test.s.v AS SELECT a FROM [u1 AS test.s.t]"),
+                normalize(&format!(
+                    "CREATE INDEX i IN CLUSTER [{cluster_id}] ON [u1 AS test.s.t] (a)"
+                )),
+            ]
+        );
+
+        // Importing into a catalog that already contains user items fails.
+        assert!(storage.import_snapshot(snapshot).await.is_err());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_effective_search_path() -> Result<(), anyhow::Error> {
         let catalog = Catalog::open_debug_sqlite(NOW_ZERO.clone()).await?;
@@ -1094,6 +1094,18 @@ pub const MZ_RAW_PEEK_DURATIONS: BuiltinLog = BuiltinLog {
     variant: LogVariant::Compute(ComputeLog::PeekDuration),
 };
 
+pub const MZ_LIR_MAPPING: BuiltinLog = BuiltinLog {
+    name: "mz_lir_mapping",
+    schema: MZ_INTERNAL_SCHEMA,
+    variant: LogVariant::Compute(ComputeLog::LirMapping),
+};
+
+pub const MZ_ARRANGEMENT_SPILLED_BYTES_INTERNAL: BuiltinLog = BuiltinLog {
+    name: "mz_arrangement_spilled_bytes_internal",
+    schema: MZ_INTERNAL_SCHEMA,
+    variant: LogVariant::Compute(ComputeLog::ArrangementSpilledBytes),
+};
+
 pub const MZ_MESSAGE_COUNTS_RECEIVED_INTERNAL: BuiltinLog = BuiltinLog {
     name: "mz_message_counts_received_internal",
     schema: MZ_INTERNAL_SCHEMA,
@@ -1363,6 +1375,18 @@ pub static MZ_SECRETS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         .with_column("schema_id", ScalarType::UInt64.nullable(false))
         .with_column("name", ScalarType::String.nullable(false)),
 });
+pub static MZ_SUBSCRIPTIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_subscriptions",
+    schema: MZ_CATALOG_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::String.nullable(false))
+        .with_column("oid", ScalarType::Oid.nullable(false))
+        .with_column("schema_id", ScalarType::UInt64.nullable(false))
+        .with_column("name", ScalarType::String.nullable(false))
+        .with_column("for_id", ScalarType::String.nullable(false))
+        .with_column("timestamp", ScalarType::MzTimestamp.nullable(false))
+        .with_column("snapshot", ScalarType::Bool.nullable(false)),
+});
 pub static MZ_CLUSTER_REPLICAS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_cluster_replicas",
     schema: MZ_CATALOG_SCHEMA,
@@ -1392,6 +1416,29 @@ pub static MZ_CLUSTER_REPLICA_HEARTBEATS: Lazy<BuiltinTable> = Lazy::new(|| Buil
         .with_column("last_heartbeat", ScalarType::TimestampTz.nullable(false)),
 });
 
+pub static MZ_COMPUTE_HYDRATION_STATUS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_compute_hydration_status",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("replica_id", ScalarType::UInt64.nullable(false))
+        .with_column("hydrated", ScalarType::Bool.nullable(false)),
+});
+
+pub static MZ_SESSIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_sessions",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::UInt32.nullable(false))
+        .with_column("user", ScalarType::String.nullable(false))
+        .with_column("database_name", ScalarType::String.nullable(false))
+        .with_column("application_name", ScalarType::String.nullable(false))
+        .with_column("connected_at", ScalarType::TimestampTz.nullable(false))
+        .with_column("state", ScalarType::String.nullable(false))
+        .with_column("query", ScalarType::String.nullable(true))
+        .with_column("query_started_at", ScalarType::TimestampTz.nullable(true)),
+});
+
 pub static MZ_AUDIT_EVENTS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_audit_events",
     schema: MZ_CATALOG_SCHEMA,
@@ -1456,6 +1503,28 @@ pub static MZ_STORAGE_SHARDS: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
         .with_column("shard_id", ScalarType::String.nullable(false)),
 });
 
+pub static MZ_STATEMENT_LOG: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_statement_log",
+    schema: MZ_INTERNAL_SCHEMA,
+    data_source: Some(IntrospectionType::StatementLog),
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::Uuid.nullable(false))
+        .with_column("session_id", ScalarType::UInt32.nullable(false))
+        .with_column("user", ScalarType::String.nullable(false))
+        .with_column("application_name", ScalarType::String.nullable(false))
+        .with_column("cluster_name", ScalarType::String.nullable(false))
+        .with_column("sql", ScalarType::String.nullable(false))
+        .with_column("began_at", ScalarType::TimestampTz.nullable(false))
+        .with_column(
+            "execution_timestamp",
+            ScalarType::MzTimestamp.nullable(true),
+        )
+        .with_column("planning_duration", ScalarType::Interval.nullable(false))
+        .with_column("execution_duration", ScalarType::Interval.nullable(false))
+        .with_column("rows_returned", ScalarType::Int64.nullable(true))
+        .with_column("error", ScalarType::String.nullable(true)),
+});
+
 pub static MZ_STORAGE_USAGE: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "mz_storage_usage",
     schema: MZ_CATALOG_SCHEMA,
@@ -1498,7 +1567,9 @@ UNION ALL
 UNION ALL
     SELECT id, oid, schema_id, name, 'function' FROM mz_catalog.mz_functions
 UNION ALL
-    SELECT id, NULL::pg_catalog.oid, schema_id, name, 'secret' FROM mz_catalog.mz_secrets",
+    SELECT id, NULL::pg_catalog.oid, schema_id, name, 'secret' FROM mz_catalog.mz_secrets
+UNION ALL
+    SELECT id, oid, schema_id, name, 'subscription' FROM mz_catalog.mz_subscriptions",
 };
 
 pub const MZ_DATAFLOWS: BuiltinView = BuiltinView {
@@ -2051,13 +2122,26 @@ records_cte AS (
         mz_internal.mz_arrangement_records_internal
     GROUP BY
         operator_id, worker_id
+),
+spilled_cte AS (
+    SELECT
+        operator_id,
+        worker_id,
+        pg_catalog.count(*) AS spilled_bytes
+    FROM
+        mz_internal.mz_arrangement_spilled_bytes_internal
+    GROUP BY
+        operator_id, worker_id
 )
 SELECT
     batches_cte.operator_id,
     batches_cte.worker_id,
     records_cte.records,
-    batches_cte.batches
-FROM batches_cte JOIN records_cte USING (operator_id, worker_id)",
+    batches_cte.batches,
+    spilled_cte.spilled_bytes
+FROM batches_cte
+JOIN records_cte USING (operator_id, worker_id)
+LEFT JOIN spilled_cte USING (operator_id, worker_id)",
 };
 
 pub const MZ_ARRANGEMENT_SHARING: BuiltinView = BuiltinView {
@@ -2264,6 +2348,31 @@ AS SELECT
 WHERE false",
 };
 
+pub const PG_STAT_ACTIVITY: BuiltinView = BuiltinView {
+    name: "pg_stat_activity",
+    schema: PG_CATALOG_SCHEMA,
+    sql: "CREATE VIEW pg_catalog.pg_stat_activity
+AS SELECT
+    d.oid AS datid,
+    s.database_name AS datname,
+    s.id::pg_catalog.int4 AS pid,
+    NULL::pg_catalog.int4 AS leader_pid,
+    r.oid AS usesysid,
+    s.\"user\" AS usename,
+    s.application_name,
+    s.connected_at AS backend_start,
+    NULL::pg_catalog.timestamptz AS xact_start,
+    s.query_started_at AS query_start,
+    NULL::pg_catalog.text AS wait_event_type,
+    NULL::pg_catalog.text AS wait_event,
+    s.state,
+    s.query,
+    'client backend'::pg_catalog.text AS backend_type
+FROM mz_internal.mz_sessions s
+LEFT JOIN mz_catalog.mz_databases d ON d.name = s.database_name
+LEFT JOIN mz_catalog.mz_roles r ON r.name = s.\"user\"",
+};
+
 pub const PG_AUTHID: BuiltinView = BuiltinView {
     name: "pg_authid",
     schema: PG_CATALOG_SCHEMA,
@@ -2351,7 +2460,10 @@ pub const MZ_SHOW_CLUSTER_REPLICAS: BuiltinView = BuiltinView {
             mz_catalog.mz_clusters.name AS cluster,
             mz_catalog.mz_cluster_replicas.name AS replica,
             mz_catalog.mz_cluster_replicas.size AS size,
-            coalesce(statuses.ready, false) AS ready
+            coalesce(statuses.ready, false) AS ready,
+            -- Replicas that maintain no indexes or materialized views are
+            -- trivially hydrated.
+            coalesce(hydration.hydrated, true) AS hydrated
         FROM
             mz_catalog.mz_cluster_replicas
                 JOIN
@@ -2373,6 +2485,19 @@ pub const MZ_SHOW_CLUSTER_REPLICAS: BuiltinView = BuiltinView {
                         )
                         AS statuses
                     ON mz_catalog.mz_cluster_replicas.id = statuses.replica_id
+                LEFT JOIN
+                    (
+                            SELECT
+                                replica_id,
+                                mz_internal.mz_all(
+                                        mz_internal.mz_compute_hydration_status.hydrated
+                                    )
+                                    AS hydrated
+                            FROM mz_internal.mz_compute_hydration_status
+                            GROUP BY replica_id
+                        )
+                        AS hydration
+                    ON mz_catalog.mz_cluster_replicas.id = hydration.replica_id
         ORDER BY 1, 2"#,
 };
 
@@ -2485,7 +2610,7 @@ pub const MZ_SHOW_CLUSTER_REPLICAS_IND: BuiltinIndex = BuiltinIndex {
     schema: MZ_INTERNAL_SCHEMA,
     sql: "CREATE INDEX mz_show_cluster_replicas_ind
 IN CLUSTER mz_introspection
-ON mz_internal.mz_show_cluster_replicas (cluster, replica, size, ready)",
+ON mz_internal.mz_show_cluster_replicas (cluster, replica, size, ready, hydrated)",
 };
 
 pub const MZ_SHOW_SECRETS_IND: BuiltinIndex = BuiltinIndex {
@@ -2630,6 +2755,8 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Log(&MZ_MESSAGE_COUNTS_SENT_INTERNAL),
         Builtin::Log(&MZ_ACTIVE_PEEKS),
         Builtin::Log(&MZ_RAW_PEEK_DURATIONS),
+        Builtin::Log(&MZ_LIR_MAPPING),
+        Builtin::Log(&MZ_ARRANGEMENT_SPILLED_BYTES_INTERNAL),
         Builtin::Log(&MZ_SCHEDULING_ELAPSED_INTERNAL),
         Builtin::Log(&MZ_RAW_COMPUTE_OPERATOR_DURATIONS_INTERNAL),
         Builtin::Log(&MZ_SCHEDULING_PARKS_INTERNAL),
@@ -2660,11 +2787,14 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Table(&MZ_FUNCTIONS),
         Builtin::Table(&MZ_CLUSTERS),
         Builtin::Table(&MZ_SECRETS),
+        Builtin::Table(&MZ_SUBSCRIPTIONS),
         Builtin::Table(&MZ_CONNECTIONS),
         Builtin::Table(&MZ_SSH_TUNNEL_CONNECTIONS),
         Builtin::Table(&MZ_CLUSTER_REPLICAS),
         Builtin::Table(&MZ_CLUSTER_REPLICA_STATUSES),
         Builtin::Table(&MZ_CLUSTER_REPLICA_HEARTBEATS),
+        Builtin::Table(&MZ_COMPUTE_HYDRATION_STATUS),
+        Builtin::Table(&MZ_SESSIONS),
         Builtin::Table(&MZ_AUDIT_EVENTS),
         Builtin::Table(&MZ_STORAGE_USAGE_BY_SHARD),
         Builtin::Table(&MZ_EGRESS_IPS),
@@ -2712,11 +2842,13 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::View(&PG_COLLATION),
         Builtin::View(&PG_POLICY),
         Builtin::View(&PG_INHERITS),
+        Builtin::View(&PG_STAT_ACTIVITY),
         Builtin::View(&INFORMATION_SCHEMA_COLUMNS),
         Builtin::View(&INFORMATION_SCHEMA_TABLES),
         Builtin::Source(&MZ_SINK_STATUS_HISTORY),
         Builtin::Source(&MZ_SOURCE_STATUS_HISTORY),
         Builtin::Source(&MZ_STORAGE_SHARDS),
+        Builtin::Source(&MZ_STATEMENT_LOG),
         Builtin::View(&MZ_STORAGE_USAGE),
         Builtin::Index(&MZ_SHOW_DATABASES_IND),
         Builtin::Index(&MZ_SHOW_SCHEMAS_IND),
@@ -32,15 +32,17 @@ use mz_storage_client::types::sinks::{KafkaSinkConnection, StorageSinkConnection
 
 use crate::catalog::builtin::{
     MZ_ARRAY_TYPES, MZ_AUDIT_EVENTS, MZ_BASE_TYPES, MZ_CLUSTERS, MZ_CLUSTER_REPLICAS,
-    MZ_CLUSTER_REPLICA_HEARTBEATS, MZ_CLUSTER_REPLICA_STATUSES, MZ_COLUMNS, MZ_CONNECTIONS,
-    MZ_DATABASES, MZ_EGRESS_IPS, MZ_FUNCTIONS, MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_KAFKA_CONNECTIONS,
-    MZ_KAFKA_SINKS, MZ_LIST_TYPES, MZ_MAP_TYPES, MZ_MATERIALIZED_VIEWS, MZ_PSEUDO_TYPES, MZ_ROLES,
-    MZ_SCHEMAS, MZ_SECRETS, MZ_SINKS, MZ_SOURCES, MZ_SSH_TUNNEL_CONNECTIONS,
-    MZ_STORAGE_USAGE_BY_SHARD, MZ_TABLES, MZ_TYPES, MZ_VIEWS,
+    MZ_CLUSTER_REPLICA_HEARTBEATS, MZ_CLUSTER_REPLICA_STATUSES, MZ_COLUMNS,
+    MZ_COMPUTE_HYDRATION_STATUS, MZ_CONNECTIONS, MZ_DATABASES, MZ_EGRESS_IPS, MZ_FUNCTIONS,
+    MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_KAFKA_CONNECTIONS, MZ_KAFKA_SINKS, MZ_LIST_TYPES,
+    MZ_MAP_TYPES, MZ_MATERIALIZED_VIEWS, MZ_PSEUDO_TYPES, MZ_ROLES, MZ_SCHEMAS, MZ_SECRETS,
+    MZ_SINKS, MZ_SOURCES, MZ_SSH_TUNNEL_CONNECTIONS, MZ_STORAGE_USAGE_BY_SHARD, MZ_SUBSCRIPTIONS,
+    MZ_TABLES, MZ_TYPES, MZ_VIEWS,
 };
 use crate::catalog::{
     CatalogItem, CatalogState, Connection, Database, Error, ErrorKind, Func, Index,
-    MaterializedView, Role, Sink, StorageSinkConnectionState, Type, View, SYSTEM_CONN_ID,
+    MaterializedView, Role, Sink, StorageSinkConnectionState, Subscription, Type, View,
+    SYSTEM_CONN_ID,
 };
 use crate::coord::ReplicaMetadata;
 
@@ -237,11 +239,14 @@ impl CatalogState {
             }
             CatalogItem::Sink(sink) => self.pack_sink_update(id, oid, schema_id, name, sink, diff),
             CatalogItem::Type(ty) => self.pack_type_update(id, oid, schema_id, name, ty, diff),
-            CatalogItem::Func(func) => self.pack_func_update(id, schema_id, name, func, diff),
+            CatalogItem::Func(func) => self.pack_func_update(id, oid, schema_id, name, func, diff),
             CatalogItem::Secret(_) => self.pack_secret_update(id, schema_id, name, diff),
             CatalogItem::Connection(connection) => {
                 self.pack_connection_update(id, oid, schema_id, name, connection, diff)
             }
+            CatalogItem::Subscription(subscription) => {
+                self.pack_subscription_update(id, oid, schema_id, name, subscription, diff)
+            }
         };
 
         if let Ok(desc) = entry.desc(&self.resolve_full_name(entry.name(), entry.conn_id())) {
@@ -674,6 +679,7 @@ impl CatalogState {
     fn pack_func_update(
         &self,
         id: GlobalId,
+        oid: u32,
         schema_id: &SchemaSpecifier,
         name: &str,
         func: &Func,
@@ -681,6 +687,13 @@ impl CatalogState {
     ) -> Vec<BuiltinTableUpdate> {
         let mut updates = vec![];
         for func_impl_details in func.inner.func_impls() {
+            // User-defined functions have a single implementation, which
+            // takes on the OID assigned to the catalog entry.
+            let oid = match func.create_sql {
+                Some(_) => oid,
+                None => func_impl_details.oid,
+            };
+
             let arg_type_ids = func_impl_details
                 .arg_typs
                 .iter()
@@ -703,7 +716,7 @@ impl CatalogState {
                 id: self.resolve_builtin_table(&MZ_FUNCTIONS),
                 row: Row::pack_slice(&[
                     Datum::String(&id.to_string()),
-                    Datum::UInt32(func_impl_details.oid),
+                    Datum::UInt32(oid),
                     Datum::UInt64(schema_id.into()),
                     Datum::String(name),
                     arg_type_ids,
@@ -745,6 +758,30 @@ impl CatalogState {
         }]
     }
 
+    fn pack_subscription_update(
+        &self,
+        id: GlobalId,
+        oid: u32,
+        schema_id: &SchemaSpecifier,
+        name: &str,
+        subscription: &Subscription,
+        diff: Diff,
+    ) -> Vec<BuiltinTableUpdate> {
+        vec![BuiltinTableUpdate {
+            id: self.resolve_builtin_table(&MZ_SUBSCRIPTIONS),
+            row: Row::pack_slice(&[
+                Datum::String(&id.to_string()),
+                Datum::UInt32(oid),
+                Datum::UInt64(schema_id.into()),
+                Datum::String(name),
+                Datum::String(&subscription.from.to_string()),
+                Datum::MzTimestamp(subscription.timestamp),
+                Datum::from(subscription.snapshot),
+            ]),
+            diff,
+        }]
+    }
+
     pub fn pack_audit_log_update(
         &self,
         event: &VersionedEvent,
@@ -811,6 +848,26 @@ impl CatalogState {
         }
     }
 
+    pub fn pack_compute_hydration_status_update(
+        &self,
+        id: GlobalId,
+        replica_id: ReplicaId,
+        hydrated: bool,
+        diff: Diff,
+    ) -> BuiltinTableUpdate {
+        let table = self.resolve_builtin_table(&MZ_COMPUTE_HYDRATION_STATUS);
+        let row = Row::pack_slice(&[
+            Datum::String(&id.to_string()),
+            Datum::UInt64(replica_id),
+            Datum::from(hydrated),
+        ]);
+        BuiltinTableUpdate {
+            id: table,
+            row,
+            diff,
+        }
+    }
+
     pub fn pack_storage_usage_update(
         &self,
         VersionedStorageUsage::V1(event): &VersionedStorageUsage,
@@ -90,6 +90,7 @@ impl Default for ClusterReplicaSizeMap {
                         cpu_limit: None,
                         scale: NonZeroUsize::new(1).unwrap(),
                         workers: NonZeroUsize::new(workers).unwrap(),
+                        disk: false,
                     },
                 )
             })
@@ -104,6 +105,7 @@ impl Default for ClusterReplicaSizeMap {
                     cpu_limit: None,
                     scale: NonZeroUsize::new(scale).unwrap(),
                     workers: NonZeroUsize::new(1).unwrap(),
+                    disk: false,
                 },
             );
 
@@ -114,6 +116,7 @@ impl Default for ClusterReplicaSizeMap {
                     cpu_limit: None,
                     scale: NonZeroUsize::new(scale).unwrap(),
                     workers: NonZeroUsize::new(scale).unwrap(),
+                    disk: false,
                 },
             );
         }
@@ -125,6 +128,7 @@ impl Default for ClusterReplicaSizeMap {
                 cpu_limit: None,
                 scale: NonZeroUsize::new(2).unwrap(),
                 workers: NonZeroUsize::new(4).unwrap(),
+                disk: false,
             },
         );
         Self(inner)
@@ -70,6 +70,13 @@ pub enum ErrorKind {
     AmbiguousRename(#[from] AmbiguousRename),
     #[error("cannot rename type: {0}")]
     TypeRename(String),
+    #[error("cannot alter {item_type} '{item}' because '{dependent}' depends on it: {message}")]
+    AlterDependency {
+        item_type: &'static str,
+        item: String,
+        dependent: String,
+        message: String,
+    },
     #[error("cannot migrate from catalog version {last_seen_version} to version {this_version} (earlier versions might still work): {cause}")]
     FailedMigration {
         last_seen_version: String,
@@ -80,6 +87,10 @@ pub enum ErrorKind {
     FailedBuiltinSchemaMigration(String),
     #[error("failpoint {0} reached)")]
     FailpointReached(String),
+    #[error("unsupported catalog snapshot version {0}")]
+    UnsupportedSnapshotVersion(u64),
+    #[error("cannot import a catalog snapshot into a catalog that contains user items")]
+    ImportIntoNonEmptyCatalog,
     #[error("{0}")]
     Unstructured(String),
     #[error(transparent)]
@@ -24,6 +24,7 @@ use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
 use mz_ore::now::EpochMillis;
 use mz_repr::GlobalId;
+use mz_sql::ast::display::AstDisplay;
 use mz_sql::catalog::{CatalogError as SqlCatalogError, CatalogItemType};
 use mz_sql::names::{
     DatabaseId, ObjectQualifiers, QualifiedObjectName, ResolvedDatabaseSpecifier, RoleId, SchemaId,
@@ -923,6 +924,248 @@ impl<S: Append> Connection<S> {
     pub async fn consolidate(&mut self, collections: &[mz_stash::Id]) -> Result<(), Error> {
         Ok(self.stash.consolidate_batch(collections).await?)
     }
+
+    /// Exports the user-defined objects of the catalog into a [`CatalogSnapshot`].
+    ///
+    /// The snapshot is only logically consistent if all reads observe the same
+    /// state of the stash, e.g. because the stash was opened in savepoint mode.
+    pub async fn export_snapshot(&mut self) -> Result<CatalogSnapshot, Error> {
+        let catalog_content_version = self.get_catalog_content_version().await?;
+        let databases = COLLECTION_DATABASE
+            .peek_one(&mut self.stash)
+            .await?
+            .into_iter()
+            .map(|(k, v)| SnapshotDatabase {
+                id: k.id,
+                name: v.name,
+            })
+            .collect();
+        let schemas = COLLECTION_SCHEMA
+            .peek_one(&mut self.stash)
+            .await?
+            .into_iter()
+            .filter_map(|(k, v)| {
+                // Ambient schemas are created by the bootstrap migration.
+                v.database_id.map(|database_id| SnapshotSchema {
+                    id: k.id,
+                    database_id,
+                    name: v.name,
+                })
+            })
+            .collect();
+        let roles = COLLECTION_ROLE
+            .peek_one(&mut self.stash)
+            .await?
+            .into_iter()
+            .filter(|(k, _)| matches!(k.id, RoleId::User(_)))
+            .map(|(_, v)| v.name)
+            .collect();
+        let mut clusters: BTreeMap<_, _> = COLLECTION_COMPUTE_INSTANCES
+            .peek_one(&mut self.stash)
+            .await?
+            .into_iter()
+            .filter(|(k, _)| matches!(k.id, ComputeInstanceId::User(_)))
+            .map(|(k, v)| {
+                let cluster = SnapshotCluster {
+                    id: k.id,
+                    name: v.name,
+                    replicas: Vec::new(),
+                };
+                (k.id, cluster)
+            })
+            .collect();
+        for (_, v) in COLLECTION_COMPUTE_REPLICAS
+            .peek_one(&mut self.stash)
+            .await?
+        {
+            if let Some(cluster) = clusters.get_mut(&v.compute_instance_id) {
+                cluster.replicas.push(SnapshotReplica {
+                    name: v.name,
+                    config: v.config,
+                });
+            }
+        }
+        let items = COLLECTION_ITEM
+            .peek_one(&mut self.stash)
+            .await?
+            .into_iter()
+            .filter(|(k, _)| k.gid.is_user())
+            .map(|(k, v)| SnapshotItem {
+                id: k.gid,
+                schema_id: v.schema_id,
+                name: v.name,
+                definition: v.definition,
+            })
+            .collect();
+        Ok(CatalogSnapshot {
+            version: CATALOG_SNAPSHOT_VERSION,
+            catalog_content_version,
+            databases,
+            schemas,
+            roles,
+            clusters: clusters.into_values().collect(),
+            items,
+        })
+    }
+
+    /// Imports a [`CatalogSnapshot`] into a catalog that does not contain any
+    /// user items. Returns the mapping from the ids of the items in the snapshot
+    /// to the newly allocated ids.
+    ///
+    /// Databases, schemas, roles, clusters and replicas that already exist by
+    /// name, like the bootstrapped `materialize` database, are reused. The ids of
+    /// the introspection collections of imported clusters and replicas are not
+    /// imported, but allocated the next time the catalog is opened. Items are
+    /// migrated the next time the catalog is opened, too.
+    ///
+    /// Imported items and clusters are assigned new ids, and the references to
+    /// them in the `CREATE` statements of imported items are rewritten
+    /// accordingly.
+    pub async fn import_snapshot(
+        &mut self,
+        snapshot: CatalogSnapshot,
+    ) -> Result<BTreeMap<GlobalId, GlobalId>, Error> {
+        if snapshot.version != CATALOG_SNAPSHOT_VERSION {
+            return Err(Error::new(ErrorKind::UnsupportedSnapshotVersion(
+                snapshot.version,
+            )));
+        }
+
+        let mut tx = self.transaction().await?;
+        if tx.items.items().keys().any(|k| k.gid.is_user()) {
+            return Err(Error::new(ErrorKind::ImportIntoNonEmptyCatalog));
+        }
+
+        let existing_databases: BTreeMap<_, _> = tx
+            .databases
+            .items()
+            .into_iter()
+            .map(|(k, v)| (v.name, k.id))
+            .collect();
+        let mut database_ids = BTreeMap::new();
+        for SnapshotDatabase { id, name } in snapshot.databases {
+            let new_id = match existing_databases.get(&name) {
+                Some(id) => *id,
+                None => tx.insert_database(&name)?.0,
+            };
+            database_ids.insert(id, new_id);
+        }
+
+        let existing_schemas: BTreeMap<_, _> = tx
+            .schemas
+            .items()
+            .into_iter()
+            .map(|(k, v)| ((v.database_id, v.name), k.id))
+            .collect();
+        let mut schema_ids = BTreeMap::new();
+        for SnapshotSchema {
+            id,
+            database_id,
+            name,
+        } in snapshot.schemas
+        {
+            let database_id = *database_ids.get(&database_id).ok_or_else(|| {
+                Error::new(ErrorKind::Corruption {
+                    detail: format!("schema {name} refers to unknown database {database_id}"),
+                })
+            })?;
+            let new_id = match existing_schemas.get(&(Some(database_id), name.clone())) {
+                Some(id) => *id,
+                None => tx.insert_schema(DatabaseId::new(database_id), &name)?.0,
+            };
+            schema_ids.insert(id, new_id);
+        }
+
+        let existing_roles: BTreeSet<_> = tx.roles.items().into_values().map(|v| v.name).collect();
+        for name in snapshot.roles {
+            if !existing_roles.contains(&name) {
+                tx.insert_user_role(&name)?;
+            }
+        }
+
+        let existing_clusters: BTreeMap<_, _> = tx
+            .compute_instances
+            .items()
+            .into_iter()
+            .map(|(k, v)| (v.name, k.id))
+            .collect();
+        let existing_replicas: BTreeSet<_> = tx
+            .compute_replicas
+            .items()
+            .into_values()
+            .map(|v| (v.compute_instance_id, v.name))
+            .collect();
+        let mut cluster_ids = HashMap::new();
+        for SnapshotCluster { id, name, replicas } in snapshot.clusters {
+            let instance_id = existing_clusters.get(&name).copied();
+            let new_id = match instance_id {
+                Some(id) => id,
+                // The introspection source indexes are allocated when opening
+                // the catalog.
+                None => tx.insert_user_compute_instance(&name, &Vec::new())?,
+            };
+            cluster_ids.insert(id, new_id);
+            for SnapshotReplica {
+                name: replica_name,
+                mut config,
+            } in replicas
+            {
+                if let Some(instance_id) = instance_id {
+                    if existing_replicas.contains(&(instance_id, replica_name.clone())) {
+                        continue;
+                    }
+                }
+                // The persisted introspection collections are allocated when
+                // opening the catalog.
+                config.logging.sources = None;
+                config.logging.views = None;
+                tx.insert_compute_replica(&name, &replica_name, &config)?;
+            }
+        }
+
+        // Items are inserted in the order of their original ids, which is an
+        // order in which every item comes after its dependencies. References
+        // to the ids of items and clusters in the snapshot are rewritten to
+        // their new ids.
+        let mut items = snapshot.items;
+        items.sort_by_key(|item| item.id);
+        let mut item_ids = HashMap::new();
+        for SnapshotItem {
+            id,
+            schema_id,
+            name,
+            definition,
+        } in items
+        {
+            let schema_id = *schema_ids.get(&schema_id).ok_or_else(|| {
+                Error::new(ErrorKind::Corruption {
+                    detail: format!("item {name} refers to unknown schema {schema_id}"),
+                })
+            })?;
+            let new_id = GlobalId::User(tx.get_and_increment_id("user".to_string())?);
+            item_ids.insert(id, new_id);
+            let SerializedCatalogItem::V1 { create_sql } = definition;
+            let mut create_stmt = mz_sql::parse::parse(&create_sql)
+                .map_err(|e| {
+                    Error::new(ErrorKind::Corruption {
+                        detail: format!("item {name} has invalid create sql: {e}"),
+                    })
+                })?
+                .into_element();
+            mz_sql::ast::transform::create_stmt_replace_ids(&mut create_stmt, &item_ids);
+            mz_sql::ast::transform::create_stmt_replace_cluster_ids(&mut create_stmt, &cluster_ids);
+            let definition = SerializedCatalogItem::V1 {
+                create_sql: create_stmt.to_ast_string_stable(),
+            };
+            tx.insert_item(new_id, SchemaId::new(schema_id), &name, definition)?;
+        }
+        tx.commit().await?;
+
+        if let Some(version) = snapshot.catalog_content_version {
+            self.set_catalog_content_version(&version).await?;
+        }
+        Ok(item_ids.into_iter().collect())
+    }
 }
 
 #[tracing::instrument(level = "trace", skip_all)]
@@ -1380,6 +1623,63 @@ impl<'a, S: Append> Transaction<'a, S> {
         }
     }
 
+    /// Swaps the names of two schemas of the same database.
+    pub fn swap_schemas(
+        &mut self,
+        database_id: &DatabaseId,
+        schema_a: &SchemaId,
+        schema_b: &SchemaId,
+    ) -> Result<(), Error> {
+        let schemas = self.schemas.items();
+        let name = |schema_id: &SchemaId| match schemas.get(&SchemaKey { id: schema_id.0 }) {
+            Some(schema) => Ok(schema.name.clone()),
+            None => Err(Error::from(SqlCatalogError::UnknownSchema(format!(
+                "{}.{}",
+                database_id.0, schema_id.0
+            )))),
+        };
+        let (name_a, name_b) = (name(schema_a)?, name(schema_b)?);
+        let n = self.schemas.update(|k, v| {
+            let name = if k.id == schema_a.0 {
+                &name_b
+            } else if k.id == schema_b.0 {
+                &name_a
+            } else {
+                return None;
+            };
+            Some(SchemaValue {
+                database_id: v.database_id,
+                name: name.clone(),
+            })
+        })?;
+        assert_eq!(n, 2);
+        Ok(())
+    }
+
+    /// Swaps the names of two compute instances.
+    pub fn swap_compute_instances(&mut self, name_a: &str, name_b: &str) -> Result<(), Error> {
+        let instances = self.compute_instances.items();
+        for name in [name_a, name_b] {
+            if !instances.values().any(|v| v.name == name) {
+                return Err(SqlCatalogError::UnknownComputeInstance(name.to_string()).into());
+            }
+        }
+        let n = self.compute_instances.update(|_k, v| {
+            let name = if v.name == name_a {
+                name_b
+            } else if v.name == name_b {
+                name_a
+            } else {
+                return None;
+            };
+            Some(ComputeInstanceValue {
+                name: name.to_string(),
+            })
+        })?;
+        assert_eq!(n, 2);
+        Ok(())
+    }
+
     /// Updates all items with ids matching the keys of `items` in the transaction, to the
     /// corresponding value in `items`.
     ///
@@ -1702,6 +2002,64 @@ pub async fn initialize_stash<S: Append>(stash: &mut S) -> Result<(), Error> {
     stash.append(&batches).await.map_err(|e| e.into())
 }
 
+/// The version of the [`CatalogSnapshot`] format. Must be incremented whenever
+/// the format changes.
+pub const CATALOG_SNAPSHOT_VERSION: u64 = 2;
+
+/// A logical snapshot of the user-defined objects in a catalog, used to back up
+/// the catalog or to clone it into another environment.
+///
+/// Secrets are only included by reference: their contents are stored in the
+/// secrets controller under the id of the secret, and must be copied
+/// separately.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CatalogSnapshot {
+    /// The version of the snapshot format.
+    pub version: u64,
+    /// The version of the binary that last migrated the exported catalog.
+    pub catalog_content_version: Option<String>,
+    pub databases: Vec<SnapshotDatabase>,
+    pub schemas: Vec<SnapshotSchema>,
+    /// The names of the user roles.
+    pub roles: Vec<String>,
+    pub clusters: Vec<SnapshotCluster>,
+    pub items: Vec<SnapshotItem>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SnapshotDatabase {
+    pub id: u64,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SnapshotSchema {
+    pub id: u64,
+    pub database_id: u64,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SnapshotCluster {
+    pub id: ComputeInstanceId,
+    pub name: String,
+    pub replicas: Vec<SnapshotReplica>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SnapshotReplica {
+    pub name: String,
+    pub config: SerializedComputeReplicaConfig,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SnapshotItem {
+    pub id: GlobalId,
+    pub schema_id: u64,
+    pub name: String,
+    pub definition: SerializedCatalogItem,
+}
+
 #[derive(Clone, Deserialize, Serialize, PartialOrd, PartialEq, Eq, Ord, Hash)]
 pub struct SettingKey {
     name: String,
@@ -200,20 +200,43 @@ impl SessionClient {
         async move {
             loop {
                 let _ = cancel_rx.changed().await;
-                if let Canceled::Canceled = *cancel_rx.borrow() {
+                if let Canceled::Canceled | Canceled::Terminated = *cancel_rx.borrow() {
                     return;
                 }
             }
         }
     }
 
+    /// Returns a future that resolves once the connection has been terminated
+    /// by `pg_terminate_backend`.
+    pub fn terminated(&self) -> impl Future<Output = ()> + Send {
+        let mut cancel_rx = self.cancel_rx.clone();
+        async move {
+            loop {
+                if let Canceled::Terminated = *cancel_rx.borrow() {
+                    return;
+                }
+                if cancel_rx.changed().await.is_err() {
+                    // The coordinator is gone, so the connection will never be
+                    // terminated.
+                    futures::future::pending::<()>().await;
+                }
+            }
+        }
+    }
+
     pub fn reset_canceled(&mut self) {
         // Clear any cancellation message.
         // TODO(mjibson): This makes the use of .changed annoying since it will
         // generally always have a NotCanceled message first that needs to be ignored,
         // and thus run in a loop. Figure out a way to have the future only resolve on
         // a Canceled message.
-        let _ = self.cancel_tx.send(Canceled::NotCanceled);
+        // A termination request is never cleared.
+        self.cancel_tx.send_modify(|canceled| {
+            if let Canceled::Canceled = canceled {
+                *canceled = Canceled::NotCanceled;
+            }
+        });
     }
 
     // Verify and return the named prepared statement. We need to verify each use
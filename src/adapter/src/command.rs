@@ -30,6 +30,7 @@ use mz_sql::plan::ExecuteTimeout;
 
 use crate::client::ConnectionId;
 use crate::coord::peek::PeekResponseUnary;
+use crate::coord::statement_logging::StatementLog;
 use crate::error::AdapterError;
 use crate::session::{EndTransactionAction, RowBatchStream, Session};
 use crate::util::Transmittable;
@@ -193,6 +194,9 @@ pub enum ExecuteResponse {
         columns: Vec<usize>,
         params: CopyFormatParams<'static>,
     },
+    /// The specified number of rows were copied to or from remote object
+    /// storage.
+    Copied(usize),
     /// The requested connection was created.
     CreatedConnection,
     /// The requested database was created.
@@ -215,6 +219,8 @@ pub enum ExecuteResponse {
     CreatedSource,
     /// The requested sources were created.
     CreatedSources,
+    /// The requested subscription was created.
+    CreatedSubscription,
     /// The requested table was created.
     CreatedTable,
     /// The requested view was created.
@@ -225,6 +231,8 @@ pub enum ExecuteResponse {
     CreatedMaterializedView,
     /// The requested type was created.
     CreatedType,
+    /// The requested function was created.
+    CreatedFunction,
     /// The requested prepared statement was removed.
     Deallocate { all: bool },
     /// The requested cursor was declared.
@@ -261,8 +269,12 @@ pub enum ExecuteResponse {
     DroppedSink,
     /// The requested type was dropped.
     DroppedType,
+    /// The requested function was dropped.
+    DroppedFunction,
     /// The requested secret was dropped.
     DroppedSecret,
+    /// The requested subscription was dropped.
+    DroppedSubscription,
     /// The provided query was empty.
     EmptyQuery,
     /// Fetch results from a cursor.
@@ -276,6 +288,9 @@ pub enum ExecuteResponse {
     },
     /// The specified number of rows were inserted into the requested table.
     Inserted(usize),
+    /// The specified number of rows were inserted, updated, or deleted by a
+    /// `MERGE` into the requested table.
+    Merged(usize),
     /// The specified prepared statement was created.
     Prepare,
     /// A user-requested warning was raised.
@@ -317,6 +332,7 @@ impl ExecuteResponse {
             ClosedCursor => Some("CLOSE CURSOR".into()),
             CopyTo { .. } => None,
             CopyFrom { .. } => None,
+            Copied(n) => Some(format!("COPY {}", n)),
             CreatedConnection { .. } => Some("CREATE CONNECTION".into()),
             CreatedDatabase { .. } => Some("CREATE DATABASE".into()),
             CreatedSchema { .. } => Some("CREATE SCHEMA".into()),
@@ -328,11 +344,13 @@ impl ExecuteResponse {
             CreatedSink { .. } => Some("CREATE SINK".into()),
             CreatedSource { .. } => Some("CREATE SOURCE".into()),
             CreatedSources => Some("CREATE SOURCES".into()),
+            CreatedSubscription => Some("CREATE SUBSCRIPTION".into()),
             CreatedTable { .. } => Some("CREATE TABLE".into()),
             CreatedView { .. } => Some("CREATE VIEW".into()),
             CreatedViews { .. } => Some("CREATE VIEWS".into()),
             CreatedMaterializedView { .. } => Some("CREATE MATERIALIZED VIEW".into()),
             CreatedType => Some("CREATE TYPE".into()),
+            CreatedFunction => Some("CREATE FUNCTION".into()),
             Deallocate { all } => Some(format!("DEALLOCATE{}", if *all { " ALL" } else { "" })),
             DeclaredCursor => Some("DECLARE CURSOR".into()),
             Deleted(n) => Some(format!("DELETE {}", n)),
@@ -351,7 +369,9 @@ impl ExecuteResponse {
             DroppedIndex => Some("DROP INDEX".into()),
             DroppedSink => Some("DROP SINK".into()),
             DroppedType => Some("DROP TYPE".into()),
+            DroppedFunction => Some("DROP FUNCTION".into()),
             DroppedSecret => Some("DROP SECRET".into()),
+            DroppedSubscription => Some("DROP SUBSCRIPTION".into()),
             EmptyQuery => None,
             Fetch { .. } => None,
             Inserted(n) => {
@@ -364,6 +384,7 @@ impl ExecuteResponse {
                 // have OIDs.
                 Some(format!("INSERT 0 {}", n))
             }
+            Merged(n) => Some(format!("MERGE {}", n)),
             Prepare => Some("PREPARE".into()),
             Raised => Some("RAISE".into()),
             SendingRows { .. } => None,
@@ -385,9 +406,17 @@ impl ExecuteResponse {
 
         match plan {
             AbortTransaction => vec![TransactionRolledBack],
-            AlterItemRename | AlterNoop | AlterSecret | AlterSink | AlterSource | RotateKeys => {
-                vec![AlteredObject]
-            }
+            AlterItemRename
+            | AlterComputeInstanceSwap
+            | AlterNoop
+            | AlterSchemaSwap
+            | AlterSecret
+            | AlterSink
+            | AlterSource
+            | AlterSourceRefreshSchema
+            | AlterSubscription
+            | AlterTable
+            | RotateKeys => vec![AlteredObject],
             AlterIndexSetOptions | AlterIndexResetOptions => {
                 vec![AlteredObject, AlteredIndexLogicalCompaction]
             }
@@ -396,6 +425,8 @@ impl ExecuteResponse {
             }
             Close => vec![ClosedCursor],
             PlanKind::CopyFrom => vec![ExecuteResponseKind::CopyFrom],
+            CopyFromUrl => vec![Copied],
+            CopyToUrl => vec![Copied, ExecuteResponseKind::Canceled],
             CommitTransaction => vec![TransactionCommitted, TransactionRolledBack],
             CreateConnection => vec![CreatedConnection],
             CreateDatabase => vec![CreatedDatabase],
@@ -406,11 +437,13 @@ impl ExecuteResponse {
             CreateSource => vec![CreatedSource, CreatedSources],
             CreateSecret => vec![CreatedSecret],
             CreateSink => vec![CreatedSink],
+            CreateSubscription => vec![CreatedSubscription],
             CreateTable => vec![CreatedTable],
             CreateView => vec![CreatedView],
             CreateMaterializedView => vec![CreatedMaterializedView],
             CreateIndex => vec![CreatedIndex],
             CreateType => vec![CreatedType],
+            CreateFunction => vec![CreatedFunction],
             PlanKind::Deallocate => vec![ExecuteResponseKind::Deallocate],
             Declare => vec![DeclaredCursor],
             DiscardTemp => vec![DiscardedTemp],
@@ -429,13 +462,17 @@ impl ExecuteResponse {
                 DroppedIndex,
                 DroppedSink,
                 DroppedType,
+                DroppedFunction,
                 DroppedSecret,
+                DroppedSubscription,
             ],
             PlanKind::EmptyQuery => vec![ExecuteResponseKind::EmptyQuery],
-            Explain | Peek | SendRows | ShowAllVariables | ShowVariable => {
+            Explain | Peek | SendRows | ShowAllVariables | ShowVariable | SignalBackend => {
                 vec![CopyTo, SendingRows]
             }
-            Execute | ReadThenWrite | SendDiffs => vec![Deleted, Inserted, SendingRows, Updated],
+            Execute | ReadThenWrite | SendDiffs => {
+                vec![Deleted, Inserted, Merged, SendingRows, Updated]
+            }
             PlanKind::Fetch => vec![ExecuteResponseKind::Fetch],
             Insert => vec![Inserted, SendingRows],
             PlanKind::Prepare => vec![ExecuteResponseKind::Prepare],
@@ -455,8 +492,37 @@ impl Transmittable for ExecuteResponse {
     fn to_allowed(&self) -> Self::Allowed {
         ExecuteResponseKind::from(self)
     }
+
+    fn complete_statement_log(&mut self, log: StatementLog) {
+        use ExecuteResponse::*;
+        let rows = match self {
+            Canceled => return log.complete(None, Some(STATEMENT_CANCELED.into())),
+            CopyTo { resp, .. } => return resp.complete_statement_log(log),
+            SendingRows { future, .. } => {
+                let rows = std::mem::replace(future, Box::pin(std::future::pending()));
+                *future = Box::pin(async move {
+                    let response = rows.await;
+                    match &response {
+                        PeekResponseUnary::Rows(rows) => log.complete(Some(rows.len()), None),
+                        PeekResponseUnary::Error(e) => log.complete(None, Some(e.clone())),
+                        PeekResponseUnary::Canceled => {
+                            log.complete(None, Some(STATEMENT_CANCELED.into()))
+                        }
+                    }
+                    response
+                });
+                return;
+            }
+            Copied(n) | Deleted(n) | Inserted(n) | Merged(n) | Updated(n) => Some(*n),
+            _ => None,
+        };
+        log.complete(rows, None);
+    }
 }
 
+/// The error recorded for canceled statements in the statement log.
+const STATEMENT_CANCELED: &str = "canceling statement due to user request";
+
 /// The state of a cancellation request.
 #[derive(Debug, Clone, Copy)]
 pub enum Canceled {
@@ -465,4 +531,7 @@ pub enum Canceled {
     /// No cancellation request has yet occurred, or a previous request has been
     /// cleared.
     NotCanceled,
+    /// A request to terminate the connection has occurred. Unlike
+    /// `Canceled`, this state is never cleared.
+    Terminated,
 }
@@ -100,9 +100,11 @@ use mz_persist_client::usage::StorageUsageClient;
 use mz_persist_client::ShardId;
 use mz_repr::{Datum, Diff, GlobalId, Row, Timestamp};
 use mz_secrets::SecretsController;
-use mz_sql::ast::{CreateSourceStatement, CreateSubsourceStatement, Raw, Statement};
+use mz_sql::ast::{
+    AlterSourceStatement, CreateSourceStatement, CreateSubsourceStatement, Raw, Statement,
+};
 use mz_sql::names::Aug;
-use mz_sql::plan::{MutationKind, Params};
+use mz_sql::plan::{CopyToUrlPlan, MutationKind, Params};
 use mz_stash::Append;
 use mz_storage_client::controller::{
     CollectionDescription, CreateExportToken, DataSource, StorageError,
@@ -120,10 +122,13 @@ use crate::catalog::{
 use crate::client::{Client, ConnectionId, Handle};
 use crate::command::{Canceled, Command, ExecuteResponse};
 use crate::coord::appends::{BuiltinTableUpdateSource, Deferred, PendingWriteTxn};
+use crate::coord::copy::PendingCopyTo;
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::metrics::Metrics;
 use crate::coord::peek::PendingPeek;
-use crate::coord::read_policy::ReadCapability;
+use crate::coord::read_policy::{ReadCapability, ReadHolds};
+use crate::coord::session_activity::{SessionActivity, SessionState};
+use crate::coord::statistics::{CollectionStatisticsCache, CollectionStatisticsMap};
 use crate::coord::timeline::{TimelineState, WriteTimestamp};
 use crate::error::AdapterError;
 use crate::session::{EndTransactionAction, Session};
@@ -133,9 +138,12 @@ use crate::AdapterNotice;
 
 pub(crate) mod id_bundle;
 pub(crate) mod peek;
+pub(crate) mod session_activity;
+pub(crate) mod statement_logging;
 
 mod appends;
 mod command_handler;
+mod copy;
 mod dataflows;
 mod ddl;
 mod indexes;
@@ -144,6 +152,7 @@ mod metrics;
 mod read_policy;
 mod sequencer;
 mod sql;
+mod statistics;
 mod timeline;
 mod timestamp_selection;
 
@@ -160,8 +169,11 @@ pub enum Message<T = mz_repr::Timestamp> {
     Command(Command),
     ControllerReady,
     CreateSourceStatementReady(CreateSourceStatementReady),
+    AlterSourceStatementReady(AlterSourceStatementReady),
     SinkConnectionReady(SinkConnectionReady),
     SendDiffs(SendDiffs),
+    CopyFromUrlReady(CopyFromUrlReady),
+    CopyToUrlReady(CopyToUrlReady),
     WriteLockGrant(tokio::sync::OwnedMutexGuard<()>),
     /// Initiates a group commit.
     GroupCommitInitiate,
@@ -182,7 +194,19 @@ pub enum Message<T = mz_repr::Timestamp> {
     LinearizeReads(Vec<PendingReadTxn>),
     StorageUsageFetch,
     StorageUsageUpdate(HashMap<Option<ShardId>, u64>),
+    CollectionStatisticsFetch,
+    CollectionStatisticsUpdate(CollectionStatisticsMap),
     Consolidate(Vec<mz_stash::Id>),
+    /// A logged statement completed, producing the contained row of
+    /// `mz_statement_log`.
+    StatementLogged(Row),
+    /// Appends the buffered rows of `mz_statement_log`.
+    StatementLogFlush,
+    /// A connection finished executing a statement and is now idle.
+    SessionIdle {
+        conn_id: ConnectionId,
+        state: SessionState,
+    },
 }
 
 #[derive(Derivative)]
@@ -195,6 +219,29 @@ pub struct SendDiffs {
     pub diffs: Result<Vec<(Row, Diff)>, AdapterError>,
     pub kind: MutationKind,
     pub returning: Vec<(Row, NonZeroUsize)>,
+    pub affected_rows: Option<usize>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct CopyFromUrlReady {
+    session: Session,
+    #[derivative(Debug = "ignore")]
+    tx: ClientTransmitter<ExecuteResponse>,
+    pub id: GlobalId,
+    pub columns: Vec<usize>,
+    #[derivative(Debug = "ignore")]
+    pub rows: Result<Vec<Row>, AdapterError>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct CopyToUrlReady {
+    session: Session,
+    #[derivative(Debug = "ignore")]
+    tx: ClientTransmitter<ExecuteResponse>,
+    pub plan: CopyToUrlPlan,
+    pub result: Result<(), AdapterError>,
 }
 
 #[derive(Derivative)]
@@ -216,6 +263,19 @@ pub struct CreateSourceStatementReady {
     pub otel_ctx: OpenTelemetryContext,
 }
 
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct AlterSourceStatementReady {
+    pub session: Session,
+    #[derivative(Debug = "ignore")]
+    pub tx: ClientTransmitter<ExecuteResponse>,
+    pub result: Result<AlterSourceStatement<Aug>, AdapterError>,
+    pub params: Params,
+    pub depends_on: Vec<GlobalId>,
+    pub original_stmt: Statement<Raw>,
+    pub otel_ctx: OpenTelemetryContext,
+}
+
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct SinkConnectionReady {
@@ -277,6 +337,9 @@ struct ConnMeta {
 
     /// Channel on which to send notices to a session.
     notice_tx: mpsc::UnboundedSender<AdapterNotice>,
+
+    /// The activity of the session, as reported by `mz_sessions`.
+    activity: SessionActivity,
 }
 
 struct TxnReads {
@@ -285,7 +348,7 @@ struct TxnReads {
     // happens if both 1) there are no referenced sources or indexes and 2)
     // `mz_now()` is not present.
     timestamp_independent: bool,
-    read_holds: crate::coord::read_policy::ReadHolds<mz_repr::Timestamp>,
+    read_holds: ReadHolds<mz_repr::Timestamp>,
 }
 
 #[derive(Debug)]
@@ -406,6 +469,13 @@ pub struct Coordinator<S> {
     /// in `self.read_capability[id]`, using the `release_read_holds` method.
     txn_reads: HashMap<ConnectionId, TxnReads>,
 
+    /// For each durable subscription, the read hold on the storage collection
+    /// it reads at the subscription's timestamp.
+    ///
+    /// The hold moves forward when the subscription is acknowledged and is
+    /// released when the subscription is dropped.
+    subscription_read_holds: HashMap<GlobalId, ReadHolds<mz_repr::Timestamp>>,
+
     /// Access to the peek fields should be restricted to methods in the [`peek`] API.
     /// A map from pending peek ids to the queue into which responses are sent, and
     /// the connection id of the client that initiated the peek.
@@ -416,6 +486,10 @@ pub struct Coordinator<S> {
     /// A map from pending subscribes to the subscribe description.
     pending_subscribes: HashMap<GlobalId, PendingSubscribe>,
 
+    /// A map from the sinks of pending `COPY ... TO` a URL to the clients
+    /// waiting for their outcome.
+    pending_copy_tos: HashMap<GlobalId, PendingCopyTo>,
+
     /// Serializes accesses to write critical sections.
     write_lock: Arc<tokio::sync::Mutex<()>>,
     /// Holds plans deferred due to write lock.
@@ -441,11 +515,23 @@ pub struct Coordinator<S> {
     /// dropped and for which no further updates should be recorded.
     transient_replica_metadata: HashMap<ReplicaId, Option<ReplicaMetadata>>,
 
+    /// The hydration status of indexes and materialized views on each replica,
+    /// as reported by the compute controller. Intended for inclusion in system
+    /// tables.
+    compute_hydration_status: BTreeMap<(GlobalId, ReplicaId), bool>,
+
     /// Persist client for fetching storage metadata such as size metrics.
     storage_usage_client: StorageUsageClient,
     /// The interval at which to collect storage usage information.
     storage_usage_collection_interval: Duration,
 
+    /// The most recently collected statistics about storage collections, for
+    /// use by the optimizer.
+    collection_statistics: CollectionStatisticsCache,
+
+    /// Rows of `mz_statement_log` waiting to be appended.
+    statement_log_buffer: Vec<Row>,
+
     /// Segment analytics client.
     segment_client: Option<mz_segment::Client>,
 
@@ -474,6 +560,7 @@ impl<S: Append + 'static> Coordinator<S> {
                 instance.id,
                 instance.log_indexes.clone(),
                 self.catalog.system_config().max_result_size(),
+                self.memory_limits(),
             )?;
             for (replica_id, replica) in instance.replicas_by_id.clone() {
                 let introspection_collections = replica
@@ -613,6 +700,7 @@ impl<S: Append + 'static> Coordinator<S> {
                                 data_source,
                                 since: None,
                                 status_collection_id: source_status_collection_id,
+                                fork_shard: None,
                             },
                         )])
                         .await
@@ -749,6 +837,13 @@ impl<S: Append + 'static> Coordinator<S> {
                         );
                     }
                 }
+                CatalogItem::Subscription(subscription) => {
+                    self.bootstrap_subscription_read_hold(
+                        entry.id(),
+                        subscription.from,
+                        subscription.timestamp,
+                    );
+                }
                 // Nothing to do for these cases
                 CatalogItem::Log(_)
                 | CatalogItem::Type(_)
@@ -918,6 +1013,7 @@ impl<S: Append + 'static> Coordinator<S> {
         let mut compute_events = self.controller.compute.watch_services();
 
         self.schedule_storage_usage_collection();
+        self.schedule_collection_statistics_collection(Duration::ZERO);
 
         loop {
             // Before adding a branch to this select loop, please ensure that the branch is
@@ -1098,9 +1194,11 @@ pub async fn serve<S: Append + 'static>(
                 storage_read_capabilities: Default::default(),
                 compute_read_capabilities: Default::default(),
                 txn_reads: Default::default(),
+                subscription_read_holds: Default::default(),
                 pending_peeks: HashMap::new(),
                 client_pending_peeks: HashMap::new(),
                 pending_subscribes: HashMap::new(),
+                pending_copy_tos: HashMap::new(),
                 write_lock: Arc::new(tokio::sync::Mutex::new(())),
                 write_lock_wait_group: VecDeque::new(),
                 pending_writes: Vec::new(),
@@ -1108,8 +1206,11 @@ pub async fn serve<S: Append + 'static>(
                 cloud_resource_controller,
                 connection_context,
                 transient_replica_metadata: HashMap::new(),
+                compute_hydration_status: BTreeMap::new(),
                 storage_usage_client,
                 storage_usage_collection_interval,
+                collection_statistics: CollectionStatisticsCache::default(),
+                statement_log_buffer: Vec::new(),
                 segment_client,
                 metrics: Metrics::register_with(&metrics_registry),
             };
@@ -66,6 +66,9 @@ pub(crate) enum PendingWriteTxn {
         write_lock_guard: Option<OwnedMutexGuard<()>>,
         /// Inner transaction.
         pending_txn: PendingTxn,
+        /// Whether the write accompanies a catalog change, and so must be
+        /// applied before the coordinator handles any other message.
+        ddl: bool,
     },
     /// Write to a system table.
     System {
@@ -88,7 +91,7 @@ impl PendingWriteTxn {
     /// asynchronously.
     fn should_block(&self) -> bool {
         match self {
-            PendingWriteTxn::User { .. } => false,
+            PendingWriteTxn::User { ddl, .. } => *ddl,
             PendingWriteTxn::System { source, .. } => match source {
                 BuiltinTableUpdateSource::DDL => true,
                 BuiltinTableUpdateSource::Background => false,
@@ -245,6 +248,7 @@ impl<S: Append + 'static> Coordinator<S> {
                 PendingWriteTxn::User {
                     writes,
                     write_lock_guard: _,
+                    ddl: _,
                     pending_txn:
                         PendingTxn {
                             client_transmitter,
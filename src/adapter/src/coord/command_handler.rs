@@ -20,7 +20,7 @@ use tracing::Instrument;
 use mz_compute_client::response::PeekResponse;
 use mz_ore::task;
 use mz_repr::ScalarType;
-use mz_sql::ast::{InsertSource, Query, Raw, SetExpr, Statement};
+use mz_sql::ast::{AlterSourceAction, InsertSource, Query, Raw, SetExpr, Statement};
 use mz_sql::catalog::SessionCatalog as _;
 use mz_sql::plan::{CreateRolePlan, Params};
 use mz_stash::Append;
@@ -32,7 +32,11 @@ use crate::command::{
 use crate::coord::appends::{Deferred, PendingWriteTxn};
 use crate::coord::metrics;
 use crate::coord::peek::PendingPeek;
-use crate::coord::{ConnMeta, Coordinator, CreateSourceStatementReady, Message, PendingTxn};
+use crate::coord::session_activity::SessionActivity;
+use crate::coord::{
+    AlterSourceStatementReady, ConnMeta, Coordinator, CreateSourceStatementReady, Message,
+    PendingTxn,
+};
 use crate::error::AdapterError;
 use crate::session::{PreparedStatement, Session, TransactionStatus};
 use crate::util::{ClientTransmitter, ResultExt};
@@ -56,7 +60,8 @@ impl<S: Append + 'static> Coordinator<S> {
                 tx,
                 span,
             } => {
-                let tx = ClientTransmitter::new(tx, self.internal_cmd_tx.clone());
+                let mut tx = ClientTransmitter::new(tx, self.internal_cmd_tx.clone());
+                tx.set_report_idle();
 
                 let span = tracing::debug_span!(parent: &span, "message_command (execute)");
                 self.handle_execute(portal_name, session, tx)
@@ -136,7 +141,8 @@ impl<S: Append + 'static> Coordinator<S> {
                 session,
                 tx,
             } => {
-                let tx = ClientTransmitter::new(tx, self.internal_cmd_tx.clone());
+                let mut tx = ClientTransmitter::new(tx, self.internal_cmd_tx.clone());
+                tx.set_report_idle();
                 self.sequence_end_transaction(tx, session, action).await;
             }
 
@@ -208,6 +214,7 @@ impl<S: Append + 'static> Coordinator<S> {
                 cancel_tx,
                 secret_key,
                 notice_tx: session.retain_notice_transmitter(),
+                activity: SessionActivity::new(&session, self.now()),
             },
         );
 
@@ -226,7 +233,7 @@ impl<S: Append + 'static> Coordinator<S> {
         &mut self,
         portal_name: String,
         mut session: Session,
-        tx: ClientTransmitter<ExecuteResponse>,
+        mut tx: ClientTransmitter<ExecuteResponse>,
     ) {
         if let Err(err) = self.verify_portal(&mut session, &portal_name) {
             return tx.send(Err(err), session);
@@ -246,6 +253,8 @@ impl<S: Append + 'static> Coordinator<S> {
             .query_total
             .with_label_values(&[stmt_type])
             .inc();
+        tx.set_statement_log(self.begin_statement_log(&session, &stmt));
+        self.mark_session_active(&session, &stmt);
 
         let params = portal.parameters.clone();
         self.handle_execute_inner(stmt, params, session, tx).await
@@ -257,7 +266,7 @@ impl<S: Append + 'static> Coordinator<S> {
         stmt: Statement<Raw>,
         params: Params,
         mut session: Session,
-        tx: ClientTransmitter<ExecuteResponse>,
+        mut tx: ClientTransmitter<ExecuteResponse>,
     ) {
         // Verify that this statement type can be executed in the current
         // transaction state.
@@ -341,7 +350,7 @@ impl<S: Append + 'static> Coordinator<S> {
                                 body: SetExpr::Values(..),
                                 ..
                             }) | InsertSource::DefaultValues
-                        ) =>
+                        ) && insert_statement.on_conflict.is_none() =>
                     {
                         // Inserting from default? values statements
                         // is always safe.
@@ -353,7 +362,10 @@ impl<S: Append + 'static> Coordinator<S> {
                     | Statement::AlterSecret(_)
                     | Statement::AlterSink(_)
                     | Statement::AlterSource(_)
+                    | Statement::AlterSubscription(_)
+                    | Statement::AlterTable(_)
                     | Statement::AlterObjectRename(_)
+                    | Statement::AlterObjectSwap(_)
                     | Statement::AlterSystemSet(_)
                     | Statement::AlterSystemReset(_)
                     | Statement::AlterSystemResetAll(_)
@@ -367,9 +379,11 @@ impl<S: Append + 'static> Coordinator<S> {
                     | Statement::CreateSecret(_)
                     | Statement::CreateSink(_)
                     | Statement::CreateSource(_)
+                    | Statement::CreateSubscription(_)
                     | Statement::CreateSubsource(_)
                     | Statement::CreateTable(_)
                     | Statement::CreateType(_)
+                    | Statement::CreateFunction(_)
                     | Statement::CreateView(_)
                     | Statement::CreateMaterializedView(_)
                     | Statement::Delete(_)
@@ -380,6 +394,7 @@ impl<S: Append + 'static> Coordinator<S> {
                     | Statement::DropClusters(_)
                     | Statement::DropClusterReplicas(_)
                     | Statement::Insert(_)
+                    | Statement::Merge(_)
                     | Statement::Update(_) => {
                         return tx.send(
                             Err(AdapterError::OperationProhibitsTransaction(
@@ -438,6 +453,39 @@ impl<S: Append + 'static> Coordinator<S> {
                 });
             }
 
+            // `ALTER SOURCE ... REFRESH SCHEMA` statements look up the new
+            // schemas off the main coordinator thread of control.
+            Statement::AlterSource(stmt)
+                if matches!(stmt.action, AlterSourceAction::RefreshSchema { seed: None }) =>
+            {
+                let internal_cmd_tx = self.internal_cmd_tx.clone();
+                let conn_id = session.conn_id();
+                let purify_fut = mz_sql::pure::purify_alter_source(
+                    Box::new(catalog.into_owned()),
+                    stmt,
+                    self.connection_context.clone(),
+                );
+                let otel_ctx = OpenTelemetryContext::obtain();
+                task::spawn(|| format!("purify:{conn_id}"), async move {
+                    let result = purify_fut.await.map_err(|e| e.into());
+                    // It is not an error for purification to complete after `internal_cmd_rx` is dropped.
+                    let result = internal_cmd_tx.send(Message::AlterSourceStatementReady(
+                        AlterSourceStatementReady {
+                            session,
+                            tx,
+                            result,
+                            params,
+                            depends_on,
+                            original_stmt,
+                            otel_ctx,
+                        },
+                    ));
+                    if let Err(e) = result {
+                        tracing::warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+                    }
+                });
+            }
+
             // `CREATE SUBSOURCE` statements are disallowed for users and are only generated
             // automatically as part of purification
             Statement::CreateSubsource(_) => tx.send(
@@ -447,7 +495,10 @@ impl<S: Append + 'static> Coordinator<S> {
 
             // All other statements are handled immediately.
             _ => match self.plan_statement(&mut session, stmt, &params) {
-                Ok(plan) => self.sequence_plan(tx, session, plan, depends_on).await,
+                Ok(plan) => {
+                    tx.mark_statement_planned();
+                    self.sequence_plan(tx, session, plan, depends_on).await
+                }
                 Err(e) => tx.send(Err(e), session),
             },
         }
@@ -478,7 +529,13 @@ impl<S: Append + 'static> Coordinator<S> {
             if conn_meta.secret_key != secret_key {
                 return;
             }
+            self.cancel_connection(conn_id);
+        }
+    }
 
+    /// Cancels the statement running on connection `conn_id`, if any.
+    pub(crate) fn cancel_connection(&mut self, conn_id: ConnectionId) {
+        if let Some(conn_meta) = self.active_conns.get(&conn_id) {
             // Cancel pending writes. There is at most one pending write per session.
             if let Some(idx) = self.pending_writes.iter().position(|pending_write_txn| {
                 matches!(pending_write_txn, PendingWriteTxn::User {
@@ -512,8 +569,13 @@ impl<S: Append + 'static> Coordinator<S> {
                 }
             }
 
-            // Inform the target session (if it asks) about the cancellation.
-            let _ = conn_meta.cancel_tx.send(Canceled::Canceled);
+            // Inform the target session (if it asks) about the cancellation,
+            // unless it is already being terminated.
+            conn_meta.cancel_tx.send_modify(|canceled| {
+                if !matches!(canceled, Canceled::Terminated) {
+                    *canceled = Canceled::Canceled;
+                }
+            });
 
             for PendingPeek {
                 sender: rows_tx,
@@ -525,6 +587,17 @@ impl<S: Append + 'static> Coordinator<S> {
                 // So we ignore errors when sending the response.
                 let _ = rows_tx.send(PeekResponse::Canceled);
             }
+
+            self.cancel_pending_copy_tos(conn_id);
+        }
+    }
+
+    /// Cancels the statement running on connection `conn_id`, if any, and
+    /// asks the connection to close.
+    pub(crate) fn terminate_connection(&mut self, conn_id: ConnectionId) {
+        self.cancel_connection(conn_id);
+        if let Some(conn_meta) = self.active_conns.get(&conn_id) {
+            let _ = conn_meta.cancel_tx.send(Canceled::Terminated);
         }
     }
 
@@ -539,7 +612,10 @@ impl<S: Append + 'static> Coordinator<S> {
             .drop_temporary_schema(session.conn_id())
             .unwrap_or_terminate("unable to drop temporary schema");
         self.metrics.active_sessions.dec();
-        self.active_conns.remove(&session.conn_id());
+        if let Some(conn_meta) = self.active_conns.remove(&session.conn_id()) {
+            self.retract_session_activity(conn_meta.activity);
+        }
         self.cancel_pending_peeks(session.conn_id());
+        self.cancel_pending_copy_tos(session.conn_id());
     }
 }
@@ -0,0 +1,212 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Reading the objects of `COPY ... FROM` a URL, and checking and tracking
+//! in-progress `COPY ... TO` a URL.
+//!
+//! Rows never stream through the client. A `COPY ... TO` installs a dataflow
+//! whose sink encodes the query's results and uploads them to S3 from the
+//! cluster, so the results never pass through the coordinator. The coordinator
+//! only waits for the sink to report the number of rows it wrote. A `COPY ...
+//! FROM` downloads and decodes one object at a time, and fails once the
+//! decoded rows exceed `max_result_size`, as they must be held in memory until
+//! they are inserted into the table.
+
+use anyhow::{anyhow, Context};
+use bytesize::ByteSize;
+use tokio::sync::oneshot;
+
+use mz_compute_client::response::CopyToResponse;
+use mz_interchange::parquet::ParquetDecoder;
+use mz_ore::cast::CastFrom;
+use mz_repr::{GlobalId, RelationDesc, Row};
+use mz_sql::plan::{CopyFromUrlFormat, CopyUrl};
+use mz_stash::Append;
+use mz_storage_client::types::connections::ConnectionContext;
+
+use crate::client::ConnectionId;
+use crate::coord::Coordinator;
+
+/// A `COPY ... TO` a URL whose sink has not yet reported its outcome.
+#[derive(Debug)]
+pub(crate) struct PendingCopyTo {
+    /// The channel on which to send the sink's response.
+    pub(crate) sender: oneshot::Sender<CopyToResponse>,
+    pub(crate) conn_id: ConnectionId,
+}
+
+impl<S: Append + 'static> Coordinator<S> {
+    /// Forwards the response of the `COPY ... TO` sink `sink_id` to the
+    /// waiting client, if it is still waiting.
+    pub(crate) fn send_copy_to_response(&mut self, sink_id: GlobalId, response: CopyToResponse) {
+        if let Some(PendingCopyTo { sender, .. }) = self.pending_copy_tos.remove(&sink_id) {
+            // The client may have stopped waiting, e.g. because the statement
+            // timed out.
+            let _ = sender.send(response);
+        }
+    }
+
+    /// Cancels the in-progress `COPY ... TO` a URL of the connection
+    /// `conn_id`, if any. Its sink is dropped when the connection's
+    /// transaction ends.
+    pub(crate) fn cancel_pending_copy_tos(&mut self, conn_id: ConnectionId) {
+        let sink_ids: Vec<_> = self
+            .pending_copy_tos
+            .iter()
+            .filter(|(_, pending)| pending.conn_id == conn_id)
+            .map(|(sink_id, _)| *sink_id)
+            .collect();
+        for sink_id in sink_ids {
+            self.send_copy_to_response(sink_id, CopyToResponse::Dropped);
+        }
+    }
+}
+
+async fn s3_client(url: &CopyUrl, connection_context: &ConnectionContext) -> aws_sdk_s3::Client {
+    let config = url
+        .connection
+        .load(
+            connection_context.aws_external_id_prefix.as_ref(),
+            Some(&url.connection_id),
+            &*connection_context.secrets_reader,
+        )
+        .await;
+    aws_sdk_s3::Client::new(&config)
+}
+
+/// Returns the prefix of the keys of the objects under `url`.
+fn key_prefix(url: &CopyUrl) -> Option<String> {
+    match url.prefix.as_str() {
+        "" => None,
+        prefix => Some(format!("{}/", prefix)),
+    }
+}
+
+/// Fails if any objects exist under `url`.
+///
+/// A `COPY ... TO` a URL never overwrites or deletes objects, so that the
+/// objects it writes are not mixed up with those of an earlier copy to the
+/// same URL.
+pub(crate) async fn check_no_objects(
+    url: &CopyUrl,
+    connection_context: &ConnectionContext,
+) -> anyhow::Result<()> {
+    let client = s3_client(url, connection_context).await;
+    let response = client
+        .list_objects_v2()
+        .bucket(&url.bucket)
+        .set_prefix(key_prefix(url))
+        .max_keys(1)
+        .send()
+        .await
+        .with_context(|| format!("listing objects in s3://{}", url.bucket))?;
+    if let Some(key) = response
+        .contents
+        .into_iter()
+        .flatten()
+        .find_map(|object| object.key)
+    {
+        anyhow::bail!(
+            "COPY TO s3://{}/{} would overwrite existing object s3://{}/{}",
+            url.bucket,
+            url.prefix,
+            url.bucket,
+            key
+        );
+    }
+    Ok(())
+}
+
+/// Downloads every object under `url` and decodes it in `format` into rows
+/// whose columns are described by `desc`.
+///
+/// Objects are read one at a time, in lexicographic order of their keys. Fails
+/// if the decoded rows, or any single object, exceed `max_size` bytes.
+pub(crate) async fn read_objects(
+    url: &CopyUrl,
+    connection_context: &ConnectionContext,
+    desc: RelationDesc,
+    format: CopyFromUrlFormat,
+    max_size: u32,
+) -> anyhow::Result<Vec<Row>> {
+    let client = s3_client(url, connection_context).await;
+    let max_size = usize::cast_from(max_size);
+    let exceeds_max_size = || {
+        anyhow!(
+            "COPY FROM s3://{} exceeds max size of {}",
+            url.bucket,
+            ByteSize::b(u64::cast_from(max_size))
+        )
+    };
+
+    let prefix = key_prefix(url);
+    let mut keys = vec![];
+    let mut continuation_token = None;
+    loop {
+        let response = client
+            .list_objects_v2()
+            .bucket(&url.bucket)
+            .set_prefix(prefix.clone())
+            .set_continuation_token(continuation_token)
+            .send()
+            .await
+            .with_context(|| format!("listing objects in s3://{}", url.bucket))?;
+        if let Some(contents) = response.contents {
+            for object in contents {
+                if let Some(key) = object.key {
+                    if usize::try_from(object.size).map_or(true, |size| size > max_size) {
+                        return Err(exceeds_max_size());
+                    }
+                    keys.push(key);
+                }
+            }
+        }
+        match response.next_continuation_token {
+            Some(token) => continuation_token = Some(token),
+            None => break,
+        }
+    }
+
+    let column_types: Vec<_> = desc
+        .iter_types()
+        .map(|typ| mz_pgrepr::Type::from(&typ.scalar_type))
+        .collect();
+    let mut parquet_decoder = ParquetDecoder::from_desc(desc);
+    let mut rows = vec![];
+    let mut total_size = 0;
+    for key in keys {
+        let object = client
+            .get_object()
+            .bucket(&url.bucket)
+            .key(&key)
+            .send()
+            .await
+            .with_context(|| format!("reading s3://{}/{}", url.bucket, key))?;
+        let bytes = object
+            .body
+            .collect()
+            .await
+            .with_context(|| format!("reading s3://{}/{}", url.bucket, key))?
+            .into_bytes();
+        let decoded = match &format {
+            CopyFromUrlFormat::Pgcopy(params) => {
+                mz_pgcopy::decode_copy_format(&bytes, &column_types, params.clone())
+                    .map_err(anyhow::Error::from)
+            }
+            CopyFromUrlFormat::Parquet => parquet_decoder.decode(&bytes),
+        };
+        let decoded = decoded.with_context(|| format!("decoding s3://{}/{}", url.bucket, key))?;
+        total_size += decoded.iter().map(|row| row.byte_len()).sum::<usize>();
+        if total_size > max_size {
+            return Err(exceeds_max_size());
+        }
+        rows.extend(decoded);
+    }
+    Ok(rows)
+}
@@ -38,11 +38,12 @@ use mz_stash::Append;
 use crate::catalog::{CatalogItem, CatalogState, DataSourceDesc, MaterializedView, Source, View};
 use crate::coord::ddl::CatalogTxn;
 use crate::coord::id_bundle::CollectionIdBundle;
+use crate::coord::statistics::CollectionStatisticsCache;
 use crate::coord::{Coordinator, DEFAULT_LOGICAL_COMPACTION_WINDOW_MS};
 use crate::session::{Session, SERVER_MAJOR_VERSION, SERVER_MINOR_VERSION};
 use crate::AdapterError;
 
-/// Borrows of catalog and indexes sufficient to build dataflow descriptions.
+/// Borrows of catalog, indexes, and statistics sufficient to build dataflow descriptions.
 pub struct DataflowBuilder<'a, T> {
     pub catalog: &'a CatalogState,
     /// A handle to the compute abstraction, which describes indexes by identifier.
@@ -50,6 +51,8 @@ pub struct DataflowBuilder<'a, T> {
     /// This can also be used to grab a handle to the storage abstraction, through
     /// its `storage_mut()` method.
     pub compute: ComputeInstanceRef<'a, T>,
+    /// Statistics about the contents of storage collections.
+    pub statistics: &'a CollectionStatisticsCache,
     recursion_guard: RecursionGuard,
 }
 
@@ -78,6 +81,7 @@ impl<S: Append + 'static> Coordinator<S> {
         DataflowBuilder {
             catalog: self.catalog.state(),
             compute,
+            statistics: &self.collection_statistics,
             recursion_guard: RecursionGuard::with_limit(RECURSION_LIMIT),
         }
     }
@@ -186,6 +190,7 @@ impl CatalogTxn<'_, mz_repr::Timestamp> {
         DataflowBuilder {
             catalog: self.catalog,
             compute,
+            statistics: self.collection_statistics,
             recursion_guard: RecursionGuard::with_limit(RECURSION_LIMIT),
         }
     }
@@ -322,7 +327,7 @@ impl<'a> DataflowBuilder<'a, mz_repr::Timestamp> {
         dataflow.export_index(id, index_description, on_type);
 
         // Optimize the dataflow across views, and any other ways that appeal.
-        mz_transform::optimize_dataflow(&mut dataflow, &self.index_oracle())?;
+        mz_transform::optimize_dataflow(&mut dataflow, &self.index_oracle(), self.statistics)?;
 
         Ok(dataflow)
     }
@@ -359,11 +364,36 @@ impl<'a> DataflowBuilder<'a, mz_repr::Timestamp> {
         dataflow.export_sink(id, sink_description);
 
         // Optimize the dataflow across views, and any other ways that appeal.
-        mz_transform::optimize_dataflow(dataflow, &self.index_oracle())?;
+        mz_transform::optimize_dataflow(dataflow, &self.index_oracle(), self.statistics)?;
 
         Ok(())
     }
 
+    /// Like `build_sink_dataflow`, but always reads the sink's input directly
+    /// from its storage collection rather than from any indexes on it.
+    ///
+    /// This is used to resume durable subscriptions, whose as-of is protected
+    /// by a read hold on the storage collection but not on its indexes.
+    pub fn build_storage_sink_dataflow(
+        &mut self,
+        name: String,
+        id: GlobalId,
+        sink_description: ComputeSinkDesc,
+    ) -> Result<DataflowDesc, AdapterError> {
+        let mut dataflow = DataflowDesc::new(name);
+        dataflow.set_as_of(sink_description.as_of.frontier.clone());
+        let from_id = sink_description.from;
+        let typ = sink_description.from_desc.typ().clone();
+        let monotonic = match self.catalog.get_entry(&from_id).item() {
+            CatalogItem::Source(source) => self.monotonic_source(source),
+            CatalogItem::MaterializedView(_) => self.monotonic_view(from_id),
+            _ => false,
+        };
+        dataflow.import_source(from_id, typ, monotonic);
+        dataflow.export_sink(id, sink_description);
+        Ok(dataflow)
+    }
+
     /// Builds a dataflow description for the materialized view specified by `id`.
     ///
     /// For this, we first build a dataflow for the view expression, then we
@@ -493,7 +523,8 @@ impl<'a> DataflowBuilder<'a, mz_repr::Timestamp> {
                 | CatalogItem::Log(_)
                 | CatalogItem::Index(_)
                 | CatalogItem::Sink(_)
-                | CatalogItem::Func(_) => Ok(false),
+                | CatalogItem::Func(_)
+                | CatalogItem::Subscription(_) => Ok(false),
             }
         })
     }
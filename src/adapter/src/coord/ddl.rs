@@ -38,6 +38,7 @@ use crate::catalog::{
 };
 use crate::client::ConnectionId;
 use crate::coord::appends::BuiltinTableUpdateSource;
+use crate::coord::statistics::CollectionStatisticsCache;
 use crate::coord::Coordinator;
 use crate::session::vars::SystemVars;
 use crate::session::Session;
@@ -48,6 +49,7 @@ use crate::{catalog, AdapterError};
 pub struct CatalogTxn<'a, T> {
     pub(crate) dataflow_client: &'a mz_controller::Controller<T>,
     pub(crate) catalog: &'a CatalogState,
+    pub(crate) collection_statistics: &'a CollectionStatisticsCache,
 }
 
 impl<S: Append + 'static> Coordinator<S> {
@@ -81,6 +83,7 @@ impl<S: Append + 'static> Coordinator<S> {
         let mut materialized_views_to_drop = vec![];
         let mut replication_slots_to_drop: Vec<(mz_postgres_util::Config, String)> = vec![];
         let mut secrets_to_drop = vec![];
+        let mut subscriptions_to_drop = vec![];
         let mut timelines_to_drop = vec![];
         let mut vpc_endpoints_to_drop = vec![];
 
@@ -131,6 +134,9 @@ impl<S: Append + 'static> Coordinator<S> {
                     CatalogItem::Secret(_) => {
                         secrets_to_drop.push(*id);
                     }
+                    CatalogItem::Subscription(_) => {
+                        subscriptions_to_drop.push(*id);
+                    }
                     CatalogItem::Connection(catalog::Connection { connection, .. }) => {
                         match connection {
                             // SSH connections have an associated secret that should be dropped
@@ -216,6 +222,7 @@ impl<S: Append + 'static> Coordinator<S> {
                 f(CatalogTxn {
                     dataflow_client: &self.controller,
                     catalog,
+                    collection_statistics: &self.collection_statistics,
                 })
             })
             .await?;
@@ -226,6 +233,9 @@ impl<S: Append + 'static> Coordinator<S> {
             self.send_builtin_table_updates(builtin_table_updates, BuiltinTableUpdateSource::DDL)
                 .await;
 
+            if !subscriptions_to_drop.is_empty() {
+                self.drop_subscriptions(subscriptions_to_drop).await;
+            }
             if !sources_to_drop.is_empty() {
                 self.drop_sources(sources_to_drop).await;
             }
@@ -317,6 +327,14 @@ impl<S: Append + 'static> Coordinator<S> {
         self.controller.storage.drop_sources(sources).await.unwrap();
     }
 
+    async fn drop_subscriptions(&mut self, subscriptions: Vec<GlobalId>) {
+        for id in subscriptions {
+            if let Some(read_holds) = self.subscription_read_holds.remove(&id) {
+                self.release_read_hold(&read_holds).await;
+            }
+        }
+    }
+
     pub(crate) async fn drop_compute_sinks(&mut self, sinks: Vec<ComputeSinkId>) {
         let by_compute_instance = sinks
             .into_iter()
@@ -640,7 +658,8 @@ impl<S: Append + 'static> Coordinator<S> {
                         | CatalogItem::View(_)
                         | CatalogItem::Index(_)
                         | CatalogItem::Type(_)
-                        | CatalogItem::Func(_) => {}
+                        | CatalogItem::Func(_)
+                        | CatalogItem::Subscription(_) => {}
                     }
                 }
                 Op::DropDatabase { .. } => {
@@ -696,11 +715,15 @@ impl<S: Append + 'static> Coordinator<S> {
                         | CatalogItem::View(_)
                         | CatalogItem::Index(_)
                         | CatalogItem::Type(_)
-                        | CatalogItem::Func(_) => {}
+                        | CatalogItem::Func(_)
+                        | CatalogItem::Subscription(_) => {}
                     }
                 }
                 Op::AlterSink { .. }
                 | Op::AlterSource { .. }
+                | Op::AlterSourceSchema { .. }
+                | Op::AlterTable { .. }
+                | Op::AlterSubscription { .. }
                 | Op::DropTimeline(_)
                 | Op::RenameItem { .. }
                 | Op::UpdateComputeInstanceStatus { .. }
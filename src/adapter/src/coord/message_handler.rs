@@ -30,7 +30,8 @@ use crate::util::ResultExt;
 use crate::{catalog, AdapterNotice};
 
 use crate::coord::{
-    Coordinator, CreateSourceStatementReady, Message, PendingReadTxn, ReplicaMetadata, SendDiffs,
+    AlterSourceStatementReady, Coordinator, CopyFromUrlReady, CopyToUrlReady,
+    CreateSourceStatementReady, Message, PendingReadTxn, ReplicaMetadata, SendDiffs,
     SinkConnectionReady,
 };
 
@@ -46,12 +47,18 @@ impl<S: Append + 'static> Coordinator<S> {
             Message::CreateSourceStatementReady(ready) => {
                 self.message_create_source_statement_ready(ready).await
             }
+            Message::AlterSourceStatementReady(ready) => {
+                self.message_alter_source_statement_ready(ready).await
+            }
             Message::SinkConnectionReady(ready) => self.message_sink_connection_ready(ready).await,
             Message::WriteLockGrant(write_lock_guard) => {
                 self.message_write_lock_grant(write_lock_guard).await;
             }
             Message::SendDiffs(diffs) => self.message_send_diffs(diffs),
+            Message::CopyFromUrlReady(ready) => self.message_copy_from_url_ready(ready),
+            Message::CopyToUrlReady(ready) => self.message_copy_to_url_ready(ready).await,
             Message::GroupCommitInitiate => {
+                self.publish_session_activity();
                 self.try_group_commit().await;
             }
             Message::GroupCommitApply(timestamp, responses, write_lock_guard) => {
@@ -79,9 +86,24 @@ impl<S: Append + 'static> Coordinator<S> {
             Message::StorageUsageUpdate(sizes) => {
                 self.storage_usage_update(sizes).await;
             }
+            Message::CollectionStatisticsFetch => {
+                self.collection_statistics_fetch();
+            }
+            Message::CollectionStatisticsUpdate(statistics) => {
+                self.collection_statistics_update(statistics);
+            }
             Message::Consolidate(collections) => {
                 self.consolidate(&collections).await;
             }
+            Message::StatementLogged(row) => {
+                self.buffer_statement_log(row);
+            }
+            Message::StatementLogFlush => {
+                self.flush_statement_log().await;
+            }
+            Message::SessionIdle { conn_id, state } => {
+                self.mark_session_idle(conn_id, state);
+            }
         }
     }
 
@@ -168,6 +190,9 @@ impl<S: Append + 'static> Coordinator<S> {
                     }
                 }
             }
+            ControllerResponse::CopyToResponse(sink_id, response) => {
+                self.send_copy_to_response(sink_id, response);
+            }
             ControllerResponse::ComputeReplicaHeartbeat(replica_id, when) => {
                 let replica_status_interval = chrono::Duration::seconds(60);
                 let when_coarsened = when
@@ -205,6 +230,34 @@ impl<S: Append + 'static> Coordinator<S> {
                         .await;
                 }
             }
+            ControllerResponse::ComputeHydrationStatus(id, replica_id, hydrated) => {
+                // `None` is the tombstone for a removed replica
+                if let Some(None) = self.transient_replica_metadata.get(&replica_id) {
+                    return;
+                }
+
+                let key = (id, replica_id);
+                let old = match hydrated {
+                    Some(hydrated) => self.compute_hydration_status.insert(key, hydrated),
+                    None => self.compute_hydration_status.remove(&key),
+                };
+                if old == hydrated {
+                    return;
+                }
+
+                let state = self.catalog.state();
+                let mut updates = Vec::new();
+                if let Some(old) = old {
+                    updates
+                        .push(state.pack_compute_hydration_status_update(id, replica_id, old, -1));
+                }
+                if let Some(new) = hydrated {
+                    updates
+                        .push(state.pack_compute_hydration_status_update(id, replica_id, new, 1));
+                }
+                self.send_builtin_table_updates(updates, BuiltinTableUpdateSource::Background)
+                    .await;
+            }
         }
     }
 
@@ -213,7 +266,7 @@ impl<S: Append + 'static> Coordinator<S> {
         &mut self,
         CreateSourceStatementReady {
             mut session,
-            tx,
+            mut tx,
             result,
             params,
             depends_on,
@@ -291,10 +344,51 @@ impl<S: Append + 'static> Coordinator<S> {
         plans.push((source_id, plan, depends_on));
 
         // Finally, sequence all plans in one go
+        tx.mark_statement_planned();
         let result = self.sequence_create_source(&mut session, plans).await;
         tx.send(result, session);
     }
 
+    #[tracing::instrument(level = "debug", skip(self, tx, session))]
+    async fn message_alter_source_statement_ready(
+        &mut self,
+        AlterSourceStatementReady {
+            mut session,
+            mut tx,
+            result,
+            params,
+            depends_on,
+            original_stmt,
+            otel_ctx,
+        }: AlterSourceStatementReady,
+    ) {
+        otel_ctx.attach_as_parent();
+
+        // Ensure that all dependencies still exist after purification. If any
+        // have gone missing, we repurify the original statement, which will
+        // report the missing object.
+        if !depends_on
+            .iter()
+            .all(|id| self.catalog.try_get_entry(id).is_some())
+        {
+            self.handle_execute_inner(original_stmt, params, session, tx)
+                .await;
+            return;
+        }
+
+        let stmt = match result {
+            Ok(stmt) => stmt,
+            Err(e) => return tx.send(Err(e), session),
+        };
+        match self.plan_statement(&mut session, Statement::AlterSource(stmt), &params) {
+            Ok(plan) => {
+                tx.mark_statement_planned();
+                self.sequence_plan(tx, session, plan, depends_on).await
+            }
+            Err(e) => tx.send(Err(e), session),
+        }
+    }
+
     #[tracing::instrument(level = "debug", skip(self, session_and_tx))]
     async fn message_sink_connection_ready(
         &mut self,
@@ -400,6 +494,7 @@ impl<S: Append + 'static> Coordinator<S> {
             diffs,
             kind,
             returning,
+            affected_rows,
         }: SendDiffs,
     ) {
         event!(Level::TRACE, diffs = format!("{:?}", diffs));
@@ -413,6 +508,7 @@ impl<S: Append + 'static> Coordinator<S> {
                             updates: diffs,
                             kind,
                             returning,
+                            affected_rows,
                         },
                     ),
                     session,
@@ -424,6 +520,41 @@ impl<S: Append + 'static> Coordinator<S> {
         }
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn message_copy_from_url_ready(
+        &mut self,
+        CopyFromUrlReady {
+            mut session,
+            tx,
+            id,
+            columns,
+            rows,
+        }: CopyFromUrlReady,
+    ) {
+        let result = rows.and_then(|rows| {
+            let count = rows.len();
+            self.sequence_copy_rows(&mut session, id, columns, rows)?;
+            Ok(ExecuteResponse::Copied(count))
+        });
+        tx.send(result, session);
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn message_copy_to_url_ready(
+        &mut self,
+        CopyToUrlReady {
+            session,
+            tx,
+            plan,
+            result,
+        }: CopyToUrlReady,
+    ) {
+        match result {
+            Ok(()) => self.sequence_copy_to_url_ready(tx, session, plan).await,
+            Err(e) => tx.send(Err(e), session),
+        }
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     async fn message_compute_instance_status(&mut self, event: ComputeInstanceEvent) {
         event!(Level::TRACE, event = format!("{:?}", event));
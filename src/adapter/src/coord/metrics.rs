@@ -50,6 +50,7 @@ where
         StatementKind::Copy => "copy",
         StatementKind::Update => "update",
         StatementKind::Delete => "delete",
+        StatementKind::Merge => "merge",
         StatementKind::CreateConnection => "create_connection",
         StatementKind::CreateDatabase => "create_database",
         StatementKind::CreateSchema => "create_schema",
@@ -61,15 +62,20 @@ where
         StatementKind::CreateTable => "create_table",
         StatementKind::CreateIndex => "create_index",
         StatementKind::CreateType => "create_type",
+        StatementKind::CreateFunction => "create_function",
         StatementKind::CreateRole => "create_role",
         StatementKind::CreateCluster => "create_cluster",
         StatementKind::CreateClusterReplica => "create_cluster_replica",
         StatementKind::CreateSecret => "create_secret",
+        StatementKind::CreateSubscription => "create_subscription",
         StatementKind::AlterObjectRename => "alter_object_rename",
+        StatementKind::AlterObjectSwap => "alter_object_swap",
         StatementKind::AlterIndex => "alter_index",
         StatementKind::AlterSecret => "alter_secret",
         StatementKind::AlterSink => "alter_sink",
         StatementKind::AlterSource => "alter_source",
+        StatementKind::AlterSubscription => "alter_subscription",
+        StatementKind::AlterTable => "alter_table",
         StatementKind::AlterSystemSet => "alter_system_set",
         StatementKind::AlterSystemReset => "alter_system_reset",
         StatementKind::AlterSystemResetAll => "alter_system_reset_all",
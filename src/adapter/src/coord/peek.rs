@@ -448,6 +448,7 @@ impl<S: Append + 'static> crate::coord::Coordinator<S> {
                 },
                 PeekResponse::Canceled => PeekResponseUnary::Canceled,
                 PeekResponse::Error(e) => PeekResponseUnary::Error(e),
+                PeekResponse::MemoryLimitExceeded(e) => PeekResponseUnary::Error(e.to_string()),
             },
         );
 
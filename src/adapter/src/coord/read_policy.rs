@@ -280,6 +280,18 @@ impl<S: Append + 'static> crate::coord::Coordinator<S> {
                         .holds
                         .update_iter(time.iter().map(|t| (*t, 1)));
                 }
+                // Subscriptions recovered during bootstrap already hold the
+                // collections they read, see `bootstrap_subscription_read_hold`.
+                for (time, _) in self
+                    .subscription_read_holds
+                    .values()
+                    .flat_map(|read_holds| read_holds.storage_ids())
+                    .filter(|(_, hold_id)| **hold_id == id)
+                {
+                    read_capability
+                        .holds
+                        .update_iter(time.iter().map(|t| (*t, 1)));
+                }
                 self.storage_read_capabilities.insert(id, read_capability);
                 storage_policy_updates.push((id, self.storage_read_capabilities[&id].policy()));
             }
@@ -387,6 +399,29 @@ impl<S: Append + 'static> crate::coord::Coordinator<S> {
         read_holds
     }
 
+    /// Records the read hold of the subscription `id` on the storage
+    /// collection `from` at `timestamp` while bootstrapping.
+    ///
+    /// Read capabilities do not exist yet at that point, so the hold is only
+    /// applied once `initialize_read_policies` creates the capability of
+    /// `from`. This keeps the collection from compacting past `timestamp` when
+    /// its read policy is first installed.
+    pub(crate) fn bootstrap_subscription_read_hold(
+        &mut self,
+        id: GlobalId,
+        from: GlobalId,
+        timestamp: mz_repr::Timestamp,
+    ) {
+        let read_holds = self.initialize_read_holds(
+            timestamp,
+            CollectionIdBundle {
+                storage_ids: BTreeSet::from([from]),
+                compute_ids: BTreeMap::new(),
+            },
+        );
+        self.subscription_read_holds.insert(id, read_holds);
+    }
+
     /// Attempt to acquire read holds on the indicated collections at the indicated `time`.
     ///
     /// If we are unable to acquire a read hold at the provided `time` for a specific id, then we
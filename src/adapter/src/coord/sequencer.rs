@@ -11,45 +11,53 @@
 
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::num::{NonZeroI64, NonZeroUsize};
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::anyhow;
 use maplit::btreeset;
 use timely::progress::{Antichain, Timestamp as TimelyTimestamp};
-use tokio::sync::{mpsc, OwnedMutexGuard};
+use tokio::sync::{mpsc, oneshot, OwnedMutexGuard};
 use tracing::{event, warn, Level};
 
 use mz_cloud_resources::VpcEndpointConfig;
 use mz_compute_client::command::{BuildDesc, DataflowDesc, IndexDesc, ReplicaId};
 use mz_compute_client::controller::{
-    ComputeInstanceId, ComputeReplicaConfig, ComputeReplicaLogging,
+    ComputeInstanceId, ComputeReplicaConfig, ComputeReplicaLogging, MemoryLimits,
 };
 use mz_compute_client::explain::{TimestampExplanation, TimestampSource};
+use mz_compute_client::response::CopyToResponse;
 use mz_compute_client::sinks::{
-    ComputeSinkConnection, ComputeSinkDesc, SinkAsOf, SubscribeSinkConnection,
+    ComputeSinkConnection, ComputeSinkDesc, CopyToS3Format, CopyToS3SinkConnection, SinkAsOf,
+    SubscribeSinkConnection,
 };
 use mz_expr::{
     permutation_for_arrangement, CollectionPlan, MirRelationExpr, MirScalarExpr,
     OptimizedMirRelationExpr, RowSetFinishing,
 };
+use mz_ore::cast::CastFrom;
 use mz_ore::task;
+use mz_persist_client::ShardId;
 use mz_repr::explain_new::Explainee;
 use mz_repr::{Datum, Diff, GlobalId, RelationDesc, Row, RowArena, Timestamp};
 use mz_sql::ast::{ExplainStage, IndexOptionName, ObjectType};
 use mz_sql::catalog::{CatalogComputeInstance, CatalogError, CatalogItemType, CatalogTypeDetails};
 use mz_sql::names::QualifiedObjectName;
 use mz_sql::plan::{
-    AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan, AlterItemRenamePlan, AlterSecretPlan,
-    AlterSinkPlan, AlterSourcePlan, AlterSystemResetAllPlan, AlterSystemResetPlan,
-    AlterSystemSetPlan, CreateComputeInstancePlan, CreateComputeReplicaPlan, CreateConnectionPlan,
-    CreateDatabasePlan, CreateIndexPlan, CreateMaterializedViewPlan, CreateRolePlan,
-    CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan,
+    AlterComputeInstanceSwapPlan, AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan,
+    AlterItemRenamePlan, AlterSchemaSwapPlan, AlterSecretPlan, AlterSinkPlan, AlterSourcePlan,
+    AlterSourceRefreshSchemaPlan, AlterSubscriptionPlan, AlterSystemResetAllPlan,
+    AlterSystemResetPlan, AlterSystemSetPlan, AlterTablePlan, AlterTablePlanAction, BackendSignal,
+    CopyFormat, CopyFromUrlPlan, CopyToUrlPlan, CreateComputeInstancePlan,
+    CreateComputeReplicaPlan, CreateConnectionPlan, CreateDatabasePlan, CreateFunctionPlan,
+    CreateIndexPlan, CreateMaterializedViewPlan, CreateRolePlan, CreateSchemaPlan,
+    CreateSecretPlan, CreateSinkPlan, CreateSourcePlan, CreateSubscriptionPlan, CreateTablePlan,
     CreateTypePlan, CreateViewPlan, DropComputeInstancesPlan, DropComputeReplicasPlan,
     DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan, ExecutePlan, ExplainPlan,
     FetchPlan, HirRelationExpr, IndexOption, InsertPlan, MaterializedView, MutationKind,
     OptimizerConfig, PeekPlan, Plan, PlanKind, QueryWhen, RaisePlan, ReadThenWritePlan,
     ResetVariablePlan, RotateKeysPlan, SendDiffsPlan, SetVariablePlan, ShowVariablePlan,
-    SubscribeFrom, SubscribePlan, View,
+    SignalBackendPlan, SubscribeFrom, SubscribePlan, View,
 };
 use mz_ssh_util::keys::SshKeyPairSet;
 use mz_stash::Append;
@@ -62,15 +70,18 @@ use crate::catalog::builtin::{
     MZ_INTROSPECTION_ROLE, MZ_SYSTEM_COMPUTE_INSTANCE, PG_CATALOG_SCHEMA,
 };
 use crate::catalog::{
-    self, Catalog, CatalogItem, ComputeInstance, Connection, DataSourceDesc, Ingestion,
-    SerializedComputeReplicaLocation, StorageSinkConnectionState, SYSTEM_USER,
+    self, Catalog, CatalogItem, ComputeInstance, Connection, DataSourceDesc, FuncDefinition,
+    Ingestion, SerializedComputeReplicaLocation, StorageSinkConnectionState, SYSTEM_USER,
 };
+use crate::client::ConnectionId;
 use crate::command::{Command, ExecuteResponse};
 use crate::coord::appends::{BuiltinTableUpdateSource, Deferred, DeferredPlan, PendingWriteTxn};
+use crate::coord::copy::PendingCopyTo;
 use crate::coord::dataflows::{prep_relation_expr, prep_scalar_expr, ExprPrepStyle};
+use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::{
-    peek, Coordinator, Message, PendingReadTxn, PendingTxn, SendDiffs, SinkConnectionReady,
-    TxnReads, DEFAULT_LOGICAL_COMPACTION_WINDOW_MS,
+    copy, peek, Coordinator, CopyFromUrlReady, CopyToUrlReady, Message, PendingReadTxn, PendingTxn,
+    SendDiffs, SinkConnectionReady, TxnReads, DEFAULT_LOGICAL_COMPACTION_WINDOW_MS,
 };
 use crate::error::AdapterError;
 use crate::explain_new::optimizer_trace::OptimizerTrace;
@@ -163,6 +174,13 @@ impl<S: Append + 'static> Coordinator<S> {
                     session,
                 );
             }
+            Plan::CreateSubscription(plan) => {
+                tx.send(
+                    self.sequence_create_subscription(&mut session, plan, depends_on)
+                        .await,
+                    session,
+                );
+            }
             Plan::CreateSink(plan) => {
                 self.sequence_create_sink(session, plan, depends_on, tx)
                     .await;
@@ -194,6 +212,13 @@ impl<S: Append + 'static> Coordinator<S> {
                     session,
                 );
             }
+            Plan::CreateFunction(plan) => {
+                tx.send(
+                    self.sequence_create_function(&session, plan, depends_on)
+                        .await,
+                    session,
+                );
+            }
             Plan::DropDatabase(plan) => {
                 tx.send(self.sequence_drop_database(&session, plan).await, session);
             }
@@ -283,6 +308,12 @@ impl<S: Append + 'static> Coordinator<S> {
                     session,
                 );
             }
+            Plan::CopyFromUrl(plan) => {
+                self.sequence_copy_from_url(tx, session, plan);
+            }
+            Plan::CopyToUrl(plan) => {
+                self.sequence_copy_to_url(tx, session, plan);
+            }
             Plan::Explain(plan) => {
                 tx.send(self.sequence_explain(&session, plan), session);
             }
@@ -307,6 +338,19 @@ impl<S: Append + 'static> Coordinator<S> {
                     session,
                 );
             }
+            Plan::AlterSchemaSwap(plan) => {
+                tx.send(
+                    self.sequence_alter_schema_swap(&session, plan).await,
+                    session,
+                );
+            }
+            Plan::AlterComputeInstanceSwap(plan) => {
+                tx.send(
+                    self.sequence_alter_compute_instance_swap(&session, plan)
+                        .await,
+                    session,
+                );
+            }
             Plan::AlterIndexSetOptions(plan) => {
                 tx.send(self.sequence_alter_index_set_options(plan).await, session);
             }
@@ -319,9 +363,25 @@ impl<S: Append + 'static> Coordinator<S> {
             Plan::AlterSink(plan) => {
                 tx.send(self.sequence_alter_sink(&session, plan).await, session);
             }
+            Plan::AlterSubscription(plan) => {
+                tx.send(
+                    self.sequence_alter_subscription(&session, plan).await,
+                    session,
+                );
+            }
             Plan::AlterSource(plan) => {
                 tx.send(self.sequence_alter_source(&session, plan).await, session);
             }
+            Plan::AlterSourceRefreshSchema(plan) => {
+                tx.send(
+                    self.sequence_alter_source_refresh_schema(&session, plan)
+                        .await,
+                    session,
+                );
+            }
+            Plan::AlterTable(plan) => {
+                self.sequence_alter_table(tx, session, plan).await;
+            }
             Plan::AlterSystemSet(plan) => {
                 tx.send(
                     self.sequence_alter_system_set(&session, plan).await,
@@ -441,6 +501,9 @@ impl<S: Append + 'static> Coordinator<S> {
             Plan::RotateKeys(RotateKeysPlan { id }) => {
                 tx.send(self.sequence_rotate_keys(&session, id).await, session);
             }
+            Plan::SignalBackend(plan) => {
+                tx.send(self.sequence_signal_backend(&session, plan), session);
+            }
         }
     }
 
@@ -563,6 +626,7 @@ impl<S: Append + 'static> Coordinator<S> {
                                 data_source,
                                 since: None,
                                 status_collection_id,
+                                fork_shard: None,
                             },
                         )])
                         .await
@@ -669,6 +733,54 @@ impl<S: Append + 'static> Coordinator<S> {
         }
     }
 
+    fn sequence_signal_backend(
+        &mut self,
+        session: &Session,
+        plan: SignalBackendPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let SignalBackendPlan { signal, mut pid } = plan;
+        prep_scalar_expr(
+            self.catalog.state(),
+            &mut pid,
+            ExprPrepStyle::OneShot {
+                logical_time: None,
+                session,
+            },
+        )?;
+        let conn_id = match pid.eval(&[], &RowArena::new())? {
+            Datum::Null => {
+                return Ok(send_immediate_rows(vec![Row::pack_slice(&[Datum::Null])]));
+            }
+            // Process IDs are connection IDs cast to `int4`; see
+            // `pg_backend_pid`.
+            pid => pid.unwrap_int32() as ConnectionId,
+        };
+
+        let signaled = match self.active_conns.get(&conn_id) {
+            None => false,
+            Some(conn_meta) => {
+                let target_user = conn_meta.activity.user();
+                if session.user() != &*SYSTEM_USER && session.user().name != target_user {
+                    let action = match signal {
+                        BackendSignal::Cancel => "query is being canceled",
+                        BackendSignal::Terminate => "process is being terminated",
+                    };
+                    return Err(AdapterError::Unauthorized(format!(
+                        "must be the user whose {action} or user '{}'",
+                        SYSTEM_USER.name,
+                    )));
+                }
+                match signal {
+                    BackendSignal::Cancel => self.cancel_connection(conn_id),
+                    BackendSignal::Terminate => self.terminate_connection(conn_id),
+                }
+                true
+            }
+        };
+        let row = Row::pack_slice(&[Datum::from(signaled)]);
+        Ok(send_immediate_rows(vec![row]))
+    }
+
     async fn sequence_create_database(
         &mut self,
         session: &mut Session,
@@ -900,6 +1012,7 @@ impl<S: Append + 'static> Coordinator<S> {
             instance_id,
             arranged_logs,
             self.catalog.system_config().max_result_size(),
+            self.memory_limits(),
         )?;
         for (replica_id, replica) in instance.replicas_by_id.clone() {
             self.controller
@@ -1078,6 +1191,43 @@ impl<S: Append + 'static> Coordinator<S> {
         Ok(ExecuteResponse::CreatedComputeReplica)
     }
 
+    /// Validates that a table described by `desc` can be created as a fork of
+    /// the shard `shard_id`.
+    ///
+    /// Forks refer to the blobs of the forked shard in place, so only the
+    /// shards of this environment's own tables can be forked, and only into a
+    /// table with the same column types.
+    fn validate_fork_shard(
+        &self,
+        shard_id: ShardId,
+        desc: &RelationDesc,
+    ) -> Result<(), AdapterError> {
+        let forked = self.catalog.user_tables().find(|entry| {
+            let collection = self.controller.storage.collection(entry.id()).unwrap();
+            collection.collection_metadata.data_shard == shard_id
+        });
+        let forked = match forked {
+            Some(forked) => forked,
+            None => {
+                return Err(AdapterError::Unstructured(anyhow!(
+                    "invalid FORK SHARD: {} is not the shard of a table",
+                    shard_id
+                )))
+            }
+        };
+        let forked_desc = match forked.item() {
+            CatalogItem::Table(table) => &table.desc,
+            _ => unreachable!("user_tables only returns tables"),
+        };
+        if forked_desc.typ().column_types != desc.typ().column_types {
+            return Err(AdapterError::Unstructured(anyhow!(
+                "invalid FORK SHARD: the columns of {} have different types",
+                self.catalog.resolve_full_name(forked.name(), None)
+            )));
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     async fn sequence_create_table(
         &mut self,
@@ -1091,6 +1241,22 @@ impl<S: Append + 'static> Coordinator<S> {
             if_not_exists,
         } = plan;
 
+        let fork_shard = match &table.fork_shard {
+            Some(fork_shard) => match fork_shard.parse::<ShardId>() {
+                Ok(shard_id) => {
+                    self.validate_fork_shard(shard_id, &table.desc)?;
+                    Some(shard_id)
+                }
+                Err(e) => {
+                    return Err(AdapterError::Unstructured(anyhow!(
+                        "invalid FORK SHARD: {}",
+                        e
+                    )))
+                }
+            },
+            None => None,
+        };
+
         let conn_id = if table.temporary {
             Some(session.conn_id())
         } else {
@@ -1116,7 +1282,10 @@ impl<S: Append + 'static> Coordinator<S> {
                 // Determine the initial validity for the table.
                 let since_ts = self.peek_local_write_ts();
 
-                let collection_desc = table.desc.clone().into();
+                let collection_desc = CollectionDescription {
+                    fork_shard,
+                    ..table.desc.clone().into()
+                };
                 self.controller
                     .storage
                     .create_collections(vec![(table_id, collection_desc)])
@@ -1204,6 +1373,96 @@ impl<S: Append + 'static> Coordinator<S> {
         }
     }
 
+    async fn sequence_create_subscription(
+        &mut self,
+        session: &mut Session,
+        plan: CreateSubscriptionPlan,
+        depends_on: Vec<GlobalId>,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let CreateSubscriptionPlan {
+            name,
+            subscription,
+            if_not_exists,
+        } = plan;
+
+        let compute_instance = self.catalog.active_compute_instance(session)?.id;
+        let id_bundle = CollectionIdBundle {
+            storage_ids: btreeset!(subscription.from),
+            compute_ids: BTreeMap::new(),
+        };
+
+        // Subscriptions start from the requested timestamp if there is one, or
+        // otherwise from the timestamp a `SUBSCRIBE` would choose right now.
+        let timestamp = match subscription.timestamp {
+            Some(timestamp) => {
+                let since = self.least_valid_read(&id_bundle);
+                if !since.less_equal(&timestamp) {
+                    return Err(AdapterError::Unstructured(anyhow!(
+                        "Timestamp ({}) is not valid for all inputs: {:?}",
+                        timestamp,
+                        since.elements()
+                    )));
+                }
+                timestamp
+            }
+            None => {
+                let timeline = self.validate_timeline(id_bundle.iter())?;
+                self.determine_timestamp(
+                    session,
+                    &id_bundle,
+                    &QueryWhen::Immediately,
+                    compute_instance,
+                    &timeline,
+                )?
+            }
+        };
+
+        let subscription = catalog::Subscription {
+            create_sql: catalog::subscription_create_sql(
+                &subscription.create_sql,
+                timestamp,
+                subscription.snapshot,
+            )?,
+            from: subscription.from,
+            timestamp,
+            snapshot: subscription.snapshot,
+            depends_on,
+        };
+        let id = self.catalog.allocate_user_id().await?;
+        let oid = self.catalog.allocate_oid()?;
+        let ops = vec![catalog::Op::CreateItem {
+            id,
+            oid,
+            name: name.clone(),
+            item: CatalogItem::Subscription(subscription.clone()),
+        }];
+
+        // Hold back the collection before the catalog transaction, so that it
+        // cannot compact past `timestamp` in the meantime.
+        let read_holds = self.acquire_read_holds(timestamp, id_bundle).await;
+
+        let result = self.catalog_transact(Some(session), ops, |_| Ok(())).await;
+        if result.is_ok() {
+            self.subscription_read_holds.insert(id, read_holds);
+        } else {
+            self.release_read_hold(&read_holds).await;
+        }
+        match result {
+            Ok(()) => Ok(ExecuteResponse::CreatedSubscription),
+            Err(AdapterError::Catalog(catalog::Error {
+                kind: catalog::ErrorKind::ItemAlreadyExists(_, _),
+                ..
+            })) if if_not_exists => {
+                session.add_notice(AdapterNotice::ObjectAlreadyExists {
+                    name: name.item,
+                    ty: "subscription",
+                });
+                Ok(ExecuteResponse::CreatedSubscription)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
     async fn sequence_create_sink(
         &mut self,
         mut session: Session,
@@ -1525,6 +1784,7 @@ impl<S: Append + 'static> Coordinator<S> {
                             data_source: DataSource::Other,
                             since: Some(as_of),
                             status_collection_id: None,
+                            fork_shard: None,
                         },
                     )])
                     .await
@@ -1646,6 +1906,38 @@ impl<S: Append + 'static> Coordinator<S> {
         }
     }
 
+    async fn sequence_create_function(
+        &mut self,
+        session: &Session,
+        plan: CreateFunctionPlan,
+        depends_on: Vec<GlobalId>,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let func = catalog::Func {
+            create_sql: Some(plan.function.create_sql),
+            inner: FuncDefinition::User(Arc::new(mz_sql::func::Func::user_defined(
+                plan.function.param_types,
+                plan.function.returns,
+                plan.function.body,
+            ))),
+            depends_on,
+        };
+        let id = self.catalog.allocate_user_id().await?;
+        let oid = self.catalog.allocate_oid()?;
+        let op = catalog::Op::CreateItem {
+            id,
+            oid,
+            name: plan.name,
+            item: CatalogItem::Func(func),
+        };
+        match self
+            .catalog_transact(Some(session), vec![op], |_| Ok(()))
+            .await
+        {
+            Ok(()) => Ok(ExecuteResponse::CreatedFunction),
+            Err(err) => Err(err),
+        }
+    }
+
     async fn sequence_drop_database(
         &mut self,
         session: &Session,
@@ -1809,12 +2101,30 @@ impl<S: Append + 'static> Coordinator<S> {
         instance_id: ComputeInstanceId,
         replica_id: ReplicaId,
     ) -> Result<(), anyhow::Error> {
+        let mut retractions = Vec::new();
         if let Some(Some(metadata)) = self.transient_replica_metadata.insert(replica_id, None) {
-            let retraction = self
-                .catalog
-                .state()
-                .pack_replica_heartbeat_update(replica_id, metadata, -1);
-            self.send_builtin_table_updates(vec![retraction], BuiltinTableUpdateSource::Background)
+            retractions.push(
+                self.catalog
+                    .state()
+                    .pack_replica_heartbeat_update(replica_id, metadata, -1),
+            );
+        }
+        let hydration_keys: Vec<_> = self
+            .compute_hydration_status
+            .keys()
+            .filter(|(_, id)| *id == replica_id)
+            .copied()
+            .collect();
+        for key in hydration_keys {
+            let hydrated = self.compute_hydration_status.remove(&key).unwrap();
+            retractions.push(
+                self.catalog
+                    .state()
+                    .pack_compute_hydration_status_update(key.0, key.1, hydrated, -1),
+            );
+        }
+        if !retractions.is_empty() {
+            self.send_builtin_table_updates(retractions, BuiltinTableUpdateSource::Background)
                 .await;
         }
         self.controller
@@ -1840,9 +2150,14 @@ impl<S: Append + 'static> Coordinator<S> {
             ObjectType::Sink => ExecuteResponse::DroppedSink,
             ObjectType::Index => ExecuteResponse::DroppedIndex,
             ObjectType::Type => ExecuteResponse::DroppedType,
+            ObjectType::Func => ExecuteResponse::DroppedFunction,
             ObjectType::Secret => ExecuteResponse::DroppedSecret,
+            ObjectType::Subscription => ExecuteResponse::DroppedSubscription,
             ObjectType::Connection => ExecuteResponse::DroppedConnection,
-            ObjectType::Role | ObjectType::Cluster | ObjectType::ClusterReplica => {
+            ObjectType::Role
+            | ObjectType::Cluster
+            | ObjectType::ClusterReplica
+            | ObjectType::Schema => {
                 unreachable!("handled through their respective sequence_drop functions")
             }
             ObjectType::Object => unreachable!("generic OBJECT cannot be dropped"),
@@ -1949,6 +2264,7 @@ impl<S: Append + 'static> Coordinator<S> {
                         session,
                         action,
                     },
+                    ddl: false,
                 });
                 return;
             }
@@ -1992,11 +2308,19 @@ impl<S: Append + 'static> Coordinator<S> {
         if let EndTransactionAction::Commit = action {
             if let (Some(mut ops), write_lock_guard) = txn.into_ops_and_lock_guard() {
                 if let TransactionOps::Writes(writes) = &mut ops {
-                    for WriteOp { id, .. } in &mut writes.iter() {
+                    for WriteOp { id, rows } in &mut writes.iter() {
                         // Re-verify this id exists.
-                        let _ = self.catalog.try_get_entry(id).ok_or_else(|| {
+                        let entry = self.catalog.try_get_entry(id).ok_or_else(|| {
                             AdapterError::SqlCatalog(CatalogError::UnknownItem(id.to_string()))
                         })?;
+                        // Re-verify that the table was not altered out from
+                        // under the transaction.
+                        if let Some(table) = entry.table() {
+                            let arity = table.desc.arity();
+                            if rows.iter().any(|(row, _)| row.iter().count() != arity) {
+                                return Err(AdapterError::ChangedPlan);
+                            }
+                        }
                     }
 
                     // `rows` can be empty if, say, a DELETE's WHERE clause had 0 results.
@@ -2209,7 +2533,11 @@ impl<S: Append + 'static> Coordinator<S> {
         );
 
         // Optimize the dataflow across views, and any other ways that appeal.
-        mz_transform::optimize_dataflow(&mut dataflow, &builder.index_oracle())?;
+        mz_transform::optimize_dataflow(
+            &mut dataflow,
+            &builder.index_oracle(),
+            builder.statistics,
+        )?;
 
         // At this point, `dataflow_plan` contains our best optimized dataflow.
         // We will check the plan to see if there is a fast path to escape full dataflow construction.
@@ -2290,12 +2618,9 @@ impl<S: Append + 'static> Coordinator<S> {
             session.add_transaction_ops(TransactionOps::Subscribe)?;
         }
 
-        let make_sink_desc = |coord: &mut Coordinator<S>, from, from_desc, uses| {
+        let make_sink_desc = |coord: &mut Coordinator<S>, from, from_desc, id_bundle| {
             // Determine the frontier of updates to subscribe *from*.
             // Updates greater or equal to this frontier will be produced.
-            let id_bundle = coord
-                .index_oracle(compute_instance_id)
-                .sufficient_collections(uses);
             let timeline = coord.validate_timeline(id_bundle.iter())?;
             // If a timestamp was explicitly requested, use that.
             let timestamp = coord.determine_timestamp(
@@ -2335,11 +2660,44 @@ impl<S: Append + 'static> Coordinator<S> {
                     .unwrap()
                     .into_owned();
                 let sink_id = self.catalog.allocate_user_id().await?;
-                let sink_desc = make_sink_desc(self, from_id, from_desc, &[from_id][..])?;
+                let id_bundle = self
+                    .index_oracle(compute_instance_id)
+                    .sufficient_collections(&[from_id]);
+                let sink_desc = make_sink_desc(self, from_id, from_desc, id_bundle)?;
                 let sink_name = format!("subscribe-{}", sink_id);
                 self.dataflow_builder(compute_instance_id)
                     .build_sink_dataflow(sink_name, sink_id, sink_desc)?
             }
+            SubscribeFrom::Subscription { id, from: from_id } => {
+                check_no_invalid_log_reads(
+                    &self.catalog,
+                    compute_instance,
+                    &btreeset!(from_id),
+                    LogReadStyle::Subscribe,
+                )?;
+                let from = self.catalog.get_entry(&from_id);
+                let from_desc = from
+                    .desc(
+                        &self
+                            .catalog
+                            .resolve_full_name(from.name(), Some(session.conn_id())),
+                    )
+                    .unwrap()
+                    .into_owned();
+                // Durable subscriptions only hold back the storage collection
+                // they read from, so resume directly from storage rather than
+                // from any indexes that may have compacted past the
+                // acknowledged timestamp.
+                let id_bundle = CollectionIdBundle {
+                    storage_ids: btreeset!(from_id),
+                    compute_ids: BTreeMap::new(),
+                };
+                let sink_desc = make_sink_desc(self, from_id, from_desc, id_bundle)?;
+                let sink_id = self.catalog.allocate_user_id().await?;
+                let sink_name = format!("subscribe-{}", id);
+                self.dataflow_builder(compute_instance_id)
+                    .build_storage_sink_dataflow(sink_name, sink_id, sink_desc)?
+            }
             SubscribeFrom::Query { expr, desc } => {
                 check_no_invalid_log_reads(
                     &self.catalog,
@@ -2350,7 +2708,10 @@ impl<S: Append + 'static> Coordinator<S> {
                 let id = self.allocate_transient_id()?;
                 let expr = self.view_optimizer.optimize(expr)?;
                 let desc = RelationDesc::new(expr.typ(), desc.iter_names());
-                let sink_desc = make_sink_desc(self, id, desc, &depends_on)?;
+                let id_bundle = self
+                    .index_oracle(compute_instance_id)
+                    .sufficient_collections(&depends_on);
+                let sink_desc = make_sink_desc(self, id, desc, id_bundle)?;
                 let mut dataflow = DataflowDesc::new(format!("subscribe-{}", id));
                 let mut dataflow_builder = self.dataflow_builder(compute_instance_id);
                 dataflow_builder.import_view_into_dataflow(&id, &expr, &mut dataflow)?;
@@ -2455,6 +2816,7 @@ impl<S: Append + 'static> Coordinator<S> {
                 mz_transform::optimize_dataflow(
                     &mut dataflow,
                     &self.index_oracle(compute_instance),
+                    &self.collection_statistics,
                 )?;
 
                 let used_indexes = dataflow
@@ -2690,7 +3052,10 @@ impl<S: Append + 'static> Coordinator<S> {
                 }
             }
 
-            usize::try_from(affected_rows).expect("positive isize must fit")
+            match plan.affected_rows {
+                Some(affected_rows) => affected_rows,
+                None => usize::try_from(affected_rows).expect("positive isize must fit"),
+            }
         };
         event!(
             Level::TRACE,
@@ -2724,6 +3089,8 @@ impl<S: Append + 'static> Coordinator<S> {
             MutationKind::Delete => ExecuteResponse::Deleted(affected_rows),
             MutationKind::Insert => ExecuteResponse::Inserted(affected_rows),
             MutationKind::Update => ExecuteResponse::Updated(affected_rows / 2),
+            MutationKind::Upsert => ExecuteResponse::Inserted(affected_rows),
+            MutationKind::Merge => ExecuteResponse::Merged(affected_rows),
         })
     }
 
@@ -2839,6 +3206,7 @@ impl<S: Append + 'static> Coordinator<S> {
                     updates: rows,
                     kind: MutationKind::Insert,
                     returning: Vec::new(),
+                    affected_rows: None,
                 };
                 self.sequence_send_diffs(session, diffs_plan)
             }
@@ -2863,6 +3231,239 @@ impl<S: Append + 'static> Coordinator<S> {
         self.sequence_insert_constant(session, id, values.into_inner())
     }
 
+    /// Reads the objects of a `COPY ... FROM` URL in a background task, then
+    /// inserts their rows when [`Message::CopyFromUrlReady`] is handled.
+    fn sequence_copy_from_url(
+        &mut self,
+        tx: ClientTransmitter<ExecuteResponse>,
+        session: Session,
+        plan: CopyFromUrlPlan,
+    ) {
+        let CopyFromUrlPlan {
+            id,
+            columns,
+            url,
+            format,
+        } = plan;
+
+        let entry = self.catalog.get_entry(&id);
+        let table_desc = entry
+            .desc(
+                &self
+                    .catalog
+                    .resolve_full_name(entry.name(), Some(session.conn_id())),
+            )
+            .expect("desc called on table")
+            .into_owned();
+        let desc = RelationDesc::from_names_and_types(columns.iter().map(|i| {
+            (
+                table_desc.get_name(*i).clone(),
+                table_desc.typ().column_types[*i].clone(),
+            )
+        }));
+
+        let max_size = self.catalog.system_config().max_result_size();
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let connection_context = self.connection_context.clone();
+        task::spawn(|| format!("sequence_copy_from_url:{id}"), async move {
+            let rows = copy::read_objects(&url, &connection_context, desc, format, max_size)
+                .await
+                .map_err(AdapterError::Unstructured);
+            // It is not an error for the objects to be read after `internal_cmd_rx` is dropped.
+            let result = internal_cmd_tx.send(Message::CopyFromUrlReady(CopyFromUrlReady {
+                session,
+                tx,
+                id,
+                columns,
+                rows,
+            }));
+            if let Err(e) = result {
+                warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+            }
+        });
+    }
+
+    /// Checks in a background task that no objects exist under the URL of a
+    /// `COPY ... TO`, so that the objects it writes are not mixed up with those
+    /// of an earlier copy, then installs its dataflow when
+    /// [`Message::CopyToUrlReady`] is handled.
+    fn sequence_copy_to_url(
+        &mut self,
+        tx: ClientTransmitter<ExecuteResponse>,
+        session: Session,
+        plan: CopyToUrlPlan,
+    ) {
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let connection_context = self.connection_context.clone();
+        task::spawn(|| "sequence_copy_to_url", async move {
+            let result = copy::check_no_objects(&plan.url, &connection_context)
+                .await
+                .map_err(AdapterError::Unstructured);
+            // It is not an error for the check to complete after `internal_cmd_rx` is dropped.
+            let result = internal_cmd_tx.send(Message::CopyToUrlReady(CopyToUrlReady {
+                session,
+                tx,
+                plan,
+                result,
+            }));
+            if let Err(e) = result {
+                warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+            }
+        });
+    }
+
+    /// Installs a dataflow whose sink writes the results of the query of a
+    /// `COPY ... TO` URL to S3 from the cluster, then responds once the sink
+    /// reports the outcome of the copy.
+    pub(super) async fn sequence_copy_to_url_ready(
+        &mut self,
+        tx: ClientTransmitter<ExecuteResponse>,
+        mut session: Session,
+        plan: CopyToUrlPlan,
+    ) {
+        let (sink_id, rx) = match self.install_copy_to_url(&mut session, plan).await {
+            Ok(res) => res,
+            Err(e) => {
+                tx.send(Err(e), session);
+                return;
+            }
+        };
+
+        let timeout_dur = *session.vars().statement_timeout();
+        task::spawn(
+            || format!("sequence_copy_to_url_ready:{sink_id}"),
+            async move {
+                let result = match tokio::time::timeout(timeout_dur, rx).await {
+                    Ok(Ok(CopyToResponse::RowCount(count))) => {
+                        Ok(ExecuteResponse::Copied(usize::cast_from(count)))
+                    }
+                    Ok(Ok(CopyToResponse::Error(e))) => Err(AdapterError::Unstructured(anyhow!(e))),
+                    Ok(Ok(CopyToResponse::Dropped)) | Ok(Err(_)) => Ok(ExecuteResponse::Canceled),
+                    // The sink is dropped when the session's transaction ends.
+                    Err(_) => Err(AdapterError::StatementTimeout),
+                };
+                tx.send(result, session);
+            },
+        );
+    }
+
+    /// Ships the dataflow of a `COPY ... TO` URL, returning the ID of its sink
+    /// and a channel on which the sink's response is delivered.
+    async fn install_copy_to_url(
+        &mut self,
+        session: &mut Session,
+        plan: CopyToUrlPlan,
+    ) -> Result<(GlobalId, oneshot::Receiver<CopyToResponse>), AdapterError> {
+        let CopyToUrlPlan {
+            expr,
+            desc,
+            when,
+            url,
+            format,
+            header,
+            max_file_size,
+        } = plan;
+
+        let compute_instance = self.catalog.active_compute_instance(session)?;
+
+        // Every replica runs the dataflow, but only the target replica writes
+        // the objects.
+        let target_replica_name = session.vars().cluster_replica();
+        let mut target_replica = target_replica_name
+            .map(|name| {
+                compute_instance
+                    .replica_id_by_name
+                    .get(name)
+                    .copied()
+                    .ok_or(AdapterError::UnknownClusterReplica {
+                        cluster_name: compute_instance.name.clone(),
+                        replica_name: name.to_string(),
+                    })
+            })
+            .transpose()?;
+
+        let first_replica = match compute_instance.replicas_by_id.keys().next() {
+            Some(replica_id) => *replica_id,
+            None => {
+                return Err(AdapterError::NoClusterReplicasAvailable(
+                    compute_instance.name.clone(),
+                ))
+            }
+        };
+
+        let source_ids = expr.depends_on();
+        check_no_invalid_log_reads(
+            &self.catalog,
+            compute_instance,
+            &source_ids,
+            LogReadStyle::Peek(&mut target_replica),
+        )?;
+        let target_replica = target_replica.unwrap_or(first_replica);
+        let compute_instance_id = compute_instance.id;
+
+        let id = self.allocate_transient_id()?;
+        let expr = self.view_optimizer.optimize(expr)?;
+        let desc = RelationDesc::new(expr.typ(), desc.iter_names());
+        let id_bundle = self
+            .index_oracle(compute_instance_id)
+            .sufficient_collections(&source_ids);
+        let timeline = self.validate_timeline(id_bundle.iter())?;
+        let timestamp =
+            self.determine_timestamp(session, &id_bundle, &when, compute_instance_id, &timeline)?;
+
+        let format = match format {
+            CopyFormat::Text => CopyToS3Format::Text,
+            CopyFormat::Csv => CopyToS3Format::Csv { header },
+            CopyFormat::Parquet => CopyToS3Format::Parquet,
+            CopyFormat::Binary => unreachable!("planning rejects COPY TO a URL in binary format"),
+        };
+        let sink_desc = ComputeSinkDesc {
+            from: id,
+            from_desc: desc.clone(),
+            connection: ComputeSinkConnection::CopyToS3(CopyToS3SinkConnection {
+                value_desc: desc,
+                bucket: url.bucket,
+                prefix: url.prefix,
+                aws_connection_id: url.connection_id,
+                aws_connection: url.connection,
+                format,
+                max_file_size,
+                target_replica,
+                // Set by the controller for each replica.
+                upload: false,
+            }),
+            as_of: SinkAsOf {
+                frontier: Antichain::from_elem(timestamp),
+                strict: false,
+            },
+        };
+        let mut dataflow = DataflowDesc::new(format!("copy-to-{}", id));
+        let mut dataflow_builder = self.dataflow_builder(compute_instance_id);
+        dataflow_builder.import_view_into_dataflow(&id, &expr, &mut dataflow)?;
+        dataflow_builder.build_sink_dataflow_into(&mut dataflow, id, sink_desc)?;
+        // The sink only writes the results at `timestamp`, so the dataflow
+        // needn't compute any later updates.
+        if let Some(next) = timestamp.checked_add(1) {
+            dataflow.until = Antichain::from_elem(next);
+        }
+
+        session.add_drop_sink(ComputeSinkId {
+            compute_instance: compute_instance_id,
+            global_id: id,
+        });
+        let (sender, rx) = oneshot::channel();
+        self.pending_copy_tos.insert(
+            id,
+            PendingCopyTo {
+                sender,
+                conn_id: session.conn_id(),
+            },
+        );
+        self.ship_dataflow(dataflow, compute_instance_id).await;
+
+        Ok((id, rx))
+    }
+
     // ReadThenWrite is a plan whose writes depend on the results of a
     // read. This works by doing a Peek then queuing a SendDiffs. No writes
     // or read-then-writes can occur between the Peek and SendDiff otherwise a
@@ -2975,6 +3576,7 @@ impl<S: Append + 'static> Coordinator<S> {
         let strict_serializable_reads_tx = self.strict_serializable_reads_tx.clone();
         task::spawn(|| format!("sequence_read_then_write:{id}"), async move {
             let arena = RowArena::new();
+            let mut affected_rows = None;
             let diffs = match peek_response {
                 ExecuteResponse::SendingRows {
                     future: batch,
@@ -2987,11 +3589,46 @@ impl<S: Append + 'static> Coordinator<S> {
                     match tokio::time::timeout(timeout_dur, batch).await {
                         Ok(res) => match res {
                             PeekResponseUnary::Rows(rows) => {
+                                // Each row of an upsert or merge selection
+                                // describes exactly one affected row.
+                                if matches!(kind, MutationKind::Upsert | MutationKind::Merge) {
+                                    affected_rows = Some(rows.len());
+                                }
                                 |rows: Vec<Row>| -> Result<Vec<(Row, Diff)>, AdapterError> {
                                     // Use 2x row len incase there's some assignments.
                                     let mut diffs = Vec::with_capacity(rows.len() * 2);
                                     let mut datum_vec = mz_repr::DatumVec::new();
+                                    let arity = desc.arity();
                                     for row in rows {
+                                        if matches!(
+                                            kind,
+                                            MutationKind::Upsert | MutationKind::Merge
+                                        ) {
+                                            // Rows are laid out as `[old row..., new row...,
+                                            // has_old, has_new]`, where null flags indicate
+                                            // that some row would be affected twice.
+                                            let datums = datum_vec.borrow_with(&row);
+                                            if datums[2 * arity] == Datum::Null {
+                                                let command = match kind {
+                                                    MutationKind::Upsert => "ON CONFLICT",
+                                                    _ => "MERGE",
+                                                };
+                                                return Err(AdapterError::Unstructured(anyhow!(
+                                                    "{command} command cannot affect row a second time"
+                                                )));
+                                            }
+                                            if datums[2 * arity] == Datum::True {
+                                                diffs.push((Row::pack_slice(&datums[..arity]), -1));
+                                            }
+                                            if datums[2 * arity + 1] == Datum::True {
+                                                let new = &datums[arity..2 * arity];
+                                                for (i, datum) in new.iter().enumerate() {
+                                                    desc.constraints_met(i, datum)?;
+                                                }
+                                                diffs.push((Row::pack_slice(new), 1));
+                                            }
+                                            continue;
+                                        }
                                         if !assignments.is_empty() {
                                             assert!(
                                                 matches!(kind, MutationKind::Update),
@@ -3025,6 +3662,9 @@ impl<S: Append + 'static> Coordinator<S> {
                                                 diffs.push((row, -1))
                                             }
                                             MutationKind::Insert => diffs.push((row, 1)),
+                                            MutationKind::Upsert | MutationKind::Merge => {
+                                                unreachable!("handled above")
+                                            }
                                         }
                                     }
                                     Ok(diffs)
@@ -3140,6 +3780,7 @@ impl<S: Append + 'static> Coordinator<S> {
                 diffs,
                 kind,
                 returning: returning_rows,
+                affected_rows,
             }));
             if let Err(e) = result {
                 warn!("internal_cmd_rx dropped before we could send: {:?}", e);
@@ -3166,6 +3807,35 @@ impl<S: Append + 'static> Coordinator<S> {
         }
     }
 
+    async fn sequence_alter_schema_swap(
+        &mut self,
+        session: &Session,
+        plan: AlterSchemaSwapPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let op = catalog::Op::SwapSchemas {
+            database_id: plan.database_id,
+            schema_a: plan.schema_a,
+            schema_b: plan.schema_b,
+        };
+        self.catalog_transact(Some(session), vec![op], |_| Ok(()))
+            .await?;
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Schema))
+    }
+
+    async fn sequence_alter_compute_instance_swap(
+        &mut self,
+        session: &Session,
+        plan: AlterComputeInstanceSwapPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let op = catalog::Op::SwapComputeInstances {
+            name_a: plan.name_a,
+            name_b: plan.name_b,
+        };
+        self.catalog_transact(Some(session), vec![op], |_| Ok(()))
+            .await?;
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Cluster))
+    }
+
     async fn sequence_alter_index_set_options(
         &mut self,
         plan: AlterIndexSetOptionsPlan,
@@ -3256,6 +3926,25 @@ impl<S: Append + 'static> Coordinator<S> {
         Ok(ExecuteResponse::AlteredObject(ObjectType::Sink))
     }
 
+    async fn sequence_alter_subscription(
+        &mut self,
+        session: &Session,
+        AlterSubscriptionPlan { id, timestamp }: AlterSubscriptionPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let op = catalog::Op::AlterSubscription { id, timestamp };
+        self.catalog_transact(Some(session), vec![op], |_| Ok(()))
+            .await?;
+
+        // Everything before the acknowledged timestamp has been consumed, so
+        // allow the underlying collection to compact up to it.
+        if let Some(read_holds) = self.subscription_read_holds.remove(&id) {
+            let read_holds = self.update_read_hold(read_holds, timestamp).await;
+            self.subscription_read_holds.insert(id, read_holds);
+        }
+
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Subscription))
+    }
+
     async fn sequence_alter_source(
         &mut self,
         session: &Session,
@@ -3282,6 +3971,281 @@ impl<S: Append + 'static> Coordinator<S> {
         Ok(ExecuteResponse::AlteredObject(ObjectType::Source))
     }
 
+    async fn sequence_alter_source_refresh_schema(
+        &mut self,
+        session: &Session,
+        AlterSourceRefreshSchemaPlan { id, source }: AlterSourceRefreshSchemaPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let old_ingestion = match self.catalog.get_entry(&id).source() {
+            Some(catalog::Source {
+                data_source: DataSourceDesc::Ingestion(ingestion),
+                ..
+            }) => ingestion.desc.clone(),
+            _ => coord_bail!("source {id} does not ingest data from an external system"),
+        };
+
+        let op = catalog::Op::AlterSourceSchema { id, source };
+        self.catalog_transact(Some(session), vec![op], |_| Ok(()))
+            .await?;
+
+        // Re-fetch the updated item from the catalog
+        let entry = self.catalog.get_entry(&id);
+        let updated_source = entry.source().ok_or_else(|| {
+            CatalogError::UnexpectedType(entry.name().to_string(), CatalogItemType::Source)
+        })?;
+        let ingestion = match &updated_source.data_source {
+            DataSourceDesc::Ingestion(ingestion) => ingestion.clone(),
+            DataSourceDesc::Introspection(_) | DataSourceDesc::Source => {
+                unreachable!("checked above")
+            }
+        };
+        // Refreshing to the schemas the source already uses is a no-op.
+        if ingestion.desc == old_ingestion {
+            return Ok(ExecuteResponse::AlteredObject(ObjectType::Source));
+        }
+
+        let source_imports = ingestion
+            .source_imports
+            .into_iter()
+            .map(|source_import| (source_import, ()))
+            .collect();
+        let mut source_exports = BTreeMap::new();
+        // By convention the first output corresponds to the main source object
+        source_exports.insert(
+            id,
+            SourceExport {
+                output_index: 0,
+                storage_metadata: (),
+            },
+        );
+        for (subsource, output_index) in ingestion.subsource_exports {
+            let export = SourceExport {
+                output_index,
+                storage_metadata: (),
+            };
+            source_exports.insert(subsource, export);
+        }
+        let data_source = DataSource::Ingestion(IngestionDescription {
+            desc: ingestion.desc,
+            ingestion_metadata: (),
+            source_imports,
+            source_exports,
+            host_config: ingestion.host_config,
+        });
+        self.controller
+            .storage
+            .alter_ingestion_desc(
+                id,
+                CollectionDescription {
+                    desc: updated_source.desc.clone(),
+                    data_source,
+                    since: None,
+                    status_collection_id: None,
+                    fork_shard: None,
+                },
+            )
+            .await?;
+
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Source))
+    }
+
+    async fn sequence_alter_table(
+        &mut self,
+        tx: ClientTransmitter<ExecuteResponse>,
+        mut session: Session,
+        plan: AlterTablePlan,
+    ) {
+        // The table's existing contents are rewritten, so no other writes may
+        // be in flight.
+        guard_write_critical_section!(self, tx, session, Plan::AlterTable(plan));
+
+        let writes = match self.sequence_alter_table_inner(&session, plan).await {
+            Ok(writes) => writes,
+            Err(e) => {
+                tx.send(Err(e), session);
+                return;
+            }
+        };
+        // The rewritten rows are written like any other write to a user table,
+        // but the group commit is applied immediately, so that no query can
+        // read the table between the catalog change and the rewrite.
+        // `ALTER TABLE` must run singly, so committing the session's
+        // transaction here is equivalent to committing it afterwards.
+        let write_lock_guard = session.take_write_lock();
+        self.pending_writes.push(PendingWriteTxn::User {
+            writes,
+            write_lock_guard,
+            pending_txn: PendingTxn {
+                client_transmitter: tx,
+                response: Ok(ExecuteResponse::AlteredObject(ObjectType::Table)),
+                session,
+                action: EndTransactionAction::Commit,
+            },
+            ddl: true,
+        });
+        self.group_commit_initiate(None).await;
+    }
+
+    /// Alters the table in the catalog and returns the writes that rewrite
+    /// its existing contents.
+    async fn sequence_alter_table_inner(
+        &mut self,
+        session: &Session,
+        AlterTablePlan { id, table, action }: AlterTablePlan,
+    ) -> Result<Vec<WriteOp>, AdapterError> {
+        // ALTER TABLE can be deferred while waiting for the write lock, so
+        // re-verify that the table still has the shape the plan expects.
+        let old_table = match self.catalog.try_get_entry(&id).and_then(|e| e.table()) {
+            Some(old_table) => old_table.clone(),
+            None => {
+                return Err(AdapterError::SqlCatalog(CatalogError::UnknownItem(
+                    id.to_string(),
+                )))
+            }
+        };
+        let unchanged = match &action {
+            AlterTablePlanAction::AddColumn { .. } => {
+                old_table.desc.arity() + 1 == table.desc.arity()
+                    && old_table
+                        .desc
+                        .iter()
+                        .eq(table.desc.iter().take(old_table.desc.arity()))
+            }
+            AlterTablePlanAction::DropColumn { index } => {
+                old_table.desc.arity() == table.desc.arity() + 1
+                    && old_table
+                        .desc
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| i != index)
+                        .map(|(_, column)| column)
+                        .eq(table.desc.iter())
+            }
+        };
+        if !unchanged {
+            return Err(AdapterError::ChangedPlan);
+        }
+        // Readers of the table expect its old shape, and the rewrite below
+        // retracts all of its rows, so the table must not be read while it is
+        // altered.
+        if self.table_has_readers(session.conn_id(), id) {
+            let entry = self.catalog.get_entry(&id);
+            let name = self
+                .catalog
+                .resolve_full_name(entry.name(), Some(session.conn_id()));
+            return Err(AdapterError::TableInUse(name.to_string()));
+        }
+
+        // Compute the rewrite of every row before touching the catalog, so
+        // that a failure (e.g., a NOT NULL violation) leaves the table as is.
+        let rewrite: Box<dyn Fn(&Row) -> Result<Row, AdapterError>> = match action {
+            AlterTablePlanAction::AddColumn { mut default } => {
+                prep_scalar_expr(
+                    self.catalog.state(),
+                    &mut default,
+                    ExprPrepStyle::OneShot {
+                        logical_time: None,
+                        session,
+                    },
+                )?;
+                let temp_storage = RowArena::new();
+                let datum = default.eval(&[], &temp_storage)?;
+                let index = table.desc.arity() - 1;
+                table.desc.constraints_met(index, &datum)?;
+                let mut value = Row::default();
+                value.packer().push(datum);
+                Box::new(move |row| {
+                    let mut new_row = Row::default();
+                    let mut packer = new_row.packer();
+                    packer.extend_by_row(row);
+                    packer.extend_by_row(&value);
+                    Ok(new_row)
+                })
+            }
+            AlterTablePlanAction::DropColumn { index } => Box::new(move |row| {
+                let mut new_row = Row::default();
+                new_row.packer().extend(
+                    row.iter()
+                        .enumerate()
+                        .filter(|(i, _)| *i != index)
+                        .map(|(_, datum)| datum),
+                );
+                Ok(new_row)
+            }),
+        };
+
+        let read_ts = self.get_local_read_ts();
+        let current_contents = self.controller.storage.snapshot(id, read_ts).await?;
+        let mut rows = Vec::with_capacity(current_contents.len() * 2);
+        for (row, diff) in current_contents {
+            rows.push((rewrite(&row)?, diff));
+            rows.push((row, -diff));
+        }
+        // Writes that were queued before we took the write lock have not yet
+        // been applied, so rewrite them in place.
+        let mut queued_writes = vec![];
+        for pending_write in &self.pending_writes {
+            if let PendingWriteTxn::User { writes, .. } = pending_write {
+                for WriteOp { id: write_id, rows } in writes {
+                    if *write_id == id {
+                        let rows = rows
+                            .iter()
+                            .map(|(row, diff)| Ok((rewrite(row)?, *diff)))
+                            .collect::<Result<Vec<_>, AdapterError>>()?;
+                        queued_writes.push(rows);
+                    }
+                }
+            }
+        }
+
+        let table = catalog::Table {
+            create_sql: table.create_sql,
+            desc: table.desc,
+            defaults: table.defaults,
+            conn_id: old_table.conn_id,
+            depends_on: old_table.depends_on,
+        };
+        let op = catalog::Op::AlterTable { id, table };
+        self.catalog_transact(Some(session), vec![op], |_| Ok(()))
+            .await?;
+
+        let mut queued_writes = queued_writes.into_iter();
+        for pending_write in &mut self.pending_writes {
+            if let PendingWriteTxn::User { writes, .. } = pending_write {
+                for WriteOp { id: write_id, rows } in writes.iter_mut() {
+                    if *write_id == id {
+                        *rows = queued_writes.next().expect("rewritten above");
+                    }
+                }
+            }
+        }
+
+        Ok(vec![WriteOp { id, rows }])
+    }
+
+    /// Reports whether any dataflow, including those of pending peeks,
+    /// `SUBSCRIBE`s, and `COPY ... TO`s, or any transaction or durable
+    /// subscription other than that of `conn_id` reads from the table `id`.
+    fn table_has_readers(&self, conn_id: ConnectionId, id: GlobalId) -> bool {
+        let dataflow_readers = self.catalog.compute_instances().any(|instance| {
+            self.controller
+                .compute
+                .instance_ref(instance.id)
+                .unwrap()
+                .storage_dependents(id)
+                .next()
+                .is_some()
+        });
+        let txn_readers = self.txn_reads.iter().any(|(txn_conn_id, txn_reads)| {
+            *txn_conn_id != conn_id && txn_reads.read_holds.id_bundle().storage_ids.contains(&id)
+        });
+        let subscription_readers = self
+            .subscription_read_holds
+            .values()
+            .any(|read_holds| read_holds.id_bundle().storage_ids.contains(&id));
+        dataflow_readers || txn_readers || subscription_readers
+    }
+
     fn extract_secret(
         &mut self,
         session: &Session,
@@ -3340,6 +4304,8 @@ impl<S: Append + 'static> Coordinator<S> {
         self.is_user_allowed_to_alter_system(session)?;
         use mz_sql::ast::{SetVariableValue, Value};
         let update_max_result_size = name == session::vars::MAX_RESULT_SIZE.name();
+        let update_memory_limits = name == session::vars::MAX_TRANSIENT_DATAFLOW_MEMORY.name()
+            || name == session::vars::MAX_REPLICA_MEMORY.name();
         let op = match value {
             SetVariableValue::Default => catalog::Op::ResetSystemConfiguration { name },
             SetVariableValue::Literal(Value::String(value)) => {
@@ -3359,6 +4325,9 @@ impl<S: Append + 'static> Coordinator<S> {
         if update_max_result_size {
             self.update_max_result_size();
         }
+        if update_memory_limits {
+            self.update_memory_limits();
+        }
         Ok(ExecuteResponse::AlteredSystemConfiguraion)
     }
 
@@ -3369,12 +4338,17 @@ impl<S: Append + 'static> Coordinator<S> {
     ) -> Result<ExecuteResponse, AdapterError> {
         self.is_user_allowed_to_alter_system(session)?;
         let update_max_result_size = name == session::vars::MAX_RESULT_SIZE.name();
+        let update_memory_limits = name == session::vars::MAX_TRANSIENT_DATAFLOW_MEMORY.name()
+            || name == session::vars::MAX_REPLICA_MEMORY.name();
         let op = catalog::Op::ResetSystemConfiguration { name };
         self.catalog_transact(Some(session), vec![op], |_| Ok(()))
             .await?;
         if update_max_result_size {
             self.update_max_result_size();
         }
+        if update_memory_limits {
+            self.update_memory_limits();
+        }
         Ok(ExecuteResponse::AlteredSystemConfiguraion)
     }
 
@@ -3388,6 +4362,7 @@ impl<S: Append + 'static> Coordinator<S> {
         self.catalog_transact(Some(session), vec![op], |_| Ok(()))
             .await?;
         self.update_max_result_size();
+        self.update_memory_limits();
         Ok(ExecuteResponse::AlteredSystemConfiguraion)
     }
 
@@ -3414,6 +4389,26 @@ impl<S: Append + 'static> Coordinator<S> {
         }
     }
 
+    fn update_memory_limits(&mut self) {
+        let memory_limits = self.memory_limits();
+        let mut compute = self.controller.active_compute();
+        for compute_instance in self.catalog.compute_instances() {
+            compute
+                .update_memory_limits(compute_instance.id, memory_limits)
+                .unwrap();
+        }
+    }
+
+    /// Returns the limits on the memory used by dataflows, as configured by
+    /// system parameters.
+    pub(crate) fn memory_limits(&self) -> MemoryLimits {
+        let system_config = self.catalog.system_config();
+        MemoryLimits {
+            max_transient_dataflow_bytes: system_config.max_transient_dataflow_memory(),
+            max_replica_bytes: system_config.max_replica_memory(),
+        }
+    }
+
     // Returns the name of the portal to execute.
     fn sequence_execute(
         &mut self,
@@ -3484,11 +4479,13 @@ impl<S: Append + 'static> Coordinator<S> {
             | Plan::CreateSource(_)
             | Plan::CreateSecret(_)
             | Plan::CreateSink(_)
+            | Plan::CreateSubscription(_)
             | Plan::CreateTable(_)
             | Plan::CreateView(_)
             | Plan::CreateMaterializedView(_)
             | Plan::CreateIndex(_)
             | Plan::CreateType(_)
+            | Plan::CreateFunction(_)
             | Plan::DiscardTemp
             | Plan::DiscardAll
             | Plan::DropDatabase(_)
@@ -3504,14 +4501,22 @@ impl<S: Append + 'static> Coordinator<S> {
             | Plan::AlterIndexResetOptions(_)
             | Plan::AlterSink(_)
             | Plan::AlterSource(_)
+            | Plan::AlterSourceRefreshSchema(_)
+            | Plan::AlterSubscription(_)
+            | Plan::AlterTable(_)
             | Plan::AlterItemRename(_)
+            | Plan::AlterSchemaSwap(_)
+            | Plan::AlterComputeInstanceSwap(_)
             | Plan::AlterSecret(_)
             | Plan::AlterSystemSet(_)
             | Plan::AlterSystemReset(_)
             | Plan::AlterSystemResetAll(_)
             | Plan::ReadThenWrite(_)
+            | Plan::CopyFromUrl(_)
+            | Plan::CopyToUrl(_)
             | Plan::Raise(_)
-            | Plan::RotateKeys(_) => {
+            | Plan::RotateKeys(_)
+            | Plan::SignalBackend(_) => {
                 return Err(AdapterError::Unauthorized(
                     "user 'mz_introspection' is unauthorized to perform this action".into(),
                 ))
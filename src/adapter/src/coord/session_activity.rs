@@ -0,0 +1,187 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Reporting of the activity of client sessions in `mz_internal.mz_sessions`.
+//!
+//! The coordinator tracks the activity of each connection alongside its other
+//! connection metadata. A session becomes active when it begins to execute a
+//! statement and becomes idle once the response to the statement has been
+//! transmitted. Rather than writing to `mz_sessions` on every change, the
+//! coordinator publishes the sessions whose activity changed at each group
+//! commit.
+
+use mz_ore::now::{to_datetime, EpochMillis};
+use mz_repr::{Datum, Row};
+use mz_sql::ast::{Raw, Statement};
+use mz_stash::Append;
+
+use crate::catalog::builtin::MZ_SESSIONS;
+use crate::catalog::BuiltinTableUpdate;
+use crate::client::ConnectionId;
+use crate::coord::appends::{BuiltinTableUpdateSource, PendingWriteTxn};
+use crate::coord::statement_logging;
+use crate::coord::Coordinator;
+use crate::session::{Session, TransactionStatus};
+
+/// The state of a session, as reported by `mz_sessions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionState {
+    /// The session is executing a statement.
+    Active,
+    /// The session is waiting for a statement outside of a transaction.
+    Idle,
+    /// The session is waiting for a statement inside of a transaction.
+    IdleInTransaction,
+    /// The session is waiting for a statement inside of a failed transaction.
+    IdleInTransactionAborted,
+}
+
+impl SessionState {
+    /// Returns the state of an idle session whose transaction is `txn`.
+    pub fn idle<T>(txn: &TransactionStatus<T>) -> SessionState {
+        match txn {
+            TransactionStatus::InTransaction(_) => SessionState::IdleInTransaction,
+            TransactionStatus::Failed(_) => SessionState::IdleInTransactionAborted,
+            TransactionStatus::Default
+            | TransactionStatus::Started(_)
+            | TransactionStatus::InTransactionImplicit(_) => SessionState::Idle,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            SessionState::Active => "active",
+            SessionState::Idle => "idle",
+            SessionState::IdleInTransaction => "idle in transaction",
+            SessionState::IdleInTransactionAborted => "idle in transaction (aborted)",
+        }
+    }
+}
+
+/// The activity of a session.
+#[derive(Debug)]
+pub(crate) struct SessionActivity {
+    user: String,
+    database: String,
+    application_name: String,
+    connected_at: EpochMillis,
+    state: SessionState,
+    /// The most recent statement executed by the session.
+    query: Option<String>,
+    query_started_at: Option<EpochMillis>,
+    /// The row of `mz_sessions` last published for the session, if any.
+    published: Option<Row>,
+}
+
+impl SessionActivity {
+    /// Returns the activity of `session`, which connected at `connected_at`.
+    pub(crate) fn new(session: &Session, connected_at: EpochMillis) -> SessionActivity {
+        SessionActivity {
+            user: session.user().name.clone(),
+            database: session.vars().database().into(),
+            application_name: session.vars().application_name().into(),
+            connected_at,
+            state: SessionState::Idle,
+            query: None,
+            query_started_at: None,
+            published: None,
+        }
+    }
+
+    /// Returns the name of the user of the session.
+    pub(crate) fn user(&self) -> &str {
+        &self.user
+    }
+
+    fn pack(&self, conn_id: ConnectionId) -> Row {
+        let timestamp_datum =
+            |t: EpochMillis| Datum::TimestampTz(to_datetime(t).try_into().expect("must fit"));
+        Row::pack_slice(&[
+            Datum::UInt32(conn_id),
+            Datum::String(&self.user),
+            Datum::String(&self.database),
+            Datum::String(&self.application_name),
+            timestamp_datum(self.connected_at),
+            Datum::String(self.state.as_str()),
+            match &self.query {
+                Some(query) => Datum::String(query),
+                None => Datum::Null,
+            },
+            match self.query_started_at {
+                Some(query_started_at) => timestamp_datum(query_started_at),
+                None => Datum::Null,
+            },
+        ])
+    }
+}
+
+impl<S: Append + 'static> Coordinator<S> {
+    /// Records that the connection of `session` began to execute `stmt`.
+    pub(crate) fn mark_session_active(&mut self, session: &Session, stmt: &Statement<Raw>) {
+        let now = self.now();
+        if let Some(conn_meta) = self.active_conns.get_mut(&session.conn_id()) {
+            let activity = &mut conn_meta.activity;
+            activity.database = session.vars().database().into();
+            activity.application_name = session.vars().application_name().into();
+            activity.state = SessionState::Active;
+            activity.query = Some(statement_logging::redacted_text(stmt));
+            activity.query_started_at = Some(now);
+        }
+    }
+
+    /// Records that connection `conn_id` finished executing its statement.
+    pub(crate) fn mark_session_idle(&mut self, conn_id: ConnectionId, state: SessionState) {
+        if let Some(conn_meta) = self.active_conns.get_mut(&conn_id) {
+            conn_meta.activity.state = state;
+        }
+    }
+
+    /// Queues the writes to `mz_sessions` for the sessions whose activity
+    /// changed since it was last published.
+    pub(crate) fn publish_session_activity(&mut self) {
+        let id = self.catalog.resolve_builtin_table(&MZ_SESSIONS);
+        let mut updates = vec![];
+        for (conn_id, conn_meta) in &mut self.active_conns {
+            let activity = &mut conn_meta.activity;
+            let row = activity.pack(*conn_id);
+            if activity.published.as_ref() == Some(&row) {
+                continue;
+            }
+            if let Some(published) = activity.published.replace(row.clone()) {
+                updates.push(BuiltinTableUpdate {
+                    id,
+                    row: published,
+                    diff: -1,
+                });
+            }
+            updates.push(BuiltinTableUpdate { id, row, diff: 1 });
+        }
+        self.pending_writes
+            .extend(updates.into_iter().map(|update| PendingWriteTxn::System {
+                update,
+                source: BuiltinTableUpdateSource::Background,
+            }));
+    }
+
+    /// Queues the retraction of the published activity of a session whose
+    /// connection has terminated.
+    pub(crate) fn retract_session_activity(&mut self, activity: SessionActivity) {
+        if let Some(row) = activity.published {
+            let update = BuiltinTableUpdate {
+                id: self.catalog.resolve_builtin_table(&MZ_SESSIONS),
+                row,
+                diff: -1,
+            };
+            self.pending_writes.push(PendingWriteTxn::System {
+                update,
+                source: BuiltinTableUpdateSource::Background,
+            });
+        }
+    }
+}
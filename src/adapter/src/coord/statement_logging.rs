@@ -0,0 +1,186 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Logging of executed statements to `mz_internal.mz_statement_log`.
+//!
+//! A sampled fraction of the statements executed by clients, as determined by
+//! the `statement_logging_sample_rate` system variable, are logged. The
+//! [`ClientTransmitter`] that carries a statement's response also carries its
+//! [`StatementLog`], which is completed once the outcome of the statement is
+//! known and then sent back to the coordinator as a row of `mz_statement_log`.
+//! This may happen after the response was transmitted, e.g., when the response
+//! delivers its rows through a future. The coordinator buffers these rows and
+//! periodically appends them to the storage-managed collection backing
+//! `mz_statement_log`, so that they survive restarts.
+//!
+//! [`ClientTransmitter`]: crate::util::ClientTransmitter
+
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use tokio::sync::mpsc::UnboundedSender;
+use uuid::Uuid;
+
+use mz_ore::now::{to_datetime, EpochMillis};
+use mz_ore::task;
+use mz_repr::adt::interval::Interval;
+use mz_repr::{Datum, Row, Timestamp};
+use mz_sql::ast::{Expr, Raw, Statement, Value};
+use mz_sql_parser::ast::display::AstDisplay;
+use mz_stash::Append;
+use mz_storage_client::controller::IntrospectionType;
+
+use crate::client::ConnectionId;
+use crate::coord::{Coordinator, Message};
+use crate::session::Session;
+
+/// The interval at which logged statements are appended to `mz_statement_log`.
+const STATEMENT_LOG_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The record of a single execution of a statement.
+#[derive(Debug)]
+pub struct StatementLog {
+    id: Uuid,
+    session_id: ConnectionId,
+    user: String,
+    application_name: String,
+    cluster: String,
+    sql: String,
+    began_at: EpochMillis,
+    began: Instant,
+    planned: Option<Instant>,
+    timestamp: Option<Timestamp>,
+    internal_cmd_tx: UnboundedSender<Message>,
+}
+
+impl StatementLog {
+    /// Marks the end of planning, if it was not already marked.
+    pub fn mark_planned(&mut self) {
+        self.planned.get_or_insert_with(Instant::now);
+    }
+
+    /// Records the timestamp the statement was executed at.
+    pub fn set_timestamp(&mut self, timestamp: Option<Timestamp>) {
+        self.timestamp = timestamp;
+    }
+
+    /// Completes the log with the outcome of the statement and sends it to
+    /// the coordinator.
+    ///
+    /// `rows` is the number of rows returned by the statement or, for writes,
+    /// affected by it.
+    pub fn complete(self, rows: Option<usize>, error: Option<String>) {
+        let finished = Instant::now();
+        let planned = self.planned.unwrap_or(finished);
+        let row = Row::pack_slice(&[
+            Datum::Uuid(self.id),
+            Datum::UInt32(self.session_id),
+            Datum::String(&self.user),
+            Datum::String(&self.application_name),
+            Datum::String(&self.cluster),
+            Datum::String(&self.sql),
+            Datum::TimestampTz(to_datetime(self.began_at).try_into().expect("must fit")),
+            match self.timestamp {
+                Some(timestamp) => Datum::MzTimestamp(timestamp),
+                None => Datum::Null,
+            },
+            duration_datum(planned.saturating_duration_since(self.began)),
+            duration_datum(finished.saturating_duration_since(planned)),
+            match rows {
+                Some(rows) => Datum::Int64(i64::try_from(rows).expect("must fit")),
+                None => Datum::Null,
+            },
+            match &error {
+                Some(error) => Datum::String(error),
+                None => Datum::Null,
+            },
+        ]);
+        // It is not an error for a statement to complete after the coordinator
+        // has shut down.
+        let _ = self.internal_cmd_tx.send(Message::StatementLogged(row));
+    }
+}
+
+fn duration_datum<'a>(duration: Duration) -> Datum<'a> {
+    let micros = i64::try_from(duration.as_micros()).expect("must fit");
+    Datum::Interval(Interval::new(0, 0, micros))
+}
+
+/// Returns the text of `stmt` as it is recorded in the statement log and in
+/// `mz_sessions`, with the values of secrets redacted.
+pub(crate) fn redacted_text(stmt: &Statement<Raw>) -> String {
+    let redacted = || Expr::Value(Value::String("<REDACTED>".into()));
+    match stmt {
+        Statement::CreateSecret(stmt) => {
+            let mut stmt = stmt.clone();
+            stmt.value = redacted();
+            stmt.to_ast_string()
+        }
+        Statement::AlterSecret(stmt) => {
+            let mut stmt = stmt.clone();
+            stmt.value = redacted();
+            stmt.to_ast_string()
+        }
+        stmt => stmt.to_ast_string(),
+    }
+}
+
+impl<S: Append + 'static> Coordinator<S> {
+    /// Begins the log of an execution of `stmt` by `session`, if the execution
+    /// is sampled.
+    pub(crate) fn begin_statement_log(
+        &self,
+        session: &Session,
+        stmt: &Statement<Raw>,
+    ) -> Option<StatementLog> {
+        let sample_rate = self.catalog.system_config().statement_logging_sample_rate();
+        if rand::thread_rng().gen::<f64>() >= sample_rate {
+            return None;
+        }
+        Some(StatementLog {
+            id: Uuid::new_v4(),
+            session_id: session.conn_id(),
+            user: session.user().name.clone(),
+            application_name: session.vars().application_name().into(),
+            cluster: session.vars().cluster().into(),
+            sql: redacted_text(stmt),
+            began_at: self.now(),
+            began: Instant::now(),
+            planned: None,
+            timestamp: None,
+            internal_cmd_tx: self.internal_cmd_tx.clone(),
+        })
+    }
+
+    /// Buffers the row of a completed statement log until the next flush.
+    pub(crate) fn buffer_statement_log(&mut self, row: Row) {
+        if self.statement_log_buffer.is_empty() {
+            let internal_cmd_tx = self.internal_cmd_tx.clone();
+            task::spawn(|| "statement_log_flush", async move {
+                tokio::time::sleep(STATEMENT_LOG_FLUSH_INTERVAL).await;
+                // If sending fails, the main thread has shutdown.
+                let _ = internal_cmd_tx.send(Message::StatementLogFlush);
+            });
+        }
+        self.statement_log_buffer.push(row);
+    }
+
+    /// Appends the buffered statement logs to `mz_statement_log`.
+    pub(crate) async fn flush_statement_log(&mut self) {
+        let updates = self
+            .statement_log_buffer
+            .drain(..)
+            .map(|row| (row, 1))
+            .collect();
+        self.controller
+            .storage
+            .append_introspection_updates(IntrospectionType::StatementLog, updates)
+            .await;
+    }
+}
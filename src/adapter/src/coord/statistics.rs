@@ -0,0 +1,147 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Periodic collection of statistics about the contents of storage
+//! collections, for use by the optimizer.
+//!
+//! Statistics are gathered in the background by the storage controller, which
+//! extrapolates them from a sample of the parts of each user collection. Only
+//! a bounded number of collections are sampled at once, and collections that
+//! have not been written to since they were last sampled are skipped. The most
+//! recent statistics for each collection are cached by the coordinator and
+//! presented to the optimizer as a [`StatisticsOracle`].
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use futures::stream::{self, StreamExt};
+use timely::progress::Antichain;
+use tracing::warn;
+
+use mz_ore::task;
+use mz_repr::stats::CollectionStatistics;
+use mz_repr::{GlobalId, Timestamp};
+use mz_stash::Append;
+use mz_storage_client::controller::StorageError;
+use mz_transform::StatisticsOracle;
+
+use crate::coord::{Coordinator, Message};
+
+/// The interval at which statistics about storage collections are refreshed.
+pub const COLLECTION_STATISTICS_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// The maximum number of collections whose statistics are collected at once.
+const COLLECTION_STATISTICS_CONCURRENCY: usize = 4;
+
+/// The most recently collected statistics about storage collections, along
+/// with the write frontier of each collection when they were collected.
+pub type CollectionStatisticsMap = HashMap<GlobalId, (CollectionStatistics, Antichain<Timestamp>)>;
+
+/// The most recently collected statistics about storage collections.
+#[derive(Debug, Default)]
+pub struct CollectionStatisticsCache {
+    statistics: CollectionStatisticsMap,
+}
+
+impl StatisticsOracle for CollectionStatisticsCache {
+    fn statistics(&self, id: GlobalId) -> Option<&CollectionStatistics> {
+        self.statistics.get(&id).map(|(statistics, _)| statistics)
+    }
+}
+
+impl<S: Append + 'static> Coordinator<S> {
+    /// Requests statistics about all user storage collections from the storage
+    /// controller, and arranges for them to be delivered back to the
+    /// coordinator via [`Message::CollectionStatisticsUpdate`].
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn collection_statistics_fetch(&mut self) {
+        let ids: Vec<_> = self
+            .catalog
+            .entries()
+            .filter(|entry| {
+                entry.id().is_user()
+                    && (entry.is_table() || entry.is_source() || entry.is_materialized_view())
+            })
+            .map(|entry| entry.id())
+            .collect();
+
+        // Statistics of collections that have not been written to since they
+        // were last collected are still accurate, and are carried over.
+        let mut statistics = HashMap::new();
+        let mut pending = Vec::new();
+        for id in ids {
+            let (as_of, upper) = match self.controller.storage.collection(id) {
+                Ok(collection) => match collection.implied_capability.as_option() {
+                    // Only collections that are readable at their since can
+                    // be summarized.
+                    Some(as_of) if !collection.write_frontier.less_equal(as_of) => {
+                        (*as_of, collection.write_frontier.clone())
+                    }
+                    _ => continue,
+                },
+                Err(_) => continue,
+            };
+            match self.collection_statistics.statistics.get(&id) {
+                Some((stats, prev_upper)) if *prev_upper == upper => {
+                    statistics.insert(id, (stats.clone(), upper));
+                    continue;
+                }
+                _ => {}
+            }
+            match self.controller.storage.collection_statistics(id, as_of) {
+                Ok(future) => pending.push(async move { (id, upper, future.await) }),
+                Err(e) => warn!("unable to collect statistics for {id}: {e}"),
+            }
+        }
+
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "collection_statistics_fetch", async move {
+            let mut results =
+                stream::iter(pending).buffer_unordered(COLLECTION_STATISTICS_CONCURRENCY);
+            while let Some((id, upper, result)) = results.next().await {
+                match result {
+                    Ok(stats) => {
+                        statistics.insert(id, (stats, upper));
+                    }
+                    // The collection was dropped concurrently.
+                    Err(StorageError::IdentifierMissing(_)) => {}
+                    Err(e) => warn!("unable to collect statistics for {id}: {e}"),
+                }
+            }
+            // It is not an error for statistics to become ready after
+            // `internal_cmd_rx` is dropped.
+            let result = internal_cmd_tx.send(Message::CollectionStatisticsUpdate(statistics));
+            if let Err(e) = result {
+                warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+            }
+        });
+    }
+
+    /// Replaces the cached statistics with freshly collected ones, and
+    /// schedules the next collection.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn collection_statistics_update(&mut self, statistics: CollectionStatisticsMap) {
+        self.collection_statistics.statistics = statistics;
+        self.schedule_collection_statistics_collection(COLLECTION_STATISTICS_INTERVAL);
+    }
+
+    /// Schedules a collection of statistics to happen after `delay`.
+    pub(crate) fn schedule_collection_statistics_collection(&self, delay: Duration) {
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "collection_statistics_collection", async move {
+            tokio::time::sleep(delay).await;
+            if internal_cmd_tx
+                .send(Message::CollectionStatisticsFetch)
+                .is_err()
+            {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+    }
+}
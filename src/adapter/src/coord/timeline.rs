@@ -480,6 +480,7 @@ impl<S: Append + 'static> Coordinator<S> {
                         | CatalogItem::Func(_)
                         | CatalogItem::Secret(_)
                         | CatalogItem::Connection(_)
+                        | CatalogItem::Subscription(_)
                         | CatalogItem::Log(_) => {}
                     }
                 }
@@ -565,6 +566,9 @@ impl<S: Append + 'static> Coordinator<S> {
                     CatalogItem::Table(table) => {
                         timelines.insert(id, table.timeline());
                     }
+                    CatalogItem::Subscription(subscription) => {
+                        ids.push(subscription.from);
+                    }
                     CatalogItem::Log(_) => {
                         timelines.insert(id, Timeline::EpochMilliseconds);
                     }
@@ -173,6 +173,9 @@ pub enum AdapterError {
         object_type: String,
         unstable_dependencies: Vec<String>,
     },
+    /// Attempted to alter a table while queries, subscriptions, or transactions
+    /// read from it.
+    TableInUse(String),
     /// Attempted to read from log sources without selecting a target replica.
     UntargetedLogRead {
         log_names: Vec<String>,
@@ -293,6 +296,11 @@ impl AdapterError {
                  selection, use `RESET cluster_replica`."
                     .into(),
             ),
+            AdapterError::TableInUse(_) => Some(
+                "Wait for the queries, SUBSCRIBEs, and transactions that read from the table \
+                 to finish, or cancel them."
+                    .into(),
+            ),
             AdapterError::PlanError(e) => e.hint(),
             _ => None,
         }
@@ -459,6 +467,13 @@ impl fmt::Display for AdapterError {
             AdapterError::UnstableDependency { object_type, .. } => {
                 write!(f, "cannot create {object_type} with unstable dependencies")
             }
+            AdapterError::TableInUse(name) => {
+                write!(
+                    f,
+                    "cannot alter table {} because it is being read",
+                    name.quoted()
+                )
+            }
             AdapterError::UntargetedLogRead { .. } => {
                 f.write_str("log source reads must target a replica")
             }
@@ -343,6 +343,11 @@ impl<'a> DisplayText for Displayable<'a, HirScalarExpr> {
                         Displayable::from(scalar.expr.as_ref()).fmt_text(f, ctx)?;
                         write!(f, ")")?
                     }
+                    WindowExprType::Aggregate(scalar) => {
+                        write!(f, "{}(", scalar.aggregate_expr.func.clone().into_expr())?;
+                        Displayable::from(scalar.aggregate_expr.expr.as_ref()).fmt_text(f, ctx)?;
+                        write!(f, ")")?
+                    }
                 }
                 write!(f, " over (")?;
                 for (i, e) in expr.partition.iter().enumerate() {
@@ -584,6 +584,14 @@ impl<T: TimestampManipulation> Session<T> {
             Some(txn) => txn.write_lock_guard.is_some(),
         }
     }
+
+    /// Takes the coordinator's write lock guard from this session's inner
+    /// transaction, if it holds it.
+    pub fn take_write_lock(&mut self) -> Option<OwnedMutexGuard<()>> {
+        self.transaction
+            .inner_mut()
+            .and_then(|txn| txn.write_lock_guard.take())
+    }
 }
 
 /// A prepared statement.
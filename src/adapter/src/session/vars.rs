@@ -259,6 +259,24 @@ pub const MAX_RESULT_SIZE: ServerVar<u32> = ServerVar {
     description: "The maximum size in bytes for a single query's result (Materialize).",
 };
 
+pub const MAX_TRANSIENT_DATAFLOW_MEMORY: ServerVar<u64> = ServerVar {
+    name: UncasedStr::new("max_transient_dataflow_memory"),
+    value: &0,
+    description: "The maximum size in bytes of the arrangements of a single query, or 0 for no limit (Materialize).",
+};
+
+pub const MAX_REPLICA_MEMORY: ServerVar<u64> = ServerVar {
+    name: UncasedStr::new("max_replica_memory"),
+    value: &0,
+    description: "The maximum size in bytes of the arrangements of a cluster replica before queries are canceled, or 0 for no limit (Materialize).",
+};
+
+pub const STATEMENT_LOGGING_SAMPLE_RATE: ServerVar<f64> = ServerVar {
+    name: UncasedStr::new("statement_logging_sample_rate"),
+    value: &0.0,
+    description: "The fraction of statements to record in mz_internal.mz_statement_log, between 0.0 and 1.0 (Materialize).",
+};
+
 static DEFAULT_ALLOWED_CLUSTER_REPLICA_SIZES: Lazy<Vec<String>> = Lazy::new(Vec::new);
 static ALLOWED_CLUSTER_REPLICA_SIZES: Lazy<ServerVar<Vec<String>>> = Lazy::new(|| ServerVar {
     name: UncasedStr::new("allowed_cluster_replica_sizes"),
@@ -798,6 +816,9 @@ pub struct SystemVars {
     max_secrets: SystemVar<u32>,
     max_roles: SystemVar<u32>,
     max_result_size: SystemVar<u32>,
+    max_transient_dataflow_memory: SystemVar<u64>,
+    max_replica_memory: SystemVar<u64>,
+    statement_logging_sample_rate: SystemVar<f64>,
     allowed_cluster_replica_sizes: SystemVar<Vec<String>>, // TODO: BTreeSet<String> will be better
 }
 
@@ -817,6 +838,9 @@ impl Default for SystemVars {
             max_secrets: SystemVar::new(&MAX_SECRETS),
             max_roles: SystemVar::new(&MAX_ROLES),
             max_result_size: SystemVar::new(&MAX_RESULT_SIZE),
+            max_transient_dataflow_memory: SystemVar::new(&MAX_TRANSIENT_DATAFLOW_MEMORY),
+            max_replica_memory: SystemVar::new(&MAX_REPLICA_MEMORY),
+            statement_logging_sample_rate: SystemVar::new(&STATEMENT_LOGGING_SAMPLE_RATE),
             allowed_cluster_replica_sizes: SystemVar::new(&ALLOWED_CLUSTER_REPLICA_SIZES),
         }
     }
@@ -840,6 +864,9 @@ impl SystemVars {
             &self.max_secrets,
             &self.max_roles,
             &self.max_result_size,
+            &self.max_transient_dataflow_memory,
+            &self.max_replica_memory,
+            &self.statement_logging_sample_rate,
             &self.allowed_cluster_replica_sizes,
         ]
         .into_iter()
@@ -882,6 +909,12 @@ impl SystemVars {
             Ok(&self.max_roles)
         } else if name == MAX_RESULT_SIZE.name {
             Ok(&self.max_result_size)
+        } else if name == MAX_TRANSIENT_DATAFLOW_MEMORY.name {
+            Ok(&self.max_transient_dataflow_memory)
+        } else if name == MAX_REPLICA_MEMORY.name {
+            Ok(&self.max_replica_memory)
+        } else if name == STATEMENT_LOGGING_SAMPLE_RATE.name {
+            Ok(&self.statement_logging_sample_rate)
         } else if name == ALLOWED_CLUSTER_REPLICA_SIZES.name {
             Ok(&self.allowed_cluster_replica_sizes)
         } else {
@@ -923,6 +956,12 @@ impl SystemVars {
             self.max_roles.set(value)
         } else if name == MAX_RESULT_SIZE.name {
             self.max_result_size.set(value)
+        } else if name == MAX_TRANSIENT_DATAFLOW_MEMORY.name {
+            self.max_transient_dataflow_memory.set(value)
+        } else if name == MAX_REPLICA_MEMORY.name {
+            self.max_replica_memory.set(value)
+        } else if name == STATEMENT_LOGGING_SAMPLE_RATE.name {
+            self.statement_logging_sample_rate.set(value)
         } else if name == ALLOWED_CLUSTER_REPLICA_SIZES.name {
             self.allowed_cluster_replica_sizes.set(value)
         } else {
@@ -962,6 +1001,12 @@ impl SystemVars {
             self.max_roles.reset()
         } else if name == MAX_RESULT_SIZE.name {
             self.max_result_size.reset()
+        } else if name == MAX_TRANSIENT_DATAFLOW_MEMORY.name {
+            self.max_transient_dataflow_memory.reset()
+        } else if name == MAX_REPLICA_MEMORY.name {
+            self.max_replica_memory.reset()
+        } else if name == STATEMENT_LOGGING_SAMPLE_RATE.name {
+            self.statement_logging_sample_rate.reset()
         } else if name == ALLOWED_CLUSTER_REPLICA_SIZES.name {
             self.allowed_cluster_replica_sizes.reset()
         } else {
@@ -1035,6 +1080,21 @@ impl SystemVars {
         *self.max_result_size.value()
     }
 
+    /// Returns the value of the `max_transient_dataflow_memory` configuration parameter.
+    pub fn max_transient_dataflow_memory(&self) -> u64 {
+        *self.max_transient_dataflow_memory.value()
+    }
+
+    /// Returns the value of the `max_replica_memory` configuration parameter.
+    pub fn max_replica_memory(&self) -> u64 {
+        *self.max_replica_memory.value()
+    }
+
+    /// Returns the value of the `statement_logging_sample_rate` configuration parameter.
+    pub fn statement_logging_sample_rate(&self) -> f64 {
+        *self.statement_logging_sample_rate.value()
+    }
+
     /// Returns the value of the `allowed_cluster_replica_sizes` configuration parameter.
     pub fn allowed_cluster_replica_sizes(&self) -> &Vec<String> {
         self.allowed_cluster_replica_sizes.value()
@@ -1314,6 +1374,35 @@ impl Value for u32 {
     }
 }
 
+impl Value for u64 {
+    const TYPE_NAME: &'static str = "unsigned integer";
+
+    fn parse(s: &str) -> Result<u64, ()> {
+        s.parse().map_err(|_| ())
+    }
+
+    fn format(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Value for f64 {
+    const TYPE_NAME: &'static str = "double precision";
+
+    /// Parses a fraction between 0.0 and 1.0, inclusive, which is what all
+    /// floating point parameters, e.g. `statement_logging_sample_rate`, are.
+    fn parse(s: &str) -> Result<f64, ()> {
+        match s.parse::<f64>() {
+            Ok(f) if (0.0..=1.0).contains(&f) => Ok(f),
+            _ => Err(()),
+        }
+    }
+
+    fn format(&self) -> String {
+        self.to_string()
+    }
+}
+
 const SEC_TO_MIN: u64 = 60u64;
 const SEC_TO_HOUR: u64 = 60u64 * 60;
 const SEC_TO_DAY: u64 = 60u64 * 60 * 24;
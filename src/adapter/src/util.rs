@@ -26,6 +26,8 @@ use mz_stash::{Append, StashError};
 
 use crate::catalog::Catalog;
 use crate::command::{Command, Response};
+use crate::coord::session_activity::SessionState;
+use crate::coord::statement_logging::StatementLog;
 use crate::coord::Message;
 use crate::error::AdapterError;
 use crate::session::{EndTransactionAction, Session};
@@ -39,6 +41,12 @@ pub struct ClientTransmitter<T: Transmittable> {
     /// Expresses an optional [`soft_assert`] on the set of values allowed to be
     /// sent from `self`.
     allowed: Option<Vec<T::Allowed>>,
+    /// The log of the statement whose response is sent from `self`, if the
+    /// statement is logged.
+    statement_log: Option<StatementLog>,
+    /// Whether to report to the coordinator that the session is idle when
+    /// sending the response.
+    report_idle: bool,
 }
 
 impl<T: Transmittable> ClientTransmitter<T> {
@@ -51,6 +59,8 @@ impl<T: Transmittable> ClientTransmitter<T> {
             tx: Some(tx),
             internal_cmd_tx,
             allowed: None,
+            statement_log: None,
+            report_idle: false,
         }
     }
 
@@ -60,7 +70,7 @@ impl<T: Transmittable> ClientTransmitter<T> {
     /// # Panics
     /// - If in `soft_assert`, `result.is_ok()`, `self.allowed.is_some()`, and
     ///   the result value is not in the set of allowed values.
-    pub fn send(mut self, result: Result<T, AdapterError>, session: Session) {
+    pub fn send(mut self, mut result: Result<T, AdapterError>, session: Session) {
         // Guarantee that the value sent is of an allowed type.
         soft_assert!(
             match (&result, self.allowed.take()) {
@@ -71,6 +81,24 @@ impl<T: Transmittable> ClientTransmitter<T> {
             see ClientTransmitter::set_allowed"
         );
 
+        if let Some(mut log) = self.statement_log.take() {
+            log.set_timestamp(session.get_transaction_timestamp());
+            match &mut result {
+                Ok(t) => t.complete_statement_log(log),
+                Err(e) => log.complete(None, Some(e.to_string())),
+            }
+        }
+
+        if self.report_idle {
+            // Report before sending the response, so that the coordinator
+            // learns of the idle session before any subsequent command from
+            // the client.
+            let _ = self.internal_cmd_tx.send(Message::SessionIdle {
+                conn_id: session.conn_id(),
+                state: SessionState::idle(session.transaction()),
+            });
+        }
+
         // If we were not able to send a message, we must clean up the session
         // ourselves. Return it to the caller for disposal.
         if let Err(res) = self.tx.take().unwrap().send(Response { result, session }) {
@@ -92,6 +120,25 @@ impl<T: Transmittable> ClientTransmitter<T> {
     pub fn set_allowed(&mut self, allowed: Vec<T::Allowed>) {
         self.allowed = Some(allowed);
     }
+
+    /// Sets `self` so that the next call to [`Self::send`] completes `log`
+    /// with the outcome of the statement.
+    pub fn set_statement_log(&mut self, log: Option<StatementLog>) {
+        self.statement_log = log;
+    }
+
+    /// Sets `self` so that [`Self::send`] reports to the coordinator that the
+    /// session is idle, as reflected in `mz_sessions`.
+    pub fn set_report_idle(&mut self) {
+        self.report_idle = true;
+    }
+
+    /// Marks the end of planning in the log of the statement, if any.
+    pub fn mark_statement_planned(&mut self) {
+        if let Some(log) = &mut self.statement_log {
+            log.mark_planned();
+        }
+    }
 }
 
 /// A helper trait for [`ClientTransmitter`].
@@ -108,6 +155,14 @@ pub trait Transmittable {
     ///   trait for `bool`, and return `true`. However, it might not be
     ///   semantically appropriate to expose `From<&Self> for bool`.
     fn to_allowed(&self) -> Self::Allowed;
+
+    /// Completes `log` with the outcome of the statement that produced `self`.
+    ///
+    /// Implementations that deliver results after being transmitted should
+    /// complete `log` once those results are available.
+    fn complete_statement_log(&mut self, log: StatementLog) {
+        log.complete(None, None);
+    }
 }
 
 /// `ClientTransmitter` with a response to send.
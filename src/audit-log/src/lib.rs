@@ -109,6 +109,7 @@ pub enum ObjectType {
     Schema,
     Sink,
     Source,
+    Subscription,
     Table,
     Type,
     View,
@@ -129,6 +130,7 @@ impl ObjectType {
             ObjectType::Secret => "Secret",
             ObjectType::Sink => "Sink",
             ObjectType::Source => "Source",
+            ObjectType::Subscription => "Subscription",
             ObjectType::Table => "Table",
             ObjectType::Type => "Type",
             ObjectType::View => "View",
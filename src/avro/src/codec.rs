@@ -47,6 +47,17 @@ pub enum Codec {
     /// compression library. Each compressed block is followed by the 4-byte, big-endian
     /// CRC32 checksum of the uncompressed data in the block.
     Snappy,
+    #[cfg(feature = "zstandard")]
+    /// The `Zstandard` codec uses Facebook's [Zstandard](https://facebook.github.io/zstd/)
+    /// compression library.
+    Zstandard,
+    #[cfg(feature = "bzip")]
+    /// The `Bzip2` codec uses [BZip2](https://sourceware.org/bzip2/) compression library.
+    Bzip2,
+    #[cfg(feature = "xz")]
+    /// The `Xz` codec uses the [xz](https://tukaani.org/xz/) compression library,
+    /// which implements the LZMA2 algorithm.
+    Xz,
 }
 
 impl ToAvro for Codec {
@@ -57,6 +68,12 @@ impl ToAvro for Codec {
                 Codec::Deflate => "deflate",
                 #[cfg(feature = "snappy")]
                 Codec::Snappy => "snappy",
+                #[cfg(feature = "zstandard")]
+                Codec::Zstandard => "zstandard",
+                #[cfg(feature = "bzip")]
+                Codec::Bzip2 => "bzip2",
+                #[cfg(feature = "xz")]
+                Codec::Xz => "xz",
             }
             .to_owned()
             .into_bytes(),
@@ -73,6 +90,12 @@ impl FromStr for Codec {
             "deflate" => Ok(Codec::Deflate),
             #[cfg(feature = "snappy")]
             "snappy" => Ok(Codec::Snappy),
+            #[cfg(feature = "zstandard")]
+            "zstandard" => Ok(Codec::Zstandard),
+            #[cfg(feature = "bzip")]
+            "bzip2" => Ok(Codec::Bzip2),
+            #[cfg(feature = "xz")]
+            "xz" => Ok(Codec::Xz),
             other => Err(DecodeError::UnrecognizedCodec(other.to_string()).into()),
         }
     }
@@ -106,6 +129,23 @@ impl Codec {
 
                 *stream = encoded;
             }
+            #[cfg(feature = "zstandard")]
+            Codec::Zstandard => {
+                *stream = zstd::stream::encode_all(&stream[..], 0)?;
+            }
+            #[cfg(feature = "bzip")]
+            Codec::Bzip2 => {
+                let mut encoder =
+                    bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::default());
+                encoder.write_all(stream)?;
+                *stream = encoder.finish()?;
+            }
+            #[cfg(feature = "xz")]
+            Codec::Xz => {
+                let mut encoder = xz2::write::XzEncoder::new(Vec::new(), 6);
+                encoder.write_all(stream)?;
+                *stream = encoder.finish()?;
+            }
         };
 
         Ok(())
@@ -150,6 +190,22 @@ impl Codec {
                 }
                 *stream = decoded;
             }
+            #[cfg(feature = "zstandard")]
+            Codec::Zstandard => {
+                *stream = zstd::stream::decode_all(&stream[..])?;
+            }
+            #[cfg(feature = "bzip")]
+            Codec::Bzip2 => {
+                let mut decoded = Vec::new();
+                bzip2::read::BzDecoder::new(&stream[..]).read_to_end(&mut decoded)?;
+                *stream = decoded;
+            }
+            #[cfg(feature = "xz")]
+            Codec::Xz => {
+                let mut decoded = Vec::new();
+                xz2::read::XzDecoder::new(&stream[..]).read_to_end(&mut decoded)?;
+                *stream = decoded;
+            }
         };
 
         Ok(())
@@ -194,4 +250,40 @@ mod tests {
         codec.decompress(&mut stream).unwrap();
         assert_eq!(INPUT, stream.as_slice());
     }
+
+    #[cfg(feature = "zstandard")]
+    #[test]
+    fn zstandard_compress_and_decompress() {
+        let codec = Codec::Zstandard;
+        let mut stream = INPUT.to_vec();
+        codec.compress(&mut stream).unwrap();
+        assert_ne!(INPUT, stream.as_slice());
+        assert!(INPUT.len() > stream.len());
+        codec.decompress(&mut stream).unwrap();
+        assert_eq!(INPUT, stream.as_slice());
+    }
+
+    #[cfg(feature = "bzip")]
+    #[test]
+    fn bzip2_compress_and_decompress() {
+        let codec = Codec::Bzip2;
+        let mut stream = INPUT.to_vec();
+        codec.compress(&mut stream).unwrap();
+        assert_ne!(INPUT, stream.as_slice());
+        assert!(INPUT.len() > stream.len());
+        codec.decompress(&mut stream).unwrap();
+        assert_eq!(INPUT, stream.as_slice());
+    }
+
+    #[cfg(feature = "xz")]
+    #[test]
+    fn xz_compress_and_decompress() {
+        let codec = Codec::Xz;
+        let mut stream = INPUT.to_vec();
+        codec.compress(&mut stream).unwrap();
+        assert_ne!(INPUT, stream.as_slice());
+        assert!(INPUT.len() > stream.len());
+        codec.decompress(&mut stream).unwrap();
+        assert_eq!(INPUT, stream.as_slice());
+    }
 }
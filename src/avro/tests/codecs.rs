@@ -0,0 +1,72 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Round trip tests for the codecs that are behind features, which this test
+//! target requires.
+
+use std::str::FromStr;
+
+use mz_avro::types::Value;
+use mz_avro::{Codec, Reader, Schema, Writer};
+
+/// Writes records to an object container file compressed with `codec` and
+/// checks that reading the file yields the same records.
+fn test_codec_round_trip(codec: Codec) {
+    let schema = Schema::from_str(
+        r#"{
+            "type": "record",
+            "name": "test",
+            "fields": [
+                {"name": "a", "type": "long"},
+                {"name": "b", "type": "string"}
+            ]
+        }"#,
+    )
+    .unwrap();
+    let values: Vec<_> = (0..1000)
+        .map(|i| {
+            Value::Record(vec![
+                ("a".into(), Value::Long(i)),
+                ("b".into(), Value::String(format!("value {}", i % 10))),
+            ])
+        })
+        .collect();
+    let mut writer = Writer::with_codec(schema, Vec::new(), codec);
+    writer.extend_from_slice(&values).unwrap();
+    let encoded = writer.into_inner();
+    let reader = Reader::new(&encoded[..]).unwrap();
+    let decoded = reader.collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(decoded, values);
+}
+
+#[test]
+fn test_snappy_codec_round_trip() {
+    test_codec_round_trip(Codec::Snappy);
+}
+
+#[test]
+fn test_zstandard_codec_round_trip() {
+    test_codec_round_trip(Codec::Zstandard);
+}
+
+#[test]
+fn test_bzip2_codec_round_trip() {
+    test_codec_round_trip(Codec::Bzip2);
+}
+
+#[test]
+fn test_xz_codec_round_trip() {
+    test_codec_round_trip(Codec::Xz);
+}
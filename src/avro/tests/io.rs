@@ -33,7 +33,7 @@ use mz_avro::{
     error::Error as AvroError,
     from_avro_datum, to_avro_datum,
     types::{DecimalValue, Value},
-    Schema, ValidationError,
+    Codec, Reader, Schema, ValidationError, Writer,
 };
 use once_cell::sync::Lazy;
 
@@ -203,6 +203,30 @@ fn test_round_trip() {
     }
 }
 
+/// Writes every value to an object container file compressed with `codec`
+/// and checks that reading the file yields the same values.
+fn test_codec_round_trip(codec: Codec) {
+    for (raw_schema, value) in SCHEMAS_TO_VALIDATE.iter() {
+        let schema = Schema::from_str(raw_schema).unwrap();
+        let mut writer = Writer::with_codec(schema, Vec::new(), codec);
+        writer.extend_from_slice(&vec![value.clone(); 100]).unwrap();
+        let encoded = writer.into_inner();
+        let reader = Reader::new(&encoded[..]).unwrap();
+        let decoded = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(decoded, vec![value.clone(); 100]);
+    }
+}
+
+#[test]
+fn test_null_codec_round_trip() {
+    test_codec_round_trip(Codec::Null);
+}
+
+#[test]
+fn test_deflate_codec_round_trip() {
+    test_codec_round_trip(Codec::Deflate);
+}
+
 #[test]
 fn test_binary_int_encoding() {
     for (number, hex_encoding) in BINARY_ENCODINGS.iter() {
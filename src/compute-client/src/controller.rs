@@ -56,7 +56,7 @@ use mz_storage_client::controller::{ReadPolicy, StorageController, StorageError}
 
 use crate::command::{DataflowDescription, ProcessId, ReplicaId};
 use crate::logging::{LogVariant, LogView, LoggingConfig};
-use crate::response::{ComputeResponse, PeekResponse, SubscribeResponse};
+use crate::response::{ComputeResponse, CopyToResponse, PeekResponse, SubscribeResponse};
 use crate::service::{ComputeClient, ComputeGrpcClient};
 
 use self::instance::{ActiveInstance, Instance};
@@ -132,9 +132,19 @@ pub enum ComputeControllerResponse<T> {
     PeekResponse(Uuid, PeekResponse, OpenTelemetryContext),
     /// See [`ComputeResponse::SubscribeResponse`](crate::response::ComputeResponse::SubscribeResponse).
     SubscribeResponse(GlobalId, SubscribeResponse<T>),
+    /// See [`ComputeResponse::CopyToResponse`](crate::response::ComputeResponse::CopyToResponse).
+    CopyToResponse(GlobalId, CopyToResponse),
     /// A notification that we heard a response from the given replica at the
     /// given time.
     ReplicaHeartbeat(ReplicaId, DateTime<Utc>),
+    /// A notification that the hydration status of an index or sink on the
+    /// given replica has changed.
+    ///
+    /// `Some(false)` indicates that the replica has started maintaining the
+    /// collection, `Some(true)` that the replica has finished processing the
+    /// collection's initial snapshot, and `None` that the replica no longer
+    /// maintains the collection.
+    HydrationStatus(GlobalId, ReplicaId, Option<bool>),
 }
 
 /// Errors arising from compute commands.
@@ -269,6 +279,9 @@ pub struct ComputeReplicaAllocation {
     pub scale: NonZeroUsize,
     /// The number of worker threads in the replica.
     pub workers: NonZeroUsize,
+    /// Whether the replica may spill large arrangement batches to local disk.
+    #[serde(default)]
+    pub disk: bool,
 }
 
 impl ComputeReplicaAllocation {
@@ -314,6 +327,20 @@ impl ComputeReplicaLogging {
     }
 }
 
+/// Limits on the memory used by the dataflows of a compute instance.
+///
+/// Memory is measured as the number of bytes that arrangements hold in memory,
+/// as reported by each replica. This includes the arrangements internal to
+/// dataflows, like those of joins and reductions. A limit of zero disables the
+/// corresponding check.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryLimits {
+    /// Max size in bytes of the arrangements of a single transient dataflow.
+    pub max_transient_dataflow_bytes: u64,
+    /// Max size in bytes of all arrangements maintained by a replica.
+    pub max_replica_bytes: u64,
+}
+
 /// A controller for the compute layer.
 pub struct ComputeController<T> {
     instances: BTreeMap<ComputeInstanceId, Instance<T>>,
@@ -335,12 +362,17 @@ impl<T> ComputeController<T> {
         build_info: &'static BuildInfo,
         orchestrator: Arc<dyn NamespacedOrchestrator>,
         computed_image: String,
+        persist_pubsub_url: Option<String>,
         envd_epoch: i64,
     ) -> Self {
         Self {
             instances: BTreeMap::new(),
             build_info,
-            orchestrator: ComputeOrchestrator::new(orchestrator, computed_image),
+            orchestrator: ComputeOrchestrator::new(
+                orchestrator,
+                computed_image,
+                persist_pubsub_url,
+            ),
             initialized: false,
             stashed_response: None,
             replica_heartbeats: BTreeMap::new(),
@@ -409,6 +441,7 @@ where
         id: ComputeInstanceId,
         arranged_logs: BTreeMap<LogVariant, GlobalId>,
         max_result_size: u32,
+        memory_limits: MemoryLimits,
     ) -> Result<(), ComputeError> {
         if self.instances.contains_key(&id) {
             return Err(ComputeError::InstanceExists(id));
@@ -421,6 +454,7 @@ where
                 self.build_info,
                 arranged_logs,
                 max_result_size,
+                memory_limits,
                 self.orchestrator.clone(),
                 self.envd_epoch,
             ),
@@ -683,6 +717,17 @@ where
         Ok(())
     }
 
+    /// Update the limits on the memory used by dataflows.
+    pub fn update_memory_limits(
+        &mut self,
+        instance_id: ComputeInstanceId,
+        memory_limits: MemoryLimits,
+    ) -> Result<(), ComputeError> {
+        self.instance(instance_id)?
+            .update_memory_limits(memory_limits);
+        Ok(())
+    }
+
     /// Processes the work queued by [`ComputeController::ready`].
     ///
     /// This method is guaranteed to return "quickly" unless doing so would
@@ -743,6 +788,12 @@ impl<T> ComputeInstanceRef<'_, T> {
     pub fn collection(&self, id: GlobalId) -> Result<&CollectionState<T>, ComputeError> {
         self.instance.collection(id)
     }
+
+    /// Return the IDs of the collections that read from the storage collection `id`,
+    /// including those of dataflows that are dropped but still serve pending peeks.
+    pub fn storage_dependents(&self, id: GlobalId) -> impl Iterator<Item = GlobalId> + '_ {
+        self.instance.storage_dependents(id)
+    }
 }
 
 /// State maintained about individual collections.
@@ -772,6 +823,9 @@ pub struct CollectionState<T> {
     write_frontier: Antichain<T>,
     /// The write frontiers reported by individual replicas.
     replica_write_frontiers: BTreeMap<ReplicaId, Antichain<T>>,
+    /// The number of bytes held in memory by the arrangements of the dataflow
+    /// exporting the collection, as reported by individual replicas.
+    replica_arrangement_sizes: BTreeMap<ReplicaId, u64>,
 }
 
 impl<T: Timestamp> CollectionState<T> {
@@ -793,6 +847,7 @@ impl<T: Timestamp> CollectionState<T> {
             compute_dependencies,
             write_frontier: Antichain::from_elem(Timestamp::minimum()),
             replica_write_frontiers: BTreeMap::new(),
+            replica_arrangement_sizes: BTreeMap::new(),
         }
     }
 
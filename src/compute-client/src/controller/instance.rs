@@ -29,7 +29,10 @@ use crate::command::{
     InstanceConfig, Peek, ReplicaId, SourceInstanceDesc,
 };
 use crate::logging::{LogVariant, LoggingConfig};
-use crate::response::{ComputeResponse, PeekResponse, SubscribeBatch, SubscribeResponse};
+use crate::response::{
+    ComputeResponse, CopyToResponse, MemoryLimitError, PeekResponse, SubscribeBatch,
+    SubscribeResponse,
+};
 use crate::service::{ComputeClient, ComputeGrpcClient};
 use crate::sinks::{ComputeSinkConnection, ComputeSinkDesc, PersistSinkConnection};
 
@@ -37,7 +40,7 @@ use super::orchestrator::ComputeOrchestrator;
 use super::replica::Replica;
 use super::{
     CollectionState, ComputeControllerResponse, ComputeError, ComputeInstanceId,
-    ComputeReplicaLocation,
+    ComputeReplicaLocation, MemoryLimits,
 };
 
 /// The state we keep for a compute instance.
@@ -57,6 +60,8 @@ pub(super) struct Instance<T> {
     peeks: HashMap<Uuid, PendingPeek<T>>,
     /// Frontiers of in-progress subscribes.
     subscribes: BTreeMap<GlobalId, Antichain<T>>,
+    /// In-progress `COPY ... TO` sinks, with the replicas whose responses we pass on.
+    copy_tos: BTreeMap<GlobalId, ReplicaId>,
     /// The command history, used when introducing new replicas or restarting existing replicas.
     history: ComputeCommandHistory<T>,
     /// IDs of replicas that have failed and require rehydration.
@@ -69,6 +74,11 @@ pub(super) struct Instance<T> {
     envd_epoch: i64,
     /// Numbers that increase with each restart of a replica.
     replica_epochs: HashMap<ReplicaId, u64>,
+    /// Limits on the memory used by dataflows.
+    memory_limits: MemoryLimits,
+    /// The number of bytes all arrangements of each replica hold in memory, as
+    /// reported by the replica.
+    replica_arrangement_sizes: HashMap<ReplicaId, u64>,
 }
 
 impl<T> Instance<T> {
@@ -79,6 +89,15 @@ impl<T> Instance<T> {
             .ok_or(ComputeError::IdentifierMissing(id))
     }
 
+    /// Returns the IDs of the collections that read from the storage
+    /// collection `id`.
+    pub fn storage_dependents(&self, id: GlobalId) -> impl Iterator<Item = GlobalId> + '_ {
+        self.collections
+            .iter()
+            .filter(move |(_, collection)| collection.storage_dependencies.contains(&id))
+            .map(|(collection_id, _)| *collection_id)
+    }
+
     /// Acquire a mutable handle to the collection state associated with `id`.
     fn collection_mut(&mut self, id: GlobalId) -> Result<&mut CollectionState<T>, ComputeError> {
         self.collections
@@ -116,6 +135,7 @@ where
         build_info: &'static BuildInfo,
         arranged_logs: BTreeMap<LogVariant, GlobalId>,
         max_result_size: u32,
+        memory_limits: MemoryLimits,
         orchestrator: ComputeOrchestrator,
         envd_epoch: i64,
     ) -> Self {
@@ -135,12 +155,15 @@ where
             arranged_logs,
             peeks: Default::default(),
             subscribes: Default::default(),
+            copy_tos: Default::default(),
             history: Default::default(),
             failed_replicas: Default::default(),
             ready_responses: Default::default(),
             orchestrator,
             envd_epoch,
             replica_epochs: Default::default(),
+            memory_limits,
+            replica_arrangement_sizes: Default::default(),
         };
 
         instance.send(ComputeCommand::CreateTimely {
@@ -331,10 +354,32 @@ where
         }
         self.remove_peeks(&peeks_to_remove).await?;
 
+        // Removing a replica fails the `COPY ... TO` sinks it was writing, as
+        // the objects it already uploaded cannot be taken back.
+        let copy_tos: Vec<_> = self
+            .compute
+            .copy_tos
+            .iter()
+            .filter(|(_, target)| **target == id)
+            .map(|(sink_id, _)| *sink_id)
+            .collect();
+        for sink_id in copy_tos {
+            self.compute.copy_tos.remove(&sink_id);
+            self.compute
+                .ready_responses
+                .push_back(ComputeControllerResponse::CopyToResponse(
+                    sink_id,
+                    CopyToResponse::Error(format!(
+                        "replica {id} failed or was dropped while writing the COPY results"
+                    )),
+                ));
+        }
+
         self.compute
             .replicas
             .remove(&id)
             .expect("replica not found");
+        self.compute.replica_arrangement_sizes.remove(&id);
 
         // In case the replica crashes and we receive a drop replica request
         // at the same time, the cleanup request will race with the rehydration.
@@ -447,18 +492,36 @@ where
                 );
                 updates.push((export_id, as_of.clone()));
             }
-            // Initialize tracking of replica frontiers.
-            let replica_ids: Vec<_> = self.compute.replicas.keys().copied().collect();
-            for replica_id in replica_ids {
-                self.update_write_frontiers(replica_id, &updates).await?;
-            }
-
             // Initialize tracking of subscribes.
             for subscribe_id in dataflow.subscribe_ids() {
                 self.compute
                     .subscribes
                     .insert(subscribe_id, Antichain::from_elem(Timestamp::minimum()));
             }
+            // Initialize tracking of `COPY ... TO` sinks.
+            for (sink_id, sink) in &dataflow.sink_exports {
+                if let ComputeSinkConnection::CopyToS3(conn) = &sink.connection {
+                    if self.compute.replicas.contains_key(&conn.target_replica) {
+                        self.compute.copy_tos.insert(*sink_id, conn.target_replica);
+                    } else {
+                        self.compute.ready_responses.push_back(
+                            ComputeControllerResponse::CopyToResponse(
+                                *sink_id,
+                                CopyToResponse::Error(format!(
+                                    "replica {} does not exist",
+                                    conn.target_replica
+                                )),
+                            ),
+                        );
+                    }
+                }
+            }
+
+            // Initialize tracking of replica frontiers.
+            let replica_ids: Vec<_> = self.compute.replicas.keys().copied().collect();
+            for replica_id in replica_ids {
+                self.update_write_frontiers(replica_id, &updates).await?;
+            }
         }
 
         // Here we augment all imported sources and all exported sinks with with the appropriate
@@ -494,6 +557,7 @@ where
                     ComputeSinkConnection::Subscribe(conn) => {
                         ComputeSinkConnection::Subscribe(conn)
                     }
+                    ComputeSinkConnection::CopyToS3(conn) => ComputeSinkConnection::CopyToS3(conn),
                 };
                 let desc = ComputeSinkDesc {
                     from: se.from,
@@ -566,8 +630,9 @@ where
                 time: timestamp.clone(),
                 unfinished,
                 target_replica,
+                served: false,
                 // TODO(guswynn): can we just hold the `tracing::Span` here instead?
-                otel_ctx: Some(otel_ctx.clone()),
+                otel_ctx: otel_ctx.clone(),
             },
         );
 
@@ -583,6 +648,12 @@ where
             otel_ctx,
         }));
 
+        // Reject the peek if a replica is already over its memory limit.
+        let replica_ids: Vec<_> = self.compute.replicas.keys().copied().collect();
+        for replica_id in replica_ids {
+            self.enforce_memory_limits(replica_id);
+        }
+
         Ok(())
     }
 
@@ -590,23 +661,22 @@ where
     pub fn cancel_peeks(&mut self, uuids: BTreeSet<Uuid>) {
         // Enqueue the response to the cancelation.
         for uuid in &uuids {
-            let otel_ctx = self
-                .compute
-                .peeks
-                .get_mut(uuid)
-                // Canceled peeks should not be further responded to.
-                .map(|pending| pending.otel_ctx.take())
-                .unwrap_or_else(|| {
+            let peek = match self.compute.peeks.get_mut(uuid) {
+                Some(peek) => peek,
+                None => {
                     tracing::warn!("did not find pending peek for {}", uuid);
-                    None
-                });
-            if let Some(ctx) = otel_ctx {
+                    continue;
+                }
+            };
+            // Canceled peeks should not be further responded to.
+            if !peek.served {
+                peek.served = true;
                 self.compute
                     .ready_responses
                     .push_back(ComputeControllerResponse::PeekResponse(
                         *uuid,
                         PeekResponse::Canceled,
-                        ctx,
+                        peek.otel_ctx.clone(),
                     ));
             }
         }
@@ -663,6 +733,11 @@ where
             .send(ComputeCommand::UpdateMaxResultSize(max_result_size))
     }
 
+    /// Update the limits on the memory used by dataflows.
+    pub fn update_memory_limits(&mut self, memory_limits: MemoryLimits) {
+        self.compute.memory_limits = memory_limits;
+    }
+
     /// Validate that a collection exists for all identifiers, and error if any do not.
     fn validate_ids(&self, ids: impl Iterator<Item = GlobalId>) -> Result<(), ComputeError> {
         for id in ids {
@@ -682,7 +757,9 @@ where
         let mut compute_read_capability_changes = BTreeMap::default();
         let mut storage_read_capability_changes = BTreeMap::default();
         let mut dropped_collection_ids = Vec::new();
+        let mut hydration_updates = Vec::new();
         for (id, new_upper) in updates.iter() {
+            let is_subscribe = self.compute.subscribes.contains_key(id);
             let collection = self
                 .compute
                 .collection_mut(*id)
@@ -701,6 +778,23 @@ where
                 dropped_collection_ids.push(*id);
             }
 
+            // Track hydration of indexes and sinks for as long as the replica maintains them.
+            // Subscribes are excluded, as they are not expected to ever finish.
+            if !collection.log_collection && !is_subscribe {
+                let was_maintained = old_upper.as_ref().map_or(false, |f| !f.is_empty());
+                let is_maintained = !new_upper.is_empty();
+                let status = match (was_maintained, is_maintained) {
+                    (false, true) => Some(Some(false)),
+                    (true, false) => Some(None),
+                    _ => None,
+                };
+                if let Some(status) = status {
+                    hydration_updates.push(ComputeControllerResponse::HydrationStatus(
+                        *id, replica_id, status,
+                    ));
+                }
+            }
+
             let mut new_read_capability = collection
                 .read_policy
                 .frontier(collection.write_frontier.borrow());
@@ -728,6 +822,7 @@ where
                 update.extend(new_upper.iter().map(|time| (time.clone(), 1)));
             }
         }
+        self.compute.ready_responses.extend(hydration_updates);
         if !compute_read_capability_changes.is_empty() {
             self.update_read_capabilities(&mut compute_read_capability_changes)
                 .await?;
@@ -768,10 +863,20 @@ where
         let mut dropped_collection_ids = Vec::new();
         for (id, collection) in self.compute.collections.iter_mut() {
             let last_upper = collection.replica_write_frontiers.remove(&replica_id);
+            collection.replica_arrangement_sizes.remove(&replica_id);
 
             if let Some(frontier) = last_upper {
                 dropped_collection_ids.push(*id);
 
+                if !collection.log_collection
+                    && !self.compute.subscribes.contains_key(id)
+                    && !frontier.is_empty()
+                {
+                    self.compute.ready_responses.push_back(
+                        ComputeControllerResponse::HydrationStatus(*id, replica_id, None),
+                    );
+                }
+
                 // Update read holds on storage dependencies.
                 for storage_id in &collection.storage_dependencies {
                     let update = storage_read_capability_changes
@@ -917,6 +1022,17 @@ where
                 self.handle_subscribe_response(id, response, replica_id)
                     .await
             }
+            ComputeResponse::CopyToResponse(id, response) => {
+                Ok(self.handle_copy_to_response(id, response, replica_id))
+            }
+            ComputeResponse::Hydrated(ids) => {
+                self.handle_hydrated(ids, replica_id);
+                Ok(None)
+            }
+            ComputeResponse::ArrangementSizes { dataflows, total } => {
+                self.handle_arrangement_sizes(dataflows, total, replica_id);
+                Ok(None)
+            }
         }
     }
 
@@ -956,6 +1072,170 @@ where
         self.update_write_frontiers(replica_id, &updates).await
     }
 
+    fn handle_hydrated(&mut self, ids: Vec<GlobalId>, replica_id: ReplicaId) {
+        for id in ids {
+            // Ignore reports for collections that the replica is not known to maintain, e.g.,
+            // because they have been dropped in the meantime.
+            let maintained = self
+                .compute
+                .collections
+                .get(&id)
+                .and_then(|collection| collection.replica_write_frontiers.get(&replica_id))
+                .map_or(false, |frontier| !frontier.is_empty());
+            if maintained {
+                self.compute
+                    .ready_responses
+                    .push_back(ComputeControllerResponse::HydrationStatus(
+                        id,
+                        replica_id,
+                        Some(true),
+                    ));
+            }
+        }
+    }
+
+    fn handle_arrangement_sizes(
+        &mut self,
+        dataflows: Vec<(GlobalId, u64)>,
+        total: u64,
+        replica_id: ReplicaId,
+    ) {
+        for (id, size) in dataflows {
+            // Ignore reports for collections that have been dropped in the meantime.
+            if let Some(collection) = self.compute.collections.get_mut(&id) {
+                collection
+                    .replica_arrangement_sizes
+                    .insert(replica_id, size);
+            }
+        }
+        self.compute
+            .replica_arrangement_sizes
+            .insert(replica_id, total);
+
+        self.enforce_memory_limits(replica_id);
+    }
+
+    /// Fails peeks, subscribes, and `COPY ... TO` sinks whose dataflows exceed the configured memory limits on the
+    /// given replica.
+    ///
+    /// A transient dataflow exceeds the per-dataflow limit if its arrangements on the replica,
+    /// including those internal to the dataflow, exceed that limit. If all arrangements of the
+    /// replica exceed the replica limit, transient dataflows are failed largest first until the
+    /// replica is within its limit again. Transient dataflows whose size the replica has not yet
+    /// reported count as empty, so new peeks are rejected while the replica is over its limit.
+    /// Dataflows maintaining indexes and materialized views are never failed.
+    ///
+    /// Failed peeks are canceled on all replicas, which releases their read holds and lets the
+    /// transient dataflows be dropped. Failed subscribes and `COPY ... TO` sinks are finished with
+    /// an error, which lets the adapter drop them.
+    fn enforce_memory_limits(&mut self, replica_id: ReplicaId) {
+        let MemoryLimits {
+            max_transient_dataflow_bytes,
+            max_replica_bytes,
+        } = self.compute.memory_limits;
+        if max_transient_dataflow_bytes == 0 && max_replica_bytes == 0 {
+            return;
+        }
+
+        // Only transient dataflows that still have to produce results can be failed. Those
+        // whose peeks have all been served are about to be dropped.
+        let targets: BTreeSet<_> = self
+            .compute
+            .peeks
+            .values()
+            .filter(|peek| !peek.served)
+            .map(|peek| peek.target)
+            .chain(self.compute.subscribes.keys().copied())
+            .chain(self.compute.copy_tos.keys().copied())
+            .filter(|id| id.is_transient())
+            .collect();
+        let mut transient: Vec<_> = targets
+            .into_iter()
+            .map(|id| {
+                let size = self
+                    .compute
+                    .collections
+                    .get(&id)
+                    .and_then(|collection| collection.replica_arrangement_sizes.get(&replica_id))
+                    .copied()
+                    .unwrap_or(0);
+                (size, id)
+            })
+            .collect();
+        transient.sort_by(|left, right| right.cmp(left));
+
+        let mut replica_bytes = self
+            .compute
+            .replica_arrangement_sizes
+            .get(&replica_id)
+            .copied()
+            .unwrap_or(0);
+        let mut failed = BTreeMap::new();
+        for (size, id) in transient {
+            let error = if max_transient_dataflow_bytes > 0 && size > max_transient_dataflow_bytes {
+                MemoryLimitError::Dataflow(max_transient_dataflow_bytes)
+            } else if max_replica_bytes > 0 && replica_bytes > max_replica_bytes {
+                MemoryLimitError::Replica(max_replica_bytes)
+            } else {
+                continue;
+            };
+            replica_bytes = replica_bytes.saturating_sub(size);
+            failed.insert(id, error);
+        }
+        if failed.is_empty() {
+            return;
+        }
+
+        let mut uuids = BTreeSet::new();
+        for (uuid, peek) in self.compute.peeks.iter_mut() {
+            if let Some(error) = failed.get(&peek.target) {
+                if !peek.served {
+                    tracing::warn!(
+                        "failing peek {uuid} at {} on replica {replica_id}: {error}",
+                        peek.target
+                    );
+                    peek.served = true;
+                    self.compute.ready_responses.push_back(
+                        ComputeControllerResponse::PeekResponse(
+                            *uuid,
+                            PeekResponse::MemoryLimitExceeded(error.clone()),
+                            peek.otel_ctx.clone(),
+                        ),
+                    );
+                    uuids.insert(*uuid);
+                }
+            }
+        }
+        if !uuids.is_empty() {
+            self.compute.send(ComputeCommand::CancelPeeks { uuids });
+        }
+
+        for (id, error) in failed {
+            if let Some(frontier) = self.compute.subscribes.remove(&id) {
+                tracing::warn!("failing subscribe {id} on replica {replica_id}: {error}");
+                self.compute.ready_responses.push_back(
+                    ComputeControllerResponse::SubscribeResponse(
+                        id,
+                        SubscribeResponse::Batch(SubscribeBatch {
+                            lower: frontier,
+                            upper: Antichain::new(),
+                            updates: Err(error.to_string()),
+                        }),
+                    ),
+                );
+            }
+            if self.compute.copy_tos.remove(&id).is_some() {
+                tracing::warn!("failing COPY TO {id} on replica {replica_id}: {error}");
+                self.compute
+                    .ready_responses
+                    .push_back(ComputeControllerResponse::CopyToResponse(
+                        id,
+                        CopyToResponse::Error(error.to_string()),
+                    ));
+            }
+        }
+    }
+
     async fn handle_peek_response(
         &mut self,
         uuid: Uuid,
@@ -979,17 +1259,16 @@ where
         //
         // NOTE: we use the `otel_ctx` from the response, not the
         // pending peek, because we currently want the parent
-        // to be whatever the compute worker did with this peek. We
-        // still `take` the pending peek's `otel_ctx` to mark it as
-        // served.
+        // to be whatever the compute worker did with this peek.
         //
         // Additionally, we just use the `otel_ctx` from the first worker to
         // respond.
         let replica_targeted = peek.target_replica.unwrap_or(replica_id) == replica_id;
-        let controller_response = if replica_targeted {
-            peek.otel_ctx
-                .take()
-                .map(|_| ComputeControllerResponse::PeekResponse(uuid, response, otel_ctx))
+        let controller_response = if replica_targeted && !peek.served {
+            peek.served = true;
+            Some(ComputeControllerResponse::PeekResponse(
+                uuid, response, otel_ctx,
+            ))
         } else {
             None
         };
@@ -1003,6 +1282,22 @@ where
         Ok(controller_response)
     }
 
+    fn handle_copy_to_response(
+        &mut self,
+        sink_id: GlobalId,
+        response: CopyToResponse,
+        replica_id: ReplicaId,
+    ) -> Option<ComputeControllerResponse<T>> {
+        // Only the target replica uploads objects, so only its response is
+        // meaningful. Responses from other replicas, or to sinks that have
+        // already finished, are ignored.
+        if self.compute.copy_tos.get(&sink_id) != Some(&replica_id) {
+            return None;
+        }
+        self.compute.copy_tos.remove(&sink_id);
+        Some(ComputeControllerResponse::CopyToResponse(sink_id, response))
+    }
+
     async fn handle_subscribe_response(
         &mut self,
         subscribe_id: GlobalId,
@@ -1086,11 +1381,10 @@ struct PendingPeek<T> {
     ///
     /// If this value is `None`, we pass on the first response.
     target_replica: Option<ReplicaId>,
+    /// Whether we have passed a response to this peek up the chain.
+    served: bool,
     /// The OpenTelemetry context for this peek.
-    ///
-    /// This value is `Some` as long as we have not yet passed a response up the chain, and `None`
-    /// afterwards.
-    otel_ctx: Option<OpenTelemetryContext>,
+    otel_ctx: OpenTelemetryContext,
 }
 
 impl<T> PendingPeek<T> {
@@ -1100,6 +1394,6 @@ impl<T> PendingPeek<T> {
         // the set of replicas we are waiting for is currently empty. It might be that the cluster
         // has no replicas or all replicas have been temporarily removed for re-hydration. In this
         // case, we wait for new replicas to be added to eventually serve the peek.
-        self.otel_ctx.is_none() && self.unfinished.is_empty()
+        self.served && self.unfinished.is_empty()
     }
 }
@@ -31,13 +31,19 @@ use super::{
 pub(super) struct ComputeOrchestrator {
     inner: Arc<dyn NamespacedOrchestrator>,
     computed_image: String,
+    persist_pubsub_url: Option<String>,
 }
 
 impl ComputeOrchestrator {
-    pub(super) fn new(inner: Arc<dyn NamespacedOrchestrator>, computed_image: String) -> Self {
+    pub(super) fn new(
+        inner: Arc<dyn NamespacedOrchestrator>,
+        computed_image: String,
+        persist_pubsub_url: Option<String>,
+    ) -> Self {
         Self {
             inner,
             computed_image,
+            persist_pubsub_url,
         }
     }
     pub(super) async fn ensure_replica_location(
@@ -114,6 +120,9 @@ impl ComputeOrchestrator {
                             compute_opts
                                 .push(format!("--opentelemetry-resource=replica_index={}", index));
                         }
+                        if let Some(url) = &self.persist_pubsub_url {
+                            compute_opts.push(format!("--persist-pubsub-url={}", url));
+                        }
                         compute_opts
                     },
                     ports: vec![
@@ -133,6 +142,7 @@ impl ComputeOrchestrator {
                     cpu_limit: allocation.cpu_limit,
                     memory_limit: allocation.memory_limit,
                     scale: allocation.scale,
+                    disk: allocation.disk,
                     labels: HashMap::from([
                         ("replica-id".into(), replica_id.to_string()),
                         ("cluster-id".into(), instance_id.to_string()),
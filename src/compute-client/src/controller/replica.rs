@@ -26,6 +26,7 @@ use crate::command::{CommunicationConfig, ComputeCommand, ComputeStartupEpoch, R
 use crate::logging::LoggingConfig;
 use crate::response::ComputeResponse;
 use crate::service::{ComputeClient, ComputeGrpcClient};
+use crate::sinks::ComputeSinkConnection;
 
 use super::orchestrator::ComputeOrchestrator;
 use super::{ComputeInstanceId, ComputeReplicaLocation};
@@ -168,6 +169,7 @@ where
             .await?;
 
         let cmd_spec = CommandSpecialization {
+            replica_id,
             logging_config,
             comm_config,
             epoch,
@@ -269,6 +271,7 @@ where
 }
 
 struct CommandSpecialization {
+    replica_id: ReplicaId,
     logging_config: LoggingConfig,
     comm_config: CommunicationConfig,
     epoch: ComputeStartupEpoch,
@@ -289,5 +292,16 @@ impl CommandSpecialization {
             *comm_config = self.comm_config.clone();
             *epoch = self.epoch;
         }
+
+        // Only the target replica of a `COPY ... TO` sink may upload its objects.
+        if let ComputeCommand::CreateDataflows(dataflows) = command {
+            for dataflow in dataflows {
+                for sink in dataflow.sink_exports.values_mut() {
+                    if let ComputeSinkConnection::CopyToS3(conn) = &mut sink.connection {
+                        conn.upload = conn.target_replica == self.replica_id;
+                    }
+                }
+            }
+        }
     }
 }
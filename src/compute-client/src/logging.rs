@@ -237,6 +237,8 @@ pub enum ComputeLog {
     PeekDuration,
     FrontierDelay,
     SourceFrontierCurrent,
+    LirMapping,
+    ArrangementSpilledBytes,
 }
 
 impl RustType<ProtoComputeLog> for ComputeLog {
@@ -251,6 +253,8 @@ impl RustType<ProtoComputeLog> for ComputeLog {
                 ComputeLog::PeekDuration => PeekDuration(()),
                 ComputeLog::FrontierDelay => FrontierDelay(()),
                 ComputeLog::SourceFrontierCurrent => SourceFrontierCurrent(()),
+                ComputeLog::LirMapping => LirMapping(()),
+                ComputeLog::ArrangementSpilledBytes => ArrangementSpilledBytes(()),
             }),
         }
     }
@@ -265,6 +269,8 @@ impl RustType<ProtoComputeLog> for ComputeLog {
             Some(PeekDuration(())) => Ok(ComputeLog::PeekDuration),
             Some(FrontierDelay(())) => Ok(ComputeLog::FrontierDelay),
             Some(SourceFrontierCurrent(())) => Ok(ComputeLog::SourceFrontierCurrent),
+            Some(LirMapping(())) => Ok(ComputeLog::LirMapping),
+            Some(ArrangementSpilledBytes(())) => Ok(ComputeLog::ArrangementSpilledBytes),
             None => Err(TryFromProtoError::missing_field("ProtoComputeLog::kind")),
         }
     }
@@ -291,6 +297,8 @@ pub static DEFAULT_LOG_VARIANTS: Lazy<Vec<LogVariant>> = Lazy::new(|| {
         LogVariant::Compute(ComputeLog::FrontierDelay),
         LogVariant::Compute(ComputeLog::PeekCurrent),
         LogVariant::Compute(ComputeLog::PeekDuration),
+        LogVariant::Compute(ComputeLog::LirMapping),
+        LogVariant::Compute(ComputeLog::ArrangementSpilledBytes),
     ];
 
     default_logs
@@ -375,13 +383,26 @@ impl LogView {
                         mz_internal.mz_arrangement_records_internal_{}
                     GROUP BY
                         operator_id, worker_id
+                ),
+                spilled_cte AS (
+                    SELECT
+                        operator_id,
+                        worker_id,
+                        pg_catalog.count(*) AS spilled_bytes
+                    FROM
+                        mz_internal.mz_arrangement_spilled_bytes_internal_{}
+                    GROUP BY
+                        operator_id, worker_id
                 )
                 SELECT
                     batches_cte.operator_id,
                     batches_cte.worker_id,
                     records_cte.records,
-                    batches_cte.batches
-                FROM batches_cte JOIN records_cte USING (operator_id, worker_id)",
+                    batches_cte.batches,
+                    spilled_cte.spilled_bytes
+                FROM batches_cte
+                JOIN records_cte USING (operator_id, worker_id)
+                LEFT JOIN spilled_cte USING (operator_id, worker_id)",
                 "mz_arrangement_sizes_{}",
             ),
 
@@ -737,6 +758,20 @@ impl LogVariant {
                 .with_column("duration_ns", ScalarType::UInt64.nullable(false))
                 .with_column("count", ScalarType::UInt64.nullable(false))
                 .with_key(vec![0, 1]),
+
+            LogVariant::Compute(ComputeLog::LirMapping) => RelationDesc::empty()
+                .with_column("export_id", ScalarType::String.nullable(false))
+                .with_column("lir_id", ScalarType::UInt64.nullable(false))
+                .with_column("worker_id", ScalarType::UInt64.nullable(false))
+                .with_column("operator", ScalarType::String.nullable(false))
+                .with_column("nesting", ScalarType::UInt64.nullable(false))
+                .with_column("operator_id_start", ScalarType::UInt64.nullable(false))
+                .with_column("operator_id_end", ScalarType::UInt64.nullable(false))
+                .with_key(vec![0, 1, 2]),
+
+            LogVariant::Compute(ComputeLog::ArrangementSpilledBytes) => RelationDesc::empty()
+                .with_column("operator_id", ScalarType::UInt64.nullable(false))
+                .with_column("worker_id", ScalarType::UInt64.nullable(false)),
         }
     }
 
@@ -786,6 +821,11 @@ impl LogVariant {
             LogVariant::Compute(ComputeLog::FrontierDelay) => vec![],
             LogVariant::Compute(ComputeLog::PeekCurrent) => vec![],
             LogVariant::Compute(ComputeLog::PeekDuration) => vec![],
+            LogVariant::Compute(ComputeLog::LirMapping) => vec![],
+            LogVariant::Compute(ComputeLog::ArrangementSpilledBytes) => vec![(
+                LogVariant::Timely(TimelyLog::Operates),
+                vec![(0, 0), (1, 1)],
+            )],
         }
     }
 }
@@ -378,6 +378,49 @@ impl<T> Plan<T> {
             .chain(second)
             .chain(rest.into_iter().flatten())
     }
+
+    /// A short description of the operator this node renders to.
+    ///
+    /// The description does not include the node's children, and is used to
+    /// attribute runtime information to the node in introspection sources.
+    pub fn operator_name(&self) -> String {
+        use Plan::*;
+        match self {
+            Constant { .. } => "Constant".to_string(),
+            Get { id, plan, .. } => match plan {
+                GetPlan::PassArrangements => format!("Get::PassArrangements {}", id),
+                GetPlan::Arrangement(..) => format!("Get::Arrangement {}", id),
+                GetPlan::Collection(..) => format!("Get::Collection {}", id),
+            },
+            Let { id, .. } => format!("Let {}", id),
+            Mfp { .. } => "Map/Filter/Project".to_string(),
+            FlatMap { func, .. } => format!("FlatMap {}", func),
+            Join { plan, .. } => match plan {
+                JoinPlan::Linear(_) => "Join::Differential".to_string(),
+                JoinPlan::Delta(_) => "Join::Delta".to_string(),
+            },
+            Reduce { plan, .. } => match plan {
+                ReducePlan::Distinct => "Reduce::Distinct".to_string(),
+                ReducePlan::DistinctNegated => "Reduce::DistinctNegated".to_string(),
+                ReducePlan::Accumulable(_) => "Reduce::Accumulable".to_string(),
+                ReducePlan::Hierarchical(_) => "Reduce::Hierarchical".to_string(),
+                ReducePlan::Basic(_) => "Reduce::Basic".to_string(),
+                ReducePlan::Collation(_) => "Reduce::Collation".to_string(),
+            },
+            TopK { top_k_plan, .. } => match top_k_plan {
+                TopKPlan::MonotonicTop1(_) => "TopK::MonotonicTop1".to_string(),
+                TopKPlan::MonotonicTopK(_) => "TopK::MonotonicTopK".to_string(),
+                TopKPlan::Basic(_) => "TopK::Basic".to_string(),
+            },
+            Negate { .. } => "Negate".to_string(),
+            Threshold { threshold_plan, .. } => match threshold_plan {
+                ThresholdPlan::Basic(_) => "Threshold::Basic".to_string(),
+                ThresholdPlan::Retractions(_) => "Threshold::Retractions".to_string(),
+            },
+            Union { .. } => "Union".to_string(),
+            ArrangeBy { .. } => "ArrangeBy".to_string(),
+        }
+    }
 }
 
 impl Arbitrary for Plan {
@@ -15,9 +15,11 @@
 
 use std::num::NonZeroUsize;
 
+use bytesize::ByteSize;
 use proptest::prelude::{any, Arbitrary, Just};
 use proptest::prop_oneof;
 use proptest::strategy::{BoxedStrategy, Strategy};
+use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 use timely::progress::frontier::Antichain;
 use uuid::Uuid;
@@ -41,6 +43,28 @@ pub enum ComputeResponse<T = mz_repr::Timestamp> {
     PeekResponse(Uuid, PeekResponse, OpenTelemetryContext),
     /// The worker's next response to a specified subscribe.
     SubscribeResponse(GlobalId, SubscribeResponse<T>),
+    /// A list of identifiers of indexes and sinks that have finished processing
+    /// their initial snapshot, i.e., whose upper frontiers have advanced past
+    /// their `as_of`.
+    ///
+    /// Each collection is reported at most once per incarnation of its
+    /// dataflow, and again after reconciliation.
+    Hydrated(Vec<GlobalId>),
+    /// The number of bytes arrangements hold in memory.
+    ///
+    /// Sizes are reported when they change, and again after reconciliation.
+    ArrangementSizes {
+        /// The identifiers of indexes and sinks, with the number of bytes the
+        /// arrangements of the dataflows exporting them hold in memory. This
+        /// includes arrangements internal to the dataflows, like those of
+        /// joins and reductions.
+        dataflows: Vec<(GlobalId, u64)>,
+        /// The number of bytes all arrangements of the replica hold in memory,
+        /// including those of logging dataflows.
+        total: u64,
+    },
+    /// The worker's response to a specified `COPY ... TO` sink.
+    CopyToResponse(GlobalId, CopyToResponse),
 }
 
 impl RustType<ProtoComputeResponse> for ComputeResponse<mz_repr::Timestamp> {
@@ -63,12 +87,34 @@ impl RustType<ProtoComputeResponse> for ComputeResponse<mz_repr::Timestamp> {
                         resp: Some(resp.into_proto()),
                     })
                 }
+                ComputeResponse::Hydrated(ids) => Hydrated(ProtoHydratedKind {
+                    ids: ids.into_proto(),
+                }),
+                ComputeResponse::ArrangementSizes { dataflows, total } => {
+                    ArrangementSizes(ProtoArrangementSizesKind {
+                        sizes: dataflows
+                            .iter()
+                            .map(|(id, size)| ProtoArrangementSize {
+                                id: Some(id.into_proto()),
+                                size: *size,
+                            })
+                            .collect(),
+                        total: *total,
+                    })
+                }
+                ComputeResponse::CopyToResponse(id, resp) => {
+                    CopyToResponse(ProtoCopyToResponseKind {
+                        id: Some(id.into_proto()),
+                        resp: Some(resp.into_proto()),
+                    })
+                }
             }),
         }
     }
 
     fn from_proto(proto: ProtoComputeResponse) -> Result<Self, TryFromProtoError> {
         use proto_compute_response::Kind::*;
+        use proto_compute_response::{ProtoArrangementSizesKind, ProtoHydratedKind};
         match proto.kind {
             Some(FrontierUppers(traces)) => {
                 Ok(ComputeResponse::FrontierUppers(traces.into_rust()?))
@@ -84,6 +130,24 @@ impl RustType<ProtoComputeResponse> for ComputeResponse<mz_repr::Timestamp> {
                 resp.resp
                     .into_rust_if_some("ProtoSubscribeResponseKind::resp")?,
             )),
+            Some(Hydrated(ProtoHydratedKind { ids })) => {
+                Ok(ComputeResponse::Hydrated(ids.into_rust()?))
+            }
+            Some(ArrangementSizes(ProtoArrangementSizesKind { sizes, total })) => {
+                let dataflows = sizes
+                    .into_iter()
+                    .map(|size| {
+                        let id = size.id.into_rust_if_some("ProtoArrangementSize::id")?;
+                        Ok((id, size.size))
+                    })
+                    .collect::<Result<_, TryFromProtoError>>()?;
+                Ok(ComputeResponse::ArrangementSizes { dataflows, total })
+            }
+            Some(CopyToResponse(resp)) => Ok(ComputeResponse::CopyToResponse(
+                resp.id.into_rust_if_some("ProtoCopyToResponseKind::id")?,
+                resp.resp
+                    .into_rust_if_some("ProtoCopyToResponseKind::resp")?,
+            )),
             None => Err(TryFromProtoError::missing_field(
                 "ProtoComputeResponse::kind",
             )),
@@ -104,6 +168,17 @@ impl Arbitrary for ComputeResponse<mz_repr::Timestamp> {
             }),
             (any::<GlobalId>(), any::<SubscribeResponse>())
                 .prop_map(|(id, resp)| ComputeResponse::SubscribeResponse(id, resp)),
+            proptest::collection::vec(any::<GlobalId>(), 1..4).prop_map(ComputeResponse::Hydrated),
+            (
+                proptest::collection::vec((any::<GlobalId>(), any::<u64>()), 1..4),
+                any::<u64>()
+            )
+                .prop_map(|(dataflows, total)| ComputeResponse::ArrangementSizes {
+                    dataflows,
+                    total
+                }),
+            (any::<GlobalId>(), any::<CopyToResponse>())
+                .prop_map(|(id, resp)| ComputeResponse::CopyToResponse(id, resp)),
         ]
         .boxed()
     }
@@ -118,13 +193,18 @@ pub enum PeekResponse {
     Rows(Vec<(Row, NonZeroUsize)>),
     Error(String),
     Canceled,
+    /// The peek was failed by the controller because its dataflow or the
+    /// replica maintaining it used too much memory.
+    MemoryLimitExceeded(MemoryLimitError),
 }
 
 impl PeekResponse {
     pub fn unwrap_rows(self) -> Vec<(Row, NonZeroUsize)> {
         match self {
             PeekResponse::Rows(rows) => rows,
-            PeekResponse::Error(_) | PeekResponse::Canceled => {
+            PeekResponse::Error(_)
+            | PeekResponse::Canceled
+            | PeekResponse::MemoryLimitExceeded(_) => {
                 panic!("PeekResponse::unwrap_rows called on {:?}", self)
             }
         }
@@ -148,6 +228,7 @@ impl RustType<ProtoPeekResponse> for PeekResponse {
                 }),
                 PeekResponse::Error(err) => proto_peek_response::Kind::Error(err.clone()),
                 PeekResponse::Canceled => Canceled(()),
+                PeekResponse::MemoryLimitExceeded(err) => MemoryLimitExceeded(err.into_proto()),
             }),
         }
     }
@@ -168,6 +249,9 @@ impl RustType<ProtoPeekResponse> for PeekResponse {
             )),
             Some(proto_peek_response::Kind::Error(err)) => Ok(PeekResponse::Error(err)),
             Some(Canceled(())) => Ok(PeekResponse::Canceled),
+            Some(MemoryLimitExceeded(err)) => {
+                Ok(PeekResponse::MemoryLimitExceeded(err.into_rust()?))
+            }
             None => Err(TryFromProtoError::missing_field("ProtoPeekResponse::kind")),
         }
     }
@@ -189,11 +273,86 @@ impl Arbitrary for PeekResponse {
             .prop_map(PeekResponse::Rows),
             ".*".prop_map(PeekResponse::Error),
             Just(PeekResponse::Canceled),
+            any::<MemoryLimitError>().prop_map(PeekResponse::MemoryLimitExceeded),
         ]
         .boxed()
     }
 }
 
+/// An error indicating that a dataflow was failed for exceeding a memory limit.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, thiserror::Error)]
+pub enum MemoryLimitError {
+    /// The arrangements of the dataflow exceeded the given per-dataflow limit,
+    /// in bytes.
+    #[error("dataflow exceeds the memory limit of {}", ByteSize::b(*.0))]
+    Dataflow(u64),
+    /// The arrangements of the replica exceeded the given per-replica limit,
+    /// in bytes.
+    #[error("replica exceeds the memory limit of {}", ByteSize::b(*.0))]
+    Replica(u64),
+}
+
+impl RustType<ProtoMemoryLimitError> for MemoryLimitError {
+    fn into_proto(&self) -> ProtoMemoryLimitError {
+        use proto_memory_limit_error::Kind::*;
+        ProtoMemoryLimitError {
+            kind: Some(match self {
+                MemoryLimitError::Dataflow(limit) => Dataflow(*limit),
+                MemoryLimitError::Replica(limit) => Replica(*limit),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoMemoryLimitError) -> Result<Self, TryFromProtoError> {
+        use proto_memory_limit_error::Kind::*;
+        match proto.kind {
+            Some(Dataflow(limit)) => Ok(MemoryLimitError::Dataflow(limit)),
+            Some(Replica(limit)) => Ok(MemoryLimitError::Replica(limit)),
+            None => Err(TryFromProtoError::missing_field(
+                "ProtoMemoryLimitError::kind",
+            )),
+        }
+    }
+}
+
+/// The response from a `COPY ... TO` sink.
+///
+/// Each `COPY ... TO` sink produces exactly one response.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum CopyToResponse {
+    /// The sink wrote its snapshot, consisting of the given number of rows.
+    RowCount(u64),
+    /// The sink failed to write its snapshot.
+    Error(String),
+    /// The sink was dropped before it wrote its snapshot.
+    Dropped,
+}
+
+impl RustType<ProtoCopyToResponse> for CopyToResponse {
+    fn into_proto(&self) -> ProtoCopyToResponse {
+        use proto_copy_to_response::Kind::*;
+        ProtoCopyToResponse {
+            kind: Some(match self {
+                CopyToResponse::RowCount(count) => RowCount(*count),
+                CopyToResponse::Error(err) => Error(err.clone()),
+                CopyToResponse::Dropped => Dropped(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoCopyToResponse) -> Result<Self, TryFromProtoError> {
+        use proto_copy_to_response::Kind::*;
+        match proto.kind {
+            Some(RowCount(count)) => Ok(CopyToResponse::RowCount(count)),
+            Some(Error(err)) => Ok(CopyToResponse::Error(err)),
+            Some(Dropped(())) => Ok(CopyToResponse::Dropped),
+            None => Err(TryFromProtoError::missing_field(
+                "ProtoCopyToResponse::kind",
+            )),
+        }
+    }
+}
+
 /// Various responses that can be communicated about the progress of a SUBSCRIBE command.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum SubscribeResponse<T = mz_repr::Timestamp> {
@@ -13,7 +13,7 @@
 
 //! Compute layer client and server.
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::iter;
 
 use async_trait::async_trait;
@@ -31,7 +31,8 @@ use mz_service::grpc::{BidiProtoClient, ClientTransport, GrpcClient, GrpcServer,
 
 use crate::command::{CommunicationConfig, ComputeCommand, ProtoComputeCommand};
 use crate::response::{
-    ComputeResponse, PeekResponse, ProtoComputeResponse, SubscribeBatch, SubscribeResponse,
+    ComputeResponse, CopyToResponse, PeekResponse, ProtoComputeResponse, SubscribeBatch,
+    SubscribeResponse,
 };
 use crate::service::proto_compute_client::ProtoComputeClient;
 use crate::service::proto_compute_server::ProtoCompute;
@@ -111,6 +112,21 @@ pub struct PartitionedComputeState<T> {
     /// subscribe is permanently borked.
     pending_subscribes:
         HashMap<GlobalId, Option<(MutableAntichain<T>, Result<Vec<(T, Row, Diff)>, String>)>>,
+    /// Tracks in-progress `COPY ... TO` sinks, and the responses of the
+    /// partitions that have finished writing their part of the snapshot.
+    ///
+    /// The entry is `None` once the response was returned, in which case we
+    /// block any further responses for the sink.
+    pending_copy_tos: HashMap<GlobalId, Option<HashMap<usize, CopyToResponse>>>,
+    /// Partitions that have reported hydration of an index or sink; the
+    /// collection is hydrated once all partitions have.
+    hydrated_parts: HashMap<GlobalId, BTreeSet<usize>>,
+    /// The arrangement sizes of dataflows most recently reported by each
+    /// partition, by their exports; the size of a dataflow is the sum across
+    /// all partitions.
+    arrangement_sizes: HashMap<GlobalId, Vec<u64>>,
+    /// The total arrangement size most recently reported by each partition.
+    total_arrangement_sizes: Vec<u64>,
 }
 
 impl<T> Partitionable<ComputeCommand<T>, ComputeResponse<T>>
@@ -126,6 +142,10 @@ where
             uppers: HashMap::new(),
             peek_responses: HashMap::new(),
             pending_subscribes: HashMap::new(),
+            pending_copy_tos: HashMap::new(),
+            hydrated_parts: HashMap::new(),
+            arrangement_sizes: HashMap::new(),
+            total_arrangement_sizes: vec![0; parts],
         }
     }
 }
@@ -140,10 +160,20 @@ where
             uppers,
             peek_responses,
             pending_subscribes,
+            pending_copy_tos,
+            hydrated_parts,
+            arrangement_sizes,
+            total_arrangement_sizes,
         } = self;
         uppers.clear();
         peek_responses.clear();
         pending_subscribes.clear();
+        pending_copy_tos.clear();
+        hydrated_parts.clear();
+        arrangement_sizes.clear();
+        total_arrangement_sizes
+            .iter_mut()
+            .for_each(|size| *size = 0);
     }
 
     /// Observes commands that move past, and prepares state for responses.
@@ -172,6 +202,8 @@ where
 
     fn cease_frontier_tracking(&mut self, id: GlobalId) {
         let previous = self.uppers.remove(&id);
+        self.hydrated_parts.remove(&id);
+        self.arrangement_sizes.remove(&id);
         assert!(
             previous.is_some(),
             "ceasing frontier tracking for absent identifier {id}",
@@ -262,6 +294,12 @@ where
                         response = match (response, r) {
                             (_, PeekResponse::Canceled) => PeekResponse::Canceled,
                             (PeekResponse::Canceled, _) => PeekResponse::Canceled,
+                            (_, PeekResponse::MemoryLimitExceeded(e)) => {
+                                PeekResponse::MemoryLimitExceeded(e)
+                            }
+                            (PeekResponse::MemoryLimitExceeded(e), _) => {
+                                PeekResponse::MemoryLimitExceeded(e)
+                            }
                             (_, PeekResponse::Error(e)) => PeekResponse::Error(e),
                             (PeekResponse::Error(e), _) => PeekResponse::Error(e),
                             (PeekResponse::Rows(mut rows), PeekResponse::Rows(r)) => {
@@ -357,6 +395,81 @@ where
                     }
                 }
             }
+            ComputeResponse::CopyToResponse(id, response) => {
+                let maybe_entry = self
+                    .pending_copy_tos
+                    .entry(id)
+                    .or_insert_with(|| Some(HashMap::new()));
+
+                let entry = match maybe_entry {
+                    None => {
+                        // This sink has already responded; we should
+                        // permanently block any messages from it.
+                        return None;
+                    }
+                    Some(entry) => entry,
+                };
+
+                let response = match response {
+                    // An error or a dropped sink means the copy will not
+                    // complete, so there is no need to wait for other parts.
+                    CopyToResponse::Error(_) | CopyToResponse::Dropped => Some(response),
+                    CopyToResponse::RowCount(_) => {
+                        entry.insert(shard_id, response);
+                        if entry.len() == self.parts {
+                            let mut count = 0;
+                            for response in entry.values() {
+                                if let CopyToResponse::RowCount(c) = response {
+                                    count += c;
+                                }
+                            }
+                            Some(CopyToResponse::RowCount(count))
+                        } else {
+                            None
+                        }
+                    }
+                };
+
+                response.map(|response| {
+                    *maybe_entry = None;
+                    Ok(ComputeResponse::CopyToResponse(id, response))
+                })
+            }
+            ComputeResponse::Hydrated(ids) => {
+                let mut new_hydrated = Vec::new();
+                for id in ids {
+                    let parts = self.hydrated_parts.entry(id).or_default();
+                    parts.insert(shard_id);
+                    if parts.len() == self.parts {
+                        self.hydrated_parts.remove(&id);
+                        new_hydrated.push(id);
+                    }
+                }
+
+                if new_hydrated.is_empty() {
+                    None
+                } else {
+                    Some(Ok(ComputeResponse::Hydrated(new_hydrated)))
+                }
+            }
+            ComputeResponse::ArrangementSizes { dataflows, total } => {
+                let mut new_sizes = Vec::new();
+                for (id, size) in dataflows {
+                    let part_sizes = self
+                        .arrangement_sizes
+                        .entry(id)
+                        .or_insert_with(|| vec![0; self.parts]);
+                    part_sizes[shard_id] = size;
+                    new_sizes.push((id, part_sizes.iter().sum()));
+                }
+
+                self.total_arrangement_sizes[shard_id] = total;
+
+                Some(Ok(ComputeResponse::ArrangementSizes {
+                    dataflows: new_sizes,
+                    total: self.total_arrangement_sizes.iter().sum(),
+                }))
+            }
         }
     }
 }
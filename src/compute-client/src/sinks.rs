@@ -15,6 +15,9 @@ use timely::progress::Antichain;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::{GlobalId, RelationDesc};
 use mz_storage_client::controller::CollectionMetadata;
+use mz_storage_client::types::connections::aws::AwsConfig;
+
+use crate::command::ReplicaId;
 
 include!(concat!(env!("OUT_DIR"), "/mz_compute_client.sinks.rs"));
 
@@ -78,6 +81,7 @@ impl RustType<ProtoComputeSinkDesc> for ComputeSinkDesc<CollectionMetadata, mz_r
 pub enum ComputeSinkConnection<S = ()> {
     Subscribe(SubscribeSinkConnection),
     Persist(PersistSinkConnection<S>),
+    CopyToS3(CopyToS3SinkConnection),
 }
 
 impl<S> ComputeSinkConnection<S> {
@@ -86,6 +90,7 @@ impl<S> ComputeSinkConnection<S> {
         match self {
             ComputeSinkConnection::Subscribe(_) => "subscribe",
             ComputeSinkConnection::Persist(_) => "persist",
+            ComputeSinkConnection::CopyToS3(_) => "copy_to_s3",
         }
     }
 
@@ -106,6 +111,7 @@ impl RustType<ProtoComputeSinkConnection> for ComputeSinkConnection<CollectionMe
             kind: Some(match self {
                 ComputeSinkConnection::Subscribe(_) => Kind::Subscribe(()),
                 ComputeSinkConnection::Persist(persist) => Kind::Persist(persist.into_proto()),
+                ComputeSinkConnection::CopyToS3(copy_to) => Kind::CopyToS3(copy_to.into_proto()),
             }),
         }
     }
@@ -118,6 +124,7 @@ impl RustType<ProtoComputeSinkConnection> for ComputeSinkConnection<CollectionMe
         Ok(match kind {
             Kind::Subscribe(_) => ComputeSinkConnection::Subscribe(SubscribeSinkConnection {}),
             Kind::Persist(persist) => ComputeSinkConnection::Persist(persist.into_rust()?),
+            Kind::CopyToS3(copy_to) => ComputeSinkConnection::CopyToS3(copy_to.into_rust()?),
         })
     }
 }
@@ -151,6 +158,108 @@ impl RustType<ProtoPersistSinkConnection> for PersistSinkConnection<CollectionMe
     }
 }
 
+/// A sink that writes the snapshot of a collection at the sink's `as_of` to
+/// objects in S3, for `COPY ... TO` a URL.
+///
+/// Each worker uploads the objects of its share of the snapshot as they fill,
+/// and the sink reports the number of rows written once the snapshot is
+/// complete.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CopyToS3SinkConnection {
+    pub value_desc: RelationDesc,
+    pub bucket: String,
+    /// The prefix of the keys of the objects, without a trailing slash.
+    pub prefix: String,
+    pub aws_connection_id: GlobalId,
+    pub aws_connection: AwsConfig,
+    pub format: CopyToS3Format,
+    /// The size in bytes after which a worker starts a new object.
+    pub max_file_size: u64,
+    /// The replica that uploads the objects.
+    ///
+    /// The sink's dataflow is installed on every replica, but only one of them
+    /// may write the objects.
+    pub target_replica: ReplicaId,
+    /// Whether this replica uploads the objects.
+    ///
+    /// Set by the controller when sending the dataflow to each replica.
+    pub upload: bool,
+}
+
+/// The format of the objects written by a [`CopyToS3SinkConnection`].
+#[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum CopyToS3Format {
+    Text,
+    Csv {
+        /// Whether each object starts with a header line.
+        header: bool,
+    },
+    Parquet,
+}
+
+impl CopyToS3Format {
+    /// The file extension of objects in this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CopyToS3Format::Text => "txt",
+            CopyToS3Format::Csv { .. } => "csv",
+            CopyToS3Format::Parquet => "parquet",
+        }
+    }
+}
+
+impl RustType<ProtoCopyToS3SinkConnection> for CopyToS3SinkConnection {
+    fn into_proto(&self) -> ProtoCopyToS3SinkConnection {
+        use proto_copy_to_s3_sink_connection::{Format, ProtoCsv};
+        ProtoCopyToS3SinkConnection {
+            value_desc: Some(self.value_desc.into_proto()),
+            bucket: self.bucket.clone(),
+            prefix: self.prefix.clone(),
+            aws_connection_id: Some(self.aws_connection_id.into_proto()),
+            aws_connection: Some(self.aws_connection.into_proto()),
+            format: Some(match self.format {
+                CopyToS3Format::Text => Format::Text(()),
+                CopyToS3Format::Csv { header } => Format::Csv(ProtoCsv { header }),
+                CopyToS3Format::Parquet => Format::Parquet(()),
+            }),
+            max_file_size: self.max_file_size,
+            target_replica: self.target_replica,
+            upload: self.upload,
+        }
+    }
+
+    fn from_proto(proto: ProtoCopyToS3SinkConnection) -> Result<Self, TryFromProtoError> {
+        use proto_copy_to_s3_sink_connection::Format;
+        let format = match proto.format {
+            Some(Format::Text(())) => CopyToS3Format::Text,
+            Some(Format::Csv(csv)) => CopyToS3Format::Csv { header: csv.header },
+            Some(Format::Parquet(())) => CopyToS3Format::Parquet,
+            None => {
+                return Err(TryFromProtoError::missing_field(
+                    "ProtoCopyToS3SinkConnection::format",
+                ))
+            }
+        };
+        Ok(CopyToS3SinkConnection {
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoCopyToS3SinkConnection::value_desc")?,
+            bucket: proto.bucket,
+            prefix: proto.prefix,
+            aws_connection_id: proto
+                .aws_connection_id
+                .into_rust_if_some("ProtoCopyToS3SinkConnection::aws_connection_id")?,
+            aws_connection: proto
+                .aws_connection
+                .into_rust_if_some("ProtoCopyToS3SinkConnection::aws_connection")?,
+            format,
+            max_file_size: proto.max_file_size,
+            target_replica: proto.target_replica,
+            upload: proto.upload,
+        })
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct SinkAsOf<T = mz_repr::Timestamp> {
     pub frontier: Antichain<T>,
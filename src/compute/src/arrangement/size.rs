@@ -0,0 +1,342 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Logging of the sizes of arrangements.
+//!
+//! Every arrangement of rows rendered into a dataflow is followed by an
+//! operator that measures the footprint of the arrangement's batches whenever
+//! the arrangement produces a new batch, and logs changes to the
+//! [`ARRANGEMENT_SIZES_LOGGER`]. This includes the arrangements internal to a
+//! dataflow, like those of joins and reductions, not just the arrangements of
+//! the indexes it exports. The operator also logs changes in spilled bytes to
+//! the compute logger, which back `mz_arrangement_sizes`.
+//!
+//! Each worker observes the logged sizes in its [`ArrangementSizes`] and
+//! attributes them to the dataflows whose operators maintain the arrangements,
+//! which lets the compute controller enforce memory limits per dataflow and per
+//! replica.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::rc::Rc;
+
+use differential_dataflow::difference::Semigroup;
+use differential_dataflow::lattice::Lattice;
+use differential_dataflow::operators::arrange::{Arranged, TraceAgent};
+use differential_dataflow::trace::implementations::ord::OrdValBatch;
+use differential_dataflow::trace::{Batch, TraceReader};
+use timely::dataflow::channels::pact::Pipeline;
+use timely::dataflow::operators::Operator;
+use timely::dataflow::Scope;
+use timely::logging::WorkerIdentifier;
+use timely::progress::frontier::AntichainRef;
+
+use mz_repr::GlobalId;
+
+use crate::arrangement::spill::HeapSize;
+use crate::logging::compute::{ComputeEvent, Logger as ComputeLogger};
+use crate::typedefs::RowSpine;
+
+/// The name of the logger that arrangement size changes are logged to.
+pub const ARRANGEMENT_SIZES_LOGGER: &str = "materialize/arrangement-sizes";
+
+/// The logger that arrangement size changes are logged to.
+pub type Logger = timely::logging_core::Logger<ArrangementSizeEvent, WorkerIdentifier>;
+
+/// A change in the footprint of an arrangement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArrangementSizeEvent {
+    /// The identifier of the operator maintaining the arrangement.
+    pub operator: usize,
+    /// The change in bytes held in main memory.
+    pub resident: isize,
+    /// The change in bytes held in files on local disk.
+    pub spilled: isize,
+}
+
+/// Extension trait to log the sizes of arrangements.
+pub trait LogArrangementSize {
+    /// Logs changes to the footprint of the arrangement, if arrangement size
+    /// logging is enabled on the worker.
+    fn log_arrangement_size(self) -> Self;
+}
+
+impl<G, K, V, R> LogArrangementSize for Arranged<G, TraceAgent<RowSpine<K, V, G::Timestamp, R>>>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+    K: Ord + Clone + 'static,
+    V: Ord + Clone + 'static,
+    R: Semigroup,
+    OrdValBatch<K, V, G::Timestamp, R>: Batch<K, V, G::Timestamp, R>,
+{
+    fn log_arrangement_size(self) -> Self {
+        let scope = self.stream.scope();
+        let logger = match scope
+            .log_register()
+            .get::<ArrangementSizeEvent>(ARRANGEMENT_SIZES_LOGGER)
+        {
+            Some(logger) => logger,
+            None => return self,
+        };
+        let compute_logger = scope
+            .log_register()
+            .get::<ComputeEvent>("materialize/compute");
+
+        // The operator only measures the trace, and must not hold back its compaction.
+        let mut trace = self.trace.clone();
+        trace.set_logical_compaction(AntichainRef::new(&[]));
+        trace.set_physical_compaction(AntichainRef::new(&[]));
+        let mut size = LoggedSize {
+            operator: trace.operator().global_id,
+            logger,
+            compute_logger,
+            size: HeapSize::default(),
+        };
+
+        self.stream.sink(Pipeline, "ArrangementSize", move |input| {
+            // New batches indicate that the arrangement has changed, but their
+            // contents are already accounted for by the trace.
+            input.for_each(|_time, _data| {});
+            let mut new_size = HeapSize::default();
+            trace.map_batches(|batch| {
+                new_size += match batch.untracked() {
+                    Some(batch) => HeapSize {
+                        resident: estimate_size(batch),
+                        spilled: 0,
+                    },
+                    None => batch.heap_size(),
+                }
+            });
+            size.update(new_size);
+        });
+        self
+    }
+}
+
+/// Estimates the number of bytes held by the columns of a batch whose size is
+/// not tracked, like one of updates at a timestamp of an iterative scope.
+///
+/// Allocations owned by the keys and values themselves are not accounted for.
+fn estimate_size<K, V, T, R>(batch: &OrdValBatch<K, V, T, R>) -> usize
+where
+    K: Ord + Clone + 'static,
+    V: Ord + Clone + 'static,
+    T: Lattice + Ord + Clone + 'static,
+    R: Semigroup,
+{
+    fn columns<X>(column: &[X]) -> usize {
+        std::mem::size_of_val(column)
+    }
+    let keys = &batch.layer;
+    let vals = &keys.vals;
+    columns(&keys.keys)
+        + columns(&keys.offs)
+        + columns(&vals.keys)
+        + columns(&vals.offs)
+        + columns(&vals.vals.vals)
+}
+
+/// The last logged footprint of an arrangement, which is retracted once the
+/// arrangement is dropped.
+struct LoggedSize {
+    operator: usize,
+    logger: Logger,
+    compute_logger: Option<ComputeLogger>,
+    size: HeapSize,
+}
+
+impl LoggedSize {
+    fn update(&mut self, size: HeapSize) {
+        if size == self.size {
+            return;
+        }
+        let resident = delta(self.size.resident, size.resident);
+        let spilled = delta(self.size.spilled, size.spilled);
+        self.logger.log(ArrangementSizeEvent {
+            operator: self.operator,
+            resident,
+            spilled,
+        });
+        if let Some(logger) = &self.compute_logger {
+            if spilled != 0 {
+                logger.log(ComputeEvent::ArrangementSpilledBytes {
+                    operator: self.operator,
+                    delta: spilled,
+                });
+            }
+        }
+        self.size = size;
+    }
+}
+
+impl Drop for LoggedSize {
+    fn drop(&mut self) {
+        self.update(HeapSize::default());
+    }
+}
+
+fn delta(prev: usize, next: usize) -> isize {
+    isize::try_from(next).expect("size must fit") - isize::try_from(prev).expect("size must fit")
+}
+
+fn apply(size: usize, delta: isize) -> usize {
+    if delta >= 0 {
+        size + delta.unsigned_abs()
+    } else {
+        size.saturating_sub(delta.unsigned_abs())
+    }
+}
+
+/// A dataflow whose arrangements are accounted for.
+#[derive(Debug)]
+struct Dataflow {
+    /// The identifiers of the dataflow's operators.
+    operators: Range<usize>,
+    /// The indexes and sinks exported by the dataflow.
+    exports: Vec<GlobalId>,
+}
+
+/// The footprints of the arrangements of a worker, as logged to the
+/// [`ARRANGEMENT_SIZES_LOGGER`].
+#[derive(Debug, Default)]
+pub struct ArrangementSizes {
+    /// The footprint of each arrangement, by the identifier of the operator
+    /// maintaining it.
+    operators: BTreeMap<usize, HeapSize>,
+    /// Tracked dataflows, by the first of their operator identifiers.
+    dataflows: BTreeMap<usize, Dataflow>,
+}
+
+impl ArrangementSizes {
+    /// Returns a logger that applies the logged events to `sizes`.
+    pub fn logger(sizes: Rc<RefCell<ArrangementSizes>>, worker: WorkerIdentifier) -> Logger {
+        Logger::new(
+            std::time::Instant::now(),
+            std::time::Duration::default(),
+            worker,
+            move |_time, data| {
+                let mut sizes = sizes.borrow_mut();
+                for (_time, _worker, event) in data.drain(..) {
+                    sizes.observe(event);
+                }
+            },
+        )
+    }
+
+    /// Applies a logged change in the footprint of an arrangement.
+    pub fn observe(&mut self, event: ArrangementSizeEvent) {
+        let size = self.operators.entry(event.operator).or_default();
+        size.resident = apply(size.resident, event.resident);
+        size.spilled = apply(size.spilled, event.spilled);
+        if *size == HeapSize::default() {
+            self.operators.remove(&event.operator);
+        }
+    }
+
+    /// Attributes the arrangements of `operators` to a dataflow exporting `exports`.
+    ///
+    /// Replaces any dataflow previously tracked for the same exports, e.g. one
+    /// that was dropped and re-created during reconciliation.
+    pub fn track_dataflow(&mut self, operators: Range<usize>, exports: Vec<GlobalId>) {
+        self.dataflows
+            .retain(|_, dataflow| dataflow.exports != exports);
+        if !operators.is_empty() {
+            self.dataflows
+                .insert(operators.start, Dataflow { operators, exports });
+        }
+    }
+
+    /// Stops tracking the dataflows for which `keep` returns `false` when
+    /// passed their exports.
+    pub fn retain_dataflows<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&[GlobalId]) -> bool,
+    {
+        self.dataflows.retain(|_, dataflow| keep(&dataflow.exports));
+    }
+
+    /// Returns the exports of each tracked dataflow, with the number of bytes
+    /// its arrangements hold in main memory.
+    pub fn dataflow_sizes(&self) -> impl Iterator<Item = (&[GlobalId], usize)> {
+        self.dataflows.values().map(|dataflow| {
+            let size = self
+                .operators
+                .range(dataflow.operators.clone())
+                .map(|(_, size)| size.resident)
+                .sum();
+            (&dataflow.exports[..], size)
+        })
+    }
+
+    /// Returns the number of bytes all arrangements of the worker hold in main
+    /// memory.
+    pub fn resident_bytes(&self) -> usize {
+        self.operators.values().map(|size| size.resident).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(operator: usize, resident: isize) -> ArrangementSizeEvent {
+        ArrangementSizeEvent {
+            operator,
+            resident,
+            spilled: 0,
+        }
+    }
+
+    #[test]
+    fn test_dataflow_sizes() {
+        let mut sizes = ArrangementSizes::default();
+        sizes.track_dataflow(10..20, vec![GlobalId::Transient(1)]);
+        sizes.track_dataflow(20..30, vec![GlobalId::User(1), GlobalId::User(2)]);
+
+        // Arrangements of operators outside of tracked dataflows, like those of
+        // logging dataflows, only count towards the worker.
+        sizes.observe(event(5, 100));
+        sizes.observe(event(12, 1000));
+        sizes.observe(event(15, 10));
+        sizes.observe(event(25, 1));
+        sizes.observe(event(12, -500));
+
+        let dataflow_sizes: Vec<_> = sizes
+            .dataflow_sizes()
+            .map(|(exports, size)| (exports.to_vec(), size))
+            .collect();
+        assert_eq!(
+            dataflow_sizes,
+            vec![
+                (vec![GlobalId::Transient(1)], 510),
+                (vec![GlobalId::User(1), GlobalId::User(2)], 1),
+            ]
+        );
+        assert_eq!(sizes.resident_bytes(), 611);
+
+        // Re-creating a dataflow replaces it.
+        sizes.track_dataflow(30..40, vec![GlobalId::Transient(1)]);
+        sizes.observe(event(35, 7));
+        sizes.retain_dataflows(|exports| exports.iter().all(|id| id.is_transient()));
+        let dataflow_sizes: Vec<_> = sizes
+            .dataflow_sizes()
+            .map(|(exports, size)| (exports.to_vec(), size))
+            .collect();
+        assert_eq!(dataflow_sizes, vec![(vec![GlobalId::Transient(1)], 7)]);
+
+        // Dropped arrangements are retracted.
+        for (operator, resident) in [(5, -100), (12, -500), (15, -10), (25, -1), (35, -7)] {
+            sizes.observe(event(operator, resident));
+        }
+        assert_eq!(sizes.resident_bytes(), 0);
+        assert!(sizes.operators.is_empty());
+    }
+}
@@ -0,0 +1,462 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Spilling of arrangement batches to local disk.
+//!
+//! Arrangements are maintained as spines of immutable batches, in which small,
+//! recent batches are repeatedly merged into fewer, larger ones. The batches in
+//! the lower levels of a spine are large and rarely rewritten, so backing them
+//! by main memory is wasteful. [`SpillBatch`] wraps differential's batch types
+//! and, once spilling has been [enabled](enable), moves each batch of an index
+//! arrangement whose contents take at least [`SPILL_THRESHOLD_BYTES`] into a
+//! memory-mapped file in the replica's scratch directory as soon as it has been
+//! sealed or merged. Its contents are laid out with [`abomonation`], so cursors
+//! and merges read them in place: the operating system is free to write the
+//! pages of spilled batches back to disk and evict them under memory pressure,
+//! and pages them in again once they are visited.
+//!
+//! Every spilled batch occupies one file and one mapping, both of which are
+//! released when the spine drops the batch. As each level of a spine is twice
+//! the size of the level above it, an arrangement holds a number of spilled
+//! batches that is logarithmic in its size. The total number of mappings is
+//! additionally capped at [`MAX_SPILLED_BATCHES`], well below the default
+//! `vm.max_map_count`, beyond which batches stay in memory.
+//!
+//! Only batches of `Row`s at the outer timestamp are spilled, as these back the
+//! indexes maintained by a replica. All other batches are held in memory.
+
+use std::any::Any;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use abomonation::abomonated::Abomonated;
+use anyhow::Context;
+use differential_dataflow::trace::implementations::ord::OrdValBatch;
+use differential_dataflow::trace::{
+    Batch, BatchReader, Batcher, Builder, Cursor, Description, Merger,
+};
+use memmap2::MmapMut;
+use once_cell::sync::OnceCell;
+use timely::progress::frontier::AntichainRef;
+use timely::progress::Antichain;
+use tracing::warn;
+
+use mz_ore::cast::CastFrom;
+use mz_repr::{Diff, Row, Timestamp};
+
+/// The minimum size of batches that are spilled to local disk.
+pub const SPILL_THRESHOLD_BYTES: usize = 64 << 20;
+
+/// The maximum number of batches that are spilled at any time.
+pub const MAX_SPILLED_BATCHES: usize = 16 << 10;
+
+/// The type of batches that may be spilled.
+type IndexBatch = OrdValBatch<Row, Row, Timestamp, Diff>;
+
+/// The directory in which scratch files are created, once spilling is enabled.
+static SCRATCH_DIRECTORY: OnceCell<PathBuf> = OnceCell::new();
+
+/// The number of batches that are currently spilled.
+static SPILLED_BATCHES: AtomicUsize = AtomicUsize::new(0);
+
+/// Enables spilling of batches to files in `directory`.
+pub fn enable(directory: PathBuf) -> Result<(), anyhow::Error> {
+    std::fs::create_dir_all(&directory).with_context(|| {
+        format!(
+            "creating arrangement scratch directory {}",
+            directory.display()
+        )
+    })?;
+    SCRATCH_DIRECTORY
+        .set(directory)
+        .map_err(|_| anyhow::anyhow!("arrangement spilling enabled twice"))
+}
+
+/// The footprint of one or more arrangement batches.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HeapSize {
+    /// The number of bytes held in main memory.
+    pub resident: usize,
+    /// The number of bytes held in files on local disk.
+    pub spilled: usize,
+}
+
+impl std::ops::AddAssign for HeapSize {
+    fn add_assign(&mut self, other: Self) {
+        self.resident += other.resident;
+        self.spilled += other.spilled;
+    }
+}
+
+/// A batch that is held in main memory or in a file on local disk.
+pub struct SpillBatch<B> {
+    contents: Contents<B>,
+    /// The number of bytes occupied by the batch's contents.
+    ///
+    /// Zero for batches other than [`IndexBatch`]es, whose size is not tracked.
+    size: usize,
+}
+
+enum Contents<B> {
+    Resident(B),
+    Spilled(SpilledBatch),
+}
+
+/// An [`IndexBatch`] that has been written to a scratch file.
+struct SpilledBatch {
+    batch: Abomonated<IndexBatch, MmapMut>,
+}
+
+impl Drop for SpilledBatch {
+    fn drop(&mut self) {
+        SPILLED_BATCHES.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl<B: 'static> SpillBatch<B> {
+    /// Wraps `batch`, spilling it if it is large enough and spilling is enabled.
+    fn new(batch: B) -> Self {
+        let index_batch = match (&batch as &dyn Any).downcast_ref::<IndexBatch>() {
+            Some(index_batch) => index_batch,
+            None => {
+                return SpillBatch {
+                    contents: Contents::Resident(batch),
+                    size: 0,
+                }
+            }
+        };
+
+        let size = abomonation::measure(index_batch);
+        if size >= SPILL_THRESHOLD_BYTES {
+            if let Some(directory) = SCRATCH_DIRECTORY.get() {
+                if SPILLED_BATCHES.fetch_add(1, Ordering::Relaxed) < MAX_SPILLED_BATCHES {
+                    match spill(directory, index_batch, size) {
+                        Ok(batch) => {
+                            return SpillBatch {
+                                contents: Contents::Spilled(SpilledBatch { batch }),
+                                size,
+                            }
+                        }
+                        Err(e) => warn!("failed to spill arrangement batch: {e:#}"),
+                    }
+                }
+                SPILLED_BATCHES.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+        SpillBatch {
+            contents: Contents::Resident(batch),
+            size,
+        }
+    }
+
+    /// Returns the wrapped batch.
+    fn batch(&self) -> &B {
+        match &self.contents {
+            Contents::Resident(batch) => batch,
+            Contents::Spilled(spilled) => (&*spilled.batch as &dyn Any)
+                .downcast_ref()
+                .expect("only index batches are spilled"),
+        }
+    }
+
+    /// Returns the wrapped batch, if it is held in main memory and its size is
+    /// not tracked.
+    pub fn untracked(&self) -> Option<&B> {
+        match &self.contents {
+            Contents::Resident(batch) if self.size == 0 => Some(batch),
+            _ => None,
+        }
+    }
+
+    /// Returns the footprint of the batch.
+    ///
+    /// The footprint of batches whose size is not tracked is zero.
+    pub fn heap_size(&self) -> HeapSize {
+        match &self.contents {
+            Contents::Resident(_) => HeapSize {
+                resident: self.size,
+                spilled: 0,
+            },
+            Contents::Spilled(_) => HeapSize {
+                resident: 0,
+                spilled: self.size,
+            },
+        }
+    }
+}
+
+/// Writes `batch`, whose contents take `size` bytes, to a file in `directory`.
+///
+/// The file is unlinked immediately, so that it is removed once the returned
+/// mapping is dropped or the process exits.
+fn spill(
+    directory: &Path,
+    batch: &IndexBatch,
+    size: usize,
+) -> Result<Abomonated<IndexBatch, MmapMut>, anyhow::Error> {
+    let file = tempfile::tempfile_in(directory)?;
+    file.set_len(u64::cast_from(size))?;
+    // SAFETY: the file is private to this process, and is not resized while mapped.
+    let mut mmap = unsafe { MmapMut::map_mut(&file)? };
+    // SAFETY: the mapping is `size` bytes, as measured from `batch`.
+    unsafe { abomonation::encode(batch, &mut &mut mmap[..])? };
+    // SAFETY: the mapping was just encoded from an `IndexBatch`, and its bytes
+    // remain at a fixed address until it is dropped. The mapping is page
+    // aligned, and all data in it stays aligned, as rows pad their data to the
+    // largest alignment of the batch's other contents.
+    unsafe { Abomonated::new(mmap) }.context("decoding spilled batch")
+}
+
+impl<K, V, T, R, B> BatchReader<K, V, T, R> for SpillBatch<B>
+where
+    B: BatchReader<K, V, T, R> + 'static,
+{
+    type Cursor = SpillCursor<K, V, T, R, B>;
+
+    fn cursor(&self) -> Self::Cursor {
+        SpillCursor {
+            cursor: self.batch().cursor(),
+            phantom: PhantomData,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.batch().len()
+    }
+
+    fn description(&self) -> &Description<T> {
+        self.batch().description()
+    }
+}
+
+/// A cursor over the contents of a [`SpillBatch`].
+pub struct SpillCursor<K, V, T, R, B: BatchReader<K, V, T, R>> {
+    cursor: B::Cursor,
+    phantom: PhantomData<(K, V, T, R)>,
+}
+
+impl<K, V, T, R, B> Cursor<K, V, T, R> for SpillCursor<K, V, T, R, B>
+where
+    B: BatchReader<K, V, T, R> + 'static,
+{
+    type Storage = SpillBatch<B>;
+
+    #[inline]
+    fn key_valid(&self, storage: &Self::Storage) -> bool {
+        self.cursor.key_valid(storage.batch())
+    }
+
+    #[inline]
+    fn val_valid(&self, storage: &Self::Storage) -> bool {
+        self.cursor.val_valid(storage.batch())
+    }
+
+    #[inline]
+    fn key<'a>(&self, storage: &'a Self::Storage) -> &'a K {
+        self.cursor.key(storage.batch())
+    }
+
+    #[inline]
+    fn val<'a>(&self, storage: &'a Self::Storage) -> &'a V {
+        self.cursor.val(storage.batch())
+    }
+
+    #[inline]
+    fn map_times<L: FnMut(&T, &R)>(&mut self, storage: &Self::Storage, logic: L) {
+        self.cursor.map_times(storage.batch(), logic)
+    }
+
+    #[inline]
+    fn step_key(&mut self, storage: &Self::Storage) {
+        self.cursor.step_key(storage.batch())
+    }
+
+    #[inline]
+    fn seek_key(&mut self, storage: &Self::Storage, key: &K) {
+        self.cursor.seek_key(storage.batch(), key)
+    }
+
+    #[inline]
+    fn step_val(&mut self, storage: &Self::Storage) {
+        self.cursor.step_val(storage.batch())
+    }
+
+    #[inline]
+    fn seek_val(&mut self, storage: &Self::Storage, val: &V) {
+        self.cursor.seek_val(storage.batch(), val)
+    }
+
+    #[inline]
+    fn rewind_keys(&mut self, storage: &Self::Storage) {
+        self.cursor.rewind_keys(storage.batch())
+    }
+
+    #[inline]
+    fn rewind_vals(&mut self, storage: &Self::Storage) {
+        self.cursor.rewind_vals(storage.batch())
+    }
+}
+
+impl<K, V, T, R, B> Batch<K, V, T, R> for SpillBatch<B>
+where
+    B: Batch<K, V, T, R> + 'static,
+{
+    type Batcher = SpillBatcher<K, V, T, R, B>;
+    type Builder = SpillBuilder<K, V, T, R, B>;
+    type Merger = SpillMerger<K, V, T, R, B>;
+}
+
+/// Batches updates into [`SpillBatch`]es.
+pub struct SpillBatcher<K, V, T, R, B: Batch<K, V, T, R>> {
+    batcher: B::Batcher,
+}
+
+impl<K, V, T, R, B> Batcher<K, V, T, R, SpillBatch<B>> for SpillBatcher<K, V, T, R, B>
+where
+    B: Batch<K, V, T, R> + 'static,
+{
+    fn new() -> Self {
+        SpillBatcher {
+            batcher: <B::Batcher as Batcher<K, V, T, R, B>>::new(),
+        }
+    }
+
+    fn push_batch(&mut self, batch: &mut Vec<((K, V), T, R)>) {
+        self.batcher.push_batch(batch)
+    }
+
+    fn seal(&mut self, upper: Antichain<T>) -> SpillBatch<B> {
+        SpillBatch::new(self.batcher.seal(upper))
+    }
+
+    fn frontier(&mut self) -> AntichainRef<T> {
+        self.batcher.frontier()
+    }
+}
+
+/// Builds [`SpillBatch`]es from ordered updates.
+pub struct SpillBuilder<K, V, T, R, B: Batch<K, V, T, R>> {
+    builder: B::Builder,
+}
+
+impl<K, V, T, R, B> Builder<K, V, T, R, SpillBatch<B>> for SpillBuilder<K, V, T, R, B>
+where
+    B: Batch<K, V, T, R> + 'static,
+{
+    fn new() -> Self {
+        SpillBuilder {
+            builder: <B::Builder as Builder<K, V, T, R, B>>::new(),
+        }
+    }
+
+    fn with_capacity(cap: usize) -> Self {
+        SpillBuilder {
+            builder: <B::Builder as Builder<K, V, T, R, B>>::with_capacity(cap),
+        }
+    }
+
+    fn push(&mut self, element: (K, V, T, R)) {
+        self.builder.push(element)
+    }
+
+    fn done(self, lower: Antichain<T>, upper: Antichain<T>, since: Antichain<T>) -> SpillBatch<B> {
+        SpillBatch::new(self.builder.done(lower, upper, since))
+    }
+}
+
+/// Merges [`SpillBatch`]es, reading spilled inputs in place.
+pub struct SpillMerger<K, V, T, R, B: Batch<K, V, T, R>> {
+    merger: B::Merger,
+}
+
+impl<K, V, T, R, B> Merger<K, V, T, R, SpillBatch<B>> for SpillMerger<K, V, T, R, B>
+where
+    B: Batch<K, V, T, R> + 'static,
+{
+    fn new(
+        source1: &SpillBatch<B>,
+        source2: &SpillBatch<B>,
+        compaction_frontier: Option<AntichainRef<T>>,
+    ) -> Self {
+        SpillMerger {
+            merger: B::begin_merge(source1.batch(), source2.batch(), compaction_frontier),
+        }
+    }
+
+    fn work(&mut self, source1: &SpillBatch<B>, source2: &SpillBatch<B>, fuel: &mut isize) {
+        self.merger.work(source1.batch(), source2.batch(), fuel)
+    }
+
+    fn done(self) -> SpillBatch<B> {
+        SpillBatch::new(self.merger.done())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::Datum;
+
+    use super::*;
+
+    /// Returns the updates held by `batch`, in order.
+    fn updates(batch: &IndexBatch) -> Vec<(Row, Row, Timestamp, Diff)> {
+        let mut updates = Vec::new();
+        let mut cursor = batch.cursor();
+        while cursor.key_valid(batch) {
+            while cursor.val_valid(batch) {
+                let key = cursor.key(batch);
+                let val = cursor.val(batch);
+                cursor.map_times(batch, |time, diff| {
+                    updates.push((key.clone(), val.clone(), *time, *diff))
+                });
+                cursor.step_val(batch);
+            }
+            cursor.step_key(batch);
+        }
+        updates
+    }
+
+    fn assert_aligned<T>(slice: &[T]) {
+        assert_eq!(slice.as_ptr() as usize % std::mem::align_of::<T>(), 0);
+    }
+
+    #[test]
+    fn test_spill_roundtrip() {
+        // Rows of both in-line and spilled data, of all lengths modulo the word size.
+        let string = "x".repeat(64);
+        let mut input = Vec::new();
+        for len in 0..string.len() {
+            let key = Row::pack_slice(&[Datum::String(&string[..len])]);
+            for val_len in [1, 29, len] {
+                let val = Row::pack_slice(&[Datum::String(&string[..val_len])]);
+                for time in 0..3 {
+                    input.push(((key.clone(), val.clone()), Timestamp::from(time), 1));
+                }
+            }
+        }
+        let mut batcher = <IndexBatch as Batch<Row, Row, Timestamp, Diff>>::Batcher::new();
+        batcher.push_batch(&mut input);
+        let batch = batcher.seal(Antichain::from_elem(Timestamp::from(3)));
+        let expected = updates(&batch);
+        assert!(!expected.is_empty());
+
+        let directory = tempfile::tempdir().unwrap();
+        let size = abomonation::measure(&batch);
+        let spilled = spill(directory.path(), &batch, size).unwrap();
+
+        assert_eq!(updates(&spilled), expected);
+        let layer = &spilled.layer;
+        assert_aligned(&layer.keys);
+        assert_aligned(&layer.offs);
+        assert_aligned(&layer.vals.keys);
+        assert_aligned(&layer.vals.offs);
+        assert_aligned(&layer.vals.vals.vals);
+    }
+}
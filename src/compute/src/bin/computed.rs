@@ -11,11 +11,13 @@ use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::process;
 
+use anyhow::Context;
 use axum::routing;
 use once_cell::sync::Lazy;
 use tracing::info;
 
 use mz_build_info::{build_info, BuildInfo};
+use mz_cloud_resources::AwsExternalIdPrefix;
 use mz_compute_client::service::proto_compute_server::ProtoComputeServer;
 use mz_orchestrator_tracing::TracingCliArgs;
 use mz_ore::cli::{self, CliConfig};
@@ -24,6 +26,7 @@ use mz_ore::now::SYSTEM_TIME;
 use mz_pid_file::PidFile;
 use mz_service::grpc::GrpcServer;
 use mz_service::secrets::SecretsReaderCliArgs;
+use mz_storage_client::types::connections::ConnectionContext;
 
 // Disable jemalloc on macOS, as it is not well supported [0][1][2].
 // The issues present as runaway latency on load test workloads that are
@@ -65,6 +68,30 @@ struct Args {
         default_value = "127.0.0.1:6878"
     )]
     internal_http_listen_addr: SocketAddr,
+    /// The URL of the persist pubsub server in environmentd.
+    ///
+    /// If unset, persist readers poll for shard changes instead.
+    #[clap(long, env = "PERSIST_PUBSUB_URL", value_name = "URL")]
+    persist_pubsub_url: Option<String>,
+    /// A local directory in which to cache reads of persist blobs, so that
+    /// they needn't be fetched again, e.g. after a restart.
+    #[clap(long, env = "PERSIST_BLOB_DISK_CACHE_DIR", value_name = "PATH")]
+    persist_blob_disk_cache_dir: Option<PathBuf>,
+    /// The maximum size in bytes of the persist blob disk cache.
+    #[clap(long, env = "PERSIST_BLOB_DISK_CACHE_MAX_SIZE", value_name = "N")]
+    persist_blob_disk_cache_max_size: Option<usize>,
+    /// A local directory to which large arrangement batches may be spilled.
+    ///
+    /// If unset, arrangements are held in memory entirely.
+    #[clap(long, env = "SCRATCH_DIRECTORY", value_name = "PATH")]
+    scratch_directory: Option<PathBuf>,
+
+    // === Cloud options. ===
+    /// An external ID to be supplied to all AWS AssumeRole operations.
+    ///
+    /// Details: <https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles_create_for-user_externalid.html>
+    #[clap(long, env = "AWS_EXTERNAL_ID", value_name = "ID", parse(from_str = AwsExternalIdPrefix::new_from_cli_argument_or_environment_variable))]
+    aws_external_id: Option<AwsExternalIdPrefix>,
 
     // === Process orchestrator options. ===
     /// Where to write a PID lock file.
@@ -158,10 +185,24 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
         );
     }
 
+    let secrets_reader = args
+        .secrets
+        .load()
+        .await
+        .context("loading secrets reader")?;
     let config = mz_compute::server::Config {
         build_info: &BUILD_INFO,
         metrics_registry,
         now: SYSTEM_TIME.clone(),
+        persist_pubsub_url: args.persist_pubsub_url,
+        persist_blob_disk_cache_dir: args.persist_blob_disk_cache_dir,
+        persist_blob_disk_cache_max_size_bytes: args.persist_blob_disk_cache_max_size,
+        scratch_directory: args.scratch_directory,
+        connection_context: ConnectionContext::from_cli_args(
+            &args.tracing.log_filter.inner,
+            args.aws_external_id,
+            secrets_reader,
+        ),
     };
 
     let (_server, client_builder) = mz_compute::server::serve(config)?;
@@ -32,21 +32,28 @@ use mz_compute_client::command::{
 };
 use mz_compute_client::logging::LoggingConfig;
 use mz_compute_client::plan::Plan;
-use mz_compute_client::response::{ComputeResponse, PeekResponse, SubscribeResponse};
+use mz_compute_client::response::{
+    ComputeResponse, CopyToResponse, PeekResponse, SubscribeResponse,
+};
 use mz_ore::cast::CastFrom;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_persist_client::cache::PersistClientCache;
 use mz_repr::{Diff, GlobalId, Row, Timestamp};
 use mz_storage_client::controller::CollectionMetadata;
+use mz_storage_client::types::connections::ConnectionContext;
 use mz_storage_client::types::errors::DataflowError;
 use mz_timely_util::activator::RcActivator;
 use mz_timely_util::operator::CollectionExt;
 use tracing::{error, span, Level};
 
 use crate::arrangement::manager::{TraceBundle, TraceManager};
+use crate::arrangement::size::{ArrangementSizes, ARRANGEMENT_SIZES_LOGGER};
 use crate::logging;
 use crate::logging::compute::ComputeEvent;
 
+/// The minimum interval between reports of the sizes of arrangements.
+pub const ARRANGEMENT_SIZES_REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Worker-local state that is maintained across dataflows.
 ///
 /// This state is restricted to the COMPUTE state, the deterministic, idempotent work
@@ -62,6 +69,8 @@ pub struct ComputeState {
     /// The entries are pairs of sink identifier (to identify the subscribe instance)
     /// and the response itself.
     pub subscribe_response_buffer: Rc<RefCell<Vec<(GlobalId, SubscribeResponse)>>>,
+    /// Shared buffer with `COPY ... TO` sink operator instances by which they can respond.
+    pub copy_to_response_buffer: Rc<RefCell<Vec<(GlobalId, CopyToResponse)>>>,
     /// Frontier of sink writes (all subsequent writes will be at times at or
     /// equal to this frontier)
     pub sink_write_frontiers: HashMap<GlobalId, Rc<RefCell<Antichain<Timestamp>>>>,
@@ -69,13 +78,29 @@ pub struct ComputeState {
     pub pending_peeks: HashMap<Uuid, PendingPeek>,
     /// Tracks the frontier information that has been sent over `response_tx`.
     pub reported_frontiers: HashMap<GlobalId, Antichain<Timestamp>>,
+    /// The `as_of` frontiers of exported indexes and sinks, used to report when
+    /// they have finished processing their initial snapshot.
+    pub export_as_ofs: HashMap<GlobalId, Antichain<Timestamp>>,
     /// Collections that were recently dropped and whose removal needs to be reported.
     pub dropped_collections: Vec<GlobalId>,
+    /// The footprints of the worker's arrangements, as logged by the operators
+    /// maintaining them.
+    pub arrangement_sizes: Rc<RefCell<ArrangementSizes>>,
+    /// Tracks the number of bytes held in memory by the arrangements of each
+    /// dataflow, by its exports, that has been sent over `response_tx`.
+    pub reported_arrangement_sizes: HashMap<GlobalId, u64>,
+    /// Tracks the number of bytes held in memory by all arrangements of the
+    /// worker that has been sent over `response_tx`.
+    pub reported_total_arrangement_size: Option<u64>,
+    /// The time at which the sizes of arrangements were last reported.
+    pub last_arrangement_sizes_report: Instant,
     /// The logger, from Timely's logging framework, if logs are enabled.
     pub compute_logger: Option<logging::compute::Logger>,
     /// A process-global cache of (blob_uri, consensus_uri) -> PersistClient.
     /// This is intentionally shared between workers.
     pub persist_clients: Arc<Mutex<PersistClientCache>>,
+    /// Configuration for connections to external systems.
+    pub connection_context: ConnectionContext,
     /// History of commands received by this workers and all its peers.
     pub command_history: ComputeCommandHistory,
     /// Max size in bytes of any result.
@@ -134,6 +159,16 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
     }
 
     fn handle_create_instance(&mut self, config: InstanceConfig) {
+        // Arrangement sizes are logged independently of the logging configuration, as the
+        // controller relies on them to enforce memory limits. The logger must be registered
+        // before any arrangements are built, including those of the logging dataflows.
+        let logger = ArrangementSizes::logger(
+            Rc::clone(&self.compute_state.arrangement_sizes),
+            self.timely_worker.index(),
+        );
+        self.timely_worker
+            .log_register()
+            .insert_logger(ARRANGEMENT_SIZES_LOGGER, logger);
         self.initialize_logging(&config.logging);
     }
 
@@ -164,6 +199,11 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
                         "existing frontier {frontier:?} for newly created dataflow id {object_id}"
                     );
                 }
+                if let Some(as_of) = &dataflow.as_of {
+                    self.compute_state
+                        .export_as_ofs
+                        .insert(object_id, as_of.clone());
+                }
 
                 // Log dataflow construction, frontier construction, and any dependencies.
                 if let Some(logger) = self.compute_state.compute_logger.as_mut() {
@@ -182,7 +222,15 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
                 }
             }
 
+            // Attribute the arrangements of the dataflow's operators to its exports.
+            let exports = dataflow.export_ids().collect();
+            let operators_start = self.timely_worker.peek_identifier();
             crate::render::build_compute_dataflow(self.timely_worker, self.compute_state, dataflow);
+            let operators_end = self.timely_worker.peek_identifier();
+            self.compute_state
+                .arrangement_sizes
+                .borrow_mut()
+                .track_dataflow(operators_start..operators_end, exports);
         }
     }
 
@@ -200,6 +248,8 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
                 // Index-specific work:
                 self.compute_state.traces.del_trace(&id);
 
+                self.compute_state.export_as_ofs.remove(&id);
+
                 // Work common to sinks and indexes (removing frontier tracking and cleaning up logging).
                 let prev_frontier = self
                     .compute_state
@@ -566,11 +616,18 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
         self.timely_worker
             .log_register()
             .remove("materialize/compute");
+        self.timely_worker
+            .log_register()
+            .remove(ARRANGEMENT_SIZES_LOGGER);
     }
 
     /// Send progress information to the coordinator.
+    ///
+    /// This includes reporting collections that have become hydrated, i.e., whose frontiers
+    /// have advanced past their `as_of` for the first time.
     pub fn report_compute_frontiers(&mut self) {
         let mut new_uppers = Vec::new();
+        let mut new_hydrated = Vec::new();
 
         let mut update_frontier = |id, new_frontier: &Antichain<Timestamp>| {
             let prev_frontier = self.compute_state.reported_frontiers.get_mut(&id);
@@ -591,6 +648,14 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
                 }
             }
 
+            if let Some(as_of) = self.compute_state.export_as_ofs.get(&id) {
+                if PartialOrder::less_equal(prev_frontier, as_of)
+                    && !PartialOrder::less_equal(new_frontier, as_of)
+                {
+                    new_hydrated.push(id);
+                }
+            }
+
             new_uppers.push((id, new_frontier.clone()));
             prev_frontier.clone_from(new_frontier);
         };
@@ -608,6 +673,69 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
         if !new_uppers.is_empty() {
             self.send_compute_response(ComputeResponse::FrontierUppers(new_uppers));
         }
+        if !new_hydrated.is_empty() {
+            self.send_compute_response(ComputeResponse::Hydrated(new_hydrated));
+        }
+    }
+
+    /// Report changes in the sizes of maintained arrangements.
+    ///
+    /// The number of bytes the arrangements of each dataflow hold in memory is reported to the
+    /// controller under each of the dataflow's exports, together with the number of bytes all
+    /// arrangements of the worker hold in memory. The controller enforces memory limits based on
+    /// them.
+    ///
+    /// Changes are reported at most once per [`ARRANGEMENT_SIZES_REPORT_INTERVAL`].
+    pub fn report_arrangement_sizes(&mut self) {
+        if self.compute_state.last_arrangement_sizes_report.elapsed()
+            < ARRANGEMENT_SIZES_REPORT_INTERVAL
+        {
+            return;
+        }
+        self.compute_state.last_arrangement_sizes_report = Instant::now();
+
+        // Apply any buffered size changes.
+        self.timely_worker.log_register().flush();
+
+        let compute_state = &mut *self.compute_state;
+        let arrangement_sizes = Rc::clone(&compute_state.arrangement_sizes);
+        let mut arrangement_sizes = arrangement_sizes.borrow_mut();
+        arrangement_sizes.retain_dataflows(|exports| {
+            exports
+                .iter()
+                .any(|id| compute_state.collection_exists(*id))
+        });
+
+        let mut new_sizes = Vec::new();
+        let mut exported = BTreeSet::new();
+        for (exports, size) in arrangement_sizes.dataflow_sizes() {
+            let size = u64::cast_from(size);
+            for id in exports {
+                if !compute_state.collection_exists(*id) {
+                    continue;
+                }
+                exported.insert(*id);
+                let prev_size = compute_state.reported_arrangement_sizes.insert(*id, size);
+                if prev_size != Some(size) {
+                    new_sizes.push((*id, size));
+                }
+            }
+        }
+        compute_state
+            .reported_arrangement_sizes
+            .retain(|id, _| exported.contains(id));
+
+        let total = u64::cast_from(arrangement_sizes.resident_bytes());
+        let total_changed = compute_state.reported_total_arrangement_size != Some(total);
+        compute_state.reported_total_arrangement_size = Some(total);
+        drop(arrangement_sizes);
+
+        if !new_sizes.is_empty() || total_changed {
+            self.send_compute_response(ComputeResponse::ArrangementSizes {
+                dataflows: new_sizes,
+                total,
+            });
+        }
     }
 
     /// Report dropped collections to the controller.
@@ -683,6 +811,14 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
         }
     }
 
+    /// Scan the shared `COPY ... TO` response buffer, and forward results along.
+    pub fn process_copy_tos(&mut self) {
+        let mut copy_to_responses = self.compute_state.copy_to_response_buffer.borrow_mut();
+        for (sink_id, response) in copy_to_responses.drain(..) {
+            self.send_compute_response(ComputeResponse::CopyToResponse(sink_id, response));
+        }
+    }
+
     /// Send a response to the coordinator.
     fn send_compute_response(&self, response: ComputeResponse) {
         // Ignore send errors because the coordinator is free to ignore our
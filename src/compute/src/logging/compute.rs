@@ -31,6 +31,7 @@ use mz_repr::{Datum, DatumVec, GlobalId, Row, Timestamp};
 use mz_timely_util::activator::RcActivator;
 use mz_timely_util::replay::MzReplay;
 
+use crate::arrangement::size::LogArrangementSize;
 use crate::compute_state::ComputeState;
 use crate::logging::persist::persist_sink;
 use crate::logging::{ComputeLog, LogVariant};
@@ -57,6 +58,28 @@ pub enum ComputeEvent {
     Frontier(GlobalId, Timestamp, i64),
     // Available frontier information for source instantiations.
     SourceFrontier(GlobalId, GlobalId, Timestamp, i8),
+    /// The operators rendered for a node of the LIR plan of an exported dataflow.
+    LirMapping {
+        /// Globally unique identifier for the dataflow export.
+        export_id: GlobalId,
+        /// The identifier of the LIR node, unique within the dataflow.
+        lir_id: usize,
+        /// A description of the LIR node's operator.
+        operator: String,
+        /// The depth of the LIR node in its plan.
+        nesting: usize,
+        /// The identifier of the first operator rendered for the LIR node.
+        operator_id_start: usize,
+        /// The identifier following the last operator rendered for the LIR node.
+        operator_id_end: usize,
+    },
+    /// A change in the number of bytes an arrangement has spilled to local disk.
+    ArrangementSpilledBytes {
+        /// The identifier of the operator maintaining the arrangement.
+        operator: usize,
+        /// The change in spilled bytes.
+        delta: isize,
+    },
 }
 
 /// A logged peek event.
@@ -123,11 +146,14 @@ pub fn construct<A: Allocate>(
         let (mut frontier_delay_out, frontier_delay) = demux.new_output();
         let (mut peek_out, peek) = demux.new_output();
         let (mut peek_duration_out, peek_duration) = demux.new_output();
+        let (mut lir_mapping_out, lir_mapping) = demux.new_output();
+        let (mut arrangement_spilled_bytes_out, arrangement_spilled_bytes) = demux.new_output();
 
         let mut demux_buffer = Vec::new();
         demux.build(move |_capability| {
             let mut active_dataflows = HashMap::new();
             let mut peek_stash = HashMap::new();
+            let mut lir_mappings = HashMap::<(GlobalId, usize), Vec<Row>>::new();
             let mut storage_sources = HashMap::<
                 (GlobalId, usize),
                 HashMap<GlobalId, (VecDeque<(mz_repr::Timestamp, u128)>, HashMap<u128, i32>)>,
@@ -140,6 +166,8 @@ pub fn construct<A: Allocate>(
                 let mut frontier_delay = frontier_delay_out.activate();
                 let mut peek = peek_out.activate();
                 let mut peek_duration = peek_duration_out.activate();
+                let mut lir_mapping = lir_mapping_out.activate();
+                let mut arrangement_spilled_bytes = arrangement_spilled_bytes_out.activate();
 
                 input.for_each(|time, data| {
                     data.swap(&mut demux_buffer);
@@ -151,6 +179,9 @@ pub fn construct<A: Allocate>(
                     let mut frontier_delay_session = frontier_delay.session(&time);
                     let mut peek_session = peek.session(&time);
                     let mut peek_duration_session = peek_duration.session(&time);
+                    let mut lir_mapping_session = lir_mapping.session(&time);
+                    let mut arrangement_spilled_bytes_session =
+                        arrangement_spilled_bytes.session(&time);
 
                     for (time, worker, datum) in demux_buffer.drain(..) {
                         let time_ms = (((time.as_millis() / interval_ms) + 1) * interval_ms)
@@ -188,6 +219,10 @@ pub fn construct<A: Allocate>(
                                             key.0, worker
                                         ),
                                     }
+                                    // Retract the LIR mapping of the dataflow, if any.
+                                    for row in lir_mappings.remove(key).into_iter().flatten() {
+                                        lir_mapping_session.give((row, time_ms, -1));
+                                    }
                                     // dataflow may or may not be associated to a storage
                                     // source instantiation. Report removal if so.
                                     if let Some(source_map) = storage_sources.remove(key) {
@@ -290,6 +325,36 @@ pub fn construct<A: Allocate>(
                                     }
                                 }
                             }
+                            ComputeEvent::LirMapping {
+                                export_id,
+                                lir_id,
+                                operator,
+                                nesting,
+                                operator_id_start,
+                                operator_id_end,
+                            } => {
+                                let row = Row::pack_slice(&[
+                                    Datum::String(&export_id.to_string()),
+                                    Datum::UInt64(u64::cast_from(lir_id)),
+                                    Datum::UInt64(u64::cast_from(worker)),
+                                    Datum::String(&operator),
+                                    Datum::UInt64(u64::cast_from(nesting)),
+                                    Datum::UInt64(u64::cast_from(operator_id_start)),
+                                    Datum::UInt64(u64::cast_from(operator_id_end)),
+                                ]);
+                                lir_mapping_session.give((row.clone(), time_ms, 1));
+                                lir_mappings
+                                    .entry((export_id, worker))
+                                    .or_default()
+                                    .push(row);
+                            }
+                            ComputeEvent::ArrangementSpilledBytes { operator, delta } => {
+                                arrangement_spilled_bytes_session.give((
+                                    (operator, worker),
+                                    time_ms,
+                                    i64::cast_from(delta),
+                                ));
+                            }
                             ComputeEvent::Peek(peek, is_install) => {
                                 let key = (worker, peek.uuid);
                                 if is_install {
@@ -386,6 +451,17 @@ pub fn construct<A: Allocate>(
             }
         });
 
+        let lir_mapping = lir_mapping.as_collection();
+
+        let arrangement_spilled_bytes = arrangement_spilled_bytes.as_collection().map({
+            move |(operator, worker)| {
+                Row::pack_slice(&[
+                    Datum::UInt64(u64::cast_from(operator)),
+                    Datum::UInt64(u64::cast_from(worker)),
+                ])
+            }
+        });
+
         let logs = vec![
             (
                 LogVariant::Compute(ComputeLog::DataflowCurrent),
@@ -409,6 +485,11 @@ pub fn construct<A: Allocate>(
             ),
             (LogVariant::Compute(ComputeLog::PeekCurrent), peek_current),
             (LogVariant::Compute(ComputeLog::PeekDuration), peek_duration),
+            (LogVariant::Compute(ComputeLog::LirMapping), lir_mapping),
+            (
+                LogVariant::Compute(ComputeLog::ArrangementSpilledBytes),
+                arrangement_spilled_bytes,
+            ),
         ];
 
         let mut result = std::collections::HashMap::new();
@@ -436,6 +517,7 @@ pub fn construct<A: Allocate>(
                         }
                     })
                     .arrange_named::<RowSpine<_, _, _, _>>(&format!("ArrangeByKey {:?}", variant))
+                    .log_arrangement_size()
                     .trace;
                 result.insert(variant.clone(), (trace, Rc::clone(&token)));
             }
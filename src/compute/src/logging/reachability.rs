@@ -30,6 +30,7 @@ use mz_repr::{Datum, Diff, Row, RowArena, Timestamp};
 use mz_timely_util::activator::RcActivator;
 use mz_timely_util::replay::MzReplay;
 
+use crate::arrangement::size::LogArrangementSize;
 use crate::compute_state::ComputeState;
 use crate::logging::persist::persist_sink;
 use crate::logging::{ConsolidateBuffer, LogVariant, TimelyLog};
@@ -217,6 +218,7 @@ pub fn construct<A: Allocate>(
 
                     let trace = updates
                         .arrange_named::<RowSpine<_, _, _, _>>(&format!("Arrange {:?}", variant))
+                        .log_arrangement_size()
                         .trace;
                     result.insert(variant.clone(), (trace, Rc::clone(&token)));
                 }
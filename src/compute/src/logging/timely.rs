@@ -29,6 +29,7 @@ use mz_repr::{datum_list_size, datum_size, Datum, DatumVec, Diff, Row, Timestamp
 use mz_timely_util::activator::RcActivator;
 use mz_timely_util::replay::MzReplay;
 
+use crate::arrangement::size::LogArrangementSize;
 use crate::compute_state::ComputeState;
 use crate::logging::persist::persist_sink;
 use crate::logging::{ConsolidateBuffer, LogVariant, TimelyLog};
@@ -490,6 +491,7 @@ pub fn construct<A: Allocate>(
 
                 let trace = rows
                     .arrange_named::<RowSpine<_, _, _, _>>(&format!("ArrangeByKey {:?}", variant))
+                    .log_arrangement_size()
                     .trace;
                 result.insert(variant.clone(), (trace, Rc::clone(&token)));
             }
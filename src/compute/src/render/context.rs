@@ -35,6 +35,7 @@ use mz_storage_client::controller::CollectionMetadata;
 use mz_storage_client::types::errors::DataflowError;
 use mz_timely_util::operator::CollectionExt;
 
+use crate::arrangement::size::LogArrangementSize;
 use crate::typedefs::{ErrSpine, RowSpine, TraceErrHandle, TraceRowHandle};
 
 // Local type definition to avoid the horror in signatures.
@@ -83,6 +84,28 @@ where
     pub until: Antichain<T>,
     /// Bindings of identifiers to collections.
     pub bindings: BTreeMap<Id, CollectionBundle<S, V, T>>,
+    /// The operators rendered for each LIR node, indexed by the order in which
+    /// the nodes were visited.
+    pub lir_mapping: Vec<LirMapping>,
+    /// The depth of the LIR node currently being rendered.
+    pub(crate) lir_nesting: usize,
+    /// The identifier of the first operator not yet attributed to an LIR node.
+    pub(crate) operator_watermark: usize,
+}
+
+/// The operators rendered for a node of an LIR plan.
+#[derive(Clone, Debug)]
+pub struct LirMapping {
+    /// A description of the node's operator.
+    pub operator: String,
+    /// The depth of the node in its plan.
+    pub nesting: usize,
+    /// The identifier of the first operator rendered for the node.
+    pub operator_id_start: usize,
+    /// The identifier following the last operator rendered for the node.
+    ///
+    /// Operators rendered for the node's inputs are not included in the range.
+    pub operator_id_end: usize,
 }
 
 impl<S: Scope, V: Data> Context<S, V>
@@ -105,6 +128,9 @@ where
             as_of_frontier,
             until: dataflow.until.clone(),
             bindings: BTreeMap::new(),
+            lir_mapping: Vec::new(),
+            lir_nesting: 0,
+            operator_watermark: 0,
         }
     }
 }
@@ -625,7 +651,9 @@ where
                     Ok::<(Row, Row), DataflowError>((key_row, val_row))
                 });
 
-                let oks = oks_keyed.arrange_named::<RowSpine<Row, Row, _, _>>(&name);
+                let oks = oks_keyed
+                    .arrange_named::<RowSpine<Row, Row, _, _>>(&name)
+                    .log_arrangement_size();
                 let errs = errs
                     .concat(&errs_keyed)
                     .arrange_named::<ErrSpine<_, _, _>>(&format!("{}-errors", name));
@@ -26,6 +26,7 @@ use mz_repr::{DatumVec, Diff, Row, RowArena};
 use mz_storage_client::types::errors::DataflowError;
 use mz_timely_util::operator::CollectionExt;
 
+use crate::arrangement::size::LogArrangementSize;
 use crate::render::context::{
     Arrangement, ArrangementFlavor, ArrangementImport, CollectionBundle, Context,
 };
@@ -202,7 +203,9 @@ where
             });
 
             errors.push(errs);
-            let arranged = keyed.arrange_named::<RowSpine<_, _, _, _>>("JoinStage");
+            let arranged = keyed
+                .arrange_named::<RowSpine<_, _, _, _>>("JoinStage")
+                .log_arrangement_size();
             joined = JoinedFlavor::Local(arranged);
         }
 
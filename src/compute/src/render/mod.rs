@@ -128,7 +128,7 @@ use crate::compute_state::ComputeState;
 use crate::logging::compute::ComputeEvent;
 use crate::logging::compute::Logger;
 pub use context::CollectionBundle;
-use context::{ArrangementFlavor, Context};
+use context::{ArrangementFlavor, Context, LirMapping};
 
 pub mod context;
 mod flat_map;
@@ -241,6 +241,26 @@ pub fn build_compute_dataflow<A: Allocate>(
                 context.build_object(region, object);
             }
 
+            // Report the operators rendered for each LIR node, for each export.
+            if let Some(logger) = compute_state.compute_logger.as_mut() {
+                for export_id in indexes
+                    .iter()
+                    .map(|(id, _, _)| *id)
+                    .chain(sinks.iter().map(|(id, _, _)| *id))
+                {
+                    for (lir_id, mapping) in context.lir_mapping.iter().enumerate() {
+                        logger.log(ComputeEvent::LirMapping {
+                            export_id,
+                            lir_id,
+                            operator: mapping.operator.clone(),
+                            nesting: mapping.nesting,
+                            operator_id_start: mapping.operator_id_start,
+                            operator_id_end: mapping.operator_id_end,
+                        });
+                    }
+                }
+            }
+
             // Export declared indexes.
             for (idx_id, imports, idx) in indexes {
                 context.export_index(compute_state, &mut tokens, imports, idx_id, &idx);
@@ -430,11 +450,44 @@ where
     ///
     /// The return type reflects the uncertainty about the data representation, perhaps
     /// as a stream of data, perhaps as an arrangement, perhaps as a stream of batches.
+    ///
+    /// The operators rendered for each node of `plan` are recorded in `self.lir_mapping`.
     pub fn render_plan(
         &mut self,
         plan: Plan,
         scope: &mut G,
         worker_index: usize,
+    ) -> CollectionBundle<G, Row> {
+        let lir_id = self.lir_mapping.len();
+        self.lir_mapping.push(LirMapping {
+            operator: plan.operator_name(),
+            nesting: self.lir_nesting,
+            operator_id_start: 0,
+            operator_id_end: 0,
+        });
+
+        self.operator_watermark = scope.peek_identifier();
+        self.lir_nesting += 1;
+        let bundle = self.render_plan_node(plan, scope, worker_index);
+        self.lir_nesting -= 1;
+
+        // Inputs are always rendered before the operators of the node itself,
+        // so the latter start where the most recently rendered input ended.
+        let operator_id_end = scope.peek_identifier();
+        let mapping = &mut self.lir_mapping[lir_id];
+        mapping.operator_id_start = self.operator_watermark;
+        mapping.operator_id_end = operator_id_end;
+        self.operator_watermark = operator_id_end;
+
+        bundle
+    }
+
+    /// Renders a single node of a plan, recursively rendering its inputs.
+    fn render_plan_node(
+        &mut self,
+        plan: Plan,
+        scope: &mut G,
+        worker_index: usize,
     ) -> CollectionBundle<G, Row> {
         match plan {
             Plan::Constant { rows } => {
@@ -38,6 +38,7 @@ use mz_repr::{Datum, DatumList, DatumVec, Diff, Row, RowArena};
 use mz_storage_client::types::errors::DataflowError;
 use mz_timely_util::operator::CollectionExt;
 
+use crate::arrangement::size::LogArrangementSize;
 use crate::render::context::{Arrangement, CollectionBundle, Context};
 use crate::render::reduce::monoids::ReductionMonoid;
 use crate::render::ArrangementFlavor;
@@ -354,6 +355,7 @@ where
                 output.push((row_buf.clone(), 1));
             }
         })
+        .log_arrangement_size()
 }
 
 /// Build the dataflow to compute the set of distinct keys.
@@ -362,14 +364,16 @@ where
     G: Scope,
     G::Timestamp: Lattice,
 {
-    collection.reduce_abelian::<_, RowSpine<_, _, _, _>>("DistinctBy", {
-        |_key, _input, output| {
-            // We're pushing an empty row here because the key is implicitly added by the
-            // arrangement, and the permutation logic takes care of using the key part of the
-            // output.
-            output.push((Row::default(), 1));
-        }
-    })
+    collection
+        .reduce_abelian::<_, RowSpine<_, _, _, _>>("DistinctBy", {
+            |_key, _input, output| {
+                // We're pushing an empty row here because the key is implicitly added by the
+                // arrangement, and the permutation logic takes care of using the key part of the
+                // output.
+                output.push((Row::default(), 1));
+            }
+        })
+        .log_arrangement_size()
 }
 
 /// Build the dataflow to compute the set of distinct keys.
@@ -442,6 +446,7 @@ where
                 output.push((row_buf.clone(), 1));
             }
         })
+        .log_arrangement_size()
 }
 
 /// Build the dataflow to compute a single basic aggregation.
@@ -502,6 +507,7 @@ where
             }
         }
     })
+    .log_arrangement_size()
 }
 
 /// Build the dataflow to compute and arrange multiple hierarchical aggregations
@@ -576,7 +582,7 @@ where
                     target.push((row_buf.clone(), 1));
                 }
             }
-        }).leave_region()
+        }).log_arrangement_size().leave_region()
     })
 }
 
@@ -694,6 +700,7 @@ where
                 output.push((row_buf.clone(), 1));
             }
         })
+        .log_arrangement_size()
 }
 
 /// Accumulates values for the various types of accumulable aggregations.
@@ -1418,6 +1425,7 @@ where
                 output.push((row_buf.clone(), 1));
             }
         })
+        .log_arrangement_size()
 }
 
 /// Monoids for in-place compaction of monotonic streams.
@@ -1551,7 +1559,8 @@ pub mod monoids {
             | AggregateFunc::DenseRank { .. }
             | AggregateFunc::LagLead { .. }
             | AggregateFunc::FirstValue { .. }
-            | AggregateFunc::LastValue { .. } => None,
+            | AggregateFunc::LastValue { .. }
+            | AggregateFunc::WindowAggregate { .. } => None,
         }
     }
 }
@@ -24,6 +24,7 @@ use mz_compute_client::plan::threshold::{
 use mz_expr::MirScalarExpr;
 use mz_repr::{Diff, Row};
 
+use crate::arrangement::size::LogArrangementSize;
 use crate::render::context::{ArrangementFlavor, CollectionBundle, Context};
 use crate::typedefs::RowSpine;
 
@@ -40,13 +41,15 @@ where
     R: ReduceCore<G, Row, Row, Diff>,
     L: Fn(&Diff) -> bool + 'static,
 {
-    arrangement.reduce_abelian(name, move |_key, s, t| {
-        for (record, count) in s.iter() {
-            if logic(count) {
-                t.push(((*record).clone(), *count));
+    arrangement
+        .reduce_abelian(name, move |_key, s, t| {
+            for (record, count) in s.iter() {
+                if logic(count) {
+                    t.push(((*record).clone(), *count));
+                }
             }
-        }
-    })
+        })
+        .log_arrangement_size()
 }
 
 /// Build a dataflow to threshold the input data.
@@ -12,8 +12,10 @@
 use std::cell::RefCell;
 use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use anyhow::{anyhow, Error};
 use async_trait::async_trait;
@@ -43,11 +45,14 @@ use mz_ore::halt;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::NowFn;
 use mz_persist_client::cache::PersistClientCache;
+use mz_persist_client::rpc::{GrpcPubSubClient, NoopPubSubClient};
 use mz_persist_client::PersistConfig;
 use mz_service::client::{GenericClient, Partitioned};
 use mz_service::local::LocalClient;
+use mz_storage_client::types::connections::ConnectionContext;
 use tracing::{info, warn};
 
+use crate::arrangement::spill;
 use crate::communication::initialize_networking;
 use crate::compute_state::ActiveComputeState;
 use crate::compute_state::ComputeState;
@@ -62,6 +67,17 @@ pub struct Config {
     pub now: NowFn,
     /// Metrics registry through which dataflow metrics will be reported.
     pub metrics_registry: MetricsRegistry,
+    /// The URL of the persist pubsub server, if any.
+    pub persist_pubsub_url: Option<String>,
+    /// A local directory in which to cache persist blob reads, if any.
+    pub persist_blob_disk_cache_dir: Option<PathBuf>,
+    /// Overrides the maximum size of the persist blob disk cache.
+    pub persist_blob_disk_cache_max_size_bytes: Option<usize>,
+    /// A local directory to which large arrangement batches may be spilled, if any.
+    pub scratch_directory: Option<PathBuf>,
+    /// Configuration for connections to external systems, like the S3 buckets
+    /// written by `COPY ... TO` sinks.
+    pub connection_context: ConnectionContext,
 }
 
 /// A client managing access to the local portion of a Timely cluster
@@ -74,6 +90,8 @@ struct ClusterClient<C> {
     trace_metrics: TraceMetrics,
     /// Handle to the persist infrastructure.
     persist_clients: Arc<tokio::sync::Mutex<PersistClientCache>>,
+    /// Configuration for connections to external systems.
+    connection_context: ConnectionContext,
     /// The handle to the Tokio runtime.
     tokio_handle: tokio::runtime::Handle,
 }
@@ -104,9 +122,26 @@ pub fn serve(
     // Various metrics related things.
     let trace_metrics = TraceMetrics::register_with(&config.metrics_registry);
 
+    if let Some(scratch_directory) = config.scratch_directory {
+        info!(
+            "spilling arrangement batches to {}",
+            scratch_directory.display()
+        );
+        spill::enable(scratch_directory)?;
+    }
+
+    let mut persist_cfg = PersistConfig::new(config.build_info, config.now.clone());
+    persist_cfg.blob_disk_cache_dir = config.persist_blob_disk_cache_dir;
+    if let Some(max_size_bytes) = config.persist_blob_disk_cache_max_size_bytes {
+        persist_cfg.blob_disk_cache_max_size_bytes = max_size_bytes;
+    }
     let persist_clients = PersistClientCache::new(
-        PersistConfig::new(config.build_info, config.now.clone()),
+        persist_cfg,
         &config.metrics_registry,
+        |_, metrics| match config.persist_pubsub_url {
+            Some(url) => GrpcPubSubClient::connect(url, metrics),
+            None => Arc::new(NoopPubSubClient),
+        },
     );
     let persist_clients = Arc::new(tokio::sync::Mutex::new(persist_clients));
     let tokio_executor = tokio::runtime::Handle::current();
@@ -114,11 +149,13 @@ pub fn serve(
 
     let client_builder = {
         let timely_container = Arc::clone(&timely_container);
+        let connection_context = config.connection_context;
         move || {
             let client = ClusterClient::new(
                 Arc::clone(&timely_container),
                 trace_metrics.clone(),
                 Arc::clone(&persist_clients),
+                connection_context.clone(),
                 tokio_executor.clone(),
             );
             Box::new(client) as Box<dyn ComputeClient>
@@ -133,6 +170,7 @@ impl ClusterClient<PartitionedClient> {
         timely_container: TimelyContainerRef,
         trace_metrics: TraceMetrics,
         persist_clients: Arc<tokio::sync::Mutex<PersistClientCache>>,
+        connection_context: ConnectionContext,
         tokio_handle: tokio::runtime::Handle,
     ) -> Self {
         Self {
@@ -140,6 +178,7 @@ impl ClusterClient<PartitionedClient> {
             inner: None,
             trace_metrics,
             persist_clients,
+            connection_context,
             tokio_handle,
         }
     }
@@ -149,6 +188,7 @@ impl ClusterClient<PartitionedClient> {
         epoch: ComputeStartupEpoch,
         trace_metrics: TraceMetrics,
         persist_clients: Arc<tokio::sync::Mutex<PersistClientCache>>,
+        connection_context: ConnectionContext,
         tokio_executor: Handle,
     ) -> Result<TimelyContainer, Error> {
         info!("Building timely container with config {comm_config:?}");
@@ -178,6 +218,7 @@ impl ClusterClient<PartitionedClient> {
                     compute_state: None,
                     trace_metrics: trace_metrics.clone(),
                     persist_clients,
+                    connection_context: connection_context.clone(),
                 }
                 .run()
             },
@@ -207,6 +248,7 @@ impl ClusterClient<PartitionedClient> {
 
         let trace_metrics = self.trace_metrics.clone();
         let persist_clients = Arc::clone(&self.persist_clients);
+        let connection_context = self.connection_context.clone();
         let handle = self.tokio_handle.clone();
 
         let mut timely_lock = self.timely_container.lock().await;
@@ -223,9 +265,15 @@ impl ClusterClient<PartitionedClient> {
                 existing
             }
             None => {
-                let build_timely_result =
-                    Self::build_timely(comm_config, epoch, trace_metrics, persist_clients, handle)
-                        .await;
+                let build_timely_result = Self::build_timely(
+                    comm_config,
+                    epoch,
+                    trace_metrics,
+                    persist_clients,
+                    connection_context,
+                    handle,
+                )
+                .await;
                 match build_timely_result {
                     Err(e) => {
                         warn!("timely initialization failed: {e}");
@@ -372,6 +420,8 @@ struct Worker<'w, A: Allocate> {
     /// A process-global cache of (blob_uri, consensus_uri) -> PersistClient.
     /// This is intentionally shared between workers
     persist_clients: Arc<tokio::sync::Mutex<PersistClientCache>>,
+    /// Configuration for connections to external systems.
+    connection_context: ConnectionContext,
 }
 
 impl<'w, A: Allocate> Worker<'w, A> {
@@ -553,6 +603,7 @@ impl<'w, A: Allocate> Worker<'w, A> {
             if let Some(mut compute_state) = self.activate_compute(&mut response_tx) {
                 compute_state.report_compute_frontiers();
                 compute_state.report_dropped_collections();
+                compute_state.report_arrangement_sizes();
             }
 
             // Handle any received commands.
@@ -575,6 +626,7 @@ impl<'w, A: Allocate> Worker<'w, A> {
             if let Some(mut compute_state) = self.activate_compute(&mut response_tx) {
                 compute_state.process_peeks();
                 compute_state.process_subscribes();
+                compute_state.process_copy_tos();
             }
         }
     }
@@ -600,12 +652,19 @@ impl<'w, A: Allocate> Worker<'w, A> {
                     subscribe_response_buffer: std::rc::Rc::new(
                         std::cell::RefCell::new(Vec::new()),
                     ),
+                    copy_to_response_buffer: std::rc::Rc::new(std::cell::RefCell::new(Vec::new())),
                     sink_write_frontiers: HashMap::new(),
                     pending_peeks: HashMap::new(),
                     reported_frontiers: HashMap::new(),
+                    export_as_ofs: HashMap::new(),
                     dropped_collections: Vec::new(),
+                    arrangement_sizes: Default::default(),
+                    reported_arrangement_sizes: HashMap::new(),
+                    reported_total_arrangement_size: None,
+                    last_arrangement_sizes_report: Instant::now(),
                     compute_logger: None,
                     persist_clients: Arc::clone(&self.persist_clients),
+                    connection_context: self.connection_context.clone(),
                     command_history: ComputeCommandHistory::default(),
                     max_result_size: config.max_result_size,
                 });
@@ -842,6 +901,9 @@ impl<'w, A: Allocate> Worker<'w, A> {
                 }
                 *frontier = timely::progress::Antichain::from_elem(<_>::minimum());
             }
+            // Re-report the sizes of all arrangements we continue to maintain.
+            compute_state.reported_arrangement_sizes.clear();
+            compute_state.reported_total_arrangement_size = None;
             // Sink tokens should be retained for retained dataflows, and dropped for dropped dataflows.
             compute_state
                 .sink_tokens
@@ -0,0 +1,355 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that writes the snapshot of a collection to objects in S3, for
+//! `COPY ... TO` a URL.
+//!
+//! Each worker encodes its share of the snapshot and uploads an object
+//! whenever the one it is writing grows past the sink's maximum file size, so
+//! at most one encoded object per worker is held in memory at a time. Only
+//! the replica targeted by the sink uploads objects; other replicas discard
+//! their input.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use anyhow::Context;
+use aws_sdk_s3::types::ByteStream;
+use differential_dataflow::Collection;
+use timely::dataflow::channels::pact::Pipeline;
+use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+use timely::dataflow::Scope;
+use timely::progress::timestamp::Timestamp as TimelyTimestamp;
+use timely::progress::Antichain;
+use timely::PartialOrder;
+
+use mz_compute_client::response::CopyToResponse;
+use mz_compute_client::sinks::{ComputeSinkDesc, CopyToS3Format, CopyToS3SinkConnection};
+use mz_interchange::parquet::encode_parquet;
+use mz_repr::{Datum, Diff, GlobalId, RelationDesc, RelationType, Row, ScalarType, Timestamp};
+use mz_storage_client::controller::CollectionMetadata;
+use mz_storage_client::types::connections::ConnectionContext;
+use mz_storage_client::types::errors::DataflowError;
+use mz_timely_util::operators_async_ext::OperatorBuilderExt;
+
+use crate::compute_state::ComputeState;
+use crate::render::sinks::SinkRender;
+
+impl<G> SinkRender<G> for CopyToS3SinkConnection
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn render_continuous_sink(
+        &self,
+        compute_state: &mut ComputeState,
+        sink: &ComputeSinkDesc<CollectionMetadata>,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, Row, Diff>,
+        err_collection: Collection<G, DataflowError, Diff>,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // The frontier of the sink only advances past its `as_of` once all
+        // objects have been written.
+        let shared_frontier = Rc::new(RefCell::new(Antichain::from_elem(Timestamp::minimum())));
+        compute_state
+            .sink_write_frontiers
+            .insert(sink_id, Rc::clone(&shared_frontier));
+
+        // An encapsulation of the `COPY ... TO` response protocol, used to
+        // report the outcome of the copy, and to alert if the dataflow was
+        // dropped before completing.
+        let protocol_handle = Rc::new(RefCell::new(Some(CopyToProtocol {
+            sink_id,
+            copy_to_response_buffer: Rc::clone(&compute_state.copy_to_response_buffer),
+        })));
+        let protocol_weak = Rc::downgrade(&protocol_handle);
+
+        copy_to_s3(
+            sinked_collection,
+            err_collection,
+            sink_id,
+            sink.as_of.frontier.clone(),
+            self.clone(),
+            compute_state.connection_context.clone(),
+            shared_frontier,
+            protocol_handle,
+        );
+
+        Some(Rc::new(scopeguard::guard((), move |_| {
+            if let Some(protocol_handle) = protocol_weak.upgrade() {
+                if let Some(protocol) = protocol_handle.borrow_mut().take() {
+                    protocol.send(CopyToResponse::Dropped);
+                }
+            }
+        })))
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn copy_to_s3<G>(
+    sinked_collection: Collection<G, Row, Diff>,
+    err_collection: Collection<G, DataflowError, Diff>,
+    sink_id: GlobalId,
+    as_of: Antichain<Timestamp>,
+    connection: CopyToS3SinkConnection,
+    connection_context: ConnectionContext,
+    shared_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    protocol_handle: Rc<RefCell<Option<CopyToProtocol>>>,
+) where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let scope = sinked_collection.scope();
+    let worker_index = scope.index();
+
+    // The rows of the snapshot only cancel out once consolidated.
+    let sinked_collection = sinked_collection.consolidate();
+
+    let mut builder = OperatorBuilder::new(format!("copy_to_s3-{}", sink_id), scope.clone());
+    let mut ok_input = builder.new_input(&sinked_collection.inner, Pipeline);
+    let mut err_input = builder.new_input(&err_collection.inner, Pipeline);
+
+    builder.build_async(
+        scope,
+        move |_capabilities, frontiers, scheduler| async move {
+            let mut ok_buffer = Vec::new();
+            let mut err_buffer = Vec::new();
+
+            let mut writer = if connection.upload {
+                Some(ObjectWriter::new(&connection, &connection_context, worker_index).await)
+            } else {
+                None
+            };
+            let mut result = Ok(());
+
+            while scheduler.notified().await {
+                // Capture current frontiers.
+                let frontiers = frontiers.borrow().clone();
+                let mut input_frontier = frontiers[0].clone();
+                input_frontier.extend(frontiers[1].iter().copied());
+
+                let mut rows = Vec::new();
+                ok_input.for_each(|_cap, data| {
+                    data.swap(&mut ok_buffer);
+                    for (row, time, diff) in ok_buffer.drain(..) {
+                        if !as_of.less_than(&time) {
+                            rows.push((row, diff));
+                        }
+                    }
+                });
+                err_input.for_each(|_cap, data| {
+                    data.swap(&mut err_buffer);
+                    for (err, time, _diff) in err_buffer.drain(..) {
+                        if !as_of.less_than(&time) && result.is_ok() {
+                            result = Err(err.to_string());
+                        }
+                    }
+                });
+
+                if let (Some(writer), Ok(())) = (&mut writer, &result) {
+                    for (row, diff) in rows {
+                        if let Err(err) = writer.write(row, diff).await {
+                            result = Err(format!("{:#}", err));
+                            break;
+                        }
+                    }
+                }
+
+                // The snapshot is complete once the inputs have advanced past
+                // the `as_of`.
+                if PartialOrder::less_equal(&input_frontier, &as_of) && result.is_ok() {
+                    shared_frontier.borrow_mut().clone_from(&input_frontier);
+                    continue;
+                }
+
+                let response = match (writer.take(), result) {
+                    (Some(writer), Ok(())) => match writer.finish().await {
+                        Ok(count) => CopyToResponse::RowCount(count),
+                        Err(err) => CopyToResponse::Error(format!("{:#}", err)),
+                    },
+                    (None, Ok(())) => CopyToResponse::RowCount(0),
+                    (_, Err(err)) => CopyToResponse::Error(err),
+                };
+                if let Some(protocol) = protocol_handle.borrow_mut().take() {
+                    protocol.send(response);
+                }
+                *shared_frontier.borrow_mut() = Antichain::new();
+                return;
+            }
+        },
+    );
+}
+
+/// Encodes rows into objects and uploads each object to S3 once it fills.
+struct ObjectWriter {
+    client: aws_sdk_s3::Client,
+    bucket: String,
+    prefix: String,
+    format: CopyToS3Format,
+    desc: RelationDesc,
+    max_file_size: usize,
+    worker_index: usize,
+    next_part: usize,
+    /// The encoded contents of the current object, for text and CSV.
+    buf: Vec<u8>,
+    /// The rows of the current object and their size in bytes, for Parquet,
+    /// which is encoded all at once.
+    chunk: Vec<Row>,
+    chunk_size: usize,
+    header: Option<(Row, RelationType)>,
+    count: u64,
+}
+
+impl ObjectWriter {
+    async fn new(
+        connection: &CopyToS3SinkConnection,
+        connection_context: &ConnectionContext,
+        worker_index: usize,
+    ) -> Self {
+        let config = connection
+            .aws_connection
+            .load(
+                connection_context.aws_external_id_prefix.as_ref(),
+                Some(&connection.aws_connection_id),
+                &*connection_context.secrets_reader,
+            )
+            .await;
+        let header = match connection.format {
+            CopyToS3Format::Csv { header: true } => Some(header_row(&connection.value_desc)),
+            _ => None,
+        };
+        ObjectWriter {
+            client: aws_sdk_s3::Client::new(&config),
+            bucket: connection.bucket.clone(),
+            prefix: connection.prefix.clone(),
+            format: connection.format,
+            desc: connection.value_desc.clone(),
+            max_file_size: usize::try_from(connection.max_file_size).unwrap_or(usize::MAX),
+            worker_index,
+            next_part: 0,
+            buf: Vec::new(),
+            chunk: Vec::new(),
+            chunk_size: 0,
+            header,
+            count: 0,
+        }
+    }
+
+    /// Writes `diff` copies of `row`, uploading the current object if it
+    /// fills.
+    async fn write(&mut self, row: Row, diff: Diff) -> anyhow::Result<()> {
+        if diff < 0 {
+            anyhow::bail!("COPY TO received a negative multiplicity for a row");
+        }
+        for _ in 0..diff {
+            match self.format {
+                CopyToS3Format::Text | CopyToS3Format::Csv { .. } => {
+                    if self.buf.is_empty() {
+                        if let Some((header, typ)) = &self.header {
+                            mz_pgcopy::encode_copy_row_csv(header.clone(), typ, &mut self.buf)?;
+                        }
+                    }
+                    match self.format {
+                        CopyToS3Format::Csv { .. } => mz_pgcopy::encode_copy_row_csv(
+                            row.clone(),
+                            self.desc.typ(),
+                            &mut self.buf,
+                        )?,
+                        _ => mz_pgcopy::encode_copy_row_text(
+                            row.clone(),
+                            self.desc.typ(),
+                            &mut self.buf,
+                        )?,
+                    }
+                    if self.buf.len() >= self.max_file_size {
+                        let object = std::mem::take(&mut self.buf);
+                        self.upload(object).await?;
+                    }
+                }
+                CopyToS3Format::Parquet => {
+                    self.chunk_size += row.byte_len();
+                    self.chunk.push(row.clone());
+                    if self.chunk_size >= self.max_file_size {
+                        let object = encode_parquet(&self.desc, &self.chunk)?;
+                        self.chunk.clear();
+                        self.chunk_size = 0;
+                        self.upload(object).await?;
+                    }
+                }
+            }
+            self.count += 1;
+        }
+        Ok(())
+    }
+
+    /// Uploads the last, partially filled object, and returns the number of
+    /// rows written.
+    async fn finish(mut self) -> anyhow::Result<u64> {
+        if !self.buf.is_empty() {
+            let object = std::mem::take(&mut self.buf);
+            self.upload(object).await?;
+        }
+        if !self.chunk.is_empty() {
+            let object = encode_parquet(&self.desc, &self.chunk)?;
+            self.upload(object).await?;
+        }
+        Ok(self.count)
+    }
+
+    async fn upload(&mut self, object: Vec<u8>) -> anyhow::Result<()> {
+        let name = format!(
+            "part-{:04}-{:05}.{}",
+            self.worker_index,
+            self.next_part,
+            self.format.extension()
+        );
+        let key = match self.prefix.as_str() {
+            "" => name,
+            prefix => format!("{}/{}", prefix, name),
+        };
+        self.next_part += 1;
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(&key)
+            .body(ByteStream::from(object))
+            .send()
+            .await
+            .with_context(|| format!("writing s3://{}/{}", self.bucket, key))?;
+        Ok(())
+    }
+}
+
+/// Returns a row holding the column names of `desc`, which is encoded like
+/// any other row to produce a CSV header line.
+fn header_row(desc: &RelationDesc) -> (Row, RelationType) {
+    let row = Row::pack(desc.iter_names().map(|name| Datum::String(name.as_str())));
+    let typ = RelationType::new(vec![ScalarType::String.nullable(false); desc.arity()]);
+    (row, typ)
+}
+
+/// A type that guides the transmission of the outcome of a `COPY ... TO` back
+/// to the coordinator.
+///
+/// A protocol instance sends exactly one response, which is consumed by
+/// `send`.
+struct CopyToProtocol {
+    sink_id: GlobalId,
+    copy_to_response_buffer: Rc<RefCell<Vec<(GlobalId, CopyToResponse)>>>,
+}
+
+impl CopyToProtocol {
+    fn send(self, response: CopyToResponse) {
+        self.copy_to_response_buffer
+            .borrow_mut()
+            .push((self.sink_id, response));
+    }
+}
@@ -7,6 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+mod copy_to_s3;
 mod persist_sink;
 mod subscribe;
 
@@ -11,13 +11,19 @@
 
 #![allow(missing_docs)]
 
+use std::rc::Rc;
+
 use differential_dataflow::operators::arrange::TraceAgent;
-use differential_dataflow::trace::implementations::ord::{OrdKeySpine, OrdValSpine};
+use differential_dataflow::trace::implementations::ord::{OrdKeySpine, OrdValBatch};
+use differential_dataflow::trace::implementations::spine_fueled::Spine;
 
 use mz_repr::{Diff, Row, Timestamp};
 use mz_storage_client::types::errors::DataflowError;
 
-pub type RowSpine<K, V, T, R, O = usize> = OrdValSpine<K, V, T, R, O>;
+use crate::arrangement::spill::SpillBatch;
+
+pub type RowSpine<K, V, T, R, O = usize> =
+    Spine<K, V, T, R, Rc<SpillBatch<OrdValBatch<K, V, T, R, O>>>>;
 pub type RowKeySpine<K, T, R, O = usize> = OrdKeySpine<K, T, R, O>;
 pub type ErrSpine<K, T, R, O = usize> = OrdKeySpine<K, T, R, O>;
 pub type TraceRowHandle<K, V, T, R> = TraceAgent<RowSpine<K, V, T, R>>;
@@ -37,7 +37,7 @@ use mz_compute_client::command::ReplicaId;
 use mz_compute_client::controller::{
     ActiveComputeController, ComputeController, ComputeControllerResponse,
 };
-use mz_compute_client::response::{PeekResponse, SubscribeResponse};
+use mz_compute_client::response::{CopyToResponse, PeekResponse, SubscribeResponse};
 use mz_compute_client::service::{ComputeClient, ComputeGrpcClient};
 use mz_orchestrator::Orchestrator;
 use mz_ore::now::{EpochMillis, NowFn};
@@ -71,6 +71,9 @@ pub struct ControllerConfig {
     pub storaged_image: String,
     /// The computed image to use when starting new compute processes.
     pub computed_image: String,
+    /// The URL of the persist pubsub server that storage and compute
+    /// processes should connect to, if any.
+    pub persist_pubsub_url: Option<String>,
     /// The now function to advance the controller's introspection collections.
     pub now: NowFn,
 }
@@ -86,9 +89,16 @@ pub enum ControllerResponse<T = mz_repr::Timestamp> {
     PeekResponse(Uuid, PeekResponse, OpenTelemetryContext),
     /// The worker's next response to a specified subscribe.
     SubscribeResponse(GlobalId, SubscribeResponse<T>),
+    /// The response of a `COPY ... TO` sink.
+    CopyToResponse(GlobalId, CopyToResponse),
     /// Notification that we have received a message from the given compute replica
     /// at the given time.
     ComputeReplicaHeartbeat(ReplicaId, DateTime<Utc>),
+    /// Notification that the hydration status of a compute collection on the
+    /// given replica has changed.
+    ///
+    /// See [`ComputeControllerResponse::HydrationStatus`].
+    ComputeHydrationStatus(GlobalId, ReplicaId, Option<bool>),
 }
 
 impl<T> From<ComputeControllerResponse<T>> for ControllerResponse<T> {
@@ -100,9 +110,15 @@ impl<T> From<ComputeControllerResponse<T>> for ControllerResponse<T> {
             ComputeControllerResponse::SubscribeResponse(id, tail) => {
                 ControllerResponse::SubscribeResponse(id, tail)
             }
+            ComputeControllerResponse::CopyToResponse(id, resp) => {
+                ControllerResponse::CopyToResponse(id, resp)
+            }
             ComputeControllerResponse::ReplicaHeartbeat(id, when) => {
                 ControllerResponse::ComputeReplicaHeartbeat(id, when)
             }
+            ComputeControllerResponse::HydrationStatus(id, replica_id, hydrated) => {
+                ControllerResponse::ComputeHydrationStatus(id, replica_id, hydrated)
+            }
         }
     }
 }
@@ -219,6 +235,7 @@ where
             config.persist_clients,
             config.orchestrator.namespace("storage"),
             config.storaged_image,
+            config.persist_pubsub_url.clone(),
             config.now,
         )
         .await;
@@ -227,6 +244,7 @@ where
             config.build_info,
             config.orchestrator.namespace("compute"),
             config.computed_image,
+            config.persist_pubsub_url,
             envd_epoch,
         );
 
@@ -55,6 +55,7 @@ use mz_ore::id_gen::PortAllocator;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::SYSTEM_TIME;
 use mz_persist_client::cache::PersistClientCache;
+use mz_persist_client::rpc::PersistPubSubServer;
 use mz_persist_client::{PersistConfig, PersistLocation};
 use mz_secrets::SecretsController;
 use mz_storage_client::types::connections::ConnectionContext;
@@ -311,6 +312,13 @@ pub struct Args {
     /// Where the persist library should perform consensus.
     #[clap(long, env = "PERSIST_CONSENSUS_URL")]
     persist_consensus_url: Url,
+    /// The URL at which storage and compute processes can reach the persist
+    /// pubsub channel, which is served on the internal HTTP listen address.
+    ///
+    /// Defaults to the internal HTTP listen address, with an unspecified IP
+    /// address replaced by `localhost`.
+    #[clap(long, env = "PERSIST_PUBSUB_URL", value_name = "URL")]
+    persist_pubsub_url: Option<String>,
     /// The PostgreSQL URL for the storage stash.
     #[clap(long, env = "STORAGE_STASH_URL", value_name = "POSTGRES_URL")]
     storage_stash_url: String,
@@ -607,10 +615,24 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
     };
     let secrets_reader = secrets_controller.reader();
     let now = SYSTEM_TIME.clone();
+    let mut persist_pubsub_server = None;
     let persist_clients = PersistClientCache::new(
         PersistConfig::new(&mz_environmentd::BUILD_INFO, now.clone()),
         &metrics_registry,
+        |_, metrics| {
+            let server = PersistPubSubServer::new(metrics);
+            let client = server.new_same_process_connection();
+            persist_pubsub_server = Some(server);
+            client
+        },
     );
+    let persist_pubsub_url = match args.persist_pubsub_url {
+        Some(url) => url,
+        None if args.internal_http_listen_addr.ip().is_unspecified() => {
+            format!("http://localhost:{}", args.internal_http_listen_addr.port())
+        }
+        None => format!("http://{}", args.internal_http_listen_addr),
+    };
     let persist_clients = Arc::new(Mutex::new(persist_clients));
     let orchestrator = Arc::new(TracingOrchestrator::new(orchestrator, args.tracing.clone()));
     let controller = ControllerConfig {
@@ -624,6 +646,7 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
         storage_stash_url: args.storage_stash_url,
         storaged_image: args.storaged_image.expect("clap enforced"),
         computed_image: args.computed_image.expect("clap enforced"),
+        persist_pubsub_url: Some(persist_pubsub_url),
         now: SYSTEM_TIME.clone(),
     };
 
@@ -723,6 +746,7 @@ max log level: {max_log_level}",
         cors_allowed_origin,
         adapter_stash_url: args.adapter_stash_url,
         controller,
+        persist_pubsub_server,
         secrets_controller,
         cloud_resource_controller,
         unsafe_mode: args.unsafe_mode,
@@ -48,6 +48,7 @@ use mz_adapter::SessionClient;
 use mz_frontegg_auth::{FronteggAuthentication, FronteggError};
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::tracing::TracingTargetCallbacks;
+use mz_persist_client::rpc::PersistPubSubServer;
 
 use crate::server::{ConnectionHandler, Server};
 use crate::BUILD_INFO;
@@ -158,6 +159,7 @@ pub struct InternalHttpConfig {
     pub metrics_registry: MetricsRegistry,
     pub tracing_target_callbacks: TracingTargetCallbacks,
     pub adapter_client_rx: oneshot::Receiver<mz_adapter::Client>,
+    pub persist_pubsub_server: Option<PersistPubSubServer>,
 }
 
 pub struct InternalHttpServer {
@@ -170,9 +172,10 @@ impl InternalHttpServer {
             metrics_registry,
             tracing_target_callbacks,
             adapter_client_rx,
+            persist_pubsub_server,
         }: InternalHttpConfig,
     ) -> InternalHttpServer {
-        let router = base_router(BaseRouterConfig { profiling: true })
+        let mut router = base_router(BaseRouterConfig { profiling: true })
             .route(
                 "/metrics",
                 routing::get(move || async move {
@@ -212,6 +215,11 @@ impl InternalHttpServer {
                 create_if_not_exists: false,
             }))
             .layer(Extension(adapter_client_rx.shared()));
+        // Storage and compute processes connect to the persist pubsub server
+        // over gRPC, which the connection handler serves as HTTP/2.
+        if let Some(persist_pubsub_server) = persist_pubsub_server {
+            router = router.merge(persist_pubsub_server.into_router());
+        }
         InternalHttpServer { router }
     }
 }
@@ -341,11 +341,13 @@ async fn execute_stmt(
         | ExecuteResponse::CreatedSecret { .. }
         | ExecuteResponse::CreatedSource { .. }
         | ExecuteResponse::CreatedSources
+        | ExecuteResponse::CreatedSubscription
         | ExecuteResponse::CreatedSink { .. }
         | ExecuteResponse::CreatedView { .. }
         | ExecuteResponse::CreatedViews { .. }
         | ExecuteResponse::CreatedMaterializedView { .. }
         | ExecuteResponse::CreatedType
+        | ExecuteResponse::CreatedFunction
         | ExecuteResponse::Deleted(_)
         | ExecuteResponse::DiscardedTemp
         | ExecuteResponse::DiscardedAll
@@ -361,10 +363,14 @@ async fn execute_stmt(
         | ExecuteResponse::DroppedView
         | ExecuteResponse::DroppedMaterializedView
         | ExecuteResponse::DroppedType
+        | ExecuteResponse::DroppedFunction
         | ExecuteResponse::DroppedSecret
+        | ExecuteResponse::DroppedSubscription
         | ExecuteResponse::DroppedConnection
         | ExecuteResponse::EmptyQuery
         | ExecuteResponse::Inserted(_)
+        | ExecuteResponse::Copied(_)
+        | ExecuteResponse::Merged(_)
         | ExecuteResponse::Raised
         | ExecuteResponse::SetVariable { .. }
         | ExecuteResponse::StartedTransaction { .. }
@@ -36,6 +36,7 @@ use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::NowFn;
 use mz_ore::task;
 use mz_ore::tracing::TracingTargetCallbacks;
+use mz_persist_client::rpc::PersistPubSubServer;
 use mz_persist_client::usage::StorageUsageClient;
 use mz_secrets::SecretsController;
 use mz_storage_client::types::connections::ConnectionContext;
@@ -84,6 +85,8 @@ pub struct Config {
     // === Controller options. ===
     /// Storage and compute controller configuration.
     pub controller: ControllerConfig,
+    /// The persist pubsub server to serve on the internal HTTP server, if any.
+    pub persist_pubsub_server: Option<PersistPubSubServer>,
     /// Secrets controller configuration.
     pub secrets_controller: Arc<dyn SecretsController>,
     /// VpcEndpoint controller configuration.
@@ -231,6 +234,7 @@ pub async fn serve(config: Config) -> Result<Server, anyhow::Error> {
             metrics_registry: config.metrics_registry.clone(),
             tracing_target_callbacks: config.tracing_target_callbacks,
             adapter_client_rx: internal_http_adapter_client_rx,
+            persist_pubsub_server: config.persist_pubsub_server,
         });
         server::serve(internal_http_conns, internal_http_server)
     });
@@ -36,6 +36,7 @@ use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::{NowFn, SYSTEM_TIME};
 use mz_ore::task;
 use mz_persist_client::cache::PersistClientCache;
+use mz_persist_client::rpc::NoopPubSubClient;
 use mz_persist_client::{PersistConfig, PersistLocation};
 use mz_secrets::SecretsController;
 use mz_storage_client::types::connections::ConnectionContext;
@@ -196,7 +197,9 @@ pub fn start_server(config: Config) -> Result<Server, anyhow::Error> {
     // Tune down the number of connections to make this all work a little easier
     // with local postgres.
     persist_cfg.consensus_connection_pool_max_size = 1;
-    let persist_clients = PersistClientCache::new(persist_cfg, &metrics_registry);
+    let persist_clients = PersistClientCache::new(persist_cfg, &metrics_registry, |_, _| {
+        Arc::new(NoopPubSubClient)
+    });
     let persist_clients = Arc::new(Mutex::new(persist_clients));
     let inner = runtime.block_on(mz_environmentd::serve(mz_environmentd::Config {
         adapter_stash_url,
@@ -205,6 +208,7 @@ pub fn start_server(config: Config) -> Result<Server, anyhow::Error> {
             orchestrator: Arc::clone(&orchestrator) as Arc<dyn Orchestrator>,
             storaged_image: "storaged".into(),
             computed_image: "computed".into(),
+            persist_pubsub_url: None,
             persist_location: PersistLocation {
                 blob_uri: format!("file://{}/persist/blob", data_directory.display()),
                 consensus_uri,
@@ -213,6 +217,7 @@ pub fn start_server(config: Config) -> Result<Server, anyhow::Error> {
             storage_stash_url,
             now: SYSTEM_TIME.clone(),
         },
+        persist_pubsub_server: None,
         secrets_controller: Arc::clone(&orchestrator) as Arc<dyn SecretsController>,
         cloud_resource_controller: None,
         sql_listen_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
@@ -19,6 +19,7 @@ use mz_lowertest::*;
 use mz_ore::result::ResultExt;
 use mz_ore::str::separated;
 use mz_repr::explain_new::{DummyHumanizer, ExprHumanizer};
+use mz_repr::stats::CollectionStatistics;
 use mz_repr::{ColumnType, GlobalId, RelationType, Row, ScalarType};
 use mz_repr_test_util::*;
 
@@ -101,6 +102,7 @@ pub fn json_to_spec(rel_json: &str, catalog: &TestCatalog) -> (String, Vec<Strin
 pub struct TestCatalog {
     objects: HashMap<String, (GlobalId, RelationType)>,
     names: HashMap<GlobalId, String>,
+    statistics: HashMap<GlobalId, CollectionStatistics>,
 }
 
 /// Contains the arguments for a command for [TestCatalog].
@@ -110,6 +112,12 @@ pub struct TestCatalog {
 enum TestCatalogCommand {
     /// Insert a source into the catalog.
     Defsource { name: String, typ: RelationType },
+    /// Record statistics about the contents of a source in the catalog.
+    Defstats {
+        name: String,
+        num_rows: usize,
+        distinct_values: Vec<usize>,
+    },
 }
 
 impl<'a> TestCatalog {
@@ -149,11 +157,18 @@ impl<'a> TestCatalog {
         self.names.get(id)
     }
 
+    /// Looks up the statistics recorded for the object referred to as `id`.
+    pub fn get_statistics(&'a self, id: &GlobalId) -> Option<&'a CollectionStatistics> {
+        self.statistics.get(id)
+    }
+
     /// Handles instructions to modify the catalog.
     ///
     /// Currently supported commands:
     /// * `(defsource [types_of_cols] [[optional_sets_of_key_cols]])` -
     ///   insert a source into the catalog.
+    /// * `(defstats name num_rows [distinct_values_of_cols])` - record
+    ///   statistics about a source previously inserted into the catalog.
     pub fn handle_test_command(&mut self, spec: &str) -> Result<(), String> {
         let mut stream_iter = tokenize(spec)?.into_iter();
         while let Some(command) = deserialize_optional_generic::<TestCatalogCommand, _>(
@@ -164,6 +179,23 @@ impl<'a> TestCatalog {
                 TestCatalogCommand::Defsource { name, typ } => {
                     self.insert(&name, typ, false)?;
                 }
+                TestCatalogCommand::Defstats {
+                    name,
+                    num_rows,
+                    distinct_values,
+                } => {
+                    let id = self
+                        .get(&name)
+                        .map(|(id, _)| *id)
+                        .ok_or_else(|| format!("Object {} does not exist in catalog", name))?;
+                    self.statistics.insert(
+                        id,
+                        CollectionStatistics {
+                            num_rows,
+                            distinct_values,
+                        },
+                    );
+                }
             }
         }
         Ok(())
@@ -185,6 +217,13 @@ impl<'a> TestCatalog {
                 true
             }
         });
+        self.statistics.retain(|k, _| {
+            if let GlobalId::Transient(_) = k {
+                false
+            } else {
+                true
+            }
+        });
     }
 }
 
@@ -139,11 +139,17 @@ impl fmt::Display for SourceInstanceId {
 
 /// Unique identifier for each part of a whole source.
 ///     Kafka -> partition
+///     KafkaTopic -> partition of one of several topics
+///     Kinesis -> shard
 ///     None -> sources that have no notion of partitioning (e.g file sources)
 #[derive(Arbitrary, Clone, Debug, Eq, Hash, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum PartitionId {
     Kafka(i32),
     None,
+    Kinesis(String),
+    /// A Kafka partition qualified with the name of its topic, for sources
+    /// that read from more than one topic.
+    KafkaTopic(String, i32),
 }
 
 impl fmt::Display for PartitionId {
@@ -151,6 +157,8 @@ impl fmt::Display for PartitionId {
         match self {
             PartitionId::Kafka(id) => write!(f, "{}", id),
             PartitionId::None => write!(f, "none"),
+            PartitionId::Kinesis(shard_id) => write!(f, "{}", shard_id),
+            PartitionId::KafkaTopic(topic, id) => write!(f, "{}/{}", topic, id),
         }
     }
 }
@@ -170,6 +178,13 @@ impl FromStr for PartitionId {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "none" => Ok(PartitionId::None),
+            // Kinesis shard ids look like `shardId-000000000000`.
+            s if s.starts_with("shardId-") => Ok(PartitionId::Kinesis(s.to_string())),
+            // Kafka topic names cannot contain slashes.
+            s if s.contains('/') => {
+                let (topic, id) = s.rsplit_once('/').unwrap();
+                Ok(PartitionId::KafkaTopic(topic.to_string(), id.parse()?))
+            }
             s => {
                 let val: i32 = s.parse()?;
                 Ok(PartitionId::Kafka(val))
@@ -185,6 +200,11 @@ impl RustType<ProtoPartitionId> for PartitionId {
             kind: Some(match self {
                 PartitionId::Kafka(x) => Kafka(*x),
                 PartitionId::None => None(()),
+                PartitionId::Kinesis(x) => Kinesis(x.clone()),
+                PartitionId::KafkaTopic(topic, partition) => KafkaTopic(ProtoKafkaTopicPartition {
+                    topic: topic.clone(),
+                    partition: *partition,
+                }),
             }),
         }
     }
@@ -194,6 +214,8 @@ impl RustType<ProtoPartitionId> for PartitionId {
         match proto.kind {
             Option::Some(Kafka(x)) => Ok(PartitionId::Kafka(x)),
             Option::Some(None(_)) => Ok(PartitionId::None),
+            Option::Some(Kinesis(x)) => Ok(PartitionId::Kinesis(x)),
+            Option::Some(KafkaTopic(x)) => Ok(PartitionId::KafkaTopic(x.topic, x.partition)),
             Option::None => Err(TryFromProtoError::missing_field("ProtoPartitionId::kind")),
         }
     }
@@ -231,5 +253,24 @@ mod tests {
             assert!(actual.is_ok());
             assert_eq!(actual.unwrap(), expect);
         }
+
+        #[test]
+        fn partition_id_protobuf_roundtrip(expect in any::<PartitionId>()) {
+            let actual = protobuf_roundtrip::<_, ProtoPartitionId>(&expect);
+            assert!(actual.is_ok());
+            assert_eq!(actual.unwrap(), expect);
+        }
+    }
+
+    #[test]
+    fn partition_id_display_roundtrip() {
+        for pid in [
+            PartitionId::Kafka(3),
+            PartitionId::None,
+            PartitionId::Kinesis("shardId-000000000001".into()),
+            PartitionId::KafkaTopic("events.tenant_1".into(), 7),
+        ] {
+            assert_eq!(pid.to_string().parse::<PartitionId>().unwrap(), pid);
+        }
     }
 }
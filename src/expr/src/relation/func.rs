@@ -9,9 +9,11 @@
 
 #![allow(missing_docs)]
 
+use std::cmp::Ordering;
+use std::collections::VecDeque;
 use std::fmt;
 use std::iter;
-use std::ops::Deref;
+use std::ops::{Deref, Range};
 
 use chrono::{DateTime, NaiveDateTime, Utc};
 use dec::OrderedDecimal;
@@ -860,99 +862,306 @@ where
     })
 }
 
-// The expected input is in the format of [((OriginalRow, InputValue), OrderByExprs...)]
-fn first_value<'a, I>(
+/// Sorts the input of a window function that is in the format of
+/// [((OriginalRow, InputValue), OrderByExprs...)] according to the ORDER BY
+/// expressions, and decodes it into (InputValue, OriginalRow) pairs.
+///
+/// Also returns the values of the ORDER BY expressions of each row, which
+/// are needed to compute window frames in RANGE and GROUPS mode.
+fn order_window_datums<'a, I>(
     datums: I,
-    temp_storage: &'a RowArena,
     order_by: &[ColumnOrder],
-    window_frame: &WindowFrame,
-) -> Datum<'a>
+) -> (Vec<(Datum<'a>, Datum<'a>)>, Vec<Row>)
 where
     I: IntoIterator<Item = Datum<'a>>,
 {
-    // Sort the datums according to the ORDER BY expressions and return the (OriginalRow, InputValue) record
-    let datums = order_aggregate_datums(datums, order_by);
-
-    // Decode the input (OriginalRow, InputValue) into separate datums
-    let datums = datums
-        .into_iter()
-        .map(|d| {
+    order_aggregate_datums_with_rank(datums, order_by)
+        .map(|(d, order_by_row)| {
             let mut iter = d.unwrap_list().iter();
             let original_row = iter.next().unwrap();
             let input_value = iter.next().unwrap();
 
-            (input_value, original_row)
+            ((input_value, original_row), order_by_row)
         })
-        .collect_vec();
+        .unzip()
+}
 
-    let length = datums.len();
-    let mut result: Vec<(Datum, Datum)> = Vec::with_capacity(length);
-    for (idx, (current_datum, original_row)) in datums.iter().enumerate() {
-        let first_value = match &window_frame.start_bound {
-            // Always return the current value
-            WindowFrameBound::CurrentRow => *current_datum,
-            WindowFrameBound::UnboundedPreceding => {
-                if let WindowFrameBound::OffsetPreceding(end_offset) = &window_frame.end_bound {
-                    let end_offset = usize::cast_from(*end_offset);
-
-                    // If the frame ends before the first row, return null
-                    if idx < end_offset {
-                        Datum::Null
-                    } else {
-                        datums[0].0
-                    }
-                } else {
-                    datums[0].0
+/// Computes the window frame of each row of a sorted partition, given the
+/// values of the ORDER BY expressions of each row.
+///
+/// The frame of each row is returned as a range of row indexes, which is
+/// empty if the frame contains no rows.
+fn window_frame_bounds(
+    window_frame: &WindowFrame,
+    order_by: &[ColumnOrder],
+    order_by_rows: &[Row],
+) -> Vec<Range<usize>> {
+    use WindowFrameBound::*;
+
+    let length = order_by_rows.len();
+    // The range of row indexes of each peer group, where peers are rows that
+    // are equal according to the ORDER BY expressions, and the index of the
+    // peer group of each row.
+    let mut groups: Vec<Range<usize>> = Vec::new();
+    let mut peer_groups = Vec::with_capacity(length);
+    for (idx, order_by_row) in order_by_rows.iter().enumerate() {
+        if idx > 0 && order_by_rows[idx - 1] == *order_by_row {
+            groups.last_mut().unwrap().end = idx + 1;
+        } else {
+            groups.push(idx..idx + 1);
+        }
+        peer_groups.push(groups.len() - 1);
+    }
+
+    peer_groups
+        .iter()
+        .enumerate()
+        .map(|(idx, group)| {
+            let (start, end) = match window_frame.units {
+                WindowFrameUnits::Rows => {
+                    let start = match window_frame.start_bound {
+                        UnboundedPreceding => 0,
+                        OffsetPreceding(offset) => idx.saturating_sub(usize::cast_from(offset)),
+                        CurrentRow => idx,
+                        OffsetFollowing(offset) => idx.saturating_add(usize::cast_from(offset)),
+                        // Forbidden during planning
+                        UnboundedFollowing => unreachable!(),
+                    };
+                    let end = match window_frame.end_bound {
+                        OffsetPreceding(offset) => {
+                            (idx + 1).saturating_sub(usize::cast_from(offset))
+                        }
+                        CurrentRow => idx + 1,
+                        OffsetFollowing(offset) => idx
+                            .saturating_add(usize::cast_from(offset))
+                            .saturating_add(1),
+                        UnboundedFollowing => length,
+                        // Forbidden during planning
+                        UnboundedPreceding => unreachable!(),
+                    };
+                    (start, end)
                 }
-            }
-            WindowFrameBound::OffsetPreceding(offset) => {
-                let start_offset = usize::cast_from(*offset);
-                let start_idx = idx.saturating_sub(start_offset);
-                if let WindowFrameBound::OffsetPreceding(end_offset) = &window_frame.end_bound {
-                    let end_offset = usize::cast_from(*end_offset);
-
-                    // If the frame is empty or ends before the first row, return null
-                    if start_offset < end_offset || idx < end_offset {
-                        Datum::Null
-                    } else {
-                        datums[start_idx].0
-                    }
+                // In RANGE mode, offsets are added to or subtracted from the
+                // value of the single ORDER BY column.
+                WindowFrameUnits::Range => {
+                    let start = match window_frame.start_bound {
+                        UnboundedPreceding => 0,
+                        OffsetPreceding(offset) => range_offset_position(
+                            order_by,
+                            order_by_rows,
+                            idx,
+                            offset,
+                            false,
+                            false,
+                        ),
+                        CurrentRow => groups[*group].start,
+                        OffsetFollowing(offset) => {
+                            range_offset_position(order_by, order_by_rows, idx, offset, true, false)
+                        }
+                        // Forbidden during planning
+                        UnboundedFollowing => unreachable!(),
+                    };
+                    let end = match window_frame.end_bound {
+                        OffsetPreceding(offset) => {
+                            range_offset_position(order_by, order_by_rows, idx, offset, false, true)
+                        }
+                        CurrentRow => groups[*group].end,
+                        OffsetFollowing(offset) => {
+                            range_offset_position(order_by, order_by_rows, idx, offset, true, true)
+                        }
+                        UnboundedFollowing => length,
+                        // Forbidden during planning
+                        UnboundedPreceding => unreachable!(),
+                    };
+                    (start, end)
+                }
+                WindowFrameUnits::Groups => {
+                    let start = match window_frame.start_bound {
+                        UnboundedPreceding => 0,
+                        OffsetPreceding(offset) => {
+                            groups[group.saturating_sub(usize::cast_from(offset))].start
+                        }
+                        CurrentRow => groups[*group].start,
+                        OffsetFollowing(offset) => groups
+                            .get(group.saturating_add(usize::cast_from(offset)))
+                            .map_or(length, |g| g.start),
+                        // Forbidden during planning
+                        UnboundedFollowing => unreachable!(),
+                    };
+                    let end = match window_frame.end_bound {
+                        OffsetPreceding(offset) => {
+                            match group.checked_sub(usize::cast_from(offset)) {
+                                Some(g) => groups[g].end,
+                                None => 0,
+                            }
+                        }
+                        CurrentRow => groups[*group].end,
+                        OffsetFollowing(offset) => groups
+                            .get(group.saturating_add(usize::cast_from(offset)))
+                            .map_or(length, |g| g.end),
+                        UnboundedFollowing => length,
+                        // Forbidden during planning
+                        UnboundedPreceding => unreachable!(),
+                    };
+                    (start, end)
+                }
+            };
+            let end = std::cmp::min(end, length);
+            let start = std::cmp::min(start, end);
+            start..end
+        })
+        .collect()
+}
+
+/// Returns the index of the first row of a sorted partition that lies past
+/// the RANGE frame bound `offset` PRECEDING or FOLLOWING the row `idx`, or, if
+/// `include_bound` is false, the first row that lies at or past that bound.
+///
+/// Planning ensures that the partition is sorted by a single numeric ORDER BY
+/// column. As in PostgreSQL, the frame bound of a row whose value is null
+/// includes exactly its peers, i.e., the other rows whose value is null.
+fn range_offset_position(
+    order_by: &[ColumnOrder],
+    order_by_rows: &[Row],
+    idx: usize,
+    offset: u64,
+    following: bool,
+    include_bound: bool,
+) -> usize {
+    let order = &order_by[0];
+    let value = order_by_rows[idx].unpack_first();
+    // Moving forward in the sort order increases the value, unless the sort
+    // order is descending.
+    let bound = if value.is_null() {
+        Some(value)
+    } else {
+        range_offset_shift(value, offset, following != order.desc)
+    };
+    // The position of the rows relative to the bound in the sort order.
+    let compare = |row: &Row| {
+        let datum = row.unpack_first();
+        let ordering = match (datum.is_null(), bound) {
+            (true, Some(bound)) if bound.is_null() => Ordering::Equal,
+            (true, _) => {
+                return if order.nulls_last {
+                    Ordering::Greater
                 } else {
-                    datums[start_idx].0
+                    Ordering::Less
                 }
             }
-            WindowFrameBound::OffsetFollowing(offset) => {
-                let start_offset = usize::cast_from(*offset);
-                let start_idx = idx.saturating_add(start_offset);
-                if let WindowFrameBound::OffsetFollowing(end_offset) = &window_frame.end_bound {
-                    // If the frame is empty or starts after the last row, return null
-                    if offset > end_offset || start_idx >= length {
-                        Datum::Null
-                    } else {
-                        datums[start_idx].0
-                    }
+            (false, Some(bound)) if bound.is_null() => {
+                return if order.nulls_last {
+                    Ordering::Less
                 } else {
-                    datums.get(start_idx).map(|d| d.0).unwrap_or(Datum::Null)
+                    Ordering::Greater
                 }
             }
-            // Forbidden during planning
-            WindowFrameBound::UnboundedFollowing => unreachable!(),
+            (false, Some(bound)) => datum.cmp(&bound),
+            // The bound lies beyond all values, in the direction of the shift.
+            (false, None) if following != order.desc => Ordering::Less,
+            (false, None) => Ordering::Greater,
         };
+        if order.desc {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    };
+    if include_bound {
+        order_by_rows.partition_point(|row| compare(row) != Ordering::Greater)
+    } else {
+        order_by_rows.partition_point(|row| compare(row) == Ordering::Less)
+    }
+}
 
-        result.push((first_value, *original_row));
+/// Adds `offset` to the numeric datum `value`, or subtracts it if `add` is
+/// false. Returns `None` if the result does not fit in the type of `value`.
+fn range_offset_shift(value: Datum, offset: u64, add: bool) -> Option<Datum> {
+    fn shift_integer<T>(value: T, offset: u64, add: bool) -> Option<T>
+    where
+        i128: From<T>,
+        T: TryFrom<i128>,
+    {
+        let value = i128::from(value);
+        let offset = i128::from(offset);
+        let shifted = if add { value + offset } else { value - offset };
+        T::try_from(shifted).ok()
     }
 
-    let result = result.into_iter().map(|(lag, original_row)| {
+    Some(match value {
+        Datum::Int16(i) => Datum::Int16(shift_integer(i, offset, add)?),
+        Datum::Int32(i) => Datum::Int32(shift_integer(i, offset, add)?),
+        Datum::Int64(i) => Datum::Int64(shift_integer(i, offset, add)?),
+        Datum::UInt16(i) => Datum::UInt16(shift_integer(i, offset, add)?),
+        Datum::UInt32(i) => Datum::UInt32(shift_integer(i, offset, add)?),
+        Datum::UInt64(i) => Datum::UInt64(shift_integer(i, offset, add)?),
+        Datum::Float32(f) => {
+            let offset = offset as f32;
+            Datum::from(if add { *f + offset } else { *f - offset })
+        }
+        Datum::Float64(f) => {
+            let offset = offset as f64;
+            Datum::from(if add { *f + offset } else { *f - offset })
+        }
+        Datum::Numeric(n) => {
+            let mut cx = numeric::cx_datum();
+            let offset = cx.from_u128(u128::from(offset));
+            let mut n = n.0;
+            if add {
+                cx.add(&mut n, &offset);
+            } else {
+                cx.sub(&mut n, &offset);
+            }
+            if cx.status().overflow() {
+                return None;
+            }
+            Datum::from(n)
+        }
+        _ => unreachable!("RANGE offsets are only planned for numeric ORDER BY columns"),
+    })
+}
+
+/// Packs the result of a window function, a list of (Result, OriginalRow)
+/// records.
+fn pack_window_results<'a, I>(results: I, temp_storage: &'a RowArena) -> Datum<'a>
+where
+    I: IntoIterator<Item = (Datum<'a>, Datum<'a>)>,
+{
+    let results = results.into_iter().map(|(result, original_row)| {
         temp_storage.make_datum(|packer| {
-            packer.push_list(vec![lag, original_row]);
+            packer.push_list(vec![result, original_row]);
         })
     });
 
     temp_storage.make_datum(|packer| {
-        packer.push_list(result);
+        packer.push_list(results);
     })
 }
 
+// The expected input is in the format of [((OriginalRow, InputValue), OrderByExprs...)]
+fn first_value<'a, I>(
+    datums: I,
+    temp_storage: &'a RowArena,
+    order_by: &[ColumnOrder],
+    window_frame: &WindowFrame,
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let (datums, order_by_rows) = order_window_datums(datums, order_by);
+    let frames = window_frame_bounds(window_frame, order_by, &order_by_rows);
+
+    let results = datums.iter().zip(frames).map(|((_, original_row), frame)| {
+        // If the frame is empty, return null
+        let first_value = datums
+            .get(frame.start)
+            .filter(|_| !frame.is_empty())
+            .map_or(Datum::Null, |d| d.0);
+        (first_value, *original_row)
+    });
+    pack_window_results(results, temp_storage)
+}
+
 // The expected input is in the format of [((OriginalRow, InputValue), OrderByExprs...)]
 fn last_value<'a, I>(
     datums: I,
@@ -963,117 +1172,300 @@ fn last_value<'a, I>(
 where
     I: IntoIterator<Item = Datum<'a>>,
 {
-    // Sort the datums according to the ORDER BY expressions and return the ((OriginalRow, InputValue), OrderByRow) record
-    // The OrderByRow is kept around because it is required to compute the peer groups in RANGE mode
-    let datums = order_aggregate_datums_with_rank(datums, order_by);
+    let (datums, order_by_rows) = order_window_datums(datums, order_by);
+    let frames = window_frame_bounds(window_frame, order_by, &order_by_rows);
 
-    // Decode the input (OriginalRow, InputValue) into separate datums, while keeping the OrderByRow
-    let datums = datums
-        .into_iter()
-        .map(|(d, order_by_row)| {
-            let mut iter = d.unwrap_list().iter();
-            let original_row = iter.next().unwrap();
-            let input_value = iter.next().unwrap();
+    let results = datums.iter().zip(frames).map(|((_, original_row), frame)| {
+        // If the frame is empty, return null
+        let last_value = match frame.end.checked_sub(1) {
+            Some(last) if !frame.is_empty() => datums[last].0,
+            _ => Datum::Null,
+        };
+        (last_value, *original_row)
+    });
+    pack_window_results(results, temp_storage)
+}
 
-            (input_value, original_row, order_by_row)
-        })
-        .collect_vec();
+// The expected input is in the format of [((OriginalRow, InputValue), OrderByExprs...)]
+fn window_aggregate<'a, I>(
+    datums: I,
+    temp_storage: &'a RowArena,
+    wrapped_aggregate: &AggregateFunc,
+    order_by: &[ColumnOrder],
+    window_frame: &WindowFrame,
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let (datums, order_by_rows) = order_window_datums(datums, order_by);
+    let frames = window_frame_bounds(window_frame, order_by, &order_by_rows);
+    let values: Vec<_> = datums.iter().map(|(value, _)| *value).collect();
+
+    let results = match SlidingAggregate::new(wrapped_aggregate) {
+        Some(sliding) => sliding.eval_frames(&values, &frames),
+        None => {
+            let mut results = Vec::with_capacity(values.len());
+            // Consecutive rows often have the same frame, e.g. the peers of a
+            // RANGE frame, in which case the aggregate is only evaluated once.
+            let mut last: Option<(Range<usize>, Datum)> = None;
+            for frame in frames {
+                let result = match &last {
+                    Some((last_frame, result)) if *last_frame == frame => *result,
+                    _ => {
+                        let result = wrapped_aggregate
+                            .eval(values[frame.clone()].iter().copied(), temp_storage);
+                        last = Some((frame, result));
+                        result
+                    }
+                };
+                results.push(result);
+            }
+            results
+        }
+    };
+    pack_window_results(
+        results
+            .into_iter()
+            .zip(datums.iter().map(|(_, original_row)| *original_row)),
+        temp_storage,
+    )
+}
 
-    let length = datums.len();
-    let mut result: Vec<(Datum, Datum)> = Vec::with_capacity(length);
-    for (idx, (current_datum, original_row, order_by_row)) in datums.iter().enumerate() {
-        let last_value = match &window_frame.end_bound {
-            WindowFrameBound::CurrentRow => match &window_frame.units {
-                // Always return the current value when in ROWS mode
-                WindowFrameUnits::Rows => *current_datum,
-                WindowFrameUnits::Range => {
-                    // When in RANGE mode, return the last value of the peer group
-                    // The peer group is the group of rows with the same ORDER BY value
-                    // Note: Range is only supported for the default window frame (RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW),
-                    // which is why it does not appear in the other branches
-                    datums[idx..]
-                        .iter()
-                        .take_while(|(_, _, row)| row == order_by_row)
-                        .last()
-                        .unwrap()
-                        .0
+/// An aggregate whose value over a window frame can be maintained as the
+/// frame slides forward, rather than evaluated anew for each frame.
+///
+/// The frames computed by [`window_frame_bounds`] only ever move forward:
+/// neither their starts nor their ends decrease from one row to the next. So
+/// over all the frames of a partition, each value enters and leaves the frame
+/// at most once.
+///
+/// Sums of floats are not maintained this way, as subtracting a value from a
+/// rounded total does not undo having added it.
+#[derive(Debug, Clone, Copy)]
+enum SlidingAggregate<'f> {
+    /// `count`, or the `sum` of integers. Values leaving the frame are
+    /// subtracted from the running total.
+    Sum(&'f AggregateFunc),
+    /// The `sum` of numerics. Finite values leaving the frame are subtracted
+    /// from a running total kept at the aggregation precision, and infinities
+    /// and NaNs are counted, as in the accumulable reductions.
+    SumNumeric,
+    /// `any` or `all`, which follow from the number of true, false, and null
+    /// values in the frame.
+    Bool { any: bool },
+    /// `max` or `min`. A monotonic queue holds the values that can still
+    /// become the extreme value of a later frame.
+    Extreme { max: bool },
+}
+
+/// The running state of a [`SlidingAggregate`] other than
+/// [`SlidingAggregate::Extreme`].
+#[derive(Debug)]
+struct SlidingCounts {
+    non_null: i64,
+    sum: i128,
+    numeric_sum: numeric::NumericAgg,
+    pos_infs: i64,
+    neg_infs: i64,
+    nans: i64,
+    trues: i64,
+    falses: i64,
+    nulls: i64,
+}
+
+impl<'f> SlidingAggregate<'f> {
+    /// Returns how to maintain `func` over sliding frames, if it can be.
+    fn new(func: &'f AggregateFunc) -> Option<Self> {
+        use AggregateFunc::*;
+        match func {
+            Count | SumInt16 | SumInt32 | SumInt64 | SumUInt16 | SumUInt32 | SumUInt64 => {
+                Some(SlidingAggregate::Sum(func))
+            }
+            SumNumeric => Some(SlidingAggregate::SumNumeric),
+            Any => Some(SlidingAggregate::Bool { any: true }),
+            All => Some(SlidingAggregate::Bool { any: false }),
+            MaxNumeric | MaxInt16 | MaxInt32 | MaxInt64 | MaxUInt16 | MaxUInt32 | MaxUInt64
+            | MaxMzTimestamp | MaxFloat32 | MaxFloat64 | MaxBool | MaxString | MaxDate
+            | MaxTimestamp | MaxTimestampTz => Some(SlidingAggregate::Extreme { max: true }),
+            MinNumeric | MinInt16 | MinInt32 | MinInt64 | MinUInt16 | MinUInt32 | MinUInt64
+            | MinMzTimestamp | MinFloat32 | MinFloat64 | MinBool | MinString | MinDate
+            | MinTimestamp | MinTimestampTz => Some(SlidingAggregate::Extreme { max: false }),
+            _ => None,
+        }
+    }
+
+    /// Evaluates the aggregate of `values` over each of `frames`, which must
+    /// only ever move forward.
+    fn eval_frames<'a>(self, values: &[Datum<'a>], frames: &[Range<usize>]) -> Vec<Datum<'a>> {
+        // The values in `values[..added]` have entered the frame, and those in
+        // `values[..removed]` have left it again.
+        let mut added = 0;
+        let mut removed = 0;
+        let mut counts = SlidingCounts {
+            non_null: 0,
+            sum: 0,
+            numeric_sum: numeric::NumericAgg::zero(),
+            pos_infs: 0,
+            neg_infs: 0,
+            nans: 0,
+            trues: 0,
+            falses: 0,
+            nulls: 0,
+        };
+        let mut queue = VecDeque::new();
+
+        let mut results = Vec::with_capacity(frames.len());
+        for frame in frames {
+            while added < frame.end {
+                let value = values[added];
+                match self {
+                    SlidingAggregate::Extreme { max } => {
+                        if !value.is_null() {
+                            // Values that are superseded by the new value can
+                            // never again be the extreme value of a frame.
+                            while let Some(last) = queue.back() {
+                                let superseded = if max {
+                                    values[*last] <= value
+                                } else {
+                                    values[*last] >= value
+                                };
+                                if !superseded {
+                                    break;
+                                }
+                                queue.pop_back();
+                            }
+                            queue.push_back(added);
+                        }
+                    }
+                    _ => self.update(&mut counts, value, 1),
                 }
-                // GROUPS is not supported, and forbidden during planning
-                WindowFrameUnits::Groups => unreachable!(),
-            },
-            WindowFrameBound::UnboundedFollowing => {
-                if let WindowFrameBound::OffsetFollowing(start_offset) = &window_frame.start_bound {
-                    let start_offset = usize::cast_from(*start_offset);
+                added += 1;
+            }
+            while removed < frame.start {
+                if !matches!(self, SlidingAggregate::Extreme { .. }) {
+                    self.update(&mut counts, values[removed], -1);
+                }
+                removed += 1;
+            }
+            while queue.front().map_or(false, |first| *first < frame.start) {
+                queue.pop_front();
+            }
 
-                    // If the frame starts after the last row of the window, return null
-                    if idx + start_offset > length - 1 {
-                        Datum::Null
+            let result = match self {
+                SlidingAggregate::Sum(AggregateFunc::Count) => Datum::Int64(counts.non_null),
+                SlidingAggregate::Sum(_) | SlidingAggregate::SumNumeric if counts.non_null == 0 => {
+                    Datum::Null
+                }
+                SlidingAggregate::Sum(AggregateFunc::SumInt16 | AggregateFunc::SumInt32) => {
+                    Datum::Int64(
+                        i64::try_from(counts.sum).expect("sum of 32-bit integers fits in i64"),
+                    )
+                }
+                SlidingAggregate::Sum(AggregateFunc::SumUInt16 | AggregateFunc::SumUInt32) => {
+                    Datum::UInt64(
+                        u64::try_from(counts.sum).expect("sum of 32-bit integers fits in u64"),
+                    )
+                }
+                SlidingAggregate::Sum(AggregateFunc::SumInt64) => Datum::from(counts.sum),
+                SlidingAggregate::Sum(AggregateFunc::SumUInt64) => {
+                    Datum::from(u128::try_from(counts.sum).expect("sum of unsigned integers"))
+                }
+                SlidingAggregate::Sum(func) => unreachable!("{} is not a sliding sum", func),
+                SlidingAggregate::SumNumeric => {
+                    let mut cx_datum = numeric::cx_datum();
+                    let sum = cx_datum.to_width(counts.numeric_sum);
+                    // A total that overflows the datum becomes an infinity.
+                    let pos_inf = counts.pos_infs > 0 || (sum.is_infinite() && !sum.is_negative());
+                    let neg_inf = counts.neg_infs > 0 || (sum.is_infinite() && sum.is_negative());
+                    if counts.nans > 0 || (pos_inf && neg_inf) {
+                        Datum::from(numeric::Numeric::nan())
+                    } else if pos_inf {
+                        Datum::from(numeric::Numeric::infinity())
+                    } else if neg_inf {
+                        let mut inf = numeric::Numeric::infinity();
+                        cx_datum.neg(&mut inf);
+                        Datum::from(inf)
                     } else {
-                        datums[length - 1].0
+                        Datum::from(sum)
                     }
-                } else {
-                    datums[length - 1].0
                 }
-            }
-            WindowFrameBound::OffsetFollowing(offset) => {
-                let end_offset = usize::cast_from(*offset);
-                let end_idx = idx.saturating_add(end_offset);
-                if let WindowFrameBound::OffsetFollowing(start_offset) = &window_frame.start_bound {
-                    let start_offset = usize::cast_from(*start_offset);
-                    let start_idx = idx.saturating_add(start_offset);
-
-                    // If the frame is empty or starts after the last row of the window, return null
-                    if end_offset < start_offset || start_idx >= length {
+                SlidingAggregate::Bool { any: true } => {
+                    if counts.trues > 0 {
+                        Datum::True
+                    } else if counts.nulls > 0 {
                         Datum::Null
                     } else {
-                        // Return the last valid element in the window
-                        datums
-                            .get(end_idx)
-                            .map(|d| d.0)
-                            .unwrap_or(datums[length - 1].0)
+                        Datum::False
                     }
-                } else {
-                    datums
-                        .get(end_idx)
-                        .map(|d| d.0)
-                        .unwrap_or(datums[length - 1].0)
                 }
-            }
-            WindowFrameBound::OffsetPreceding(offset) => {
-                let end_offset = usize::cast_from(*offset);
-                let end_idx = idx.saturating_sub(end_offset);
-                if idx < end_offset {
-                    // If the frame ends before the first row, return null
-                    Datum::Null
-                } else if let WindowFrameBound::OffsetPreceding(start_offset) =
-                    &window_frame.start_bound
-                {
-                    // If the frame is empty, return null
-                    if offset > start_offset {
+                SlidingAggregate::Bool { any: false } => {
+                    if counts.falses > 0 {
+                        Datum::False
+                    } else if counts.nulls > 0 {
                         Datum::Null
                     } else {
-                        datums[end_idx].0
+                        Datum::True
                     }
+                }
+                SlidingAggregate::Extreme { .. } => match queue.front() {
+                    Some(first) => values[*first],
+                    None => Datum::Null,
+                },
+            };
+            results.push(result);
+        }
+        results
+    }
+
+    /// Adds `value` to `counts` if `diff` is `1`, or removes it again if
+    /// `diff` is `-1`.
+    fn update(self, counts: &mut SlidingCounts, value: Datum, diff: i64) {
+        match (self, value) {
+            (SlidingAggregate::Bool { .. }, Datum::True) => counts.trues += diff,
+            (SlidingAggregate::Bool { .. }, Datum::False) => counts.falses += diff,
+            (SlidingAggregate::Bool { .. }, _) => counts.nulls += diff,
+            (_, Datum::Null) => {}
+            (SlidingAggregate::SumNumeric, Datum::Numeric(n)) => {
+                counts.non_null += diff;
+                if n.0.is_nan() {
+                    counts.nans += diff;
+                } else if n.0.is_infinite() && n.0.is_negative() {
+                    counts.neg_infs += diff;
+                } else if n.0.is_infinite() {
+                    counts.pos_infs += diff;
                 } else {
-                    datums[end_idx].0
+                    let mut cx_agg = numeric::cx_agg();
+                    let n = cx_agg.to_width(n.0);
+                    if diff > 0 {
+                        cx_agg.add(&mut counts.numeric_sum, &n);
+                    } else {
+                        cx_agg.sub(&mut counts.numeric_sum, &n);
+                    }
+                    // As in the accumulable reductions, the aggregation
+                    // precision is wide enough that this never rounds.
+                    assert!(!cx_agg.status().rounded(), "sliding numeric sum overflow");
                 }
             }
-            // Forbidden during planning
-            WindowFrameBound::UnboundedPreceding => unreachable!(),
-        };
-
-        result.push((last_value, *original_row));
+            (_, value) => {
+                counts.non_null += diff;
+                counts.sum += i128::from(diff) * Self::integer(value);
+            }
+        }
     }
 
-    let result = result.into_iter().map(|(lag, original_row)| {
-        temp_storage.make_datum(|packer| {
-            packer.push_list(vec![lag, original_row]);
-        })
-    });
-
-    temp_storage.make_datum(|packer| {
-        packer.push_list(result);
-    })
+    /// Returns the value of an integer datum.
+    fn integer(datum: Datum) -> i128 {
+        match datum {
+            Datum::Int16(i) => i128::from(i),
+            Datum::Int32(i) => i128::from(i),
+            Datum::Int64(i) => i128::from(i),
+            Datum::UInt16(i) => i128::from(i),
+            Datum::UInt32(i) => i128::from(i),
+            Datum::UInt64(i) => i128::from(i),
+            // `count` counts values of any type, and ignores their value.
+            _ => 0,
+        }
+    }
 }
 
 /// Identify whether the given aggregate function is Lag or Lead, since they share
@@ -1177,6 +1569,13 @@ pub enum AggregateFunc {
         order_by: Vec<ColumnOrder>,
         window_frame: WindowFrame,
     },
+    /// Evaluates `wrapped_aggregate` over the window frame of each row of a
+    /// partition, as in `sum(x) OVER (ORDER BY t ROWS 6 PRECEDING)`.
+    WindowAggregate {
+        wrapped_aggregate: Box<AggregateFunc>,
+        order_by: Vec<ColumnOrder>,
+        window_frame: WindowFrame,
+    },
     /// Accumulates any number of `Datum::Dummy`s into `Datum::Dummy`.
     ///
     /// Useful for removing an expensive aggregation while maintaining the shape
@@ -1276,6 +1675,22 @@ impl Arbitrary for AggregateFunc {
                     order_by,
                     window_frame,
                 }),
+            (
+                prop_oneof![
+                    Just(AggregateFunc::SumInt64),
+                    Just(AggregateFunc::MaxString),
+                    Just(AggregateFunc::Count),
+                ],
+                vec(proptest_any::<ColumnOrder>(), 1..4),
+                proptest_any::<WindowFrame>()
+            )
+                .prop_map(|(wrapped_aggregate, order_by, window_frame)| {
+                    AggregateFunc::WindowAggregate {
+                        wrapped_aggregate: Box::new(wrapped_aggregate),
+                        order_by,
+                        window_frame,
+                    }
+                }),
             Just(AggregateFunc::Dummy)
         ]
     }
@@ -1376,6 +1791,15 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                     order_by: Some(order_by.into_proto()),
                     window_frame: Some(window_frame.into_proto()),
                 }),
+                AggregateFunc::WindowAggregate {
+                    wrapped_aggregate,
+                    order_by,
+                    window_frame,
+                } => Kind::WindowAggregate(Box::new(proto_aggregate_func::ProtoWindowAggregate {
+                    wrapped_aggregate: Some(wrapped_aggregate.into_proto()),
+                    order_by: Some(order_by.into_proto()),
+                    window_frame: Some(window_frame.into_proto()),
+                })),
                 AggregateFunc::Dummy => Kind::Dummy(()),
             }),
         }
@@ -1482,6 +1906,17 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                     .window_frame
                     .into_rust_if_some("ProtoWindowFrame::window_frame")?,
             },
+            Kind::WindowAggregate(pwa) => AggregateFunc::WindowAggregate {
+                wrapped_aggregate: pwa
+                    .wrapped_aggregate
+                    .into_rust_if_some("ProtoWindowAggregate::wrapped_aggregate")?,
+                order_by: pwa
+                    .order_by
+                    .into_rust_if_some("ProtoWindowAggregate::order_by")?,
+                window_frame: pwa
+                    .window_frame
+                    .into_rust_if_some("ProtoWindowAggregate::window_frame")?,
+            },
             Kind::Dummy(()) => AggregateFunc::Dummy,
         })
     }
@@ -1556,6 +1991,17 @@ impl AggregateFunc {
                 order_by,
                 window_frame,
             } => last_value(datums, temp_storage, order_by, window_frame),
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate,
+                order_by,
+                window_frame,
+            } => window_aggregate(
+                datums,
+                temp_storage,
+                wrapped_aggregate,
+                order_by,
+                window_frame,
+            ),
             AggregateFunc::Dummy => Datum::Dummy,
         }
     }
@@ -1586,6 +2032,7 @@ impl AggregateFunc {
             AggregateFunc::LagLead { .. } => Datum::empty_list(),
             AggregateFunc::FirstValue { .. } => Datum::empty_list(),
             AggregateFunc::LastValue { .. } => Datum::empty_list(),
+            AggregateFunc::WindowAggregate { .. } => Datum::empty_list(),
             _ => Datum::Null,
         }
     }
@@ -1732,6 +2179,35 @@ impl AggregateFunc {
                     custom_id: None,
                 }
             }
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate, ..
+            } => {
+                // The input type for WindowAggregate is ((OriginalRow, InputValue), OrderByExprs...)
+                let fields = input_type.scalar_type.unwrap_record_element_type();
+                let original_row_type = fields[0].unwrap_record_element_type()[0]
+                    .clone()
+                    .nullable(false);
+                let input_value_type = fields[0].unwrap_record_element_type()[1]
+                    .clone()
+                    .nullable(true);
+                // Frames may be empty, in which case all aggregates but count
+                // produce null.
+                let value_type = wrapped_aggregate
+                    .output_type(input_value_type)
+                    .scalar_type
+                    .nullable(!matches!(**wrapped_aggregate, AggregateFunc::Count));
+
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::Record {
+                        fields: vec![
+                            (ColumnName::from("?window_agg?"), value_type),
+                            (ColumnName::from("?record?"), original_row_type),
+                        ],
+                        custom_id: None,
+                    }),
+                    custom_id: None,
+                }
+            }
             // Note AggregateFunc::MaxString, MinString rely on returning input
             // type as output type to support the proper return type for
             // character input.
@@ -2053,6 +2529,9 @@ impl fmt::Display for AggregateFunc {
             } => f.write_str("lead"),
             AggregateFunc::FirstValue { .. } => f.write_str("first_value"),
             AggregateFunc::LastValue { .. } => f.write_str("last_value"),
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate, ..
+            } => write!(f, "window_agg[{}]", wrapped_aggregate),
             AggregateFunc::Dummy => f.write_str("dummy"),
         }
     }
@@ -2517,10 +2996,98 @@ impl fmt::Display for TableFunc {
 
 #[cfg(test)]
 mod tests {
-    use super::{AggregateFunc, ProtoAggregateFunc, ProtoTableFunc, TableFunc};
+    use super::{AggregateFunc, ProtoAggregateFunc, ProtoTableFunc, SlidingAggregate, TableFunc};
     use mz_proto::protobuf_roundtrip;
+    use mz_repr::adt::numeric::{self, Numeric};
+    use mz_repr::{Datum, RowArena};
     use proptest::prelude::*;
 
+    #[test]
+    fn sliding_aggregate_matches_eval() {
+        let ints = [
+            Datum::Int32(3),
+            Datum::Null,
+            Datum::Int32(-7),
+            Datum::Int32(5),
+            Datum::Int32(5),
+            Datum::Null,
+            Datum::Null,
+            Datum::Int32(1),
+            Datum::Int32(9),
+            Datum::Int32(-2),
+        ];
+        let mut neg_inf = Numeric::infinity();
+        numeric::cx_datum().neg(&mut neg_inf);
+        let numerics = [
+            Datum::from(Numeric::from(3)),
+            Datum::Null,
+            Datum::from(Numeric::from(-7)),
+            Datum::from(Numeric::infinity()),
+            Datum::from(Numeric::from(5)),
+            Datum::Null,
+            Datum::from(neg_inf),
+            Datum::from(Numeric::from(1)),
+            Datum::from(Numeric::nan()),
+            Datum::from(Numeric::from(-2)),
+        ];
+        let bools = [
+            Datum::True,
+            Datum::Null,
+            Datum::False,
+            Datum::False,
+            Datum::True,
+            Datum::Null,
+            Datum::Null,
+            Datum::False,
+            Datum::True,
+            Datum::False,
+        ];
+        let cases = [
+            (AggregateFunc::Count, &ints),
+            (AggregateFunc::SumInt32, &ints),
+            (AggregateFunc::MaxInt32, &ints),
+            (AggregateFunc::MinInt32, &ints),
+            (AggregateFunc::Count, &numerics),
+            (AggregateFunc::SumNumeric, &numerics),
+            (AggregateFunc::Any, &bools),
+            (AggregateFunc::All, &bools),
+        ];
+
+        let len = ints.len();
+        // Frames like those of `ROWS BETWEEN x PRECEDING AND y FOLLOWING`,
+        // with y from -2 to 3, including empty frames.
+        let mut all_frames = vec![];
+        for preceding in 0..4 {
+            for following in 0..6 {
+                let frames: Vec<_> = (0..len)
+                    .map(|idx| {
+                        let end = (idx + 1 + following).saturating_sub(2).min(len);
+                        let start = idx.saturating_sub(preceding).min(end);
+                        start..end
+                    })
+                    .collect();
+                all_frames.push(frames);
+            }
+        }
+        let temp_storage = RowArena::new();
+        for (func, values) in &cases {
+            let sliding = SlidingAggregate::new(func).unwrap();
+            for frames in &all_frames {
+                let expected: Vec<_> = frames
+                    .iter()
+                    .map(|frame| func.eval(values[frame.clone()].iter().copied(), &temp_storage))
+                    .collect();
+                assert_eq!(
+                    sliding.eval_frames(&values[..], frames),
+                    expected,
+                    "{} over {:?}",
+                    func,
+                    frames
+                );
+            }
+        }
+    }
+
     proptest! {
        #[test]
         fn aggregate_func_protobuf_roundtrip(expect in any::<AggregateFunc>() ) {
@@ -2025,6 +2025,62 @@ impl AggregateExpr {
                 }
             }
 
+            // The input type for WindowAggregate is a ((OriginalRow, InputValue), OrderByExprs...)
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate,
+                window_frame,
+                ..
+            } => {
+                let tuple = self
+                    .expr
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Get the overall return type
+                let return_type = self
+                    .typ(input_type)
+                    .scalar_type
+                    .unwrap_list_element_type()
+                    .clone();
+                let window_agg_return_type = return_type.unwrap_record_element_type()[0].clone();
+
+                // Extract the original row
+                let original_row = tuple
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Extract the input value
+                let expr = tuple.call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(1)));
+
+                // If the window frame includes the current (single) row, evaluate the wrapped
+                // aggregate on its value, and on an empty input otherwise
+                let value = if window_frame.includes_current_row() {
+                    AggregateExpr {
+                        func: (**wrapped_aggregate).clone(),
+                        expr,
+                        distinct: false,
+                    }
+                    .on_unique(input_type)
+                } else {
+                    MirScalarExpr::literal_ok(wrapped_aggregate.default(), window_agg_return_type)
+                };
+
+                MirScalarExpr::CallVariadic {
+                    func: VariadicFunc::ListCreate {
+                        elem_type: return_type,
+                    },
+                    exprs: vec![MirScalarExpr::CallVariadic {
+                        func: VariadicFunc::RecordCreate {
+                            field_names: vec![
+                                ColumnName::from("?window_agg?"),
+                                ColumnName::from("?record?"),
+                            ],
+                        },
+                        exprs: vec![value, original_row],
+                    }],
+                }
+            }
+
             // All other variants should return the argument to the aggregation.
             AggregateFunc::MaxNumeric
             | AggregateFunc::MaxInt16
@@ -2145,13 +2201,17 @@ impl JoinImplementation {
 ///
 /// A candidate is described by a collection and a key, and may have various liabilities.
 /// Primarily, the candidate may risk substantial inflation of records, which is something
-/// that concerns us greatly. Additionally the candidate may be unarranged, and we would
-/// prefer candidates that do not require additional memory. Finally, we prefer lower id
-/// collections in the interest of consistent tie-breaking.
+/// that concerns us greatly. If statistics are available, we can estimate that inflation
+/// directly. Additionally the candidate may be unarranged, and we would prefer candidates
+/// that do not require additional memory. Finally, we prefer lower id collections in the
+/// interest of consistent tie-breaking.
 #[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Serialize, Deserialize, Hash, MzReflect)]
 pub struct JoinInputCharacteristics {
     /// An excellent indication that record count will not increase.
     pub unique_key: bool,
+    /// The estimated number of records matching each looked up record, rounded up, if
+    /// statistics are available for all inputs of the join.
+    pub fanout: Option<std::cmp::Reverse<usize>>,
     /// A weaker signal that record count will not increase.
     pub key_length: usize,
     /// Indicates that there will be no additional in-memory footprint.
@@ -2166,6 +2226,7 @@ impl JoinInputCharacteristics {
     /// Creates a new instance with the given characteristics.
     pub fn new(
         unique_key: bool,
+        fanout: Option<usize>,
         key_length: usize,
         arranged: bool,
         filters: FilterCharacteristics,
@@ -2173,6 +2234,7 @@ impl JoinInputCharacteristics {
     ) -> Self {
         Self {
             unique_key,
+            fanout: fanout.map(std::cmp::Reverse),
             key_length,
             arranged,
             filters,
@@ -2193,6 +2255,9 @@ impl JoinInputCharacteristics {
             e.push_str("A");
         }
         e.push_str(&self.filters.explain());
+        if let Some(std::cmp::Reverse(fanout)) = self.fanout {
+            e.push_str(&format!("~{}", fanout));
+        }
         e
     }
 }
@@ -2523,10 +2588,11 @@ pub enum WindowFrameUnits {
     /// Each row is treated as the unit of work for bounds
     Rows,
     /// Each peer group is treated as the unit of work for bounds,
-    /// and offset-based bounds use the value of the ORDER BY expression
+    /// and offset-based bounds use the value of the ORDER BY expression.
+    /// Offset-based bounds are currently not supported, and they are
+    /// rejected during planning.
     Range,
-    /// Each peer group is treated as the unit of work for bounds.
-    /// Groups is currently not supported, and it is rejected during planning.
+    /// Each peer group is treated as the unit of work for bounds
     Groups,
 }
 
@@ -16,12 +16,14 @@ mod schema;
 
 pub use envelope_cdc_v2 as cdc_v2;
 
-pub use self::decode::{Decoder, DiffPair};
+pub use self::decode::{ocf_writer_schema, Decoder, DiffPair, OcfDecoder, OcfRows};
 pub use self::encode::{
     encode_datums_as_avro, encode_debezium_transaction_unchecked, get_debezium_transaction_schema,
     AvroEncoder, AvroSchemaGenerator,
 };
-pub use self::schema::{parse_schema, schema_to_relationdesc, ConfluentAvroResolver};
+pub use self::schema::{
+    check_backward_compatibility, parse_schema, schema_to_relationdesc, ConfluentAvroResolver,
+};
 
 fn is_null(schema: &SchemaPieceOrNamed) -> bool {
     matches!(schema, SchemaPieceOrNamed::Piece(SchemaPiece::Null))
@@ -75,6 +77,46 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn backward_compatibility() -> anyhow::Result<()> {
+        let old = r#"{
+            "type": "record",
+            "name": "test",
+            "fields": [
+                { "name": "f1", "type": "int" }
+            ]
+        }"#;
+        let added_optional = r#"{
+            "type": "record",
+            "name": "test",
+            "fields": [
+                { "name": "f1", "type": "int" },
+                { "name": "f2", "type": ["null", "string"], "default": null }
+            ]
+        }"#;
+        let added_required = r#"{
+            "type": "record",
+            "name": "test",
+            "fields": [
+                { "name": "f1", "type": "int" },
+                { "name": "f2", "type": "string" }
+            ]
+        }"#;
+        let changed_type = r#"{
+            "type": "record",
+            "name": "test",
+            "fields": [
+                { "name": "f1", "type": "string" }
+            ]
+        }"#;
+
+        check_backward_compatibility(old, old)?;
+        check_backward_compatibility(old, added_optional)?;
+        assert!(check_backward_compatibility(old, added_required).is_err());
+        assert!(check_backward_compatibility(old, changed_type).is_err());
+        Ok(())
+    }
+
     #[test]
     /// Test that primitive Avro Schema types are allow Datums to be correctly
     /// serialized into Avro Values.
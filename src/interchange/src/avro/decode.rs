@@ -20,9 +20,10 @@ use tracing::trace;
 use uuid::Uuid;
 
 use mz_avro::error::{DecodeError, Error as AvroError};
+use mz_avro::schema::Schema;
 use mz_avro::{
     define_unexpected, give_value, AvroArrayAccess, AvroDecode, AvroDeserializer, AvroMapAccess,
-    AvroRead, AvroRecordAccess, GeneralDeserializer, StatefulAvroDecodable, ValueDecoder,
+    AvroRead, AvroRecordAccess, GeneralDeserializer, Reader, StatefulAvroDecodable, ValueDecoder,
     ValueOrReader,
 };
 use mz_ore::result::ResultExt;
@@ -30,7 +31,7 @@ use mz_repr::adt::jsonb::JsonbPacker;
 use mz_repr::adt::numeric;
 use mz_repr::{Datum, Row, RowPacker};
 
-use crate::avro::ConfluentAvroResolver;
+use crate::avro::{parse_schema, ConfluentAvroResolver};
 
 /// Manages decoding of Avro-encoded bytes.
 #[derive(Debug)]
@@ -126,6 +127,94 @@ impl Decoder {
     }
 }
 
+/// Manages decoding of Avro object container files.
+///
+/// Unlike [`Decoder`], which decodes individual records, an `OcfDecoder`
+/// decodes entire files, which carry their writer schema in their header. Each
+/// file's writer schema is resolved against the reader schema, so files
+/// written with different (but compatible) schemas can be decoded by the same
+/// `OcfDecoder`.
+#[derive(Debug)]
+pub struct OcfDecoder {
+    reader_schema: Schema,
+}
+
+impl OcfDecoder {
+    /// Creates a new `OcfDecoder` with the provided reader schema.
+    pub fn new(reader_schema: &str) -> anyhow::Result<OcfDecoder> {
+        Ok(OcfDecoder {
+            reader_schema: parse_schema(reader_schema)?,
+        })
+    }
+
+    /// Decodes every record in the object container file `bytes` into a `Row`.
+    pub fn decode(&mut self, bytes: &[u8]) -> anyhow::Result<Vec<Row>> {
+        self.read(bytes)?.collect()
+    }
+
+    /// Returns the records of the object container file read from `reader`,
+    /// decoded into `Row`s.
+    ///
+    /// Only the header of the file is read upfront. Blocks are read and
+    /// decoded as the returned iterator reaches them.
+    pub fn read<R: AvroRead>(&self, reader: R) -> anyhow::Result<OcfRows<R>> {
+        let reader = Reader::with_schema(&self.reader_schema, reader)
+            .context("reading avro object container file header")?;
+        Ok(OcfRows {
+            reader,
+            failed: false,
+            buf: vec![],
+            row_buf: Row::default(),
+        })
+    }
+}
+
+/// An iterator over the records of an Avro object container file, which reads
+/// one block at a time.
+///
+/// Iteration ends after the first error.
+pub struct OcfRows<R> {
+    reader: Reader<R>,
+    /// Whether decoding has failed.
+    failed: bool,
+    buf: Vec<u8>,
+    row_buf: Row,
+}
+
+impl<R: AvroRead> Iterator for OcfRows<R> {
+    type Item = anyhow::Result<Row>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let result = self
+            .reader
+            .next()?
+            .context("reading avro object container file block")
+            .and_then(|value| {
+                let mut packer = self.row_buf.packer();
+                let dec = AvroFlatDecoder {
+                    packer: &mut packer,
+                    buf: &mut self.buf,
+                    is_top: true,
+                };
+                give_value(dec, &value).context("unable to decode row")
+            })
+            .map(|()| self.row_buf.clone());
+        if result.is_err() {
+            self.failed = true;
+        }
+        Some(result)
+    }
+}
+
+/// Returns the writer schema of the Avro object container file `bytes`.
+pub fn ocf_writer_schema(bytes: &[u8]) -> anyhow::Result<String> {
+    let reader = Reader::new(bytes).context("reading avro object container file header")?;
+    Ok(serde_json::to_string(reader.writer_schema())?)
+}
+
 pub struct AvroStringDecoder<'a> {
     pub buf: &'a mut Vec<u8>,
 }
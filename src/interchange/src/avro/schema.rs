@@ -59,6 +59,16 @@ pub fn parse_schema(schema: &str) -> anyhow::Result<Schema> {
     Ok(Schema::parse(&schema)?)
 }
 
+/// Checks that `new_schema` is a backward compatible evolution of
+/// `old_schema`, i.e. that data written with `old_schema` can be read with
+/// `new_schema`.
+pub fn check_backward_compatibility(old_schema: &str, new_schema: &str) -> anyhow::Result<()> {
+    let old_schema = parse_schema(old_schema).context("parsing old schema")?;
+    let new_schema = parse_schema(new_schema).context("parsing new schema")?;
+    resolve_schemas(&old_schema, &new_schema)?;
+    Ok(())
+}
+
 /// Converts an Apache Avro schema into a list of column names and types.
 // TODO(petrosagg): find a way to make this a TryFrom impl somewhere
 pub fn schema_to_relationdesc(schema: Schema) -> Result<RelationDesc, anyhow::Error> {
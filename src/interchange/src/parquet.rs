@@ -0,0 +1,625 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Translations between Apache Parquet objects and rows.
+//!
+//! The columns to read from a Parquet object are described by an Avro record
+//! schema, which lets Parquet sources share the type mapping (and the
+//! `USING SCHEMA` syntax) of Avro sources. [`infer_parquet_schema`] produces
+//! such a schema from the footer of an existing object. Columns are matched by
+//! name, so objects whose columns appear in a different order, or that lack
+//! nullable columns, can still be decoded.
+//!
+//! [`ParquetDecoder::read`] decodes an object one row group at a time, so
+//! only the row group being decoded is held in memory.
+//!
+//! [`encode_parquet`] writes rows to a new Parquet object, mapping each column
+//! to the closest Arrow type. Columns of types without an obvious Arrow
+//! counterpart, like `uuid` or `jsonb`, are rejected.
+
+use std::io::{Cursor, Read, Seek};
+
+use anyhow::{anyhow, bail, Context};
+use arrow2::array::{
+    Array, BinaryArray, BooleanArray, FixedSizeBinaryArray, PrimitiveArray, Utf8Array,
+};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow2::io::parquet::read::{infer_schema, read_metadata, FileReader};
+use arrow2::io::parquet::write::{
+    CompressionOptions, Encoding, FileWriter, RowGroupIterator, Version, WriteOptions,
+};
+use chrono::NaiveDateTime;
+use serde_json::json;
+
+use mz_repr::adt::date::Date;
+use mz_repr::adt::numeric;
+use mz_repr::adt::timestamp::CheckedTimestamp;
+use mz_repr::{ColumnType, Datum, RelationDesc, Row, RowPacker, ScalarType};
+
+use crate::avro::{parse_schema, schema_to_relationdesc};
+
+/// Infers an Avro record schema describing the columns of the Parquet object
+/// `bytes`.
+pub fn infer_parquet_schema(bytes: &[u8]) -> anyhow::Result<String> {
+    let metadata = read_metadata(&mut Cursor::new(bytes)).context("reading parquet footer")?;
+    let schema = infer_schema(&metadata)?;
+    let mut fields = vec![];
+    for field in &schema.fields {
+        let ty = avro_type(&field.data_type)
+            .ok_or_else(|| anyhow!("column {} has unsupported parquet type", field.name))?;
+        fields.push(if field.is_nullable {
+            json!({"name": field.name, "type": ["null", ty], "default": null})
+        } else {
+            json!({"name": field.name, "type": ty})
+        });
+    }
+    let schema = json!({
+        "type": "record",
+        "name": "row",
+        "fields": fields,
+    });
+    Ok(schema.to_string())
+}
+
+/// Returns the Avro type corresponding to an Arrow type, if there is one.
+fn avro_type(data_type: &DataType) -> Option<serde_json::Value> {
+    Some(match data_type.to_logical_type() {
+        DataType::Boolean => json!("boolean"),
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::UInt8 | DataType::UInt16 => {
+            json!("int")
+        }
+        DataType::Int64 | DataType::UInt32 => json!("long"),
+        DataType::Float32 => json!("float"),
+        DataType::Float64 => json!("double"),
+        DataType::Utf8 | DataType::LargeUtf8 => json!("string"),
+        DataType::Binary | DataType::LargeBinary | DataType::FixedSizeBinary(_) => {
+            json!("bytes")
+        }
+        DataType::Date32 => json!({"type": "int", "logicalType": "date"}),
+        DataType::Timestamp(TimeUnit::Second | TimeUnit::Millisecond, _) => {
+            json!({"type": "long", "logicalType": "timestamp-millis"})
+        }
+        DataType::Timestamp(TimeUnit::Microsecond | TimeUnit::Nanosecond, _) => {
+            json!({"type": "long", "logicalType": "timestamp-micros"})
+        }
+        DataType::Decimal(precision, scale) => json!({
+            "type": "bytes",
+            "logicalType": "decimal",
+            "precision": precision,
+            "scale": scale,
+        }),
+        _ => return None,
+    })
+}
+
+/// The maximum number of rows in each row group written by [`encode_parquet`].
+const ROW_GROUP_ROWS: usize = 64 << 10;
+
+/// Manages decoding of whole Parquet objects.
+#[derive(Debug)]
+pub struct ParquetDecoder {
+    desc: RelationDesc,
+}
+
+impl ParquetDecoder {
+    /// Creates a new `ParquetDecoder` that reads the columns described by the
+    /// Avro record schema `reader_schema`.
+    pub fn new(reader_schema: &str) -> anyhow::Result<ParquetDecoder> {
+        let desc = schema_to_relationdesc(parse_schema(reader_schema)?)?;
+        Ok(ParquetDecoder { desc })
+    }
+
+    /// Creates a new `ParquetDecoder` that reads the columns of `desc`.
+    pub fn from_desc(desc: RelationDesc) -> ParquetDecoder {
+        ParquetDecoder { desc }
+    }
+
+    /// Decodes every row of every row group in the Parquet object `bytes`.
+    pub fn decode(&mut self, bytes: &[u8]) -> anyhow::Result<Vec<Row>> {
+        self.read(Cursor::new(bytes))?.collect()
+    }
+
+    /// Returns the rows of the Parquet object read from `reader`.
+    ///
+    /// Only the footer of the object is read upfront. Row groups are read and
+    /// decoded as the returned iterator reaches them.
+    pub fn read<R: Read + Seek>(&self, mut reader: R) -> anyhow::Result<ParquetRows<R>> {
+        let metadata = read_metadata(&mut reader).context("reading parquet footer")?;
+        let schema = infer_schema(&metadata)?;
+
+        // Only read the columns we need, in the order we need them. `columns`
+        // maps each output column to its position in the projection, or `None`
+        // if the object does not contain the column.
+        let mut projection: Vec<Field> = vec![];
+        let mut columns = vec![];
+        for (name, typ) in self.desc.iter() {
+            match schema.fields.iter().find(|f| f.name == name.as_str()) {
+                Some(field) => {
+                    columns.push(Some(projection.len()));
+                    projection.push(field.clone());
+                }
+                None if typ.nullable => columns.push(None),
+                None => bail!("parquet object is missing non-nullable column {}", name),
+            }
+        }
+
+        let reader = FileReader::new(
+            reader,
+            metadata.row_groups,
+            Schema::from(projection),
+            None,
+            None,
+            None,
+        );
+        Ok(ParquetRows {
+            desc: self.desc.clone(),
+            columns,
+            reader,
+            chunk: None,
+            next_row: 0,
+            failed: false,
+            row_buf: Row::default(),
+        })
+    }
+}
+
+/// An iterator over the rows of a Parquet object, which decodes one row group
+/// at a time.
+///
+/// Iteration ends after the first error.
+pub struct ParquetRows<R: Read + Seek> {
+    desc: RelationDesc,
+    columns: Vec<Option<usize>>,
+    reader: FileReader<R>,
+    /// The row group being decoded.
+    chunk: Option<Chunk<Box<dyn Array>>>,
+    /// The index of the next row to decode in `chunk`.
+    next_row: usize,
+    /// Whether decoding has failed.
+    failed: bool,
+    row_buf: Row,
+}
+
+impl<R: Read + Seek> Iterator for ParquetRows<R> {
+    type Item = anyhow::Result<Row>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        loop {
+            if let Some(chunk) = &self.chunk {
+                if self.next_row < chunk.len() {
+                    let i = self.next_row;
+                    self.next_row += 1;
+                    let result = decode_row(&self.desc, &self.columns, chunk, i, &mut self.row_buf);
+                    if result.is_err() {
+                        self.failed = true;
+                    }
+                    return Some(result);
+                }
+            }
+            match self.reader.next()? {
+                Ok(chunk) => {
+                    self.chunk = Some(chunk);
+                    self.next_row = 0;
+                }
+                Err(err) => {
+                    self.failed = true;
+                    return Some(Err(err.into()));
+                }
+            }
+        }
+    }
+}
+
+/// Decodes the `i`th row of `chunk`, whose columns are projected as described
+/// by `columns`.
+fn decode_row(
+    desc: &RelationDesc,
+    columns: &[Option<usize>],
+    chunk: &Chunk<Box<dyn Array>>,
+    i: usize,
+    row_buf: &mut Row,
+) -> anyhow::Result<Row> {
+    let arrays = chunk.arrays();
+    let mut packer = row_buf.packer();
+    for ((name, typ), column) in desc.iter().zip(columns) {
+        match column {
+            Some(column) => push_datum(&mut packer, &*arrays[*column], i, typ)
+                .with_context(|| format!("decoding column {}", name))?,
+            None => packer.push(Datum::Null),
+        }
+    }
+    Ok(row_buf.clone())
+}
+
+/// Downcasts `array` to the concrete array type `$t` and returns its `i`th value.
+macro_rules! value {
+    ($array:expr, $t:ty, $i:expr) => {
+        $array
+            .as_any()
+            .downcast_ref::<$t>()
+            .expect("array matches its data type")
+            .value($i)
+    };
+}
+
+/// Pushes the `i`th value of `array` onto `packer` as a datum of type `typ`.
+fn push_datum(
+    packer: &mut RowPacker,
+    array: &dyn Array,
+    i: usize,
+    typ: &ColumnType,
+) -> anyhow::Result<()> {
+    if array.is_null(i) {
+        if !typ.nullable {
+            bail!("unexpected null value");
+        }
+        packer.push(Datum::Null);
+        return Ok(());
+    }
+    let datum = match (array.data_type().to_logical_type(), &typ.scalar_type) {
+        (DataType::Boolean, ScalarType::Bool) => Datum::from(value!(array, BooleanArray, i)),
+        (DataType::Int8, ScalarType::Int32) => {
+            Datum::Int32(value!(array, PrimitiveArray<i8>, i).into())
+        }
+        (DataType::Int16, ScalarType::Int32) => {
+            Datum::Int32(value!(array, PrimitiveArray<i16>, i).into())
+        }
+        (DataType::Int32, ScalarType::Int32) => Datum::Int32(value!(array, PrimitiveArray<i32>, i)),
+        (DataType::UInt8, ScalarType::Int32) => {
+            Datum::Int32(value!(array, PrimitiveArray<u8>, i).into())
+        }
+        (DataType::UInt16, ScalarType::Int32) => {
+            Datum::Int32(value!(array, PrimitiveArray<u16>, i).into())
+        }
+        (DataType::Int8, ScalarType::Int64) => {
+            Datum::Int64(value!(array, PrimitiveArray<i8>, i).into())
+        }
+        (DataType::Int16, ScalarType::Int64) => {
+            Datum::Int64(value!(array, PrimitiveArray<i16>, i).into())
+        }
+        (DataType::Int32, ScalarType::Int64) => {
+            Datum::Int64(value!(array, PrimitiveArray<i32>, i).into())
+        }
+        (DataType::Int64, ScalarType::Int64) => Datum::Int64(value!(array, PrimitiveArray<i64>, i)),
+        (DataType::UInt8, ScalarType::Int64) => {
+            Datum::Int64(value!(array, PrimitiveArray<u8>, i).into())
+        }
+        (DataType::UInt16, ScalarType::Int64) => {
+            Datum::Int64(value!(array, PrimitiveArray<u16>, i).into())
+        }
+        (DataType::UInt32, ScalarType::Int64) => {
+            Datum::Int64(value!(array, PrimitiveArray<u32>, i).into())
+        }
+        (DataType::Float32, ScalarType::Float32) => {
+            Datum::Float32(value!(array, PrimitiveArray<f32>, i).into())
+        }
+        (DataType::Float32, ScalarType::Float64) => {
+            Datum::Float64(f64::from(value!(array, PrimitiveArray<f32>, i)).into())
+        }
+        (DataType::Float64, ScalarType::Float64) => {
+            Datum::Float64(value!(array, PrimitiveArray<f64>, i).into())
+        }
+        (DataType::Utf8, ScalarType::String) => Datum::String(value!(array, Utf8Array<i32>, i)),
+        (DataType::LargeUtf8, ScalarType::String) => {
+            Datum::String(value!(array, Utf8Array<i64>, i))
+        }
+        (DataType::Binary, ScalarType::Bytes) => Datum::Bytes(value!(array, BinaryArray<i32>, i)),
+        (DataType::LargeBinary, ScalarType::Bytes) => {
+            Datum::Bytes(value!(array, BinaryArray<i64>, i))
+        }
+        (DataType::FixedSizeBinary(_), ScalarType::Bytes) => {
+            Datum::Bytes(value!(array, FixedSizeBinaryArray, i))
+        }
+        (DataType::Date32, ScalarType::Date) => {
+            let days = value!(array, PrimitiveArray<i32>, i);
+            Datum::Date(Date::from_unix_epoch(days).map_err(|_| anyhow!("date out of range"))?)
+        }
+        (DataType::Timestamp(unit, _), ScalarType::Timestamp) => {
+            let ts = value!(array, PrimitiveArray<i64>, i);
+            let (per_second, nanos_per_unit) = match unit {
+                TimeUnit::Second => (1, 1_000_000_000),
+                TimeUnit::Millisecond => (1_000, 1_000_000),
+                TimeUnit::Microsecond => (1_000_000, 1_000),
+                TimeUnit::Nanosecond => (1_000_000_000, 1),
+            };
+            let secs = ts.div_euclid(per_second);
+            let nanos = u32::try_from(ts.rem_euclid(per_second) * nanos_per_unit)
+                .expect("remainder fits in u32");
+            let ts = NaiveDateTime::from_timestamp_opt(secs, nanos)
+                .ok_or_else(|| anyhow!("timestamp out of range"))?;
+            Datum::Timestamp(CheckedTimestamp::from_timestamplike(ts)?)
+        }
+        (DataType::Decimal(_, scale), ScalarType::Numeric { .. }) => {
+            let mut bytes = value!(array, PrimitiveArray<i128>, i).to_be_bytes();
+            let scale = u8::try_from(*scale).map_err(|_| anyhow!("decimal scale too large"))?;
+            Datum::from(numeric::twos_complement_be_to_numeric(&mut bytes, scale)?)
+        }
+        (data_type, scalar_type) => bail!(
+            "cannot decode parquet type {:?} as {:?}",
+            data_type,
+            scalar_type
+        ),
+    };
+    packer.push(datum);
+    Ok(())
+}
+
+/// Encodes `rows`, whose columns are described by `desc`, as a single Parquet
+/// object with one row group.
+pub fn encode_parquet(desc: &RelationDesc, rows: &[Row]) -> anyhow::Result<Vec<u8>> {
+    let rows: Vec<Vec<Datum>> = rows.iter().map(|row| row.unpack()).collect();
+    let mut fields = vec![];
+    let mut row_groups = vec![];
+    // Write the rows in bounded row groups, so that readers can decode the
+    // object one row group at a time. Empty objects have one empty row group.
+    let mut start = 0;
+    loop {
+        let end = std::cmp::min(start + ROW_GROUP_ROWS, rows.len());
+        fields.clear();
+        let mut arrays = vec![];
+        for (i, (name, typ)) in desc.iter().enumerate() {
+            let datums = rows[start..end].iter().map(|datums| datums[i]);
+            let array = encode_column(&typ.scalar_type, datums).ok_or_else(|| {
+                anyhow!(
+                    "column {} has type {:?}, which cannot be written to parquet",
+                    name,
+                    typ.scalar_type
+                )
+            })?;
+            fields.push(Field::new(
+                name.as_str(),
+                array.data_type().clone(),
+                typ.nullable,
+            ));
+            arrays.push(array);
+        }
+        row_groups.push(arrays);
+        start = end;
+        if start == rows.len() {
+            break;
+        }
+    }
+    write_parquet(fields, row_groups)
+}
+
+/// Collects `datums` into an Arrow array, or returns `None` if columns of type
+/// `scalar_type` cannot be represented in Parquet.
+fn encode_column<'a>(
+    scalar_type: &ScalarType,
+    datums: impl Iterator<Item = Datum<'a>>,
+) -> Option<Box<dyn Array>> {
+    fn collect<'a, T>(
+        datums: impl Iterator<Item = Datum<'a>>,
+        f: impl Fn(Datum<'a>) -> T,
+    ) -> Vec<Option<T>> {
+        datums
+            .map(|datum| {
+                if datum.is_null() {
+                    None
+                } else {
+                    Some(f(datum))
+                }
+            })
+            .collect()
+    }
+
+    Some(match scalar_type {
+        ScalarType::Bool => Box::new(BooleanArray::from(collect(datums, |d| d.unwrap_bool()))),
+        ScalarType::Int16 => Box::new(PrimitiveArray::<i16>::from(collect(datums, |d| {
+            d.unwrap_int16()
+        }))),
+        ScalarType::Int32 => Box::new(PrimitiveArray::<i32>::from(collect(datums, |d| {
+            d.unwrap_int32()
+        }))),
+        ScalarType::Int64 => Box::new(PrimitiveArray::<i64>::from(collect(datums, |d| {
+            d.unwrap_int64()
+        }))),
+        ScalarType::Float32 => Box::new(PrimitiveArray::<f32>::from(collect(datums, |d| {
+            d.unwrap_float32()
+        }))),
+        ScalarType::Float64 => Box::new(PrimitiveArray::<f64>::from(collect(datums, |d| {
+            d.unwrap_float64()
+        }))),
+        ScalarType::String | ScalarType::VarChar { .. } => {
+            Box::new(Utf8Array::<i32>::from(collect(datums, |d| d.unwrap_str())))
+        }
+        ScalarType::Bytes => Box::new(BinaryArray::<i32>::from(collect(datums, |d| {
+            d.unwrap_bytes()
+        }))),
+        ScalarType::Date => Box::new(
+            PrimitiveArray::<i32>::from(collect(datums, |d| d.unwrap_date().unix_epoch_days()))
+                .to(DataType::Date32),
+        ),
+        ScalarType::Timestamp => Box::new(
+            PrimitiveArray::<i64>::from(collect(datums, |d| {
+                d.unwrap_timestamp().timestamp_micros()
+            }))
+            .to(DataType::Timestamp(TimeUnit::Microsecond, None)),
+        ),
+        _ => return None,
+    })
+}
+
+/// Writes a Parquet object with one row group for each element of `row_groups`.
+fn write_parquet(
+    fields: Vec<Field>,
+    row_groups: Vec<Vec<Box<dyn Array>>>,
+) -> anyhow::Result<Vec<u8>> {
+    let schema = Schema::from(fields);
+    let options = WriteOptions {
+        write_statistics: false,
+        compression: CompressionOptions::Uncompressed,
+        version: Version::V2,
+    };
+    let encodings = schema
+        .fields
+        .iter()
+        .map(|_| vec![Encoding::Plain])
+        .collect();
+    let chunks = row_groups.into_iter().map(|arrays| Ok(Chunk::new(arrays)));
+    let row_groups = RowGroupIterator::try_new(chunks, &schema, options, encodings)?;
+    let mut buf = vec![];
+    let mut writer = FileWriter::try_new(&mut buf, schema, options)?;
+    for group in row_groups {
+        writer.write(group?)?;
+    }
+    writer.end(None)?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_and_decode() {
+        let bytes = write_parquet(
+            vec![
+                Field::new("id", DataType::Int64, false),
+                Field::new("name", DataType::Utf8, true),
+            ],
+            vec![vec![
+                Box::new(PrimitiveArray::<i64>::from_slice([1, 2])),
+                Box::new(Utf8Array::<i32>::from([Some("a"), None])),
+            ]],
+        )
+        .unwrap();
+
+        let schema = infer_parquet_schema(&bytes).unwrap();
+        let desc = schema_to_relationdesc(parse_schema(&schema).unwrap()).unwrap();
+        assert_eq!(
+            desc,
+            RelationDesc::empty()
+                .with_column("id", ScalarType::Int64.nullable(false))
+                .with_column("name", ScalarType::String.nullable(true))
+        );
+
+        let mut decoder = ParquetDecoder::new(&schema).unwrap();
+        assert_eq!(
+            decoder.decode(&bytes).unwrap(),
+            vec![
+                Row::pack_slice(&[Datum::Int64(1), Datum::String("a")]),
+                Row::pack_slice(&[Datum::Int64(2), Datum::Null]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_by_name() {
+        // Columns are matched by name, and nullable columns that are missing
+        // from an object decode as nulls.
+        let bytes = write_parquet(
+            vec![
+                Field::new("b", DataType::Int32, false),
+                Field::new("a", DataType::Boolean, false),
+            ],
+            vec![vec![
+                Box::new(PrimitiveArray::<i32>::from_slice([7])),
+                Box::new(BooleanArray::from_slice([true])),
+            ]],
+        )
+        .unwrap();
+        let schema = r#"{
+            "type": "record",
+            "name": "row",
+            "fields": [
+                {"name": "a", "type": "boolean"},
+                {"name": "b", "type": "long"},
+                {"name": "c", "type": ["null", "string"], "default": null}
+            ]
+        }"#;
+        let mut decoder = ParquetDecoder::new(schema).unwrap();
+        assert_eq!(
+            decoder.decode(&bytes).unwrap(),
+            vec![Row::pack_slice(&[
+                Datum::True,
+                Datum::Int64(7),
+                Datum::Null
+            ])]
+        );
+
+        let schema = r#"{
+            "type": "record",
+            "name": "row",
+            "fields": [{"name": "c", "type": "string"}]
+        }"#;
+        let mut decoder = ParquetDecoder::new(schema).unwrap();
+        assert!(decoder.decode(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_read_row_groups() {
+        // Row groups are decoded as they are reached, so rows of earlier row
+        // groups are produced before an error in a later one.
+        let bytes = write_parquet(
+            vec![Field::new("a", DataType::Int64, true)],
+            vec![
+                vec![Box::new(PrimitiveArray::<i64>::from_slice([1, 2]))],
+                vec![Box::new(PrimitiveArray::<i64>::from([Some(3), None]))],
+                vec![Box::new(PrimitiveArray::<i64>::from_slice([4]))],
+            ],
+        )
+        .unwrap();
+        let desc = RelationDesc::empty().with_column("a", ScalarType::Int64.nullable(false));
+        let decoder = ParquetDecoder::from_desc(desc);
+        let mut rows = decoder.read(Cursor::new(&bytes)).unwrap();
+        for i in 1..=3 {
+            assert_eq!(
+                rows.next().unwrap().unwrap(),
+                Row::pack_slice(&[Datum::Int64(i)])
+            );
+        }
+        assert!(rows.next().unwrap().is_err());
+        assert!(rows.next().is_none());
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let desc = RelationDesc::empty()
+            .with_column("b", ScalarType::Bool.nullable(false))
+            .with_column("i", ScalarType::Int32.nullable(true))
+            .with_column("f", ScalarType::Float64.nullable(false))
+            .with_column("s", ScalarType::String.nullable(true))
+            .with_column("d", ScalarType::Date.nullable(false))
+            .with_column("ts", ScalarType::Timestamp.nullable(false));
+        let date = Date::from_unix_epoch(19000).unwrap();
+        let ts = CheckedTimestamp::from_timestamplike(
+            NaiveDateTime::from_timestamp_opt(1_650_000_000, 123_456_000).unwrap(),
+        )
+        .unwrap();
+        let rows = vec![
+            Row::pack_slice(&[
+                Datum::True,
+                Datum::Int32(1),
+                Datum::Float64(1.5.into()),
+                Datum::String("a"),
+                Datum::Date(date),
+                Datum::Timestamp(ts),
+            ]),
+            Row::pack_slice(&[
+                Datum::False,
+                Datum::Null,
+                Datum::Float64((-2.0).into()),
+                Datum::Null,
+                Datum::Date(date),
+                Datum::Timestamp(ts),
+            ]),
+        ];
+        let bytes = encode_parquet(&desc, &rows).unwrap();
+        let mut decoder = ParquetDecoder::from_desc(desc);
+        assert_eq!(decoder.decode(&bytes).unwrap(), rows);
+
+        let desc = RelationDesc::empty().with_column("u", ScalarType::Uuid.nullable(false));
+        assert!(encode_parquet(&desc, &[]).is_err());
+    }
+}
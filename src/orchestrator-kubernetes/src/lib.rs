@@ -19,8 +19,9 @@ use clap::ArgEnum;
 use futures::stream::{BoxStream, StreamExt};
 use k8s_openapi::api::apps::v1::{StatefulSet, StatefulSetSpec};
 use k8s_openapi::api::core::v1::{
-    Affinity, Container, ContainerPort, Pod, PodAffinityTerm, PodAntiAffinity, PodSpec,
-    PodTemplateSpec, ResourceRequirements, Secret, Service as K8sService, ServicePort, ServiceSpec,
+    Affinity, Container, ContainerPort, EmptyDirVolumeSource, Pod, PodAffinityTerm,
+    PodAntiAffinity, PodSpec, PodTemplateSpec, ResourceRequirements, Secret, Service as K8sService,
+    ServicePort, ServiceSpec, Volume, VolumeMount,
 };
 use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, LabelSelectorRequirement};
@@ -45,6 +46,10 @@ pub mod secrets;
 pub mod util;
 
 const FIELD_MANAGER: &str = "environmentd";
+/// The name of the volume backing the scratch directory of services.
+const SCRATCH_VOLUME_NAME: &str = "scratch";
+/// The path at which the scratch directory of services is mounted.
+const SCRATCH_MOUNT_PATH: &str = "/scratch";
 
 /// Configures a [`KubernetesOrchestrator`].
 #[derive(Debug, Clone)]
@@ -214,6 +219,7 @@ impl NamespacedOrchestrator for NamespacedKubernetesOrchestrator {
             memory_limit,
             cpu_limit,
             scale,
+            disk,
             labels: labels_in,
             availability_zone,
             anti_affinity,
@@ -318,6 +324,9 @@ impl NamespacedOrchestrator for NamespacedKubernetesOrchestrator {
             "--secrets-reader-kubernetes-context={}",
             self.config.context
         ));
+        if disk {
+            args.push(format!("--scratch-directory={SCRATCH_MOUNT_PATH}"));
+        }
 
         let anti_affinity = anti_affinity
             .map(|label_selectors| -> Result<_, anyhow::Error> {
@@ -383,8 +392,22 @@ impl NamespacedOrchestrator for NamespacedKubernetesOrchestrator {
                         limits: Some(limits),
                         ..Default::default()
                     }),
+                    volume_mounts: disk.then(|| {
+                        vec![VolumeMount {
+                            name: SCRATCH_VOLUME_NAME.into(),
+                            mount_path: SCRATCH_MOUNT_PATH.into(),
+                            ..Default::default()
+                        }]
+                    }),
                     ..Default::default()
                 }],
+                volumes: disk.then(|| {
+                    vec![Volume {
+                        name: SCRATCH_VOLUME_NAME.into(),
+                        empty_dir: Some(EmptyDirVolumeSource::default()),
+                        ..Default::default()
+                    }]
+                }),
                 node_selector: Some(node_selector),
                 service_account: self.config.service_account.clone(),
                 affinity: Some(Affinity {
@@ -76,6 +76,7 @@ pub struct ProcessOrchestrator {
     namespaces: Mutex<HashMap<String, Arc<dyn NamespacedOrchestrator>>>,
     data_dir: PathBuf,
     secrets_dir: PathBuf,
+    scratch_dir: PathBuf,
     command_wrapper: Vec<String>,
 }
 
@@ -97,6 +98,10 @@ impl ProcessOrchestrator {
         fs::set_permissions(&secrets_dir, Permissions::from_mode(0o700))
             .await
             .context("setting secrets directory permissions")?;
+        let scratch_dir = data_dir.join("scratch");
+        fs::create_dir_all(&scratch_dir)
+            .await
+            .context("creating scratch directory")?;
         Ok(ProcessOrchestrator {
             image_dir: fs::canonicalize(image_dir).await?,
             port_allocator,
@@ -104,6 +109,7 @@ impl ProcessOrchestrator {
             namespaces: Mutex::new(HashMap::new()),
             data_dir: fs::canonicalize(data_dir).await?,
             secrets_dir: fs::canonicalize(secrets_dir).await?,
+            scratch_dir: fs::canonicalize(scratch_dir).await?,
             command_wrapper,
         })
     }
@@ -121,6 +127,7 @@ impl Orchestrator for ProcessOrchestrator {
                 supervisors: Arc::new(Mutex::new(HashMap::new())),
                 data_dir: self.data_dir.clone(),
                 secrets_dir: self.secrets_dir.clone(),
+                scratch_dir: self.scratch_dir.clone(),
                 command_wrapper: self.command_wrapper.clone(),
             })
         }))
@@ -136,6 +143,7 @@ struct NamespacedProcessOrchestrator {
     supervisors: Arc<Mutex<HashMap<String, Vec<AbortOnDrop>>>>,
     data_dir: PathBuf,
     secrets_dir: PathBuf,
+    scratch_dir: PathBuf,
     command_wrapper: Vec<String>,
 }
 
@@ -151,6 +159,7 @@ impl NamespacedOrchestrator for NamespacedProcessOrchestrator {
             memory_limit: _,
             cpu_limit: _,
             scale: scale_in,
+            disk,
             labels: _,
             availability_zone: _,
             anti_affinity: _,
@@ -278,6 +287,13 @@ impl NamespacedOrchestrator for NamespacedProcessOrchestrator {
                     "--secrets-reader-process-dir={}",
                     self.secrets_dir.display()
                 ));
+                if disk {
+                    let process_file_name = format!("{}-{}-{}", self.namespace, id, i);
+                    args.push(format!(
+                        "--scratch-directory={}",
+                        self.scratch_dir.join(process_file_name).display()
+                    ));
+                }
 
                 let command_wrapper = self.command_wrapper.clone();
                 handles.push(AbortOnDrop(Box::new(mz_ore::task::spawn(
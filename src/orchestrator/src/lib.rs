@@ -154,6 +154,12 @@ pub struct ServiceConfig<'a> {
     pub cpu_limit: Option<CpuLimit>,
     /// The number of copies of this service to run.
     pub scale: NonZeroUsize,
+    /// Whether to provide each process of the service with a scratch
+    /// directory on local disk.
+    ///
+    /// The orchestrator passes the location of the directory to the process
+    /// via the `--scratch-directory` flag.
+    pub disk: bool,
     /// Arbitrary key–value pairs to attach to the service in the orchestrator
     /// backend.
     ///
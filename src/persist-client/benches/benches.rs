@@ -25,6 +25,7 @@ use mz_persist::postgres::{PostgresConsensus, PostgresConsensusConfig};
 use mz_persist::s3::{S3Blob, S3BlobConfig};
 use mz_persist::workload::DataGenerator;
 use mz_persist_client::async_runtime::CpuHeavyRuntime;
+use mz_persist_client::rpc::NoopPubSubClient;
 use mz_persist_client::write::WriteHandle;
 use mz_persist_client::{Metrics, PersistClient, PersistConfig};
 use mz_persist_types::Codec64;
@@ -103,7 +104,14 @@ fn create_mem_mem_client() -> Result<PersistClient, ExternalError> {
     let consensus = Arc::new(MemConsensus::default());
     let metrics = Arc::new(Metrics::new(&cfg, &MetricsRegistry::new()));
     let cpu_heavy_runtime = Arc::new(CpuHeavyRuntime::new());
-    PersistClient::new(cfg, blob, consensus, metrics, cpu_heavy_runtime)
+    PersistClient::new(
+        cfg,
+        blob,
+        consensus,
+        metrics,
+        cpu_heavy_runtime,
+        Arc::new(NoopPubSubClient),
+    )
 }
 
 async fn create_file_pg_client(
@@ -121,7 +129,14 @@ async fn create_file_pg_client(
     let consensus = Arc::clone(&postgres_consensus) as Arc<dyn Consensus + Send + Sync>;
     let metrics = Arc::new(Metrics::new(&cfg, &MetricsRegistry::new()));
     let cpu_heavy_runtime = Arc::new(CpuHeavyRuntime::new());
-    let client = PersistClient::new(cfg, blob, consensus, metrics, cpu_heavy_runtime)?;
+    let client = PersistClient::new(
+        cfg,
+        blob,
+        consensus,
+        metrics,
+        cpu_heavy_runtime,
+        Arc::new(NoopPubSubClient),
+    )?;
     Ok(Some((postgres_consensus, client, dir)))
 }
 
@@ -142,7 +157,14 @@ async fn create_s3_pg_client(
     let consensus = Arc::clone(&postgres_consensus) as Arc<dyn Consensus + Send + Sync>;
     let metrics = Arc::new(Metrics::new(&cfg, &MetricsRegistry::new()));
     let cpu_heavy_runtime = Arc::new(CpuHeavyRuntime::new());
-    let client = PersistClient::new(cfg, blob, consensus, metrics, cpu_heavy_runtime)?;
+    let client = PersistClient::new(
+        cfg,
+        blob,
+        consensus,
+        metrics,
+        cpu_heavy_runtime,
+        Arc::new(NoopPubSubClient),
+    )?;
     Ok(Some((postgres_consensus, client)))
 }
 
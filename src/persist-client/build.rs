@@ -16,4 +16,14 @@ fn main() {
         .type_attribute(".", "#[derive(serde::Serialize)]")
         .compile_protos(&["persist-client/src/internal/state.proto"], &[".."])
         .unwrap();
+
+    tonic_build::configure()
+        // Enabling `emit_rerun_if_changed` will rerun the build script when
+        // anything in the include directory (..) changes. This causes quite a
+        // bit of spurious recompilation, so we disable it. The default behavior
+        // is to re-run if any file in the crate changes; that's still a bit too
+        // broad, but it's better.
+        .emit_rerun_if_changed(false)
+        .compile(&["persist-client/src/internal/service.proto"], &[".."])
+        .unwrap();
 }
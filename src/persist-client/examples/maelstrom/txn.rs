@@ -31,6 +31,7 @@ use mz_persist::location::{Blob, Consensus, ExternalError};
 use mz_persist::unreliable::{UnreliableBlob, UnreliableConsensus, UnreliableHandle};
 use mz_persist_client::async_runtime::CpuHeavyRuntime;
 use mz_persist_client::read::{Listen, ListenEvent};
+use mz_persist_client::rpc::NoopPubSubClient;
 use mz_persist_client::write::WriteHandle;
 use mz_persist_client::{Metrics, PersistClient, PersistConfig, ShardId};
 
@@ -551,7 +552,14 @@ impl Service for TransactorService {
 
         // Wire up the TransactorService.
         let cpu_heavy_runtime = Arc::new(CpuHeavyRuntime::new());
-        let client = PersistClient::new(config, blob, consensus, metrics, cpu_heavy_runtime)?;
+        let client = PersistClient::new(
+            config,
+            blob,
+            consensus,
+            metrics,
+            cpu_heavy_runtime,
+            Arc::new(NoopPubSubClient),
+        )?;
         let transactor = Transactor::new(&client, shard_id).await?;
         let service = TransactorService(Arc::new(Mutex::new(transactor)));
         Ok(service)
@@ -20,6 +20,7 @@ use mz_build_info::DUMMY_BUILD_INFO;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::SYSTEM_TIME;
 use mz_persist_client::cache::PersistClientCache;
+use mz_persist_client::rpc::NoopPubSubClient;
 use prometheus::Encoder;
 use tokio::sync::mpsc::error::SendError;
 use tokio::sync::Barrier;
@@ -130,6 +131,7 @@ pub async fn run(args: Args) -> Result<(), anyhow::Error> {
     let persist = PersistClientCache::new(
         PersistConfig::new(&DUMMY_BUILD_INFO, SYSTEM_TIME.clone()),
         &metrics_registry,
+        |_, _| Arc::new(NoopPubSubClient),
     )
     .open(location)
     .await?;
@@ -31,6 +31,7 @@ use futures_util::future::BoxFuture;
 use mz_build_info::DUMMY_BUILD_INFO;
 use mz_ore::metrics::MetricsRegistry;
 use mz_persist_client::async_runtime::CpuHeavyRuntime;
+use mz_persist_client::rpc::NoopPubSubClient;
 use tracing::{error, trace};
 
 use mz_ore::now::SYSTEM_TIME;
@@ -199,7 +200,14 @@ async fn persist_client(args: Args) -> Result<PersistClient, ExternalError> {
     let consensus = Arc::new(UnreliableConsensus::new(consensus, unreliable))
         as Arc<dyn Consensus + Send + Sync>;
     let cpu_heavy_runtime = Arc::new(CpuHeavyRuntime::new());
-    PersistClient::new(config, blob, consensus, metrics, cpu_heavy_runtime)
+    PersistClient::new(
+        config,
+        blob,
+        consensus,
+        metrics,
+        cpu_heavy_runtime,
+        Arc::new(NoopPubSubClient),
+    )
 }
 
 mod api {
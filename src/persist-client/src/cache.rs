@@ -15,12 +15,14 @@ use std::sync::Arc;
 
 use mz_ore::metrics::MetricsRegistry;
 use mz_persist::cfg::{BlobConfig, ConsensusConfig};
+use mz_persist::disk_cache::{DiskCacheBlob, DiskCacheBlobConfig};
 use mz_persist::location::{Blob, Consensus, ExternalError};
 use tracing::instrument;
 
 use crate::async_runtime::CpuHeavyRuntime;
 use crate::internal::machine::retry_external;
 use crate::internal::metrics::{Metrics, MetricsBlob, MetricsConsensus};
+use crate::rpc::PubSubClient;
 use crate::{PersistClient, PersistConfig, PersistLocation};
 
 /// A cache of [PersistClient]s indexed by [PersistLocation]s.
@@ -38,18 +40,27 @@ pub struct PersistClientCache {
     blob_by_uri: HashMap<String, Arc<dyn Blob + Send + Sync>>,
     consensus_by_uri: HashMap<String, Arc<dyn Consensus + Send + Sync>>,
     cpu_heavy_runtime: Arc<CpuHeavyRuntime>,
+    pubsub_client: Arc<dyn PubSubClient>,
 }
 
 impl PersistClientCache {
     /// Returns a new [PersistClientCache].
-    pub fn new(cfg: PersistConfig, registry: &MetricsRegistry) -> Self {
-        let metrics = Metrics::new(&cfg, registry);
+    ///
+    /// `pubsub_client_fn` is called once with the cache's config and metrics
+    /// to construct the [PubSubClient] shared by all of its [PersistClient]s.
+    pub fn new<F>(cfg: PersistConfig, registry: &MetricsRegistry, pubsub_client_fn: F) -> Self
+    where
+        F: FnOnce(&PersistConfig, Arc<Metrics>) -> Arc<dyn PubSubClient>,
+    {
+        let metrics = Arc::new(Metrics::new(&cfg, registry));
+        let pubsub_client = pubsub_client_fn(&cfg, Arc::clone(&metrics));
         PersistClientCache {
             cfg,
-            metrics: Arc::new(metrics),
+            metrics,
             blob_by_uri: HashMap::new(),
             consensus_by_uri: HashMap::new(),
             cpu_heavy_runtime: Arc::new(CpuHeavyRuntime::new()),
+            pubsub_client,
         }
     }
 
@@ -60,8 +71,14 @@ impl PersistClientCache {
         use mz_build_info::DUMMY_BUILD_INFO;
         use mz_ore::now::SYSTEM_TIME;
 
+        use crate::rpc::PersistPubSubServer;
+
         let cfg = PersistConfig::new(&DUMMY_BUILD_INFO, SYSTEM_TIME.clone());
-        Self::new(cfg, &MetricsRegistry::new())
+        // Route state diffs through an in-process server, so that tests
+        // exercise the pubsub path of listeners.
+        Self::new(cfg, &MetricsRegistry::new(), |_, metrics| {
+            PersistPubSubServer::new(metrics).new_same_process_connection()
+        })
     }
 
     /// Returns a new [PersistClient] for interfacing with persist shards made
@@ -100,6 +117,7 @@ impl PersistClientCache {
             consensus,
             Arc::clone(&self.metrics),
             Arc::clone(&self.cpu_heavy_runtime),
+            Arc::clone(&self.pubsub_client),
         )
     }
 
@@ -117,6 +135,19 @@ impl PersistClientCache {
                     blob.clone().open()
                 })
                 .await;
+                let blob = match &self.cfg.blob_disk_cache_dir {
+                    Some(base_dir) => {
+                        let config = DiskCacheBlobConfig {
+                            base_dir: base_dir.clone(),
+                            location: x.key().clone(),
+                            max_size_bytes: self.cfg.blob_disk_cache_max_size_bytes,
+                            metrics: self.metrics.blob_disk_cache.clone(),
+                        };
+                        Arc::new(DiskCacheBlob::open(config, blob).await?)
+                            as Arc<dyn Blob + Send + Sync>
+                    }
+                    None => blob,
+                };
                 Arc::clone(x.insert(blob))
             }
         };
@@ -130,6 +161,8 @@ mod tests {
     use mz_build_info::DUMMY_BUILD_INFO;
     use mz_ore::now::SYSTEM_TIME;
 
+    use crate::rpc::NoopPubSubClient;
+
     use super::*;
 
     #[tokio::test]
@@ -137,6 +170,7 @@ mod tests {
         let mut cache = PersistClientCache::new(
             PersistConfig::new(&DUMMY_BUILD_INFO, SYSTEM_TIME.clone()),
             &MetricsRegistry::new(),
+            |_, _| Arc::new(NoopPubSubClient),
         );
         assert_eq!(cache.blob_by_uri.len(), 0);
         assert_eq!(cache.consensus_by_uri.len(), 0);
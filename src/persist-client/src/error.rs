@@ -105,6 +105,20 @@ pub enum InvalidUsage<T> {
     CodecMismatch(Box<CodecMismatch>),
     /// An unregistered or expired [crate::write::WriterId] was used by [crate::write::WriteHandle]
     UnknownWriter(WriterId),
+    /// A shard was forked into a shard that already contains data.
+    ForkIntoNonEmptyShard {
+        /// The shard that was to become the fork
+        fork_shard: ShardId,
+        /// The upper of the fork shard
+        upper: Antichain<T>,
+    },
+    /// A shard was forked at a since that is not beyond its own since.
+    ForkSinceNotBeyondSince {
+        /// The since given to the fork call
+        fork_since: Antichain<T>,
+        /// The since of the shard being forked
+        shard_since: Antichain<T>,
+    },
 }
 
 impl<T: Debug> std::fmt::Display for InvalidUsage<T> {
@@ -151,6 +165,19 @@ impl<T: Debug> std::fmt::Display for InvalidUsage<T> {
             InvalidUsage::UnknownWriter(writer_id) => {
                 write!(f, "writer id {} is not registered", writer_id)
             }
+            InvalidUsage::ForkIntoNonEmptyShard { fork_shard, upper } => write!(
+                f,
+                "cannot fork into {} which already has upper {:?}",
+                fork_shard, upper
+            ),
+            InvalidUsage::ForkSinceNotBeyondSince {
+                fork_since,
+                shard_since,
+            } => write!(
+                f,
+                "fork since {:?} is not beyond the since {:?} of the forked shard",
+                fork_since, shard_since
+            ),
         }
     }
 }
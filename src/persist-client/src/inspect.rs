@@ -29,6 +29,7 @@ use crate::internal::paths::{
     BlobKey, BlobKeyPrefix, PartialBatchKey, PartialBlobKey, PartialRollupKey,
 };
 use crate::internal::state::{ProtoStateDiff, ProtoStateRollup};
+use crate::rpc::NoopPubSubClient;
 use crate::{Metrics, PersistConfig, ShardId, StateVersions};
 
 const READ_ALL_BUILD_INFO: BuildInfo = BuildInfo {
@@ -51,7 +52,13 @@ pub async fn fetch_latest_state(
     let blob = BlobConfig::try_from(blob_uri).await?;
     let blob = blob.clone().open().await?;
 
-    let state_versions = StateVersions::new(cfg, consensus, blob, Arc::clone(&metrics));
+    let state_versions = StateVersions::new(
+        cfg,
+        consensus,
+        blob,
+        Arc::clone(&metrics),
+        Arc::new(NoopPubSubClient),
+    );
     let versions = state_versions.fetch_live_diffs(&shard_id).await;
 
     let state = match state_versions
@@ -120,8 +127,13 @@ pub async fn fetch_state_rollups(
 
     let mut rollup_keys = HashSet::new();
 
-    let state_versions =
-        StateVersions::new(cfg, consensus, Arc::clone(&blob), Arc::clone(&metrics));
+    let state_versions = StateVersions::new(
+        cfg,
+        consensus,
+        Arc::clone(&blob),
+        Arc::clone(&metrics),
+        Arc::new(NoopPubSubClient),
+    );
     let mut state_iter = match state_versions
         .fetch_live_states::<K, V, u64, D>(&shard_id)
         .await
@@ -175,7 +187,13 @@ pub async fn fetch_state_diffs(
     let blob = BlobConfig::try_from(blob_uri).await?;
     let blob = blob.clone().open().await?;
 
-    let state_versions = StateVersions::new(cfg, consensus, blob, Arc::clone(&metrics));
+    let state_versions = StateVersions::new(
+        cfg,
+        consensus,
+        blob,
+        Arc::clone(&metrics),
+        Arc::new(NoopPubSubClient),
+    );
 
     let mut live_states = vec![];
     let mut state_iter = match state_versions
@@ -228,6 +246,7 @@ pub async fn blob_counts(blob_uri: &str) -> Result<impl serde::Serialize, anyhow
                     blob_count.rollup_count += 1;
                     blob_count.rollup_bytes += usize::cast_from(metadata.size_in_bytes);
                 }
+                Ok((_, PartialBlobKey::ForkRefs(_))) => {}
                 Err(err) => {
                     eprintln!("error parsing blob: {}", err);
                 }
@@ -270,12 +289,18 @@ pub async fn unreferenced_blobs(
                 Ok((_, PartialBlobKey::Rollup(seqno, rollup))) => {
                     all_rollups.push(PartialRollupKey::new(seqno, &rollup));
                 }
-                Err(_) => {}
+                Ok((_, PartialBlobKey::ForkRefs(_))) | Err(_) => {}
             },
         )
         .await?;
 
-    let state_versions = StateVersions::new(cfg, consensus, blob, Arc::clone(&metrics));
+    let state_versions = StateVersions::new(
+        cfg,
+        consensus,
+        blob,
+        Arc::clone(&metrics),
+        Arc::new(NoopPubSubClient),
+    );
     let mut state_iter = match state_versions
         .fetch_live_states::<K, V, u64, D>(shard_id)
         .await
@@ -309,6 +334,9 @@ pub async fn unreferenced_blobs(
         }
     }
 
+    // Parts still referenced by a fork of this shard are not unreferenced.
+    known_parts.extend(state_versions.fetch_fork_refs(shard_id).await);
+
     let mut unreferenced_blobs = UnreferencedBlobs::default();
     for (part, writer) in all_parts {
         if !known_writers.contains(&writer) && !known_parts.contains(&part) {
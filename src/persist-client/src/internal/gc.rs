@@ -7,7 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::time::Instant;
@@ -23,7 +23,8 @@ use tokio::sync::{mpsc, oneshot};
 use tracing::{debug, debug_span, warn, Instrument, Span};
 
 use crate::internal::machine::{retry_external, Machine};
-use crate::internal::paths::{PartialRollupKey, RollupId};
+use crate::internal::paths::{PartialBatchKey, PartialRollupKey, RollupId};
+use crate::internal::state::State;
 use crate::ShardId;
 
 #[derive(Debug, Clone)]
@@ -86,6 +87,17 @@ impl<K, V, T, D> Clone for GarbageCollector<K, V, T, D> {
 ///   results in the HashSet containing every blob eligible for deletion. It
 ///   deletes those blobs and then truncates the state to the new_seqno_since to
 ///   indicate that this work doesn't need to be done again.
+/// - Blobs that don't belong to this shard (foreign keys inherited from a shard
+///   this one was forked from) or that are still referenced by a fork of this
+///   shard (as recorded in its fork refs blobs) are never deleted. The fork
+///   refs are only fetched after scanning state: a fork registers its refs
+///   while holding a seqno capability on this shard, so any refs that could
+///   protect the blobs being deleted are guaranteed to already be visible.
+/// - Instead, when a fork retires foreign blobs, it shrinks its fork refs to
+///   the foreign blobs it still references, or deletes them once its since is
+///   empty. It then deletes the released blobs that the shard that wrote them
+///   no longer references either, and that shard's GC rechecks the blobs it
+///   skipped after truncating, so that one of the two always deletes them.
 /// - Note that these requests are being processed concurrently, so it's always
 ///   possible that some future request has already deleted the blobs and
 ///   truncated consensus. It's also possible that this is the future request.
@@ -320,6 +332,25 @@ where
             req.shard_id, rollup_seqno, applied
         );
 
+        // Parts with foreign keys belong to the shard this one was forked
+        // from, so they're not ours to delete. Instead, stop protecting the
+        // ones we no longer reference from garbage collection of their owner.
+        let (retired_foreign_blobs, deleteable_batch_blobs): (HashSet<_>, HashSet<_>) =
+            deleteable_batch_blobs
+                .into_iter()
+                .partition(|x| x.is_foreign());
+        Self::release_fork_refs(machine, &state, retired_foreign_blobs).await;
+
+        // Parts referenced by a fork of this shard are still in use, so they
+        // may not be deleted here. Once the fork releases them, whichever of
+        // this GC and the fork's observes the release deletes them.
+        let fork_refs = if !deleteable_batch_blobs.is_empty() {
+            machine.state_versions.fetch_fork_refs(&req.shard_id).await
+        } else {
+            HashSet::new()
+        };
+        let mut forked_batch_blobs = Vec::new();
+
         // There's also a bulk delete API in s3 if the performance of this
         // becomes an issue. Maybe make Blob::delete take a list of keys?
         //
@@ -329,15 +360,11 @@ where
         // concurrently, but this requires a bunch of Arc cloning, so wait to
         // see if it's worth it.
         for key in deleteable_batch_blobs {
-            retry_external(&machine.metrics.retries.external.batch_delete, || async {
-                machine
-                    .state_versions
-                    .blob
-                    .delete(&key.complete(&req.shard_id))
-                    .await
-            })
-            .instrument(debug_span!("batch::delete"))
-            .await;
+            if fork_refs.contains(&key) {
+                forked_batch_blobs.push(key);
+                continue;
+            }
+            Self::delete_batch_blob(machine, &req.shard_id, &key).await;
         }
         debug!("gc {} deleted batch blobs", req.shard_id);
 
@@ -351,5 +378,122 @@ where
             "gc {} truncated diffs through seqno {}",
             req.shard_id, req.new_seqno_since
         );
+
+        // A fork may have released some of the parts it protected after we
+        // fetched its refs, but before we truncated. It then saw them in our
+        // live states and left them for us, so check again.
+        if !forked_batch_blobs.is_empty() {
+            let fork_refs = machine.state_versions.fetch_fork_refs(&req.shard_id).await;
+            for key in forked_batch_blobs {
+                if !fork_refs.contains(&key) {
+                    Self::delete_batch_blob(machine, &req.shard_id, &key).await;
+                }
+            }
+        }
+    }
+
+    async fn delete_batch_blob(
+        machine: &Machine<K, V, T, D>,
+        shard_id: &ShardId,
+        key: &PartialBatchKey,
+    ) {
+        retry_external(&machine.metrics.retries.external.batch_delete, || async {
+            machine
+                .state_versions
+                .blob
+                .delete(&key.complete(shard_id))
+                .await
+        })
+        .instrument(debug_span!("batch::delete"))
+        .await;
+    }
+
+    /// Updates the fork refs that this shard, as a fork of other shards,
+    /// holds on the parts of those shards.
+    ///
+    /// `state` is the state at the new seqno since, and `retired` are the
+    /// foreign parts that are no longer referenced by it or any later state.
+    /// The refs are shrunk to the foreign parts still referenced by `state`,
+    /// or deleted once this shard's since is empty and its parts can never be
+    /// read again. A released part is then deleted if its owner doesn't
+    /// reference it either.
+    ///
+    /// A concurrent GC of this shard may write refs computed from an earlier
+    /// state, but those are a superset of ours, so parts are only ever
+    /// retained for longer than necessary, never deleted early.
+    async fn release_fork_refs(
+        machine: &Machine<K, V, T, D>,
+        state: &State<K, V, T, D>,
+        retired: HashSet<PartialBatchKey>,
+    ) {
+        let fork_id = state.shard_id;
+        let finalized = state.collections.trace.since().is_empty();
+        if retired.is_empty() && !finalized {
+            return;
+        }
+
+        let mut live = BTreeMap::<_, BTreeSet<_>>::new();
+        state.collections.trace.map_batches(|b| {
+            for part in b.parts.iter().filter(|x| x.key.is_foreign()) {
+                let (owner, key) = part.key.owner(&fork_id);
+                live.entry(owner).or_default().insert(key);
+            }
+        });
+        let mut released = BTreeMap::<_, Vec<_>>::new();
+        for key in retired {
+            let (owner, key) = key.owner(&fork_id);
+            released.entry(owner).or_default().push(key);
+        }
+        let retired_owners: BTreeSet<_> = released.keys().copied().collect();
+        if finalized {
+            for (owner, keys) in std::mem::take(&mut live) {
+                released.entry(owner).or_default().extend(keys);
+            }
+        }
+
+        for (owner, keys) in released {
+            match live.get(&owner) {
+                Some(live) => {
+                    machine
+                        .state_versions
+                        .write_fork_refs(&owner, &fork_id, live)
+                        .await
+                }
+                None => {
+                    let existed = machine
+                        .state_versions
+                        .delete_fork_refs(&owner, &fork_id)
+                        .await;
+                    // Every GC of a finalized shard ends up here, but only
+                    // the first needs to release anything.
+                    if !existed && !retired_owners.contains(&owner) {
+                        continue;
+                    }
+                }
+            }
+
+            // The owner's GC skipped any of these parts that it retired while
+            // we protected them. Only delete the ones that neither the owner
+            // nor any other fork of it still references. Reading the owner's
+            // live states after releasing our refs guarantees that either we
+            // or the owner's GC see the release.
+            let mut referenced = machine.state_versions.fetch_fork_refs(&owner).await;
+            let mut states = machine
+                .state_versions
+                .fetch_live_states::<K, V, T, D>(&owner)
+                .await
+                .expect("shard codecs should not change");
+            while let Some(state) = states.next() {
+                state.collections.trace.map_batches(|b| {
+                    referenced.extend(b.parts.iter().map(|x| x.key.clone()));
+                });
+            }
+            for key in keys {
+                if !referenced.contains(&key) {
+                    Self::delete_batch_blob(machine, &owner, &key).await;
+                }
+            }
+        }
+        debug!("gc {} released fork refs", fork_id);
     }
 }
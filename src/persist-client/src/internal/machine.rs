@@ -25,7 +25,7 @@ use tracing::{debug, info, warn};
 
 #[allow(unused_imports)] // False positive.
 use mz_ore::fmt::FormatBuffer;
-use mz_persist::location::{ExternalError, Indeterminate, SeqNo};
+use mz_persist::location::{ExternalError, Indeterminate, SeqNo, VersionedData};
 use mz_persist::retry::Retry;
 use mz_persist_types::{Codec, Codec64};
 
@@ -45,6 +45,7 @@ use crate::internal::state_diff::StateDiff;
 use crate::internal::state_versions::StateVersions;
 use crate::internal::trace::{ApplyMergeResult, FueledMergeRes};
 use crate::read::LeasedReaderId;
+use crate::rpc::ShardSubscription;
 use crate::write::WriterId;
 use crate::{PersistConfig, ShardId};
 
@@ -230,6 +231,68 @@ where
         read_cap
     }
 
+    /// Returns every batch in this shard's trace, with each part rewritten to
+    /// a foreign key so that the batches can be referenced from the state of
+    /// another shard.
+    pub fn foreign_batches(&self) -> Vec<HollowBatch<T>> {
+        let shard_id = self.shard_id();
+        self.state
+            .collections
+            .trace
+            .batches()
+            .into_iter()
+            .map(|batch| {
+                let mut batch = batch.clone();
+                for part in batch.parts.iter_mut() {
+                    part.key = part.key.foreign(&shard_id);
+                }
+                batch
+            })
+            .collect()
+    }
+
+    pub async fn fork_from(
+        &mut self,
+        batches: &[HollowBatch<T>],
+        since: &Antichain<T>,
+    ) -> Result<SeqNo, InvalidUsage<T>> {
+        let metrics = Arc::clone(&self.metrics);
+        let shard_id = self.shard_id();
+        let mut retry = self
+            .metrics
+            .retries
+            .idempotent_cmd
+            .stream(Retry::persist_defaults(SystemTime::now()).into_retry_stream());
+        loop {
+            // NB: State::fork_from is idempotent, so it's safe to retry after
+            // indeterminate errors.
+            match self
+                .apply_unbatched_cmd(&metrics.cmds.fork, |_, state| {
+                    state.fork_from(&shard_id, batches, since)
+                })
+                .await
+            {
+                Ok((seqno, res, _maintenance)) => return res.map(|()| seqno),
+                Err(err) => {
+                    if retry.attempt() >= INFO_MIN_ATTEMPTS {
+                        info!(
+                            "fork_from received an indeterminate error, retrying in {:?}: {}",
+                            retry.next_sleep(),
+                            err
+                        );
+                    } else {
+                        debug!(
+                            "fork_from received an indeterminate error, retrying in {:?}: {}",
+                            retry.next_sleep(),
+                            err
+                        );
+                    }
+                    retry = retry.sleep().await;
+                }
+            }
+        }
+    }
+
     pub async fn compare_and_append(
         &mut self,
         batch: &HollowBatch<T>,
@@ -803,6 +866,36 @@ where
             self.state.seqno
         );
     }
+
+    /// Returns a subscription to state diffs pushed for this shard by other
+    /// processes.
+    pub fn subscribe_to_state_diffs(&self) -> ShardSubscription {
+        self.state_versions
+            .pubsub_client
+            .subscribe(&self.state.shard_id)
+    }
+
+    /// Applies a state diff received over pubsub, if it immediately follows
+    /// our current state.
+    ///
+    /// Returns false if the diff could not be applied (we've missed some
+    /// intermediate diff), in which case the caller should fall back to
+    /// fetching state from consensus.
+    pub fn apply_pushed_diff(&mut self, diff: VersionedData) -> bool {
+        self.metrics.pubsub.push_received.inc();
+        if diff.seqno <= self.state.seqno {
+            // We've already seen this one, nothing to do.
+            return true;
+        }
+        if diff.seqno != self.state.seqno.next() {
+            self.metrics.pubsub.push_skipped.inc();
+            return false;
+        }
+        self.state
+            .apply_encoded_diffs(&self.cfg, &self.metrics, std::iter::once(&diff));
+        self.metrics.pubsub.push_applied.inc();
+        true
+    }
 }
 
 pub const INFO_MIN_ATTEMPTS: usize = 3;
@@ -937,6 +1030,7 @@ pub mod datadriven {
                 Arc::clone(&client.consensus),
                 Arc::clone(&client.blob),
                 Arc::clone(&client.metrics),
+                Arc::clone(&client.pubsub_client),
             ));
             let machine = Machine::new(
                 client.cfg.clone(),
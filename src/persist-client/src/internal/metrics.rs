@@ -24,7 +24,7 @@ use mz_ore::metrics::{
 use mz_persist::location::{
     Atomicity, Blob, BlobMetadata, Consensus, ExternalError, SeqNo, VersionedData,
 };
-use mz_persist::metrics::PostgresConsensusMetrics;
+use mz_persist::metrics::{DiskCacheBlobMetrics, PostgresConsensusMetrics};
 use mz_persist::retry::RetryStream;
 use mz_persist_types::Codec64;
 use prometheus::core::{AtomicI64, AtomicU64};
@@ -69,9 +69,13 @@ pub struct Metrics {
     pub shards: ShardsMetrics,
     /// Metrics for auditing persist usage
     pub audit: UsageAuditMetrics,
+    /// Metrics for the pub/sub channel of state diffs.
+    pub pubsub: PubSubMetrics,
 
     /// Metrics for Postgres-backed consensus implementation
     pub postgres_consensus: PostgresConsensusMetrics,
+    /// Metrics for the local disk cache of blob reads
+    pub blob_disk_cache: DiskCacheBlobMetrics,
 }
 
 impl Metrics {
@@ -104,7 +108,9 @@ impl Metrics {
             state: StateMetrics::new(registry),
             shards: ShardsMetrics::new(registry),
             audit: UsageAuditMetrics::new(registry),
+            pubsub: PubSubMetrics::new(registry),
             postgres_consensus: PostgresConsensusMetrics::new(registry),
+            blob_disk_cache: DiskCacheBlobMetrics::new(registry),
             _vecs: vecs,
             _uptime: uptime,
         }
@@ -314,6 +320,7 @@ impl MetricsVecs {
             heartbeat_writer: self.cmd_metrics("heartbeat_writer"),
             expire_reader: self.cmd_metrics("expire_reader"),
             expire_writer: self.cmd_metrics("expire_writer"),
+            fork: self.cmd_metrics("fork"),
             merge_res: self.cmd_metrics("merge_res"),
         }
     }
@@ -342,6 +349,9 @@ impl MetricsVecs {
                 consensus_open: self.retry_metrics("consensus::open"),
                 fetch_batch_get: self.retry_metrics("fetch_batch::get"),
                 fetch_state_scan: self.retry_metrics("fetch_state::scan"),
+                fork_refs_delete: self.retry_metrics("fork_refs::delete"),
+                fork_refs_get: self.retry_metrics("fork_refs::get"),
+                fork_refs_set: self.retry_metrics("fork_refs::set"),
                 gc_truncate: self.retry_metrics("gc::truncate"),
                 maybe_init_cas: self.retry_metrics("maybe_init::cas"),
                 rollup_delete: self.retry_metrics("rollup::delete"),
@@ -487,6 +497,7 @@ pub struct CmdsMetrics {
     pub(crate) heartbeat_reader: CmdMetrics,
     pub(crate) expire_reader: CmdMetrics,
     pub(crate) expire_writer: CmdMetrics,
+    pub(crate) fork: CmdMetrics,
     pub(crate) merge_res: CmdMetrics,
 }
 
@@ -519,6 +530,9 @@ pub struct RetryExternal {
     pub(crate) consensus_open: RetryMetrics,
     pub(crate) fetch_batch_get: RetryMetrics,
     pub(crate) fetch_state_scan: RetryMetrics,
+    pub(crate) fork_refs_delete: RetryMetrics,
+    pub(crate) fork_refs_get: RetryMetrics,
+    pub(crate) fork_refs_set: RetryMetrics,
     pub(crate) gc_truncate: RetryMetrics,
     pub(crate) maybe_init_cas: RetryMetrics,
     pub(crate) rollup_delete: RetryMetrics,
@@ -934,6 +948,47 @@ impl StateMetrics {
     }
 }
 
+#[derive(Debug)]
+pub struct PubSubMetrics {
+    pub(crate) push_sent: IntCounter,
+    pub(crate) push_received: IntCounter,
+    pub(crate) push_applied: IntCounter,
+    pub(crate) push_skipped: IntCounter,
+    pub(crate) connected: UIntGauge,
+    pub(crate) reconnects: IntCounter,
+}
+
+impl PubSubMetrics {
+    pub(crate) fn new(registry: &MetricsRegistry) -> Self {
+        PubSubMetrics {
+            push_sent: registry.register(metric!(
+                name: "mz_persist_pubsub_push_sent",
+                help: "count of state diffs published after a successful compare_and_set",
+            )),
+            push_received: registry.register(metric!(
+                name: "mz_persist_pubsub_push_received",
+                help: "count of state diffs received by listeners over pubsub",
+            )),
+            push_applied: registry.register(metric!(
+                name: "mz_persist_pubsub_push_applied",
+                help: "count of received state diffs that were applied directly to state",
+            )),
+            push_skipped: registry.register(metric!(
+                name: "mz_persist_pubsub_push_skipped",
+                help: "count of received state diffs that did not apply and required a state fetch",
+            )),
+            connected: registry.register(metric!(
+                name: "mz_persist_pubsub_connected",
+                help: "1 if the pubsub client is connected to the pubsub server, 0 otherwise",
+            )),
+            reconnects: registry.register(metric!(
+                name: "mz_persist_pubsub_reconnects",
+                help: "count of connection attempts made by the pubsub client",
+            )),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ShardsMetrics {
     // Unlike all the other metrics in here, ShardsMetrics intentionally uses
@@ -54,6 +54,11 @@ impl PartId {
 /// Used to reduce the bytes needed to refer to a blob key in memory and in
 /// persistent state, all access to blobs are always within the context of an
 /// individual shard.
+///
+/// The one exception is a shard created by forking another shard (see
+/// [crate::PersistClient::fork_shard]): its state refers to the parts it
+/// inherited using keys that already include the [ShardId] of the shard that
+/// wrote them. Such keys are "foreign" and [Self::complete] leaves them as-is.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct PartialBatchKey(pub(crate) String);
 
@@ -63,7 +68,40 @@ impl PartialBatchKey {
     }
 
     pub fn complete(&self, shard_id: &ShardId) -> BlobKey {
-        BlobKey(format!("{}/{}", shard_id, self))
+        if self.is_foreign() {
+            BlobKey(self.0.clone())
+        } else {
+            BlobKey(format!("{}/{}", shard_id, self))
+        }
+    }
+
+    /// Returns a key that refers to this part of `shard_id` from the state of
+    /// some other shard.
+    pub fn foreign(&self, shard_id: &ShardId) -> Self {
+        PartialBatchKey(self.complete(shard_id).0)
+    }
+
+    /// Returns whether this key refers to a part written by a shard other than
+    /// the one whose state contains it.
+    pub fn is_foreign(&self) -> bool {
+        // Writer ids start with 'w', shard ids with 's'.
+        self.0.starts_with('s')
+    }
+
+    /// Returns the shard that wrote the part referred to by this key, along
+    /// with the key relative to that shard.
+    ///
+    /// `shard_id` is the shard whose state contains this key.
+    pub fn owner(&self, shard_id: &ShardId) -> (ShardId, PartialBatchKey) {
+        if !self.is_foreign() {
+            return (*shard_id, self.clone());
+        }
+        let (shard, key) = self
+            .0
+            .split_once('/')
+            .expect("foreign batch key should contain a shard id");
+        let shard = ShardId::from_str(shard).expect("foreign batch key should contain a shard id");
+        (shard, PartialBatchKey(key.to_owned()))
     }
 }
 
@@ -154,6 +192,9 @@ pub enum PartialBlobKey {
     Batch(WriterId, PartId),
     /// A parsed [PartialRollupKey].
     Rollup(SeqNo, RollupId),
+    /// The set of this shard's parts that are referenced by the state of the
+    /// given fork of this shard. See [BlobKey::fork_refs].
+    ForkRefs(ShardId),
 }
 
 /// Fully encoded path used in [mz_persist::location::Blob] storage. Composed of
@@ -180,6 +221,15 @@ impl Deref for BlobKey {
 }
 
 impl BlobKey {
+    /// The key of the blob recording which parts of `shard_id` are referenced
+    /// by the state of `fork_id`.
+    ///
+    /// While this blob exists, garbage collection of `shard_id` will not delete
+    /// any of the parts listed in it.
+    pub fn fork_refs(shard_id: &ShardId, fork_id: &ShardId) -> BlobKey {
+        BlobKey(format!("{}/{}", shard_id, fork_id))
+    }
+
     pub fn parse_ids(key: &str) -> Result<(ShardId, PartialBlobKey), String> {
         let ids = key.split('/').collect::<Vec<_>>();
 
@@ -190,7 +240,10 @@ impl BlobKey {
             [shard, seqno, rollup] if seqno.starts_with('v') => Ok(
                 (ShardId::from_str(shard)?, PartialBlobKey::Rollup(SeqNo::from_str(seqno)?, RollupId::from_str(rollup)?))
             ),
-            _ => Err(format!("invalid blob key format. expected either <shard_id>/<writer_id>/<part_id> or <shard_id>/<seqno>/<rollup_id>. got: {}", key)),
+            [shard, fork] if fork.starts_with('s') => Ok(
+                (ShardId::from_str(shard)?, PartialBlobKey::ForkRefs(ShardId::from_str(fork)?))
+            ),
+            _ => Err(format!("invalid blob key format. expected one of <shard_id>/<writer_id>/<part_id>, <shard_id>/<seqno>/<rollup_id> or <shard_id>/<fork_shard_id>. got: {}", key)),
         }
     }
 }
@@ -202,6 +255,8 @@ pub enum BlobKeyPrefix<'a> {
     All,
     /// Scoped to the batch and state rollup blobs of an individual shard
     Shard(&'a ShardId),
+    /// Scoped to the fork reference blobs of an individual shard
+    ForkRefs(&'a ShardId),
     /// Scoped to the batch blobs of an individual writer
     #[cfg(test)]
     Writer(&'a ShardId, &'a WriterId),
@@ -215,6 +270,7 @@ impl std::fmt::Display for BlobKeyPrefix<'_> {
         let s = match self {
             BlobKeyPrefix::All => "".into(),
             BlobKeyPrefix::Shard(shard) => format!("{}", shard),
+            BlobKeyPrefix::ForkRefs(shard) => format!("{}/s", shard),
             #[cfg(test)]
             BlobKeyPrefix::Writer(shard, writer) => format!("{}/{}", shard, writer),
             #[cfg(test)]
@@ -238,6 +294,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn foreign_blob_key_completion() {
+        let (shard_id, fork_id) = (ShardId::new(), ShardId::new());
+        let partial_key = PartialBatchKey::new(&WriterId::new(), &PartId::new());
+        assert!(!partial_key.is_foreign());
+        assert_eq!(
+            partial_key.owner(&shard_id),
+            (shard_id, partial_key.clone())
+        );
+
+        let foreign_key = partial_key.foreign(&shard_id);
+        assert!(foreign_key.is_foreign());
+        assert_eq!(foreign_key.owner(&fork_id), (shard_id, partial_key.clone()));
+        // A foreign key completes to the blob of the shard that wrote it, no
+        // matter which shard it's completed with.
+        assert_eq!(
+            foreign_key.complete(&fork_id),
+            partial_key.complete(&shard_id)
+        );
+        assert_eq!(foreign_key.foreign(&fork_id), foreign_key);
+    }
+
     #[test]
     fn blob_key_parse() -> Result<(), String> {
         let (shard_id, writer_id, part_id) = (ShardId::new(), WriterId::new(), PartId::new());
@@ -248,6 +326,13 @@ mod tests {
             Ok((shard_id, PartialBlobKey::Batch(writer_id, part_id)))
         );
 
+        // can parse fork refs key
+        let fork_id = ShardId::new();
+        assert_eq!(
+            BlobKey::parse_ids(&BlobKey::fork_refs(&shard_id, &fork_id)),
+            Ok((shard_id, PartialBlobKey::ForkRefs(fork_id)))
+        );
+
         // fails on invalid blob key formats
         assert!(matches!(
             BlobKey::parse_ids(&format!("{}/{}", WriterId::new(), PartId::new())),
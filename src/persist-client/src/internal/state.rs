@@ -316,6 +316,59 @@ where
         Continue(merge_reqs)
     }
 
+    /// Initializes this (empty) shard as a fork of another shard, which is
+    /// described by its `batches` and a `since` at which the fork is readable.
+    ///
+    /// The parts of `batches` are expected to have foreign keys, so that they
+    /// are not deleted by garbage collection of this shard.
+    pub fn fork_from(
+        &mut self,
+        shard_id: &ShardId,
+        batches: &[HollowBatch<T>],
+        since: &Antichain<T>,
+    ) -> ControlFlow<InvalidUsage<T>, ()> {
+        debug_assert!(batches
+            .iter()
+            .all(|b| b.parts.iter().all(|x| x.key.is_foreign())));
+
+        if self.trace.upper() != &Antichain::from_elem(T::minimum()) {
+            // Commands are retried after indeterminate errors, so this fork may
+            // have already been applied by an earlier attempt.
+            let mut trace_batches = self.trace.batches().into_iter();
+            let already_applied =
+                !batches.is_empty() && batches.iter().all(|b| trace_batches.next() == Some(b));
+            if already_applied {
+                return Continue(());
+            }
+            return Break(InvalidUsage::ForkIntoNonEmptyShard {
+                fork_shard: *shard_id,
+                upper: self.trace.upper().clone(),
+            });
+        }
+
+        for batch in batches {
+            // These batches were already arranged into a spine by the forked
+            // shard. Any compaction they need will be requested again by
+            // subsequent writes to this shard, so ignore the merge reqs.
+            let _merge_reqs = self.trace.push_batch(batch.clone());
+        }
+
+        // Readers that registered before the fork (e.g. to create the shard)
+        // could only ever have read it as empty. Advance them to the since of
+        // the fork, as if they had registered after it.
+        for reader in self.leased_readers.values_mut() {
+            reader.since.join_assign(since);
+        }
+        for reader in self.critical_readers.values_mut() {
+            reader.since.join_assign(since);
+        }
+        let mut trace_since = self.trace.since().clone();
+        trace_since.join_assign(since);
+        self.trace.downgrade_since(&trace_since);
+
+        Continue(())
+    }
+
     pub fn apply_merge_res(
         &mut self,
         res: &FueledMergeRes<T>,
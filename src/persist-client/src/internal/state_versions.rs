@@ -9,6 +9,7 @@
 
 //! A durable, truncatable log of versions of [State].
 
+use std::collections::{BTreeSet, HashSet};
 use std::fmt::Debug;
 use std::ops::ControlFlow::{Break, Continue};
 use std::sync::Arc;
@@ -17,18 +18,22 @@ use std::time::SystemTime;
 use bytes::Bytes;
 use differential_dataflow::difference::Semigroup;
 use differential_dataflow::lattice::Lattice;
-use mz_persist::location::{Atomicity, Blob, Consensus, Indeterminate, SeqNo, VersionedData};
+use mz_persist::location::{
+    Atomicity, Blob, Consensus, ExternalError, Indeterminate, SeqNo, VersionedData,
+};
 use mz_persist::retry::Retry;
 use mz_persist_types::{Codec, Codec64};
+use prost::Message;
 use timely::progress::Timestamp;
 use tracing::{debug, debug_span, trace, warn, Instrument};
 
 use crate::error::CodecMismatch;
 use crate::internal::machine::{retry_determinate, retry_external};
 use crate::internal::metrics::ShardMetrics;
-use crate::internal::paths::{PartialRollupKey, RollupId};
-use crate::internal::state::State;
+use crate::internal::paths::{BlobKey, BlobKeyPrefix, PartialBatchKey, PartialRollupKey, RollupId};
+use crate::internal::state::{ProtoForkRefs, State};
 use crate::internal::state_diff::{StateDiff, StateFieldValDiff};
+use crate::rpc::PubSubClient;
 use crate::{Metrics, PersistConfig, ShardId};
 
 /// A durable, truncatable log of versions of [State].
@@ -87,6 +92,7 @@ pub struct StateVersions {
     pub(crate) consensus: Arc<dyn Consensus + Send + Sync>,
     pub(crate) blob: Arc<dyn Blob + Send + Sync>,
     metrics: Arc<Metrics>,
+    pub(crate) pubsub_client: Arc<dyn PubSubClient>,
 }
 
 impl StateVersions {
@@ -95,12 +101,14 @@ impl StateVersions {
         consensus: Arc<dyn Consensus + Send + Sync>,
         blob: Arc<dyn Blob + Send + Sync>,
         metrics: Arc<Metrics>,
+        pubsub_client: Arc<dyn PubSubClient>,
     ) -> Self {
         StateVersions {
             cfg,
             consensus,
             blob,
             metrics,
+            pubsub_client,
         }
     }
 
@@ -235,6 +243,9 @@ impl StateVersions {
                 shard_metrics.set_encoded_batch_size(new_state.encoded_batch_size());
                 shard_metrics.set_seqnos_held(new_state.seqnos_held());
                 shard_metrics.inc_encoded_diff_size(payload_len);
+                // Let any listeners know about the new state without waiting
+                // for them to poll consensus.
+                self.pubsub_client.push(&new_state.shard_id, &new);
                 Ok(Ok(()))
             }
             Err(live_diffs) => {
@@ -632,6 +643,81 @@ impl StateVersions {
         .await
         .instrument(debug_span!("rollup::delete"));
     }
+
+    /// Records that the state of `fork_id` references the given parts of
+    /// `shard_id`, overwriting any previous record for `fork_id`.
+    ///
+    /// While this record exists, garbage collection of `shard_id` will not
+    /// delete any of these parts.
+    pub async fn write_fork_refs(
+        &self,
+        shard_id: &ShardId,
+        fork_id: &ShardId,
+        parts: &BTreeSet<PartialBatchKey>,
+    ) {
+        let proto = ProtoForkRefs {
+            part_keys: parts.iter().map(|x| x.0.clone()).collect(),
+        };
+        let buf = Bytes::from(proto.encode_to_vec());
+        let key = BlobKey::fork_refs(shard_id, fork_id);
+        retry_external(&self.metrics.retries.external.fork_refs_set, || async {
+            self.blob
+                .set(&key, Bytes::clone(&buf), Atomicity::RequireAtomic)
+                .await
+        })
+        .instrument(debug_span!("fork_refs::set"))
+        .await;
+    }
+
+    /// Removes the record of the parts of `shard_id` referenced by the state
+    /// of `fork_id`, once the fork no longer references any of them.
+    ///
+    /// Returns whether the record existed.
+    pub async fn delete_fork_refs(&self, shard_id: &ShardId, fork_id: &ShardId) -> bool {
+        let key = BlobKey::fork_refs(shard_id, fork_id);
+        retry_external(&self.metrics.retries.external.fork_refs_delete, || async {
+            self.blob.delete(&key).await
+        })
+        .instrument(debug_span!("fork_refs::delete"))
+        .await
+        .is_some()
+    }
+
+    /// Returns every part of `shard_id` that is referenced by the state of
+    /// some fork of `shard_id`.
+    pub async fn fetch_fork_refs(&self, shard_id: &ShardId) -> HashSet<PartialBatchKey> {
+        let keys = retry_external(&self.metrics.retries.external.fork_refs_get, || async {
+            let mut keys = Vec::new();
+            self.blob
+                .list_keys_and_metadata(
+                    &BlobKeyPrefix::ForkRefs(shard_id).to_string(),
+                    &mut |metadata| keys.push(metadata.key.to_owned()),
+                )
+                .await?;
+            Ok::<_, ExternalError>(keys)
+        })
+        .instrument(debug_span!("fork_refs::list"))
+        .await;
+
+        let mut parts = HashSet::new();
+        for key in keys {
+            let buf = retry_external(&self.metrics.retries.external.fork_refs_get, || async {
+                self.blob.get(&key).await
+            })
+            .instrument(debug_span!("fork_refs::get"))
+            .await;
+            // The fork refs may have been deleted since we listed them, in
+            // which case they no longer need to be respected.
+            let buf = match buf {
+                Some(buf) => buf,
+                None => continue,
+            };
+            let proto = ProtoForkRefs::decode(buf.as_slice())
+                .unwrap_or_else(|err| panic!("invalid fork refs at {}: {}", key, err));
+            parts.extend(proto.part_keys.into_iter().map(PartialBatchKey));
+        }
+        parts
+    }
 }
 
 /// An iterator over consecutive versions of [State].
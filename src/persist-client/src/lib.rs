@@ -17,7 +17,9 @@
     clippy::cast_sign_loss
 )]
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Debug;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -31,7 +33,8 @@ use mz_persist_types::{Codec, Codec64};
 use proptest_derive::Arbitrary;
 use semver::Version;
 use serde::{Deserialize, Serialize};
-use timely::progress::Timestamp;
+use timely::progress::{Antichain, Timestamp};
+use timely::PartialOrder;
 use tracing::instrument;
 use uuid::Uuid;
 
@@ -44,6 +47,7 @@ use crate::internal::gc::GarbageCollector;
 use crate::internal::machine::{retry_external, Machine};
 use crate::internal::state_versions::StateVersions;
 use crate::read::{LeasedReaderId, ReadHandle};
+use crate::rpc::PubSubClient;
 use crate::write::{WriteHandle, WriterId};
 
 pub mod async_runtime;
@@ -54,6 +58,7 @@ pub mod error;
 pub mod fetch;
 pub mod inspect;
 pub mod read;
+pub mod rpc;
 pub mod usage;
 pub mod write;
 
@@ -243,6 +248,15 @@ pub struct PersistConfig {
     pub reader_lease_duration: Duration,
     /// Length of time between critical handles' calls to downgrade since
     pub critical_downgrade_interval: Duration,
+    /// Length of time a listener that is connected to pubsub waits for a
+    /// pushed state diff before fetching state from consensus anyway.
+    pub listen_pubsub_fallback_interval: Duration,
+    /// A local directory in which to cache blob reads, or None to not cache
+    /// them.
+    pub blob_disk_cache_dir: Option<PathBuf>,
+    /// The maximum size of the local blob disk cache, after which the least
+    /// recently used blobs are evicted.
+    pub blob_disk_cache_max_size_bytes: usize,
 }
 
 // Tuning inputs:
@@ -309,6 +323,9 @@ impl PersistConfig {
             writer_lease_duration: 60 * Duration::from_secs(60),
             reader_lease_duration: Self::DEFAULT_READ_LEASE_DURATION,
             critical_downgrade_interval: Duration::from_secs(30),
+            listen_pubsub_fallback_interval: Duration::from_secs(10),
+            blob_disk_cache_dir: None,
+            blob_disk_cache_max_size_bytes: 10 * 1024 * MB,
         }
     }
 }
@@ -347,6 +364,7 @@ pub struct PersistClient {
     consensus: Arc<dyn Consensus + Send + Sync>,
     metrics: Arc<Metrics>,
     cpu_heavy_runtime: Arc<CpuHeavyRuntime>,
+    pubsub_client: Arc<dyn PubSubClient>,
 }
 
 impl PersistClient {
@@ -361,6 +379,7 @@ impl PersistClient {
         consensus: Arc<dyn Consensus + Send + Sync>,
         metrics: Arc<Metrics>,
         cpu_heavy_runtime: Arc<CpuHeavyRuntime>,
+        pubsub_client: Arc<dyn PubSubClient>,
     ) -> Result<Self, ExternalError> {
         // TODO: Verify somehow that blob matches consensus to prevent
         // accidental misuse.
@@ -370,6 +389,7 @@ impl PersistClient {
             consensus,
             metrics,
             cpu_heavy_runtime,
+            pubsub_client,
         })
     }
 
@@ -423,6 +443,7 @@ impl PersistClient {
             Arc::clone(&self.consensus),
             Arc::clone(&self.blob),
             Arc::clone(&self.metrics),
+            Arc::clone(&self.pubsub_client),
         );
         let mut machine = Machine::new(
             self.cfg.clone(),
@@ -514,6 +535,7 @@ impl PersistClient {
             Arc::clone(&self.consensus),
             Arc::clone(&self.blob),
             Arc::clone(&self.metrics),
+            Arc::clone(&self.pubsub_client),
         );
         let mut machine = Machine::new(
             self.cfg.clone(),
@@ -556,6 +578,7 @@ impl PersistClient {
             Arc::clone(&self.consensus),
             Arc::clone(&self.blob),
             Arc::clone(&self.metrics),
+            Arc::clone(&self.pubsub_client),
         );
         let mut machine = Machine::new(
             self.cfg.clone(),
@@ -594,6 +617,91 @@ impl PersistClient {
         Ok(writer)
     }
 
+    /// Initializes the shard identified by `fork_id` as a fork of the shard
+    /// identified by `source_id`, readable at `since`.
+    ///
+    /// The fork starts out with the contents and upper of `source_id`, but
+    /// doesn't copy any of its data: its state refers directly to the blobs
+    /// written by `source_id`. From then on, the two shards are independent.
+    /// Writes to and compaction of either shard are not visible in the other,
+    /// and garbage collection of `source_id` keeps any blobs still referenced
+    /// by the fork. Garbage collection of the fork releases these blobs once
+    /// it has compacted them away, or once its since is advanced to the empty
+    /// antichain.
+    ///
+    /// `fork_id` must not contain any data yet and `since` must not be less
+    /// than the since of `source_id`. Any readers already registered on
+    /// `fork_id` have their since advanced to `since`.
+    #[instrument(level = "debug", skip_all, fields(source = %source_id, fork = %fork_id))]
+    pub async fn fork_shard<K, V, T, D>(
+        &self,
+        source_id: ShardId,
+        fork_id: ShardId,
+        since: Antichain<T>,
+    ) -> Result<(), InvalidUsage<T>>
+    where
+        K: Debug + Codec,
+        V: Debug + Codec,
+        T: Timestamp + Lattice + Codec64,
+        D: Semigroup + Codec64 + Send + Sync,
+    {
+        let state_versions = Arc::new(StateVersions::new(
+            self.cfg.clone(),
+            Arc::clone(&self.consensus),
+            Arc::clone(&self.blob),
+            Arc::clone(&self.metrics),
+            Arc::clone(&self.pubsub_client),
+        ));
+        let mut source = Machine::<K, V, T, D>::new(
+            self.cfg.clone(),
+            source_id,
+            Arc::clone(&self.metrics),
+            Arc::clone(&state_versions),
+        )
+        .await?;
+        let mut fork = Machine::<K, V, T, D>::new(
+            self.cfg.clone(),
+            fork_id,
+            Arc::clone(&self.metrics),
+            Arc::clone(&state_versions),
+        )
+        .await?;
+
+        // Hold back garbage collection of the source shard until the fork's
+        // references to its blobs have been recorded.
+        let reader_id = LeasedReaderId::new();
+        let (_, read_cap) = source
+            .register_leased_reader(&reader_id, self.cfg.reader_lease_duration, (self.cfg.now)())
+            .await;
+
+        let res = async {
+            if !PartialOrder::less_equal(&read_cap.since, &since) {
+                return Err(InvalidUsage::ForkSinceNotBeyondSince {
+                    fork_since: since.clone(),
+                    shard_since: read_cap.since.clone(),
+                });
+            }
+
+            let batches = source.foreign_batches();
+            let mut fork_refs = BTreeMap::<_, BTreeSet<_>>::new();
+            for batch in batches.iter() {
+                for part in batch.parts.iter() {
+                    let (owner, key) = part.key.owner(&fork_id);
+                    fork_refs.entry(owner).or_default().insert(key);
+                }
+            }
+            for (owner, keys) in fork_refs.iter() {
+                state_versions.write_fork_refs(owner, &fork_id, keys).await;
+            }
+
+            fork.fork_from(&batches, &since).await.map(|_seqno| ())
+        }
+        .await;
+
+        source.expire_leased_reader(&reader_id).await;
+        res
+    }
+
     /// Test helper for a [Self::open] call that is expected to succeed.
     #[cfg(test)]
     #[track_caller]
@@ -642,6 +750,7 @@ mod tests {
 
     use crate::cache::PersistClientCache;
     use crate::error::CodecMismatch;
+    use crate::internal::gc::GcReq;
     use crate::internal::paths::BlobKey;
     use crate::internal::state::Upper;
     use crate::read::ListenEvent;
@@ -1072,6 +1181,163 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn fork_shard() {
+        mz_ore::test::init_logging();
+
+        let data = vec![
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+            (("3".to_owned(), "three".to_owned()), 3, 1),
+            (("4".to_owned(), "four".to_owned()), 4, 1),
+        ];
+
+        let (source_id, fork_id) = (ShardId::new(), ShardId::new());
+        let client = new_test_client().await;
+        let (mut source_write, mut source_read) = client
+            .expect_open::<String, String, u64, i64>(source_id)
+            .await;
+        source_write
+            .expect_compare_and_append(&data[..2], u64::minimum(), 3)
+            .await;
+        source_read.downgrade_since(&Antichain::from_elem(1)).await;
+
+        // The fork can't be readable at times that the source has already
+        // compacted away.
+        assert_eq!(
+            client
+                .fork_shard::<String, String, u64, i64>(source_id, fork_id, Antichain::from_elem(0))
+                .await
+                .unwrap_err(),
+            InvalidUsage::ForkSinceNotBeyondSince {
+                fork_since: Antichain::from_elem(0),
+                shard_since: Antichain::from_elem(1),
+            }
+        );
+
+        client
+            .fork_shard::<String, String, u64, i64>(source_id, fork_id, Antichain::from_elem(2))
+            .await
+            .expect("fork failed");
+
+        // The fork starts out with the contents of the source, without copying
+        // any of its blobs.
+        let (mut fork_write, mut fork_read) = client
+            .expect_open::<String, String, u64, i64>(fork_id)
+            .await;
+        assert_eq!(fork_write.upper(), &Antichain::from_elem(3));
+        assert_eq!(fork_read.since(), &Antichain::from_elem(2));
+        assert_eq!(
+            fork_read.expect_snapshot_and_fetch(2).await,
+            all_ok(&data[..2], 2)
+        );
+        let fork_refs = fork_read
+            .machine
+            .state_versions
+            .fetch_fork_refs(&source_id)
+            .await;
+        for batch in fork_read.machine.foreign_batches() {
+            for part in batch.parts {
+                let (owner, key) = part.key.owner(&fork_id);
+                assert_eq!(owner, source_id);
+                assert!(fork_refs.contains(&key));
+            }
+        }
+
+        // From then on, writes to one aren't visible in the other.
+        source_write
+            .expect_compare_and_append(&data[2..3], 3, 4)
+            .await;
+        fork_write.expect_compare_and_append(&data[3..], 3, 5).await;
+        assert_eq!(
+            source_read.expect_snapshot_and_fetch(3).await,
+            all_ok(&data[..3], 3)
+        );
+        assert_eq!(
+            fork_read.expect_snapshot_and_fetch(4).await,
+            all_ok(data[..2].iter().chain(&data[3..]), 4)
+        );
+
+        // A shard with data can't become a fork.
+        assert_eq!(
+            client
+                .fork_shard::<String, String, u64, i64>(source_id, fork_id, Antichain::from_elem(2))
+                .await
+                .unwrap_err(),
+            InvalidUsage::ForkIntoNonEmptyShard {
+                fork_shard: fork_id,
+                upper: Antichain::from_elem(5),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn fork_shard_gc() {
+        mz_ore::test::init_logging();
+
+        let data = vec![
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+        ];
+
+        let (source_id, fork_id) = (ShardId::new(), ShardId::new());
+        let client = new_test_client().await;
+        let (mut source_write, mut source_read) = client
+            .expect_open::<String, String, u64, i64>(source_id)
+            .await;
+        source_write
+            .expect_compare_and_append(&data, u64::minimum(), 3)
+            .await;
+        client
+            .fork_shard::<String, String, u64, i64>(source_id, fork_id, Antichain::from_elem(0))
+            .await
+            .expect("fork failed");
+        let (_fork_write, mut fork_read) = client
+            .expect_open::<String, String, u64, i64>(fork_id)
+            .await;
+        let foreign_keys: Vec<_> = fork_read
+            .machine
+            .foreign_batches()
+            .into_iter()
+            .flat_map(|b| b.parts.into_iter().map(|x| x.key))
+            .collect();
+        assert!(!foreign_keys.is_empty());
+        assert!(foreign_keys.iter().all(|x| x.is_foreign()));
+        assert!(!fork_read
+            .machine
+            .state_versions
+            .fetch_fork_refs(&source_id)
+            .await
+            .is_empty());
+
+        // Once the fork can never be read again, its GC releases its refs on
+        // the source, but leaves the blobs the source still references alone.
+        fork_read.downgrade_since(&Antichain::new()).await;
+        let req = GcReq {
+            shard_id: fork_id,
+            new_seqno_since: fork_read.machine.seqno(),
+        };
+        GarbageCollector::gc_and_truncate(&mut fork_read.machine, req).await;
+        assert!(fork_read
+            .machine
+            .state_versions
+            .fetch_fork_refs(&source_id)
+            .await
+            .is_empty());
+        for key in foreign_keys.iter() {
+            let blob = client
+                .blob
+                .get(&key.complete(&fork_id))
+                .await
+                .expect("blob get failed");
+            assert!(blob.is_some());
+        }
+        assert_eq!(
+            source_read.expect_snapshot_and_fetch(2).await,
+            all_ok(&data, 2)
+        );
+    }
+
     #[tokio::test]
     async fn fetch_upper() {
         mz_ore::test::init_logging();
@@ -37,6 +37,7 @@ use crate::fetch::{
 use crate::internal::machine::Machine;
 use crate::internal::metrics::{Metrics, MetricsRetryStream};
 use crate::internal::state::{HollowBatch, Since};
+use crate::rpc::ShardSubscription;
 use crate::{parse_id, GarbageCollector, PersistConfig};
 
 /// An opaque identifier for a reader of a persist durable TVC (aka shard).
@@ -426,6 +427,7 @@ where
     last_heartbeat: EpochMillis,
     explicitly_expired: bool,
     leased_seqnos: BTreeMap<SeqNo, usize>,
+    state_subscription: Option<ShardSubscription>,
 
     pub(crate) heartbeat_task: Option<JoinHandle<()>>,
 }
@@ -458,6 +460,7 @@ where
             last_heartbeat,
             explicitly_expired: false,
             leased_seqnos: BTreeMap::new(),
+            state_subscription: None,
             heartbeat_task: Some(machine.start_reader_heartbeat_task(reader_id).await),
         }
     }
@@ -602,6 +605,62 @@ where
         Ok(contents)
     }
 
+    /// Generates a [Self::snapshot], and folds each update in a sample of at
+    /// most `max_parts` of its parts into `init` with `f`.
+    ///
+    /// The sampled parts are spread evenly across the snapshot. Along with
+    /// the folded value, returns the number of updates in the whole snapshot,
+    /// which is known from the shard's metadata without fetching any parts.
+    /// Only one part is held in memory at a time. Updates are presented in no
+    /// particular order and are not consolidated.
+    pub async fn snapshot_and_fold_sample<A, F>(
+        &mut self,
+        as_of: Antichain<T>,
+        max_parts: usize,
+        init: A,
+        mut f: F,
+    ) -> Result<(usize, A), Since<T>>
+    where
+        F: FnMut(&mut A, ((Result<K, String>, Result<V, String>), T, D)),
+    {
+        let batches = self.machine.snapshot(&as_of).await?;
+        let num_updates = batches.iter().map(|batch| batch.len).sum();
+        let num_parts: usize = batches.iter().map(|batch| batch.parts.len()).sum();
+        let max_parts = std::cmp::max(1, max_parts);
+        let step = std::cmp::max(1, (num_parts + max_parts - 1) / max_parts);
+
+        let metadata = SerdeLeasedBatchPartMetadata::Snapshot {
+            as_of: as_of.iter().map(T::encode).collect(),
+        };
+        let mut sample = Vec::new();
+        let mut part_idx = 0;
+        for mut batch in batches {
+            batch.parts.retain(|_| {
+                let sampled = part_idx % step == 0;
+                part_idx += 1;
+                sampled
+            });
+            sample.extend(self.lease_batch_parts(batch, metadata.clone()));
+        }
+
+        let mut acc = init;
+        for part in sample {
+            let (part, fetched_part) = fetch_leased_part(
+                part,
+                self.blob.as_ref(),
+                Arc::clone(&self.metrics),
+                &self.metrics.read.snapshot,
+                Some(&self.reader_id),
+            )
+            .await;
+            self.process_returned_leased_part(part);
+            for update in fetched_part {
+                f(&mut acc, update);
+            }
+        }
+        Ok((num_updates, acc))
+    }
+
     /// Returns a snapshot of all of a shard's data using `as_of`, followed by
     /// listening to any future updates.
     ///
@@ -770,6 +829,42 @@ where
             if let Some(b) = self.machine.next_listen_batch(frontier) {
                 return b;
             }
+
+            // If we're connected to pubsub, wait for the next state diff to be
+            // pushed to us instead of polling consensus. Still fetch state
+            // every so often in case a push got lost somewhere.
+            if self.state_subscription.is_none() {
+                // Subscribe before fetching, so that we can't miss a diff
+                // that's pushed in between.
+                self.state_subscription = Some(self.machine.subscribe_to_state_diffs());
+                self.machine.fetch_and_update_state().await;
+                continue;
+            }
+            let subscription = self
+                .state_subscription
+                .as_mut()
+                .expect("subscription initialized above");
+            if subscription.is_connected() {
+                retry = None;
+                let pushed = tokio::time::timeout(
+                    self.cfg.listen_pubsub_fallback_interval,
+                    subscription.recv(),
+                )
+                .instrument(trace_span!("listen::wait_for_push"))
+                .await;
+                if let Ok(Some(diff)) = pushed {
+                    if self.machine.apply_pushed_diff(diff) {
+                        continue;
+                    }
+                } else {
+                    // Same as below, heartbeat so we don't lose our lease
+                    // while the shard is quiet.
+                    self.maybe_heartbeat_reader().await;
+                }
+                self.machine.fetch_and_update_state().await;
+                continue;
+            }
+
             // Only sleep after the first fetch, because the first time through
             // maybe our state was just out of date.
             retry = Some(match retry.take() {
@@ -781,9 +876,6 @@ where
                 Some(retry) => {
                     // Wait a bit and try again. Intentionally don't ever log
                     // this at info level.
-                    //
-                    // TODO: See if we can watch for changes in Consensus to be
-                    // more reactive here.
                     debug!(
                         "next_listen_batch didn't find new data, retrying in {:?}",
                         retry.next_sleep()
@@ -894,6 +986,7 @@ mod tests {
 
     use crate::async_runtime::CpuHeavyRuntime;
     use crate::internal::metrics::Metrics;
+    use crate::rpc::NoopPubSubClient;
     use crate::tests::{all_ok, new_test_client};
     use crate::{PersistClient, PersistConfig, ShardId};
 
@@ -1098,6 +1191,45 @@ mod tests {
         assert_eq!(container.reader_id, id);
     }
 
+    #[tokio::test]
+    async fn snapshot_and_fold_sample() {
+        mz_ore::test::init_logging();
+        let data = vec![
+            (("0".to_owned(), "zero".to_owned()), 0, 1),
+            (("1".to_owned(), "one".to_owned()), 1, 2),
+            (("2".to_owned(), "two".to_owned()), 2, 3),
+        ];
+
+        let (mut write, mut read) = new_test_client()
+            .await
+            .expect_open::<String, String, u64, i64>(ShardId::new())
+            .await;
+        write.expect_compare_and_append(&data[0..2], 0, 2).await;
+        write.expect_compare_and_append(&data[2..3], 2, 3).await;
+
+        // A sample of at least as many parts as the snapshot has is the
+        // whole snapshot.
+        let expected = read.expect_snapshot_and_fetch(2).await;
+        let (num_updates, mut actual) = read
+            .snapshot_and_fold_sample(Antichain::from_elem(2), 2, Vec::new(), |acc, update| {
+                acc.push(update)
+            })
+            .await
+            .expect("cannot serve requested as_of");
+        actual.sort();
+        assert_eq!(num_updates, 3);
+        assert_eq!(actual, expected);
+
+        // Each batch was written as a single part, so sampling one part
+        // fetches only the updates of the first batch.
+        let (num_updates, sampled) = read
+            .snapshot_and_fold_sample(Antichain::from_elem(2), 1, 0, |acc, _| *acc += 1)
+            .await
+            .expect("cannot serve requested as_of");
+        assert_eq!(num_updates, 3);
+        assert_eq!(sampled, 2);
+    }
+
     // Verifies performance optimizations where a Listener doesn't fetch the
     // latest Consensus state if the one it currently has can serve the next
     // request.
@@ -1124,6 +1256,7 @@ mod tests {
             consensus,
             metrics,
             Arc::new(CpuHeavyRuntime::new()),
+            Arc::new(NoopPubSubClient),
         )
         .expect("client construction failed")
         .expect_open::<String, String, u64, i64>(ShardId::new())
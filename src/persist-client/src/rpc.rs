@@ -0,0 +1,682 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Push-based notifications of shard state changes.
+//!
+//! Every successful compare-and-set of a shard's state is published as the
+//! encoded [StateDiff](crate::internal::state_diff::StateDiff) that was written
+//! to [Consensus](mz_persist::location::Consensus). Listeners subscribe to the
+//! shards they read and apply these diffs as they arrive, instead of polling
+//! Consensus for new versions of state.
+//!
+//! environmentd serves the channel in-process with a [PersistPubSubServer],
+//! mounted on its internal HTTP server, which clusters connect to over gRPC
+//! with a [GrpcPubSubClient]. Delivery is
+//! best-effort: a dropped or out-of-order diff is noticed by its [SeqNo] and
+//! repaired by fetching state from Consensus, and listeners fall back to
+//! polling Consensus whenever their client is disconnected.
+//!
+//! [SeqNo]: mz_persist::location::SeqNo
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use anyhow::anyhow;
+use axum::{routing, Router};
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use mz_persist::location::{SeqNo, VersionedData};
+use mz_persist::retry::Retry;
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tonic::transport::{Endpoint, NamedService};
+use tonic::{Request, Response, Status, Streaming};
+use tracing::{debug, info, warn};
+
+use crate::internal::metrics::Metrics;
+use crate::rpc::proto_persist_pub_sub_client::ProtoPersistPubSubClient;
+use crate::rpc::proto_persist_pub_sub_server::{ProtoPersistPubSub, ProtoPersistPubSubServer};
+use crate::rpc::proto_pub_sub_message::Message;
+use crate::ShardId;
+
+include!(concat!(
+    env!("OUT_DIR"),
+    "/mz_persist_client.internal.service.rs"
+));
+
+/// The number of diffs buffered for each subscription before the oldest are
+/// dropped. A subscriber that falls this far behind fetches state instead.
+const SUBSCRIPTION_CAPACITY: usize = 64;
+
+/// A connection to the pub/sub channel of shard state diffs.
+pub trait PubSubClient: Debug + Send + Sync {
+    /// Publishes `diff`, which was just compare-and-set into the state of
+    /// `shard_id`.
+    fn push(&self, shard_id: &ShardId, diff: &VersionedData);
+
+    /// Returns a subscription to the diffs published for `shard_id`.
+    fn subscribe(&self, shard_id: &ShardId) -> ShardSubscription;
+}
+
+/// A subscription to the diffs published for a shard.
+#[derive(Debug)]
+pub struct ShardSubscription {
+    rx: broadcast::Receiver<VersionedData>,
+    connected: Arc<AtomicBool>,
+}
+
+impl ShardSubscription {
+    /// Whether diffs are currently being delivered to this subscription.
+    ///
+    /// While this is false, diffs may be missed without notice, so readers
+    /// should poll Consensus instead.
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    /// Waits for the next diff published for the shard.
+    ///
+    /// Returns `None` if diffs were dropped because this subscription fell
+    /// behind, in which case the caller should fetch the current state.
+    pub async fn recv(&mut self) -> Option<VersionedData> {
+        match self.rx.recv().await {
+            Ok(diff) => Some(diff),
+            Err(broadcast::error::RecvError::Lagged(_)) => None,
+            // The sending half is only dropped along with the client, after
+            // which nothing is ever delivered again.
+            Err(broadcast::error::RecvError::Closed) => futures::future::pending().await,
+        }
+    }
+}
+
+/// A [PubSubClient] that publishes nothing and whose subscriptions are never
+/// connected, for processes that are not connected to a [PersistPubSubServer].
+#[derive(Debug)]
+pub struct NoopPubSubClient;
+
+impl PubSubClient for NoopPubSubClient {
+    fn push(&self, _shard_id: &ShardId, _diff: &VersionedData) {}
+
+    fn subscribe(&self, _shard_id: &ShardId) -> ShardSubscription {
+        let (_tx, rx) = broadcast::channel(1);
+        ShardSubscription {
+            rx,
+            connected: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+/// The per-shard broadcast channels of a client's subscriptions.
+#[derive(Debug, Default)]
+struct ShardSubscriptions {
+    by_shard: Mutex<HashMap<ShardId, broadcast::Sender<VersionedData>>>,
+}
+
+impl ShardSubscriptions {
+    /// Returns a receiver of the diffs of `shard_id`, and whether the shard
+    /// had no live receivers before.
+    fn subscribe(&self, shard_id: &ShardId) -> (broadcast::Receiver<VersionedData>, bool) {
+        let mut by_shard = self.by_shard.lock().expect("lock poisoned");
+        match by_shard.get(shard_id) {
+            Some(tx) if tx.receiver_count() > 0 => (tx.subscribe(), false),
+            _ => {
+                let (tx, rx) = broadcast::channel(SUBSCRIPTION_CAPACITY);
+                by_shard.insert(*shard_id, tx);
+                (rx, true)
+            }
+        }
+    }
+
+    /// Delivers `diff` to the receivers of `shard_id`.
+    ///
+    /// Returns false, and forgets the shard, if it has no live receivers.
+    fn deliver(&self, shard_id: &ShardId, diff: &VersionedData) -> bool {
+        let mut by_shard = self.by_shard.lock().expect("lock poisoned");
+        let delivered = match by_shard.get(shard_id) {
+            Some(tx) => tx.send(diff.clone()).is_ok(),
+            None => return false,
+        };
+        if !delivered {
+            by_shard.remove(shard_id);
+        }
+        delivered
+    }
+
+    /// Returns the shards with live receivers.
+    fn shards(&self) -> Vec<ShardId> {
+        let by_shard = self.by_shard.lock().expect("lock poisoned");
+        by_shard
+            .iter()
+            .filter(|(_, tx)| tx.receiver_count() > 0)
+            .map(|(shard_id, _)| *shard_id)
+            .collect()
+    }
+}
+
+fn push_diff_message(shard_id: &ShardId, diff: &VersionedData) -> ProtoPubSubMessage {
+    ProtoPubSubMessage {
+        message: Some(Message::PushDiff(ProtoPushDiff {
+            shard_id: shard_id.to_string(),
+            seqno: diff.seqno.0,
+            diff: diff.data.to_vec(),
+        })),
+    }
+}
+
+fn subscribe_message(shard_id: &ShardId) -> ProtoPubSubMessage {
+    ProtoPubSubMessage {
+        message: Some(Message::Subscribe(ProtoSubscribe {
+            shard_id: shard_id.to_string(),
+        })),
+    }
+}
+
+fn unsubscribe_message(shard_id: &ShardId) -> ProtoPubSubMessage {
+    ProtoPubSubMessage {
+        message: Some(Message::Unsubscribe(ProtoUnsubscribe {
+            shard_id: shard_id.to_string(),
+        })),
+    }
+}
+
+fn parse_push_diff(push: ProtoPushDiff) -> Result<(ShardId, VersionedData), String> {
+    let shard_id = push.shard_id.parse::<ShardId>()?;
+    let diff = VersionedData {
+        seqno: SeqNo(push.seqno),
+        data: Bytes::from(push.diff),
+    };
+    Ok((shard_id, diff))
+}
+
+/// A client of the pub/sub channel.
+#[derive(Debug)]
+enum PubSubConnection {
+    /// A client in the same process as the server. It receives every diff and
+    /// drops those for shards it has no subscriptions to.
+    Local(Arc<ShardSubscriptions>),
+    /// A client connected over gRPC. It receives the diffs for the shards it
+    /// subscribed to.
+    Remote {
+        tx: mpsc::UnboundedSender<Result<ProtoPubSubMessage, Status>>,
+        shards: HashSet<ShardId>,
+    },
+}
+
+#[derive(Debug, Default)]
+struct PubSubServerState {
+    next_connection_id: AtomicUsize,
+    connections: Mutex<HashMap<usize, PubSubConnection>>,
+}
+
+impl PubSubServerState {
+    fn register(&self, connection: PubSubConnection) -> usize {
+        let id = self.next_connection_id.fetch_add(1, Ordering::SeqCst);
+        let mut connections = self.connections.lock().expect("lock poisoned");
+        connections.insert(id, connection);
+        id
+    }
+
+    fn deregister(&self, id: usize) {
+        let mut connections = self.connections.lock().expect("lock poisoned");
+        connections.remove(&id);
+    }
+
+    fn update_subscription(&self, id: usize, shard_id: ShardId, subscribed: bool) {
+        let mut connections = self.connections.lock().expect("lock poisoned");
+        if let Some(PubSubConnection::Remote { shards, .. }) = connections.get_mut(&id) {
+            if subscribed {
+                shards.insert(shard_id);
+            } else {
+                shards.remove(&shard_id);
+            }
+        }
+    }
+
+    /// Forwards a diff pushed by the connection `from` to every other
+    /// connection interested in `shard_id`.
+    ///
+    /// Remote connections whose client has gone away are deregistered, along
+    /// with their subscriptions.
+    fn push(&self, from: usize, shard_id: &ShardId, diff: &VersionedData) {
+        let mut connections = self.connections.lock().expect("lock poisoned");
+        let mut message = None;
+        connections.retain(|id, connection| {
+            if *id == from {
+                return true;
+            }
+            match connection {
+                PubSubConnection::Local(subscriptions) => {
+                    subscriptions.deliver(shard_id, diff);
+                    true
+                }
+                PubSubConnection::Remote { tx, shards } => {
+                    if !shards.contains(shard_id) {
+                        return !tx.is_closed();
+                    }
+                    let message = message.get_or_insert_with(|| push_diff_message(shard_id, diff));
+                    // A send error means the response stream was dropped,
+                    // which happens when the client disconnects.
+                    tx.send(Ok(message.clone())).is_ok()
+                }
+            }
+        });
+    }
+}
+
+/// The server side of the pub/sub channel of shard state diffs.
+///
+/// There should be exactly one of these, run by environmentd. Other processes
+/// connect to it with a [GrpcPubSubClient].
+#[derive(Debug, Clone)]
+pub struct PersistPubSubServer {
+    state: Arc<PubSubServerState>,
+    metrics: Arc<Metrics>,
+}
+
+impl PersistPubSubServer {
+    /// Returns a new [PersistPubSubServer] without any connections.
+    pub fn new(metrics: Arc<Metrics>) -> Self {
+        PersistPubSubServer {
+            state: Arc::new(PubSubServerState::default()),
+            metrics,
+        }
+    }
+
+    /// Returns a client that is connected to this server within the same
+    /// process.
+    pub fn new_same_process_connection(&self) -> Arc<dyn PubSubClient> {
+        let subscriptions = Arc::new(ShardSubscriptions::default());
+        let id = self
+            .state
+            .register(PubSubConnection::Local(Arc::clone(&subscriptions)));
+        Arc::new(LocalPubSubClient {
+            id,
+            state: Arc::clone(&self.state),
+            subscriptions,
+            connected: Arc::new(AtomicBool::new(true)),
+            metrics: Arc::clone(&self.metrics),
+        })
+    }
+
+    /// Returns a router that serves [GrpcPubSubClient]s, for mounting in an
+    /// existing HTTP server.
+    ///
+    /// gRPC requires HTTP/2, so the server must accept HTTP/2 connections.
+    pub fn into_router(self) -> Router {
+        let path = format!(
+            "/{}/*rpc",
+            <ProtoPersistPubSubServer<Self> as NamedService>::NAME
+        );
+        Router::new().route(
+            &path,
+            routing::any_service(ProtoPersistPubSubServer::new(self)),
+        )
+    }
+}
+
+#[tonic::async_trait]
+impl ProtoPersistPubSub for PersistPubSubServer {
+    type PubSubStream = Pin<Box<dyn Stream<Item = Result<ProtoPubSubMessage, Status>> + Send>>;
+
+    async fn pub_sub(
+        &self,
+        request: Request<Streaming<ProtoPubSubMessage>>,
+    ) -> Result<Response<Self::PubSubStream>, Status> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let closed = tx.clone();
+        let id = self.state.register(PubSubConnection::Remote {
+            tx,
+            shards: HashSet::new(),
+        });
+        info!("persist pubsub: client {} connected", id);
+
+        let state = Arc::clone(&self.state);
+        let mut requests = request.into_inner();
+        mz_ore::task::spawn(
+            || format!("persist_pubsub_connection({})", id),
+            async move {
+                loop {
+                    // The response stream is dropped when the client
+                    // disconnects, even if the request stream is left hanging.
+                    let message = tokio::select! {
+                        message = requests.next() => message,
+                        () = closed.closed() => break,
+                    };
+                    let message = match message {
+                        Some(Ok(message)) => message,
+                        Some(Err(err)) => {
+                            debug!("persist pubsub: client {} errored: {}", id, err);
+                            break;
+                        }
+                        None => break,
+                    };
+                    match message.message {
+                        Some(Message::PushDiff(push)) => match parse_push_diff(push) {
+                            Ok((shard_id, diff)) => state.push(id, &shard_id, &diff),
+                            Err(err) => warn!("persist pubsub: invalid diff from {}: {}", id, err),
+                        },
+                        Some(Message::Subscribe(ProtoSubscribe { shard_id })) => {
+                            match shard_id.parse() {
+                                Ok(shard_id) => state.update_subscription(id, shard_id, true),
+                                Err(err) => {
+                                    warn!("persist pubsub: invalid shard from {}: {}", id, err)
+                                }
+                            }
+                        }
+                        Some(Message::Unsubscribe(ProtoUnsubscribe { shard_id })) => match shard_id
+                            .parse()
+                        {
+                            Ok(shard_id) => state.update_subscription(id, shard_id, false),
+                            Err(err) => warn!("persist pubsub: invalid shard from {}: {}", id, err),
+                        },
+                        None => {}
+                    }
+                }
+                // Dropping the connection's senders ends the response stream.
+                drop(closed);
+                state.deregister(id);
+                info!("persist pubsub: client {} disconnected", id);
+            },
+        );
+
+        Ok(Response::new(
+            Box::pin(UnboundedReceiverStream::new(rx)) as Self::PubSubStream
+        ))
+    }
+}
+
+/// A [PubSubClient] in the same process as the [PersistPubSubServer].
+#[derive(Debug)]
+struct LocalPubSubClient {
+    id: usize,
+    state: Arc<PubSubServerState>,
+    subscriptions: Arc<ShardSubscriptions>,
+    connected: Arc<AtomicBool>,
+    metrics: Arc<Metrics>,
+}
+
+impl PubSubClient for LocalPubSubClient {
+    fn push(&self, shard_id: &ShardId, diff: &VersionedData) {
+        self.metrics.pubsub.push_sent.inc();
+        self.subscriptions.deliver(shard_id, diff);
+        self.state.push(self.id, shard_id, diff);
+    }
+
+    fn subscribe(&self, shard_id: &ShardId) -> ShardSubscription {
+        let (rx, _) = self.subscriptions.subscribe(shard_id);
+        ShardSubscription {
+            rx,
+            connected: Arc::clone(&self.connected),
+        }
+    }
+}
+
+impl Drop for LocalPubSubClient {
+    fn drop(&mut self) {
+        self.state.deregister(self.id);
+    }
+}
+
+/// A [PubSubClient] connected to a [PersistPubSubServer] over gRPC.
+///
+/// The connection is maintained by a background task, which reconnects with
+/// backoff whenever it is lost.
+#[derive(Debug)]
+pub struct GrpcPubSubClient {
+    tx: mpsc::UnboundedSender<ProtoPubSubMessage>,
+    subscriptions: Arc<ShardSubscriptions>,
+    connected: Arc<AtomicBool>,
+    metrics: Arc<Metrics>,
+}
+
+impl GrpcPubSubClient {
+    /// Returns a client that connects to the [PersistPubSubServer] at `url`.
+    ///
+    /// The returned client is usable immediately, but reports its
+    /// subscriptions as disconnected until the connection is established.
+    pub fn connect(url: String, metrics: Arc<Metrics>) -> Arc<dyn PubSubClient> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let subscriptions = Arc::new(ShardSubscriptions::default());
+        let connected = Arc::new(AtomicBool::new(false));
+        mz_ore::task::spawn(
+            || format!("persist_pubsub_client({})", url),
+            Self::maintain_connection(
+                url,
+                rx,
+                Arc::clone(&subscriptions),
+                Arc::clone(&connected),
+                Arc::clone(&metrics),
+            ),
+        );
+        Arc::new(GrpcPubSubClient {
+            tx,
+            subscriptions,
+            connected,
+            metrics,
+        })
+    }
+
+    async fn maintain_connection(
+        url: String,
+        mut rx: mpsc::UnboundedReceiver<ProtoPubSubMessage>,
+        subscriptions: Arc<ShardSubscriptions>,
+        connected: Arc<AtomicBool>,
+        metrics: Arc<Metrics>,
+    ) {
+        let retry = Retry {
+            initial_backoff: Duration::from_millis(100),
+            ..Retry::persist_defaults(SystemTime::now())
+        };
+        let mut retry_stream = retry.clone().into_retry_stream();
+        loop {
+            metrics.pubsub.reconnects.inc();
+            let res =
+                Self::run_connection(&url, &mut rx, &subscriptions, &connected, &metrics).await;
+            connected.store(false, Ordering::SeqCst);
+            metrics.pubsub.connected.set(0);
+            match res {
+                // The client was dropped.
+                Ok(()) => return,
+                Err(ConnectionError::Established(err)) => {
+                    warn!("persist pubsub: connection to {} lost: {:#}", url, err);
+                    retry_stream = retry.clone().into_retry_stream();
+                }
+                Err(ConnectionError::Unestablished(err)) => {
+                    debug!(
+                        "persist pubsub: connecting to {} failed, retrying in {:?}: {:#}",
+                        url,
+                        retry_stream.next_sleep(),
+                        err
+                    );
+                    retry_stream = retry_stream.sleep().await;
+                }
+            }
+        }
+    }
+
+    async fn run_connection(
+        url: &str,
+        rx: &mut mpsc::UnboundedReceiver<ProtoPubSubMessage>,
+        subscriptions: &ShardSubscriptions,
+        connected: &AtomicBool,
+        metrics: &Metrics,
+    ) -> Result<(), ConnectionError> {
+        let channel = Endpoint::new(url.to_owned())
+            .map_err(|err| ConnectionError::Unestablished(err.into()))?
+            .connect()
+            .await
+            .map_err(|err| ConnectionError::Unestablished(err.into()))?;
+        let mut client = ProtoPersistPubSubClient::new(channel);
+        let (conn_tx, conn_rx) = mpsc::unbounded_channel();
+        let mut responses = client
+            .pub_sub(UnboundedReceiverStream::new(conn_rx))
+            .await
+            .map_err(|err| ConnectionError::Unestablished(err.into()))?
+            .into_inner();
+
+        // Mark ourselves connected before re-subscribing, so that a concurrent
+        // subscription is either in the snapshot below or sends its own
+        // subscribe message.
+        connected.store(true, Ordering::SeqCst);
+        metrics.pubsub.connected.set(1);
+        info!("persist pubsub: connected to {}", url);
+        let closed = || ConnectionError::Established(anyhow!("server closed the connection"));
+        for shard_id in subscriptions.shards() {
+            conn_tx
+                .send(subscribe_message(&shard_id))
+                .map_err(|_| closed())?;
+        }
+
+        loop {
+            tokio::select! {
+                message = rx.recv() => match message {
+                    Some(message) => conn_tx.send(message).map_err(|_| closed())?,
+                    None => return Ok(()),
+                },
+                response = responses.message() => {
+                    let response = response
+                        .map_err(|err| ConnectionError::Established(err.into()))?
+                        .ok_or_else(closed)?;
+                    if let Some(Message::PushDiff(push)) = response.message {
+                        let (shard_id, diff) = match parse_push_diff(push) {
+                            Ok(x) => x,
+                            Err(err) => {
+                                warn!("persist pubsub: invalid diff from server: {}", err);
+                                continue;
+                            }
+                        };
+                        if !subscriptions.deliver(&shard_id, &diff) {
+                            conn_tx
+                                .send(unsubscribe_message(&shard_id))
+                                .map_err(|_| closed())?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// An error that ended a [GrpcPubSubClient]'s connection.
+#[derive(Debug)]
+enum ConnectionError {
+    /// The connection could not be established.
+    Unestablished(anyhow::Error),
+    /// The connection was established and then lost.
+    Established(anyhow::Error),
+}
+
+impl PubSubClient for GrpcPubSubClient {
+    fn push(&self, shard_id: &ShardId, diff: &VersionedData) {
+        self.metrics.pubsub.push_sent.inc();
+        self.subscriptions.deliver(shard_id, diff);
+        // Diffs pushed while disconnected would only be stale by the time we
+        // reconnect, so drop them instead of queueing them.
+        if self.connected.load(Ordering::SeqCst) {
+            let _ = self.tx.send(push_diff_message(shard_id, diff));
+        }
+    }
+
+    fn subscribe(&self, shard_id: &ShardId) -> ShardSubscription {
+        let (rx, new) = self.subscriptions.subscribe(shard_id);
+        // While disconnected, the subscription is sent when reconnecting.
+        if new && self.connected.load(Ordering::SeqCst) {
+            let _ = self.tx.send(subscribe_message(shard_id));
+        }
+        ShardSubscription {
+            rx,
+            connected: Arc::clone(&self.connected),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_build_info::DUMMY_BUILD_INFO;
+    use mz_ore::cast::CastFrom;
+    use mz_ore::metrics::MetricsRegistry;
+    use mz_ore::now::SYSTEM_TIME;
+
+    use crate::PersistConfig;
+
+    use super::*;
+
+    fn diff(seqno: u64) -> VersionedData {
+        VersionedData {
+            seqno: SeqNo(seqno),
+            data: Bytes::from(vec![u8::try_from(seqno).unwrap()]),
+        }
+    }
+
+    #[tokio::test]
+    async fn same_process_delivery() {
+        let cfg = PersistConfig::new(&DUMMY_BUILD_INFO, SYSTEM_TIME.clone());
+        let metrics = Arc::new(Metrics::new(&cfg, &MetricsRegistry::new()));
+        let server = PersistPubSubServer::new(metrics);
+        let client1 = server.new_same_process_connection();
+        let client2 = server.new_same_process_connection();
+
+        let shard = ShardId::new();
+        let other_shard = ShardId::new();
+        let mut sub1 = client1.subscribe(&shard);
+        let mut sub2 = client2.subscribe(&shard);
+        assert!(sub1.is_connected());
+
+        // Diffs reach subscribers of the shard in this client and others.
+        client1.push(&shard, &diff(1));
+        assert_eq!(sub1.recv().await, Some(diff(1)));
+        assert_eq!(sub2.recv().await, Some(diff(1)));
+
+        // Diffs of other shards are not delivered.
+        client2.push(&other_shard, &diff(2));
+        client2.push(&shard, &diff(3));
+        assert_eq!(sub1.recv().await, Some(diff(3)));
+        assert_eq!(sub2.recv().await, Some(diff(3)));
+
+        // Subscribers that fall behind are told so.
+        for seqno in 0..=u64::cast_from(SUBSCRIPTION_CAPACITY) {
+            client1.push(&shard, &diff(seqno));
+        }
+        assert_eq!(sub2.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn disconnected_remote_is_deregistered() {
+        let state = PubSubServerState::default();
+        let (tx, rx) = mpsc::unbounded_channel();
+        let shard = ShardId::new();
+        let id = state.register(PubSubConnection::Remote {
+            tx,
+            shards: HashSet::new(),
+        });
+        state.update_subscription(id, shard, true);
+
+        // Diffs reach connected remote subscribers.
+        let mut rx = UnboundedReceiverStream::new(rx);
+        state.push(usize::MAX, &shard, &diff(1));
+        assert!(rx.next().await.is_some());
+        assert_eq!(state.connections.lock().unwrap().len(), 1);
+
+        // Once the client is gone, its connection and subscriptions are
+        // removed, whether or not it was subscribed to the pushed shard.
+        drop(rx);
+        state.push(usize::MAX, &ShardId::new(), &diff(2));
+        assert!(state.connections.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn noop_is_disconnected() {
+        let sub = NoopPubSubClient.subscribe(&ShardId::new());
+        assert!(!sub.is_connected());
+    }
+}
@@ -78,6 +78,7 @@ impl StorageUsageClient {
                                         rollup_size += metadata.size_in_bytes;
                                         rollup_count += 1;
                                     }
+                                    PartialBlobKey::ForkRefs(_) => {}
                                 }
                             }
                             _ => {
@@ -0,0 +1,393 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A [Blob] wrapper that caches values on local disk.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use md5::{Digest, Md5};
+use mz_ore::cast::CastFrom;
+use tokio::fs::{self, File};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::error::Error;
+use crate::file::FileBlob;
+use crate::location::{Atomicity, Blob, BlobMetadata, ExternalError};
+use crate::metrics::DiskCacheBlobMetrics;
+
+/// The extension of files that are still being written to the cache.
+const TMP_EXTENSION: &str = "tmp";
+
+/// Configuration for opening a [DiskCacheBlob].
+#[derive(Debug, Clone)]
+pub struct DiskCacheBlobConfig {
+    /// The directory under which cached values are stored.
+    ///
+    /// This may be shared by caches of several blob locations, each of which
+    /// gets its own subdirectory.
+    pub base_dir: PathBuf,
+    /// The location of the wrapped [Blob], used to pick the subdirectory of
+    /// `base_dir` in which its values are cached.
+    pub location: String,
+    /// The size on disk above which the least recently used values are
+    /// evicted.
+    pub max_size_bytes: usize,
+    /// Metrics for the cache.
+    pub metrics: DiskCacheBlobMetrics,
+}
+
+/// Implementation of [Blob] that caches the values read from another [Blob]
+/// on local disk.
+///
+/// Persist only ever writes a given key once, so a cached value is never out
+/// of date. Nonetheless, `set` and `delete` remove any cached value for the
+/// key.
+///
+/// The cache survives restarts: values found in the cache directory on open
+/// are served without fetching them again.
+#[derive(Debug)]
+pub struct DiskCacheBlob {
+    blob: Arc<dyn Blob + Send + Sync>,
+    dir: PathBuf,
+    max_size_bytes: usize,
+    metrics: DiskCacheBlobMetrics,
+    index: Mutex<CacheIndex>,
+}
+
+/// The least-recently-used bookkeeping of a [DiskCacheBlob].
+#[derive(Debug, Default)]
+struct CacheIndex {
+    /// The last use and size of each cached key.
+    entries: HashMap<String, (u64, usize)>,
+    /// The cached keys, by last use.
+    by_last_use: BTreeMap<u64, String>,
+    next_use: u64,
+    size_bytes: usize,
+}
+
+impl CacheIndex {
+    /// Marks `key` as used, returning whether it is cached.
+    fn touch(&mut self, key: &str) -> bool {
+        let use_ = self.next_use;
+        match self.entries.get_mut(key) {
+            Some((last_use, _)) => {
+                let key = self
+                    .by_last_use
+                    .remove(last_use)
+                    .expect("cached key has a last use");
+                *last_use = use_;
+                self.by_last_use.insert(use_, key);
+                self.next_use += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds `key` as the most recently used entry.
+    fn insert(&mut self, key: String, size_bytes: usize) {
+        if self.touch(&key) {
+            return;
+        }
+        let use_ = self.next_use;
+        self.next_use += 1;
+        self.entries.insert(key.clone(), (use_, size_bytes));
+        self.by_last_use.insert(use_, key);
+        self.size_bytes += size_bytes;
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some((last_use, size_bytes)) = self.entries.remove(key) {
+            self.by_last_use.remove(&last_use);
+            self.size_bytes -= size_bytes;
+        }
+    }
+
+    /// Removes the least recently used entries until the cache is no larger
+    /// than `max_size_bytes`, returning the removed keys.
+    fn evict(&mut self, max_size_bytes: usize) -> Vec<String> {
+        let mut evicted = Vec::new();
+        while self.size_bytes > max_size_bytes {
+            let last_use = match self.by_last_use.keys().next() {
+                Some(last_use) => *last_use,
+                None => break,
+            };
+            let key = self
+                .by_last_use
+                .remove(&last_use)
+                .expect("last use was just found");
+            let (_, size_bytes) = self.entries.remove(&key).expect("cached key has an entry");
+            self.size_bytes -= size_bytes;
+            evicted.push(key);
+        }
+        evicted
+    }
+}
+
+impl DiskCacheBlob {
+    /// Opens a cache of `blob` in the given location, picking up any values
+    /// cached there by a previous process.
+    pub async fn open(
+        config: DiskCacheBlobConfig,
+        blob: Arc<dyn Blob + Send + Sync>,
+    ) -> Result<Self, ExternalError> {
+        // Don't put the location itself in the path: it might contain
+        // credentials.
+        let location_hash = Md5::digest(config.location.as_bytes());
+        let dir = config.base_dir.join(format!("{:x}", location_hash));
+        fs::create_dir_all(&dir).await.map_err(Error::from)?;
+
+        let mut index = CacheIndex::default();
+        let mut entries = fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().map_or(false, |x| x == TMP_EXTENSION) {
+                // Left behind by a write that didn't finish.
+                let _ = fs::remove_file(&path).await;
+                continue;
+            }
+            let metadata = entry.metadata().await?;
+            if !metadata.is_file() {
+                continue;
+            }
+            if let Some(name) = path.file_name().and_then(|x| x.to_str()) {
+                let key = FileBlob::restore_forward_slashes(name);
+                index.insert(key, usize::cast_from(metadata.len()));
+            }
+        }
+        debug!(
+            "opened disk cache at {} with {} entries ({} bytes)",
+            dir.display(),
+            index.entries.len(),
+            index.size_bytes
+        );
+
+        let ret = DiskCacheBlob {
+            blob,
+            dir,
+            max_size_bytes: config.max_size_bytes,
+            metrics: config.metrics,
+            index: Mutex::new(index),
+        };
+        ret.evict().await;
+        Ok(ret)
+    }
+
+    fn cache_path(&self, key: &str) -> PathBuf {
+        self.dir.join(FileBlob::replace_forward_slashes(key))
+    }
+
+    /// Returns the cached value of `key`, if any.
+    async fn read_cached(&self, key: &str) -> Option<Vec<u8>> {
+        if !self.index.lock().expect("lock poisoned").touch(key) {
+            return None;
+        }
+        let mut file = match File::open(self.cache_path(key)).await {
+            Ok(file) => file,
+            // Evicted or invalidated since we checked the index.
+            Err(err) if err.kind() == ErrorKind::NotFound => return None,
+            Err(err) => {
+                warn!("failed to open cached blob {}: {}", key, err);
+                return None;
+            }
+        };
+        let mut buf = Vec::new();
+        if let Err(err) = file.read_to_end(&mut buf).await {
+            warn!("failed to read cached blob {}: {}", key, err);
+            return None;
+        }
+        Some(buf)
+    }
+
+    /// Adds `value` to the cache, evicting other values as necessary.
+    ///
+    /// The cache is best effort, so failures are logged and otherwise
+    /// ignored.
+    async fn write_cached(&self, key: &str, value: &[u8]) {
+        if value.len() > self.max_size_bytes {
+            return;
+        }
+        let path = self.cache_path(key);
+        // Concurrent misses of the same key each write their own tmp file.
+        let mut tmp_path = path.clone();
+        tmp_path.set_extension(format!("{}.{}", Uuid::new_v4(), TMP_EXTENSION));
+        let res = async {
+            let mut file = File::create(&tmp_path).await?;
+            file.write_all(value).await?;
+            file.sync_all().await?;
+            fs::rename(&tmp_path, &path).await
+        }
+        .await;
+        if let Err(err) = res {
+            warn!("failed to cache blob {}: {}", key, err);
+            let _ = fs::remove_file(&tmp_path).await;
+            return;
+        }
+        self.index
+            .lock()
+            .expect("lock poisoned")
+            .insert(key.to_owned(), value.len());
+        self.evict().await;
+    }
+
+    /// Removes any cached value of `key`.
+    ///
+    /// The file is removed even if this cache doesn't know about it, in case
+    /// another [DiskCacheBlob] for the same location cached it.
+    async fn invalidate(&self, key: &str) {
+        let size_bytes = {
+            let mut index = self.index.lock().expect("lock poisoned");
+            index.remove(key);
+            index.size_bytes
+        };
+        self.metrics.size_bytes.set(u64::cast_from(size_bytes));
+        match fs::remove_file(self.cache_path(key)).await {
+            Ok(()) => {}
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => warn!("failed to remove cached blob {}: {}", key, err),
+        }
+    }
+
+    async fn evict(&self) {
+        let (evicted, size_bytes) = {
+            let mut index = self.index.lock().expect("lock poisoned");
+            (index.evict(self.max_size_bytes), index.size_bytes)
+        };
+        self.metrics.size_bytes.set(u64::cast_from(size_bytes));
+        self.metrics.evictions.inc_by(u64::cast_from(evicted.len()));
+        for key in evicted {
+            match fs::remove_file(self.cache_path(&key)).await {
+                Ok(()) => {}
+                Err(err) if err.kind() == ErrorKind::NotFound => {}
+                Err(err) => warn!("failed to evict cached blob {}: {}", key, err),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Blob for DiskCacheBlob {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ExternalError> {
+        if let Some(value) = self.read_cached(key).await {
+            self.metrics.hits.inc();
+            self.metrics.hit_bytes.inc_by(u64::cast_from(value.len()));
+            return Ok(Some(value));
+        }
+        self.metrics.misses.inc();
+        let value = self.blob.get(key).await?;
+        if let Some(value) = value.as_ref() {
+            self.write_cached(key, value).await;
+        }
+        Ok(value)
+    }
+
+    async fn list_keys_and_metadata(
+        &self,
+        key_prefix: &str,
+        f: &mut (dyn FnMut(BlobMetadata) + Send + Sync),
+    ) -> Result<(), ExternalError> {
+        self.blob.list_keys_and_metadata(key_prefix, f).await
+    }
+
+    async fn set(&self, key: &str, value: Bytes, atomic: Atomicity) -> Result<(), ExternalError> {
+        self.invalidate(key).await;
+        self.blob.set(key, value, atomic).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<Option<usize>, ExternalError> {
+        self.invalidate(key).await;
+        self.blob.delete(key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_ore::metrics::MetricsRegistry;
+
+    use crate::location::tests::blob_impl_test;
+    use crate::mem::{MemBlob, MemBlobConfig, MemMultiRegistry};
+
+    use super::*;
+
+    fn config(base_dir: PathBuf, max_size_bytes: usize) -> DiskCacheBlobConfig {
+        DiskCacheBlobConfig {
+            base_dir,
+            location: "mem://".to_owned(),
+            max_size_bytes,
+            metrics: DiskCacheBlobMetrics::new(&MetricsRegistry::new()),
+        }
+    }
+
+    #[tokio::test]
+    async fn disk_cache_blob() -> Result<(), ExternalError> {
+        let temp_dir = tempfile::tempdir().map_err(Error::from)?;
+        let registry = Arc::new(tokio::sync::Mutex::new(MemMultiRegistry::new()));
+        blob_impl_test(move |path| {
+            let base_dir = temp_dir.path().join(path);
+            let registry = Arc::clone(&registry);
+            async move {
+                let blob = Arc::new(registry.lock().await.blob(path));
+                DiskCacheBlob::open(config(base_dir, 1024), blob).await
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn disk_cache_blob_hits_and_evictions() -> Result<(), ExternalError> {
+        let temp_dir = tempfile::tempdir().map_err(Error::from)?;
+        let blob = Arc::new(MemBlob::open(MemBlobConfig::default()));
+        for key in ["a", "b/c", "d"] {
+            blob.set(key, Bytes::from(vec![0u8; 4]), Atomicity::RequireAtomic)
+                .await?;
+        }
+
+        // Room for two of the three values.
+        let cache =
+            DiskCacheBlob::open(config(temp_dir.path().into(), 8), Arc::clone(&blob)).await?;
+        assert_eq!(cache.get("a").await?, Some(vec![0u8; 4]));
+        assert_eq!(cache.get("b/c").await?, Some(vec![0u8; 4]));
+        assert_eq!(cache.get("a").await?, Some(vec![0u8; 4]));
+        assert_eq!(cache.metrics.misses.get(), 2);
+        assert_eq!(cache.metrics.hits.get(), 1);
+
+        // Caching "d" evicts "b/c", which was used least recently.
+        assert_eq!(cache.get("d").await?, Some(vec![0u8; 4]));
+        assert_eq!(cache.metrics.evictions.get(), 1);
+        assert_eq!(cache.get("a").await?, Some(vec![0u8; 4]));
+        assert_eq!(cache.get("b/c").await?, Some(vec![0u8; 4]));
+        assert_eq!(cache.metrics.misses.get(), 4);
+        assert_eq!(cache.metrics.hits.get(), 2);
+
+        // Missing keys aren't cached.
+        assert_eq!(cache.get("nope").await?, None);
+        assert_eq!(cache.metrics.misses.get(), 5);
+
+        // A new cache in the same place picks up the cached values, even
+        // though they're gone from the wrapped blob.
+        drop(cache);
+        blob.delete("a").await?;
+        let cache =
+            DiskCacheBlob::open(config(temp_dir.path().into(), 8), Arc::clone(&blob)).await?;
+        assert_eq!(cache.get("a").await?, Some(vec![0u8; 4]));
+        assert_eq!(cache.metrics.hits.get(), 1);
+
+        // Deleting through the cache invalidates it.
+        cache.delete("b/c").await?;
+        assert_eq!(cache.get("b/c").await?, None);
+        Ok(())
+    }
+}
@@ -64,11 +64,11 @@ impl FileBlob {
     ///
     /// (And apologies to the callers who really did want to use U+2215 code points in their
     /// filenames.)
-    fn replace_forward_slashes(key: &str) -> String {
+    pub(crate) fn replace_forward_slashes(key: &str) -> String {
         key.replace('/', "∕")
     }
 
-    fn restore_forward_slashes(key: &str) -> String {
+    pub(crate) fn restore_forward_slashes(key: &str) -> String {
         key.replace('∕', "/")
     }
 }
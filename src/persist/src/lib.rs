@@ -18,6 +18,7 @@
 )]
 
 pub mod cfg;
+pub mod disk_cache;
 pub mod error;
 pub mod file;
 pub mod gen;
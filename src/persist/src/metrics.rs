@@ -39,3 +39,41 @@ impl PostgresConsensusMetrics {
         }
     }
 }
+
+/// Metrics for [crate::disk_cache::DiskCacheBlob].
+#[derive(Debug, Clone)]
+pub struct DiskCacheBlobMetrics {
+    pub(crate) hits: IntCounter,
+    pub(crate) hit_bytes: IntCounter,
+    pub(crate) misses: IntCounter,
+    pub(crate) evictions: IntCounter,
+    pub(crate) size_bytes: UIntGauge,
+}
+
+impl DiskCacheBlobMetrics {
+    /// Returns a new [DiskCacheBlobMetrics] instance connected to the given registry.
+    pub fn new(registry: &MetricsRegistry) -> Self {
+        Self {
+            hits: registry.register(metric!(
+                name: "mz_persist_blob_disk_cache_hits",
+                help: "count of blob gets served from the local disk cache",
+            )),
+            hit_bytes: registry.register(metric!(
+                name: "mz_persist_blob_disk_cache_hit_bytes",
+                help: "total size of blob gets served from the local disk cache",
+            )),
+            misses: registry.register(metric!(
+                name: "mz_persist_blob_disk_cache_misses",
+                help: "count of blob gets not served from the local disk cache",
+            )),
+            evictions: registry.register(metric!(
+                name: "mz_persist_blob_disk_cache_evictions",
+                help: "count of values evicted from the local disk cache",
+            )),
+            size_bytes: registry.register(metric!(
+                name: "mz_persist_blob_disk_cache_size_bytes",
+                help: "size of the values in the local disk cache",
+            )),
+        }
+    }
+}
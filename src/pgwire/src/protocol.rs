@@ -263,6 +263,7 @@ where
     conn.send_all(buf).await?;
     conn.flush().await?;
 
+    let is_terminated = adapter_client.terminated();
     let machine = StateMachine {
         conn,
         adapter_client: &mut adapter_client,
@@ -276,6 +277,12 @@ where
                 .await?;
             conn.flush().await
         }
+        _ = is_terminated => {
+            conn
+                .send(ErrorResponse::fatal(SqlState::ADMIN_SHUTDOWN, "terminating connection due to administrator command"))
+                .await?;
+            conn.flush().await
+        }
     }
 }
 
@@ -1283,8 +1290,10 @@ where
             | ExecuteResponse::CreatedSink { .. }
             | ExecuteResponse::CreatedSource { .. }
             | ExecuteResponse::CreatedSources
+            | ExecuteResponse::CreatedSubscription
             | ExecuteResponse::CreatedTable { .. }
             | ExecuteResponse::CreatedType
+            | ExecuteResponse::CreatedFunction
             | ExecuteResponse::CreatedView { .. }
             | ExecuteResponse::CreatedViews { .. }
             | ExecuteResponse::Deallocate { .. }
@@ -1295,6 +1304,7 @@ where
             | ExecuteResponse::DroppedComputeReplica
             | ExecuteResponse::DroppedConnection
             | ExecuteResponse::DroppedDatabase
+            | ExecuteResponse::DroppedFunction
             | ExecuteResponse::DroppedIndex
             | ExecuteResponse::DroppedMaterializedView
             | ExecuteResponse::DroppedRole
@@ -1302,10 +1312,13 @@ where
             | ExecuteResponse::DroppedSecret
             | ExecuteResponse::DroppedSink
             | ExecuteResponse::DroppedSource
+            | ExecuteResponse::DroppedSubscription
             | ExecuteResponse::DroppedTable
             | ExecuteResponse::DroppedType
             | ExecuteResponse::DroppedView
             | ExecuteResponse::Inserted(..)
+            | ExecuteResponse::Copied(..)
+            | ExecuteResponse::Merged(..)
             | ExecuteResponse::Prepare
             | ExecuteResponse::Raised
             | ExecuteResponse::StartedTransaction { .. }
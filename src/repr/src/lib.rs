@@ -33,6 +33,7 @@ pub mod antichain;
 pub mod chrono;
 pub mod explain_new;
 pub mod global_id;
+pub mod stats;
 pub mod strconv;
 pub mod url;
 
@@ -302,6 +303,9 @@ impl differential_dataflow::lattice::Lattice for Timestamp {
     }
 }
 
+// The timestamp holds no pointers, and so is written as-is.
+impl abomonation::Abomonation for Timestamp {}
+
 impl mz_persist_types::Codec64 for Timestamp {
     fn codec_name() -> String {
         u64::codec_name()
@@ -12,9 +12,11 @@ use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::convert::{TryFrom, TryInto};
 use std::fmt::{self, Debug};
+use std::io::{self, Write};
 use std::mem::{size_of, transmute};
 use std::str;
 
+use abomonation::Abomonation;
 use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Timelike, Utc};
 use mz_ore::soft_assert;
 use mz_ore::vec::Vector;
@@ -139,6 +141,66 @@ impl Row {
     const SIZE: usize = 24;
 }
 
+/// The alignment to which the data of rows is padded when entombed.
+///
+/// Abomonation writes the owned data of a value directly after that of the
+/// values before it, and reads it in place. Padding the data of rows ensures
+/// that anything written after a row, such as the offsets, times and diffs of an
+/// arrangement batch, stays aligned.
+const ENTOMB_ALIGNMENT: usize = std::mem::align_of::<u64>();
+
+/// Rows that store their data in-line are written as-is. The data of rows that
+/// have spilled to the heap is written after them, padded to
+/// [`ENTOMB_ALIGNMENT`], and is referenced in place once decoded.
+impl Abomonation for Row {
+    unsafe fn entomb<W: Write>(&self, write: &mut W) -> io::Result<()> {
+        if self.data.spilled() {
+            write.write_all(&self.data)?;
+            let padding = [0; ENTOMB_ALIGNMENT];
+            write.write_all(&padding[..entombed_len(self.data.len()) - self.data.len()])?;
+        }
+        Ok(())
+    }
+
+    unsafe fn exhume<'a, 'b>(&'a mut self, bytes: &'b mut [u8]) -> Option<&'b mut [u8]> {
+        if !self.data.spilled() {
+            return Some(bytes);
+        }
+        let len = self.data.len();
+        let padded_len = entombed_len(len);
+        if padded_len > bytes.len() {
+            return None;
+        }
+        let (data, rest) = bytes.split_at_mut(padded_len);
+        let data = &mut data[..len];
+        let decoded = if len > Self::SIZE {
+            // SAFETY: the row is never dropped nor grown, as it only ever lives
+            // in the buffer that holds its data, and it claims no more capacity
+            // than that buffer holds.
+            SmallVec::from_raw_parts(data.as_mut_ptr(), len, len)
+        } else {
+            // Rows that were spilled but have since shrunk fit in-line.
+            SmallVec::from_slice(data)
+        };
+        std::ptr::write(&mut self.data, decoded);
+        Some(rest)
+    }
+
+    fn extent(&self) -> usize {
+        if self.data.spilled() {
+            entombed_len(self.data.len())
+        } else {
+            0
+        }
+    }
+}
+
+/// Returns the number of bytes the data of a spilled row of `len` bytes takes
+/// when entombed.
+fn entombed_len(len: usize) -> usize {
+    (len + ENTOMB_ALIGNMENT - 1) / ENTOMB_ALIGNMENT * ENTOMB_ALIGNMENT
+}
+
 /// These implementations order first by length, and then by slice contents.
 /// This allows many comparisons to complete without dereferencing memory.
 /// Warning: These order by the u8 array representation, and NOT by Datum::cmp.
@@ -1844,6 +1906,36 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_abomonation() {
+        let mut shrunk = Row::with_capacity(100);
+        shrunk.packer().push(Datum::Int32(1));
+        let mut rows = vec![
+            Row::pack_slice(&[Datum::Int32(1)]),
+            Row::pack_slice(&[Datum::String(
+                "a string that is too long to be stored in-line",
+            )]),
+            shrunk,
+            Row::default(),
+        ];
+        // Rows of all lengths modulo the alignment of their entombed data.
+        let string = "x".repeat(Row::SIZE + ENTOMB_ALIGNMENT);
+        for len in Row::SIZE..string.len() {
+            rows.push(Row::pack_slice(&[Datum::String(&string[..len])]));
+        }
+        assert!(!rows[0].data.spilled());
+        assert!(rows[1].data.spilled());
+        assert!(rows[2].data.spilled() && rows[2].data.len() <= Row::SIZE);
+
+        let mut bytes = Vec::new();
+        unsafe { abomonation::encode(&rows, &mut bytes).unwrap() };
+        assert_eq!(bytes.len(), abomonation::measure(&rows));
+        assert_eq!(bytes.len() % ENTOMB_ALIGNMENT, 0);
+        let (decoded, rest) = unsafe { abomonation::decode::<Vec<Row>>(&mut bytes).unwrap() };
+        assert!(rest.is_empty());
+        assert_eq!(decoded, &rows);
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,260 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Approximate statistics about the contents of a collection.
+//!
+//! These statistics are collected out of band from the dataflows that
+//! maintain a collection, and are meant to inform the optimizer. They are
+//! only ever approximate: they may be stale, they are usually extrapolated
+//! from a sample of the collection, and distinct value counts are estimated
+//! with a HyperLogLog sketch.
+
+use serde::{Deserialize, Serialize};
+
+use mz_ore::hash::hash;
+
+use crate::{Diff, Row};
+
+/// Approximate statistics about the contents of a collection.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CollectionStatistics {
+    /// The number of rows in the collection.
+    pub num_rows: usize,
+    /// The estimated number of distinct values of each column.
+    pub distinct_values: Vec<usize>,
+}
+
+impl CollectionStatistics {
+    /// The estimated number of distinct values of `column`, if known.
+    ///
+    /// The estimate is capped at the number of rows in the collection.
+    pub fn distinct(&self, column: usize) -> Option<usize> {
+        self.distinct_values
+            .get(column)
+            .map(|distinct| std::cmp::min(*distinct, self.num_rows))
+    }
+}
+
+/// Accumulates the updates of a collection, or of a sample of them, into
+/// [`CollectionStatistics`].
+///
+/// Updates may be presented in any order and need not be consolidated.
+/// Retractions reduce the row count, but cannot be removed from the distinct
+/// value sketches, which therefore over-estimate in their presence.
+#[derive(Clone, Debug)]
+pub struct CollectionStatisticsBuilder {
+    num_updates: usize,
+    num_inserts: usize,
+    num_rows: Diff,
+    sketches: Vec<DistinctSketch>,
+}
+
+impl CollectionStatisticsBuilder {
+    /// Creates a builder for a collection with `arity` columns.
+    pub fn new(arity: usize) -> Self {
+        CollectionStatisticsBuilder {
+            num_updates: 0,
+            num_inserts: 0,
+            num_rows: 0,
+            sketches: (0..arity).map(|_| DistinctSketch::new()).collect(),
+        }
+    }
+
+    /// Accounts for `row` with multiplicity `diff`.
+    pub fn add(&mut self, row: &Row, diff: Diff) {
+        self.num_updates += 1;
+        self.num_rows += diff;
+        if diff > 0 {
+            self.num_inserts += 1;
+            for (datum, sketch) in row.iter().zip(self.sketches.iter_mut()) {
+                sketch.insert(hash(&datum));
+            }
+        }
+    }
+
+    /// Produces the statistics of all updates added so far.
+    pub fn build(&self) -> CollectionStatistics {
+        self.build_from_sample(self.num_updates)
+    }
+
+    /// Produces the statistics of a collection with `total_updates` updates,
+    /// of which the updates added so far are a sample.
+    ///
+    /// The row count is scaled up by the fraction of updates that were
+    /// sampled. Distinct value counts are scaled up likewise only for columns
+    /// that are (nearly) unique in the sample, as the values of other columns
+    /// are likely to repeat outside of the sample.
+    pub fn build_from_sample(&self, total_updates: usize) -> CollectionStatistics {
+        let scale = if self.num_updates == 0 {
+            1.0
+        } else {
+            total_updates as f64 / self.num_updates as f64
+        };
+        let scaled = |n: f64| (n * scale).round() as usize;
+        CollectionStatistics {
+            num_rows: usize::try_from(self.num_rows)
+                .map(|n| scaled(n as f64))
+                .unwrap_or(0),
+            distinct_values: self
+                .sketches
+                .iter()
+                .map(|s| {
+                    let distinct = s.estimate();
+                    if distinct * 10 >= self.num_inserts * 9 {
+                        scaled(distinct as f64)
+                    } else {
+                        distinct
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The number of bits of a hash used to select a register.
+const SKETCH_PRECISION: u32 = 10;
+/// The number of registers in a sketch.
+const SKETCH_REGISTERS: usize = 1 << SKETCH_PRECISION;
+
+/// A HyperLogLog sketch estimating the number of distinct hashes inserted.
+///
+/// With 1024 registers the standard error of the estimate is about 3%.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistinctSketch {
+    registers: Vec<u8>,
+}
+
+impl Default for DistinctSketch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DistinctSketch {
+    /// Creates an empty sketch.
+    pub fn new() -> Self {
+        DistinctSketch {
+            registers: vec![0; SKETCH_REGISTERS],
+        }
+    }
+
+    /// Records the presence of a value with hash `hash`.
+    pub fn insert(&mut self, hash: u64) {
+        let index = usize::try_from(hash >> (64 - SKETCH_PRECISION)).expect("fits in usize");
+        let rest = hash << SKETCH_PRECISION;
+        // The position of the leftmost one bit in the remaining bits, capped
+        // by their width should they all be zero.
+        let rank = std::cmp::min(rest.leading_zeros(), 64 - SKETCH_PRECISION) + 1;
+        let rank = u8::try_from(rank).expect("at most 65");
+        if self.registers[index] < rank {
+            self.registers[index] = rank;
+        }
+    }
+
+    /// Combines the values recorded by `other` into `self`.
+    pub fn merge(&mut self, other: &DistinctSketch) {
+        for (mine, theirs) in self.registers.iter_mut().zip(other.registers.iter()) {
+            *mine = std::cmp::max(*mine, *theirs);
+        }
+    }
+
+    /// Estimates the number of distinct values recorded.
+    pub fn estimate(&self) -> usize {
+        let m = SKETCH_REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|r| 2f64.powi(-i32::from(*r)))
+            .sum();
+        let raw = alpha * m * m / sum;
+        let zeros = self.registers.iter().filter(|r| **r == 0).count();
+        let estimate = if raw <= 2.5 * m && zeros > 0 {
+            // Linear counting is more accurate for small cardinalities.
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        };
+        estimate.round() as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Datum;
+
+    use super::*;
+
+    #[test]
+    fn test_distinct_sketch() {
+        for n in [0u64, 1, 10, 100, 1_000, 10_000, 100_000] {
+            let mut sketch = DistinctSketch::new();
+            for i in 0..n {
+                // Inserting each value twice must not change the estimate.
+                sketch.insert(hash(&i));
+                sketch.insert(hash(&i));
+            }
+            let estimate = u64::try_from(sketch.estimate()).unwrap();
+            let error = estimate.abs_diff(n);
+            assert!(error <= n / 10, "estimated {} for {}", estimate, n);
+        }
+    }
+
+    #[test]
+    fn test_distinct_sketch_merge() {
+        let mut a = DistinctSketch::new();
+        let mut b = DistinctSketch::new();
+        for i in 0..5_000u64 {
+            a.insert(hash(&i));
+            b.insert(hash(&(i + 2_500)));
+        }
+        a.merge(&b);
+        let estimate = a.estimate();
+        assert!(
+            (6_750..=8_250).contains(&estimate),
+            "estimated {}",
+            estimate
+        );
+    }
+
+    #[test]
+    fn test_collection_statistics() {
+        let mut builder = CollectionStatisticsBuilder::new(2);
+        for i in 0..1_000i64 {
+            let row = Row::pack_slice(&[Datum::Int64(i), Datum::Int64(i % 10)]);
+            builder.add(&row, 1);
+        }
+        builder.add(&Row::pack_slice(&[Datum::Int64(0), Datum::Int64(0)]), -1);
+        let stats = builder.build();
+        assert_eq!(stats.num_rows, 999);
+        let distinct = stats.distinct(0).unwrap();
+        assert!((900..=999).contains(&distinct), "estimated {}", distinct);
+        let distinct = stats.distinct(1).unwrap();
+        assert!((9..=11).contains(&distinct), "estimated {}", distinct);
+        assert_eq!(stats.distinct(2), None);
+
+        // Statistics from a tenth of the collection's updates are
+        // extrapolated, except for the distinct values of columns that
+        // repeat within the sample.
+        let stats = builder.build_from_sample(10_010);
+        assert!(
+            (9_900..=10_100).contains(&stats.num_rows),
+            "estimated {}",
+            stats.num_rows
+        );
+        let distinct = stats.distinct(0).unwrap();
+        assert!(
+            (9_000..=10_000).contains(&distinct),
+            "estimated {}",
+            distinct
+        );
+        let distinct = stats.distinct(1).unwrap();
+        assert!((9..=11).contains(&distinct), "estimated {}", distinct);
+    }
+}
@@ -340,6 +340,18 @@ pub enum Format<T: AstInfo> {
     },
     Json,
     Text,
+    /// `PARQUET [USING SCHEMA '...']`: whole Parquet objects. The schema is an
+    /// Avro schema describing the columns to read, and is inferred during
+    /// purification if not specified.
+    Parquet {
+        schema: Option<String>,
+    },
+    /// `AVRO OCF [USING SCHEMA '...']`: whole Avro object container files. The
+    /// schema is the reader schema, and is inferred during purification if not
+    /// specified.
+    AvroOcf {
+        schema: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -484,6 +496,22 @@ impl<T: AstInfo> AstDisplay for Format<T> {
             }
             Self::Json => f.write_str("JSON"),
             Self::Text => f.write_str("TEXT"),
+            Self::Parquet { schema } => {
+                f.write_str("PARQUET");
+                if let Some(schema) = schema {
+                    f.write_str(" USING SCHEMA '");
+                    f.write_node(&display::escape_single_quote_string(schema));
+                    f.write_str("'");
+                }
+            }
+            Self::AvroOcf { schema } => {
+                f.write_str("AVRO OCF");
+                if let Some(schema) = schema {
+                    f.write_str(" USING SCHEMA '");
+                    f.write_node(&display::escape_single_quote_string(schema));
+                    f.write_str("'");
+                }
+            }
         }
     }
 }
@@ -492,6 +520,8 @@ impl_display_t!(Format);
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Compression {
     Gzip,
+    Zstd,
+    Bzip2,
     None,
 }
 
@@ -499,6 +529,8 @@ impl AstDisplay for Compression {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
             Self::Gzip => f.write_str("GZIP"),
+            Self::Zstd => f.write_str("ZSTD"),
+            Self::Bzip2 => f.write_str("BZIP2"),
             Self::None => f.write_str("NONE"),
         }
     }
@@ -874,6 +906,8 @@ pub enum KafkaConfigOptionName {
     StatisticsIntervalMs,
     Topic,
     TopicMetadataRefreshIntervalMs,
+    TopicPattern,
+    Topics,
     TransactionTimeoutMs,
     StartTimestamp,
     StartOffset,
@@ -897,6 +931,8 @@ impl AstDisplay for KafkaConfigOptionName {
             KafkaConfigOptionName::TopicMetadataRefreshIntervalMs => {
                 "TOPIC METADATA REFRESH INTERVAL MS"
             }
+            KafkaConfigOptionName::TopicPattern => "TOPIC PATTERN",
+            KafkaConfigOptionName::Topics => "TOPICS",
             KafkaConfigOptionName::TransactionTimeoutMs => "TRANSACTION TIMEOUT MS",
             KafkaConfigOptionName::StartOffset => "START OFFSET",
             KafkaConfigOptionName::StartTimestamp => "START TIMESTAMP",
@@ -1095,6 +1131,7 @@ pub enum LoadGenerator {
     Auction,
     Datums,
     Tpch,
+    KeyValue,
 }
 
 impl AstDisplay for LoadGenerator {
@@ -1104,6 +1141,7 @@ impl AstDisplay for LoadGenerator {
             Self::Auction => f.write_str("AUCTION"),
             Self::Datums => f.write_str("DATUMS"),
             Self::Tpch => f.write_str("TPCH"),
+            Self::KeyValue => f.write_str("KEY VALUE"),
         }
     }
 }
@@ -1113,6 +1151,12 @@ impl_display!(LoadGenerator);
 pub enum LoadGeneratorOptionName {
     ScaleFactor,
     TickInterval,
+    Keys,
+    SnapshotRounds,
+    ValueSize,
+    BatchSize,
+    Partitions,
+    Seed,
 }
 
 impl AstDisplay for LoadGeneratorOptionName {
@@ -1120,6 +1164,12 @@ impl AstDisplay for LoadGeneratorOptionName {
         f.write_str(match self {
             LoadGeneratorOptionName::ScaleFactor => "SCALE FACTOR",
             LoadGeneratorOptionName::TickInterval => "TICK INTERVAL",
+            LoadGeneratorOptionName::Keys => "KEYS",
+            LoadGeneratorOptionName::SnapshotRounds => "SNAPSHOT ROUNDS",
+            LoadGeneratorOptionName::ValueSize => "VALUE SIZE",
+            LoadGeneratorOptionName::BatchSize => "BATCH SIZE",
+            LoadGeneratorOptionName::Partitions => "PARTITIONS",
+            LoadGeneratorOptionName::Seed => "SEED",
         })
     }
 }
@@ -230,7 +230,7 @@ pub struct Select<T: AstInfo> {
     /// WHERE
     pub selection: Option<Expr<T>>,
     /// GROUP BY
-    pub group_by: Vec<Expr<T>>,
+    pub group_by: Vec<GroupByExpr<T>>,
     /// HAVING
     pub having: Option<Expr<T>>,
     /// OPTION
@@ -290,6 +290,51 @@ impl<T: AstInfo> Select<T> {
     }
 }
 
+/// An element of a `GROUP BY` clause.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GroupByExpr<T: AstInfo> {
+    /// A plain grouping expression.
+    Expr(Expr<T>),
+    /// A parenthesized list of grouping expressions, like `(a, b)` or `()`,
+    /// which is treated as a single unit when forming grouping sets.
+    List(Vec<Expr<T>>),
+    /// `ROLLUP (<element>, ...)`
+    Rollup(Vec<GroupByExpr<T>>),
+    /// `CUBE (<element>, ...)`
+    Cube(Vec<GroupByExpr<T>>),
+    /// `GROUPING SETS (<element>, ...)`
+    GroupingSets(Vec<GroupByExpr<T>>),
+}
+
+impl<T: AstInfo> AstDisplay for GroupByExpr<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            GroupByExpr::Expr(expr) => f.write_node(expr),
+            GroupByExpr::List(exprs) => {
+                f.write_str("(");
+                f.write_node(&display::comma_separated(exprs));
+                f.write_str(")");
+            }
+            GroupByExpr::Rollup(elements) => {
+                f.write_str("ROLLUP (");
+                f.write_node(&display::comma_separated(elements));
+                f.write_str(")");
+            }
+            GroupByExpr::Cube(elements) => {
+                f.write_str("CUBE (");
+                f.write_node(&display::comma_separated(elements));
+                f.write_str(")");
+            }
+            GroupByExpr::GroupingSets(elements) => {
+                f.write_str("GROUPING SETS (");
+                f.write_node(&display::comma_separated(elements));
+                f.write_str(")");
+            }
+        }
+    }
+}
+impl_display_t!(GroupByExpr);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Distinct<T: AstInfo> {
     EntireRow,
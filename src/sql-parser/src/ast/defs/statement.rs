@@ -29,10 +29,10 @@ use enum_kinds::EnumKind;
 use crate::ast::display::{self, AstDisplay, AstFormatter};
 use crate::ast::{
     AstInfo, ColumnDef, CreateConnection, CreateSinkConnection, CreateSourceConnection,
-    CreateSourceFormat, CreateSourceOption, CreateSourceOptionName, DeferredObjectName, Envelope,
-    Expr, Format, Ident, KeyConstraint, Query, SelectItem, SourceIncludeMetadata, TableAlias,
-    TableConstraint, TableWithJoins, UnresolvedDatabaseName, UnresolvedObjectName,
-    UnresolvedSchemaName, Value,
+    CreateSourceFormat, CreateSourceOption, CreateSourceOptionName, CsrSeedAvro,
+    DeferredObjectName, Envelope, Expr, Format, Ident, KeyConstraint, Query, SelectItem,
+    SourceIncludeMetadata, TableAlias, TableConstraint, TableFactor, TableWithJoins,
+    UnresolvedDatabaseName, UnresolvedObjectName, UnresolvedSchemaName, Value,
 };
 
 /// A top-level statement (SELECT, INSERT, CREATE, etc.)
@@ -45,6 +45,7 @@ pub enum Statement<T: AstInfo> {
     Copy(CopyStatement<T>),
     Update(UpdateStatement<T>),
     Delete(DeleteStatement<T>),
+    Merge(MergeStatement<T>),
     CreateConnection(CreateConnectionStatement<T>),
     CreateDatabase(CreateDatabaseStatement),
     CreateSchema(CreateSchemaStatement),
@@ -56,15 +57,20 @@ pub enum Statement<T: AstInfo> {
     CreateTable(CreateTableStatement<T>),
     CreateIndex(CreateIndexStatement<T>),
     CreateType(CreateTypeStatement<T>),
+    CreateFunction(CreateFunctionStatement<T>),
     CreateRole(CreateRoleStatement),
     CreateCluster(CreateClusterStatement<T>),
     CreateClusterReplica(CreateClusterReplicaStatement<T>),
     CreateSecret(CreateSecretStatement<T>),
+    CreateSubscription(CreateSubscriptionStatement<T>),
     AlterObjectRename(AlterObjectRenameStatement),
+    AlterObjectSwap(AlterObjectSwapStatement),
     AlterIndex(AlterIndexStatement<T>),
     AlterSecret(AlterSecretStatement<T>),
+    AlterSubscription(AlterSubscriptionStatement),
     AlterSink(AlterSinkStatement<T>),
     AlterSource(AlterSourceStatement<T>),
+    AlterTable(AlterTableStatement<T>),
     AlterSystemSet(AlterSystemSetStatement),
     AlterSystemReset(AlterSystemResetStatement),
     AlterSystemResetAll(AlterSystemResetAllStatement),
@@ -102,6 +108,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::Copy(stmt) => f.write_node(stmt),
             Statement::Update(stmt) => f.write_node(stmt),
             Statement::Delete(stmt) => f.write_node(stmt),
+            Statement::Merge(stmt) => f.write_node(stmt),
             Statement::CreateConnection(stmt) => f.write_node(stmt),
             Statement::CreateDatabase(stmt) => f.write_node(stmt),
             Statement::CreateSchema(stmt) => f.write_node(stmt),
@@ -114,14 +121,19 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::CreateIndex(stmt) => f.write_node(stmt),
             Statement::CreateRole(stmt) => f.write_node(stmt),
             Statement::CreateSecret(stmt) => f.write_node(stmt),
+            Statement::CreateSubscription(stmt) => f.write_node(stmt),
             Statement::CreateType(stmt) => f.write_node(stmt),
+            Statement::CreateFunction(stmt) => f.write_node(stmt),
             Statement::CreateCluster(stmt) => f.write_node(stmt),
             Statement::CreateClusterReplica(stmt) => f.write_node(stmt),
             Statement::AlterObjectRename(stmt) => f.write_node(stmt),
+            Statement::AlterObjectSwap(stmt) => f.write_node(stmt),
             Statement::AlterIndex(stmt) => f.write_node(stmt),
             Statement::AlterSecret(stmt) => f.write_node(stmt),
+            Statement::AlterSubscription(stmt) => f.write_node(stmt),
             Statement::AlterSink(stmt) => f.write_node(stmt),
             Statement::AlterSource(stmt) => f.write_node(stmt),
+            Statement::AlterTable(stmt) => f.write_node(stmt),
             Statement::AlterSystemSet(stmt) => f.write_node(stmt),
             Statement::AlterSystemReset(stmt) => f.write_node(stmt),
             Statement::AlterSystemResetAll(stmt) => f.write_node(stmt),
@@ -181,6 +193,8 @@ pub struct InsertStatement<T: AstInfo> {
     pub columns: Vec<Ident>,
     /// A SQL query that specifies what to insert.
     pub source: InsertSource<T>,
+    /// ON CONFLICT
+    pub on_conflict: Option<OnConflict<T>>,
     /// RETURNING
     pub returning: Vec<SelectItem<T>>,
 }
@@ -196,6 +210,10 @@ impl<T: AstInfo> AstDisplay for InsertStatement<T> {
         }
         f.write_str(" ");
         f.write_node(&self.source);
+        if let Some(on_conflict) = &self.on_conflict {
+            f.write_str(" ");
+            f.write_node(on_conflict);
+        }
         if !self.returning.is_empty() {
             f.write_str(" RETURNING ");
             f.write_node(&display::comma_separated(&self.returning));
@@ -204,6 +222,60 @@ impl<T: AstInfo> AstDisplay for InsertStatement<T> {
 }
 impl_display_t!(InsertStatement);
 
+/// `ON CONFLICT [(<columns>)] DO ...`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnConflict<T: AstInfo> {
+    /// The columns that identify a conflicting row. If empty, any of the
+    /// table's keys identify a conflicting row.
+    pub target: Vec<Ident>,
+    pub action: OnConflictAction<T>,
+}
+
+impl<T: AstInfo> AstDisplay for OnConflict<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ON CONFLICT");
+        if !self.target.is_empty() {
+            f.write_str(" (");
+            f.write_node(&display::comma_separated(&self.target));
+            f.write_str(")");
+        }
+        f.write_str(" ");
+        f.write_node(&self.action);
+    }
+}
+impl_display_t!(OnConflict);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OnConflictAction<T: AstInfo> {
+    /// `DO NOTHING`
+    DoNothing,
+    /// `DO UPDATE SET ... [WHERE ...]`
+    DoUpdate {
+        assignments: Vec<Assignment<T>>,
+        selection: Option<Expr<T>>,
+    },
+}
+
+impl<T: AstInfo> AstDisplay for OnConflictAction<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            OnConflictAction::DoNothing => f.write_str("DO NOTHING"),
+            OnConflictAction::DoUpdate {
+                assignments,
+                selection,
+            } => {
+                f.write_str("DO UPDATE SET ");
+                f.write_node(&display::comma_separated(assignments));
+                if let Some(selection) = selection {
+                    f.write_str(" WHERE ");
+                    f.write_node(selection);
+                }
+            }
+        }
+    }
+}
+impl_display_t!(OnConflictAction);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CopyRelation<T: AstInfo> {
     Table {
@@ -234,14 +306,21 @@ impl_display!(CopyDirection);
 pub enum CopyTarget {
     Stdin,
     Stdout,
+    /// A URL of objects in remote object storage, like `s3://bucket/prefix`.
+    Url(String),
 }
 
 impl AstDisplay for CopyTarget {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
-        f.write_str(match self {
-            CopyTarget::Stdin => "STDIN",
-            CopyTarget::Stdout => "STDOUT",
-        })
+        match self {
+            CopyTarget::Stdin => f.write_str("STDIN"),
+            CopyTarget::Stdout => f.write_str("STDOUT"),
+            CopyTarget::Url(url) => {
+                f.write_str("'");
+                f.write_node(&display::escape_single_quote_string(url));
+                f.write_str("'");
+            }
+        }
     }
 }
 impl_display!(CopyTarget);
@@ -254,6 +333,8 @@ pub enum CopyOptionName {
     Escape,
     Quote,
     Header,
+    Connection,
+    MaxFileSize,
 }
 
 impl AstDisplay for CopyOptionName {
@@ -265,6 +346,8 @@ impl AstDisplay for CopyOptionName {
             CopyOptionName::Escape => "ESCAPE",
             CopyOptionName::Quote => "QUOTE",
             CopyOptionName::Header => "HEADER",
+            CopyOptionName::Connection => "CONNECTION",
+            CopyOptionName::MaxFileSize => "MAX FILE SIZE",
         })
     }
 }
@@ -394,6 +477,106 @@ impl<T: AstInfo> AstDisplay for DeleteStatement<T> {
 }
 impl_display_t!(DeleteStatement);
 
+/// `MERGE`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MergeStatement<T: AstInfo> {
+    /// `INTO`
+    pub table_name: T::ObjectName,
+    /// `AS`
+    pub alias: Option<TableAlias>,
+    /// `USING`
+    pub using: TableFactor<T>,
+    /// `ON`
+    pub on: Expr<T>,
+    /// `WHEN ...`, in the order in which they are tried.
+    pub clauses: Vec<MergeClause<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for MergeStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("MERGE INTO ");
+        f.write_node(&self.table_name);
+        if let Some(alias) = &self.alias {
+            f.write_str(" AS ");
+            f.write_node(alias);
+        }
+        f.write_str(" USING ");
+        f.write_node(&self.using);
+        f.write_str(" ON ");
+        f.write_node(&self.on);
+        for clause in &self.clauses {
+            f.write_str(" ");
+            f.write_node(clause);
+        }
+    }
+}
+impl_display_t!(MergeStatement);
+
+/// `WHEN [NOT] MATCHED [AND <condition>] THEN <action>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MergeClause<T: AstInfo> {
+    pub matched: bool,
+    pub condition: Option<Expr<T>>,
+    pub action: MergeAction<T>,
+}
+
+impl<T: AstInfo> AstDisplay for MergeClause<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("WHEN ");
+        if !self.matched {
+            f.write_str("NOT ");
+        }
+        f.write_str("MATCHED");
+        if let Some(condition) = &self.condition {
+            f.write_str(" AND ");
+            f.write_node(condition);
+        }
+        f.write_str(" THEN ");
+        f.write_node(&self.action);
+    }
+}
+impl_display_t!(MergeClause);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MergeAction<T: AstInfo> {
+    /// `UPDATE SET ...`; only valid for matched rows.
+    Update { assignments: Vec<Assignment<T>> },
+    /// `DELETE`; only valid for matched rows.
+    Delete,
+    /// `INSERT [(<columns>)] VALUES (...)`; only valid for unmatched rows.
+    Insert {
+        columns: Vec<Ident>,
+        values: Vec<Expr<T>>,
+    },
+    /// `DO NOTHING`
+    DoNothing,
+}
+
+impl<T: AstInfo> AstDisplay for MergeAction<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            MergeAction::Update { assignments } => {
+                f.write_str("UPDATE SET ");
+                f.write_node(&display::comma_separated(assignments));
+            }
+            MergeAction::Delete => f.write_str("DELETE"),
+            MergeAction::Insert { columns, values } => {
+                f.write_str("INSERT ");
+                if !columns.is_empty() {
+                    f.write_str("(");
+                    f.write_node(&display::comma_separated(columns));
+                    f.write_str(") ");
+                }
+                f.write_str("VALUES (");
+                f.write_node(&display::comma_separated(values));
+                f.write_str(")");
+            }
+            MergeAction::DoNothing => f.write_str("DO NOTHING"),
+        }
+    }
+}
+impl_display_t!(MergeAction);
+
 /// `CREATE DATABASE`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateDatabaseStatement {
@@ -839,6 +1022,7 @@ pub struct CreateTableStatement<T: AstInfo> {
     pub constraints: Vec<TableConstraint<T>>,
     pub if_not_exists: bool,
     pub temporary: bool,
+    pub with_options: Vec<TableOption<T>>,
 }
 
 impl<T: AstInfo> AstDisplay for CreateTableStatement<T> {
@@ -859,10 +1043,48 @@ impl<T: AstInfo> AstDisplay for CreateTableStatement<T> {
             f.write_node(&display::comma_separated(&self.constraints));
         }
         f.write_str(")");
+        if !self.with_options.is_empty() {
+            f.write_str(" WITH (");
+            f.write_node(&display::comma_separated(&self.with_options));
+            f.write_str(")");
+        }
     }
 }
 impl_display_t!(CreateTableStatement);
 
+/// An option in a `CREATE TABLE` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TableOptionName {
+    /// The persist shard of which the table's contents are a fork.
+    ForkShard,
+}
+
+impl AstDisplay for TableOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            TableOptionName::ForkShard => {
+                f.write_str("FORK SHARD");
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TableOption<T: AstInfo> {
+    pub name: TableOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for TableOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+
 /// `CREATE INDEX`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateIndexStatement<T: AstInfo> {
@@ -1020,6 +1242,174 @@ impl<T: AstInfo> AstDisplay for CreateSecretStatement<T> {
 }
 impl_display_t!(CreateSecretStatement);
 
+/// A `CREATE SUBSCRIPTION` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateSubscriptionStatement<T: AstInfo> {
+    pub name: UnresolvedObjectName,
+    pub if_not_exists: bool,
+    /// The object whose changes the subscription follows.
+    pub from: T::ObjectName,
+    pub with_options: Vec<CreateSubscriptionOption<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for CreateSubscriptionStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("CREATE SUBSCRIPTION ");
+        if self.if_not_exists {
+            f.write_str("IF NOT EXISTS ");
+        }
+        f.write_node(&self.name);
+        f.write_str(" FOR ");
+        f.write_node(&self.from);
+        if !self.with_options.is_empty() {
+            f.write_str(" WITH (");
+            f.write_node(&display::comma_separated(&self.with_options));
+            f.write_str(")");
+        }
+    }
+}
+impl_display_t!(CreateSubscriptionStatement);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CreateSubscriptionOptionName {
+    Snapshot,
+    Timestamp,
+}
+
+impl AstDisplay for CreateSubscriptionOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            CreateSubscriptionOptionName::Snapshot => f.write_str("SNAPSHOT"),
+            CreateSubscriptionOptionName::Timestamp => f.write_str("TIMESTAMP"),
+        }
+    }
+}
+impl_display!(CreateSubscriptionOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateSubscriptionOption<T: AstInfo> {
+    pub name: CreateSubscriptionOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for CreateSubscriptionOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(CreateSubscriptionOption);
+
+/// `CREATE FUNCTION ..`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateFunctionStatement<T: AstInfo> {
+    /// Name of the created function.
+    pub name: UnresolvedObjectName,
+    /// The function's parameters.
+    pub args: Vec<CreateFunctionArg<T>>,
+    /// The function's return type.
+    pub returns: CreateFunctionReturns<T>,
+    /// The language in which the function body is written.
+    pub language: Ident,
+    /// The function body.
+    pub body: CreateFunctionBody<T>,
+}
+
+impl<T: AstInfo> AstDisplay for CreateFunctionStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("CREATE FUNCTION ");
+        f.write_node(&self.name);
+        f.write_str("(");
+        f.write_node(&display::comma_separated(&self.args));
+        f.write_str(") RETURNS ");
+        f.write_node(&self.returns);
+        f.write_str(" LANGUAGE ");
+        f.write_node(&self.language);
+        f.write_str(" AS ");
+        f.write_node(&self.body);
+    }
+}
+impl_display_t!(CreateFunctionStatement);
+
+/// A parameter in a `CREATE FUNCTION` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateFunctionArg<T: AstInfo> {
+    /// The optional name of the parameter.
+    pub name: Option<Ident>,
+    /// The type of the parameter.
+    pub data_type: T::DataType,
+}
+
+impl<T: AstInfo> AstDisplay for CreateFunctionArg<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        if let Some(name) = &self.name {
+            f.write_node(name);
+            f.write_str(" ");
+        }
+        f.write_node(&self.data_type);
+    }
+}
+impl_display_t!(CreateFunctionArg);
+
+/// The return type of a `CREATE FUNCTION` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CreateFunctionReturns<T: AstInfo> {
+    /// `RETURNS <type>`
+    Scalar(T::DataType),
+    /// `RETURNS SETOF <type>`
+    SetOf(T::DataType),
+    /// `RETURNS TABLE (<name> <type>, ...)`
+    Table(Vec<CreateFunctionArg<T>>),
+}
+
+impl<T: AstInfo> AstDisplay for CreateFunctionReturns<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            CreateFunctionReturns::Scalar(data_type) => f.write_node(data_type),
+            CreateFunctionReturns::SetOf(data_type) => {
+                f.write_str("SETOF ");
+                f.write_node(data_type);
+            }
+            CreateFunctionReturns::Table(columns) => {
+                f.write_str("TABLE (");
+                f.write_node(&display::comma_separated(columns));
+                f.write_str(")");
+            }
+        }
+    }
+}
+impl_display_t!(CreateFunctionReturns);
+
+/// The body of a `CREATE FUNCTION` statement.
+///
+/// The body is written as a string literal, but is parsed eagerly so that the
+/// objects it references can be resolved like those in any other statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CreateFunctionBody<T: AstInfo> {
+    /// The body of a function that returns a single value.
+    Expr(Expr<T>),
+    /// The body of a function that returns a set of rows.
+    Query(Query<T>),
+}
+
+impl<T: AstInfo> AstDisplay for CreateFunctionBody<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        let body = match (self, f.stable()) {
+            (CreateFunctionBody::Expr(expr), false) => expr.to_ast_string(),
+            (CreateFunctionBody::Expr(expr), true) => expr.to_ast_string_stable(),
+            (CreateFunctionBody::Query(query), false) => query.to_ast_string(),
+            (CreateFunctionBody::Query(query), true) => query.to_ast_string_stable(),
+        };
+        f.write_str("'");
+        f.write_node(&display::escape_single_quote_string(&body));
+        f.write_str("'");
+    }
+}
+impl_display_t!(CreateFunctionBody);
+
 /// `CREATE TYPE ..`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateTypeStatement<T: AstInfo> {
@@ -1314,6 +1704,26 @@ impl AstDisplay for AlterObjectRenameStatement {
 }
 impl_display!(AlterObjectRenameStatement);
 
+/// `ALTER <OBJECT> ... SWAP WITH`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterObjectSwapStatement {
+    pub object_type: ObjectType,
+    pub name_a: UnresolvedObjectName,
+    pub name_b: Ident,
+}
+
+impl AstDisplay for AlterObjectSwapStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER ");
+        f.write_node(&self.object_type);
+        f.write_str(" ");
+        f.write_node(&self.name_a);
+        f.write_str(" SWAP WITH ");
+        f.write_node(&self.name_b);
+    }
+}
+impl_display!(AlterObjectSwapStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AlterIndexAction<T: AstInfo> {
     SetOptions(Vec<IndexOption<T>>),
@@ -1395,6 +1805,11 @@ impl<T: AstInfo> AstDisplay for AlterSinkStatement<T> {
 pub enum AlterSourceAction<T: AstInfo> {
     SetOptions(Vec<CreateSourceOption<T>>),
     ResetOptions(Vec<CreateSourceOptionName>),
+    /// Switches the source to a newer schema from the schema registry. The
+    /// seed is filled in with the latest schemas during purification.
+    RefreshSchema {
+        seed: Option<CsrSeedAvro>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1424,12 +1839,72 @@ impl<T: AstInfo> AstDisplay for AlterSourceStatement<T> {
                 f.write_node(&display::comma_separated(options));
                 f.write_str(")");
             }
+            AlterSourceAction::RefreshSchema { seed } => {
+                f.write_str("REFRESH SCHEMA");
+                if let Some(seed) = seed {
+                    f.write_str(" ");
+                    f.write_node(seed);
+                }
+            }
         }
     }
 }
 
 impl_display_t!(AlterSourceStatement);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AlterTableAction<T: AstInfo> {
+    AddColumn {
+        if_not_exists: bool,
+        column_def: ColumnDef<T>,
+    },
+    DropColumn {
+        if_exists: bool,
+        name: Ident,
+    },
+}
+
+/// `ALTER TABLE ... {ADD, DROP} COLUMN`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterTableStatement<T: AstInfo> {
+    pub table_name: UnresolvedObjectName,
+    pub if_exists: bool,
+    pub action: AlterTableAction<T>,
+}
+
+impl<T: AstInfo> AstDisplay for AlterTableStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER TABLE ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.table_name);
+        f.write_str(" ");
+
+        match &self.action {
+            AlterTableAction::AddColumn {
+                if_not_exists,
+                column_def,
+            } => {
+                f.write_str("ADD COLUMN ");
+                if *if_not_exists {
+                    f.write_str("IF NOT EXISTS ");
+                }
+                f.write_node(column_def);
+            }
+            AlterTableAction::DropColumn { if_exists, name } => {
+                f.write_str("DROP COLUMN ");
+                if *if_exists {
+                    f.write_str("IF EXISTS ");
+                }
+                f.write_node(name);
+            }
+        }
+    }
+}
+
+impl_display_t!(AlterTableStatement);
+
 /// `ALTER SECRET ... AS`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AlterSecretStatement<T: AstInfo> {
@@ -1452,6 +1927,29 @@ impl<T: AstInfo> AstDisplay for AlterSecretStatement<T> {
 
 impl_display_t!(AlterSecretStatement);
 
+/// `ALTER SUBSCRIPTION ... ACKNOWLEDGE`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterSubscriptionStatement {
+    pub name: UnresolvedObjectName,
+    pub if_exists: bool,
+    /// The timestamp through which the subscriber has processed all updates.
+    pub timestamp: u64,
+}
+
+impl AstDisplay for AlterSubscriptionStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER SUBSCRIPTION ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.name);
+        f.write_str(" ACKNOWLEDGE ");
+        f.write_str(self.timestamp);
+    }
+}
+
+impl_display!(AlterSubscriptionStatement);
+
 /// `ALTER CONNECTION ... ROTATE KEYS`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AlterConnectionStatement {
@@ -1781,9 +2279,12 @@ impl<T: AstInfo> AstDisplay for ShowObjectsStatement<T> {
             ObjectType::Role => "ROLES",
             ObjectType::Cluster => "CLUSTERS",
             ObjectType::ClusterReplica => "CLUSTER REPLICAS",
+            ObjectType::Schema => "SCHEMAS",
             ObjectType::Object => "OBJECTS",
             ObjectType::Secret => "SECRETS",
             ObjectType::Connection => "CONNECTIONS",
+            ObjectType::Func => "FUNCTIONS",
+            ObjectType::Subscription => "SUBSCRIPTIONS",
             ObjectType::Index => unreachable!(),
         });
         if let Some(from) = &self.from {
@@ -2150,9 +2651,12 @@ pub enum ObjectType {
     Role,
     Cluster,
     ClusterReplica,
+    Schema,
     Object,
     Secret,
     Connection,
+    Func,
+    Subscription,
 }
 
 impl AstDisplay for ObjectType {
@@ -2168,9 +2672,12 @@ impl AstDisplay for ObjectType {
             ObjectType::Role => "ROLE",
             ObjectType::Cluster => "CLUSTER",
             ObjectType::ClusterReplica => "CLUSTER REPLICA",
+            ObjectType::Schema => "SCHEMA",
             ObjectType::Object => "OBJECT",
             ObjectType::Secret => "SECRET",
             ObjectType::Connection => "CONNECTION",
+            ObjectType::Func => "FUNCTION",
+            ObjectType::Subscription => "SUBSCRIPTION",
         })
     }
 }
@@ -2361,6 +2868,9 @@ pub enum ExplainStage {
     Trace,
     /// The dependent and selected timestamps
     Timestamp,
+    /// The runtime cost of each node of the mz_compute_client::plan::Plan
+    /// of a running dataflow
+    Analyze,
 }
 
 impl ExplainStage {
@@ -2375,6 +2885,7 @@ impl ExplainStage {
             ExplainStage::PhysicalPlan => "optimize/mir_to_lir",
             ExplainStage::Trace => unreachable!(),
             ExplainStage::Timestamp => unreachable!(),
+            ExplainStage::Analyze => unreachable!(),
         }
     }
 }
@@ -2390,6 +2901,7 @@ impl AstDisplay for ExplainStage {
             ExplainStage::PhysicalPlan => f.write_str("PHYSICAL PLAN"),
             ExplainStage::Trace => f.write_str("OPTIMIZER TRACE"),
             ExplainStage::Timestamp => f.write_str("TIMESTAMP"),
+            ExplainStage::Analyze => f.write_str("ANALYZE"),
         }
     }
 }
@@ -2399,6 +2911,7 @@ impl_display!(ExplainStage);
 pub enum Explainee<T: AstInfo> {
     View(T::ObjectName),
     MaterializedView(T::ObjectName),
+    Index(T::ObjectName),
     Query(Query<T>),
 }
 
@@ -2419,6 +2932,10 @@ impl<T: AstInfo> AstDisplay for Explainee<T> {
                 f.write_str("MATERIALIZED VIEW ");
                 f.write_node(name);
             }
+            Self::Index(name) => {
+                f.write_str("INDEX ");
+                f.write_node(name);
+            }
             Self::Query(query) => f.write_node(query),
         }
     }
@@ -257,6 +257,7 @@ impl<'a> Parser<'a> {
                 Token::Keyword(DELETE) => Ok(self.parse_delete()?),
                 Token::Keyword(INSERT) => Ok(self.parse_insert()?),
                 Token::Keyword(UPDATE) => Ok(self.parse_update()?),
+                Token::Keyword(MERGE) => Ok(self.parse_merge()?),
                 Token::Keyword(ALTER) => Ok(self.parse_alter()?),
                 Token::Keyword(COPY) => Ok(self.parse_copy()?),
                 Token::Keyword(SET) => Ok(self.parse_set()?),
@@ -1613,6 +1614,8 @@ impl<'a> Parser<'a> {
             self.parse_create_sink()
         } else if self.peek_keyword(TYPE) {
             self.parse_create_type()
+        } else if self.peek_keyword(FUNCTION) {
+            self.parse_create_function()
         } else if self.peek_keyword(ROLE) || self.peek_keyword(USER) {
             self.parse_create_role()
         } else if self.peek_keyword(CLUSTER) {
@@ -1635,6 +1638,8 @@ impl<'a> Parser<'a> {
             self.parse_create_table()
         } else if self.peek_keyword(SECRET) {
             self.parse_create_secret()
+        } else if self.peek_keyword(SUBSCRIPTION) {
+            self.parse_create_subscription()
         } else if self.peek_keyword(CONNECTION) {
             self.parse_create_connection()
         } else if self.peek_keywords(&[MATERIALIZED, VIEW])
@@ -1654,7 +1659,7 @@ impl<'a> Parser<'a> {
             } else {
                 self.expected(
                     self.peek_pos(),
-                    "DATABASE, SCHEMA, ROLE, USER, TYPE, INDEX, SINK, SOURCE, TABLE, SECRET, [OR REPLACE] [TEMPORARY] VIEW, or [OR REPLACE] MATERIALIZED VIEW after CREATE",
+                    "DATABASE, SCHEMA, ROLE, USER, TYPE, FUNCTION, INDEX, SINK, SOURCE, TABLE, SECRET, SUBSCRIPTION, [OR REPLACE] [TEMPORARY] VIEW, or [OR REPLACE] MATERIALIZED VIEW after CREATE",
                     self.peek_token(),
                 )
             }
@@ -1683,8 +1688,18 @@ impl<'a> Parser<'a> {
 
     fn parse_format(&mut self) -> Result<Format<Raw>, ParserError> {
         let format = if self.parse_keyword(AVRO) {
-            self.expect_keyword(USING)?;
-            Format::Avro(self.parse_avro_schema()?)
+            if self.parse_keyword(OCF) {
+                Format::AvroOcf {
+                    schema: self.parse_object_format_schema()?,
+                }
+            } else {
+                self.expect_keyword(USING)?;
+                Format::Avro(self.parse_avro_schema()?)
+            }
+        } else if self.parse_keyword(PARQUET) {
+            Format::Parquet {
+                schema: self.parse_object_format_schema()?,
+            }
         } else if self.parse_keyword(PROTOBUF) {
             Format::Protobuf(self.parse_protobuf_schema()?)
         } else if self.parse_keyword(REGEX) {
@@ -1720,13 +1735,23 @@ impl<'a> Parser<'a> {
         } else {
             return self.expected(
                 self.peek_pos(),
-                "AVRO, PROTOBUF, REGEX, CSV, JSON, TEXT, or BYTES",
+                "AVRO, PROTOBUF, REGEX, CSV, JSON, TEXT, BYTES, or PARQUET",
                 self.peek_token(),
             );
         };
         Ok(format)
     }
 
+    /// Parses the optional `USING SCHEMA '...'` clause of a whole-object
+    /// format, like `PARQUET` or `AVRO OCF`.
+    fn parse_object_format_schema(&mut self) -> Result<Option<String>, ParserError> {
+        if self.parse_keywords(&[USING, SCHEMA]) {
+            Ok(Some(self.parse_literal_string()?))
+        } else {
+            Ok(None)
+        }
+    }
+
     fn parse_avro_schema(&mut self) -> Result<AvroSchema<Raw>, ParserError> {
         let avro_schema = if self.parse_keywords(&[CONFLUENT, SCHEMA, REGISTRY]) {
             let csr_connection = self.parse_csr_connection_avro()?;
@@ -1827,24 +1852,27 @@ impl<'a> Parser<'a> {
         })
     }
 
-    fn parse_csr_connection_avro(&mut self) -> Result<CsrConnectionAvro<Raw>, ParserError> {
-        let connection = self.parse_csr_connection_reference()?;
-        let seed = if self.parse_keyword(SEED) {
-            let key_schema = if self.parse_keyword(KEY) {
-                self.expect_keyword(SCHEMA)?;
-                Some(self.parse_literal_string()?)
-            } else {
-                None
-            };
-            self.expect_keywords(&[VALUE, SCHEMA])?;
-            let value_schema = self.parse_literal_string()?;
-            Some(CsrSeedAvro {
-                key_schema,
-                value_schema,
-            })
+    fn parse_csr_seed_avro(&mut self) -> Result<Option<CsrSeedAvro>, ParserError> {
+        if !self.parse_keyword(SEED) {
+            return Ok(None);
+        }
+        let key_schema = if self.parse_keyword(KEY) {
+            self.expect_keyword(SCHEMA)?;
+            Some(self.parse_literal_string()?)
         } else {
             None
         };
+        self.expect_keywords(&[VALUE, SCHEMA])?;
+        let value_schema = self.parse_literal_string()?;
+        Ok(Some(CsrSeedAvro {
+            key_schema,
+            value_schema,
+        }))
+    }
+
+    fn parse_csr_connection_avro(&mut self) -> Result<CsrConnectionAvro<Raw>, ParserError> {
+        let connection = self.parse_csr_connection_reference()?;
+        let seed = self.parse_csr_seed_avro()?;
 
         let mut parse_schema_strategy =
             |kws| -> Result<Option<ReaderSchemaSelectionStrategy>, ParserError> {
@@ -1943,8 +1971,16 @@ impl<'a> Parser<'a> {
             Compression::None
         } else if self.parse_keyword(GZIP) {
             Compression::Gzip
+        } else if self.parse_keyword(ZSTD) {
+            Compression::Zstd
+        } else if self.parse_keyword(BZIP2) {
+            Compression::Bzip2
         } else {
-            return self.expected(self.peek_pos(), "NONE or GZIP", self.peek_token());
+            return self.expected(
+                self.peek_pos(),
+                "NONE, GZIP, ZSTD, or BZIP2",
+                self.peek_token(),
+            );
         };
         Ok(compression)
     }
@@ -2145,6 +2181,7 @@ impl<'a> Parser<'a> {
             START,
             STATISTICS,
             TOPIC,
+            TOPICS,
             TRANSACTION,
         ])? {
             ACKS => KafkaConfigOptionName::Acks,
@@ -2189,10 +2226,13 @@ impl<'a> Parser<'a> {
                 if self.parse_keyword(METADATA) {
                     self.expect_keywords(&[REFRESH, INTERVAL, MS])?;
                     KafkaConfigOptionName::TopicMetadataRefreshIntervalMs
+                } else if self.parse_keyword(PATTERN) {
+                    KafkaConfigOptionName::TopicPattern
                 } else {
                     KafkaConfigOptionName::Topic
                 }
             }
+            TOPICS => KafkaConfigOptionName::Topics,
             TRANSACTION => {
                 self.expect_keywords(&[TIMEOUT, MS])?;
                 KafkaConfigOptionName::TransactionTimeoutMs
@@ -2665,11 +2705,15 @@ impl<'a> Parser<'a> {
             LOAD => {
                 self.expect_keyword(GENERATOR)?;
                 let generator =
-                    match self.expect_one_of_keywords(&[COUNTER, AUCTION, TPCH, DATUMS])? {
+                    match self.expect_one_of_keywords(&[COUNTER, AUCTION, TPCH, DATUMS, KEY])? {
                         COUNTER => LoadGenerator::Counter,
                         AUCTION => LoadGenerator::Auction,
                         TPCH => LoadGenerator::Tpch,
                         DATUMS => LoadGenerator::Datums,
+                        KEY => {
+                            self.expect_keyword(VALUE)?;
+                            LoadGenerator::KeyValue
+                        }
                         _ => unreachable!(),
                     };
                 let options = if self.consume_token(&Token::LParen) {
@@ -2705,7 +2749,9 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_load_generator_option(&mut self) -> Result<LoadGeneratorOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[SCALE, TICK])? {
+        let name = match self.expect_one_of_keywords(&[
+            SCALE, TICK, KEYS, SNAPSHOT, VALUE, BATCH, PARTITIONS, SEED,
+        ])? {
             SCALE => {
                 self.expect_keyword(FACTOR)?;
                 LoadGeneratorOptionName::ScaleFactor
@@ -2714,6 +2760,21 @@ impl<'a> Parser<'a> {
                 self.expect_keyword(INTERVAL)?;
                 LoadGeneratorOptionName::TickInterval
             }
+            KEYS => LoadGeneratorOptionName::Keys,
+            SNAPSHOT => {
+                self.expect_keyword(ROUNDS)?;
+                LoadGeneratorOptionName::SnapshotRounds
+            }
+            VALUE => {
+                self.expect_keyword(SIZE)?;
+                LoadGeneratorOptionName::ValueSize
+            }
+            BATCH => {
+                self.expect_keyword(SIZE)?;
+                LoadGeneratorOptionName::BatchSize
+            }
+            PARTITIONS => LoadGeneratorOptionName::Partitions,
+            SEED => LoadGeneratorOptionName::Seed,
             _ => unreachable!(),
         };
 
@@ -2955,6 +3016,42 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    fn parse_create_subscription(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(SUBSCRIPTION)?;
+        let if_not_exists = self.parse_if_not_exists()?;
+        let name = self.parse_object_name()?;
+        self.expect_keyword(FOR)?;
+        let from = self.parse_raw_name()?;
+        let with_options = if self.parse_keyword(WITH) {
+            self.expect_token(&Token::LParen)?;
+            let options = self.parse_comma_separated(Parser::parse_create_subscription_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+        Ok(Statement::CreateSubscription(CreateSubscriptionStatement {
+            name,
+            if_not_exists,
+            from,
+            with_options,
+        }))
+    }
+
+    fn parse_create_subscription_option(
+        &mut self,
+    ) -> Result<CreateSubscriptionOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[SNAPSHOT, TIMESTAMP])? {
+            SNAPSHOT => CreateSubscriptionOptionName::Snapshot,
+            TIMESTAMP => CreateSubscriptionOptionName::Timestamp,
+            _ => unreachable!(),
+        };
+        Ok(CreateSubscriptionOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_create_type(&mut self) -> Result<Statement<Raw>, ParserError> {
         self.expect_keyword(TYPE)?;
         let name = self.parse_object_name()?;
@@ -2991,6 +3088,120 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn parse_create_function(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(FUNCTION)?;
+        let name = self.parse_object_name()?;
+        self.expect_token(&Token::LParen)?;
+        let args = if self.consume_token(&Token::RParen) {
+            vec![]
+        } else {
+            let args = self.parse_comma_separated(Parser::parse_create_function_arg)?;
+            self.expect_token(&Token::RParen)?;
+            args
+        };
+
+        self.expect_keyword(RETURNS)?;
+        let returns = if self.parse_keyword(SETOF) {
+            CreateFunctionReturns::SetOf(self.parse_data_type()?)
+        } else if self.parse_keyword(TABLE) {
+            self.expect_token(&Token::LParen)?;
+            let columns = self.parse_comma_separated(|parser| {
+                Ok(CreateFunctionArg {
+                    name: Some(parser.parse_identifier()?),
+                    data_type: parser.parse_data_type()?,
+                })
+            })?;
+            self.expect_token(&Token::RParen)?;
+            CreateFunctionReturns::Table(columns)
+        } else {
+            CreateFunctionReturns::Scalar(self.parse_data_type()?)
+        };
+
+        // As in PostgreSQL, the `LANGUAGE` and `AS` clauses may appear in
+        // either order.
+        let mut language = None;
+        let mut body = None;
+        while language.is_none() || body.is_none() {
+            match self.expect_one_of_keywords(&[AS, LANGUAGE])? {
+                LANGUAGE if language.is_none() => language = Some(self.parse_identifier()?),
+                AS if body.is_none() => {
+                    let pos = self.peek_pos();
+                    body = Some((pos, self.parse_literal_string()?));
+                }
+                kw => {
+                    return parser_err!(
+                        self,
+                        self.peek_prev_pos(),
+                        "{} specified more than once",
+                        kw
+                    )
+                }
+            }
+        }
+        let language = language.expect("known to be present");
+        let (body_pos, body) = body.expect("known to be present");
+        let body = self.parse_create_function_body(body_pos, &body, &returns)?;
+
+        Ok(Statement::CreateFunction(CreateFunctionStatement {
+            name,
+            args,
+            returns,
+            language,
+            body,
+        }))
+    }
+
+    fn parse_create_function_arg(&mut self) -> Result<CreateFunctionArg<Raw>, ParserError> {
+        // Parameter names are optional, so first try to parse the parameter as
+        // a bare data type. This avoids misinterpreting multi-word types, like
+        // `double precision`, as a name followed by a type.
+        let index = self.index;
+        if let Ok(data_type) = self.parse_data_type() {
+            if matches!(self.peek_token(), Some(Token::Comma | Token::RParen)) {
+                return Ok(CreateFunctionArg {
+                    name: None,
+                    data_type,
+                });
+            }
+        }
+        self.index = index;
+        Ok(CreateFunctionArg {
+            name: Some(self.parse_identifier()?),
+            data_type: self.parse_data_type()?,
+        })
+    }
+
+    /// Parses the body of a `CREATE FUNCTION` statement, which was specified
+    /// as the string literal `body` at position `pos`.
+    fn parse_create_function_body(
+        &self,
+        pos: usize,
+        body: &str,
+        returns: &CreateFunctionReturns<Raw>,
+    ) -> Result<CreateFunctionBody<Raw>, ParserError> {
+        let invalid = |e: ParserError| self.error(pos, format!("invalid function body: {}", e));
+        let tokens = lexer::lex(body).map_err(invalid)?;
+        let mut parser = Parser::new(body, tokens);
+        let body = match returns {
+            CreateFunctionReturns::Scalar(_) => {
+                CreateFunctionBody::Expr(parser.parse_expr().map_err(invalid)?)
+            }
+            CreateFunctionReturns::SetOf(_) | CreateFunctionReturns::Table(_) => {
+                CreateFunctionBody::Query(parser.parse_query().map_err(invalid)?)
+            }
+        };
+        match parser.next_token() {
+            None => Ok(body),
+            Some(token) => Err(self.error(
+                pos,
+                format!(
+                    "invalid function body: expected end of body, found {}",
+                    token
+                ),
+            )),
+        }
+    }
+
     fn parse_create_type_list_option(&mut self) -> Result<CreateTypeListOption<Raw>, ParserError> {
         self.expect_keywords(&[ELEMENT, TYPE])?;
         let name = CreateTypeListOptionName::ElementType;
@@ -3151,6 +3362,7 @@ impl<'a> Parser<'a> {
             CONNECTION,
             CLUSTER,
             DATABASE,
+            FUNCTION,
             INDEX,
             MATERIALIZED,
             ROLE,
@@ -3158,6 +3370,7 @@ impl<'a> Parser<'a> {
             SCHEMA,
             SINK,
             SOURCE,
+            SUBSCRIPTION,
             TABLE,
             TYPE,
             USER,
@@ -3207,6 +3420,7 @@ impl<'a> Parser<'a> {
             INDEX => ObjectType::Index,
             SINK => ObjectType::Sink,
             SOURCE => ObjectType::Source,
+            SUBSCRIPTION => ObjectType::Subscription,
             TABLE => ObjectType::Table,
             TYPE => ObjectType::Type,
             VIEW => ObjectType::View,
@@ -3216,6 +3430,7 @@ impl<'a> Parser<'a> {
             }
             SECRET => ObjectType::Secret,
             CONNECTION => ObjectType::Connection,
+            FUNCTION => ObjectType::Func,
             _ => unreachable!(),
         };
 
@@ -3269,15 +3484,39 @@ impl<'a> Parser<'a> {
         // parse optional column list (schema)
         let (columns, constraints) = self.parse_columns(Mandatory)?;
 
+        let with_options = if self.parse_keyword(WITH) {
+            self.expect_token(&Token::LParen)?;
+            let options = self.parse_comma_separated(Parser::parse_table_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+
         Ok(Statement::CreateTable(CreateTableStatement {
             name: table_name,
             columns,
             constraints,
             if_not_exists,
             temporary,
+            with_options,
         }))
     }
 
+    /// Parse the name of a CREATE TABLE optional parameter
+    fn parse_table_option_name(&mut self) -> Result<TableOptionName, ParserError> {
+        self.expect_keywords(&[FORK, SHARD])?;
+        Ok(TableOptionName::ForkShard)
+    }
+
+    /// Parse a NAME = VALUE parameter for CREATE TABLE
+    fn parse_table_option(&mut self) -> Result<TableOption<Raw>, ParserError> {
+        Ok(TableOption {
+            name: self.parse_table_option_name()?,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_columns(
         &mut self,
         optional: IsOptional,
@@ -3305,26 +3544,7 @@ impl<'a> Parser<'a> {
             if let Some(constraint) = self.parse_optional_table_constraint()? {
                 constraints.push(constraint);
             } else if let Some(column_name) = self.consume_identifier() {
-                let data_type = self.parse_data_type()?;
-                let collation = if self.parse_keyword(COLLATE) {
-                    Some(self.parse_object_name()?)
-                } else {
-                    None
-                };
-                let mut options = vec![];
-                loop {
-                    match self.peek_token() {
-                        None | Some(Token::Comma) | Some(Token::RParen) => break,
-                        _ => options.push(self.parse_column_option_def()?),
-                    }
-                }
-
-                columns.push(ColumnDef {
-                    name: column_name,
-                    data_type,
-                    collation,
-                    options,
-                });
+                columns.push(self.parse_column_def(column_name)?);
             } else {
                 return self.expected(
                     self.peek_pos(),
@@ -3348,6 +3568,29 @@ impl<'a> Parser<'a> {
         Ok((columns, constraints))
     }
 
+    /// Parses the remainder of a column definition, after its name.
+    fn parse_column_def(&mut self, name: Ident) -> Result<ColumnDef<Raw>, ParserError> {
+        let data_type = self.parse_data_type()?;
+        let collation = if self.parse_keyword(COLLATE) {
+            Some(self.parse_object_name()?)
+        } else {
+            None
+        };
+        let mut options = vec![];
+        loop {
+            match self.peek_token() {
+                None | Some(Token::Comma) | Some(Token::RParen) | Some(Token::Semicolon) => break,
+                _ => options.push(self.parse_column_option_def()?),
+            }
+        }
+        Ok(ColumnDef {
+            name,
+            data_type,
+            collation,
+            options,
+        })
+    }
+
     fn parse_column_option_def(&mut self) -> Result<ColumnOptionDef<Raw>, ParserError> {
         let name = if self.parse_keyword(CONSTRAINT) {
             Some(self.parse_identifier()?)
@@ -3498,10 +3741,13 @@ impl<'a> Parser<'a> {
             SINK,
             SOURCE,
             VIEW,
+            SCHEMA,
+            CLUSTER,
             MATERIALIZED,
             TABLE,
             INDEX,
             SECRET,
+            SUBSCRIPTION,
             SYSTEM,
             CONNECTION,
         ])? {
@@ -3512,11 +3758,14 @@ impl<'a> Parser<'a> {
                 self.expect_keyword(VIEW)?;
                 ObjectType::MaterializedView
             }
-            TABLE => ObjectType::Table,
+            TABLE => return self.parse_alter_table(),
             INDEX => return self.parse_alter_index(),
             SECRET => return self.parse_alter_secret(),
+            SUBSCRIPTION => return self.parse_alter_subscription(),
             SYSTEM => return self.parse_alter_system(),
             CONNECTION => return self.parse_alter_connection(),
+            SCHEMA => return self.parse_alter_swap(ObjectType::Schema),
+            CLUSTER => return self.parse_alter_swap(ObjectType::Cluster),
             _ => unreachable!(),
         };
 
@@ -3534,30 +3783,103 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    fn parse_alter_swap(&mut self, object_type: ObjectType) -> Result<Statement<Raw>, ParserError> {
+        let name_a = match object_type {
+            ObjectType::Schema => self.parse_object_name()?,
+            _ => UnresolvedObjectName(vec![self.parse_identifier()?]),
+        };
+        self.expect_keywords(&[SWAP, WITH])?;
+        let name_b = self.parse_identifier()?;
+        Ok(Statement::AlterObjectSwap(AlterObjectSwapStatement {
+            object_type,
+            name_a,
+            name_b,
+        }))
+    }
+
     fn parse_alter_source(&mut self) -> Result<Statement<Raw>, ParserError> {
         let if_exists = self.parse_if_exists()?;
         let name = self.parse_object_name()?;
 
-        Ok(match self.expect_one_of_keywords(&[RESET, SET, RENAME])? {
-            RESET => {
-                self.expect_token(&Token::LParen)?;
-                let reset_options = self.parse_comma_separated(Parser::parse_source_option_name)?;
-                self.expect_token(&Token::RParen)?;
+        Ok(
+            match self.expect_one_of_keywords(&[RESET, SET, RENAME, REFRESH])? {
+                REFRESH => {
+                    self.expect_keyword(SCHEMA)?;
+                    let seed = self.parse_csr_seed_avro()?;
+                    Statement::AlterSource(AlterSourceStatement {
+                        source_name: name,
+                        if_exists,
+                        action: AlterSourceAction::RefreshSchema { seed },
+                    })
+                }
+                RESET => {
+                    self.expect_token(&Token::LParen)?;
+                    let reset_options =
+                        self.parse_comma_separated(Parser::parse_source_option_name)?;
+                    self.expect_token(&Token::RParen)?;
+
+                    Statement::AlterSource(AlterSourceStatement {
+                        source_name: name,
+                        if_exists,
+                        action: AlterSourceAction::ResetOptions(reset_options),
+                    })
+                }
+                SET => {
+                    self.expect_token(&Token::LParen)?;
+                    let set_options = self.parse_comma_separated(Parser::parse_source_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    Statement::AlterSource(AlterSourceStatement {
+                        source_name: name,
+                        if_exists,
+                        action: AlterSourceAction::SetOptions(set_options),
+                    })
+                }
+                RENAME => {
+                    self.expect_keyword(TO)?;
+                    let to_item_name = self.parse_identifier()?;
+
+                    Statement::AlterObjectRename(AlterObjectRenameStatement {
+                        object_type: ObjectType::Source,
+                        if_exists,
+                        name,
+                        to_item_name,
+                    })
+                }
+                _ => unreachable!(),
+            },
+        )
+    }
 
-                Statement::AlterSource(AlterSourceStatement {
-                    source_name: name,
+    fn parse_alter_table(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let if_exists = self.parse_if_exists()?;
+        let name = self.parse_object_name()?;
+
+        Ok(match self.expect_one_of_keywords(&[ADD, DROP, RENAME])? {
+            ADD => {
+                let _ = self.parse_keyword(COLUMN);
+                let if_not_exists = self.parse_if_not_exists()?;
+                let column_name = self.parse_identifier()?;
+                let column_def = self.parse_column_def(column_name)?;
+                Statement::AlterTable(AlterTableStatement {
+                    table_name: name,
                     if_exists,
-                    action: AlterSourceAction::ResetOptions(reset_options),
+                    action: AlterTableAction::AddColumn {
+                        if_not_exists,
+                        column_def,
+                    },
                 })
             }
-            SET => {
-                self.expect_token(&Token::LParen)?;
-                let set_options = self.parse_comma_separated(Parser::parse_source_option)?;
-                self.expect_token(&Token::RParen)?;
-                Statement::AlterSource(AlterSourceStatement {
-                    source_name: name,
+            DROP => {
+                let _ = self.parse_keyword(COLUMN);
+                let column_if_exists = self.parse_if_exists()?;
+                let column_name = self.parse_identifier()?;
+                Statement::AlterTable(AlterTableStatement {
+                    table_name: name,
                     if_exists,
-                    action: AlterSourceAction::SetOptions(set_options),
+                    action: AlterTableAction::DropColumn {
+                        if_exists: column_if_exists,
+                        name: column_name,
+                    },
                 })
             }
             RENAME => {
@@ -3565,7 +3887,7 @@ impl<'a> Parser<'a> {
                 let to_item_name = self.parse_identifier()?;
 
                 Statement::AlterObjectRename(AlterObjectRenameStatement {
-                    object_type: ObjectType::Source,
+                    object_type: ObjectType::Table,
                     if_exists,
                     name,
                     to_item_name,
@@ -3644,6 +3966,34 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_alter_subscription(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let if_exists = self.parse_if_exists()?;
+        let name = self.parse_object_name()?;
+
+        Ok(match self.expect_one_of_keywords(&[ACKNOWLEDGE, RENAME])? {
+            ACKNOWLEDGE => {
+                let timestamp = self.parse_literal_uint()?;
+                Statement::AlterSubscription(AlterSubscriptionStatement {
+                    name,
+                    if_exists,
+                    timestamp,
+                })
+            }
+            RENAME => {
+                self.expect_keyword(TO)?;
+                let to_item_name = self.parse_identifier()?;
+
+                Statement::AlterObjectRename(AlterObjectRenameStatement {
+                    object_type: ObjectType::Subscription,
+                    if_exists,
+                    name,
+                    to_item_name,
+                })
+            }
+            _ => unreachable!(),
+        })
+    }
+
     /// Parse an ALTER SINK statement.
     fn parse_alter_sink(&mut self) -> Result<Statement<Raw>, ParserError> {
         let if_exists = self.parse_if_exists()?;
@@ -3765,12 +4115,24 @@ impl<'a> Parser<'a> {
                         "queries not allowed in COPY FROM"
                     );
                 }
-                self.expect_keyword(STDIN)?;
-                (CopyDirection::From, CopyTarget::Stdin)
+                let target = match self.peek_token() {
+                    Some(Token::String(_)) => CopyTarget::Url(self.parse_literal_string()?),
+                    _ => {
+                        self.expect_keyword(STDIN)?;
+                        CopyTarget::Stdin
+                    }
+                };
+                (CopyDirection::From, target)
             }
             TO => {
-                self.expect_keyword(STDOUT)?;
-                (CopyDirection::To, CopyTarget::Stdout)
+                let target = match self.peek_token() {
+                    Some(Token::String(_)) => CopyTarget::Url(self.parse_literal_string()?),
+                    _ => {
+                        self.expect_keyword(STDOUT)?;
+                        CopyTarget::Stdout
+                    }
+                };
+                (CopyDirection::To, target)
             }
             _ => unreachable!(),
         };
@@ -3799,16 +4161,27 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_copy_option(&mut self) -> Result<CopyOption<Raw>, ParserError> {
-        let name =
-            match self.expect_one_of_keywords(&[FORMAT, DELIMITER, NULL, ESCAPE, QUOTE, HEADER])? {
-                FORMAT => CopyOptionName::Format,
-                DELIMITER => CopyOptionName::Delimiter,
-                NULL => CopyOptionName::Null,
-                ESCAPE => CopyOptionName::Escape,
-                QUOTE => CopyOptionName::Quote,
-                HEADER => CopyOptionName::Header,
-                _ => unreachable!(),
-            };
+        let name = match self.expect_one_of_keywords(&[
+            FORMAT, DELIMITER, NULL, ESCAPE, QUOTE, HEADER, CONNECTION, MAX,
+        ])? {
+            FORMAT => CopyOptionName::Format,
+            DELIMITER => CopyOptionName::Delimiter,
+            NULL => CopyOptionName::Null,
+            ESCAPE => CopyOptionName::Escape,
+            QUOTE => CopyOptionName::Quote,
+            HEADER => CopyOptionName::Header,
+            CONNECTION => {
+                return Ok(CopyOption {
+                    name: CopyOptionName::Connection,
+                    value: Some(self.parse_object_option_value()?),
+                });
+            }
+            MAX => {
+                self.expect_keywords(&[FILE, SIZE])?;
+                CopyOptionName::MaxFileSize
+            }
+            _ => unreachable!(),
+        };
         let value = self.parse_optional_option_value()?;
         Ok(CopyOption { name, value })
     }
@@ -4601,7 +4974,7 @@ impl<'a> Parser<'a> {
         };
 
         let group_by = if self.parse_keywords(&[GROUP, BY]) {
-            self.parse_comma_separated(Parser::parse_expr)?
+            self.parse_comma_separated(Parser::parse_group_by_expr)?
         } else {
             vec![]
         };
@@ -4632,6 +5005,52 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// Parses an element of a `GROUP BY` clause, including the `ROLLUP`,
+    /// `CUBE`, and `GROUPING SETS` constructs.
+    fn parse_group_by_expr(&mut self) -> Result<GroupByExpr<Raw>, ParserError> {
+        if self.peek_keyword(ROLLUP) && self.peek_nth_token(1) == Some(Token::LParen) {
+            self.expect_keyword(ROLLUP)?;
+            self.expect_token(&Token::LParen)?;
+            let elements = self.parse_comma_separated(Parser::parse_grouping_element)?;
+            self.expect_token(&Token::RParen)?;
+            Ok(GroupByExpr::Rollup(elements))
+        } else if self.peek_keyword(CUBE) && self.peek_nth_token(1) == Some(Token::LParen) {
+            self.expect_keyword(CUBE)?;
+            self.expect_token(&Token::LParen)?;
+            let elements = self.parse_comma_separated(Parser::parse_grouping_element)?;
+            self.expect_token(&Token::RParen)?;
+            Ok(GroupByExpr::Cube(elements))
+        } else if self.parse_keywords(&[GROUPING, SETS]) {
+            self.expect_token(&Token::LParen)?;
+            let elements = self.parse_comma_separated(Parser::parse_group_by_expr)?;
+            self.expect_token(&Token::RParen)?;
+            Ok(GroupByExpr::GroupingSets(elements))
+        } else {
+            self.parse_grouping_element()
+        }
+    }
+
+    /// Parses either a single grouping expression or a parenthesized list of
+    /// grouping expressions, which may be empty.
+    fn parse_grouping_element(&mut self) -> Result<GroupByExpr<Raw>, ParserError> {
+        if self.peek_token() != Some(Token::LParen) {
+            return Ok(GroupByExpr::Expr(self.parse_expr()?));
+        }
+        if self.peek_nth_token(1) == Some(Token::RParen) {
+            self.expect_token(&Token::LParen)?;
+            self.expect_token(&Token::RParen)?;
+            return Ok(GroupByExpr::List(vec![]));
+        }
+        // A parenthesized list is parsed as a nested expression or a row
+        // constructor, depending on its length. Unwrap either into a list, so
+        // that `(a, b)` groups by `a` and `b` rather than by the record.
+        match self.parse_expr()? {
+            Expr::Nested(expr) => Ok(GroupByExpr::List(vec![*expr])),
+            Expr::Row { exprs } => Ok(GroupByExpr::List(exprs)),
+            expr => Ok(GroupByExpr::Expr(expr)),
+        }
+    }
+
     fn parse_select_option(&mut self) -> Result<SelectOption<Raw>, ParserError> {
         self.expect_keywords(&[EXPECTED, GROUP, SIZE])?;
         let name = SelectOptionName::ExpectedGroupSize;
@@ -4772,6 +5191,7 @@ impl<'a> Parser<'a> {
             MATERIALIZED,
             SECRETS,
             CONNECTIONS,
+            SUBSCRIPTIONS,
         ]) {
             let object_type = match object_type {
                 OBJECTS => ObjectType::Object,
@@ -4797,6 +5217,7 @@ impl<'a> Parser<'a> {
                 }
                 SECRETS => ObjectType::Secret,
                 CONNECTIONS => ObjectType::Connection,
+                SUBSCRIPTIONS => ObjectType::Subscription,
                 _ => unreachable!(),
             };
 
@@ -5147,15 +5568,46 @@ impl<'a> Parser<'a> {
         } else {
             InsertSource::Query(self.parse_query()?)
         };
+        let on_conflict = if self.parse_keywords(&[ON, CONFLICT]) {
+            Some(self.parse_on_conflict()?)
+        } else {
+            None
+        };
         let returning = self.parse_returning()?;
         Ok(Statement::Insert(InsertStatement {
             table_name,
             columns,
             source,
+            on_conflict,
             returning,
         }))
     }
 
+    /// Parse the remainder of an `ON CONFLICT` clause, assuming the `ON
+    /// CONFLICT` tokens have already been consumed.
+    fn parse_on_conflict(&mut self) -> Result<OnConflict<Raw>, ParserError> {
+        let target = self.parse_parenthesized_column_list(Optional)?;
+        self.expect_keyword(DO)?;
+        let action = match self.expect_one_of_keywords(&[NOTHING, UPDATE])? {
+            NOTHING => OnConflictAction::DoNothing,
+            UPDATE => {
+                self.expect_keyword(SET)?;
+                let assignments = self.parse_comma_separated(Parser::parse_assignment)?;
+                let selection = if self.parse_keyword(WHERE) {
+                    Some(self.parse_expr()?)
+                } else {
+                    None
+                };
+                OnConflictAction::DoUpdate {
+                    assignments,
+                    selection,
+                }
+            }
+            _ => unreachable!(),
+        };
+        Ok(OnConflict { target, action })
+    }
+
     fn parse_returning(&mut self) -> Result<Vec<SelectItem<Raw>>, ParserError> {
         Ok(if self.parse_keyword(RETURNING) {
             self.parse_comma_separated(Parser::parse_select_item)?
@@ -5182,6 +5634,71 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    fn parse_merge(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(INTO)?;
+        let table_name = RawObjectName::Name(self.parse_object_name()?);
+        let alias = self.parse_optional_table_alias()?;
+        self.expect_keyword(USING)?;
+        let using = self.parse_table_factor()?;
+        self.expect_keyword(ON)?;
+        let on = self.parse_expr()?;
+
+        let mut clauses = vec![];
+        while self.parse_keyword(WHEN) {
+            clauses.push(self.parse_merge_clause()?);
+        }
+        if clauses.is_empty() {
+            return self.expected(self.peek_pos(), "WHEN", self.peek_token());
+        }
+
+        Ok(Statement::Merge(MergeStatement {
+            table_name,
+            alias,
+            using,
+            on,
+            clauses,
+        }))
+    }
+
+    /// Parse a `WHEN [NOT] MATCHED` clause of a `MERGE` statement, assuming
+    /// the `WHEN` token has already been consumed.
+    fn parse_merge_clause(&mut self) -> Result<MergeClause<Raw>, ParserError> {
+        let matched = !self.parse_keyword(NOT);
+        self.expect_keyword(MATCHED)?;
+        let condition = if self.parse_keyword(AND) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+        self.expect_keyword(THEN)?;
+        let action = if self.parse_keywords(&[DO, NOTHING]) {
+            MergeAction::DoNothing
+        } else if matched {
+            match self.expect_one_of_keywords(&[UPDATE, DELETE])? {
+                UPDATE => {
+                    self.expect_keyword(SET)?;
+                    let assignments = self.parse_comma_separated(Parser::parse_assignment)?;
+                    MergeAction::Update { assignments }
+                }
+                DELETE => MergeAction::Delete,
+                _ => unreachable!(),
+            }
+        } else {
+            self.expect_keyword(INSERT)?;
+            let columns = self.parse_parenthesized_column_list(Optional)?;
+            self.expect_keyword(VALUES)?;
+            self.expect_token(&Token::LParen)?;
+            let values = self.parse_comma_separated(Parser::parse_expr)?;
+            self.expect_token(&Token::RParen)?;
+            MergeAction::Insert { columns, values }
+        };
+        Ok(MergeClause {
+            matched,
+            condition,
+            action,
+        })
+    }
+
     /// Parse a `var = expr` assignment, used in an UPDATE statement
     fn parse_assignment(&mut self) -> Result<Assignment<Raw>, ParserError> {
         let id = self.parse_identifier()?;
@@ -5418,6 +5935,7 @@ impl<'a> Parser<'a> {
             OPTIMIZER,
             QUERY,
             TIMESTAMP,
+            ANALYZE,
         ]) {
             Some(RAW) => {
                 self.expect_keyword(PLAN)?;
@@ -5450,6 +5968,7 @@ impl<'a> Parser<'a> {
                 Some(ExplainStage::Trace)
             }
             Some(TIMESTAMP) => Some(ExplainStage::Timestamp),
+            Some(ANALYZE) => Some(ExplainStage::Analyze),
             None => None,
             _ => unreachable!(),
         };
@@ -5479,8 +5998,18 @@ impl<'a> Parser<'a> {
             self.expect_keyword(FOR)?;
         }
 
-        // VIEW name | MATERIALIZED VIEW name | query
-        let explainee = if self.parse_keyword(VIEW) {
+        // VIEW name | MATERIALIZED VIEW name | INDEX name | query
+        let explainee = if stage == Some(ExplainStage::Analyze) {
+            // Only objects that are maintained by a dataflow can be analyzed.
+            match self.expect_one_of_keywords(&[INDEX, MATERIALIZED])? {
+                INDEX => Explainee::Index(self.parse_raw_name()?),
+                MATERIALIZED => {
+                    self.expect_keyword(VIEW)?;
+                    Explainee::MaterializedView(self.parse_raw_name()?)
+                }
+                _ => unreachable!(),
+            }
+        } else if self.parse_keyword(VIEW) {
             Explainee::View(self.parse_raw_name()?)
         } else if self.parse_keywords(&[MATERIALIZED, VIEW]) {
             Explainee::MaterializedView(self.parse_raw_name()?)
@@ -5529,6 +6058,7 @@ impl<'a> Parser<'a> {
             stmt @ Statement::Select(_)
             | stmt @ Statement::Insert(_)
             | stmt @ Statement::Delete(_)
+            | stmt @ Statement::Merge(_)
             | stmt @ Statement::Update(_) => stmt,
             _ => return parser_err!(self, pos, "unpreparable statement"),
         };
@@ -9,18 +9,21 @@
 
 //! Provides a publicly available interface to transform our SQL ASTs.
 
+use mz_compute_client::controller::ComputeInstanceId;
 use mz_repr::GlobalId;
 use std::collections::{HashMap, HashSet};
 
+use mz_ore::collections::CollectionExt;
 use mz_ore::str::StrExt;
 
 use crate::ast::visit::{self, Visit};
 use crate::ast::visit_mut::{self, VisitMut};
 use crate::ast::{
-    AstInfo, CreateConnectionStatement, CreateIndexStatement, CreateMaterializedViewStatement,
-    CreateSecretStatement, CreateSinkStatement, CreateSourceStatement, CreateTableStatement,
-    CreateViewStatement, Expr, Ident, Query, Raw, RawObjectName, Statement, UnresolvedObjectName,
-    ViewDefinition,
+    AstInfo, CreateConnectionStatement, CreateFunctionBody, CreateFunctionStatement,
+    CreateIndexStatement, CreateMaterializedViewStatement, CreateSecretStatement,
+    CreateSinkStatement, CreateSourceStatement, CreateTableStatement, CreateViewStatement, Expr,
+    Ident, Query, Raw, RawClusterName, RawObjectName, Select, SelectItem, SetExpr, Statement,
+    UnresolvedObjectName, ViewDefinition,
 };
 use crate::names::FullObjectName;
 
@@ -102,6 +105,25 @@ pub fn create_stmt_rename_refs(
         | Statement::CreateMaterializedView(CreateMaterializedViewStatement { query, .. }) => {
             rewrite_query(from_name, to_item_name, query)?;
         }
+        Statement::CreateFunction(CreateFunctionStatement { body, .. }) => match body {
+            CreateFunctionBody::Query(query) => rewrite_query(from_name, to_item_name, query)?,
+            CreateFunctionBody::Expr(expr) => {
+                // Rewrite the expression as the sole projection of a query,
+                // so that its references are assessed like a view's.
+                let mut query = Query::select(Select::default().project(SelectItem::Expr {
+                    expr: expr.clone(),
+                    alias: None,
+                }));
+                rewrite_query(from_name, to_item_name, &mut query)?;
+                *expr = match query.body {
+                    SetExpr::Select(select) => match select.projection.into_element() {
+                        SelectItem::Expr { expr, .. } => expr,
+                        _ => unreachable!("projection is a single expression"),
+                    },
+                    _ => unreachable!("query is a single SELECT"),
+                };
+            }
+        },
         Statement::CreateSource(_)
         | Statement::CreateTable(_)
         | Statement::CreateSecret(_)
@@ -381,3 +403,124 @@ impl<'ast> VisitMut<'ast, Raw> for CreateSqlIdReplacer<'_> {
         }
     }
 }
+
+/// Updates all cluster ids from the keys of `ids` to the values of `ids` within
+/// `create_stmt`.
+pub fn create_stmt_replace_cluster_ids(
+    create_stmt: &mut Statement<Raw>,
+    ids: &HashMap<ComputeInstanceId, ComputeInstanceId>,
+) {
+    let mut id_replacer = CreateSqlClusterIdReplacer { ids };
+    id_replacer.visit_statement_mut(create_stmt);
+}
+
+struct CreateSqlClusterIdReplacer<'a> {
+    ids: &'a HashMap<ComputeInstanceId, ComputeInstanceId>,
+}
+
+impl<'ast> VisitMut<'ast, Raw> for CreateSqlClusterIdReplacer<'_> {
+    fn visit_cluster_name_mut(
+        &mut self,
+        cluster_name: &'ast mut <mz_sql_parser::ast::Raw as AstInfo>::ClusterName,
+    ) {
+        match cluster_name {
+            RawClusterName::Resolved(id) => {
+                let old_id = match id.parse() {
+                    Ok(old_id) => old_id,
+                    Err(_) => panic!("invalid persisted cluster id {id}"),
+                };
+                if let Some(new_id) = self.ids.get(&old_id) {
+                    *id = new_id.to_string();
+                }
+            }
+            RawClusterName::Unresolved(_) => {}
+        }
+    }
+}
+
+/// Swaps all references to the schemas named `schema_a` and `schema_b` within
+/// `database` in `create_stmt`, or errors if a reference is not qualified
+/// enough to be swapped unambiguously.
+///
+/// References to items that are qualified only by schema name, e.g.
+/// `schema.item.column`, are rejected because the schema they resolve to
+/// depends on the session's database.
+pub fn create_stmt_swap_schemas(
+    create_stmt: &mut Statement<Raw>,
+    database: &str,
+    schema_a: &str,
+    schema_b: &str,
+) -> Result<(), String> {
+    let mut swapper = CreateSqlSchemaSwapper {
+        database: Ident::new(database),
+        schema_a: Ident::new(schema_a),
+        schema_b: Ident::new(schema_b),
+        err: None,
+    };
+    swapper.visit_statement_mut(create_stmt);
+    match swapper.err {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+struct CreateSqlSchemaSwapper {
+    database: Ident,
+    schema_a: Ident,
+    schema_b: Ident,
+    err: Option<String>,
+}
+
+impl CreateSqlSchemaSwapper {
+    /// Swaps the schema in `name` if it is a fully qualified reference of the
+    /// form `<database>.<schema>.<item>`.
+    fn maybe_swap_idents(&mut self, name: &mut [Ident]) {
+        if name.len() == 3 && name[0] == self.database {
+            self.swap_schema(&mut name[1]);
+        } else if name.len() == 2 && (name[0] == self.schema_a || name[0] == self.schema_b) {
+            self.err = Some(format!(
+                "{} is not sufficiently qualified to support swapping schemas",
+                name[0].as_str().quoted()
+            ));
+        }
+    }
+
+    fn swap_schema(&self, schema: &mut Ident) {
+        if *schema == self.schema_a {
+            *schema = self.schema_b.clone();
+        } else if *schema == self.schema_b {
+            *schema = self.schema_a.clone();
+        }
+    }
+}
+
+impl<'ast> VisitMut<'ast, Raw> for CreateSqlSchemaSwapper {
+    fn visit_expr_mut(&mut self, e: &'ast mut Expr<Raw>) {
+        match e {
+            Expr::Identifier(id) => {
+                // The last ID component is a column name that should not be
+                // considered in the swap.
+                let i = id.len() - 1;
+                self.maybe_swap_idents(&mut id[..i]);
+            }
+            Expr::QualifiedWildcard(id) => {
+                self.maybe_swap_idents(id);
+            }
+            _ => visit_mut::visit_expr_mut(self, e),
+        }
+    }
+    fn visit_unresolved_object_name_mut(
+        &mut self,
+        unresolved_object_name: &'ast mut UnresolvedObjectName,
+    ) {
+        self.maybe_swap_idents(&mut unresolved_object_name.0);
+    }
+    fn visit_object_name_mut(
+        &mut self,
+        object_name: &'ast mut <mz_sql_parser::ast::Raw as AstInfo>::ObjectName,
+    ) {
+        match object_name {
+            RawObjectName::Name(n) | RawObjectName::Id(_, n) => self.maybe_swap_idents(&mut n.0),
+        }
+    }
+}
@@ -27,7 +27,7 @@ use mz_compute_client::controller::ComputeInstanceId;
 use mz_expr::MirScalarExpr;
 use mz_ore::now::{EpochMillis, NowFn, NOW_ZERO};
 use mz_repr::explain_new::{DummyHumanizer, ExprHumanizer};
-use mz_repr::{ColumnName, GlobalId, RelationDesc, ScalarType};
+use mz_repr::{ColumnName, GlobalId, RelationDesc, ScalarType, Timestamp};
 use mz_sql_parser::ast::Expr;
 use mz_storage_client::types::connections::Connection;
 use mz_storage_client::types::sources::SourceDesc;
@@ -310,7 +310,7 @@ pub trait CatalogItem {
     ///
     /// If the catalog item is not of a type that produces functions (i.e.,
     /// anything other than a function), it returns an error.
-    fn func(&self) -> Result<&'static Func, CatalogError>;
+    fn func(&self) -> Result<&Func, CatalogError>;
 
     /// Returns the resolved source connection.
     ///
@@ -351,6 +351,11 @@ pub trait CatalogItem {
     /// Returns the type information associated with the catalog item, if the
     /// catalog item is a type.
     fn type_details(&self) -> Option<&CatalogTypeDetails<IdReference>>;
+
+    /// Returns the ID of the subscribed object, the timestamp to resume from,
+    /// and whether the snapshot at that timestamp is still to be emitted, if
+    /// the catalog item is a subscription.
+    fn subscription_details(&self) -> Option<(GlobalId, Timestamp, bool)>;
 }
 
 /// The type of a [`CatalogItem`].
@@ -376,6 +381,8 @@ pub enum CatalogItemType {
     Secret,
     /// A connection.
     Connection,
+    /// A subscription.
+    Subscription,
 }
 
 impl fmt::Display for CatalogItemType {
@@ -391,6 +398,7 @@ impl fmt::Display for CatalogItemType {
             CatalogItemType::Func => f.write_str("func"),
             CatalogItemType::Secret => f.write_str("secret"),
             CatalogItemType::Connection => f.write_str("connection"),
+            CatalogItemType::Subscription => f.write_str("subscription"),
         }
     }
 }
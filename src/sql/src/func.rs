@@ -23,9 +23,9 @@ use mz_ore::collections::CollectionExt;
 use mz_pgrepr::oid;
 use mz_repr::{ColumnName, ColumnType, Datum, RelationType, Row, ScalarBaseType, ScalarType};
 
-use crate::ast::{SelectStatement, Statement};
+use crate::ast::{CreateFunctionBody, Expr, Query, SelectStatement, Statement};
 use crate::catalog::{CatalogType, TypeCategory, TypeReference};
-use crate::names::{self, PartialObjectName};
+use crate::names::{self, Aug, PartialObjectName};
 use crate::plan::error::PlanError;
 use crate::plan::expr::{
     AggregateFunc, BinaryFunc, CoercibleScalarExpr, ColumnOrder, HirRelationExpr, HirScalarExpr,
@@ -443,6 +443,102 @@ fn experimental_sql_impl_table_func(
     sql_impl_table_func_inner(sql, Some(feature))
 }
 
+/// The return type of a user-defined SQL function.
+#[derive(Debug, Clone)]
+pub enum UserFuncReturns {
+    /// The function returns a single value of the specified type.
+    Scalar(ScalarType),
+    /// The function returns a set of rows with the specified columns.
+    Table(Vec<(ColumnName, ScalarType)>),
+}
+
+/// Reconstructs `scx` such that the parameter types are bound to
+/// `param_types`.
+fn bind_param_types<'a>(
+    scx: &StatementContext<'a>,
+    param_types: &[ScalarType],
+) -> StatementContext<'a> {
+    let mut scx = scx.clone();
+    scx.param_types = RefCell::new(
+        param_types
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, ty)| (i + 1, ty))
+            .collect(),
+    );
+    scx
+}
+
+/// Plans the body of a user-defined scalar function.
+///
+/// The function's arguments are left as parameters in the returned
+/// expression; see [`HirScalarExpr::splice_parameters`].
+pub(crate) fn plan_user_func_expr(
+    qcx: &QueryContext,
+    param_types: &[ScalarType],
+    return_type: &ScalarType,
+    expr: &Expr<Aug>,
+) -> Result<HirScalarExpr, PlanError> {
+    let scx = bind_param_types(qcx.scx, param_types);
+    let qcx = QueryContext::root(&scx, qcx.lifetime);
+
+    let mut expr = expr.clone();
+    transform_ast::transform_expr(&scx, &mut expr)?;
+
+    let ecx = ExprContext {
+        qcx: &qcx,
+        name: "function body",
+        scope: &Scope::empty(),
+        relation_type: &RelationType::empty(),
+        allow_aggregates: false,
+        allow_subqueries: true,
+        allow_windows: false,
+    };
+    let expr = query::plan_expr(&ecx, &expr)?;
+    let expr = typeconv::plan_coerce(&ecx, expr, return_type)?;
+    check_no_unbound_params(&scx, param_types.len())?;
+    let body_type = ecx.scalar_type(&expr);
+    typeconv::plan_cast(&ecx, CastContext::Assignment, expr, return_type).map_err(|_| {
+        sql_err!(
+            "return type mismatch in function declared to return {}: \
+            function body is of type {}",
+            qcx.humanize_scalar_type(return_type),
+            qcx.humanize_scalar_type(&body_type),
+        )
+    })
+}
+
+/// Plans the body of a user-defined set-returning function.
+///
+/// The function's arguments are left as parameters in the returned
+/// expression; see [`HirRelationExpr::splice_parameters`].
+pub(crate) fn plan_user_func_query(
+    qcx: &QueryContext,
+    param_types: &[ScalarType],
+    column_types: &[ScalarType],
+    query: &Query<Aug>,
+) -> Result<HirRelationExpr, PlanError> {
+    let scx = bind_param_types(qcx.scx, param_types);
+    let mut qcx = QueryContext::root(&scx, qcx.lifetime);
+
+    let mut query = query.clone();
+    transform_ast::transform_query(&scx, &mut query)?;
+
+    let expr = query::plan_function_body_query(&mut qcx, &query, column_types)?;
+    check_no_unbound_params(&scx, param_types.len())?;
+    Ok(expr)
+}
+
+/// Ensures that planning a function body did not bind any parameters beyond
+/// the function's `num_params` arguments.
+fn check_no_unbound_params(scx: &StatementContext, num_params: usize) -> Result<(), PlanError> {
+    match scx.param_types.borrow().keys().find(|n| **n > num_params) {
+        Some(n) => Err(PlanError::UnknownParameter(*n)),
+        None => Ok(()),
+    }
+}
+
 /// Describes a single function's implementation.
 pub struct FuncImpl<R> {
     oid: u32,
@@ -1741,6 +1837,76 @@ impl Func {
     }
 }
 
+impl Func {
+    /// Constructs a user-defined SQL function.
+    ///
+    /// The function accepts arguments of `param_types` and evaluates `body`,
+    /// which refers to the arguments using the standard parameter syntax
+    /// (`$1`, `$2`, ...). As with built-in functions that are defined in SQL,
+    /// calls to the function are inlined into the calling query.
+    ///
+    /// The implementation's OID is left as zero, as user-defined functions are
+    /// assigned their OID by the catalog.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `body` is not an expression when `returns` is
+    /// [`UserFuncReturns::Scalar`], or if `body` is not a query when `returns`
+    /// is [`UserFuncReturns::Table`].
+    pub fn user_defined(
+        param_types: Vec<ScalarType>,
+        returns: UserFuncReturns,
+        body: CreateFunctionBody<Aug>,
+    ) -> Func {
+        let params = ParamList::Exact(param_types.iter().cloned().map(ParamType::Plain).collect());
+        match (returns, body) {
+            (UserFuncReturns::Scalar(typ), CreateFunctionBody::Expr(expr)) => {
+                let return_type = ReturnType::scalar(typ.clone().into());
+                let op = Operation::variadic(move |ecx, args| {
+                    let mut out = plan_user_func_expr(ecx.qcx, &param_types, &typ, &expr)?;
+                    out.splice_parameters(&args, 0);
+                    Ok(out)
+                });
+                Func::Scalar(vec![FuncImpl {
+                    oid: 0,
+                    params,
+                    return_type,
+                    op,
+                }])
+            }
+            (UserFuncReturns::Table(columns), CreateFunctionBody::Query(query)) => {
+                // Mirror the treatment of built-in table functions: a single
+                // column is returned as is, while multiple columns are
+                // returned as a record.
+                let return_type = match &columns[..] {
+                    [(_, typ)] => ReturnType::set_of(typ.clone().into()),
+                    _ => ReturnType::set_of(ParamType::RecordAny),
+                };
+                let (column_names, column_types): (Vec<_>, Vec<_>) = columns.into_iter().unzip();
+                let op = Operation::variadic(move |ecx, args| {
+                    let mut expr =
+                        plan_user_func_query(ecx.qcx, &param_types, &column_types, &query)?;
+                    expr.splice_parameters(&args, 0);
+                    Ok(TableFuncPlan {
+                        expr,
+                        column_names: column_names.clone(),
+                    })
+                });
+                Func::Table(vec![FuncImpl {
+                    oid: 0,
+                    params,
+                    return_type,
+                    op,
+                }])
+            }
+            (returns, body) => panic!(
+                "user-defined function body {} does not match return type {:?}",
+                body, returns
+            ),
+        }
+    }
+}
+
 /// Functions using this macro should be transformed/planned away before
 /// reaching function selection code, but still need to be present in the
 /// catalog during planning.
@@ -2111,6 +2277,14 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
         "pg_backend_pid" => Scalar {
             params!() => UnmaterializableFunc::PgBackendPid, 2026;
         },
+        // `pg_cancel_backend` and `pg_terminate_backend` have side effects, so
+        // they are planned specially when they are the sole expression of a
+        // `SELECT`. See `plan::statement::dml::plan_backend_signal`.
+        "pg_cancel_backend" => Scalar {
+            params!(Int32) => Operation::unary(|_ecx, _e| {
+                bail_unsupported!("pg_cancel_backend in this position")
+            }) => Bool, 2171;
+        },
         // pg_get_constraintdef gives more info about a constraint within the `pg_constraint`
         // view. Certain meta commands rely on this function not throwing an error, but the
         // `pg_constraint` view is empty in materialize. Therefore we know any oid provided is
@@ -2205,6 +2379,11 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
                      WHERE o.oid = $1)"
             ) => Bool, 2079;
         },
+        "pg_terminate_backend" => Scalar {
+            params!(Int32) => Operation::unary(|_ecx, _e| {
+                bail_unsupported!("pg_terminate_backend in this position")
+            }) => Bool, 2096;
+        },
         "pg_type_is_visible" => Scalar {
             params!(Oid) => sql_impl_func(
                 "(SELECT s.name = ANY(pg_catalog.current_schemas(true))
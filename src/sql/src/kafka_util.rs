@@ -23,6 +23,7 @@ use mz_ore::task;
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{AstInfo, KafkaConfigOption, KafkaConfigOptionName};
 use mz_storage_client::types::connections::{ConnectionContext, KafkaConnection, StringOrSecret};
+use mz_storage_client::types::sources::KafkaTopics;
 
 use crate::names::Aug;
 use crate::normalize::generate_extracted_config;
@@ -54,6 +55,8 @@ pub fn validate_options_for_context<T: AstInfo>(
             StatisticsIntervalMs => None,
             Topic => None,
             TopicMetadataRefreshIntervalMs => None,
+            TopicPattern => Some(Source),
+            Topics => Some(Source),
             TransactionTimeoutMs => None,
             StartTimestamp => Some(Source),
             StartOffset => Some(Source),
@@ -92,6 +95,8 @@ generate_extracted_config!(
     (StatisticsIntervalMs, i32, Default(1_000)),
     (Topic, String),
     (TopicMetadataRefreshIntervalMs, i32),
+    (TopicPattern, String),
+    (Topics, Vec<String>),
     (TransactionTimeoutMs, i32),
     (StartTimestamp, i64),
     (StartOffset, Vec<i64>),
@@ -203,6 +208,36 @@ impl TryFrom<&KafkaConfigOptionExtracted> for Option<KafkaStartOffsetType> {
     }
 }
 
+impl TryFrom<&KafkaConfigOptionExtracted> for KafkaTopics {
+    type Error = PlanError;
+    fn try_from(
+        KafkaConfigOptionExtracted {
+            topic,
+            topics,
+            topic_pattern,
+            ..
+        }: &KafkaConfigOptionExtracted,
+    ) -> Result<KafkaTopics, Self::Error> {
+        Ok(match (topic, topics, topic_pattern) {
+            (Some(topic), None, None) => KafkaTopics::Topic(topic.clone()),
+            (None, Some(topics), None) => {
+                if topics.is_empty() {
+                    sql_bail!("TOPICS must contain at least one topic");
+                }
+                KafkaTopics::List(topics.clone())
+            }
+            (None, None, Some(pattern)) => {
+                if let Err(e) = KafkaTopics::compile_pattern(pattern) {
+                    sql_bail!("invalid TOPIC PATTERN: {}", e);
+                }
+                KafkaTopics::Pattern(pattern.clone())
+            }
+            (None, None, None) => sql_bail!("KAFKA CONNECTION without TOPIC"),
+            _ => sql_bail!("cannot specify more than one of TOPIC, TOPICS, and TOPIC PATTERN"),
+        })
+    }
+}
+
 /// Create a new `rdkafka::ClientConfig` with the provided
 /// [`options`](https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md),
 /// and test its ability to create an `rdkafka::consumer::BaseConsumer`.
@@ -219,7 +254,7 @@ impl TryFrom<&KafkaConfigOptionExtracted> for Option<KafkaStartOffsetType> {
 pub async fn create_consumer(
     connection_context: &ConnectionContext,
     kafka_connection: &KafkaConnection,
-    topic: &str,
+    topic: Option<&str>,
 ) -> Result<Arc<BaseConsumer<BrokerRewritingClientContext<KafkaErrCheckContext>>>, PlanError> {
     let consumer: BaseConsumer<_> = kafka_connection
         .create_with_context(
@@ -232,16 +267,16 @@ pub async fn create_consumer(
     let consumer = Arc::new(consumer);
 
     let context = Arc::clone(consumer.context());
-    let owned_topic = String::from(topic);
+    let owned_topic = topic.map(String::from);
     // Wait for a metadata request for up to one second. This greatly
     // increases the probability that we'll see a connection error if
     // e.g. the hostname was mistyped. librdkafka doesn't expose a
     // better API for asking whether a connection succeeded or failed,
     // unfortunately.
-    task::spawn_blocking(move || format!("kafka_get_metadata:{topic}"), {
+    task::spawn_blocking(move || format!("kafka_get_metadata:{topic:?}"), {
         let consumer = Arc::clone(&consumer);
         move || {
-            let _ = consumer.fetch_metadata(Some(&owned_topic), Duration::from_secs(1));
+            let _ = consumer.fetch_metadata(owned_topic.as_deref(), Duration::from_secs(1));
         }
     })
     .await
@@ -347,6 +382,37 @@ where
     .map_err(|e| sql_err!("{}", e))?
 }
 
+/// Returns the names of the existing topics that match the `TOPIC PATTERN`
+/// `pattern`, in sorted order.
+pub async fn lookup_pattern_topics<C>(
+    consumer: Arc<BaseConsumer<C>>,
+    pattern: &str,
+) -> Result<Vec<String>, PlanError>
+where
+    C: ConsumerContext + 'static,
+{
+    let pattern = KafkaTopics::compile_pattern(pattern)
+        .map_err(|e| sql_err!("invalid TOPIC PATTERN: {}", e))?;
+    task::spawn_blocking(
+        || "kafka_lookup_pattern_topics",
+        move || {
+            let metadata = consumer
+                .fetch_metadata(None, Duration::from_secs(10))
+                .map_err(|e| sql_err!("{}", e))?;
+            let mut topics: Vec<_> = metadata
+                .topics()
+                .iter()
+                .filter(|topic| topic.error().is_none() && pattern.is_match(topic.name()))
+                .map(|topic| topic.name().to_string())
+                .collect();
+            topics.sort();
+            Ok(topics)
+        },
+    )
+    .await
+    .map_err(|e| sql_err!("{}", e))?
+}
+
 // Kafka supports bulk lookup of watermarks, but it is not exposed in rdkafka.
 // If that ever changes, we will want to first collect all pids that have no
 // offset for a given timestamp and then do a single request (instead of doing
@@ -714,6 +714,25 @@ impl<'a> NameResolver<'a> {
         }
     }
 
+    /// Records a dependency on the user-defined function named `name`, if any.
+    ///
+    /// Function names are otherwise resolved during planning, as their
+    /// resolution depends on the types of their arguments. Built-in functions
+    /// can never be dropped, so only dependencies on user-defined functions
+    /// need to be tracked.
+    fn note_function_dependency(&mut self, name: &UnresolvedObjectName) {
+        let name = match normalize::unresolved_object_name(name.clone()) {
+            Ok(name) => name,
+            // Invalid names are reported during planning.
+            Err(_) => return,
+        };
+        if let Ok(item) = self.catalog.resolve_function(&name) {
+            if item.id().is_user() {
+                self.ids.insert(item.id());
+            }
+        }
+    }
+
     fn fold_data_type_internal(
         &mut self,
         data_type: <Raw as AstInfo>::DataType,
@@ -1113,6 +1132,16 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
             ConnectionKafkaBroker(broker) => ConnectionKafkaBroker(self.fold_kafka_broker(broker)),
         }
     }
+
+    fn fold_function(&mut self, func: ast::Function<Raw>) -> ast::Function<Aug> {
+        self.note_function_dependency(&func.name);
+        ast::fold::fold_function(self, func)
+    }
+
+    fn fold_table_function(&mut self, func: ast::TableFunction<Raw>) -> ast::TableFunction<Aug> {
+        self.note_function_dependency(&func.name);
+        ast::fold::fold_table_function(self, func)
+    }
 }
 
 /// Resolves names in an AST node using the provided catalog.
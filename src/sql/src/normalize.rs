@@ -22,8 +22,9 @@ use mz_repr::{ColumnName, GlobalId};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::visit_mut::{self, VisitMut};
 use mz_sql_parser::ast::{
-    CreateConnectionStatement, CreateIndexStatement, CreateMaterializedViewStatement,
-    CreateSecretStatement, CreateSinkStatement, CreateSourceStatement, CreateSubsourceStatement,
+    CreateConnectionStatement, CreateFunctionStatement, CreateIndexStatement,
+    CreateMaterializedViewStatement, CreateSecretStatement, CreateSinkStatement,
+    CreateSourceStatement, CreateSubscriptionStatement, CreateSubsourceStatement,
     CreateTableStatement, CreateTypeStatement, CreateViewStatement, Function, FunctionArgs, Ident,
     IfExistsBehavior, Op, Query, Statement, TableFactor, TableFunction, UnresolvedObjectName,
     UnresolvedSchemaName, Value, ViewDefinition,
@@ -325,6 +326,7 @@ pub fn create_statement(
             constraints: _,
             if_not_exists,
             temporary,
+            with_options: _,
         }) => {
             *name = if *temporary {
                 allocate_temporary_name(name)?
@@ -424,6 +426,20 @@ pub fn create_statement(
                 return Err(err);
             }
         }
+        Statement::CreateFunction(CreateFunctionStatement {
+            name,
+            args: _,
+            returns: _,
+            language: _,
+            body,
+        }) => {
+            *name = allocate_name(name)?;
+            let mut normalizer = QueryNormalizer::new(scx);
+            normalizer.visit_create_function_body_mut(body);
+            if let Some(err) = normalizer.err {
+                return Err(err);
+            }
+        }
         Statement::CreateSecret(CreateSecretStatement {
             name,
             if_not_exists,
@@ -441,6 +457,16 @@ pub fn create_statement(
             *if_not_exists = false;
         }
 
+        Statement::CreateSubscription(CreateSubscriptionStatement {
+            name,
+            if_not_exists,
+            from: _,
+            with_options: _,
+        }) => {
+            *name = allocate_name(name)?;
+            *if_not_exists = false;
+        }
+
         _ => unreachable!(),
     }
 
@@ -43,15 +43,17 @@ use mz_expr::{MirRelationExpr, MirScalarExpr, RowSetFinishing};
 use mz_ore::now::{self, NOW_ZERO};
 use mz_pgcopy::CopyFormatParams;
 use mz_repr::explain_new::{ExplainConfig, ExplainFormat};
-use mz_repr::{ColumnName, Diff, GlobalId, RelationDesc, Row, ScalarType};
+use mz_repr::{ColumnName, Diff, GlobalId, RelationDesc, Row, ScalarType, Timestamp};
+use mz_storage_client::types::connections::aws::AwsConfig;
 use mz_storage_client::types::sinks::{SinkEnvelope, StorageSinkConnectionBuilder};
 use mz_storage_client::types::sources::{SourceDesc, Timeline};
 
 use crate::ast::{
-    ExplainStage, Expr, FetchDirection, IndexOptionName, NoticeSeverity, ObjectType, Raw,
-    SetVariableValue, Statement, StatementKind, TransactionAccessMode,
+    CreateFunctionBody, ExplainStage, Expr, FetchDirection, IndexOptionName, NoticeSeverity,
+    ObjectType, Raw, SetVariableValue, Statement, StatementKind, TransactionAccessMode,
 };
 use crate::catalog::{CatalogType, IdReference};
+use crate::func::UserFuncReturns;
 use crate::names::{
     Aug, DatabaseId, FullObjectName, QualifiedObjectName, ResolvedDatabaseSpecifier, SchemaId,
 };
@@ -93,11 +95,13 @@ pub enum Plan {
     CreateSource(CreateSourcePlan),
     CreateSecret(CreateSecretPlan),
     CreateSink(CreateSinkPlan),
+    CreateSubscription(CreateSubscriptionPlan),
     CreateTable(CreateTablePlan),
     CreateView(CreateViewPlan),
     CreateMaterializedView(CreateMaterializedViewPlan),
     CreateIndex(CreateIndexPlan),
     CreateType(CreateTypePlan),
+    CreateFunction(CreateFunctionPlan),
     DiscardTemp,
     DiscardAll,
     DropDatabase(DropDatabasePlan),
@@ -118,6 +122,8 @@ pub enum Plan {
     Subscribe(SubscribePlan),
     SendRows(SendRowsPlan),
     CopyFrom(CopyFromPlan),
+    CopyFromUrl(CopyFromUrlPlan),
+    CopyToUrl(CopyToUrlPlan),
     Explain(ExplainPlan),
     SendDiffs(SendDiffsPlan),
     Insert(InsertPlan),
@@ -126,8 +132,13 @@ pub enum Plan {
     AlterIndexResetOptions(AlterIndexResetOptionsPlan),
     AlterSink(AlterSinkPlan),
     AlterSource(AlterSourcePlan),
+    AlterSourceRefreshSchema(AlterSourceRefreshSchemaPlan),
+    AlterTable(AlterTablePlan),
     AlterItemRename(AlterItemRenamePlan),
+    AlterSchemaSwap(AlterSchemaSwapPlan),
+    AlterComputeInstanceSwap(AlterComputeInstanceSwapPlan),
     AlterSecret(AlterSecretPlan),
+    AlterSubscription(AlterSubscriptionPlan),
     AlterSystemSet(AlterSystemSetPlan),
     AlterSystemReset(AlterSystemResetPlan),
     AlterSystemResetAll(AlterSystemResetAllPlan),
@@ -140,6 +151,7 @@ pub enum Plan {
     Deallocate(DeallocatePlan),
     Raise(RaisePlan),
     RotateKeys(RotateKeysPlan),
+    SignalBackend(SignalBackendPlan),
 }
 
 impl Plan {
@@ -156,9 +168,21 @@ impl Plan {
             StatementKind::AlterObjectRename => {
                 vec![PlanKind::AlterItemRename, PlanKind::AlterNoop]
             }
+            StatementKind::AlterObjectSwap => vec![
+                PlanKind::AlterComputeInstanceSwap,
+                PlanKind::AlterSchemaSwap,
+            ],
             StatementKind::AlterSecret => vec![PlanKind::AlterNoop, PlanKind::AlterSecret],
             StatementKind::AlterSink => vec![PlanKind::AlterNoop, PlanKind::AlterSink],
-            StatementKind::AlterSource => vec![PlanKind::AlterNoop, PlanKind::AlterSource],
+            StatementKind::AlterSubscription => {
+                vec![PlanKind::AlterNoop, PlanKind::AlterSubscription]
+            }
+            StatementKind::AlterSource => vec![
+                PlanKind::AlterNoop,
+                PlanKind::AlterSource,
+                PlanKind::AlterSourceRefreshSchema,
+            ],
+            StatementKind::AlterTable => vec![PlanKind::AlterNoop, PlanKind::AlterTable],
             StatementKind::AlterSystemReset => {
                 vec![PlanKind::AlterNoop, PlanKind::AlterSystemReset]
             }
@@ -170,6 +194,8 @@ impl Plan {
             StatementKind::Commit => vec![PlanKind::CommitTransaction],
             StatementKind::Copy => vec![
                 PlanKind::CopyFrom,
+                PlanKind::CopyFromUrl,
+                PlanKind::CopyToUrl,
                 PlanKind::Peek,
                 PlanKind::SendDiffs,
                 PlanKind::Subscribe,
@@ -178,6 +204,7 @@ impl Plan {
             StatementKind::CreateClusterReplica => vec![PlanKind::CreateComputeReplica],
             StatementKind::CreateConnection => vec![PlanKind::CreateConnection],
             StatementKind::CreateDatabase => vec![PlanKind::CreateDatabase],
+            StatementKind::CreateFunction => vec![PlanKind::CreateFunction],
             StatementKind::CreateIndex => vec![PlanKind::CreateIndex],
             StatementKind::CreateMaterializedView => vec![PlanKind::CreateMaterializedView],
             StatementKind::CreateRole => vec![PlanKind::CreateRole],
@@ -187,6 +214,7 @@ impl Plan {
             StatementKind::CreateSource | StatementKind::CreateSubsource => {
                 vec![PlanKind::CreateSource]
             }
+            StatementKind::CreateSubscription => vec![PlanKind::CreateSubscription],
             StatementKind::CreateTable => vec![PlanKind::CreateTable],
             StatementKind::CreateType => vec![PlanKind::CreateType],
             StatementKind::CreateView => vec![PlanKind::CreateView],
@@ -203,12 +231,13 @@ impl Plan {
             StatementKind::Execute => vec![PlanKind::Execute],
             StatementKind::Explain => vec![PlanKind::Explain],
             StatementKind::Fetch => vec![PlanKind::Fetch],
-            StatementKind::Insert => vec![PlanKind::Insert],
+            StatementKind::Insert => vec![PlanKind::Insert, PlanKind::ReadThenWrite],
+            StatementKind::Merge => vec![PlanKind::ReadThenWrite],
             StatementKind::Prepare => vec![PlanKind::Prepare],
             StatementKind::Raise => vec![PlanKind::Raise],
             StatementKind::ResetVariable => vec![PlanKind::ResetVariable],
             StatementKind::Rollback => vec![PlanKind::AbortTransaction],
-            StatementKind::Select => vec![PlanKind::Peek],
+            StatementKind::Select => vec![PlanKind::Peek, PlanKind::SignalBackend],
             StatementKind::SetTransaction => vec![],
             StatementKind::SetVariable => vec![PlanKind::SetVariable],
             StatementKind::Show => vec![
@@ -356,6 +385,13 @@ pub struct CreateSinkPlan {
     pub host_config: StorageHostConfig,
 }
 
+#[derive(Debug)]
+pub struct CreateSubscriptionPlan {
+    pub name: QualifiedObjectName,
+    pub subscription: Subscription,
+    pub if_not_exists: bool,
+}
+
 #[derive(Debug)]
 pub struct CreateTablePlan {
     pub name: QualifiedObjectName,
@@ -395,6 +431,12 @@ pub struct CreateTypePlan {
     pub typ: Type,
 }
 
+#[derive(Debug)]
+pub struct CreateFunctionPlan {
+    pub name: QualifiedObjectName,
+    pub function: Function,
+}
+
 #[derive(Debug)]
 pub struct DropDatabasePlan {
     pub id: Option<DatabaseId>,
@@ -463,6 +505,12 @@ pub struct SubscribePlan {
 #[derive(Debug)]
 pub enum SubscribeFrom {
     Id(GlobalId),
+    /// Resumes the subscription `id`, which reads the storage collection
+    /// `from`.
+    Subscription {
+        id: GlobalId,
+        from: GlobalId,
+    },
     Query {
         expr: MirRelationExpr,
         desc: RelationDesc,
@@ -481,6 +529,51 @@ pub struct CopyFromPlan {
     pub params: CopyFormatParams<'static>,
 }
 
+/// Copies the contents of objects in remote object storage into a table.
+#[derive(Debug)]
+pub struct CopyFromUrlPlan {
+    pub id: GlobalId,
+    pub columns: Vec<usize>,
+    pub url: CopyUrl,
+    pub format: CopyFromUrlFormat,
+}
+
+#[derive(Debug)]
+pub enum CopyFromUrlFormat {
+    /// One of the formats that `COPY FROM STDIN` accepts.
+    Pgcopy(CopyFormatParams<'static>),
+    Parquet,
+}
+
+/// Copies the results of a query to objects in remote object storage.
+#[derive(Debug)]
+pub struct CopyToUrlPlan {
+    /// The query whose results are copied. Any `ORDER BY`, `LIMIT`, and
+    /// `OFFSET` clauses are planned into the expression.
+    pub expr: MirRelationExpr,
+    /// The description of the query's results.
+    pub desc: RelationDesc,
+    pub when: QueryWhen,
+    pub url: CopyUrl,
+    pub format: CopyFormat,
+    /// Whether to write a header line to each object. Only valid for
+    /// [`CopyFormat::Csv`].
+    pub header: bool,
+    /// The size in bytes after which the remaining results are written to a
+    /// new object.
+    pub max_file_size: u64,
+}
+
+/// The objects in S3 that a `COPY` reads from or writes to.
+#[derive(Debug, Clone)]
+pub struct CopyUrl {
+    pub bucket: String,
+    /// The prefix of the keys of the objects, without a trailing slash.
+    pub prefix: String,
+    pub connection_id: GlobalId,
+    pub connection: AwsConfig,
+}
+
 #[derive(Debug)]
 pub struct ExplainPlan {
     pub raw_plan: HirRelationExpr,
@@ -497,6 +590,9 @@ pub struct SendDiffsPlan {
     pub updates: Vec<(Row, Diff)>,
     pub kind: MutationKind,
     pub returning: Vec<(Row, NonZeroUsize)>,
+    /// The number of rows affected by the statement, if it differs from the
+    /// number implied by `updates`.
+    pub affected_rows: Option<usize>,
 }
 
 #[derive(Debug)]
@@ -556,6 +652,31 @@ pub struct AlterSourcePlan {
     pub remote: AlterOptionParameter,
 }
 
+#[derive(Debug)]
+pub struct AlterSourceRefreshSchemaPlan {
+    pub id: GlobalId,
+    /// The source's definition after switching to the new schema. Its
+    /// relation extends the existing one with trailing nullable columns.
+    pub source: Source,
+}
+
+#[derive(Debug)]
+pub struct AlterTablePlan {
+    pub id: GlobalId,
+    /// The table's definition after the alteration.
+    pub table: Table,
+    pub action: AlterTablePlanAction,
+}
+
+/// Describes how the existing contents of an altered table are rewritten.
+#[derive(Debug)]
+pub enum AlterTablePlanAction {
+    /// Appends a column to every row, whose value is computed by `default`.
+    AddColumn { default: MirScalarExpr },
+    /// Removes the column at position `index` from every row.
+    DropColumn { index: usize },
+}
+
 #[derive(Debug)]
 pub struct AlterItemRenamePlan {
     pub id: GlobalId,
@@ -564,12 +685,31 @@ pub struct AlterItemRenamePlan {
     pub object_type: ObjectType,
 }
 
+#[derive(Debug)]
+pub struct AlterSchemaSwapPlan {
+    pub database_id: DatabaseId,
+    pub schema_a: SchemaId,
+    pub schema_b: SchemaId,
+}
+
+#[derive(Debug)]
+pub struct AlterComputeInstanceSwapPlan {
+    pub name_a: String,
+    pub name_b: String,
+}
+
 #[derive(Debug)]
 pub struct AlterSecretPlan {
     pub id: GlobalId,
     pub secret_as: MirScalarExpr,
 }
 
+#[derive(Debug)]
+pub struct AlterSubscriptionPlan {
+    pub id: GlobalId,
+    pub timestamp: Timestamp,
+}
+
 #[derive(Debug)]
 pub struct AlterSystemSetPlan {
     pub name: String,
@@ -630,12 +770,42 @@ pub struct RaisePlan {
     pub severity: NoticeSeverity,
 }
 
+/// A call to `pg_cancel_backend` or `pg_terminate_backend`.
+#[derive(Debug)]
+pub struct SignalBackendPlan {
+    pub signal: BackendSignal,
+    /// An `int4` expression for the process ID of the connection to signal.
+    pub pid: MirScalarExpr,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendSignal {
+    /// Cancels the statement running on the connection.
+    Cancel,
+    /// Cancels the statement running on the connection and then closes the
+    /// connection.
+    Terminate,
+}
+
+impl BackendSignal {
+    /// Returns the name of the function that sends the signal.
+    pub fn function_name(&self) -> &'static str {
+        match self {
+            BackendSignal::Cancel => "pg_cancel_backend",
+            BackendSignal::Terminate => "pg_terminate_backend",
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Table {
     pub create_sql: String,
     pub desc: RelationDesc,
     pub defaults: Vec<Expr<Aug>>,
     pub temporary: bool,
+    /// The ID of the persist shard whose contents the table starts out with,
+    /// if any. It is validated when the table is created.
+    pub fork_shard: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -672,6 +842,18 @@ pub struct Sink {
     pub envelope: SinkEnvelope,
 }
 
+/// A durable subscription to the changes of a storage collection.
+#[derive(Clone, Debug)]
+pub struct Subscription {
+    pub create_sql: String,
+    pub from: GlobalId,
+    /// The timestamp to resume from. `None` if the coordinator should choose
+    /// one when the subscription is created.
+    pub timestamp: Option<Timestamp>,
+    /// Whether the snapshot at `timestamp` is emitted on the next attach.
+    pub snapshot: bool,
+}
+
 #[derive(Clone, Debug)]
 pub struct View {
     pub create_sql: String,
@@ -702,6 +884,17 @@ pub struct Type {
     pub inner: CatalogType<IdReference>,
 }
 
+/// A user-defined SQL function.
+///
+/// See [`Func::user_defined`](crate::func::Func::user_defined) for details.
+#[derive(Clone, Debug)]
+pub struct Function {
+    pub create_sql: String,
+    pub param_types: Vec<ScalarType>,
+    pub returns: UserFuncReturns,
+    pub body: CreateFunctionBody<Aug>,
+}
+
 /// Specifies when a `Peek` or `Subscribe` should occur.
 #[derive(Debug, PartialEq)]
 pub enum QueryWhen {
@@ -754,6 +947,15 @@ pub enum MutationKind {
     Insert,
     Update,
     Delete,
+    /// `INSERT ... ON CONFLICT`.
+    ///
+    /// Each row of the selection describes one change to the table, laid out as
+    /// `[old row..., new row..., has_old, has_new]`, where the boolean columns
+    /// indicate whether the old row is to be retracted and whether the new row
+    /// is to be inserted.
+    Upsert,
+    /// `MERGE`. The selection is laid out as for [`MutationKind::Upsert`].
+    Merge,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -761,6 +963,7 @@ pub enum CopyFormat {
     Text,
     Csv,
     Binary,
+    Parquet,
 }
 
 #[derive(Debug, Copy, Clone)]
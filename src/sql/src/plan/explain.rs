@@ -545,6 +545,11 @@ impl<'a> Explanation<'a> {
                         self.fmt_scalar_expr(f, &scalar.expr)?;
                         write!(f, ")")?
                     }
+                    WindowExprType::Aggregate(scalar) => {
+                        write!(f, "{}(", scalar.aggregate_expr.func.clone().into_expr())?;
+                        self.fmt_scalar_expr(f, &scalar.aggregate_expr.expr)?;
+                        write!(f, ")")?
+                    }
                 }
                 write!(f, " over (")?;
                 for (i, e) in expr.partition.iter().enumerate() {
@@ -336,6 +336,7 @@ impl VisitChildren<HirScalarExpr> for WindowExpr {
 pub enum WindowExprType {
     Scalar(ScalarWindowExpr),
     Value(ValueWindowExpr),
+    Aggregate(AggregateWindowExpr),
 }
 
 impl WindowExprType {
@@ -348,6 +349,7 @@ impl WindowExprType {
         match self {
             Self::Scalar(expr) => expr.visit_expressions(f),
             Self::Value(expr) => expr.visit_expressions(f),
+            Self::Aggregate(expr) => expr.visit_expressions(f),
         }
     }
 
@@ -360,6 +362,7 @@ impl WindowExprType {
         match self {
             Self::Scalar(expr) => expr.visit_expressions_mut(f),
             Self::Value(expr) => expr.visit_expressions_mut(f),
+            Self::Aggregate(expr) => expr.visit_expressions_mut(f),
         }
     }
 
@@ -372,6 +375,7 @@ impl WindowExprType {
         match self {
             Self::Scalar(expr) => expr.typ(outers, inner, params),
             Self::Value(expr) => expr.typ(outers, inner, params),
+            Self::Aggregate(expr) => expr.typ(outers, inner, params),
         }
     }
 }
@@ -384,6 +388,7 @@ impl VisitChildren<HirScalarExpr> for WindowExprType {
         match self {
             Self::Scalar(_) => (),
             Self::Value(expr) => expr.visit_children(f),
+            Self::Aggregate(expr) => expr.visit_children(f),
         }
     }
 
@@ -394,6 +399,7 @@ impl VisitChildren<HirScalarExpr> for WindowExprType {
         match self {
             Self::Scalar(_) => (),
             Self::Value(expr) => expr.visit_mut_children(f),
+            Self::Aggregate(expr) => expr.visit_mut_children(f),
         }
     }
 
@@ -405,6 +411,7 @@ impl VisitChildren<HirScalarExpr> for WindowExprType {
         match self {
             Self::Scalar(_) => Ok(()),
             Self::Value(expr) => expr.try_visit_children(f),
+            Self::Aggregate(expr) => expr.try_visit_children(f),
         }
     }
 
@@ -416,6 +423,7 @@ impl VisitChildren<HirScalarExpr> for WindowExprType {
         match self {
             Self::Scalar(_) => Ok(()),
             Self::Value(expr) => expr.try_visit_mut_children(f),
+            Self::Aggregate(expr) => expr.try_visit_mut_children(f),
         }
     }
 }
@@ -602,6 +610,97 @@ impl ValueWindowFunc {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AggregateWindowExpr {
+    pub aggregate_expr: AggregateExpr,
+    pub order_by: Vec<ColumnOrder>,
+    pub window_frame: WindowFrame,
+}
+
+impl AggregateWindowExpr {
+    #[deprecated = "Use `VisitChildren<HirScalarExpr>::visit_children` instead."]
+    pub fn visit_expressions<'a, F, E>(&'a self, f: &mut F) -> Result<(), E>
+    where
+        F: FnMut(&'a HirScalarExpr) -> Result<(), E>,
+    {
+        f(&self.aggregate_expr.expr)
+    }
+
+    #[deprecated = "Use `VisitChildren<HirScalarExpr>::visit_mut_children` instead."]
+    pub fn visit_expressions_mut<'a, F, E>(&'a mut self, f: &mut F) -> Result<(), E>
+    where
+        F: FnMut(&'a mut HirScalarExpr) -> Result<(), E>,
+    {
+        f(&mut self.aggregate_expr.expr)
+    }
+
+    fn typ(
+        &self,
+        outers: &[RelationType],
+        inner: &RelationType,
+        params: &BTreeMap<usize, ScalarType>,
+    ) -> ColumnType {
+        self.aggregate_expr.typ(outers, inner, params)
+    }
+
+    /// Whether the window frame of every row is its entire partition, in
+    /// which case the aggregate can be computed by a plain reduction over the
+    /// partition.
+    pub fn frame_is_partition(&self) -> bool {
+        use mz_expr::WindowFrameBound::*;
+        match (&self.window_frame.start_bound, &self.window_frame.end_bound) {
+            (UnboundedPreceding, UnboundedFollowing) => true,
+            // Without an ORDER BY all rows of a partition are peers, so
+            // frames that end at the current peer group span all of them.
+            (UnboundedPreceding, CurrentRow) => {
+                self.order_by.is_empty()
+                    && self.window_frame.units != mz_expr::WindowFrameUnits::Rows
+            }
+            _ => false,
+        }
+    }
+
+    pub fn into_expr(self) -> mz_expr::AggregateFunc {
+        mz_expr::AggregateFunc::WindowAggregate {
+            wrapped_aggregate: Box::new(self.aggregate_expr.func.into_expr()),
+            order_by: self.order_by,
+            window_frame: self.window_frame,
+        }
+    }
+}
+
+impl VisitChildren<HirScalarExpr> for AggregateWindowExpr {
+    fn visit_children<F>(&self, mut f: F)
+    where
+        F: FnMut(&HirScalarExpr),
+    {
+        f(&self.aggregate_expr.expr)
+    }
+
+    fn visit_mut_children<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut HirScalarExpr),
+    {
+        f(&mut self.aggregate_expr.expr)
+    }
+
+    fn try_visit_children<F, E>(&self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&HirScalarExpr) -> Result<(), E>,
+        E: From<RecursionLimitError>,
+    {
+        f(&self.aggregate_expr.expr)
+    }
+
+    fn try_visit_mut_children<F, E>(&mut self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&mut HirScalarExpr) -> Result<(), E>,
+        E: From<RecursionLimitError>,
+    {
+        f(&mut self.aggregate_expr.expr)
+    }
+}
+
 /// A `CoercibleScalarExpr` is a [`HirScalarExpr`] whose type is not fully
 /// determined. Several SQL expressions can be freely coerced based upon where
 /// in the expression tree they appear. For example, the string literal '42'
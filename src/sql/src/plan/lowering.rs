@@ -1050,8 +1050,99 @@ impl HirScalarExpr {
                                     });
                             SS::Column(inner.arity() - 1)
                         }
-                        WindowExprType::Value(func) => {
-                            let hir_scalar_input = func.expr.clone();
+                        WindowExprType::Aggregate(func) if func.frame_is_partition() => {
+                            // The frame of every row is its entire partition, so the aggregate
+                            // can be maintained incrementally by a reduction keyed by the
+                            // partition, whose results are joined back to the partition's rows.
+                            let AggregateExpr {
+                                func: aggregate_func,
+                                expr: aggregate_input,
+                                distinct,
+                            } = func.aggregate_expr;
+                            *inner =
+                                inner
+                                    .take_dangerous()
+                                    .let_in(id_gen, |id_gen, mut get_inner| {
+                                        let aggregate_input = aggregate_input.applied_to(
+                                            id_gen,
+                                            col_map,
+                                            cte_map,
+                                            &mut get_inner,
+                                            subquery_map,
+                                        );
+
+                                        // Record input arity here so that any group_keys that need to mutate get_inner
+                                        // don't add those columns to the output.
+                                        let input_arity = get_inner.typ().arity();
+                                        // The reduction must be keyed on the columns from the outer context, plus
+                                        // the expressions in the partition key, as for the other window functions.
+                                        let mut group_key = col_map
+                                            .inner
+                                            .iter()
+                                            .map(|(_, outer_col)| *outer_col)
+                                            .sorted()
+                                            .collect_vec();
+                                        for p in partition {
+                                            let key = p.applied_to(
+                                                id_gen,
+                                                col_map,
+                                                cte_map,
+                                                &mut get_inner,
+                                                subquery_map,
+                                            );
+                                            if let mz_expr::MirScalarExpr::Column(c) = key {
+                                                group_key.push(c);
+                                            } else {
+                                                get_inner = get_inner.map_one(key);
+                                                group_key.push(get_inner.arity() - 1);
+                                            }
+                                        }
+
+                                        get_inner.let_in(id_gen, |_id_gen, get_inner| {
+                                            let aggregate = mz_expr::AggregateExpr {
+                                                func: aggregate_func.into_expr(),
+                                                expr: aggregate_input,
+                                                distinct,
+                                            };
+                                            let reduce = get_inner.clone().reduce(
+                                                group_key.clone(),
+                                                vec![aggregate],
+                                                None,
+                                            );
+
+                                            // Join every row with the aggregate of its partition.
+                                            let inner_arity = get_inner.arity();
+                                            let equivalences = group_key
+                                                .iter()
+                                                .enumerate()
+                                                .map(|(i, c)| vec![(0, *c), (1, i)])
+                                                .collect_vec();
+                                            let agg_col = inner_arity + group_key.len();
+                                            mz_expr::MirRelationExpr::join(
+                                                vec![get_inner, reduce],
+                                                equivalences,
+                                            )
+                                            .project(
+                                                (0..input_arity)
+                                                    .chain(std::iter::once(agg_col))
+                                                    .collect_vec(),
+                                            )
+                                        })
+                                    });
+                            SS::Column(inner.arity() - 1)
+                        }
+                        func @ (WindowExprType::Value(_) | WindowExprType::Aggregate(_)) => {
+                            // Value window functions and aggregate window functions over other
+                            // frames are evaluated over the list of all rows of each partition.
+                            let (hir_scalar_input, func) = match func {
+                                WindowExprType::Value(func) => {
+                                    (func.expr.clone(), func.into_expr())
+                                }
+                                WindowExprType::Aggregate(func) => {
+                                    (func.aggregate_expr.expr.clone(), func.into_expr())
+                                }
+                                WindowExprType::Scalar(_) => unreachable!(),
+                            };
                             *inner =
                                 inner
                                     .take_dangerous()
@@ -1194,7 +1285,6 @@ impl HirScalarExpr {
                                             }
                                             .nullable(false);
 
-                                            let func = func.into_expr();
                                             let aggregate = mz_expr::AggregateExpr {
                                                 func,
                                                 expr: agg_input,
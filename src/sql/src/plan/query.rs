@@ -33,7 +33,7 @@
 
 use std::borrow::Cow;
 use std::cell::RefCell;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::convert::{TryFrom, TryInto};
 
 use std::iter;
@@ -59,11 +59,12 @@ use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::visit_mut::{self, VisitMut};
 use mz_sql_parser::ast::{
     AsOf, Assignment, AstInfo, DeleteStatement, Distinct, Expr, Function, FunctionArgs,
-    HomogenizingFunction, Ident, InsertSource, IsExprConstruct, Join, JoinConstraint, JoinOperator,
-    Limit, OrderByExpr, Query, Select, SelectItem, SelectOption, SelectOptionName, SetExpr,
-    SetOperator, ShowStatement, SubscriptPosition, TableAlias, TableFactor, TableFunction,
-    TableWithJoins, UnresolvedObjectName, UpdateStatement, Value, Values, WindowFrame,
-    WindowFrameBound, WindowFrameUnits, WindowSpec,
+    GroupByExpr, HomogenizingFunction, Ident, InsertSource, IsExprConstruct, Join, JoinConstraint,
+    JoinOperator, Limit, MergeAction, MergeClause, MergeStatement, OnConflict, OnConflictAction,
+    OrderByExpr, Query, Select, SelectItem, SelectOption, SelectOptionName, SetExpr, SetOperator,
+    ShowStatement, SubscriptPosition, TableAlias, TableFactor, TableFunction, TableWithJoins,
+    UnresolvedObjectName, UpdateStatement, Value, Values, WindowFrame, WindowFrameBound,
+    WindowFrameUnits, WindowSpec,
 };
 
 use crate::catalog::{CatalogItemType, CatalogType, SessionCatalog};
@@ -72,10 +73,10 @@ use crate::names::{Aug, PartialObjectName, ResolvedDataType, ResolvedObjectName}
 use crate::normalize;
 use crate::plan::error::PlanError;
 use crate::plan::expr::{
-    AbstractColumnType, AbstractExpr, AggregateExpr, AggregateFunc, BinaryFunc,
-    CoercibleScalarExpr, ColumnOrder, ColumnRef, Hir, HirRelationExpr, HirScalarExpr, JoinKind,
-    ScalarWindowExpr, ScalarWindowFunc, UnaryFunc, ValueWindowExpr, VariadicFunc, WindowExpr,
-    WindowExprType,
+    AbstractColumnType, AbstractExpr, AggregateExpr, AggregateFunc, AggregateWindowExpr,
+    BinaryFunc, CoercibleScalarExpr, ColumnOrder, ColumnRef, Hir, HirRelationExpr, HirScalarExpr,
+    JoinKind, ScalarWindowExpr, ScalarWindowFunc, UnaryFunc, ValueWindowExpr, VariadicFunc,
+    WindowExpr, WindowExprType,
 };
 use crate::plan::plan_utils::{self, JoinSide};
 use crate::plan::scope::{Scope, ScopeItem};
@@ -751,6 +752,515 @@ fn handle_mutation_using_clause(
     Ok(get.filter(vec![using_rel_expr.exists()]))
 }
 
+/// Plans `INSERT ... ON CONFLICT` as a read-then-write operation.
+///
+/// The proposed rows are right outer joined against the table on the conflict
+/// target (or, if none was specified, on each of the table's declared keys).
+/// Proposed rows without a conflicting row are inserted, while conflicting rows
+/// are handled as described by the `ON CONFLICT` action. See
+/// [`plan_merge_changes`] for the shape of the returned selection.
+pub fn plan_insert_on_conflict_query(
+    scx: &StatementContext,
+    table_name: ResolvedObjectName,
+    columns: Vec<Ident>,
+    source: InsertSource<Aug>,
+    returning: Vec<SelectItem<Aug>>,
+    mut on_conflict: OnConflict<Aug>,
+) -> Result<(ReadThenWritePlan, PlannedQuery<Vec<HirScalarExpr>>), PlanError> {
+    transform_ast::run_transforms(
+        scx,
+        |t, on_conflict| t.visit_on_conflict_mut(on_conflict),
+        &mut on_conflict,
+    )?;
+
+    let (_, proposed, returning) =
+        plan_insert_query(scx, table_name.clone(), columns, source, returning)?;
+
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot(scx.pcx()?));
+    let (id, get, scope, desc, defaults) = plan_merge_target(&qcx, table_name)?;
+    let arity = desc.arity();
+
+    let keys = if on_conflict.target.is_empty() {
+        if let OnConflictAction::DoUpdate { .. } = on_conflict.action {
+            sql_bail!("ON CONFLICT DO UPDATE requires inference specification or constraint name");
+        }
+        // Unlike PostgreSQL, which inserts every row when no conflicts are
+        // possible, reject tables without keys: the statement is meaningless.
+        if desc.typ().keys.is_empty() {
+            sql_bail!(
+                "there is no unique or exclusion constraint matching the ON CONFLICT specification"
+            );
+        }
+        desc.typ().keys.clone()
+    } else {
+        let mut key = vec![];
+        for name in on_conflict.target {
+            let name = normalize::column_name(name);
+            match desc.get_by_name(&name) {
+                Some((idx, _)) => key.push(idx),
+                None => sql_bail!("column {} does not exist", name.as_str().quoted()),
+            }
+        }
+        // Like PostgreSQL, the conflict target must name exactly the columns
+        // of one of the table's keys, in any order.
+        let key_set: BTreeSet<_> = key.iter().copied().collect();
+        if !desc
+            .typ()
+            .keys
+            .iter()
+            .any(|k| k.iter().copied().collect::<BTreeSet<_>>() == key_set)
+        {
+            sql_bail!(
+                "there is no unique or exclusion constraint matching the ON CONFLICT specification"
+            );
+        }
+        vec![key]
+    };
+
+    // Join the table, extended with a marker column that indicates whether the
+    // proposed row conflicts, to the proposed rows.
+    let on = HirScalarExpr::variadic_or(
+        keys.iter()
+            .map(|key| {
+                HirScalarExpr::variadic_and(
+                    key.iter()
+                        .map(|i| {
+                            HirScalarExpr::column(*i)
+                                .call_binary(HirScalarExpr::column(arity + 1 + *i), BinaryFunc::Eq)
+                        })
+                        .collect(),
+                )
+            })
+            .collect(),
+    );
+    let joined = get.clone().map(vec![HirScalarExpr::literal_true()]).join(
+        proposed.clone(),
+        on,
+        JoinKind::RightOuter,
+    );
+    let mut joined_scope = scope;
+    joined_scope.items.push(ScopeItem::empty());
+    let excluded_scope = Scope::from_source(
+        Some(PartialObjectName {
+            database: None,
+            schema: None,
+            item: "excluded".into(),
+        }),
+        desc.iter_names(),
+    );
+    let joined_scope = joined_scope.product(excluded_scope)?;
+
+    // Express the `ON CONFLICT` action as the equivalent `MERGE` clauses.
+    let mut clauses = vec![MergeClause {
+        matched: false,
+        condition: None,
+        action: MergeAction::Insert {
+            columns: vec![],
+            values: desc
+                .iter_names()
+                .map(|name| {
+                    Expr::Identifier(vec![Ident::new("excluded"), Ident::new(name.as_str())])
+                })
+                .collect(),
+        },
+    }];
+    if let OnConflictAction::DoUpdate {
+        assignments,
+        selection,
+    } = on_conflict.action
+    {
+        clauses.push(MergeClause {
+            matched: true,
+            condition: selection,
+            action: MergeAction::Update { assignments },
+        });
+    }
+
+    let mut changes =
+        plan_merge_changes(&qcx, &desc, &defaults, get, joined, &joined_scope, clauses)?;
+
+    // Proposed rows may not conflict with one another, as the outcome would
+    // depend on the order in which they are applied. Unlike PostgreSQL, this
+    // is an error even for `DO NOTHING`, where PostgreSQL inserts whichever of
+    // the conflicting rows comes first.
+    for key in keys {
+        let duplicates = proposed
+            .clone()
+            .filter(
+                key.iter()
+                    .map(|i| HirScalarExpr::column(*i).call_is_null().not())
+                    .collect(),
+            )
+            .reduce(
+                key.clone(),
+                vec![AggregateExpr {
+                    func: AggregateFunc::Count,
+                    expr: Box::new(HirScalarExpr::literal_true()),
+                    distinct: false,
+                }],
+                None,
+            )
+            .filter(vec![HirScalarExpr::column(key.len()).call_binary(
+                HirScalarExpr::literal(Datum::Int64(1), ScalarType::Int64),
+                BinaryFunc::Gt,
+            )]);
+        changes = changes.union(merge_conflict_rows(&desc, duplicates));
+    }
+
+    Ok((
+        ReadThenWritePlan {
+            id,
+            selection: changes,
+            assignments: HashMap::new(),
+            finishing: merge_finishing(arity),
+        },
+        returning,
+    ))
+}
+
+/// Plans `MERGE` as a read-then-write operation.
+///
+/// The `USING` relation is right outer joined against the target table, and
+/// each joined row is handled by the first `WHEN` clause that applies to it.
+/// See [`plan_merge_changes`] for the shape of the returned selection.
+pub fn plan_merge_query(
+    scx: &StatementContext,
+    mut merge_stmt: MergeStatement<Aug>,
+) -> Result<ReadThenWritePlan, PlanError> {
+    transform_ast::run_transforms(
+        scx,
+        |t, merge_stmt| t.visit_merge_statement_mut(merge_stmt),
+        &mut merge_stmt,
+    )?;
+
+    let MergeStatement {
+        table_name,
+        alias,
+        using,
+        on,
+        clauses,
+    } = merge_stmt;
+
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot(scx.pcx()?));
+    let (id, get, scope, desc, defaults) = plan_merge_target(&qcx, table_name)?;
+    let scope = plan_table_alias(scope, alias.as_ref())?;
+    let arity = desc.arity();
+
+    // Extend the table with a marker column that indicates whether a joined
+    // row matched a row in the table.
+    let target = get.clone().map(vec![HirScalarExpr::literal_true()]);
+    let mut target_scope = scope;
+    target_scope.items.push(ScopeItem::empty());
+    let (joined, joined_scope) = plan_join(
+        &qcx,
+        target,
+        target_scope,
+        &Join {
+            relation: using,
+            join_operator: JoinOperator::RightOuter(JoinConstraint::On(on)),
+        },
+    )?;
+
+    let changes = plan_merge_changes(&qcx, &desc, &defaults, get, joined, &joined_scope, clauses)?;
+    Ok(ReadThenWritePlan {
+        id,
+        selection: changes,
+        assignments: HashMap::new(),
+        finishing: merge_finishing(arity),
+    })
+}
+
+/// Validates the target table of an `INSERT ... ON CONFLICT` or `MERGE`,
+/// returning its ID, a `Get` of the table and its scope, and its description
+/// and column defaults.
+fn plan_merge_target(
+    qcx: &QueryContext,
+    table_name: ResolvedObjectName,
+) -> Result<
+    (
+        GlobalId,
+        HirRelationExpr,
+        Scope,
+        RelationDesc,
+        Vec<Expr<Aug>>,
+    ),
+    PlanError,
+> {
+    let id = match table_name {
+        ResolvedObjectName::Object { id, .. } => id,
+        _ => sql_bail!("cannot mutate non-user table"),
+    };
+    let item = qcx.scx.get_item(&id);
+    if item.item_type() != CatalogItemType::Table {
+        sql_bail!(
+            "cannot mutate {} '{}'",
+            item.item_type(),
+            table_name.full_name_str()
+        );
+    }
+    if id.is_system() {
+        sql_bail!(
+            "cannot mutate system table '{}'",
+            table_name.full_name_str()
+        );
+    }
+    let desc = item
+        .desc(&qcx.scx.catalog.resolve_full_name(item.name()))?
+        .into_owned();
+    let defaults = item
+        .table_details()
+        .expect("attempted to mutate non-table")
+        .to_vec();
+    let (get, scope) = qcx.resolve_table_name(table_name)?;
+    Ok((id, get, scope, desc, defaults))
+}
+
+/// Plans the changes described by `clauses` against `joined`, the right outer
+/// join of the target table, extended with a non-null marker column, with the
+/// source relation.
+///
+/// Each row of the returned relation describes one change to the table, laid
+/// out as `[old row..., new row..., has_old, has_new]`. Rows in which both
+/// `has_old` and `has_new` are `NULL` indicate that some row of the table would
+/// be affected more than once, which the sequencer reports as an error.
+fn plan_merge_changes(
+    qcx: &QueryContext,
+    desc: &RelationDesc,
+    defaults: &[Expr<Aug>],
+    get: HirRelationExpr,
+    joined: HirRelationExpr,
+    scope: &Scope,
+    clauses: Vec<MergeClause<Aug>>,
+) -> Result<HirRelationExpr, PlanError> {
+    let arity = desc.arity();
+    let relation_type = qcx.relation_type(&joined);
+    let matched = HirScalarExpr::column(arity).call_is_null().not();
+
+    // Determine which clause, if any, applies to each joined row: the first
+    // clause whose `MATCHED` state and condition are both satisfied.
+    let mut applies = vec![];
+    for clause in &clauses {
+        let mut predicate = if clause.matched {
+            matched.clone()
+        } else {
+            matched.clone().not()
+        };
+        if let Some(condition) = &clause.condition {
+            let ecx = &ExprContext {
+                qcx,
+                name: "WHEN clause",
+                scope,
+                relation_type: &relation_type,
+                allow_aggregates: false,
+                allow_subqueries: true,
+                allow_windows: false,
+            };
+            predicate = predicate.and(plan_expr(ecx, condition)?.type_as(ecx, &ScalarType::Bool)?);
+        }
+        applies.push(predicate);
+    }
+    let clause_idx = applies.into_iter().enumerate().rev().fold(
+        HirScalarExpr::literal_null(ScalarType::Int32),
+        |els, (i, cond)| HirScalarExpr::If {
+            cond: Box::new(cond),
+            then: Box::new(HirScalarExpr::literal(
+                Datum::Int32(i32::try_from(i).expect("too many WHEN clauses")),
+                ScalarType::Int32,
+            )),
+            els: Box::new(els),
+        },
+    );
+    let joined = joined.map(vec![clause_idx]);
+    let clause_idx_col = relation_type.arity();
+
+    let ecx = &ExprContext {
+        qcx,
+        name: "WHEN clause",
+        scope,
+        relation_type: &relation_type,
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_windows: false,
+    };
+    let old = || (0..arity).map(HirScalarExpr::column).collect::<Vec<_>>();
+    let nulls = || {
+        desc.iter_types()
+            .map(|typ| HirScalarExpr::literal_null(typ.scalar_type.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    let mut changes: Option<HirRelationExpr> = None;
+    for (i, clause) in clauses.into_iter().enumerate() {
+        let (mut exprs, has_old, has_new) = match clause.action {
+            MergeAction::DoNothing => continue,
+            MergeAction::Update { assignments } => {
+                let mut new = old();
+                let mut set = HashSet::new();
+                for Assignment { id, value } in assignments {
+                    let name = normalize::column_name(id);
+                    match desc.get_by_name(&name) {
+                        Some((idx, typ)) => {
+                            if !set.insert(idx) {
+                                sql_bail!("column {} set twice", name)
+                            }
+                            new[idx] = plan_expr(ecx, &value)?.cast_to(
+                                ecx,
+                                CastContext::Assignment,
+                                &typ.scalar_type,
+                            )?;
+                        }
+                        None => sql_bail!("unknown column {}", name),
+                    }
+                }
+                let mut exprs = old();
+                exprs.extend(new);
+                (exprs, true, true)
+            }
+            MergeAction::Delete => {
+                let mut exprs = old();
+                exprs.extend(nulls());
+                (exprs, true, false)
+            }
+            MergeAction::Insert { columns, values } => {
+                let columns = if columns.is_empty() {
+                    (0..arity).take(values.len()).collect::<Vec<_>>()
+                } else {
+                    let mut idxs = vec![];
+                    for name in columns {
+                        let name = normalize::column_name(name);
+                        match desc.get_by_name(&name) {
+                            Some((idx, _)) if idxs.contains(&idx) => {
+                                sql_bail!(
+                                    "column {} specified more than once",
+                                    name.as_str().quoted()
+                                )
+                            }
+                            Some((idx, _)) => idxs.push(idx),
+                            None => sql_bail!("column {} does not exist", name.as_str().quoted()),
+                        }
+                    }
+                    idxs
+                };
+                if values.len() > columns.len() {
+                    sql_bail!("INSERT has more expressions than target columns");
+                }
+                if values.len() < columns.len() {
+                    sql_bail!("INSERT has more target columns than expressions");
+                }
+                let mut new = vec![None; arity];
+                for (idx, value) in columns.into_iter().zip(values) {
+                    let typ = &desc.typ().column_types[idx].scalar_type;
+                    new[idx] =
+                        Some(plan_expr(ecx, &value)?.cast_to(ecx, CastContext::Assignment, typ)?);
+                }
+                let mut exprs = nulls();
+                for (idx, (expr, typ)) in new.into_iter().zip(desc.iter_types()).enumerate() {
+                    exprs.push(match expr {
+                        Some(expr) => expr,
+                        None => plan_default_expr(qcx.scx, &defaults[idx], &typ.scalar_type)?,
+                    });
+                }
+                (exprs, false, true)
+            }
+        };
+        for flag in [has_old, has_new] {
+            exprs.push(if flag {
+                HirScalarExpr::literal_true()
+            } else {
+                HirScalarExpr::literal_false()
+            });
+        }
+        let change = joined
+            .clone()
+            .filter(vec![HirScalarExpr::column(clause_idx_col).call_binary(
+                HirScalarExpr::literal(
+                    Datum::Int32(i32::try_from(i).expect("too many WHEN clauses")),
+                    ScalarType::Int32,
+                ),
+                BinaryFunc::Eq,
+            )])
+            .map(exprs)
+            .project((clause_idx_col + 1..clause_idx_col + 1 + 2 * arity + 2).collect());
+        changes = Some(match changes {
+            Some(changes) => changes.union(change),
+            None => change,
+        });
+    }
+    let changes = match changes {
+        Some(changes) => changes,
+        None => return Ok(HirRelationExpr::constant(vec![], merge_relation_type(desc))),
+    };
+
+    // Each row of the table may be retracted at most once. Count the
+    // retractions of each distinct row, less the number of times the row
+    // occurs in the table, and flag any row for which that count is positive.
+    let retractions = changes
+        .clone()
+        .filter(vec![HirScalarExpr::column(2 * arity)])
+        .project((0..arity).collect())
+        .map(vec![HirScalarExpr::literal(
+            Datum::Int32(1),
+            ScalarType::Int32,
+        )])
+        .union(get.map(vec![HirScalarExpr::literal(
+            Datum::Int32(-1),
+            ScalarType::Int32,
+        )]))
+        .reduce(
+            (0..arity).collect(),
+            vec![AggregateExpr {
+                func: AggregateFunc::SumInt32,
+                expr: Box::new(HirScalarExpr::column(arity)),
+                distinct: false,
+            }],
+            None,
+        )
+        .filter(vec![HirScalarExpr::column(arity).call_binary(
+            HirScalarExpr::literal(Datum::Int64(0), ScalarType::Int64),
+            BinaryFunc::Gt,
+        )]);
+
+    Ok(changes.union(merge_conflict_rows(desc, retractions)))
+}
+
+/// Produces one conflict row, as described in [`plan_merge_changes`], for each
+/// row of `input`.
+fn merge_conflict_rows(desc: &RelationDesc, input: HirRelationExpr) -> HirRelationExpr {
+    let mut exprs = vec![];
+    for _ in 0..2 {
+        exprs.extend(
+            desc.iter_types()
+                .map(|typ| HirScalarExpr::literal_null(typ.scalar_type.clone())),
+        );
+    }
+    exprs.push(HirScalarExpr::literal_null(ScalarType::Bool));
+    exprs.push(HirScalarExpr::literal_null(ScalarType::Bool));
+    input.project(vec![]).map(exprs)
+}
+
+/// The type of the changes returned by [`plan_merge_changes`].
+fn merge_relation_type(desc: &RelationDesc) -> RelationType {
+    let mut column_types = vec![];
+    for _ in 0..2 {
+        column_types.extend(
+            desc.iter_types()
+                .map(|typ| typ.scalar_type.clone().nullable(true)),
+        );
+    }
+    column_types.push(ScalarType::Bool.nullable(true));
+    column_types.push(ScalarType::Bool.nullable(true));
+    RelationType::new(column_types)
+}
+
+fn merge_finishing(arity: usize) -> RowSetFinishing {
+    RowSetFinishing {
+        order_by: vec![],
+        limit: None,
+        offset: 0,
+        project: (0..2 * arity + 2).collect(),
+    }
+}
+
 struct CastRelationError {
     column: usize,
     source_type: ScalarType,
@@ -808,6 +1318,36 @@ where
     Ok(expr.map(map_exprs).project(project_key))
 }
 
+/// Plans the body of a user-defined function that returns a set of rows.
+///
+/// The columns produced by `query` are cast to `column_types`, the types of
+/// the columns that the function is declared to return.
+pub(crate) fn plan_function_body_query(
+    qcx: &mut QueryContext,
+    query: &Query<Aug>,
+    column_types: &[ScalarType],
+) -> Result<HirRelationExpr, PlanError> {
+    let (expr, _scope) = plan_nested_query(qcx, query)?;
+    let arity = qcx.relation_type(&expr).arity();
+    if arity != column_types.len() {
+        sql_bail!(
+            "return type mismatch in function declared to return {} columns: \
+            function body returns {} columns",
+            column_types.len(),
+            arity
+        );
+    }
+    cast_relation(qcx, CastContext::Assignment, expr, column_types).map_err(|e| {
+        sql_err!(
+            "return type mismatch in function declared to return {}: \
+            column {} of function body is of type {}",
+            qcx.humanize_scalar_type(&e.target_type),
+            e.column + 1,
+            qcx.humanize_scalar_type(&e.source_type),
+        )
+    })
+}
+
 /// Plans an expression in the AS OF position of a `SELECT` or `SUBSCRIBE` statement.
 pub fn plan_as_of(
     scx: &StatementContext,
@@ -871,6 +1411,31 @@ pub fn plan_secret_as(
     Ok(expr)
 }
 
+/// Plans the argument of a call to `pg_cancel_backend` or
+/// `pg_terminate_backend`, named `name`.
+pub fn plan_backend_signal_arg(
+    scx: &StatementContext,
+    name: &str,
+    mut expr: Expr<Aug>,
+) -> Result<HirScalarExpr, PlanError> {
+    let scope = Scope::empty();
+    let desc = RelationDesc::empty();
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot(scx.pcx()?));
+
+    transform_ast::transform_expr(scx, &mut expr)?;
+
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name,
+        scope: &scope,
+        relation_type: desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_windows: false,
+    };
+    plan_expr(ecx, &expr)?.cast_to(ecx, CastContext::Implicit, &ScalarType::Int32)
+}
+
 pub fn plan_default_expr(
     scx: &StatementContext,
     expr: &Expr<Aug>,
@@ -1577,8 +2142,8 @@ fn plan_view_select(
         relation_expr = relation_expr.filter(vec![expr]);
     }
 
-    // Step 3. Gather aggregates and table functions.
-    let (aggregates, table_funcs) = {
+    // Step 3. Gather aggregates, grouping operations, and table functions.
+    let (aggregates, groupings, table_funcs) = {
         let mut visitor = AggregateTableFuncVisitor::new(qcx.scx);
         visitor.visit_select_mut(&mut s);
         for o in order_by_exprs.iter_mut() {
@@ -1622,6 +2187,17 @@ fn plan_view_select(
 
     // Step 5. Handle GROUP BY clause.
     let (mut group_scope, select_all_mapping) = {
+        // Expand any `ROLLUP`, `CUBE`, and `GROUPING SETS` constructs into the
+        // grouping sets they describe. A plain `GROUP BY` clause describes
+        // exactly one grouping set.
+        let grouping_sets = expand_grouping_sets(&s.group_by)?;
+        let mut group_by_exprs: Vec<&Expr<Aug>> = vec![];
+        for expr in grouping_sets.iter().flatten() {
+            if !group_by_exprs.contains(expr) {
+                group_by_exprs.push(expr);
+            }
+        }
+
         // Compute GROUP BY expressions.
         let ecx = &ExprContext {
             qcx,
@@ -1637,8 +2213,10 @@ fn plan_view_select(
         let mut group_hir_exprs = vec![];
         let mut group_scope = Scope::empty();
         let mut select_all_mapping = BTreeMap::new();
+        // The position in `group_hir_exprs` of each of `group_by_exprs`.
+        let mut group_by_columns = vec![];
 
-        for group_expr in &s.group_by {
+        for group_expr in group_by_exprs.iter().copied() {
             let (group_expr, expr) = plan_group_by_expr(ecx, group_expr, &projection)?;
             let new_column = group_key.len();
 
@@ -1648,6 +2226,11 @@ fn plan_view_select(
                 // next AST expression to its set
                 if let Some(existing_scope_item) = group_exprs.get_mut(&expr) {
                     existing_scope_item.exprs.insert(group_expr.clone());
+                    let column = group_hir_exprs
+                        .iter()
+                        .position(|e| *e == expr)
+                        .expect("group expression already planned");
+                    group_by_columns.push(column);
                     continue;
                 }
             }
@@ -1673,6 +2256,7 @@ fn plan_view_select(
                 scope_item.exprs.insert(group_expr);
             }
 
+            group_by_columns.push(new_column);
             group_key.push(from_scope.len() + group_exprs.len());
             group_hir_exprs.push(expr.clone());
             group_exprs.insert(expr, scope_item);
@@ -1685,30 +2269,158 @@ fn plan_view_select(
             }
         }
 
+        // Describe each grouping set by the GROUP BY columns it contains.
+        let grouping_sets: Vec<BTreeSet<usize>> = grouping_sets
+            .iter()
+            .map(|set| {
+                set.iter()
+                    .map(|expr| {
+                        let i = group_by_exprs
+                            .iter()
+                            .position(|e| e == expr)
+                            .expect("grouping set expression is a GROUP BY expression");
+                        group_by_columns[i]
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // Plan grouping operations, which are described by the GROUP BY
+        // columns of their arguments.
+        let mut grouping_columns = vec![];
+        for grouping in &groupings {
+            grouping_columns.push(plan_grouping_arguments(ecx, grouping, &group_hir_exprs)?);
+        }
+
         // Plan aggregates.
+        let input_type = qcx.relation_type(&relation_expr.clone().map(group_hir_exprs.clone()));
         let ecx = &ExprContext {
             qcx,
             name: "aggregate function",
             scope: &from_scope,
-            relation_type: &qcx.relation_type(&relation_expr.clone().map(group_hir_exprs.clone())),
+            relation_type: &input_type,
             allow_aggregates: false,
             allow_subqueries: true,
             allow_windows: false,
         };
         let mut agg_exprs = vec![];
+        let mut agg_scope_items = vec![];
         for sql_function in aggregates {
             agg_exprs.push(plan_aggregate(ecx, &sql_function)?);
-            group_scope
-                .items
-                .push(ScopeItem::from_expr(Expr::Function(sql_function.clone())));
+            agg_scope_items.push(ScopeItem::from_expr(Expr::Function(sql_function.clone())));
         }
-        if !agg_exprs.is_empty() || !group_key.is_empty() || s.having.is_some() {
+        let expected_group_size = expected_group_size.map(usize::cast_from);
+        if grouping_sets.len() > 1 || !groupings.is_empty() {
+            // Each grouping set is computed by the same reduction, keyed by the
+            // GROUP BY columns with those outside of the set replaced by
+            // nulls, followed by an identifier of the grouping set and the
+            // values of the grouping operations. Grouping sets that are empty
+            // are instead planned as separate global aggregations, so that
+            // they produce a row even when their input is empty.
+            let key_types = &input_type.column_types[from_scope.len()..];
+
+            let (empty_sets, sets): (Vec<_>, Vec<_>) = (0..)
+                .zip(grouping_sets.iter())
+                .partition(|(_id, set)| set.is_empty());
+
+            let mut grouped = None;
+            if !sets.is_empty() {
+                let arity = from_scope.len();
+                let key_arity = group_hir_exprs.len();
+                let mut rows = vec![];
+                for (id, set) in sets {
+                    let mut row = vec![Datum::Int32(id)];
+                    row.extend(
+                        grouping_columns
+                            .iter()
+                            .map(|columns| Datum::Int32(grouping_value(set, columns))),
+                    );
+                    row.extend((0..key_arity).map(|column| Datum::from(set.contains(&column))));
+                    rows.push(row);
+                }
+                let typ = RelationType::new(
+                    iter::repeat(ScalarType::Int32.nullable(false))
+                        .take(1 + grouping_columns.len())
+                        .chain(iter::repeat(ScalarType::Bool.nullable(false)).take(key_arity))
+                        .collect(),
+                );
+                let sets = HirRelationExpr::constant(rows, typ);
+
+                // The GROUP BY columns, where those outside of the grouping set
+                // are replaced by nulls.
+                let included_start = arity + key_arity + 1 + grouping_columns.len();
+                let masked_keys = key_types
+                    .iter()
+                    .enumerate()
+                    .map(|(column, typ)| HirScalarExpr::If {
+                        cond: Box::new(HirScalarExpr::column(included_start + column)),
+                        then: Box::new(HirScalarExpr::column(arity + column)),
+                        els: Box::new(HirScalarExpr::literal_null(typ.scalar_type.clone())),
+                    })
+                    .collect();
+                let masked_start = included_start + key_arity;
+                let group_key = (masked_start..masked_start + key_arity)
+                    .chain(arity + key_arity..included_start)
+                    .collect();
+
+                grouped = Some(
+                    relation_expr
+                        .clone()
+                        .map(group_hir_exprs.clone())
+                        .join(sets, HirScalarExpr::literal_true(), JoinKind::Inner)
+                        .map(masked_keys)
+                        .reduce(group_key, agg_exprs.clone(), expected_group_size),
+                );
+            }
+            for (id, set) in empty_sets {
+                let mut exprs: Vec<_> = key_types
+                    .iter()
+                    .map(|typ| HirScalarExpr::literal_null(typ.scalar_type.clone()))
+                    .collect();
+                exprs.push(HirScalarExpr::literal(Datum::Int32(id), ScalarType::Int32));
+                exprs.extend(grouping_columns.iter().map(|columns| {
+                    HirScalarExpr::literal(
+                        Datum::Int32(grouping_value(set, columns)),
+                        ScalarType::Int32,
+                    )
+                }));
+                let agg_arity = agg_exprs.len();
+                let project = (agg_arity..agg_arity + exprs.len())
+                    .chain(0..agg_arity)
+                    .collect();
+                let total = relation_expr
+                    .clone()
+                    .reduce(vec![], agg_exprs.clone(), expected_group_size)
+                    .map(exprs)
+                    .project(project);
+                grouped = Some(match grouped {
+                    Some(grouped) => grouped.union(total),
+                    None => total,
+                });
+            }
+            relation_expr = grouped.expect("at least one grouping set");
+
+            // The grouping set identifier is not nameable.
+            group_scope.items.push(ScopeItem::empty());
+            for grouping in groupings {
+                group_scope
+                    .items
+                    .push(ScopeItem::from_expr(Expr::Function(grouping)));
+            }
+            group_scope.items.extend(agg_scope_items);
+            (group_scope, select_all_mapping)
+        } else if !agg_exprs.is_empty()
+            || !group_key.is_empty()
+            || !s.group_by.is_empty()
+            || s.having.is_some()
+        {
             // apply GROUP BY / aggregates
             relation_expr = relation_expr.map(group_hir_exprs).reduce(
                 group_key,
                 agg_exprs,
-                expected_group_size.map(usize::cast_from),
+                expected_group_size,
             );
+            group_scope.items.extend(agg_scope_items);
             (group_scope, select_all_mapping)
         } else {
             // if no GROUP BY, aggregates or having then all columns remain in scope
@@ -2057,6 +2769,157 @@ fn plan_group_by_expr<'a>(
     }
 }
 
+/// The maximum number of grouping sets that a `GROUP BY` clause can describe.
+const MAX_GROUPING_SETS: usize = 4096;
+
+/// The maximum number of elements in a `CUBE` construct.
+const MAX_CUBE_ELEMENTS: usize = 12;
+
+/// Expands the elements of a `GROUP BY` clause into the grouping sets they
+/// describe.
+///
+/// Plain expressions and parenthesized lists belong to every grouping set,
+/// `ROLLUP (a, b)` describes the sets `(a, b)`, `(a)` and `()`, `CUBE (a, b)`
+/// describes every subset of `(a, b)`, and `GROUPING SETS` describes the union
+/// of the sets described by its elements. Multiple elements describe the cross
+/// product of their grouping sets.
+fn expand_grouping_sets(group_by: &[GroupByExpr<Aug>]) -> Result<Vec<Vec<&Expr<Aug>>>, PlanError> {
+    fn element_sets(element: &GroupByExpr<Aug>) -> Result<Vec<Vec<&Expr<Aug>>>, PlanError> {
+        // Returns the expressions of a `ROLLUP` or `CUBE` element.
+        fn unit(element: &GroupByExpr<Aug>) -> Result<Vec<&Expr<Aug>>, PlanError> {
+            match element {
+                GroupByExpr::Expr(expr) => Ok(vec![expr]),
+                GroupByExpr::List(exprs) => Ok(exprs.iter().collect()),
+                _ => sql_bail!("ROLLUP and CUBE cannot be nested"),
+            }
+        }
+
+        let sets = match element {
+            GroupByExpr::Expr(expr) => vec![vec![expr]],
+            GroupByExpr::List(exprs) => vec![exprs.iter().collect()],
+            GroupByExpr::Rollup(elements) => {
+                let units = elements.iter().map(unit).collect::<Result<Vec<_>, _>>()?;
+                (0..=units.len())
+                    .rev()
+                    .map(|len| units[..len].concat())
+                    .collect()
+            }
+            GroupByExpr::Cube(elements) => {
+                if elements.len() > MAX_CUBE_ELEMENTS {
+                    sql_bail!("CUBE is limited to {} elements", MAX_CUBE_ELEMENTS);
+                }
+                let units = elements.iter().map(unit).collect::<Result<Vec<_>, _>>()?;
+                // Enumerate the subsets in the same order as PostgreSQL, from
+                // the full set down to the empty set.
+                (0..1usize << units.len())
+                    .rev()
+                    .map(|mask| {
+                        units
+                            .iter()
+                            .enumerate()
+                            .filter(|(i, _)| mask & (1 << (units.len() - 1 - i)) != 0)
+                            .flat_map(|(_, unit)| unit.iter().copied())
+                            .collect()
+                    })
+                    .collect()
+            }
+            GroupByExpr::GroupingSets(elements) => {
+                let mut sets = vec![];
+                for element in elements {
+                    sets.extend(element_sets(element)?);
+                }
+                sets
+            }
+        };
+        if sets.len() > MAX_GROUPING_SETS {
+            sql_bail!(
+                "too many grouping sets present (maximum {})",
+                MAX_GROUPING_SETS
+            );
+        }
+        Ok(sets)
+    }
+
+    let mut sets = vec![vec![]];
+    for element in group_by {
+        let element_sets = element_sets(element)?;
+        if sets.len() * element_sets.len() > MAX_GROUPING_SETS {
+            sql_bail!(
+                "too many grouping sets present (maximum {})",
+                MAX_GROUPING_SETS
+            );
+        }
+        sets = sets
+            .iter()
+            .cartesian_product(&element_sets)
+            .map(|(set, element_set)| set.iter().chain(element_set).copied().collect())
+            .collect();
+    }
+    Ok(sets)
+}
+
+/// Reports whether `name` refers to the `GROUPING` operation.
+///
+/// `GROUPING` is not a function in the catalog, since its arguments are not
+/// evaluated. Instead, it reports which of its arguments, which must be
+/// `GROUP BY` expressions, are absent from the grouping set of a row.
+fn is_grouping_operation(name: &UnresolvedObjectName) -> bool {
+    matches!(&name.0[..], [ident] if ident.as_str() == "grouping")
+}
+
+/// Plans the arguments of a `GROUPING` operation, returning the index of the
+/// `GROUP BY` expression that each refers to.
+fn plan_grouping_arguments(
+    ecx: &ExprContext,
+    Function::<Aug> {
+        name: _,
+        args,
+        filter,
+        over,
+        distinct,
+    }: &Function<Aug>,
+    group_exprs: &[HirScalarExpr],
+) -> Result<Vec<usize>, PlanError> {
+    let args = match args {
+        FunctionArgs::Args { args, order_by }
+            if order_by.is_empty() && filter.is_none() && over.is_none() && !*distinct =>
+        {
+            args
+        }
+        _ => sql_bail!("GROUPING does not support *, DISTINCT, ORDER BY, FILTER, or OVER"),
+    };
+    if args.is_empty() {
+        sql_bail!("GROUPING requires at least one argument");
+    }
+    // The result is an integer with one bit per argument.
+    if args.len() >= 32 {
+        sql_bail!("GROUPING must have fewer than 32 arguments");
+    }
+    let mut columns = vec![];
+    for arg in args {
+        let expr = plan_expr(ecx, arg)?.type_as_any(ecx)?;
+        match group_exprs.iter().position(|e| *e == expr) {
+            Some(column) => columns.push(column),
+            None => sql_bail!(
+                "arguments to GROUPING must be grouping expressions of the associated query level"
+            ),
+        }
+    }
+    Ok(columns)
+}
+
+/// Computes the value of a `GROUPING` operation, whose arguments refer to the
+/// `GROUP BY` columns in `columns`, for rows of a grouping set.
+///
+/// The value has one bit per argument, with the rightmost bit corresponding
+/// to the last argument, which is set if the argument is not in the grouping
+/// set.
+fn grouping_value(set: &BTreeSet<usize>, columns: &[usize]) -> i32 {
+    columns.iter().fold(0, |value, column| {
+        (value << 1) | i32::from(!set.contains(column))
+    })
+}
+
 /// Plans a slice of `ORDER BY` expressions.
 ///
 /// See `plan_order_by_or_distinct_expr` for details on the `output_columns`
@@ -3935,7 +4798,7 @@ fn plan_aggregate(
         name,
         args,
         filter,
-        over,
+        over: _,
         distinct,
     }: &Function<Aug>,
 ) -> Result<AggregateExpr, PlanError> {
@@ -3957,10 +4820,6 @@ fn plan_aggregate(
         _ => unreachable!("plan_aggregate called on non-aggregate function,"),
     };
 
-    if over.is_some() {
-        bail_unsupported!("aggregate window functions");
-    }
-
     let name = normalize::unresolved_object_name(name.clone())?;
 
     // We follow PostgreSQL's rule here for mapping `count(*)` into the
@@ -4152,9 +5011,23 @@ fn plan_function<'a>(
         distinct,
     }: &'a Function<Aug>,
 ) -> Result<HirScalarExpr, PlanError> {
+    if is_grouping_operation(name) {
+        // Valid grouping operations should already have been caught by
+        // `scope.resolve_expr` in `plan_expr`.
+        if ecx.allow_aggregates {
+            sql_bail!(
+                "arguments to GROUPING must be grouping expressions of the associated query level"
+            );
+        }
+        sql_bail!("grouping operations are not allowed in {}", ecx.name);
+    }
+
     let unresolved_name = normalize::unresolved_object_name(name.clone())?;
 
     let impls = match resolve_func(ecx, name, args)? {
+        Func::Aggregate(_) if over.is_some() => {
+            return plan_aggregate_window_function(ecx, f);
+        }
         Func::Aggregate(_) if ecx.allow_aggregates => {
             // should already have been caught by `scope.resolve_expr` in `plan_expr`
             sql_bail!(
@@ -4261,12 +5134,12 @@ fn plan_function<'a>(
 
 /// Resolves the name to a set of function implementations.
 ///
-/// If the name does not specify a known built-in function, returns an error.
-pub fn resolve_func(
-    ecx: &ExprContext,
+/// If the name does not specify a known function, returns an error.
+pub fn resolve_func<'a>(
+    ecx: &ExprContext<'a>,
     name: &UnresolvedObjectName,
     args: &mz_sql_parser::ast::FunctionArgs<Aug>,
-) -> Result<&'static Func, PlanError> {
+) -> Result<&'a Func, PlanError> {
     if let Ok(i) = ecx.qcx.scx.resolve_function(name.clone()) {
         if let Ok(f) = i.func() {
             return Ok(f);
@@ -4439,9 +5312,7 @@ fn validate_window_function_plan<'a>(
     ),
     PlanError,
 > {
-    if !ecx.allow_windows {
-        sql_bail!("window functions are not allowed in {}", ecx.name);
-    }
+    let (window_spec, window_frame, partition) = plan_window_spec(ecx, name, over)?;
 
     // Various things are duplicated here and in `plan_function` to improve error messages.
 
@@ -4456,6 +5327,42 @@ fn validate_window_function_plan<'a>(
         bail_unsupported!("FILTER in non-aggregate window functions");
     }
 
+    let scalar_args = match &args {
+        FunctionArgs::Star => {
+            sql_bail!("* argument is invalid with non-aggregate function {}", name)
+        }
+        FunctionArgs::Args { args, order_by } => {
+            if !order_by.is_empty() {
+                sql_bail!(
+                    "ORDER BY specified, but {} is not an aggregate function",
+                    name
+                );
+            }
+            plan_exprs(ecx, args)?
+        }
+    };
+
+    Ok((window_spec, window_frame, scalar_args, partition))
+}
+
+/// Plans the window specification of a call to a window function, returning
+/// the planned frame and partition key.
+fn plan_window_spec<'a>(
+    ecx: &ExprContext,
+    name: &ResolvedObjectName,
+    over: &'a Option<WindowSpec<Aug>>,
+) -> Result<
+    (
+        &'a WindowSpec<Aug>,
+        mz_expr::WindowFrame,
+        Vec<HirScalarExpr>,
+    ),
+    PlanError,
+> {
+    if !ecx.allow_windows {
+        sql_bail!("window functions are not allowed in {}", ecx.name);
+    }
+
     let window_spec = match over.as_ref() {
         Some(over) => over,
         None => sql_bail!("window function {} requires an OVER clause", name),
@@ -4464,27 +5371,102 @@ fn validate_window_function_plan<'a>(
         Some(frame) => plan_window_frame(frame)?,
         None => mz_expr::WindowFrame::default(),
     };
+    if window_frame.units == mz_expr::WindowFrameUnits::Groups && window_spec.order_by.is_empty() {
+        sql_bail!("GROUPS mode requires an ORDER BY clause");
+    }
+    // The frame of a RANGE offset is found by adding the offset to, or
+    // subtracting it from, the value of the ORDER BY column.
+    if window_frame.units == mz_expr::WindowFrameUnits::Range
+        && [&window_frame.start_bound, &window_frame.end_bound]
+            .iter()
+            .any(|b| {
+                matches!(
+                    b,
+                    mz_expr::WindowFrameBound::OffsetPreceding(_)
+                        | mz_expr::WindowFrameBound::OffsetFollowing(_)
+                )
+            })
+    {
+        if window_spec.order_by.len() != 1 {
+            sql_bail!("RANGE with offset PRECEDING/FOLLOWING requires exactly one ORDER BY column");
+        }
+        let order_by = plan_expr(ecx, &window_spec.order_by[0].expr)?.type_as_any(ecx)?;
+        match ecx.scalar_type(&order_by) {
+            ScalarType::Int16
+            | ScalarType::Int32
+            | ScalarType::Int64
+            | ScalarType::UInt16
+            | ScalarType::UInt32
+            | ScalarType::UInt64
+            | ScalarType::Float32
+            | ScalarType::Float64
+            | ScalarType::Numeric { .. } => (),
+            typ => sql_bail!(
+                "RANGE with offset PRECEDING/FOLLOWING is not supported for column type {}",
+                ecx.humanize_scalar_type(&typ)
+            ),
+        }
+    }
     let mut partition = Vec::new();
     for expr in &window_spec.partition_by {
         partition.push(plan_expr(ecx, expr)?.type_as_any(ecx)?);
     }
 
-    let scalar_args = match &args {
-        FunctionArgs::Star => {
-            sql_bail!("* argument is invalid with non-aggregate function {}", name)
-        }
+    Ok((window_spec, window_frame, partition))
+}
+
+/// Plans a call to an aggregate function with an OVER clause, like
+/// `sum(x) OVER (PARTITION BY k ORDER BY t ROWS 6 PRECEDING)`.
+fn plan_aggregate_window_function(
+    ecx: &ExprContext,
+    f @ Function {
+        name,
+        args,
+        filter: _,
+        over,
+        distinct,
+    }: &Function<Aug>,
+) -> Result<HirScalarExpr, PlanError> {
+    let (window_spec, window_frame, partition) = plan_window_spec(ecx, name, over)?;
+
+    if *distinct {
+        bail_unsupported!("DISTINCT in aggregate window functions");
+    }
+
+    // Order-sensitive aggregates see the rows of a window frame in the order
+    // of the window, so plan the aggregate with the window's ORDER BY.
+    let args = match args {
+        FunctionArgs::Star => FunctionArgs::Star,
         FunctionArgs::Args { args, order_by } => {
             if !order_by.is_empty() {
-                sql_bail!(
-                    "ORDER BY specified, but {} is not an aggregate function",
-                    name
-                );
+                bail_unsupported!("ORDER BY in aggregate window functions");
+            }
+            FunctionArgs::Args {
+                args: args.clone(),
+                order_by: window_spec.order_by.clone(),
             }
-            plan_exprs(ecx, args)?
         }
     };
+    let aggregate_expr = plan_aggregate(
+        ecx,
+        &Function {
+            args,
+            over: None,
+            ..f.clone()
+        },
+    )?;
 
-    Ok((window_spec, window_frame, scalar_args, partition))
+    let (order_by, col_orders) = plan_function_order_by(ecx, &window_spec.order_by)?;
+
+    Ok(HirScalarExpr::Windowing(WindowExpr {
+        func: WindowExprType::Aggregate(AggregateWindowExpr {
+            aggregate_expr,
+            order_by: col_orders,
+            window_frame,
+        }),
+        partition,
+        order_by,
+    }))
 }
 
 fn plan_window_frame(
@@ -4495,7 +5477,7 @@ fn plan_window_frame(
     }: &WindowFrame,
 ) -> Result<mz_expr::WindowFrame, PlanError> {
     use mz_expr::WindowFrameBound::*;
-    let units = window_frame_unit_ast_to_expr(units)?;
+    let units = window_frame_unit_ast_to_expr(units);
     let start_bound = window_frame_bound_ast_to_expr(start_bound);
     let end_bound = end_bound
         .as_ref()
@@ -4523,13 +5505,6 @@ fn plan_window_frame(
         (_, _) => (),
     }
 
-    // RANGE is only supported in the default frame
-    if units == mz_expr::WindowFrameUnits::Range
-        && (start_bound != UnboundedPreceding || end_bound != CurrentRow)
-    {
-        bail_unsupported!("RANGE in non-default window frames")
-    }
-
     let frame = mz_expr::WindowFrame {
         units,
         start_bound,
@@ -4538,13 +5513,11 @@ fn plan_window_frame(
     Ok(frame)
 }
 
-fn window_frame_unit_ast_to_expr(
-    unit: &WindowFrameUnits,
-) -> Result<mz_expr::WindowFrameUnits, PlanError> {
+fn window_frame_unit_ast_to_expr(unit: &WindowFrameUnits) -> mz_expr::WindowFrameUnits {
     match unit {
-        WindowFrameUnits::Rows => Ok(mz_expr::WindowFrameUnits::Rows),
-        WindowFrameUnits::Range => Ok(mz_expr::WindowFrameUnits::Range),
-        WindowFrameUnits::Groups => bail_unsupported!("GROUPS in window frames"),
+        WindowFrameUnits::Rows => mz_expr::WindowFrameUnits::Rows,
+        WindowFrameUnits::Range => mz_expr::WindowFrameUnits::Range,
+        WindowFrameUnits::Groups => mz_expr::WindowFrameUnits::Groups,
     }
 }
 
@@ -4783,6 +5756,7 @@ fn scalar_type_from_catalog(
 struct AggregateTableFuncVisitor<'a> {
     scx: &'a StatementContext<'a>,
     aggs: Vec<Function<Aug>>,
+    groupings: Vec<Function<Aug>>,
     within_aggregate: bool,
     tables: HashMap<TableFunction<Aug>, String>,
     table_disallowed_context: Vec<&'static str>,
@@ -4795,6 +5769,7 @@ impl<'a> AggregateTableFuncVisitor<'a> {
         AggregateTableFuncVisitor {
             scx,
             aggs: Vec::new(),
+            groupings: Vec::new(),
             within_aggregate: false,
             tables: HashMap::new(),
             table_disallowed_context: Vec::new(),
@@ -4805,7 +5780,14 @@ impl<'a> AggregateTableFuncVisitor<'a> {
 
     fn into_result(
         self,
-    ) -> Result<(Vec<Function<Aug>>, HashMap<TableFunction<Aug>, String>), PlanError> {
+    ) -> Result<
+        (
+            Vec<Function<Aug>>,
+            Vec<Function<Aug>>,
+            HashMap<TableFunction<Aug>, String>,
+        ),
+        PlanError,
+    > {
         match self.err {
             Some(err) => Err(err),
             None => {
@@ -4817,7 +5799,13 @@ impl<'a> AggregateTableFuncVisitor<'a> {
                     .into_iter()
                     .filter(move |agg| seen.insert(agg.clone()))
                     .collect();
-                Ok((aggs, self.tables))
+                let mut seen = HashSet::new();
+                let groupings = self
+                    .groupings
+                    .into_iter()
+                    .filter(move |grouping| seen.insert(grouping.clone()))
+                    .collect();
+                Ok((aggs, groupings, self.tables))
             }
         }
     }
@@ -4825,6 +5813,19 @@ impl<'a> AggregateTableFuncVisitor<'a> {
 
 impl<'a> VisitMut<'_, Aug> for AggregateTableFuncVisitor<'a> {
     fn visit_function_mut(&mut self, func: &mut Function<Aug>) {
+        if is_grouping_operation(&func.name) {
+            if self.within_aggregate {
+                self.err = Some(sql_err!(
+                    "aggregate function calls cannot contain grouping operations"
+                ));
+                return;
+            }
+            // The arguments of a grouping operation are GROUP BY expressions,
+            // so there is nothing to gather from within them.
+            self.groupings.push(func.clone());
+            return;
+        }
+
         let item = match self.scx.resolve_function(func.name.clone()) {
             Ok(i) => i,
             // Catching missing functions later in planning improves error messages.
@@ -4832,7 +5833,9 @@ impl<'a> VisitMut<'_, Aug> for AggregateTableFuncVisitor<'a> {
         };
 
         match item.func() {
-            Ok(Func::Aggregate { .. }) => {
+            // Aggregate window functions are planned like other window
+            // functions, and do not turn the query into an aggregation.
+            Ok(Func::Aggregate { .. }) if func.over.is_none() => {
                 if self.within_aggregate {
                     self.err = Some(sql_err!("nested aggregate functions are not allowed",));
                     return;
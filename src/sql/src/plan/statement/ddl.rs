@@ -32,13 +32,16 @@ use mz_ore::str::StrExt;
 use mz_proto::RustType;
 use mz_repr::adt::interval::Interval;
 use mz_repr::strconv;
-use mz_repr::{ColumnName, ColumnType, GlobalId, RelationDesc, RelationType, ScalarType};
+use mz_repr::{
+    ColumnName, ColumnType, GlobalId, RelationDesc, RelationType, ScalarType, Timestamp,
+};
 use mz_sql_parser::ast::display::comma_separated;
 use mz_sql_parser::ast::{
     AlterSinkAction, AlterSinkStatement, AlterSourceAction, AlterSourceStatement,
     AlterSystemResetAllStatement, AlterSystemResetStatement, AlterSystemSetStatement,
-    CreateTypeListOption, CreateTypeListOptionName, CreateTypeMapOption, CreateTypeMapOptionName,
-    DeferredObjectName, SetVariableValue, SshConnectionOption,
+    AlterTableAction, AlterTableStatement, CreateTypeListOption, CreateTypeListOptionName,
+    CreateTypeMapOption, CreateTypeMapOptionName, DeferredObjectName, SetVariableValue,
+    SshConnectionOption,
 };
 use mz_storage_client::types::connections::aws::{
     AwsAssumeRole, AwsConfig, AwsCredentials, SerdeUri,
@@ -53,11 +56,12 @@ use mz_storage_client::types::sinks::{
     KafkaSinkFormat, SinkEnvelope, StorageSinkConnectionBuilder,
 };
 use mz_storage_client::types::sources::encoding::{
-    included_column_desc, AvroEncoding, ColumnSpec, CsvEncoding, DataEncoding, DataEncodingInner,
-    ProtobufEncoding, RegexEncoding, SourceDataEncoding, SourceDataEncodingInner,
+    included_column_desc, AvroEncoding, AvroOcfEncoding, ColumnSpec, CsvEncoding, DataEncoding,
+    DataEncodingInner, ParquetEncoding, ProtobufEncoding, RegexEncoding, SourceDataEncoding,
+    SourceDataEncodingInner,
 };
 use mz_storage_client::types::sources::{
-    GenericSourceConnection, IncludedColumnPos, KafkaSourceConnection, KeyEnvelope,
+    GenericSourceConnection, IncludedColumnPos, KafkaSourceConnection, KafkaTopics, KeyEnvelope,
     KinesisSourceConnection, LoadGenerator, LoadGeneratorSourceConnection,
     PostgresSourceConnection, PostgresSourceDetails, ProtoPostgresSourceDetails,
     S3SourceConnection, SourceDesc, SourceEnvelope, TestScriptSourceConnection, Timeline,
@@ -65,19 +69,23 @@ use mz_storage_client::types::sources::{
 };
 
 use crate::ast::display::AstDisplay;
+use crate::ast::visit_mut::{self, VisitMut};
 use crate::ast::{
     AlterConnectionStatement, AlterIndexAction, AlterIndexStatement, AlterObjectRenameStatement,
-    AlterSecretStatement, AvroSchema, AvroSchemaOption, AvroSchemaOptionName, AwsConnectionOption,
-    AwsConnectionOptionName, AwsPrivatelinkConnectionOption, AwsPrivatelinkConnectionOptionName,
-    ClusterOption, ClusterOptionName, ColumnOption, Compression, CreateClusterReplicaStatement,
+    AlterObjectSwapStatement, AlterSecretStatement, AlterSubscriptionStatement, AvroSchema,
+    AvroSchemaOption, AvroSchemaOptionName, AwsConnectionOption, AwsConnectionOptionName,
+    AwsPrivatelinkConnectionOption, AwsPrivatelinkConnectionOptionName, ClusterOption,
+    ClusterOptionName, ColumnOption, Compression, CreateClusterReplicaStatement,
     CreateClusterStatement, CreateConnection, CreateConnectionStatement, CreateDatabaseStatement,
-    CreateIndexStatement, CreateMaterializedViewStatement, CreateReferencedSubsources,
-    CreateRoleOption, CreateRoleStatement, CreateSchemaStatement, CreateSecretStatement,
-    CreateSinkConnection, CreateSinkOption, CreateSinkOptionName, CreateSinkStatement,
-    CreateSourceConnection, CreateSourceFormat, CreateSourceOption, CreateSourceOptionName,
-    CreateSourceStatement, CreateSubsourceStatement, CreateTableStatement, CreateTypeAs,
-    CreateTypeStatement, CreateViewStatement, CsrConfigOption, CsrConfigOptionName, CsrConnection,
-    CsrConnectionAvro, CsrConnectionOption, CsrConnectionOptionName, CsrConnectionProtobuf,
+    CreateFunctionBody, CreateFunctionReturns, CreateFunctionStatement, CreateIndexStatement,
+    CreateMaterializedViewStatement, CreateReferencedSubsources, CreateRoleOption,
+    CreateRoleStatement, CreateSchemaStatement, CreateSecretStatement, CreateSinkConnection,
+    CreateSinkOption, CreateSinkOptionName, CreateSinkStatement, CreateSourceConnection,
+    CreateSourceFormat, CreateSourceOption, CreateSourceOptionName, CreateSourceStatement,
+    CreateSubscriptionOption, CreateSubscriptionOptionName, CreateSubscriptionStatement,
+    CreateSubsourceStatement, CreateTableStatement, CreateTypeAs, CreateTypeStatement,
+    CreateViewStatement, CsrConfigOption, CsrConfigOptionName, CsrConnection, CsrConnectionAvro,
+    CsrConnectionOption, CsrConnectionOptionName, CsrConnectionProtobuf, CsrSeedAvro,
     CsrSeedProtobuf, CsvColumns, DbzMode, DropClusterReplicasStatement, DropClustersStatement,
     DropDatabaseStatement, DropObjectsStatement, DropRolesStatement, DropSchemaStatement, Envelope,
     Expr, Format, Ident, IfExistsBehavior, IndexOption, IndexOptionName, KafkaBroker,
@@ -86,10 +94,11 @@ use crate::ast::{
     LoadGeneratorOptionName, ObjectType, PgConfigOption, PgConfigOptionName,
     PostgresConnectionOption, PostgresConnectionOptionName, ProtobufSchema, QualifiedReplica,
     ReplicaDefinition, ReplicaOption, ReplicaOptionName, SourceIncludeMetadata,
-    SourceIncludeMetadataType, SshConnectionOptionName, Statement, TableConstraint,
-    UnresolvedDatabaseName, Value, ViewDefinition,
+    SourceIncludeMetadataType, SshConnectionOptionName, Statement, TableConstraint, TableOption,
+    TableOptionName, UnresolvedDatabaseName, UnresolvedSchemaName, Value, ViewDefinition,
 };
 use crate::catalog::{CatalogItem, CatalogItemType, CatalogType, CatalogTypeDetails};
+use crate::func::{self, UserFuncReturns};
 use crate::kafka_util::{self, KafkaConfigOptionExtracted, KafkaStartOffsetType};
 use crate::names::{
     Aug, FullSchemaName, QualifiedObjectName, RawDatabaseSpecifier, ResolvedClusterName,
@@ -104,16 +113,19 @@ use crate::plan::statement::{StatementContext, StatementDesc};
 use crate::plan::typeconv::{plan_cast, CastContext};
 use crate::plan::with_options::{self, OptionalInterval, TryFromValue};
 use crate::plan::{
-    plan_utils, query, AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan, AlterItemRenamePlan,
-    AlterNoopPlan, AlterOptionParameter, AlterSecretPlan, AlterSinkPlan, AlterSourcePlan,
-    AlterSystemResetAllPlan, AlterSystemResetPlan, AlterSystemSetPlan, ComputeReplicaConfig,
-    ComputeReplicaIntrospectionConfig, CreateComputeInstancePlan, CreateComputeReplicaPlan,
-    CreateConnectionPlan, CreateDatabasePlan, CreateIndexPlan, CreateMaterializedViewPlan,
-    CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan,
-    CreateTablePlan, CreateTypePlan, CreateViewPlan, DropComputeInstancesPlan,
-    DropComputeReplicasPlan, DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan,
-    FullObjectName, HirScalarExpr, Index, Ingestion, MaterializedView, Params, Plan, QueryContext,
-    RotateKeysPlan, Secret, Sink, Source, StorageHostConfig, Table, Type, View,
+    plan_utils, query, AlterComputeInstanceSwapPlan, AlterIndexResetOptionsPlan,
+    AlterIndexSetOptionsPlan, AlterItemRenamePlan, AlterNoopPlan, AlterOptionParameter,
+    AlterSchemaSwapPlan, AlterSecretPlan, AlterSinkPlan, AlterSourcePlan,
+    AlterSourceRefreshSchemaPlan, AlterSubscriptionPlan, AlterSystemResetAllPlan,
+    AlterSystemResetPlan, AlterSystemSetPlan, AlterTablePlan, AlterTablePlanAction,
+    ComputeReplicaConfig, ComputeReplicaIntrospectionConfig, CreateComputeInstancePlan,
+    CreateComputeReplicaPlan, CreateConnectionPlan, CreateDatabasePlan, CreateFunctionPlan,
+    CreateIndexPlan, CreateMaterializedViewPlan, CreateRolePlan, CreateSchemaPlan,
+    CreateSecretPlan, CreateSinkPlan, CreateSourcePlan, CreateSubscriptionPlan, CreateTablePlan,
+    CreateTypePlan, CreateViewPlan, DropComputeInstancesPlan, DropComputeReplicasPlan,
+    DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan, FullObjectName, Function,
+    HirScalarExpr, Index, Ingestion, MaterializedView, Params, Plan, QueryContext, RotateKeysPlan,
+    Secret, Sink, Source, StorageHostConfig, Subscription, Table, Type, View,
 };
 
 pub fn describe_create_database(
@@ -182,6 +194,8 @@ pub fn describe_create_table(
     Ok(StatementDesc::new(None))
 }
 
+generate_extracted_config!(TableOption, (ForkShard, String));
+
 pub fn plan_create_table(
     scx: &StatementContext,
     stmt: CreateTableStatement<Aug>,
@@ -192,6 +206,7 @@ pub fn plan_create_table(
         constraints,
         if_not_exists,
         temporary,
+        with_options,
     } = &stmt;
 
     let names: Vec<_> = columns
@@ -281,6 +296,10 @@ pub fn plan_create_table(
 
     let typ = RelationType::new(column_types).with_keys(keys);
 
+    // The forked shard is validated when the table is created, as only the
+    // coordinator knows which tables the shards belong to.
+    let TableOptionExtracted { fork_shard, .. } = with_options.clone().try_into()?;
+
     let temporary = *temporary;
     let name = if temporary {
         scx.allocate_temporary_qualified_name(normalize::unresolved_object_name(name.to_owned())?)?
@@ -295,6 +314,7 @@ pub fn plan_create_table(
         desc,
         defaults,
         temporary,
+        fork_shard,
     };
     Ok(Plan::CreateTable(CreateTablePlan {
         name,
@@ -392,6 +412,8 @@ pub fn plan_create_source(
                 opt.name != KafkaConfigOptionName::StartOffset
                     && opt.name != KafkaConfigOptionName::StartTimestamp
                     && opt.name != KafkaConfigOptionName::Topic
+                    && opt.name != KafkaConfigOptionName::Topics
+                    && opt.name != KafkaConfigOptionName::TopicPattern
             }) {
                 scx.require_unsafe_mode(&format!("KAFKA CONNECTION option {}", opt.name))?;
             }
@@ -410,15 +432,16 @@ pub fn plan_create_source(
                 kafka_connection.options.insert(k, v);
             }
 
-            let topic = extracted_options
-                .topic
-                .expect("validated exists during purification");
+            let topics = KafkaTopics::try_from(&extracted_options)?;
             let group_id_prefix = extracted_options.group_id_prefix;
 
             let mut start_offsets = HashMap::new();
             match optional_start_offset {
                 None => (),
                 Some(KafkaStartOffsetType::StartOffset(offsets)) => {
+                    if !matches!(topics, KafkaTopics::Topic(_)) {
+                        sql_bail!("START OFFSET requires a single TOPIC");
+                    }
                     for (part, offset) in offsets.iter().enumerate() {
                         if *offset < 0 {
                             sql_bail!("START OFFSET must be a nonnegative integer");
@@ -440,7 +463,7 @@ pub fn plan_create_source(
             let mut connection = KafkaSourceConnection {
                 connection: kafka_connection,
                 connection_id: connection_item.id(),
-                topic,
+                topics,
                 start_offsets,
                 group_id_prefix,
                 environment_id: scx.catalog.config().environment_id.clone(),
@@ -478,12 +501,7 @@ pub fn plan_create_source(
                         connection.include_partition = unwrap_name(item.alias, "partition", pos);
                     }
                     SourceIncludeMetadataType::Topic => {
-                        // TODO(bwm): This requires deeper thought, the current structure of the
-                        // code requires us to clone the topic name around all over the place
-                        // whether or not anyone ever uses it. Considering we expect the
-                        // overwhelming majority of people will *not* want topics in dataflows that
-                        // is an unnacceptable cost.
-                        bail_unsupported!("INCLUDE TOPIC");
+                        connection.include_topic = unwrap_name(item.alias, "topic", pos);
                     }
                     SourceIncludeMetadataType::Offset => {
                         connection.include_offset = unwrap_name(item.alias, "offset", pos);
@@ -579,6 +597,8 @@ pub fn plan_create_source(
                 aws,
                 compression: match compression {
                     Compression::Gzip => mz_storage_client::types::sources::Compression::Gzip,
+                    Compression::Zstd => mz_storage_client::types::sources::Compression::Zstd,
+                    Compression::Bzip2 => mz_storage_client::types::sources::Compression::Bzip2,
                     Compression::None => mz_storage_client::types::sources::Compression::None,
                 },
             });
@@ -1081,7 +1101,13 @@ pub fn plan_create_subsource(
 generate_extracted_config!(
     LoadGeneratorOption,
     (TickInterval, Interval),
-    (ScaleFactor, f64)
+    (ScaleFactor, f64),
+    (Keys, u64, Default(1_000)),
+    (SnapshotRounds, u64, Default(1)),
+    (ValueSize, u64, Default(64)),
+    (BatchSize, u64, Default(1)),
+    (Partitions, u64, Default(1)),
+    (Seed, u64, Default(0))
 );
 
 pub(crate) fn load_generator_ast_to_generator(
@@ -1133,6 +1159,39 @@ pub(crate) fn load_generator_ast_to_generator(
                 count_clerk,
             }
         }
+        mz_sql_parser::ast::LoadGenerator::KeyValue => {
+            let LoadGeneratorOptionExtracted {
+                keys,
+                snapshot_rounds,
+                value_size,
+                batch_size,
+                partitions,
+                seed,
+                ..
+            } = options.to_vec().try_into()?;
+
+            if keys == 0 {
+                sql_bail!("KEYS must be greater than 0");
+            }
+            if batch_size == 0 {
+                sql_bail!("BATCH SIZE must be greater than 0");
+            }
+            if partitions == 0 || partitions > keys {
+                sql_bail!("PARTITIONS must be between 1 and KEYS");
+            }
+            if i32::try_from(partitions).is_err() {
+                sql_bail!("PARTITIONS must be at most {}", i32::MAX);
+            }
+
+            LoadGenerator::KeyValue {
+                keys,
+                snapshot_rounds,
+                value_size,
+                batch_size,
+                partitions,
+                seed,
+            }
+        }
     };
 
     let mut available_subsources = BTreeMap::new();
@@ -1144,6 +1203,7 @@ pub(crate) fn load_generator_ast_to_generator(
                 LoadGenerator::Auction => "auction".into(),
                 LoadGenerator::Datums => "datums".into(),
                 LoadGenerator::Tpch { .. } => "tpch".into(),
+                LoadGenerator::KeyValue { .. } => "key_value".into(),
                 // Please use `snake_case` for any multi-word load generators
                 // that you add.
             },
@@ -1205,6 +1265,18 @@ fn get_encoding(
         && matches!(envelope, Envelope::None);
     let encoding = encoding.into_source_data_encoding(force_nullable_keys);
 
+    // Newline-delimited JSON is only supported for S3 objects. (The
+    // whole-object formats are restricted to S3 sources during purification.)
+    if !matches!(connection, Some(CreateSourceConnection::S3 { .. }))
+        && encoding
+            .key_ref()
+            .into_iter()
+            .chain([encoding.value_ref()])
+            .any(|encoding| matches!(encoding.inner, DataEncodingInner::Json))
+    {
+        bail_unsupported!("JSON sources");
+    }
+
     let requires_keyvalue = matches!(
         envelope,
         Envelope::Debezium(DbzMode::Plain) | Envelope::Upsert
@@ -1406,8 +1478,20 @@ fn get_encoding_inner(
                 },
             })
         }
-        Format::Json => bail_unsupported!("JSON sources"),
+        Format::Json => DataEncodingInner::Json,
         Format::Text => DataEncodingInner::Text,
+        Format::Parquet { schema } => match schema {
+            Some(schema) => DataEncodingInner::Parquet(ParquetEncoding {
+                schema: schema.clone(),
+            }),
+            None => unreachable!("Parquet schema inference should already have been called"),
+        },
+        Format::AvroOcf { schema } => match schema {
+            Some(schema) => DataEncodingInner::AvroOcf(AvroOcfEncoding {
+                schema: schema.clone(),
+            }),
+            None => unreachable!("Avro OCF schema inference should already have been called"),
+        },
     }))
 }
 
@@ -1453,11 +1537,13 @@ fn get_unnamed_key_envelope(key: &DataEncoding) -> Result<KeyEnvelope, PlanError
     //
     // Otherwise it gets the names of the columns in the type
     let is_composite = match key.inner {
-        DataEncodingInner::RowCodec(_) => {
+        DataEncodingInner::Parquet(_) | DataEncodingInner::AvroOcf(_) => {
             sql_bail!("{} sources cannot use INCLUDE KEY", key.op_name())
         }
-        DataEncodingInner::Bytes | DataEncodingInner::Text => false,
-        DataEncodingInner::Avro(_)
+        DataEncodingInner::Bytes | DataEncodingInner::Text | DataEncodingInner::Json => false,
+        // Row-encoded keys, as produced by load generators, name their columns.
+        DataEncodingInner::RowCodec(_)
+        | DataEncodingInner::Avro(_)
         | DataEncodingInner::Csv(_)
         | DataEncodingInner::Protobuf(_)
         | DataEncodingInner::Regex { .. } => true,
@@ -2275,6 +2361,149 @@ generate_extracted_config!(
     (ValueType, ResolvedDataType)
 );
 
+pub fn describe_create_function(
+    _: &StatementContext,
+    _: CreateFunctionStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_create_function(
+    scx: &StatementContext,
+    stmt: CreateFunctionStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let create_sql = normalize::create_statement(scx, Statement::CreateFunction(stmt))?;
+
+    // Plan the function from its normalized definition, in which references
+    // to other functions are fully qualified, so that the body is interpreted
+    // identically now and when the function is reloaded from the catalog.
+    let parsed = crate::parse::parse(&create_sql)?.into_element();
+    let CreateFunctionStatement {
+        name,
+        args,
+        returns,
+        language,
+        mut body,
+    } = match crate::names::resolve(scx.catalog, parsed)?.0 {
+        Statement::CreateFunction(stmt) => stmt,
+        _ => unreachable!("normalized CREATE FUNCTION must be a CREATE FUNCTION statement"),
+    };
+
+    let language = normalize::ident(language);
+    if language != "sql" {
+        bail_unsupported!(format!("LANGUAGE {}", language));
+    }
+
+    let name = scx.allocate_qualified_name(normalize::unresolved_object_name(name)?)?;
+    let full_name = scx.catalog.resolve_full_name(&name);
+    if scx.item_exists(&name)
+        || scx
+            .catalog
+            .resolve_function(&full_name.clone().into())
+            .is_ok()
+    {
+        sql_bail!("catalog item '{}' already exists", full_name);
+    }
+
+    let plan_type = |data_type: &ResolvedDataType| -> Result<ScalarType, PlanError> {
+        let typ = query::scalar_type_from_sql(scx, data_type)?;
+        if typ.is_custom_type() {
+            bail_unsupported!("custom types in function signatures");
+        }
+        Ok(typ)
+    };
+
+    let mut param_types = vec![];
+    let mut param_names = HashMap::new();
+    for (i, arg) in args.iter().enumerate() {
+        param_types.push(plan_type(&arg.data_type)?);
+        if let Some(arg_name) = &arg.name {
+            let arg_name = normalize::ident(arg_name.clone());
+            if param_names.insert(arg_name.clone(), i + 1).is_some() {
+                sql_bail!("parameter name {} used more than once", arg_name.quoted());
+            }
+        }
+    }
+
+    let returns = match returns {
+        CreateFunctionReturns::Scalar(data_type) => UserFuncReturns::Scalar(plan_type(&data_type)?),
+        CreateFunctionReturns::SetOf(data_type) => UserFuncReturns::Table(vec![(
+            ColumnName::from(name.item.clone()),
+            plan_type(&data_type)?,
+        )]),
+        CreateFunctionReturns::Table(columns) => {
+            let mut out = Vec::with_capacity(columns.len());
+            for column in columns {
+                let column_name = column.name.expect("RETURNS TABLE columns are always named");
+                out.push((
+                    normalize::column_name(column_name),
+                    plan_type(&column.data_type)?,
+                ));
+            }
+            UserFuncReturns::Table(out)
+        }
+    };
+
+    // Rewrite references to named parameters as positional parameters, which
+    // are what the function's arguments are bound to when it is called.
+    // Parameters may also be referenced by qualifying them with the name of
+    // the function. Note that, unlike in PostgreSQL, a parameter name takes
+    // precedence over any column of the same name in the body.
+    struct ParamNameRewriter<'a> {
+        func_name: &'a str,
+        param_names: &'a HashMap<String, usize>,
+    }
+
+    impl<'a, 'ast> VisitMut<'ast, Aug> for ParamNameRewriter<'a> {
+        fn visit_expr_mut(&mut self, expr: &'ast mut Expr<Aug>) {
+            if let Expr::Identifier(idents) = expr {
+                let param = match &idents[..] {
+                    [param] => self.param_names.get(&normalize::ident(param.clone())),
+                    [func, param] if normalize::ident(func.clone()) == self.func_name => {
+                        self.param_names.get(&normalize::ident(param.clone()))
+                    }
+                    _ => None,
+                };
+                if let Some(n) = param {
+                    *expr = Expr::Parameter(*n);
+                    return;
+                }
+            }
+            visit_mut::visit_expr_mut(self, expr);
+        }
+    }
+
+    ParamNameRewriter {
+        func_name: &name.item,
+        param_names: &param_names,
+    }
+    .visit_create_function_body_mut(&mut body);
+
+    // Validate the body by planning it once, as it would be planned when the
+    // function is called.
+    let qcx = QueryContext::root(scx, QueryLifetime::Static);
+    match (&returns, &body) {
+        (UserFuncReturns::Scalar(return_type), CreateFunctionBody::Expr(expr)) => {
+            func::plan_user_func_expr(&qcx, &param_types, return_type, expr)?;
+        }
+        (UserFuncReturns::Table(columns), CreateFunctionBody::Query(query)) => {
+            let column_types = columns.iter().map(|(_, typ)| typ.clone()).collect_vec();
+            func::plan_user_func_query(&qcx, &param_types, &column_types, query)?;
+        }
+        _ => unreachable!("parser guarantees that the body matches the return type"),
+    }
+
+    Ok(Plan::CreateFunction(CreateFunctionPlan {
+        name,
+        function: Function {
+            create_sql,
+            param_types,
+            returns,
+            body,
+        },
+    }))
+}
+
 pub fn describe_create_role(
     _: &StatementContext,
     _: CreateRoleStatement,
@@ -2515,6 +2744,62 @@ pub fn plan_create_secret(
     }))
 }
 
+pub fn describe_create_subscription(
+    _: &StatementContext,
+    _: CreateSubscriptionStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+generate_extracted_config!(
+    CreateSubscriptionOption,
+    (Snapshot, bool, Default(true)),
+    (Timestamp, u64)
+);
+
+pub fn plan_create_subscription(
+    scx: &StatementContext,
+    stmt: CreateSubscriptionStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let create_sql = normalize::create_statement(scx, Statement::CreateSubscription(stmt.clone()))?;
+    let CreateSubscriptionStatement {
+        name,
+        if_not_exists,
+        from,
+        with_options,
+    } = stmt;
+
+    let name = scx.allocate_qualified_name(normalize::unresolved_object_name(name)?)?;
+    let from = scx.get_item_by_resolved_name(&from)?;
+    // Only storage collections are durable enough to resume from an old
+    // timestamp after a restart.
+    match from.item_type() {
+        CatalogItemType::Table | CatalogItemType::Source | CatalogItemType::MaterializedView => {}
+        ty => sql_bail!(
+            "{} cannot be subscribed to durably because it is a {}",
+            scx.catalog.resolve_full_name(from.name()),
+            ty,
+        ),
+    }
+
+    let CreateSubscriptionOptionExtracted {
+        snapshot,
+        timestamp,
+        seen: _,
+    } = with_options.try_into()?;
+
+    Ok(Plan::CreateSubscription(CreateSubscriptionPlan {
+        name,
+        subscription: Subscription {
+            create_sql,
+            from: from.id(),
+            timestamp: timestamp.map(Timestamp::from),
+            snapshot,
+        },
+        if_not_exists,
+    }))
+}
+
 pub fn describe_create_connection(
     _: &StatementContext,
     _: CreateConnectionStatement<Aug>,
@@ -3015,7 +3300,12 @@ pub fn plan_drop_objects(
     let mut items = vec![];
     for name in names {
         let name = normalize::unresolved_object_name(name)?;
-        match scx.catalog.resolve_item(&name) {
+        // Functions live in a separate namespace from all other items.
+        let item = match object_type {
+            ObjectType::Func => scx.catalog.resolve_function(&name),
+            _ => scx.catalog.resolve_item(&name),
+        };
+        match item {
             Ok(item) => items.push(item),
             Err(_) if if_exists => {
                 // TODO(benesch/jkosh44): generate a notice indicating items do not exist.
@@ -3033,8 +3323,13 @@ pub fn plan_drop_objects(
         | ObjectType::Sink
         | ObjectType::Type
         | ObjectType::Secret
-        | ObjectType::Connection => plan_drop_items(scx, object_type, &items, cascade),
-        ObjectType::Role | ObjectType::Cluster | ObjectType::ClusterReplica => {
+        | ObjectType::Connection
+        | ObjectType::Subscription
+        | ObjectType::Func => plan_drop_items(scx, object_type, &items, cascade),
+        ObjectType::Role
+        | ObjectType::Cluster
+        | ObjectType::ClusterReplica
+        | ObjectType::Schema => {
             unreachable!("handled through their respective plan_drop functions")
         }
         ObjectType::Object => unreachable!("cannot drop generic OBJECT, must provide object type"),
@@ -3321,7 +3616,7 @@ pub fn plan_drop_item(
 /// Does the dependency `dep` prevent a drop of a non-cascade query?
 fn dependency_prevents_drop(object_type: ObjectType, dep: &dyn CatalogItem) -> bool {
     match object_type {
-        ObjectType::Type => true,
+        ObjectType::Type | ObjectType::Func => true,
         _ => match dep.item_type() {
             CatalogItemType::Func
             | CatalogItemType::Table
@@ -3331,7 +3626,8 @@ fn dependency_prevents_drop(object_type: ObjectType, dep: &dyn CatalogItem) -> b
             | CatalogItemType::Sink
             | CatalogItemType::Type
             | CatalogItemType::Secret
-            | CatalogItemType::Connection => true,
+            | CatalogItemType::Connection
+            | CatalogItemType::Subscription => true,
             CatalogItemType::Index => false,
         },
     }
@@ -3475,6 +3771,75 @@ pub fn plan_alter_object_rename(
     }
 }
 
+pub fn describe_alter_object_swap(
+    _: &StatementContext,
+    _: AlterObjectSwapStatement,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_object_swap(
+    scx: &StatementContext,
+    AlterObjectSwapStatement {
+        object_type,
+        name_a,
+        name_b,
+    }: AlterObjectSwapStatement,
+) -> Result<Plan, PlanError> {
+    match object_type {
+        ObjectType::Schema => {
+            let schema_a = scx.resolve_schema(UnresolvedSchemaName(name_a.0))?;
+            let database_id = match schema_a.database() {
+                ResolvedDatabaseSpecifier::Ambient => sql_bail!(
+                    "cannot swap schema {} because it is required by the database system",
+                    schema_a.name().schema
+                ),
+                ResolvedDatabaseSpecifier::Id(id) => *id,
+            };
+            // The schemas must belong to the same database, so that swapping
+            // them doesn't move any items across databases.
+            let schema_b = scx.resolve_schema_in_database(schema_a.database(), &name_b)?;
+            let (schema_a, schema_b) = match (schema_a.id(), schema_b.id()) {
+                (SchemaSpecifier::Id(a), SchemaSpecifier::Id(b)) => (*a, *b),
+                _ => sql_bail!("cannot swap temporary schemas"),
+            };
+            if schema_a == schema_b {
+                sql_bail!(
+                    "cannot swap schema {} with itself",
+                    name_b.as_str().quoted()
+                );
+            }
+            Ok(Plan::AlterSchemaSwap(AlterSchemaSwapPlan {
+                database_id,
+                schema_a,
+                schema_b,
+            }))
+        }
+        ObjectType::Cluster => {
+            let name_a = name_a.0.into_element();
+            let instance_a = scx
+                .catalog
+                .resolve_compute_instance(Some(name_a.as_str()))?;
+            let instance_b = scx
+                .catalog
+                .resolve_compute_instance(Some(name_b.as_str()))?;
+            if instance_a.id() == instance_b.id() {
+                sql_bail!(
+                    "cannot swap cluster {} with itself",
+                    name_b.as_str().quoted()
+                );
+            }
+            Ok(Plan::AlterComputeInstanceSwap(
+                AlterComputeInstanceSwapPlan {
+                    name_a: instance_a.name().to_string(),
+                    name_b: instance_b.name().to_string(),
+                },
+            ))
+        }
+        _ => unreachable!("only schemas and clusters can be swapped"),
+    }
+}
+
 pub fn describe_alter_secret_options(
     _: &StatementContext,
     _: AlterSecretStatement<Aug>,
@@ -3523,6 +3888,55 @@ pub fn describe_alter_sink(
     Ok(StatementDesc::new(None))
 }
 
+pub fn describe_alter_subscription(
+    _: &StatementContext,
+    _: AlterSubscriptionStatement,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_subscription(
+    scx: &StatementContext,
+    AlterSubscriptionStatement {
+        name,
+        if_exists,
+        timestamp,
+    }: AlterSubscriptionStatement,
+) -> Result<Plan, PlanError> {
+    let name = normalize::unresolved_object_name(name)?;
+    let entry = match scx.catalog.resolve_item(&name) {
+        Ok(entry) => entry,
+        Err(_) if if_exists => {
+            return Ok(Plan::AlterNoop(AlterNoopPlan {
+                object_type: ObjectType::Subscription,
+            }));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let full_name = scx.catalog.resolve_full_name(entry.name());
+    let acknowledged = match entry.subscription_details() {
+        Some((_, acknowledged, _)) => acknowledged,
+        None => sql_bail!(
+            "{} is a {} not a subscription",
+            full_name,
+            entry.item_type()
+        ),
+    };
+    let timestamp = Timestamp::from(timestamp);
+    if timestamp < acknowledged {
+        sql_bail!(
+            "cannot acknowledge {} at {}: it has already been acknowledged at {}",
+            full_name,
+            timestamp,
+            acknowledged
+        );
+    }
+    Ok(Plan::AlterSubscription(AlterSubscriptionPlan {
+        id: entry.id(),
+        timestamp,
+    }))
+}
+
 pub fn plan_alter_sink(
     scx: &StatementContext,
     stmt: AlterSinkStatement<Aug>,
@@ -3686,11 +4100,306 @@ pub fn plan_alter_source(
                 }
             }
         }
+        AlterSourceAction::RefreshSchema { seed } => {
+            return plan_alter_source_refresh_schema(scx, entry, seed);
+        }
     };
 
     Ok(Plan::AlterSource(AlterSourcePlan { id, size, remote }))
 }
 
+fn plan_alter_source_refresh_schema(
+    scx: &StatementContext,
+    entry: &dyn CatalogItem,
+    seed: Option<CsrSeedAvro>,
+) -> Result<Plan, PlanError> {
+    let full_name = scx.catalog.resolve_full_name(entry.name());
+
+    // The source gains columns, which running dataflows cannot accommodate.
+    ensure_no_dataflow_dependents(scx, entry)?;
+
+    // Sources are serialized as their purified `CREATE SOURCE` statement, which
+    // records the schemas that were current at creation. We switch the source
+    // to the new schemas by rewriting that statement and planning it anew.
+    let parsed = crate::parse::parse(entry.create_sql())
+        .expect("Sql for existing source should be valid sql")
+        .into_element();
+    let mut create_stmt = match crate::names::resolve(scx.catalog, parsed)?.0 {
+        Statement::CreateSource(stmt) => stmt,
+        _ => panic!("Sql for existing source should parse as a source"),
+    };
+    let current_seed = match &mut create_stmt.format {
+        CreateSourceFormat::Bare(Format::Avro(AvroSchema::Csr {
+            csr_connection: CsrConnectionAvro { seed, .. },
+        })) => seed,
+        _ => sql_bail!(
+            "cannot refresh the schema of source {}: only sources using \
+            FORMAT AVRO USING CONFLUENT SCHEMA REGISTRY support REFRESH SCHEMA",
+            full_name
+        ),
+    };
+    let old_seed = current_seed
+        .take()
+        .expect("CREATE SOURCE statements are purified");
+    let seed = match seed {
+        Some(seed) => seed,
+        None => sql_bail!("[internal error] ALTER SOURCE ... REFRESH SCHEMA was not purified"),
+    };
+
+    // Data that was written with the old schemas must remain readable.
+    mz_interchange::avro::check_backward_compatibility(&old_seed.value_schema, &seed.value_schema)
+        .map_err(|e| {
+            sql_err!(
+                "new value schema of source {} is not backward compatible: {:#}",
+                full_name,
+                e
+            )
+        })?;
+    if let Some(old_key_schema) = &old_seed.key_schema {
+        let key_schema = match &seed.key_schema {
+            Some(key_schema) => key_schema,
+            None => sql_bail!("key schema of source {} no longer exists", full_name),
+        };
+        mz_interchange::avro::check_backward_compatibility(old_key_schema, key_schema).map_err(
+            |e| {
+                sql_err!(
+                    "new key schema of source {} is not backward compatible: {:#}",
+                    full_name,
+                    e
+                )
+            },
+        )?;
+    }
+    *current_seed = Some(seed);
+
+    let source = match plan_create_source(scx, create_stmt)? {
+        Plan::CreateSource(CreateSourcePlan { source, .. }) => source,
+        _ => unreachable!("plan_create_source returns CreateSource plans"),
+    };
+
+    // Existing rows are padded with nulls when read, so the source may only
+    // gain trailing nullable columns.
+    let old_desc = entry.desc(&full_name)?;
+    let arity = old_desc.arity();
+    if source.desc.arity() < arity || !old_desc.iter().eq(source.desc.iter().take(arity)) {
+        sql_bail!(
+            "cannot refresh the schema of source {}: the new schema changes existing columns",
+            full_name
+        );
+    }
+    for (name, typ) in source.desc.iter().skip(arity) {
+        if !typ.nullable {
+            sql_bail!(
+                "cannot refresh the schema of source {}: new column {} must be nullable",
+                full_name,
+                name.as_str().quoted()
+            );
+        }
+    }
+
+    Ok(Plan::AlterSourceRefreshSchema(
+        AlterSourceRefreshSchemaPlan {
+            id: entry.id(),
+            source,
+        },
+    ))
+}
+
+pub fn describe_alter_table(
+    _: &StatementContext,
+    _: AlterTableStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_table(
+    scx: &StatementContext,
+    stmt: AlterTableStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let AlterTableStatement {
+        table_name,
+        if_exists,
+        action,
+    } = stmt;
+    let table_name = normalize::unresolved_object_name(table_name)?;
+    let entry = match scx.catalog.resolve_item(&table_name) {
+        Ok(table) => table,
+        Err(_) if if_exists => {
+            return Ok(Plan::AlterNoop(AlterNoopPlan {
+                object_type: ObjectType::Table,
+            }));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let full_name = scx.catalog.resolve_full_name(entry.name());
+    if entry.item_type() != CatalogItemType::Table {
+        sql_bail!("{} is a {} not a table", full_name, entry.item_type())
+    }
+    if entry.id().is_system() {
+        sql_bail!(
+            "cannot alter item {} because it is required by the database system",
+            full_name
+        );
+    }
+    let id = entry.id();
+
+    // Adding or removing a column changes the arity of the table's rows, which
+    // would invalidate the plans of any running dataflows that read from it.
+    ensure_no_dataflow_dependents(scx, entry)?;
+
+    // Tables are serialized as their `CREATE TABLE` statement, so we alter the
+    // table by rewriting that statement and planning it anew.
+    let parsed = crate::parse::parse(entry.create_sql())
+        .expect("Sql for existing table should be valid sql")
+        .into_element();
+    let mut create_stmt = match crate::names::resolve(scx.catalog, parsed)?.0 {
+        Statement::CreateTable(stmt) => stmt,
+        _ => panic!("Sql for existing table should parse as a table"),
+    };
+
+    let action = match action {
+        AlterTableAction::AddColumn {
+            if_not_exists,
+            column_def,
+        } => {
+            let column_name = normalize::column_name(column_def.name.clone());
+            if create_stmt
+                .columns
+                .iter()
+                .any(|c| normalize::column_name(c.name.clone()) == column_name)
+            {
+                if if_not_exists {
+                    return Ok(Plan::AlterNoop(AlterNoopPlan {
+                        object_type: ObjectType::Table,
+                    }));
+                }
+                sql_bail!(
+                    "column {} of relation {} already exists",
+                    column_name.as_str().quoted(),
+                    full_name
+                );
+            }
+
+            // The table's dependencies are fixed at creation, so the new column
+            // may only use user-defined types the table already depends on.
+            let mut types = vec![&column_def.data_type];
+            while let Some(ty) = types.pop() {
+                match ty {
+                    ResolvedDataType::AnonymousList(element_type) => types.push(element_type),
+                    ResolvedDataType::AnonymousMap {
+                        key_type,
+                        value_type,
+                    } => types.extend([&**key_type, &**value_type]),
+                    ResolvedDataType::Named { id, full_name, .. }
+                        if id.is_user() && !entry.uses().contains(id) =>
+                    {
+                        bail_unsupported!(format!(
+                            "ALTER TABLE ... ADD COLUMN with new type dependency {}",
+                            full_name
+                        ))
+                    }
+                    ResolvedDataType::Named { .. } | ResolvedDataType::Error => {}
+                }
+            }
+            let ty = query::scalar_type_from_sql(scx, &column_def.data_type)?;
+            let mut default = Expr::null();
+            for option in &column_def.options {
+                match &option.option {
+                    ColumnOption::NotNull => {}
+                    ColumnOption::Default(expr) => default = expr.clone(),
+                    other => bail_unsupported!(format!(
+                        "ALTER TABLE ... ADD COLUMN with column constraint: {}",
+                        other
+                    )),
+                }
+            }
+            let default = query::plan_default_expr(scx, &default, &ty)?.lower_uncorrelated()?;
+            if default.contains_temporal() {
+                sql_bail!("calls to mz_now in DEFAULT expressions are not supported");
+            }
+
+            create_stmt.columns.push(column_def);
+            AlterTablePlanAction::AddColumn { default }
+        }
+        AlterTableAction::DropColumn { if_exists, name } => {
+            let column_name = normalize::column_name(name);
+            let index = match create_stmt
+                .columns
+                .iter()
+                .position(|c| normalize::column_name(c.name.clone()) == column_name)
+            {
+                Some(index) => index,
+                None if if_exists => {
+                    return Ok(Plan::AlterNoop(AlterNoopPlan {
+                        object_type: ObjectType::Table,
+                    }));
+                }
+                None => sql_bail!(
+                    "column {} of relation {} does not exist",
+                    column_name.as_str().quoted(),
+                    full_name
+                ),
+            };
+            for constraint in &create_stmt.constraints {
+                if let TableConstraint::Unique { columns, .. } = constraint {
+                    if columns
+                        .iter()
+                        .any(|c| normalize::column_name(c.clone()) == column_name)
+                    {
+                        sql_bail!(
+                            "cannot drop column {} of relation {} because it is part of a key constraint",
+                            column_name.as_str().quoted(),
+                            full_name
+                        );
+                    }
+                }
+            }
+
+            create_stmt.columns.remove(index);
+            AlterTablePlanAction::DropColumn { index }
+        }
+    };
+
+    let table = match plan_create_table(scx, create_stmt)? {
+        Plan::CreateTable(CreateTablePlan { table, .. }) => table,
+        _ => unreachable!("plan_create_table returns CreateTable plans"),
+    };
+
+    Ok(Plan::AlterTable(AlterTablePlan { id, table, action }))
+}
+
+/// Errors if a dataflow, i.e. an index, materialized view, or sink, depends on
+/// `entry` directly or through views.
+///
+/// Used by alterations that change the shape of an item's rows, which running
+/// dataflows cannot accommodate.
+fn ensure_no_dataflow_dependents(
+    scx: &StatementContext,
+    entry: &dyn CatalogItem,
+) -> Result<(), PlanError> {
+    let mut to_visit = entry.used_by().to_vec();
+    let mut seen = BTreeSet::new();
+    while let Some(dep_id) = to_visit.pop() {
+        if !seen.insert(dep_id) {
+            continue;
+        }
+        let dep = scx.catalog.get_item(&dep_id);
+        match dep.item_type() {
+            CatalogItemType::Index | CatalogItemType::MaterializedView | CatalogItemType::Sink => {
+                sql_bail!(
+                    "cannot alter {} {} because {} {} depends on it",
+                    entry.item_type(),
+                    scx.catalog.resolve_full_name(entry.name()),
+                    dep.item_type(),
+                    scx.catalog.resolve_full_name(dep.name())
+                )
+            }
+            _ => to_visit.extend(dep.used_by()),
+        }
+    }
+    Ok(())
+}
+
 pub fn describe_alter_system_set(
     _: &StatementContext,
     _: AlterSystemSetStatement,
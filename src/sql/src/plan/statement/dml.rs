@@ -15,29 +15,35 @@
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 
-use mz_expr::MirRelationExpr;
+use mz_expr::{MirRelationExpr, MirScalarExpr};
 use mz_ore::collections::CollectionExt;
 use mz_pgcopy::{CopyCsvFormatParams, CopyFormatParams, CopyTextFormatParams};
 use mz_repr::adt::numeric::NumericMaxScale;
 use mz_repr::explain_new::{ExplainConfig, ExplainFormat};
-use mz_repr::{RelationDesc, ScalarType};
+use mz_repr::{Datum, GlobalId, RelationDesc, ScalarType};
+use mz_storage_client::types::connections::Connection;
 
 use crate::ast::display::AstDisplay;
 use crate::ast::{
     AstInfo, CopyDirection, CopyOption, CopyOptionName, CopyRelation, CopyStatement, CopyTarget,
     CreateMaterializedViewStatement, CreateViewStatement, DeleteStatement, ExplainStage,
-    ExplainStatement, Explainee, Ident, InsertStatement, Query, SelectStatement, Statement,
+    ExplainStatement, Explainee, Expr, Function, FunctionArgs, Ident, InsertStatement,
+    MergeStatement, Query, Select, SelectItem, SelectStatement, SetExpr, Statement,
     SubscribeOption, SubscribeOptionName, SubscribeRelation, SubscribeStatement, UpdateStatement,
     ViewDefinition,
 };
 use crate::catalog::CatalogItemType;
 use crate::names::{self, Aug, ResolvedObjectName};
+use crate::normalize;
+use crate::plan::expr::HirScalarExpr;
 use crate::plan::query::QueryLifetime;
-use crate::plan::statement::{StatementContext, StatementDesc};
-use crate::plan::with_options::TryFromValue;
+use crate::plan::statement::{show, StatementContext, StatementDesc};
+use crate::plan::with_options::{self, TryFromValue};
 use crate::plan::{
-    query, CopyFormat, CopyFromPlan, ExplainPlan, InsertPlan, MutationKind, Params, PeekPlan, Plan,
-    PlanError, QueryContext, ReadThenWritePlan, SubscribeFrom, SubscribePlan,
+    query, BackendSignal, CopyFormat, CopyFromPlan, CopyFromUrlFormat, CopyFromUrlPlan,
+    CopyToUrlPlan, CopyUrl, ExplainPlan, InsertPlan, MutationKind, Params, PeekPlan, Plan,
+    PlanError, QueryContext, QueryWhen, ReadThenWritePlan, SignalBackendPlan, SubscribeFrom,
+    SubscribePlan,
 };
 
 // TODO(benesch): currently, describing a `SELECT` or `INSERT` query
@@ -52,10 +58,24 @@ pub fn describe_insert(
         table_name,
         columns,
         source,
+        on_conflict,
         returning,
     }: InsertStatement<Aug>,
 ) -> Result<StatementDesc, PlanError> {
-    let (_, _, returning) = query::plan_insert_query(scx, table_name, columns, source, returning)?;
+    let returning = match on_conflict {
+        Some(on_conflict) => {
+            let (_, returning) = query::plan_insert_on_conflict_query(
+                scx,
+                table_name,
+                columns,
+                source,
+                returning,
+                on_conflict,
+            )?;
+            returning
+        }
+        None => query::plan_insert_query(scx, table_name, columns, source, returning)?.2,
+    };
     let desc = if returning.expr.is_empty() {
         None
     } else {
@@ -70,10 +90,23 @@ pub fn plan_insert(
         table_name,
         columns,
         source,
+        on_conflict,
         returning,
     }: InsertStatement<Aug>,
     params: &Params,
 ) -> Result<Plan, PlanError> {
+    if let Some(on_conflict) = on_conflict {
+        let (rtw_plan, returning) = query::plan_insert_on_conflict_query(
+            scx,
+            table_name,
+            columns,
+            source,
+            returning,
+            on_conflict,
+        )?;
+        return plan_read_then_write(MutationKind::Upsert, scx, params, rtw_plan, returning.expr);
+    }
+
     let (id, mut expr, returning) =
         query::plan_insert_query(scx, table_name, columns, source, returning)?;
     expr.bind_parameters(params)?;
@@ -105,7 +138,7 @@ pub fn plan_delete(
     params: &Params,
 ) -> Result<Plan, PlanError> {
     let rtw_plan = query::plan_delete_query(scx, stmt)?;
-    plan_read_then_write(MutationKind::Delete, scx, params, rtw_plan)
+    plan_read_then_write(MutationKind::Delete, scx, params, rtw_plan, vec![])
 }
 
 pub fn describe_update(
@@ -122,7 +155,24 @@ pub fn plan_update(
     params: &Params,
 ) -> Result<Plan, PlanError> {
     let rtw_plan = query::plan_update_query(scx, stmt)?;
-    plan_read_then_write(MutationKind::Update, scx, params, rtw_plan)
+    plan_read_then_write(MutationKind::Update, scx, params, rtw_plan, vec![])
+}
+
+pub fn describe_merge(
+    scx: &StatementContext,
+    stmt: MergeStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    query::plan_merge_query(scx, stmt)?;
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_merge(
+    scx: &StatementContext,
+    stmt: MergeStatement<Aug>,
+    params: &Params,
+) -> Result<Plan, PlanError> {
+    let rtw_plan = query::plan_merge_query(scx, stmt)?;
+    plan_read_then_write(MutationKind::Merge, scx, params, rtw_plan, vec![])
 }
 
 pub fn plan_read_then_write(
@@ -135,6 +185,7 @@ pub fn plan_read_then_write(
         finishing,
         assignments,
     }: query::ReadThenWritePlan,
+    returning: Vec<HirScalarExpr>,
 ) -> Result<Plan, PlanError> {
     selection.bind_parameters(params)?;
     let selection = selection.optimize_and_lower(&scx.into())?;
@@ -144,6 +195,10 @@ pub fn plan_read_then_write(
         let set = set.lower_uncorrelated()?;
         assignments_outer.insert(idx, set);
     }
+    let returning = returning
+        .into_iter()
+        .map(|expr| expr.lower_uncorrelated())
+        .collect::<Result<Vec<_>, _>>()?;
 
     Ok(Plan::ReadThenWrite(ReadThenWritePlan {
         id,
@@ -151,7 +206,7 @@ pub fn plan_read_then_write(
         finishing,
         assignments: assignments_outer,
         kind,
-        returning: Vec::new(),
+        returning,
     }))
 }
 
@@ -159,6 +214,16 @@ pub fn describe_select(
     scx: &StatementContext,
     stmt: SelectStatement<Aug>,
 ) -> Result<StatementDesc, PlanError> {
+    if let Some((signal, alias, arg)) = extract_backend_signal(scx, &stmt) {
+        query::plan_backend_signal_arg(scx, signal.function_name(), arg)?;
+        let name = match alias {
+            Some(alias) => normalize::column_name(alias),
+            None => signal.function_name().into(),
+        };
+        let desc = RelationDesc::empty().with_column(name, ScalarType::Bool.nullable(true));
+        return Ok(StatementDesc::new(Some(desc)));
+    }
+
     let query::PlannedQuery { desc, .. } =
         query::plan_root_query(scx, stmt.query, QueryLifetime::OneShot(scx.pcx()?))?;
     Ok(StatementDesc::new(Some(desc)))
@@ -170,6 +235,14 @@ pub fn plan_select(
     params: &Params,
     copy_to: Option<CopyFormat>,
 ) -> Result<Plan, PlanError> {
+    let stmt = SelectStatement { query, as_of };
+    if copy_to.is_none() {
+        if let Some((signal, _alias, arg)) = extract_backend_signal(scx, &stmt) {
+            return plan_backend_signal(scx, signal, arg, params);
+        }
+    }
+
+    let SelectStatement { query, as_of } = stmt;
     let query::PlannedQuery {
         expr, finishing, ..
     } = plan_query(scx, query, params, QueryLifetime::OneShot(scx.pcx()?))?;
@@ -182,12 +255,95 @@ pub fn plan_select(
     }))
 }
 
+/// Returns the signal, column alias, and argument of `stmt` if it consists of
+/// nothing but a call to `pg_cancel_backend` or `pg_terminate_backend`, as in
+/// `SELECT pg_cancel_backend(42)`.
+///
+/// These functions have side effects, and so are not supported in any other
+/// position.
+fn extract_backend_signal(
+    scx: &StatementContext,
+    stmt: &SelectStatement<Aug>,
+) -> Option<(BackendSignal, Option<Ident>, Expr<Aug>)> {
+    let select = match stmt {
+        SelectStatement {
+            query:
+                Query {
+                    ctes,
+                    body: SetExpr::Select(select),
+                    order_by,
+                    limit: None,
+                    offset: None,
+                },
+            as_of: None,
+        } if ctes.is_empty() && order_by.is_empty() => select,
+        _ => return None,
+    };
+    let (expr, alias) = match &**select {
+        Select {
+            distinct: None,
+            projection,
+            from,
+            selection: None,
+            group_by,
+            having: None,
+            options,
+        } if from.is_empty() && group_by.is_empty() && options.is_empty() => {
+            match &projection[..] {
+                [SelectItem::Expr { expr, alias }] => (expr, alias),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    let (name, arg) = match expr {
+        Expr::Function(Function {
+            name,
+            args: FunctionArgs::Args { args, order_by },
+            filter: None,
+            over: None,
+            distinct: false,
+        }) if order_by.is_empty() => match &args[..] {
+            [arg] => (name, arg),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let func = scx.resolve_function(name.clone()).ok()?;
+    let full_name = scx.catalog.resolve_full_name(func.name());
+    let signal = match (full_name.schema.as_str(), full_name.item.as_str()) {
+        ("pg_catalog", "pg_cancel_backend") => BackendSignal::Cancel,
+        ("pg_catalog", "pg_terminate_backend") => BackendSignal::Terminate,
+        _ => return None,
+    };
+    Some((signal, alias.clone(), arg.clone()))
+}
+
+fn plan_backend_signal(
+    scx: &StatementContext,
+    signal: BackendSignal,
+    arg: Expr<Aug>,
+    params: &Params,
+) -> Result<Plan, PlanError> {
+    let mut pid = query::plan_backend_signal_arg(scx, signal.function_name(), arg)?;
+    pid.bind_parameters(params)?;
+    let pid = pid.lower_uncorrelated()?;
+    Ok(Plan::SignalBackend(SignalBackendPlan { signal, pid }))
+}
+
 pub fn describe_explain(
     scx: &StatementContext,
     ExplainStatement {
-        stage, explainee, ..
+        stage,
+        format,
+        explainee,
+        ..
     }: ExplainStatement<Aug>,
 ) -> Result<StatementDesc, PlanError> {
+    if stage == ExplainStage::Analyze {
+        return show::explain_analyze(scx, format, explainee)?.describe();
+    }
+
     let mut relation_desc = RelationDesc::empty();
 
     match stage {
@@ -225,6 +381,7 @@ pub fn describe_explain(
                 .with_column("Path", ScalarType::String.nullable(false))
                 .with_column("Plan", ScalarType::String.nullable(false));
         }
+        ExplainStage::Analyze => unreachable!("handled above"),
     };
 
     Ok(
@@ -254,6 +411,13 @@ pub fn plan_explain(
     }: ExplainStatement<Aug>,
     params: &Params,
 ) -> Result<Plan, PlanError> {
+    if stage == ExplainStage::Analyze {
+        if !config_flags.is_empty() {
+            sql_bail!("EXPLAIN ANALYZE does not support options");
+        }
+        return show::explain_analyze(scx, format, explainee)?.plan();
+    }
+
     let is_view = matches!(explainee, Explainee::View(_));
     let (explainee, query) = match explainee {
         Explainee::View(name) => {
@@ -313,6 +477,9 @@ pub fn plan_explain(
                 names::resolve(qcx.scx.catalog, query)?.0,
             )
         }
+        Explainee::Index(_) => {
+            sql_bail!("EXPLAIN {} is not supported for indexes", stage)
+        }
         Explainee::Query(query) => (mz_repr::explain_new::Explainee::Query, query),
     };
     // Previously we would bail here for ORDER BY and LIMIT; this has been relaxed to silently
@@ -384,7 +551,11 @@ pub fn describe_subscribe(
 ) -> Result<StatementDesc, PlanError> {
     let relation_desc = match stmt.relation {
         SubscribeRelation::Name(name) => {
-            let item = scx.get_item_by_resolved_name(&name)?;
+            let mut item = scx.get_item_by_resolved_name(&name)?;
+            // A subscription produces the rows of the object it subscribes to.
+            if let Some((from, _, _)) = item.subscription_details() {
+                item = scx.get_item(&from);
+            }
             item.desc(&scx.catalog.resolve_full_name(item.name()))?
                 .into_owned()
         }
@@ -425,6 +596,9 @@ pub fn plan_subscribe(
     }: SubscribeStatement<Aug>,
     copy_to: Option<CopyFormat>,
 ) -> Result<Plan, PlanError> {
+    let SubscribeOptionExtracted {
+        progress, snapshot, ..
+    } = options.try_into()?;
     let from = match relation {
         SubscribeRelation::Name(name) => {
             let entry = scx.get_item_by_resolved_name(&name)?;
@@ -433,6 +607,32 @@ pub fn plan_subscribe(
                 | CatalogItemType::Source
                 | CatalogItemType::View
                 | CatalogItemType::MaterializedView => SubscribeFrom::Id(entry.id()),
+                CatalogItemType::Subscription => {
+                    // A subscription resumes from its persisted timestamp, so
+                    // it determines both the as of and the snapshot behavior.
+                    if as_of.is_some() {
+                        sql_bail!("cannot specify AS OF when subscribing to a subscription");
+                    }
+                    if snapshot.is_some() {
+                        sql_bail!("cannot specify SNAPSHOT when subscribing to a subscription");
+                    }
+                    let (from, timestamp, with_snapshot) = entry
+                        .subscription_details()
+                        .expect("subscription items have subscription details");
+                    return Ok(Plan::Subscribe(SubscribePlan {
+                        from: SubscribeFrom::Subscription {
+                            id: entry.id(),
+                            from,
+                        },
+                        when: QueryWhen::AtTimestamp(MirScalarExpr::literal_ok(
+                            Datum::MzTimestamp(timestamp),
+                            ScalarType::MzTimestamp,
+                        )),
+                        with_snapshot,
+                        copy_to,
+                        emit_progress: progress.unwrap_or(false),
+                    }));
+                }
                 CatalogItemType::Func
                 | CatalogItemType::Index
                 | CatalogItemType::Sink
@@ -466,9 +666,6 @@ pub fn plan_subscribe(
     };
 
     let when = query::plan_as_of(scx, as_of)?;
-    let SubscribeOptionExtracted {
-        progress, snapshot, ..
-    } = options.try_into()?;
     Ok(Plan::Subscribe(SubscribePlan {
         from,
         when,
@@ -489,8 +686,14 @@ pub fn describe_table(
 
 pub fn describe_copy(
     scx: &StatementContext,
-    CopyStatement { relation, .. }: CopyStatement<Aug>,
+    CopyStatement {
+        relation, target, ..
+    }: CopyStatement<Aug>,
 ) -> Result<StatementDesc, PlanError> {
+    // A `COPY` to or from a URL does not transfer any rows to the client.
+    if let CopyTarget::Url(_) = target {
+        return Ok(StatementDesc::new(None));
+    }
     Ok(match relation {
         CopyRelation::Table { name, columns } => describe_table(scx, name, columns)?,
         CopyRelation::Select(stmt) => describe_select(scx, stmt)?,
@@ -506,6 +709,139 @@ fn plan_copy_from(
     format: CopyFormat,
     options: CopyOptionExtracted,
 ) -> Result<Plan, PlanError> {
+    let params = plan_copy_format_params(format, options)?;
+    let (id, _, columns) = query::plan_copy_from(scx, table_name, columns)?;
+    Ok(Plan::CopyFrom(CopyFromPlan {
+        id,
+        columns,
+        params,
+    }))
+}
+
+fn plan_copy_from_url(
+    scx: &StatementContext,
+    table_name: ResolvedObjectName,
+    columns: Vec<Ident>,
+    url: &str,
+    format: CopyFormat,
+    mut options: CopyOptionExtracted,
+) -> Result<Plan, PlanError> {
+    if options.max_file_size.is_some() {
+        sql_bail!("COPY FROM does not support MAX FILE SIZE option");
+    }
+    let url = plan_copy_url(scx, url, options.connection.take())?;
+    let format = match format {
+        CopyFormat::Parquet => {
+            if options.delimiter.is_some()
+                || options.null.is_some()
+                || options.escape.is_some()
+                || options.quote.is_some()
+                || options.header.is_some()
+            {
+                sql_bail!("COPY FORMAT PARQUET does not support formatting options");
+            }
+            CopyFromUrlFormat::Parquet
+        }
+        format => CopyFromUrlFormat::Pgcopy(plan_copy_format_params(format, options)?),
+    };
+    let (id, _, columns) = query::plan_copy_from(scx, table_name, columns)?;
+    Ok(Plan::CopyFromUrl(CopyFromUrlPlan {
+        id,
+        columns,
+        url,
+        format,
+    }))
+}
+
+fn plan_copy_to_url(
+    scx: &StatementContext,
+    SelectStatement { query, as_of }: SelectStatement<Aug>,
+    url: &str,
+    format: CopyFormat,
+    options: CopyOptionExtracted,
+) -> Result<Plan, PlanError> {
+    match format {
+        CopyFormat::Binary => bail_unsupported!("FORMAT BINARY"),
+        CopyFormat::Text | CopyFormat::Parquet if options.header.is_some() => {
+            sql_bail!("COPY HEADER available only in CSV mode")
+        }
+        _ => (),
+    }
+    if options.quote.is_some() {
+        sql_bail!("COPY TO does not support QUOTE option yet");
+    }
+    if options.escape.is_some() {
+        sql_bail!("COPY TO does not support ESCAPE option yet");
+    }
+    let max_file_size = match options.max_file_size {
+        Some(0) => sql_bail!("COPY MAX FILE SIZE must be greater than zero"),
+        Some(max_file_size) => max_file_size,
+        None => DEFAULT_COPY_MAX_FILE_SIZE,
+    };
+    let url = plan_copy_url(scx, url, options.connection)?;
+    // The query is run by a sink in a dataflow, to which finishing operations
+    // cannot be applied, so we wrap it like the query of a `SUBSCRIBE`. Note
+    // that the order of the rows is not preserved across objects.
+    let query = Query::query(query);
+    let query = plan_query(
+        scx,
+        query,
+        &Params::empty(),
+        QueryLifetime::OneShot(scx.pcx()?),
+    )?;
+    assert!(query.finishing.is_trivial(query.desc.arity()));
+    let when = query::plan_as_of(scx, as_of)?;
+    Ok(Plan::CopyToUrl(CopyToUrlPlan {
+        expr: query.expr,
+        desc: query.desc,
+        when,
+        url,
+        format,
+        header: options.header.unwrap_or(false),
+        max_file_size,
+    }))
+}
+
+/// The default size in bytes after which `COPY ... TO` a URL starts writing to
+/// a new object.
+const DEFAULT_COPY_MAX_FILE_SIZE: u64 = 256 << 20;
+
+/// Plans the objects that a `COPY` to or from `url` reads or writes, which
+/// are accessed with the AWS connection `connection`.
+fn plan_copy_url(
+    scx: &StatementContext,
+    url: &str,
+    connection: Option<with_options::Object>,
+) -> Result<CopyUrl, PlanError> {
+    let path = match url.strip_prefix("s3://") {
+        Some(path) => path,
+        None => sql_bail!("invalid COPY URL '{}': only s3:// URLs are supported", url),
+    };
+    let (bucket, prefix) = path.split_once('/').unwrap_or((path, ""));
+    if bucket.is_empty() {
+        sql_bail!("invalid COPY URL '{}': missing bucket name", url);
+    }
+    let connection_id = match connection {
+        Some(connection) => GlobalId::from(connection),
+        None => sql_bail!("COPY to or from a URL requires the CONNECTION option"),
+    };
+    let entry = scx.catalog.get_item(&connection_id);
+    let connection = match entry.connection()? {
+        Connection::Aws(aws) => aws.clone(),
+        _ => sql_bail!("{} is not an AWS connection", entry.name().item),
+    };
+    Ok(CopyUrl {
+        bucket: bucket.to_string(),
+        prefix: prefix.trim_end_matches('/').to_string(),
+        connection_id,
+        connection,
+    })
+}
+
+fn plan_copy_format_params(
+    format: CopyFormat,
+    options: CopyOptionExtracted,
+) -> Result<CopyFormatParams<'static>, PlanError> {
     fn only_available_with_csv<T>(option: Option<T>, param: &str) -> Result<(), PlanError> {
         match option {
             Some(_) => sql_bail!("COPY {} available only in CSV mode", param),
@@ -564,14 +900,11 @@ fn plan_copy_from(
             })
         }
         CopyFormat::Binary => bail_unsupported!("FORMAT BINARY"),
+        CopyFormat::Parquet => {
+            sql_bail!("COPY FORMAT PARQUET is only supported for COPY to or from a URL")
+        }
     };
-
-    let (id, _, columns) = query::plan_copy_from(scx, table_name, columns)?;
-    Ok(Plan::CopyFrom(CopyFromPlan {
-        id,
-        columns,
-        params,
-    }))
+    Ok(params)
 }
 
 generate_extracted_config!(
@@ -581,7 +914,9 @@ generate_extracted_config!(
     (Null, String),
     (Escape, String),
     (Quote, String),
-    (Header, bool)
+    (Header, bool),
+    (Connection, with_options::Object),
+    (MaxFileSize, u64)
 );
 
 pub fn plan_copy(
@@ -598,6 +933,7 @@ pub fn plan_copy(
         "text" => CopyFormat::Text,
         "csv" => CopyFormat::Csv,
         "binary" => CopyFormat::Binary,
+        "parquet" => CopyFormat::Parquet,
         _ => sql_bail!("unknown FORMAT: {}", options.format),
     };
     if let CopyDirection::To = direction {
@@ -608,6 +944,25 @@ pub fn plan_copy(
             sql_bail!("COPY TO does not support NULL option yet");
         }
     }
+    if !matches!(target, CopyTarget::Url(_)) {
+        if options.connection.is_some() {
+            sql_bail!(
+                "COPY {} {} does not support CONNECTION option",
+                direction,
+                target
+            );
+        }
+        if options.max_file_size.is_some() {
+            sql_bail!(
+                "COPY {} {} does not support MAX FILE SIZE option",
+                direction,
+                target
+            );
+        }
+        if format == CopyFormat::Parquet {
+            sql_bail!("COPY FORMAT PARQUET is only supported for COPY to or from a URL");
+        }
+    }
     match (&direction, &target) {
         (CopyDirection::To, CopyTarget::Stdout) => match relation {
             CopyRelation::Table { .. } => sql_bail!("table with COPY TO unsupported"),
@@ -622,6 +977,17 @@ pub fn plan_copy(
             }
             _ => sql_bail!("COPY FROM {} not supported", target),
         },
+        (CopyDirection::To, CopyTarget::Url(url)) => match relation {
+            CopyRelation::Table { .. } => sql_bail!("table with COPY TO unsupported"),
+            CopyRelation::Select(stmt) => plan_copy_to_url(scx, stmt, url, format, options),
+            CopyRelation::Subscribe(_) => sql_bail!("SUBSCRIBE with COPY TO a URL unsupported"),
+        },
+        (CopyDirection::From, CopyTarget::Url(url)) => match relation {
+            CopyRelation::Table { name, columns } => {
+                plan_copy_from_url(scx, name, columns, url, format, options)
+            }
+            _ => sql_bail!("COPY FROM {} not supported", target),
+        },
         _ => sql_bail!("COPY {} {} not supported", direction, target),
     }
 }
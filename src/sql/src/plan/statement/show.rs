@@ -23,10 +23,11 @@ use query::QueryContext;
 
 use crate::ast::visit_mut::VisitMut;
 use crate::ast::{
-    ObjectType, SelectStatement, ShowColumnsStatement, ShowCreateIndexStatement,
-    ShowCreateSinkStatement, ShowCreateSourceStatement, ShowCreateTableStatement,
-    ShowCreateViewStatement, ShowDatabasesStatement, ShowIndexesStatement, ShowObjectsStatement,
-    ShowSchemasStatement, ShowStatementFilter, Statement, Value,
+    ExplainFormat, Explainee, ObjectType, SelectStatement, ShowColumnsStatement,
+    ShowCreateIndexStatement, ShowCreateSinkStatement, ShowCreateSourceStatement,
+    ShowCreateTableStatement, ShowCreateViewStatement, ShowDatabasesStatement,
+    ShowIndexesStatement, ShowObjectsStatement, ShowSchemasStatement, ShowStatementFilter,
+    Statement, Value,
 };
 use crate::catalog::{CatalogItemType, SessionCatalog};
 use crate::names::{
@@ -315,8 +316,11 @@ pub fn show_objects<'a>(
         ObjectType::Cluster => show_clusters(scx, filter),
         ObjectType::ClusterReplica => show_cluster_replicas(scx, filter),
         ObjectType::Secret => show_secrets(scx, from, filter),
+        ObjectType::Subscription => show_subscriptions(scx, from, filter),
         ObjectType::Index => unreachable!("SHOW INDEX handled separately"),
+        ObjectType::Schema => unreachable!("SHOW SCHEMAS handled separately"),
         ObjectType::Connection => show_connections(scx, from, filter),
+        ObjectType::Func => bail_unsupported!("SHOW FUNCTIONS"),
     }
 }
 
@@ -513,6 +517,7 @@ pub fn show_columns<'a>(
         | ty @ CatalogItemType::Index
         | ty @ CatalogItemType::Func
         | ty @ CatalogItemType::Secret
+        | ty @ CatalogItemType::Subscription
         | ty @ CatalogItemType::Type
         | ty @ CatalogItemType::Sink => {
             sql_bail!("{full_name} is a {ty} and so does not have columns");
@@ -573,6 +578,149 @@ pub fn show_secrets<'a>(
     ShowSelect::new(scx, query, filter, None, None)
 }
 
+pub fn show_subscriptions<'a>(
+    scx: &'a StatementContext<'a>,
+    from: Option<ResolvedSchemaName>,
+    filter: Option<ShowStatementFilter<Aug>>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let schema_spec = scx.resolve_optional_schema(&from)?;
+
+    let query = format!(
+        "SELECT subscriptions.name, objects.name AS for, subscriptions.timestamp
+        FROM mz_catalog.mz_subscriptions AS subscriptions
+        JOIN mz_catalog.mz_objects AS objects ON subscriptions.for_id = objects.id
+        WHERE subscriptions.schema_id = {schema_spec}",
+    );
+
+    ShowSelect::new(scx, query, filter, None, None)
+}
+
+/// Reports the runtime cost of each node of the physical plan of an index or
+/// materialized view, as recorded by the introspection sources of the active
+/// cluster.
+///
+/// Plan nodes are attributed the operators they rendered via
+/// `mz_internal.mz_lir_mapping`, and in turn the elapsed time and arranged
+/// records of those operators.
+pub fn explain_analyze<'a>(
+    scx: &'a StatementContext<'a>,
+    format: ExplainFormat,
+    explainee: Explainee<Aug>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let id = match explainee {
+        Explainee::Index(name) => {
+            let item = scx.get_item_by_resolved_name(&name)?;
+            if item.item_type() != CatalogItemType::Index {
+                sql_bail!(
+                    "Expected {} to be an index, not a {}",
+                    name.full_name_str(),
+                    item.item_type()
+                );
+            }
+            item.id()
+        }
+        Explainee::MaterializedView(name) => {
+            let item = scx.get_item_by_resolved_name(&name)?;
+            if item.item_type() != CatalogItemType::MaterializedView {
+                sql_bail!(
+                    "Expected {} to be a materialized view, not a {}",
+                    name.full_name_str(),
+                    item.item_type()
+                );
+            }
+            item.id()
+        }
+        Explainee::View(_) | Explainee::Query(_) => {
+            sql_bail!("EXPLAIN ANALYZE is only supported for indexes and materialized views")
+        }
+    };
+
+    // The mapping contains one entry per worker, each of which attributes
+    // that worker's operators to the plan nodes.
+    let nodes = format!(
+        "WITH
+            mapping AS (
+                SELECT lir_id, worker_id, operator, nesting, operator_id_start, operator_id_end
+                FROM mz_internal.mz_lir_mapping
+                WHERE export_id = '{id}'
+            ),
+            elapsed AS (
+                SELECT mapping.lir_id, pg_catalog.sum(elapsed.elapsed_ns) AS elapsed_ns
+                FROM mapping, mz_internal.mz_scheduling_elapsed AS elapsed
+                WHERE
+                    mapping.worker_id = elapsed.worker_id AND
+                    elapsed.id >= mapping.operator_id_start AND
+                    elapsed.id < mapping.operator_id_end
+                GROUP BY mapping.lir_id
+            ),
+            arrangements AS (
+                SELECT
+                    mapping.lir_id,
+                    pg_catalog.sum(sizes.records) AS records,
+                    pg_catalog.sum(sizes.batches) AS batches
+                FROM mapping, mz_internal.mz_arrangement_sizes AS sizes
+                WHERE
+                    mapping.worker_id = sizes.worker_id AND
+                    sizes.operator_id >= mapping.operator_id_start AND
+                    sizes.operator_id < mapping.operator_id_end
+                GROUP BY mapping.lir_id
+            ),
+            nodes AS (SELECT DISTINCT lir_id, operator, nesting FROM mapping)
+        SELECT
+            nodes.lir_id::int8 AS lir_id,
+            nodes.operator,
+            nodes.nesting::int8 AS nesting,
+            pg_catalog.coalesce(elapsed.elapsed_ns, 0)::int8 AS elapsed_ns,
+            pg_catalog.coalesce(arrangements.records, 0)::int8 AS records,
+            pg_catalog.coalesce(arrangements.batches, 0)::int8 AS batches
+        FROM nodes
+        LEFT JOIN elapsed ON nodes.lir_id = elapsed.lir_id
+        LEFT JOIN arrangements ON nodes.lir_id = arrangements.lir_id"
+    );
+
+    match format {
+        ExplainFormat::Text => {
+            let query = format!(
+                "SELECT
+                    lir_id,
+                    pg_catalog.repeat('  ', nesting::int4) || operator AS operator,
+                    (elapsed_ns / 1000)::float8 * '1 microsecond'::interval AS elapsed,
+                    records,
+                    batches
+                FROM ({nodes}) nodes"
+            );
+            ShowSelect::new(
+                scx,
+                query,
+                None,
+                Some("lir_id"),
+                Some(&["operator", "elapsed", "records", "batches"]),
+            )
+        }
+        ExplainFormat::Json => {
+            let query = format!(
+                "SELECT pg_catalog.coalesce(
+                    pg_catalog.jsonb_agg(
+                        pg_catalog.jsonb_build_object(
+                            'lir_id', lir_id,
+                            'operator', operator,
+                            'nesting', nesting,
+                            'elapsed_ns', elapsed_ns,
+                            'records', records,
+                            'batches', batches
+                        )
+                        ORDER BY lir_id
+                    ),
+                    '[]'
+                ) AS \"Analysis\"
+                FROM ({nodes}) nodes"
+            );
+            ShowSelect::new(scx, query, None, None, None)
+        }
+        ExplainFormat::Dot => sql_bail!("EXPLAIN ANALYZE does not support the DOT format"),
+    }
+}
+
 /// An intermediate result when planning a `SHOW` query.
 ///
 /// Can be interrogated for its columns, or converted into a proper [`Plan`].
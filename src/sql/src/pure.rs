@@ -18,10 +18,13 @@ use std::path::Path;
 use std::sync::Arc;
 
 use anyhow::{anyhow, bail, Context};
+use async_compression::tokio::bufread::{BzDecoder, GzipDecoder, ZstdDecoder};
+use globset::GlobBuilder;
 use itertools::Itertools;
 use prost::Message;
 use protobuf_native::compiler::{SourceTreeDescriptorDatabase, VirtualSourceTree};
 use protobuf_native::MessageLite;
+use tokio::io::AsyncReadExt;
 use tracing::info;
 use uuid::Uuid;
 
@@ -29,6 +32,7 @@ use mz_ccsr::Schema as CcsrSchema;
 use mz_ccsr::{Client, GetByIdError, GetBySubjectError};
 use mz_cloud_resources::AwsExternalIdPrefix;
 use mz_ore::cast::CastFrom;
+use mz_ore::str::StrExt;
 use mz_proto::RustType;
 use mz_repr::{strconv, GlobalId};
 use mz_secrets::SecretsReader;
@@ -40,12 +44,13 @@ use mz_sql_parser::ast::{
 };
 use mz_storage_client::types::connections::aws::AwsConfig;
 use mz_storage_client::types::connections::{Connection, ConnectionContext};
-use mz_storage_client::types::sources::PostgresSourceDetails;
+use mz_storage_client::types::sources::{KafkaTopics, PostgresSourceDetails};
 
 use crate::ast::{
-    AvroSchema, CreateReferencedSubsources, CreateSourceConnection, CreateSourceFormat,
-    CreateSourceStatement, CreateSourceSubsource, CreateSubsourceStatement, CsrConnectionAvro,
-    CsrConnectionProtobuf, CsvColumns, Format, ProtobufSchema, Value, WithOptionValue,
+    AlterSourceAction, AlterSourceStatement, AvroSchema, Compression, CreateReferencedSubsources,
+    CreateSourceConnection, CreateSourceFormat, CreateSourceStatement, CreateSourceSubsource,
+    CreateSubsourceStatement, CsrConnectionAvro, CsrConnectionProtobuf, CsvColumns, Format,
+    ProtobufSchema, S3KeySource, Statement, Value, WithOptionValue,
 };
 use crate::catalog::SessionCatalog;
 use crate::kafka_util;
@@ -190,19 +195,25 @@ pub async fn purify_create_source(
                 connection.options.insert(k, v);
             }
 
-            let topic = extracted_options
-                .topic
-                .ok_or_else(|| sql_err!("KAFKA CONNECTION without TOPIC"))?;
+            let topics = KafkaTopics::try_from(&extracted_options)?;
+            let topic = match &topics {
+                KafkaTopics::Topic(topic) => Some(topic.as_str()),
+                KafkaTopics::List(_) | KafkaTopics::Pattern(_) => None,
+            };
 
-            let consumer = kafka_util::create_consumer(&connection_context, &connection, &topic)
+            let consumer = kafka_util::create_consumer(&connection_context, &connection, topic)
                 .await
                 .map_err(|e| anyhow!("Failed to create and connect Kafka consumer: {}", e))?;
 
             if let Some(offset_type) = offset_type {
+                let topic = match topic {
+                    Some(topic) => topic,
+                    None => bail!("START OFFSET and START TIMESTAMP require a single TOPIC"),
+                };
                 // Translate `START TIMESTAMP` to a start offset
                 match kafka_util::lookup_start_offsets(
                     Arc::clone(&consumer),
-                    &topic,
+                    topic,
                     offset_type,
                     now,
                 )
@@ -545,6 +556,61 @@ pub async fn purify_create_source(
     Ok((subsources, stmt))
 }
 
+/// Purifies an `ALTER SOURCE ... REFRESH SCHEMA` statement by looking up the
+/// latest schemas of the source in the schema registry.
+///
+/// Whether the schemas are compatible with the source is checked during
+/// planning.
+pub async fn purify_alter_source(
+    catalog: Box<dyn SessionCatalog>,
+    mut stmt: AlterSourceStatement<Aug>,
+    connection_context: ConnectionContext,
+) -> Result<AlterSourceStatement<Aug>, anyhow::Error> {
+    if !matches!(stmt.action, AlterSourceAction::RefreshSchema { seed: None }) {
+        return Ok(stmt);
+    }
+
+    let name = normalize::unresolved_object_name(stmt.source_name.clone())?;
+    let item = match catalog.resolve_item(&name) {
+        Ok(item) => item,
+        // Planning reports missing sources, or ignores them if requested.
+        Err(_) => return Ok(stmt),
+    };
+    let parsed = crate::parse::parse(item.create_sql())?.into_element();
+    let mut create_stmt = match crate::names::resolve(&*catalog, parsed)?.0 {
+        Statement::CreateSource(create_stmt) => create_stmt,
+        // Planning reports that the item is not a source.
+        _ => return Ok(stmt),
+    };
+
+    let CreateSourceStatement {
+        connection,
+        format,
+        envelope,
+        ..
+    } = &mut create_stmt;
+    if let CreateSourceFormat::Bare(Format::Avro(AvroSchema::Csr { csr_connection })) = format {
+        // Regardless of how the source originally selected its schemas, it
+        // switches to the latest ones.
+        csr_connection.seed = None;
+        csr_connection.key_strategy = None;
+        csr_connection.value_strategy = None;
+        purify_csr_connection_avro(
+            &*catalog,
+            connection,
+            csr_connection,
+            envelope,
+            &connection_context,
+        )
+        .await?;
+        stmt.action = AlterSourceAction::RefreshSchema {
+            seed: csr_connection.seed.take(),
+        };
+    }
+
+    Ok(stmt)
+}
+
 async fn purify_source_format(
     catalog: &dyn SessionCatalog,
     format: &mut CreateSourceFormat<Aug>,
@@ -628,11 +694,191 @@ async fn purify_source_format_single(
                 }
             }
         }
+        Format::Parquet { schema } => {
+            if !matches!(connection, CreateSourceConnection::S3 { .. }) {
+                bail!("FORMAT PARQUET is only supported for S3 sources");
+            }
+            if schema.is_none() {
+                let object = fetch_sample_s3_object(catalog, connection, connection_context)
+                    .await
+                    .context("inferring schema for FORMAT PARQUET")?;
+                *schema = Some(mz_interchange::parquet::infer_parquet_schema(&object)?);
+            }
+        }
+        Format::AvroOcf { schema } => {
+            if !matches!(connection, CreateSourceConnection::S3 { .. }) {
+                bail!("FORMAT AVRO OCF is only supported for S3 sources");
+            }
+            if schema.is_none() {
+                let object = fetch_sample_s3_object(catalog, connection, connection_context)
+                    .await
+                    .context("inferring schema for FORMAT AVRO OCF")?;
+                *schema = Some(mz_interchange::avro::ocf_writer_schema(&object)?);
+            }
+        }
         Format::Bytes | Format::Regex(_) | Format::Json | Format::Text => (),
     }
     Ok(())
 }
 
+/// Downloads the first object that an S3 source would ingest, so that its
+/// schema can be inferred.
+///
+/// Only sources that scan a bucket can be sampled; sources fed exclusively by
+/// SQS notifications must specify their schema explicitly.
+async fn fetch_sample_s3_object(
+    catalog: &dyn SessionCatalog,
+    connection: &CreateSourceConnection<Aug>,
+    connection_context: &ConnectionContext,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let (connection, key_sources, pattern, compression) = match connection {
+        CreateSourceConnection::S3 {
+            connection,
+            key_sources,
+            pattern,
+            compression,
+        } => (connection, key_sources, pattern, compression),
+        _ => unreachable!("only called for S3 sources"),
+    };
+    let bucket = key_sources
+        .iter()
+        .find_map(|ks| match ks {
+            S3KeySource::Scan { bucket } => Some(bucket),
+            S3KeySource::SqsNotifications { .. } => None,
+        })
+        .ok_or_else(|| {
+            anyhow!("schema inference requires DISCOVER OBJECTS USING BUCKET SCAN; specify the schema with USING SCHEMA instead")
+        })?;
+    let glob = pattern
+        .as_ref()
+        .map(|p| {
+            GlobBuilder::new(p)
+                .literal_separator(true)
+                .backslash_escape(true)
+                .build()
+                .map(|g| g.compile_matcher())
+        })
+        .transpose()
+        .context("parsing glob")?;
+
+    let scx = StatementContext::new(None, &*catalog);
+    let aws = {
+        let item = scx.get_item_by_resolved_name(connection)?;
+        match item.connection()? {
+            Connection::Aws(aws) => aws.clone(),
+            _ => bail!("{} is not an AWS connection", item.name()),
+        }
+    };
+    let config = aws
+        .load(
+            connection_context.aws_external_id_prefix.as_ref(),
+            None,
+            &*connection_context.secrets_reader,
+        )
+        .await;
+    let client = aws_sdk_s3::Client::new(&config);
+
+    let mut continuation_token = None;
+    let key = loop {
+        let response = client
+            .list_objects_v2()
+            .bucket(bucket)
+            .set_continuation_token(continuation_token)
+            .send()
+            .await
+            .with_context(|| format!("listing objects in bucket {}", bucket.quoted()))?;
+        let key = response
+            .contents
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|obj| obj.key)
+            .find(|key| glob.as_ref().map_or(true, |g| g.is_match(key)));
+        if let Some(key) = key {
+            break key;
+        }
+        match response.next_continuation_token {
+            Some(token) => continuation_token = Some(token),
+            None => bail!(
+                "bucket {} contains no objects to infer a schema from",
+                bucket.quoted()
+            ),
+        }
+    };
+
+    let object = client
+        .get_object()
+        .bucket(bucket)
+        .key(&key)
+        .send()
+        .await
+        .with_context(|| format!("downloading object {}", key.quoted()))?;
+    let bytes = object
+        .body
+        .collect()
+        .await
+        .with_context(|| format!("downloading object {}", key.quoted()))?
+        .into_bytes();
+
+    let mut buf = vec![];
+    match compression {
+        Compression::None => buf.extend_from_slice(&bytes),
+        Compression::Gzip => {
+            GzipDecoder::new(&bytes[..]).read_to_end(&mut buf).await?;
+        }
+        Compression::Zstd => {
+            ZstdDecoder::new(&bytes[..]).read_to_end(&mut buf).await?;
+        }
+        Compression::Bzip2 => {
+            BzDecoder::new(&bytes[..]).read_to_end(&mut buf).await?;
+        }
+    }
+    Ok(buf)
+}
+
+/// Returns the topic whose schema registry subjects describe the messages of a
+/// Kafka source.
+///
+/// All topics of a source share the same format, so the subjects of the first
+/// topic are used. For a `TOPIC PATTERN`, that is the first existing topic, in
+/// sorted order, that matches the pattern.
+async fn csr_subject_topic(
+    catalog: &dyn SessionCatalog,
+    kafka_connection: &KafkaConnection<Aug>,
+    connection_context: &ConnectionContext,
+) -> Result<String, anyhow::Error> {
+    let extracted_options: KafkaConfigOptionExtracted = kafka_connection
+        .options
+        .clone()
+        .try_into()
+        .expect("already verified options valid provided");
+    match KafkaTopics::try_from(&extracted_options).expect("already validated topics provided") {
+        KafkaTopics::Topic(topic) => Ok(topic),
+        KafkaTopics::List(topics) => Ok(topics.into_iter().next().expect("TOPICS not empty")),
+        KafkaTopics::Pattern(pattern) => {
+            let scx = StatementContext::new(None, &*catalog);
+            let item = scx.get_item_by_resolved_name(&kafka_connection.connection)?;
+            let connection = match item.connection()? {
+                Connection::Kafka(connection) => connection.clone(),
+                _ => bail!("{} is not a kafka connection", item.name()),
+            };
+            let consumer = kafka_util::create_consumer(connection_context, &connection, None)
+                .await
+                .map_err(|e| anyhow!("Failed to create and connect Kafka consumer: {}", e))?;
+            match kafka_util::lookup_pattern_topics(consumer, &pattern)
+                .await?
+                .into_iter()
+                .next()
+            {
+                Some(topic) => Ok(topic),
+                None => bail!(
+                    "no topics match TOPIC PATTERN {} to look up schemas for",
+                    pattern.quoted()
+                ),
+            }
+        }
+    }
+}
+
 async fn purify_csr_connection_proto(
     catalog: &dyn SessionCatalog,
     connection: &mut CreateSourceConnection<Aug>,
@@ -640,19 +886,13 @@ async fn purify_csr_connection_proto(
     envelope: &Option<Envelope>,
     connection_context: &ConnectionContext,
 ) -> Result<(), anyhow::Error> {
-    let topic = if let CreateSourceConnection::Kafka(KafkaSourceConnection {
-        connection: KafkaConnection { options, .. },
-        ..
-    }) = connection
-    {
-        let KafkaConfigOptionExtracted { topic, .. } = options
-            .clone()
-            .try_into()
-            .expect("already verified options valid provided");
-        topic.expect("already validated topic provided")
-    } else {
-        bail!("Confluent Schema Registry is only supported with Kafka sources")
-    };
+    let kafka_connection =
+        if let CreateSourceConnection::Kafka(KafkaSourceConnection { connection, .. }) = connection
+        {
+            connection
+        } else {
+            bail!("Confluent Schema Registry is only supported with Kafka sources")
+        };
 
     let CsrConnectionProtobuf {
         seed,
@@ -674,6 +914,7 @@ async fn purify_csr_connection_proto(
                 .connect(&*connection_context.secrets_reader)
                 .await?;
 
+            let topic = csr_subject_topic(catalog, kafka_connection, connection_context).await?;
             let value = compile_proto(&format!("{}-value", topic), &ccsr_client).await?;
             let key = compile_proto(&format!("{}-key", topic), &ccsr_client)
                 .await
@@ -698,19 +939,13 @@ async fn purify_csr_connection_avro(
     envelope: &Option<Envelope>,
     connection_context: &ConnectionContext,
 ) -> Result<(), anyhow::Error> {
-    let topic = if let CreateSourceConnection::Kafka(KafkaSourceConnection {
-        connection: KafkaConnection { options, .. },
-        ..
-    }) = connection
-    {
-        let KafkaConfigOptionExtracted { topic, .. } = options
-            .clone()
-            .try_into()
-            .expect("already verified options valid provided");
-        topic.expect("already validated topic provided")
-    } else {
-        bail!("Confluent Schema Registry is only supported with Kafka sources")
-    };
+    let kafka_connection =
+        if let CreateSourceConnection::Kafka(KafkaSourceConnection { connection, .. }) = connection
+        {
+            connection
+        } else {
+            bail!("Confluent Schema Registry is only supported with Kafka sources")
+        };
 
     let CsrConnectionAvro {
         connection: CsrConnection { connection, .. },
@@ -728,6 +963,7 @@ async fn purify_csr_connection_avro(
             .connect(&*connection_context.secrets_reader)
             .await?;
 
+        let topic = csr_subject_topic(catalog, kafka_connection, connection_context).await?;
         let Schema {
             key_schema,
             value_schema,
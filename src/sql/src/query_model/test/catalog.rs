@@ -22,7 +22,7 @@ use mz_expr::MirScalarExpr;
 use mz_lowertest::*;
 use mz_ore::now::{EpochMillis, NOW_ZERO};
 use mz_repr::explain_new::{DummyHumanizer, ExprHumanizer};
-use mz_repr::{GlobalId, RelationDesc, ScalarType};
+use mz_repr::{GlobalId, RelationDesc, ScalarType, Timestamp};
 use mz_storage_client::types::connections::Connection;
 use mz_storage_client::types::sources::SourceDesc;
 
@@ -96,7 +96,7 @@ impl CatalogItem for TestCatalogItem {
         }
     }
 
-    fn func(&self) -> Result<&'static Func, CatalogError> {
+    fn func(&self) -> Result<&Func, CatalogError> {
         match &self {
             TestCatalogItem::Func(func) => Ok(func),
             _ => Err(CatalogError::UnknownFunction(format!(
@@ -145,6 +145,10 @@ impl CatalogItem for TestCatalogItem {
         unimplemented!()
     }
 
+    fn subscription_details(&self) -> Option<(GlobalId, Timestamp, bool)> {
+        unimplemented!()
+    }
+
     fn connection(&self) -> Result<&Connection, CatalogError> {
         unimplemented!()
     }
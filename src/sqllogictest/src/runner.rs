@@ -46,6 +46,7 @@ use fallible_iterator::FallibleIterator;
 use futures::sink::SinkExt;
 use md5::{Digest, Md5};
 use mz_persist_client::cache::PersistClientCache;
+use mz_persist_client::rpc::NoopPubSubClient;
 use mz_repr::adt::date::Date;
 use once_cell::sync::Lazy;
 use postgres_protocol::types;
@@ -656,6 +657,7 @@ impl Runner {
         let persist_clients = PersistClientCache::new(
             PersistConfig::new(&mz_environmentd::BUILD_INFO, now.clone()),
             &metrics_registry,
+            |_, _| Arc::new(NoopPubSubClient),
         );
         let persist_clients = Arc::new(Mutex::new(persist_clients));
         let server_config = mz_environmentd::Config {
@@ -665,6 +667,7 @@ impl Runner {
                 orchestrator: Arc::clone(&orchestrator) as Arc<dyn Orchestrator>,
                 storaged_image: "storaged".into(),
                 computed_image: "computed".into(),
+                persist_pubsub_url: None,
                 persist_location: PersistLocation {
                     blob_uri: format!("file://{}/persist/blob", temp_dir.path().display()),
                     consensus_uri,
@@ -673,6 +676,7 @@ impl Runner {
                 storage_stash_url,
                 now: SYSTEM_TIME.clone(),
             },
+            persist_pubsub_server: None,
             secrets_controller: Arc::clone(&orchestrator) as Arc<dyn SecretsController>,
             cloud_resource_controller: None,
             // Setting the port to 0 means that the OS will automatically
@@ -69,6 +69,19 @@ enum Action {
     /// non-zero. Can be used on a running environmentd. Operates without
     /// interfering with it or committing any data to that stash.
     UpgradeCheck,
+    /// Exports a logically consistent snapshot of the user-defined objects of
+    /// the adapter catalog to a versioned JSON file, or stdout if no target is
+    /// given. Can be used on a running environmentd.
+    Export {
+        target: Option<PathBuf>,
+    },
+    /// Imports a snapshot created by `export` into a stash without user items,
+    /// allocating new ids for all items and running the catalog migrations.
+    /// Prints the mapping from old to new item ids, which is needed to copy the
+    /// contents of secrets.
+    Import {
+        source: PathBuf,
+    },
 }
 
 #[tokio::main]
@@ -87,6 +100,12 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
     let tls = mz_postgres_util::make_tls(&tokio_postgres::config::Config::from_str(
         &args.postgres_url,
     )?)?;
+    if let Action::Import { source } = args.action {
+        // The target stash might not have been initialized yet, so we cannot
+        // determine its usage.
+        let stash = Postgres::new(args.postgres_url, None, tls).await?;
+        return import(stash, source).await;
+    }
     let mut stash = Postgres::new_readonly(args.postgres_url.clone(), None, tls.clone()).await?;
     let usage = Usage::from_stash(&mut stash).await?;
 
@@ -113,6 +132,19 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
             let stash = Postgres::new_savepoint(args.postgres_url, tls).await?;
             upgrade_check(stash, usage).await
         }
+        Action::Export { target } => {
+            // Read all collections in a single transaction that is never
+            // committed, so that the snapshot is consistent.
+            let stash = Postgres::new_savepoint(args.postgres_url, tls).await?;
+            let target: Box<dyn Write> = if let Some(path) = target {
+                Box::new(File::create(path)?)
+            } else {
+                Box::new(io::stdout().lock())
+            };
+            export(stash, usage, target).await
+        }
+        // Handled above, before determining the usage.
+        Action::Import { .. } => unreachable!(),
     }
 }
 
@@ -144,6 +176,89 @@ async fn upgrade_check(stash: impl Append, usage: Usage) -> Result<(), anyhow::E
     Ok(())
 }
 
+async fn export(
+    stash: impl Append,
+    usage: Usage,
+    mut target: impl Write,
+) -> Result<(), anyhow::Error> {
+    if !matches!(usage, Usage::Catalog) {
+        anyhow::bail!("export expected Catalog stash, found {:?}", usage);
+    }
+    let mut storage = open_storage(stash).await?;
+    let snapshot = storage.export_snapshot().await?;
+    serde_json::to_writer_pretty(&mut target, &snapshot)?;
+    write!(&mut target, "\n")?;
+    Ok(())
+}
+
+async fn import(mut stash: impl Append, source: PathBuf) -> Result<(), anyhow::Error> {
+    if !stash.collections().await?.is_empty() {
+        let usage = Usage::from_stash(&mut stash).await?;
+        if !matches!(usage, Usage::Catalog) {
+            anyhow::bail!("import expected Catalog stash, found {:?}", usage);
+        }
+    }
+    let snapshot: catalog::CatalogSnapshot = serde_json::from_reader(File::open(source)?)?;
+    let mut storage = open_storage(stash).await?;
+    let ids = storage.import_snapshot(snapshot).await?;
+    // Opening the catalog migrates the imported items and allocates the ids of
+    // the introspection collections of imported clusters and replicas.
+    let last_catalog_version = open_catalog(storage).await?;
+    for (old, new) in ids {
+        println!("{old} -> {new}");
+    }
+    eprintln!(
+        "imported catalog from {} into {}",
+        last_catalog_version,
+        BUILD_INFO.human_version(),
+    );
+    Ok(())
+}
+
+async fn open_storage<S: Append>(stash: S) -> Result<catalog::Connection<S>, anyhow::Error> {
+    let (consolidations_tx, consolidations_rx) = mpsc::unbounded_channel();
+    // Leak the receiver so it's not dropped and send will work.
+    std::mem::forget(consolidations_rx);
+    let storage = catalog::Connection::open(
+        stash,
+        &BootstrapArgs {
+            now: (SYSTEM_TIME)(),
+            default_cluster_replica_size: "1".into(),
+            builtin_cluster_replica_size: "1".into(),
+            default_availability_zone: DUMMY_AVAILABILITY_ZONE.into(),
+        },
+        consolidations_tx,
+    )
+    .await?;
+    Ok(storage)
+}
+
+/// Opens the catalog, running all migrations, and returns the version of the
+/// catalog before opening it.
+async fn open_catalog<S: Append>(storage: catalog::Connection<S>) -> Result<String, anyhow::Error> {
+    let metrics_registry = &MetricsRegistry::new();
+    let secrets_reader = Arc::new(InMemorySecretsController::new());
+    let (_catalog, _, _, last_catalog_version) = Catalog::open(Config {
+        storage,
+        unsafe_mode: true,
+        persisted_introspection: true,
+        build_info: &BUILD_INFO,
+        environment_id: "environment-stash-debug".to_string(),
+        now: SYSTEM_TIME.clone(),
+        skip_migrations: false,
+        metrics_registry,
+        cluster_replica_sizes: Default::default(),
+        storage_host_sizes: Default::default(),
+        default_storage_host_size: None,
+        bootstrap_system_vars: None,
+        availability_zones: vec![],
+        secrets_reader,
+        egress_ips: vec![],
+    })
+    .await?;
+    Ok(last_catalog_version)
+}
+
 #[derive(Debug)]
 enum Usage {
     Catalog,
@@ -303,41 +418,8 @@ impl Usage {
             anyhow::bail!("upgrade_check expected Catalog stash, found {:?}", self);
         }
 
-        let metrics_registry = &MetricsRegistry::new();
-        let now = SYSTEM_TIME.clone();
-        let (consolidations_tx, consolidations_rx) = mpsc::unbounded_channel();
-        // Leak the receiver so it's not dropped and send will work.
-        std::mem::forget(consolidations_rx);
-        let storage = mz_adapter::catalog::storage::Connection::open(
-            stash,
-            &BootstrapArgs {
-                now: (now)(),
-                default_cluster_replica_size: "1".into(),
-                builtin_cluster_replica_size: "1".into(),
-                default_availability_zone: DUMMY_AVAILABILITY_ZONE.into(),
-            },
-            consolidations_tx.clone(),
-        )
-        .await?;
-        let secrets_reader = Arc::new(InMemorySecretsController::new());
-        let (_catalog, _, _, last_catalog_version) = Catalog::open(Config {
-            storage,
-            unsafe_mode: true,
-            persisted_introspection: true,
-            build_info: &BUILD_INFO,
-            environment_id: "environment-stash-debug".to_string(),
-            now,
-            skip_migrations: false,
-            metrics_registry,
-            cluster_replica_sizes: Default::default(),
-            storage_host_sizes: Default::default(),
-            default_storage_host_size: None,
-            bootstrap_system_vars: None,
-            availability_zones: vec![],
-            secrets_reader,
-            egress_ips: vec![],
-        })
-        .await?;
+        let storage = open_storage(stash).await?;
+        let last_catalog_version = open_catalog(storage).await?;
 
         Ok(format!(
             "catalog upgrade from {} to {} would succeed",
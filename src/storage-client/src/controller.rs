@@ -29,6 +29,7 @@ use async_trait::async_trait;
 use bytes::BufMut;
 use derivative::Derivative;
 use differential_dataflow::lattice::Lattice;
+use futures::future::BoxFuture;
 use futures::stream::StreamExt;
 use itertools::Itertools;
 use proptest::prelude::{any, Arbitrary, BoxedStrategy, Strategy};
@@ -49,6 +50,7 @@ use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::{PersistLocation, ShardId};
 use mz_persist_types::{Codec, Codec64};
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
+use mz_repr::stats::CollectionStatistics;
 use mz_repr::{Datum, Diff, GlobalId, RelationDesc, Row, TimestampManipulation};
 use mz_stash::{self, StashError, TypedCollection};
 
@@ -60,7 +62,7 @@ use crate::controller::hosts::{StorageHosts, StorageHostsConfig};
 use crate::types::errors::DataflowError;
 use crate::types::hosts::StorageHostConfig;
 use crate::types::sinks::{ProtoDurableExportMetadata, SinkAsOf, StorageSinkDesc};
-use crate::types::sources::{IngestionDescription, SourceExport};
+use crate::types::sources::{IngestionDescription, SourceData, SourceExport};
 
 mod hosts;
 mod rehydration;
@@ -99,6 +101,9 @@ pub enum IntrospectionType {
     SinkStatusHistory,
     SourceStatusHistory,
     ShardMapping,
+    /// The adapter appends a record for each logged statement. The contents
+    /// are retained across restarts.
+    StatementLog,
 }
 
 /// Describes how data is written to the collection.
@@ -127,6 +132,13 @@ pub struct CollectionDescription<T> {
     /// A GlobalId to use for this collection to use for the status collection.
     /// Used to keep track of source status/error information.
     pub status_collection_id: Option<GlobalId>,
+    /// An optional persist shard whose contents the collection should start
+    /// out with, without copying any data.
+    ///
+    /// The shard must hold data of the same shape as `desc`. Only set this
+    /// when the collection is first created: the fork fails if the
+    /// collection's own shard already holds data.
+    pub fork_shard: Option<ShardId>,
 }
 
 impl<T> From<RelationDesc> for CollectionDescription<T> {
@@ -136,6 +148,7 @@ impl<T> From<RelationDesc> for CollectionDescription<T> {
             data_source: DataSource::Other,
             since: None,
             status_collection_id: None,
+            fork_shard: None,
         }
     }
 }
@@ -196,6 +209,18 @@ pub trait StorageController: Debug + Send {
         collections: Vec<(GlobalId, StorageHostConfig)>,
     ) -> Result<(), StorageError>;
 
+    /// Replaces the description of the ingestion collection `id` and restarts
+    /// the ingestion with it.
+    ///
+    /// The new description may only append nullable columns to the
+    /// collection's relation. The collection keeps its contents; rows written
+    /// before the alteration are padded with nulls when read.
+    async fn alter_ingestion_desc(
+        &mut self,
+        id: GlobalId,
+        description: CollectionDescription<Self::Timestamp>,
+    ) -> Result<(), StorageError>;
+
     /// Acquire an immutable reference to the export state, should it exist.
     fn export(&self, id: GlobalId) -> Result<&ExportState<Self::Timestamp>, StorageError>;
 
@@ -275,6 +300,27 @@ pub trait StorageController: Debug + Send {
         as_of: Self::Timestamp,
     ) -> Result<Vec<(Row, Diff)>, StorageError>;
 
+    /// Appends `updates` to the introspection collection of type `type_`, at a
+    /// timestamp decided on by the controller.
+    ///
+    /// The updates are dropped if no collection of that type has been created.
+    async fn append_introspection_updates(
+        &mut self,
+        type_: IntrospectionType,
+        updates: Vec<(Row, Diff)>,
+    );
+
+    /// Computes approximate statistics about the contents of the collection named `id` at
+    /// `as_of`, from a sample of the collection.
+    ///
+    /// The method returns a future that computes the statistics in the background once it is
+    /// first polled, or an error if `id` does not name a known collection.
+    fn collection_statistics(
+        &mut self,
+        id: GlobalId,
+        as_of: Self::Timestamp,
+    ) -> Result<BoxFuture<'static, Result<CollectionStatistics, StorageError>>, StorageError>;
+
     /// Assigns a read policy to specific identifiers.
     ///
     /// The policies are assigned in the order presented, and repeated identifiers should
@@ -418,6 +464,10 @@ pub struct CollectionMetadata {
     pub data_shard: ShardId,
     /// The persist shard containing the status updates for this storage collection
     pub status_shard: Option<ShardId>,
+    /// The number of columns of the collection's rows. Collections can gain
+    /// trailing nullable columns, so rows that were written before that are
+    /// padded with nulls to this arity when read.
+    pub arity: usize,
 }
 
 impl RustType<ProtoCollectionMetadata> for CollectionMetadata {
@@ -428,6 +478,7 @@ impl RustType<ProtoCollectionMetadata> for CollectionMetadata {
             data_shard: self.data_shard.to_string(),
             remap_shard: self.remap_shard.to_string(),
             status_shard: self.status_shard.map(|s| s.to_string()),
+            arity: self.arity.into_proto(),
         }
     }
 
@@ -449,6 +500,7 @@ impl RustType<ProtoCollectionMetadata> for CollectionMetadata {
                 .status_shard
                 .map(|s| s.parse().map_err(TryFromProtoError::InvalidShardId))
                 .transpose()?,
+            arity: value.arity.into_rust()?,
         })
     }
 }
@@ -869,6 +921,7 @@ where
                 remap_shard: collection_shards.remap_shard,
                 data_shard: collection_shards.data_shard,
                 status_shard,
+                arity: description.desc.arity(),
             };
 
             // should be replaced with real introspection (https://github.com/MaterializeInc/materialize/issues/14266)
@@ -886,6 +939,24 @@ where
                 .await
                 .unwrap();
 
+            if let Some(fork_shard) = description.fork_shard {
+                // Fork at the current since of the forked shard, which the
+                // reader holds in place until the fork is done.
+                let fork_read = persist_client
+                    .open_leased_reader::<SourceData, (), T, Diff>(fork_shard)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("cannot fork shard {fork_shard}: {e}"))?;
+                let fork_res = persist_client
+                    .fork_shard::<SourceData, (), T, Diff>(
+                        fork_shard,
+                        metadata.data_shard,
+                        fork_read.since().clone(),
+                    )
+                    .await;
+                fork_read.expire().await;
+                fork_res.map_err(|e| anyhow::anyhow!("cannot fork shard {fork_shard}: {e}"))?;
+            }
+
             let (write, mut read) = persist_client
                 .open(metadata.data_shard)
                 .await
@@ -911,50 +982,7 @@ where
 
             match description.data_source {
                 DataSource::Ingestion(ingestion) => {
-                    // Each ingestion is augmented with the collection metadata.
-                    let mut source_imports = BTreeMap::new();
-                    for (id, _) in ingestion.source_imports {
-                        let metadata = self.collection(id)?.collection_metadata.clone();
-                        source_imports.insert(id, metadata);
-                    }
-
-                    // The ingestion metadata is simply the collection metadata of the collection with
-                    // the associated ingestion
-                    let ingestion_metadata = self.collection(id)?.collection_metadata.clone();
-
-                    let mut source_exports = BTreeMap::new();
-                    for (id, export) in ingestion.source_exports {
-                        let storage_metadata = self.collection(id)?.collection_metadata.clone();
-                        source_exports.insert(
-                            id,
-                            SourceExport {
-                                storage_metadata,
-                                output_index: export.output_index,
-                            },
-                        );
-                    }
-
-                    let desc = IngestionDescription {
-                        source_imports,
-                        source_exports,
-                        ingestion_metadata,
-                        // The rest of the fields are identical
-                        desc: ingestion.desc,
-                        host_config: ingestion.host_config,
-                    };
-                    let mut persist_clients = self.persist.lock().await;
-                    let mut state = desc.initialize_state(&mut persist_clients).await;
-                    let resume_upper = desc.calculate_resumption_frontier(&mut state).await;
-
-                    // Provision a storage host for the ingestion.
-                    let client = self.hosts.provision(id, desc.host_config.clone()).await?;
-                    let augmented_ingestion = CreateSourceCommand {
-                        id,
-                        description: desc,
-                        resume_upper,
-                    };
-
-                    client.send(StorageCommand::CreateSources(vec![augmented_ingestion]));
+                    self.run_ingestion(id, ingestion).await?;
                 }
                 DataSource::Introspection(i) => {
                     let prev = self.state.introspection_ids.insert(i, id);
@@ -974,6 +1002,9 @@ where
                         | IntrospectionType::SinkStatusHistory => {
                             // nothing to do: only storaged writes rows to these collections
                         }
+                        IntrospectionType::StatementLog => {
+                            // nothing to do: the adapter appends rows to this collection
+                        }
                     }
                 }
                 DataSource::Other => {}
@@ -993,6 +1024,25 @@ where
         Ok(())
     }
 
+    async fn alter_ingestion_desc(
+        &mut self,
+        id: GlobalId,
+        description: CollectionDescription<Self::Timestamp>,
+    ) -> Result<(), StorageError> {
+        let ingestion = match &description.data_source {
+            DataSource::Ingestion(ingestion) => ingestion.clone(),
+            _ => return Err(anyhow::anyhow!("collection {id} is not an ingestion").into()),
+        };
+        let collection = self.collection_mut(id)?;
+        collection.collection_metadata.arity = description.desc.arity();
+        collection.description.desc = description.desc;
+        collection.description.data_source = description.data_source;
+
+        // The storage host notices that the ingestion changed and restarts,
+        // rehydrating from the new description.
+        self.run_ingestion(id, ingestion).await
+    }
+
     fn export(&self, id: GlobalId) -> Result<&ExportState<Self::Timestamp>, StorageError> {
         self.state
             .exports
@@ -1212,6 +1262,26 @@ where
             .unwrap()
     }
 
+    async fn append_introspection_updates(
+        &mut self,
+        type_: IntrospectionType,
+        updates: Vec<(Row, Diff)>,
+    ) {
+        if let Some(id) = self.state.introspection_ids.get(&type_).copied() {
+            self.append_to_managed_collection(id, updates).await;
+        }
+    }
+
+    fn collection_statistics(
+        &mut self,
+        id: GlobalId,
+        as_of: Self::Timestamp,
+    ) -> Result<BoxFuture<'static, Result<CollectionStatistics, StorageError>>, StorageError> {
+        self.collection(id)?;
+        let as_of = Antichain::from_elem(as_of);
+        Ok(self.state.persist_read_handles.statistics(id, as_of))
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     async fn set_read_policy(
         &mut self,
@@ -1391,6 +1461,7 @@ where
         persist_clients: Arc<Mutex<PersistClientCache>>,
         orchestrator: Arc<dyn NamespacedOrchestrator>,
         storaged_image: String,
+        persist_pubsub_url: Option<String>,
         now: NowFn,
     ) -> Self {
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
@@ -1402,6 +1473,7 @@ where
                     build_info,
                     orchestrator,
                     storaged_image,
+                    persist_pubsub_url,
                 },
                 Arc::clone(&persist_clients),
             ),
@@ -1422,6 +1494,60 @@ where
 
     Self: StorageController<Timestamp = T>,
 {
+    /// Augments `ingestion` with the metadata of the collections it reads
+    /// from and writes to, and sends it to the ingestion's storage host.
+    async fn run_ingestion(
+        &mut self,
+        id: GlobalId,
+        ingestion: IngestionDescription,
+    ) -> Result<(), StorageError> {
+        // Each ingestion is augmented with the collection metadata.
+        let mut source_imports = BTreeMap::new();
+        for (id, _) in ingestion.source_imports {
+            let metadata = self.collection(id)?.collection_metadata.clone();
+            source_imports.insert(id, metadata);
+        }
+
+        // The ingestion metadata is simply the collection metadata of the collection with
+        // the associated ingestion
+        let ingestion_metadata = self.collection(id)?.collection_metadata.clone();
+
+        let mut source_exports = BTreeMap::new();
+        for (id, export) in ingestion.source_exports {
+            let storage_metadata = self.collection(id)?.collection_metadata.clone();
+            source_exports.insert(
+                id,
+                SourceExport {
+                    storage_metadata,
+                    output_index: export.output_index,
+                },
+            );
+        }
+
+        let desc = IngestionDescription {
+            source_imports,
+            source_exports,
+            ingestion_metadata,
+            // The rest of the fields are identical
+            desc: ingestion.desc,
+            host_config: ingestion.host_config,
+        };
+        let mut persist_clients = self.persist.lock().await;
+        let mut state = desc.initialize_state(&mut persist_clients).await;
+        let resume_upper = desc.calculate_resumption_frontier(&mut state).await;
+
+        // Provision a storage host for the ingestion.
+        let client = self.hosts.provision(id, desc.host_config.clone()).await?;
+        let augmented_ingestion = CreateSourceCommand {
+            id,
+            description: desc,
+            resume_upper,
+        };
+
+        client.send(StorageCommand::CreateSources(vec![augmented_ingestion]));
+        Ok(())
+    }
+
     /// Validate that a collection exists for all identifiers, and error if any do not.
     fn validate_collection_ids(
         &self,
@@ -1715,6 +1841,7 @@ mod persist_read_handles {
     use std::collections::{BTreeMap, HashSet};
 
     use differential_dataflow::lattice::Lattice;
+    use futures::future::BoxFuture;
     use futures::stream::FuturesUnordered;
     use futures::StreamExt;
     use timely::progress::{Antichain, Timestamp};
@@ -1722,6 +1849,7 @@ mod persist_read_handles {
 
     use mz_persist_client::read::ReadHandle;
     use mz_persist_types::Codec64;
+    use mz_repr::stats::{CollectionStatistics, CollectionStatisticsBuilder};
     use mz_repr::Row;
     use mz_repr::{Diff, GlobalId};
     use tracing::Instrument;
@@ -1729,6 +1857,10 @@ mod persist_read_handles {
     use crate::controller::StorageError;
     use crate::types::sources::SourceData;
 
+    /// The maximum number of parts of a collection that are fetched to compute
+    /// its statistics.
+    const STATISTICS_SAMPLE_PARTS: usize = 32;
+
     /// A wrapper that holds on to backing persist shards/collections that the
     /// storage controller is aware of. The handles hold back the since frontier and
     /// we need to downgrade them when the read capabilities change.
@@ -1763,6 +1895,11 @@ mod persist_read_handles {
             Antichain<T>,
             tokio::sync::oneshot::Sender<Result<Vec<(Row, Diff)>, StorageError>>,
         ),
+        Statistics(
+            GlobalId,
+            Antichain<T>,
+            tokio::sync::oneshot::Sender<Result<CollectionStatistics, StorageError>>,
+        ),
         Shutdown,
     }
 
@@ -1858,6 +1995,56 @@ mod persist_read_handles {
                                             };
                                             oneshot.send(result).expect("Oneshot should not fail");
                                         }
+                                        PersistWorkerCmd::Statistics(id, as_of, oneshot) => {
+                                            async fn statistics<T2: Timestamp + Lattice + Codec64>(
+                                                mut read_handle: ReadHandle<SourceData, (), T2, Diff>,
+                                                id: GlobalId,
+                                                as_of: Antichain<T2>,
+                                            ) -> Result<CollectionStatistics, StorageError>
+                                            {
+                                                let fold = |builder: &mut Option<CollectionStatisticsBuilder>,
+                                                            ((source_data, _pid), _ts, diff): ((Result<SourceData, String>, _), T2, Diff)| {
+                                                    // Errors are not rows, and do not contribute
+                                                    // to the statistics.
+                                                    if let Ok(SourceData(Ok(row))) = source_data {
+                                                        let arity = row.iter().count();
+                                                        builder
+                                                            .get_or_insert_with(|| CollectionStatisticsBuilder::new(arity))
+                                                            .add(&row, diff);
+                                                    }
+                                                };
+                                                let sample = read_handle
+                                                    .snapshot_and_fold_sample(as_of, STATISTICS_SAMPLE_PARTS, None, fold)
+                                                    .await
+                                                    .map_err(|_| StorageError::ReadBeforeSince(id));
+                                                read_handle.expire().await;
+                                                let (num_updates, builder) = sample?;
+                                                Ok(builder
+                                                    .map(|b| b.build_from_sample(num_updates))
+                                                    .unwrap_or_default())
+                                            }
+
+                                            match read_handles.get(&id) {
+                                                Some(Some(read_handle)) => {
+                                                    // Fetching the sample can take a while, so do it
+                                                    // with a separate handle in its own task, rather
+                                                    // than blocking since downgrades.
+                                                    let read_handle = read_handle.clone().await;
+                                                    mz_ore::task::spawn(
+                                                        || format!("PersistReadHandles::statistics ({})", id),
+                                                        async move {
+                                                            let result = statistics(read_handle, id, as_of).await;
+                                                            // The requester may have gone away.
+                                                            let _ = oneshot.send(result);
+                                                        }
+                                                        .instrument(span.clone()),
+                                                    );
+                                                }
+                                                Some(None) | None => {
+                                                    let _ = oneshot.send(Err(StorageError::IdentifierMissing(id)));
+                                                }
+                                            }
+                                        }
                                         PersistWorkerCmd::Shutdown => {
                                             shutdown = true;
                                         }
@@ -1937,6 +2124,29 @@ mod persist_read_handles {
             rx
         }
 
+        /// Returns a future that computes statistics about the collection
+        /// `id` at `as_of`.
+        ///
+        /// The request is only issued once the future is first polled, so
+        /// that callers can bound how many collections are sampled at once.
+        pub(crate) fn statistics(
+            &self,
+            id: GlobalId,
+            as_of: Antichain<T>,
+        ) -> BoxFuture<'static, Result<CollectionStatistics, StorageError>> {
+            let worker_tx = self.tx.clone();
+            let span = tracing::Span::current();
+            Box::pin(async move {
+                let (tx, rx) = tokio::sync::oneshot::channel();
+                if let Err(e) = worker_tx.send((span, PersistWorkerCmd::Statistics(id, as_of, tx)))
+                {
+                    tracing::trace!("could not forward command: {:?}", e);
+                }
+                // The worker drops the request if it shuts down.
+                rx.await.unwrap_or(Err(StorageError::IdentifierMissing(id)))
+            })
+        }
+
         fn send(&self, cmd: PersistWorkerCmd<T>) {
             match self.tx.send((tracing::Span::current(), cmd)) {
                 Ok(()) => (), // All good!
@@ -51,6 +51,8 @@ pub struct StorageHostsConfig {
     pub orchestrator: Arc<dyn NamespacedOrchestrator>,
     /// The storaged image to use when starting new storage hosts.
     pub storaged_image: String,
+    /// The URL of the persist pubsub server, if any.
+    pub persist_pubsub_url: Option<String>,
 }
 
 /// Manages provisioning of storage hosts and assignment of storage objects
@@ -65,6 +67,8 @@ pub struct StorageHosts<T> {
     orchestrator: Arc<dyn NamespacedOrchestrator>,
     /// The storaged image to use when starting new storage hosts.
     storaged_image: String,
+    /// The URL of the persist pubsub server, if any.
+    persist_pubsub_url: Option<String>,
     /// The known storage hosts, identified by network address.
     hosts: HashMap<StorageHostAddr, StorageHost<T>>,
     /// The assignment of storage objects to storage hosts.
@@ -100,6 +104,7 @@ where
             build_info: config.build_info,
             orchestrator: config.orchestrator,
             storaged_image: config.storaged_image,
+            persist_pubsub_url: config.persist_pubsub_url,
             objects: HashMap::new(),
             hosts: HashMap::new(),
             initialized: false,
@@ -233,7 +238,7 @@ where
                 ServiceConfig {
                     image: self.storaged_image.clone(),
                     args: &|assigned| {
-                        vec![
+                        let mut storage_opts = vec![
                             format!("--workers={}", allocation.workers),
                             format!(
                                 "--controller-listen-addr={}:{}",
@@ -244,7 +249,11 @@ where
                                 assigned.listen_host, assigned.ports["internal-http"]
                             ),
                             format!("--opentelemetry-resource=storage_id={}", id),
-                        ]
+                        ];
+                        if let Some(url) = &self.persist_pubsub_url {
+                            storage_opts.push(format!("--persist-pubsub-url={}", url));
+                        }
+                        storage_opts
                     },
                     ports: vec![
                         ServicePort {
@@ -259,6 +268,7 @@ where
                     cpu_limit: allocation.cpu_limit,
                     memory_limit: allocation.memory_limit,
                     scale: NonZeroUsize::new(1).unwrap(),
+                    disk: false,
                     labels: HashMap::from_iter([(
                         "size".to_string(),
                         allocation.workers.to_string(),
@@ -31,7 +31,7 @@ use mz_ore::cast::CastFrom;
 use mz_persist::location::ExternalError;
 use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::fetch::{LeasedBatchPart, SerdeLeasedBatchPart};
-use mz_repr::{Diff, GlobalId, Row, Timestamp};
+use mz_repr::{Datum, Diff, GlobalId, Row, Timestamp};
 use mz_timely_util::builder_async::{Event, OperatorBuilder as AsyncOperatorBuilder};
 
 use crate::controller::CollectionMetadata;
@@ -82,6 +82,23 @@ where
     (ok_stream, err_stream, token)
 }
 
+/// Pads `row` with nulls up to `arity` columns.
+///
+/// Collections can gain trailing nullable columns after they were created
+/// (e.g. when a source's schema is refreshed), in which case the rows that
+/// were written before have fewer columns than the collection.
+fn pad_row(row: Row, arity: usize) -> Row {
+    let row_arity = row.iter().count();
+    if row_arity >= arity {
+        return row;
+    }
+    let mut padded = Row::default();
+    let mut packer = padded.packer();
+    packer.extend_by_row(&row);
+    packer.extend(std::iter::repeat(Datum::Null).take(arity - row_arity));
+    padded
+}
+
 /// The stream of batches from persist cannot be dropped at the discretion of
 /// the program unaided without potentially panicking (check `LeasedBatchPart`).
 /// To prevent panics, ensure that all of the stream's values are consumed,
@@ -216,6 +233,7 @@ where
     let persist_clients_stream = Arc::<Mutex<PersistClientCache>>::clone(&persist_clients);
     let persist_location_stream = metadata.persist_location.clone();
     let data_shard = metadata.data_shard.clone();
+    let arity = metadata.arity;
     let as_of_stream = as_of;
 
     // Connects the consumed part collector operator with the part-issuing
@@ -462,6 +480,7 @@ where
                         if !until.less_equal(&time) {
                             match (key, val) {
                                 (Ok(SourceData(Ok(row))), Ok(())) => {
+                                    let row = pad_row(row, arity);
                                     if let Some(mfp) = &mut map_filter_project {
                                         let arena = mz_repr::RowArena::new();
                                         let mut datums_local = datum_vec.borrow_with(&row);
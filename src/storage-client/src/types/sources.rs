@@ -95,6 +95,7 @@ impl<T: timely::progress::Timestamp + Lattice + Codec64> ResumptionFrontierCalcu
                 data_shard,
                 // The status shard only contains non-definite status updates
                 status_shard: _,
+                arity: _,
             } = &export.storage_metadata;
             let handle = client_cache
                 .open(persist_location.clone())
@@ -112,6 +113,7 @@ impl<T: timely::progress::Timestamp + Lattice + Codec64> ResumptionFrontierCalcu
             data_shard: _,
             // The status shard only contains non-definite status updates
             status_shard: _,
+            arity: _,
         } = &self.ingestion_metadata;
         let remap_handle = client_cache
             .open(persist_location.clone())
@@ -1142,7 +1144,7 @@ pub trait SourceConnection: Clone {
 pub struct KafkaSourceConnection {
     pub connection: KafkaConnection,
     pub connection_id: GlobalId,
-    pub topic: String,
+    pub topics: KafkaTopics,
     // Map from partition -> starting offset
     pub start_offsets: HashMap<i32, i64>,
     pub group_id_prefix: Option<String>,
@@ -1158,12 +1160,90 @@ pub struct KafkaSourceConnection {
     pub include_headers: Option<IncludedColumnPos>,
 }
 
+impl KafkaSourceConnection {
+    /// Reports whether the partition identities of this source, as reported
+    /// to reclocking, include the topic name.
+    ///
+    /// Sources that read from a single `TOPIC` identify partitions by their
+    /// number alone, which keeps the bindings of existing sources valid, and
+    /// take the value of `INCLUDE TOPIC` from [`Self::single_topic`]. Sources
+    /// that read from several topics must qualify each partition with its
+    /// topic.
+    pub fn topic_qualified_partitions(&self) -> bool {
+        !matches!(self.topics, KafkaTopics::Topic(_))
+    }
+
+    /// Returns the topic of sources that read from a single `TOPIC`.
+    pub fn single_topic(&self) -> Option<&str> {
+        match &self.topics {
+            KafkaTopics::Topic(topic) => Some(topic),
+            _ => None,
+        }
+    }
+}
+
 impl SourceConnection for KafkaSourceConnection {
     fn name(&self) -> &'static str {
         "kafka"
     }
 }
 
+/// The topics that a Kafka source reads from.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum KafkaTopics {
+    /// A single topic, specified with `TOPIC`.
+    Topic(String),
+    /// A fixed list of topics, specified with `TOPICS`.
+    List(Vec<String>),
+    /// Every topic whose entire name matches a regular expression, specified
+    /// with `TOPIC PATTERN`. Matching topics are discovered as they are
+    /// created.
+    Pattern(String),
+}
+
+impl KafkaTopics {
+    /// Compiles the regular expression of a `TOPIC PATTERN`, which must
+    /// match a topic's name in its entirety.
+    pub fn compile_pattern(pattern: &str) -> Result<regex::Regex, regex::Error> {
+        regex::Regex::new(&format!("^(?:{})$", pattern))
+    }
+}
+
+impl std::fmt::Display for KafkaTopics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KafkaTopics::Topic(topic) => f.write_str(topic),
+            KafkaTopics::List(topics) => f.write_str(&topics.join(",")),
+            KafkaTopics::Pattern(pattern) => write!(f, "/{}/", pattern),
+        }
+    }
+}
+
+impl RustType<ProtoKafkaTopics> for KafkaTopics {
+    fn into_proto(&self) -> ProtoKafkaTopics {
+        use proto_kafka_topics::{Kind, ProtoTopicList};
+        ProtoKafkaTopics {
+            kind: Some(match self {
+                KafkaTopics::Topic(topic) => Kind::Topic(topic.clone()),
+                KafkaTopics::List(topics) => Kind::List(ProtoTopicList {
+                    topics: topics.clone(),
+                }),
+                KafkaTopics::Pattern(pattern) => Kind::Pattern(pattern.clone()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoKafkaTopics) -> Result<Self, TryFromProtoError> {
+        use proto_kafka_topics::Kind;
+        Ok(match proto.kind {
+            Some(Kind::Topic(topic)) => KafkaTopics::Topic(topic),
+            Some(Kind::List(list)) => KafkaTopics::List(list.topics),
+            Some(Kind::Pattern(pattern)) => KafkaTopics::Pattern(pattern),
+            None => return Err(TryFromProtoError::missing_field("ProtoKafkaTopics::kind")),
+        })
+    }
+}
+
 impl Arbitrary for KafkaSourceConnection {
     type Strategy = BoxedStrategy<Self>;
     type Parameters = ();
@@ -1172,7 +1252,7 @@ impl Arbitrary for KafkaSourceConnection {
         (
             any::<KafkaConnection>(),
             any::<GlobalId>(),
-            any::<String>(),
+            any::<KafkaTopics>(),
             proptest::collection::hash_map(any::<i32>(), any::<i64>(), 1..4),
             any::<Option<String>>(),
             any::<String>(),
@@ -1186,7 +1266,7 @@ impl Arbitrary for KafkaSourceConnection {
                 |(
                     connection,
                     connection_id,
-                    topic,
+                    topics,
                     start_offsets,
                     group_id_prefix,
                     environment_id,
@@ -1198,7 +1278,7 @@ impl Arbitrary for KafkaSourceConnection {
                 )| KafkaSourceConnection {
                     connection,
                     connection_id,
-                    topic,
+                    topics,
                     start_offsets,
                     group_id_prefix,
                     environment_id,
@@ -1218,7 +1298,11 @@ impl RustType<ProtoKafkaSourceConnection> for KafkaSourceConnection {
         ProtoKafkaSourceConnection {
             connection: Some(self.connection.into_proto()),
             connection_id: Some(self.connection_id.into_proto()),
-            topic: self.topic.clone(),
+            topic: match &self.topics {
+                KafkaTopics::Topic(topic) => topic.clone(),
+                _ => String::new(),
+            },
+            topics: Some(self.topics.into_proto()),
             start_offsets: self.start_offsets.clone(),
             group_id_prefix: self.group_id_prefix.clone(),
             environment_id: None,
@@ -1239,7 +1323,10 @@ impl RustType<ProtoKafkaSourceConnection> for KafkaSourceConnection {
             connection_id: proto
                 .connection_id
                 .into_rust_if_some("ProtoKafkaSourceConnection::connection_id")?,
-            topic: proto.topic,
+            topics: match proto.topics {
+                Some(topics) => topics.into_rust()?,
+                None => KafkaTopics::Topic(proto.topic),
+            },
             start_offsets: proto.start_offsets,
             group_id_prefix: proto.group_id_prefix,
             environment_id: match (proto.environment_id, proto.environment_name) {
@@ -1262,6 +1349,8 @@ impl RustType<ProtoKafkaSourceConnection> for KafkaSourceConnection {
 #[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Compression {
     Gzip,
+    Zstd,
+    Bzip2,
     None,
 }
 
@@ -1271,6 +1360,8 @@ impl RustType<ProtoCompression> for Compression {
         ProtoCompression {
             kind: Some(match self {
                 Compression::Gzip => Kind::Gzip(()),
+                Compression::Zstd => Kind::Zstd(()),
+                Compression::Bzip2 => Kind::Bzip2(()),
                 Compression::None => Kind::None(()),
             }),
         }
@@ -1280,6 +1371,8 @@ impl RustType<ProtoCompression> for Compression {
         use proto_compression::Kind;
         Ok(match proto.kind {
             Some(Kind::Gzip(())) => Compression::Gzip,
+            Some(Kind::Zstd(())) => Compression::Zstd,
+            Some(Kind::Bzip2(())) => Compression::Bzip2,
             Some(Kind::None(())) => Compression::None,
             None => {
                 return Err(TryFromProtoError::MissingField(
@@ -1597,9 +1690,10 @@ impl GenericSourceConnection {
     ///  TODO: decide whether we want file paths and other upstream names to show up in metrics too.
     pub fn upstream_name(&self) -> Option<&str> {
         match self {
-            GenericSourceConnection::Kafka(KafkaSourceConnection { topic, .. }) => {
-                Some(topic.as_str())
-            }
+            GenericSourceConnection::Kafka(KafkaSourceConnection { topics, .. }) => match topics {
+                KafkaTopics::Topic(topic) => Some(topic.as_str()),
+                KafkaTopics::List(_) | KafkaTopics::Pattern(_) => None,
+            },
             GenericSourceConnection::Kinesis(KinesisSourceConnection { stream_name, .. }) => {
                 Some(stream_name.as_str())
             }
@@ -1786,6 +1880,21 @@ pub enum LoadGenerator {
         count_orders: i64,
         count_clerk: i64,
     },
+    /// Upserts random values for keys drawn from a fixed key space.
+    KeyValue {
+        /// The number of distinct keys.
+        keys: u64,
+        /// The number of times every key is written in the initial snapshot.
+        snapshot_rounds: u64,
+        /// The size of each value, in bytes.
+        value_size: u64,
+        /// The number of keys updated per tick after the snapshot.
+        batch_size: u64,
+        /// The number of partitions the keys are spread across.
+        partitions: u64,
+        /// The seed of the random number generator.
+        seed: u64,
+    },
 }
 
 impl LoadGenerator {
@@ -1818,11 +1927,27 @@ impl LoadGenerator {
                 RelationDesc::empty().with_column("counter", ScalarType::Int64.nullable(false)),
             ),
             LoadGenerator::Tpch { .. } => DataEncodingInner::RowCodec(RelationDesc::empty()),
+            LoadGenerator::KeyValue { .. } => DataEncodingInner::RowCodec(
+                RelationDesc::empty()
+                    .with_column("partition", ScalarType::UInt64.nullable(false))
+                    .with_column("value", ScalarType::Bytes.nullable(false)),
+            ),
         }
     }
 
     pub fn data_encoding(&self) -> SourceDataEncoding {
-        SourceDataEncoding::Single(DataEncoding::new(self.data_encoding_inner()))
+        let value = DataEncoding::new(self.data_encoding_inner());
+        match self {
+            // The generated rows contain the key columns followed by the value
+            // columns.
+            LoadGenerator::KeyValue { .. } => SourceDataEncoding::KeyValue {
+                key: DataEncoding::new(DataEncodingInner::RowCodec(
+                    RelationDesc::empty().with_column("key", ScalarType::UInt64.nullable(false)),
+                )),
+                value,
+            },
+            _ => SourceDataEncoding::Single(value),
+        }
     }
 
     /// Returns the list of table names and their column types that this generator generates
@@ -1874,6 +1999,7 @@ impl LoadGenerator {
             ],
             LoadGenerator::Counter => vec![],
             LoadGenerator::Datums => vec![],
+            LoadGenerator::KeyValue { .. } => vec![],
             LoadGenerator::Tpch { .. } => {
                 let identifier = ScalarType::Int64.nullable(false);
                 let decimal = ScalarType::Numeric {
@@ -2023,6 +2149,21 @@ impl RustType<ProtoLoadGeneratorSourceConnection> for LoadGeneratorSourceConnect
                     count_clerk: *count_clerk,
                 }),
                 LoadGenerator::Datums => ProtoGenerator::Datums(()),
+                LoadGenerator::KeyValue {
+                    keys,
+                    snapshot_rounds,
+                    value_size,
+                    batch_size,
+                    partitions,
+                    seed,
+                } => ProtoGenerator::KeyValue(ProtoKeyValueLoadGenerator {
+                    keys: *keys,
+                    snapshot_rounds: *snapshot_rounds,
+                    value_size: *value_size,
+                    batch_size: *batch_size,
+                    partitions: *partitions,
+                    seed: *seed,
+                }),
             }),
             tick_micros: self.tick_micros,
         }
@@ -2050,6 +2191,21 @@ impl RustType<ProtoLoadGeneratorSourceConnection> for LoadGeneratorSourceConnect
                     count_clerk,
                 },
                 ProtoGenerator::Datums(()) => LoadGenerator::Datums,
+                ProtoGenerator::KeyValue(ProtoKeyValueLoadGenerator {
+                    keys,
+                    snapshot_rounds,
+                    value_size,
+                    batch_size,
+                    partitions,
+                    seed,
+                }) => LoadGenerator::KeyValue {
+                    keys,
+                    snapshot_rounds,
+                    value_size,
+                    batch_size,
+                    partitions,
+                    seed,
+                },
             },
             tick_micros: proto.tick_micros,
         })
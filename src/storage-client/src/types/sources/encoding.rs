@@ -139,6 +139,10 @@ pub enum DataEncodingInner {
     Bytes,
     Text,
     RowCodec(RelationDesc),
+    /// Newline-delimited JSON.
+    Json,
+    Parquet(ParquetEncoding),
+    AvroOcf(AvroOcfEncoding),
 }
 
 impl RustType<ProtoDataEncodingInner> for DataEncodingInner {
@@ -153,6 +157,9 @@ impl RustType<ProtoDataEncodingInner> for DataEncodingInner {
                 DataEncodingInner::Bytes => Kind::Bytes(()),
                 DataEncodingInner::Text => Kind::Text(()),
                 DataEncodingInner::RowCodec(e) => Kind::RowCodec(e.into_proto()),
+                DataEncodingInner::Json => Kind::Json(()),
+                DataEncodingInner::Parquet(e) => Kind::Parquet(e.into_proto()),
+                DataEncodingInner::AvroOcf(e) => Kind::AvroOcf(e.into_proto()),
             }),
         }
     }
@@ -170,6 +177,9 @@ impl RustType<ProtoDataEncodingInner> for DataEncodingInner {
             Kind::Bytes(()) => DataEncodingInner::Bytes,
             Kind::Text(()) => DataEncodingInner::Text,
             Kind::RowCodec(e) => DataEncodingInner::RowCodec(e.into_rust()?),
+            Kind::Json(()) => DataEncodingInner::Json,
+            Kind::Parquet(e) => DataEncodingInner::Parquet(e.into_rust()?),
+            Kind::AvroOcf(e) => DataEncodingInner::AvroOcf(e.into_rust()?),
         })
     }
 }
@@ -271,6 +281,14 @@ impl DataEncoding {
                 RelationDesc::empty().with_column("text", ScalarType::String.nullable(false))
             }
             DataEncodingInner::RowCodec(desc) => desc.clone(),
+            DataEncodingInner::Json => {
+                RelationDesc::empty().with_column("data", ScalarType::Jsonb.nullable(false))
+            }
+            DataEncodingInner::Parquet(ParquetEncoding { schema })
+            | DataEncodingInner::AvroOcf(AvroOcfEncoding { schema }) => {
+                let parsed_schema = avro::parse_schema(schema).context("validating avro schema")?;
+                avro::schema_to_relationdesc(parsed_schema).context("validating avro schema")?
+            }
         };
 
         if self.force_nullable_columns {
@@ -292,6 +310,9 @@ impl DataEncoding {
             DataEncodingInner::Csv(_) => "Csv",
             DataEncodingInner::Text => "Text",
             DataEncodingInner::RowCodec(_) => "RowCodec",
+            DataEncodingInner::Json => "Json",
+            DataEncodingInner::Parquet(_) => "Parquet",
+            DataEncodingInner::AvroOcf(_) => "AvroOcf",
         }
     }
 }
@@ -322,6 +343,49 @@ impl RustType<ProtoAvroEncoding> for AvroEncoding {
     }
 }
 
+/// Encoding of whole objects in Parquet format.
+///
+/// The columns to read are described by an Avro record schema.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct ParquetEncoding {
+    pub schema: String,
+}
+
+impl RustType<ProtoParquetEncoding> for ParquetEncoding {
+    fn into_proto(&self) -> ProtoParquetEncoding {
+        ProtoParquetEncoding {
+            schema: self.schema.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoParquetEncoding) -> Result<Self, TryFromProtoError> {
+        Ok(ParquetEncoding {
+            schema: proto.schema,
+        })
+    }
+}
+
+/// Encoding of whole objects in the Avro object container file format.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct AvroOcfEncoding {
+    /// The reader schema.
+    pub schema: String,
+}
+
+impl RustType<ProtoAvroOcfEncoding> for AvroOcfEncoding {
+    fn into_proto(&self) -> ProtoAvroOcfEncoding {
+        ProtoAvroOcfEncoding {
+            schema: self.schema.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoAvroOcfEncoding) -> Result<Self, TryFromProtoError> {
+        Ok(AvroOcfEncoding {
+            schema: proto.schema,
+        })
+    }
+}
+
 /// Encoding in Protobuf format.
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ProtobufEncoding {
@@ -69,6 +69,18 @@ struct Args {
         default_value = "127.0.0.1:6878"
     )]
     internal_http_listen_addr: SocketAddr,
+    /// The URL of the persist pubsub server in environmentd.
+    ///
+    /// If unset, persist readers poll for shard changes instead.
+    #[clap(long, env = "PERSIST_PUBSUB_URL", value_name = "URL")]
+    persist_pubsub_url: Option<String>,
+    /// A local directory in which to cache reads of persist blobs, so that
+    /// they needn't be fetched again, e.g. after a restart.
+    #[clap(long, env = "PERSIST_BLOB_DISK_CACHE_DIR", value_name = "PATH")]
+    persist_blob_disk_cache_dir: Option<PathBuf>,
+    /// The maximum size in bytes of the persist blob disk cache.
+    #[clap(long, env = "PERSIST_BLOB_DISK_CACHE_MAX_SIZE", value_name = "N")]
+    persist_blob_disk_cache_max_size: Option<usize>,
 
     // === Dataflow options. ===
     /// Number of dataflow worker threads.
@@ -211,6 +223,9 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
             args.aws_external_id,
             secrets_reader,
         ),
+        persist_pubsub_url: args.persist_pubsub_url,
+        persist_blob_disk_cache_dir: args.persist_blob_disk_cache_dir,
+        persist_blob_disk_cache_max_size_bytes: args.persist_blob_disk_cache_max_size,
     };
 
     // Initialize fail crate for failpoint support
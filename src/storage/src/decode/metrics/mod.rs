@@ -13,6 +13,7 @@ use mz_ore::metric;
 use mz_ore::metrics::raw::IntCounterVec;
 use mz_ore::metrics::MetricsRegistry;
 
+use crate::decode::object::ObjectFormat;
 use crate::decode::{DataDecoderInner, PreDelimitedFormat};
 
 /// Metrics specific to a single worker.
@@ -37,10 +38,15 @@ impl DecodeMetrics {
         let format_label = match decoder {
             DataDecoderInner::Avro(_) => "avro",
             DataDecoderInner::Csv(_) => "csv",
+            DataDecoderInner::Object(object) => match object.format() {
+                ObjectFormat::Parquet(_) => "parquet",
+                ObjectFormat::AvroOcf(_) => "avro-ocf",
+            },
             DataDecoderInner::DelimitedBytes { format, .. }
             | DataDecoderInner::PreDelimited(format) => match format {
                 PreDelimitedFormat::Bytes => "raw",
                 PreDelimitedFormat::Text => "text",
+                PreDelimitedFormat::Json => "json",
                 PreDelimitedFormat::Regex(..) => "regex",
                 PreDelimitedFormat::Protobuf(..) => "protobuf",
             },
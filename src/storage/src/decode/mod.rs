@@ -25,7 +25,7 @@ use differential_dataflow::Hashable;
 use differential_dataflow::{AsCollection, Collection};
 use regex::Regex;
 use timely::dataflow::channels::pact::{Exchange, Pipeline};
-use timely::dataflow::operators::Operator;
+use timely::dataflow::operators::{Capability, Operator};
 use timely::dataflow::{Scope, Stream};
 use timely::scheduling::SyncActivator;
 use tokio::runtime::Handle as TokioHandle;
@@ -33,25 +33,29 @@ use tracing::error;
 
 use mz_avro::{AvroDeserializer, GeneralDeserializer};
 use mz_expr::PartitionId;
-use mz_interchange::avro::ConfluentAvroResolver;
+use mz_interchange::avro::{ConfluentAvroResolver, OcfDecoder};
+use mz_interchange::parquet::ParquetDecoder;
+use mz_repr::adt::jsonb::Jsonb;
 use mz_repr::{adt::timestamp::CheckedTimestamp, Datum};
 use mz_repr::{Diff, Row, Timestamp};
 use mz_storage_client::types::connections::ConnectionContext;
 use mz_storage_client::types::errors::{DecodeError, DecodeErrorKind};
 use mz_storage_client::types::sources::encoding::{
-    AvroEncoding, DataEncoding, DataEncodingInner, RegexEncoding,
+    AvroEncoding, AvroOcfEncoding, DataEncoding, DataEncodingInner, ParquetEncoding, RegexEncoding,
 };
 use mz_storage_client::types::sources::{IncludedColumnSource, MzOffset};
 
 use self::avro::AvroDecoderState;
 use self::csv::CsvDecoderState;
 use self::metrics::DecodeMetrics;
+use self::object::{ObjectDecoderState, ObjectFormat};
 use self::protobuf::ProtobufDecoderState;
 use crate::source::types::{DecodeResult, SourceOutput};
 
 mod avro;
 mod csv;
 pub mod metrics;
+mod object;
 mod protobuf;
 
 /// Decode delimited CDCv2 messages.
@@ -136,6 +140,7 @@ pub fn render_decode_cdcv2<G: Scope<Timestamp = Timestamp>>(
 pub(crate) enum PreDelimitedFormat {
     Bytes,
     Text,
+    Json,
     Regex(Regex, Row),
     Protobuf(ProtobufDecoderState),
 }
@@ -149,6 +154,12 @@ impl PreDelimitedFormat {
                     .map_err(|_| DecodeErrorKind::Text("Failed to decode UTF-8".to_string()))?;
                 Ok(Some(Row::pack(Some(Datum::String(s)))))
             }
+            PreDelimitedFormat::Json => {
+                let jsonb = Jsonb::from_slice(bytes).map_err(|e| {
+                    DecodeErrorKind::Text(format!("Failed to decode JSON: {:#}", e))
+                })?;
+                Ok(Some(jsonb.into_row()))
+            }
             PreDelimitedFormat::Regex(regex, row_buf) => {
                 let s = std::str::from_utf8(bytes)
                     .map_err(|_| DecodeErrorKind::Text("Failed to decode UTF-8".to_string()))?;
@@ -177,6 +188,7 @@ pub(crate) enum DataDecoderInner {
         format: PreDelimitedFormat,
     },
     Csv(CsvDecoderState),
+    Object(ObjectDecoderState),
 
     PreDelimited(PreDelimitedFormat),
 }
@@ -202,6 +214,10 @@ impl DataDecoder {
             }
             DataDecoderInner::Avro(avro) => avro.decode(bytes),
             DataDecoderInner::Csv(csv) => csv.decode(bytes),
+            DataDecoderInner::Object(object) => {
+                object.buffer(bytes);
+                Ok(None)
+            }
             DataDecoderInner::PreDelimited(format) => {
                 let result = format.decode(*bytes);
                 *bytes = &[];
@@ -231,10 +247,23 @@ impl DataDecoder {
                     format.decode(data)
                 }
             }
+            DataDecoderInner::Object(object) => object.eof(),
             _ => Ok(None),
         }
     }
 
+    /// Get the next of any records of the object ended by the last call to
+    /// `eof` that have not yet been returned.
+    ///
+    /// Only decoders that decode entire objects produce more than one record
+    /// on EOF. They decode the remaining records as they are requested.
+    pub fn next_pending(&mut self) -> Option<Result<Row, DecodeErrorKind>> {
+        match &mut self.inner {
+            DataDecoderInner::Object(object) => object.next_pending(),
+            _ => None,
+        }
+    }
+
     pub fn log_errors(&self, n: usize) {
         self.metrics.count_errors(&self.inner, n);
     }
@@ -282,6 +311,7 @@ fn get_decoder(
         }
         DataEncodingInner::Text
         | DataEncodingInner::Bytes
+        | DataEncodingInner::Json
         | DataEncodingInner::Protobuf(_)
         | DataEncodingInner::Regex(_) => {
             let after_delimiting = match encoding.inner {
@@ -296,6 +326,7 @@ fn get_decoder(
                 }
                 DataEncodingInner::Bytes => PreDelimitedFormat::Bytes,
                 DataEncodingInner::Text => PreDelimitedFormat::Text,
+                DataEncodingInner::Json => PreDelimitedFormat::Json,
                 _ => unreachable!(),
             };
             let inner = if is_connection_delimited {
@@ -315,6 +346,26 @@ fn get_decoder(
                 metrics,
             }
         }
+        DataEncodingInner::Parquet(ParquetEncoding { schema }) => {
+            let decoder = ParquetDecoder::new(&schema)
+                .expect("Failed to create parquet decoder, even though we validated the schema in planning.");
+            DataDecoder {
+                inner: DataDecoderInner::Object(ObjectDecoderState::new(ObjectFormat::Parquet(
+                    decoder,
+                ))),
+                metrics,
+            }
+        }
+        DataEncodingInner::AvroOcf(AvroOcfEncoding { schema }) => {
+            let decoder = OcfDecoder::new(&schema)
+                .expect("Failed to create avro ocf decoder, even though we validated the schema in planning.");
+            DataDecoder {
+                inner: DataDecoderInner::Object(ObjectDecoderState::new(ObjectFormat::AvroOcf(
+                    decoder,
+                ))),
+                metrics,
+            }
+        }
         DataEncodingInner::RowCodec(_) => {
             unreachable!("RowCodec sources should not go through the general decoding path.")
         }
@@ -357,6 +408,7 @@ pub fn render_decode_delimited<G>(
     value_encoding: DataEncoding,
     debug_name: &str,
     metadata_items: Vec<IncludedColumnSource>,
+    single_topic: Option<String>,
     metrics: DecodeMetrics,
     connection_context: &ConnectionContext,
 ) -> (Stream<G, DecodeResult>, Option<Box<dyn Any + Send + Sync>>)
@@ -438,6 +490,7 @@ where
                         partition: partition.clone(),
                         metadata: to_metadata_row(
                             &metadata_items,
+                            single_topic.as_deref(),
                             partition.clone(),
                             *position,
                             *upstream_time_millis,
@@ -500,123 +553,58 @@ where
     // We therefore ignore it, and keep track ourselves of how many records we've seen (for filling in `mz_line_no`, etc).
     // Historically, non-delimited sources have their offset start at 1
     let mut n_seen = 1..;
-    let results = stream.unary_frontier(Pipeline, &op_name, move |_, _| {
+    let results = stream.unary_frontier(Pipeline, &op_name, move |_, info| {
+        let activator = stream.scope().activator_for(&info.address[..]);
         let metadata_items = metadata_items;
+        // Input that has not been decoded yet, because the operator yielded
+        // while producing the records of an object.
+        let mut queued = VecDeque::new();
+        // The source of the records of the last object that have not yet been
+        // produced, if any.
+        let mut pending_object = None;
         move |input, output| {
             let mut n_errors = 0;
             let mut n_successes = 0;
             input.for_each(|cap, data| {
-                // Currently Kafka is the only kind of source that can have metadata, and it is
-                // always delimited, so we will never have metadata in `render_decode`
-                let mut session = output.session(&cap);
-                for SourceOutput {
-                    key: _,
-                    value,
-                    position: _,
+                let mut buffer = Vec::new();
+                data.swap(&mut buffer);
+                queued.push_back((cap.retain(), VecDeque::from(buffer)));
+            });
+
+            let mut budget = MAX_OBJECT_RECORDS_PER_ACTIVATION;
+            loop {
+                // Produce the remaining records of the last object before
+                // decoding further input.
+                if let Some(PendingObject {
+                    cap,
                     upstream_time_millis,
                     partition,
                     headers,
-                    diff: (),
-                } in data.iter()
+                }) = &pending_object
                 {
-                    let value = match value {
-                        Some(data) => data,
-                        None => {
-                            let data = &mut &value_buf[..];
-                            let mut result = value_decoder.eof(data);
-                            if result.is_ok() && !data.is_empty() {
-                                result = Err(DecodeErrorKind::Text(format!(
-                                    "Saw unexpected EOF with bytes remaining in buffer: {:?}",
-                                    data
-                                )));
-                            }
-                            value_buf.clear();
-
-                            match result.transpose() {
-                                None => continue,
-                                Some(value) => {
-                                    if value.is_err() {
-                                        n_errors += 1;
-                                    } else if matches!(&value, Ok(_)) {
-                                        n_successes += 1;
-                                    }
-                                    // `RangeFrom` `Iterator`'s never end
-                                    let position = n_seen.next().unwrap();
-                                    let metadata = to_metadata_row(
-                                        &metadata_items,
-                                        partition.clone(),
-                                        position.into(),
-                                        *upstream_time_millis,
-                                        headers.as_deref(),
-                                    );
-
-                                    session.give(DecodeResult {
-                                        key: None,
-                                        value: Some(value.map(|r| (r, 1)).map_err(|inner| {
-                                            DecodeError {
-                                                kind: inner,
-                                                raw: None,
-                                            }
-                                        })),
-                                        position: position.into(),
-                                        upstream_time_millis: *upstream_time_millis,
-                                        partition: partition.clone(),
-                                        metadata,
-                                    });
-                                }
-                            }
-                            continue;
-                        }
-                    };
-
-                    // Check whether we have a partial message from last time.
-                    // If so, we need to prepend it to the bytes we got from _this_ message.
-                    let value = if value_buf.is_empty() {
-                        value
-                    } else {
-                        value_buf.extend_from_slice(&*value);
-                        &value_buf
-                    };
-
-                    let value_bytes_remaining = &mut value.as_slice();
-                    // The intent is that the below loop runs as long as there are more bytes to decode.
-                    //
-                    // We'd like to be able to write `while !value_cursor.empty()`
-                    // here, but that runs into borrow checker issues, so we use `loop`
-                    // and break manually.
-                    loop {
-                        let old_value_cursor = *value_bytes_remaining;
-                        let value = match value_decoder.next(value_bytes_remaining) {
-                            Err(e) => Err(e),
-                            Ok(None) => {
-                                let leftover = value_bytes_remaining.to_vec();
-                                value_buf = leftover;
-                                break;
+                    {
+                        let mut session = output.session(cap);
+                        while budget > 0 {
+                            let value = match value_decoder.next_pending() {
+                                Some(value) => value,
+                                None => break,
+                            };
+                            budget -= 1;
+                            if value.is_err() {
+                                n_errors += 1;
+                            } else {
+                                n_successes += 1;
                             }
-                            Ok(Some(value)) => Ok(value),
-                        };
-
-                        // If the decoders decoded a message, they need to have made progress consuming the bytes.
-                        // Otherwise, we risk going into an infinite loop.
-                        assert!(old_value_cursor != *value_bytes_remaining || value.is_err());
-
-                        let is_err = value.is_err();
-                        if is_err {
-                            n_errors += 1;
-                        } else if matches!(&value, Ok(_)) {
-                            n_successes += 1;
-                        }
-                        // `RangeFrom` `Iterator`'s never end
-                        let position = n_seen.next().unwrap();
-                        let metadata = to_metadata_row(
-                            &metadata_items,
-                            partition.clone(),
-                            position.into(),
-                            *upstream_time_millis,
-                            headers.as_deref(),
-                        );
-
-                        if value_bytes_remaining.is_empty() {
+                            // `RangeFrom` `Iterator`'s never end
+                            let position = n_seen.next().unwrap();
+                            let metadata = to_metadata_row(
+                                &metadata_items,
+                                None,
+                                partition.clone(),
+                                position.into(),
+                                *upstream_time_millis,
+                                headers.as_deref(),
+                            );
                             session.give(DecodeResult {
                                 key: None,
                                 value: Some(value.map(|r| (r, 1)).map_err(|inner| DecodeError {
@@ -628,9 +616,70 @@ where
                                 partition: partition.clone(),
                                 metadata,
                             });
-                            value_buf = vec![];
-                            break;
-                        } else {
+                        }
+                    }
+                    if budget == 0 {
+                        // Yield, so that the produced records can be processed downstream
+                        // before the remaining ones are decoded.
+                        activator.activate();
+                        break;
+                    }
+                    pending_object = None;
+                }
+
+                let (cap, data) = match queued.front_mut() {
+                    Some(front) => front,
+                    None => break,
+                };
+                let SourceOutput {
+                    key: _,
+                    value,
+                    position: _,
+                    upstream_time_millis,
+                    partition,
+                    headers,
+                    diff: (),
+                } = match data.pop_front() {
+                    Some(output) => output,
+                    None => {
+                        queued.pop_front();
+                        continue;
+                    }
+                };
+                // Currently Kafka is the only kind of source that can have metadata, and it is
+                // always delimited, so we will never have metadata in `render_decode`
+                let mut session = output.session(&*cap);
+
+                let value = match value {
+                    Some(data) => data,
+                    None => {
+                        let data = &mut &value_buf[..];
+                        let mut result = value_decoder.eof(data);
+                        if result.is_ok() && !data.is_empty() {
+                            result = Err(DecodeErrorKind::Text(format!(
+                                "Saw unexpected EOF with bytes remaining in buffer: {:?}",
+                                data
+                            )));
+                        }
+                        value_buf.clear();
+
+                        if let Some(value) = result.transpose() {
+                            if value.is_err() {
+                                n_errors += 1;
+                            } else {
+                                n_successes += 1;
+                            }
+                            // `RangeFrom` `Iterator`'s never end
+                            let position = n_seen.next().unwrap();
+                            let metadata = to_metadata_row(
+                                &metadata_items,
+                                None,
+                                partition.clone(),
+                                position.into(),
+                                upstream_time_millis,
+                                headers.as_deref(),
+                            );
+
                             session.give(DecodeResult {
                                 key: None,
                                 value: Some(value.map(|r| (r, 1)).map_err(|inner| DecodeError {
@@ -638,19 +687,105 @@ where
                                     raw: None,
                                 })),
                                 position: position.into(),
-                                upstream_time_millis: *upstream_time_millis,
+                                upstream_time_millis,
                                 partition: partition.clone(),
                                 metadata,
                             });
                         }
-                        if is_err {
-                            // If decoding has gone off the rails, we can no longer be sure that the delimiters are correct, so it
-                            // makes no sense to keep going.
+
+                        // Decoders of whole objects may produce more than one
+                        // record at EOF, which are produced incrementally.
+                        pending_object = Some(PendingObject {
+                            cap: cap.clone(),
+                            upstream_time_millis,
+                            partition,
+                            headers,
+                        });
+                        continue;
+                    }
+                };
+                // Check whether we have a partial message from last time.
+                // If so, we need to prepend it to the bytes we got from _this_ message.
+                let value = if value_buf.is_empty() {
+                    &value
+                } else {
+                    value_buf.extend_from_slice(&*value);
+                    &value_buf
+                };
+
+                let value_bytes_remaining = &mut value.as_slice();
+                // The intent is that the below loop runs as long as there are more bytes to decode.
+                //
+                // We'd like to be able to write `while !value_cursor.empty()`
+                // here, but that runs into borrow checker issues, so we use `loop`
+                // and break manually.
+                loop {
+                    let old_value_cursor = *value_bytes_remaining;
+                    let value = match value_decoder.next(value_bytes_remaining) {
+                        Err(e) => Err(e),
+                        Ok(None) => {
+                            let leftover = value_bytes_remaining.to_vec();
+                            value_buf = leftover;
                             break;
                         }
+                        Ok(Some(value)) => Ok(value),
+                    };
+
+                    // If the decoders decoded a message, they need to have made progress consuming the bytes.
+                    // Otherwise, we risk going into an infinite loop.
+                    assert!(old_value_cursor != *value_bytes_remaining || value.is_err());
+
+                    let is_err = value.is_err();
+                    if is_err {
+                        n_errors += 1;
+                    } else if matches!(&value, Ok(_)) {
+                        n_successes += 1;
+                    }
+                    // `RangeFrom` `Iterator`'s never end
+                    let position = n_seen.next().unwrap();
+                    let metadata = to_metadata_row(
+                        &metadata_items,
+                        None,
+                        partition.clone(),
+                        position.into(),
+                        upstream_time_millis,
+                        headers.as_deref(),
+                    );
+
+                    if value_bytes_remaining.is_empty() {
+                        session.give(DecodeResult {
+                            key: None,
+                            value: Some(value.map(|r| (r, 1)).map_err(|inner| DecodeError {
+                                kind: inner,
+                                raw: None,
+                            })),
+                            position: position.into(),
+                            upstream_time_millis: upstream_time_millis,
+                            partition: partition.clone(),
+                            metadata,
+                        });
+                        value_buf = vec![];
+                        break;
+                    } else {
+                        session.give(DecodeResult {
+                            key: None,
+                            value: Some(value.map(|r| (r, 1)).map_err(|inner| DecodeError {
+                                kind: inner,
+                                raw: None,
+                            })),
+                            position: position.into(),
+                            upstream_time_millis: upstream_time_millis,
+                            partition: partition.clone(),
+                            metadata,
+                        });
+                    }
+                    if is_err {
+                        // If decoding has gone off the rails, we can no longer be sure that the delimiters are correct, so it
+                        // makes no sense to keep going.
+                        break;
                     }
                 }
-            });
+            }
             // Matching historical practice, we only log metrics on the value decoder.
             if n_errors > 0 {
                 value_decoder.log_errors(n_errors);
@@ -663,8 +798,26 @@ where
     (results, None)
 }
 
+/// The maximum number of records of an object that [`render_decode`] produces in
+/// one activation.
+const MAX_OBJECT_RECORDS_PER_ACTIVATION: usize = 10_000;
+
+/// The metadata of an object whose records [`render_decode`] has not yet fully
+/// produced.
+struct PendingObject {
+    cap: Capability<Timestamp>,
+    upstream_time_millis: Option<i64>,
+    partition: PartitionId,
+    headers: Option<Vec<(String, Option<Vec<u8>>)>>,
+}
+
+/// Packs the metadata columns of a message.
+///
+/// `single_topic` is the topic of Kafka sources that read from a single topic,
+/// whose partition ids are not qualified with the topic.
 fn to_metadata_row(
     metadata_items: &[IncludedColumnSource],
+    single_topic: Option<&str>,
     partition: PartitionId,
     position: MzOffset,
     upstream_time_millis: Option<i64>,
@@ -674,7 +827,12 @@ fn to_metadata_row(
     let mut row = Row::default();
     let mut packer = row.packer();
     match partition {
-        PartitionId::Kafka(partition) => {
+        PartitionId::Kafka(_) | PartitionId::KafkaTopic(_, _) => {
+            let (topic, partition) = match &partition {
+                PartitionId::Kafka(partition) => (single_topic, *partition),
+                PartitionId::KafkaTopic(topic, partition) => (Some(topic.as_str()), *partition),
+                _ => unreachable!(),
+            };
             for item in metadata_items.iter() {
                 match item {
                     IncludedColumnSource::Partition => packer.push(Datum::from(partition)),
@@ -696,7 +854,10 @@ fn to_metadata_row(
                             .into();
                         packer.push(d)
                     }
-                    IncludedColumnSource::Topic => unreachable!("Topic is not implemented yet"),
+                    IncludedColumnSource::Topic => {
+                        let topic = topic.expect("topic included without known topic");
+                        packer.push(Datum::String(topic))
+                    }
                     IncludedColumnSource::Headers => {
                         packer.push_list_with(|r| {
                             // If the source asked for headers, but we didn't get any, we still
@@ -723,7 +884,7 @@ fn to_metadata_row(
                 }
             }
         }
-        PartitionId::None => {
+        PartitionId::None | PartitionId::Kinesis(_) => {
             if !metadata_items.is_empty() {
                 unreachable!("Only Kafka supports metadata items");
             }
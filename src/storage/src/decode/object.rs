@@ -0,0 +1,160 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Decoding of formats that can only be decoded once an entire object is
+//! available, like Parquet and Avro object container files.
+//!
+//! The bytes of each object are written to an anonymous scratch file as they
+//! arrive, rather than buffered in memory. Once the end of the object is seen,
+//! its rows are decoded from the file one row group or block at a time, as
+//! they are requested.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{Seek, SeekFrom, Write};
+
+use mz_interchange::avro::{OcfDecoder, OcfRows};
+use mz_interchange::parquet::{ParquetDecoder, ParquetRows};
+use mz_repr::Row;
+use mz_storage_client::types::errors::DecodeErrorKind;
+
+#[derive(Debug)]
+pub enum ObjectFormat {
+    Parquet(ParquetDecoder),
+    AvroOcf(OcfDecoder),
+}
+
+impl ObjectFormat {
+    fn name(&self) -> &'static str {
+        match self {
+            ObjectFormat::Parquet(_) => "parquet",
+            ObjectFormat::AvroOcf(_) => "avro ocf",
+        }
+    }
+}
+
+/// The rows of an object that remain to be decoded.
+enum ObjectRows {
+    Parquet(ParquetRows<File>),
+    AvroOcf(OcfRows<File>),
+}
+
+/// Spools the bytes of an object to a scratch file until its end is seen, then
+/// decodes its rows incrementally.
+pub struct ObjectDecoderState {
+    format: ObjectFormat,
+    /// The scratch file holding the bytes of the current object, if any.
+    file: Option<File>,
+    /// An error that occurred while spooling the current object.
+    error: Option<String>,
+    /// The rows of the last object that have not yet been returned.
+    pending: Option<ObjectRows>,
+}
+
+impl fmt::Debug for ObjectDecoderState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObjectDecoderState")
+            .field("format", &self.format)
+            .field("file", &self.file)
+            .field("error", &self.error)
+            .field("pending", &self.pending.is_some())
+            .finish()
+    }
+}
+
+impl ObjectDecoderState {
+    pub fn new(format: ObjectFormat) -> Self {
+        ObjectDecoderState {
+            format,
+            file: None,
+            error: None,
+            pending: None,
+        }
+    }
+
+    pub fn format(&self) -> &ObjectFormat {
+        &self.format
+    }
+
+    /// Consumes all of `chunk`. No rows are produced until [`Self::eof`].
+    pub fn buffer(&mut self, chunk: &mut &[u8]) {
+        let bytes = std::mem::take(chunk);
+        if bytes.is_empty() || self.error.is_some() {
+            return;
+        }
+        // Rows of the previous object that were not requested are dropped.
+        self.pending = None;
+        let result = match &mut self.file {
+            Some(file) => file.write_all(bytes),
+            None => tempfile::tempfile().and_then(|mut file| {
+                file.write_all(bytes)?;
+                self.file = Some(file);
+                Ok(())
+            }),
+        };
+        if let Err(err) = result {
+            self.file = None;
+            self.error = Some(format!("spooling object to scratch file: {}", err));
+        }
+    }
+
+    /// Starts decoding the spooled object, returning its first row. The
+    /// remaining rows are available via [`Self::next_pending`].
+    pub fn eof(&mut self) -> Result<Option<Row>, DecodeErrorKind> {
+        if let Some(err) = self.error.take() {
+            return Err(self.decode_error(err));
+        }
+        let mut file = match self.file.take() {
+            Some(file) => file,
+            None => return Ok(None),
+        };
+        let rows = file
+            .seek(SeekFrom::Start(0))
+            .map_err(anyhow::Error::from)
+            .and_then(|_| match &self.format {
+                ObjectFormat::Parquet(decoder) => decoder.read(file).map(ObjectRows::Parquet),
+                ObjectFormat::AvroOcf(decoder) => decoder.read(file).map(ObjectRows::AvroOcf),
+            });
+        match rows {
+            Ok(rows) => {
+                self.pending = Some(rows);
+                self.next_pending().transpose()
+            }
+            Err(err) => Err(self.decode_error(format!("{:#}", err))),
+        }
+    }
+
+    /// Decodes the next row of the last object seen by [`Self::eof`], if any
+    /// remain.
+    pub fn next_pending(&mut self) -> Option<Result<Row, DecodeErrorKind>> {
+        let next = match self.pending.as_mut()? {
+            ObjectRows::Parquet(rows) => rows.next(),
+            ObjectRows::AvroOcf(rows) => rows.next(),
+        };
+        match next {
+            Some(Ok(row)) => Some(Ok(row)),
+            Some(Err(err)) => {
+                self.pending = None;
+                Some(Err(self.decode_error(format!("{:#}", err))))
+            }
+            None => {
+                self.pending = None;
+                None
+            }
+        }
+    }
+
+    fn decode_error(&self, err: String) -> DecodeErrorKind {
+        DecodeErrorKind::Text(format!(
+            "{} deserialization error: {}",
+            self.format.name(),
+            err
+        ))
+    }
+}
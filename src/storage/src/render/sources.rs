@@ -236,6 +236,16 @@ where
 {
     let mut needed_tokens: Vec<Rc<dyn Any>> = vec![];
 
+    // Kafka sources that read from a single topic do not qualify their
+    // partitions with the topic, so the decoder must be told which topic to
+    // report for `INCLUDE TOPIC`.
+    let single_topic = match &description.desc.connection {
+        GenericSourceConnection::Kafka(connection) => {
+            connection.single_topic().map(|topic| topic.to_string())
+        }
+        _ => None,
+    };
+
     let SourceDesc {
         encoding,
         envelope,
@@ -293,6 +303,7 @@ where
                     value_encoding,
                     dataflow_debug_name,
                     metadata_columns,
+                    single_topic,
                     storage_state.decode_metrics.clone(),
                     &storage_state.connection_context,
                 ),
@@ -304,17 +315,35 @@ where
                     storage_state.decode_metrics.clone(),
                     &storage_state.connection_context,
                 ),
-                SourceType::Row(source) => (
-                    source.map(|r| DecodeResult {
-                        key: None,
-                        value: Some(Ok((r.value, r.diff))),
-                        position: r.position,
-                        upstream_time_millis: r.upstream_time_millis,
-                        partition: r.partition,
-                        metadata: Row::default(),
-                    }),
-                    None,
-                ),
+                SourceType::Row(source) => {
+                    // Row sources with a key encoding produce rows that contain the key
+                    // columns followed by the value columns.
+                    let key_arity = key_encoding.map(|key| match key.inner {
+                        DataEncodingInner::RowCodec(desc) => desc.arity(),
+                        _ => unreachable!("row sources must have row-encoded keys"),
+                    });
+                    (
+                        source.map(move |r| {
+                            let (key, value) = match key_arity {
+                                Some(key_arity) => {
+                                    let mut datums = r.value.iter();
+                                    let key = Row::pack(datums.by_ref().take(key_arity));
+                                    (Some(Ok(key)), Row::pack(datums))
+                                }
+                                None => (None, r.value),
+                            };
+                            DecodeResult {
+                                key,
+                                value: Some(Ok((value, r.diff))),
+                                position: r.position,
+                                upstream_time_millis: r.upstream_time_millis,
+                                partition: r.partition,
+                                metadata: Row::default(),
+                            }
+                        }),
+                        None,
+                    )
+                }
             };
             if let Some(tok) = extra_token {
                 needed_tokens.push(Rc::new(tok));
@@ -10,6 +10,7 @@
 //! An interactive dataflow server.
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
 use anyhow::anyhow;
@@ -20,6 +21,7 @@ use mz_build_info::BuildInfo;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::NowFn;
 use mz_persist_client::cache::PersistClientCache;
+use mz_persist_client::rpc::{GrpcPubSubClient, NoopPubSubClient};
 use mz_persist_client::PersistConfig;
 use mz_service::local::LocalClient;
 use mz_storage_client::client::StorageClient;
@@ -44,6 +46,12 @@ pub struct Config {
     pub metrics_registry: MetricsRegistry,
     /// Configuration for source and sink connection.
     pub connection_context: ConnectionContext,
+    /// The URL of the persist pubsub server, if any.
+    pub persist_pubsub_url: Option<String>,
+    /// A local directory in which to cache persist blob reads, if any.
+    pub persist_blob_disk_cache_dir: Option<PathBuf>,
+    /// Overrides the maximum size of the persist blob disk cache.
+    pub persist_blob_disk_cache_max_size_bytes: Option<usize>,
 }
 
 /// A handle to a running dataflow server.
@@ -73,9 +81,18 @@ pub fn serve(
 
     let tokio_executor = tokio::runtime::Handle::current();
     let now = config.now;
+    let mut persist_cfg = PersistConfig::new(config.build_info, now.clone());
+    persist_cfg.blob_disk_cache_dir = config.persist_blob_disk_cache_dir;
+    if let Some(max_size_bytes) = config.persist_blob_disk_cache_max_size_bytes {
+        persist_cfg.blob_disk_cache_max_size_bytes = max_size_bytes;
+    }
     let persist_clients = PersistClientCache::new(
-        PersistConfig::new(config.build_info, now.clone()),
+        persist_cfg,
         &config.metrics_registry,
+        |_, metrics| match config.persist_pubsub_url {
+            Some(url) => GrpcPubSubClient::connect(url, metrics),
+            None => Arc::new(NoopPubSubClient),
+        },
     );
     let persist_clients = Arc::new(tokio::sync::Mutex::new(persist_clients));
 
@@ -259,6 +259,7 @@ mod tests {
     use once_cell::sync::Lazy;
 
     use mz_ore::metrics::MetricsRegistry;
+    use mz_persist_client::rpc::NoopPubSubClient;
     use mz_persist_client::{PersistConfig, PersistLocation, ShardId};
 
     // Test suite
@@ -498,6 +499,7 @@ mod tests {
         Arc::new(Mutex::new(PersistClientCache::new(
             PersistConfig::new(&DUMMY_BUILD_INFO, SYSTEM_TIME.clone()),
             &MetricsRegistry::new(),
+            |_, _| Arc::new(NoopPubSubClient),
         )))
     }
 
@@ -10,6 +10,8 @@
 //! A wrapper that turns a regular [`SourceReader`] into a delimited source
 //! reader.
 
+use std::collections::HashMap;
+
 use timely::scheduling::activate::SyncActivator;
 
 use mz_expr::PartitionId;
@@ -53,6 +55,7 @@ where
         worker_count: usize,
         consumer_activator: SyncActivator,
         restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        resume_tokens: HashMap<PartitionId, String>,
         encoding: SourceDataEncoding,
         metrics: crate::source::metrics::SourceBaseMetrics,
         connection_context: ConnectionContext,
@@ -65,6 +68,7 @@ where
                 worker_count,
                 consumer_activator,
                 restored_offsets,
+                resume_tokens,
                 encoding,
                 metrics,
                 connection_context,
@@ -133,6 +137,9 @@ where
             NextMessage::Ready(SourceMessageType::SourceStatus(update)) => {
                 Ok(NextMessage::Ready(SourceMessageType::SourceStatus(update)))
             }
+            NextMessage::Ready(SourceMessageType::ResumeToken(pid, offset, token)) => Ok(
+                NextMessage::Ready(SourceMessageType::ResumeToken(pid, offset, token)),
+            ),
             NextMessage::Pending => Ok(NextMessage::Pending),
             NextMessage::TransientDelay => Ok(NextMessage::TransientDelay),
             NextMessage::Finished => Ok(NextMessage::Finished),
@@ -7,6 +7,8 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::{HashMap, VecDeque};
+use std::mem;
 use std::time::{Duration, Instant};
 
 use timely::scheduling::SyncActivator;
@@ -28,15 +30,18 @@ use crate::source::{NextMessage, SourceReader, SourceReaderError};
 mod auction;
 mod counter;
 mod datums;
+mod key_value;
 mod tpch;
 
 pub use auction::Auction;
 pub use counter::Counter;
 pub use datums::Datums;
+pub use key_value::KeyValue;
 pub use tpch::Tpch;
 
-pub fn as_generator(g: &LoadGenerator) -> Box<dyn Generator> {
-    match g {
+/// Returns the partitions of `g`, each with the generator of its messages.
+pub fn as_generators(g: &LoadGenerator) -> Vec<(PartitionId, Box<dyn Generator>)> {
+    let generator: Box<dyn Generator> = match g {
         LoadGenerator::Auction => Box::new(Auction {}),
         LoadGenerator::Counter => Box::new(Counter {}),
         LoadGenerator::Datums => Box::new(Datums {}),
@@ -53,22 +58,52 @@ pub fn as_generator(g: &LoadGenerator) -> Box<dyn Generator> {
             count_orders: *count_orders,
             count_clerk: *count_clerk,
         }),
-    }
+        LoadGenerator::KeyValue {
+            keys,
+            snapshot_rounds,
+            value_size,
+            batch_size,
+            partitions,
+            seed,
+        } => {
+            return (0..*partitions)
+                .map(|partition| {
+                    let pid = PartitionId::Kafka(
+                        i32::try_from(partition).expect("partition count validated in planning"),
+                    );
+                    let generator: Box<dyn Generator> = Box::new(KeyValue {
+                        keys: *keys,
+                        snapshot_rounds: *snapshot_rounds,
+                        value_size: *value_size,
+                        batch_size: *batch_size,
+                        partitions: *partitions,
+                        partition,
+                        seed: *seed,
+                    });
+                    (pid, generator)
+                })
+                .collect();
+        }
+    };
+    vec![(PartitionId::None, generator)]
 }
 
 pub struct LoadGeneratorSourceReader {
+    // The partitions this worker is responsible for reading, which take turns
+    // producing messages.
+    partitions: VecDeque<GeneratorPartition>,
+    tick: Duration,
+    // The partitions this worker is not responsible for reading, which it has
+    // to report back before it produces any messages.
+    unconsumed_partitions: Vec<PartitionId>,
+}
+
+/// A partition of a load generator.
+struct GeneratorPartition {
+    pid: PartitionId,
     rows: Box<dyn Iterator<Item = (usize, GeneratorMessageType, Row)>>,
     last: Instant,
-    tick: Duration,
     offset: MzOffset,
-    // Load-generator sources support single-threaded ingestion only, so only
-    // one of the `LoadGeneratorSourceReader`s will actually produce data.
-    active_read_worker: bool,
-    // The non-active reader (see above `active_read_worker`) has to report back
-    // that is is not consuming from the one [`PartitionId:None`] partition.
-    // Before it can return a [`NextMessage::Finished`]. This is keeping track
-    // of that.
-    reported_unconsumed_partitions: bool,
 }
 
 impl SourceConnectionBuilder for LoadGeneratorSourceConnection {
@@ -83,42 +118,41 @@ impl SourceConnectionBuilder for LoadGeneratorSourceConnection {
         worker_count: usize,
         _consumer_activator: SyncActivator,
         start_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _resume_tokens: HashMap<PartitionId, String>,
         _encoding: SourceDataEncoding,
         _metrics: SourceBaseMetrics,
         _connection_context: ConnectionContext,
     ) -> Result<(Self::Reader, Self::OffsetCommitter), anyhow::Error> {
-        let active_read_worker =
-            crate::source::responsible_for(&source_id, worker_id, worker_count, &PartitionId::None);
-
-        let offset = start_offsets
+        let start_offsets: HashMap<_, _> = start_offsets
             .into_iter()
-            .find_map(|(pid, offset)| {
-                if pid == PartitionId::None {
-                    offset
-                } else {
-                    None
-                }
-            })
-            .unwrap_or_default();
-
-        let mut rows =
-            as_generator(&self.load_generator).by_seed(mz_ore::now::SYSTEM_TIME.clone(), None);
+            .filter_map(|(pid, offset)| offset.map(|offset| (pid, offset)))
+            .collect();
+        let tick = Duration::from_micros(self.tick_micros.unwrap_or(1_000_000));
 
-        // Skip forward to the requested offset.
-        for _ in 0..offset.offset {
-            rows.next();
+        let mut partitions = VecDeque::new();
+        let mut unconsumed_partitions = vec![];
+        for (pid, generator) in as_generators(&self.load_generator) {
+            if !crate::source::responsible_for(&source_id, worker_id, worker_count, &pid) {
+                unconsumed_partitions.push(pid);
+                continue;
+            }
+            let offset = start_offsets.get(&pid).copied().unwrap_or_default();
+            let mut rows = generator.by_seed(mz_ore::now::SYSTEM_TIME.clone(), None);
+            skip_to_offset(&mut rows, offset);
+            partitions.push_back(GeneratorPartition {
+                pid,
+                rows,
+                // Subtract tick so we immediately produce a row.
+                last: Instant::now() - tick,
+                offset,
+            });
         }
 
-        let tick = Duration::from_micros(self.tick_micros.unwrap_or(1_000_000));
         Ok((
             LoadGeneratorSourceReader {
-                rows: Box::new(rows),
-                // Subtract tick so we immediately produce a row.
-                last: Instant::now() - tick,
+                partitions,
                 tick,
-                offset,
-                active_read_worker,
-                reported_unconsumed_partitions: false,
+                unconsumed_partitions,
             },
             LogCommitter {
                 source_id,
@@ -129,6 +163,24 @@ impl SourceConnectionBuilder for LoadGeneratorSourceConnection {
     }
 }
 
+/// Skips forward to the messages at `offset`, as when resuming a source.
+///
+/// Offsets only advance on finalized messages, so this skips every message up
+/// to and including the `offset`-th finalized one.
+fn skip_to_offset(
+    rows: &mut dyn Iterator<Item = (usize, GeneratorMessageType, Row)>,
+    offset: MzOffset,
+) {
+    let mut skipped = 0;
+    while skipped < offset.offset {
+        match rows.next() {
+            Some((_, GeneratorMessageType::Finalized, _)) => skipped += 1,
+            Some((_, GeneratorMessageType::InProgress, _)) => {}
+            None => break,
+        }
+    }
+}
+
 impl SourceReader for LoadGeneratorSourceReader {
     type Key = ();
     type Value = Row;
@@ -138,43 +190,149 @@ impl SourceReader for LoadGeneratorSourceReader {
     fn get_next_message(
         &mut self,
     ) -> Result<NextMessage<Self::Key, Self::Value, Self::Diff>, SourceReaderError> {
-        if !self.active_read_worker {
-            if !self.reported_unconsumed_partitions {
-                self.reported_unconsumed_partitions = true;
-                return Ok(NextMessage::Ready(
-                    SourceMessageType::DropPartitionCapabilities(vec![PartitionId::None]),
-                ));
-            }
-            return Ok(NextMessage::Finished);
+        if !self.unconsumed_partitions.is_empty() {
+            return Ok(NextMessage::Ready(
+                SourceMessageType::DropPartitionCapabilities(mem::take(
+                    &mut self.unconsumed_partitions,
+                )),
+            ));
         }
 
-        if self.last.elapsed() < self.tick {
-            return Ok(NextMessage::Pending);
+        loop {
+            if self.partitions.is_empty() {
+                return Ok(NextMessage::Finished);
+            }
+            let tick = self.tick;
+            let ready = self
+                .partitions
+                .iter()
+                .position(|partition| partition.last.elapsed() >= tick);
+            let mut partition = match ready {
+                Some(idx) => self.partitions.remove(idx).expect("valid index"),
+                None => return Ok(NextMessage::Pending),
+            };
+
+            // Exhausted partitions are not read again.
+            let (output, typ, value) = match partition.rows.next() {
+                Some(row) => row,
+                None => continue,
+            };
+
+            let message = SourceMessage {
+                output,
+                partition: partition.pid.clone(),
+                offset: partition.offset,
+                upstream_time_millis: None,
+                key: (),
+                value,
+                headers: None,
+                specific_diff: 1,
+            };
+            let message = match typ {
+                GeneratorMessageType::Finalized => {
+                    partition.last += tick;
+                    partition.offset += 1;
+                    SourceMessageType::Finalized(message)
+                }
+                GeneratorMessageType::InProgress => SourceMessageType::InProgress(message),
+            };
+            self.partitions.push_back(partition);
+            return Ok(NextMessage::Ready(message));
         }
+    }
+}
 
-        let (output, typ, value) = match self.rows.next() {
-            Some(row) => row,
-            None => return Ok(NextMessage::Finished),
+#[cfg(test)]
+mod tests {
+    use mz_ore::cast::CastFrom;
+    use mz_storage_client::types::sources::{Generator, GeneratorMessageType, MzOffset};
+
+    use mz_expr::PartitionId;
+    use mz_repr::Datum;
+    use mz_storage_client::types::sources::LoadGenerator;
+
+    use super::{as_generators, skip_to_offset, KeyValue};
+
+    #[test]
+    fn resume_at_offset() {
+        let generator = KeyValue {
+            keys: 4,
+            snapshot_rounds: 2,
+            value_size: 4,
+            batch_size: 3,
+            partitions: 1,
+            partition: 0,
+            seed: 42,
         };
+        let now = mz_ore::now::SYSTEM_TIME.clone();
+        // The snapshot of 8 messages is at offset 0, and every following batch
+        // of 3 messages at the next offset.
+        let messages: Vec<_> = generator
+            .by_seed(now.clone(), None)
+            .take(20)
+            .map(|(output, typ, row)| {
+                let finalized = matches!(typ, GeneratorMessageType::Finalized);
+                (output, finalized, row)
+            })
+            .collect();
+        let mut starts = vec![0];
+        starts.extend(
+            messages
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, finalized, _))| *finalized)
+                .map(|(idx, _)| idx + 1),
+        );
+        assert_eq!(starts, vec![0, 8, 11, 14, 17, 20]);
+
+        // A source that is restarted at an offset produces the same messages
+        // as one that kept running.
+        for (offset, start) in starts.into_iter().enumerate() {
+            let mut rows = generator.by_seed(now.clone(), None);
+            skip_to_offset(&mut rows, MzOffset::from(u64::cast_from(offset)));
+            let resumed: Vec<_> = rows
+                .take(messages.len() - start)
+                .map(|(output, typ, row)| {
+                    let finalized = matches!(typ, GeneratorMessageType::Finalized);
+                    (output, finalized, row)
+                })
+                .collect();
+            assert_eq!(resumed, messages[start..], "resumed at offset {}", offset);
+        }
+    }
 
-        let message = SourceMessage {
-            output,
-            partition: PartitionId::None,
-            offset: self.offset,
-            upstream_time_millis: None,
-            key: (),
-            value,
-            headers: None,
-            specific_diff: 1,
+    #[test]
+    fn partitions_split_keys() {
+        let generator = LoadGenerator::KeyValue {
+            keys: 10,
+            snapshot_rounds: 1,
+            value_size: 4,
+            batch_size: 1,
+            partitions: 3,
+            seed: 0,
         };
-        let message = match typ {
-            GeneratorMessageType::Finalized => {
-                self.last += self.tick;
-                self.offset += 1;
-                SourceMessageType::Finalized(message)
+        let now = mz_ore::now::SYSTEM_TIME.clone();
+        let mut keys = vec![];
+        for (pid, generator) in as_generators(&generator) {
+            let partition = match pid {
+                PartitionId::Kafka(partition) => u64::try_from(partition).unwrap(),
+                pid => panic!("unexpected partition {}", pid),
+            };
+            // Every partition snapshots its own keys, at its own first offset.
+            let mut rows = generator.by_seed(now.clone(), None);
+            loop {
+                let (_, typ, row) = rows.next().unwrap();
+                let datums: Vec<_> = row.iter().collect();
+                let key = datums[0].unwrap_uint64();
+                assert_eq!(key % 3, partition);
+                assert_eq!(datums[1], Datum::UInt64(partition));
+                keys.push(key);
+                if let GeneratorMessageType::Finalized = typ {
+                    break;
+                }
             }
-            GeneratorMessageType::InProgress => SourceMessageType::InProgress(message),
-        };
-        Ok(NextMessage::Ready(message))
+        }
+        keys.sort();
+        assert_eq!(keys, (0..10).collect::<Vec<_>>());
     }
 }
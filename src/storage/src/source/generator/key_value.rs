@@ -0,0 +1,95 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::iter;
+
+use rand::prelude::{Rng, SmallRng};
+use rand::SeedableRng;
+
+use mz_ore::cast::CastFrom;
+use mz_ore::now::NowFn;
+use mz_repr::{Datum, Row};
+use mz_storage_client::types::sources::{Generator, GeneratorMessageType};
+
+/// Generates keyed records suitable for `ENVELOPE UPSERT`.
+///
+/// The keys are spread across `partitions` partitions, key `k` belonging to
+/// partition `k % partitions`, and each partition is generated independently.
+/// This generates the records of the keys of `partition`.
+///
+/// The snapshot writes every key `snapshot_rounds` times. Afterwards, each
+/// tick updates `batch_size` keys chosen at random. Every record is a row of
+/// the key, the partition of the key and a random value of `value_size`
+/// bytes.
+pub struct KeyValue {
+    pub keys: u64,
+    pub snapshot_rounds: u64,
+    pub value_size: u64,
+    pub batch_size: u64,
+    pub partitions: u64,
+    pub partition: u64,
+    pub seed: u64,
+}
+
+impl Generator for KeyValue {
+    fn by_seed(
+        &self,
+        _now: NowFn,
+        seed: Option<u64>,
+    ) -> Box<dyn Iterator<Item = (usize, GeneratorMessageType, Row)>> {
+        let KeyValue {
+            keys,
+            snapshot_rounds,
+            value_size,
+            batch_size,
+            partitions,
+            partition,
+            seed: default_seed,
+        } = *self;
+        // Every partition gets its own sequence of random numbers.
+        let mut rng = SmallRng::seed_from_u64(seed.unwrap_or(default_seed).wrapping_add(partition));
+        // The keys of the partition are `partition + i * partitions` for `i`
+        // in `0..partition_keys`.
+        let partition_keys = (keys - partition + partitions - 1) / partitions;
+        let snapshot_len = partition_keys.saturating_mul(snapshot_rounds);
+        let mut value = vec![0; usize::cast_from(value_size)];
+        let mut snapshotted = 0;
+        let mut batched = 0;
+
+        Box::new(iter::from_fn(move || {
+            let (idx, typ) = if snapshotted < snapshot_len {
+                let idx = snapshotted % partition_keys;
+                snapshotted += 1;
+                // The entire snapshot is emitted at a single offset.
+                if snapshotted == snapshot_len {
+                    (idx, GeneratorMessageType::Finalized)
+                } else {
+                    (idx, GeneratorMessageType::InProgress)
+                }
+            } else {
+                let idx = rng.gen_range(0..partition_keys);
+                batched += 1;
+                if batched == batch_size {
+                    batched = 0;
+                    (idx, GeneratorMessageType::Finalized)
+                } else {
+                    (idx, GeneratorMessageType::InProgress)
+                }
+            };
+            rng.fill(&mut value[..]);
+
+            let mut row = Row::with_capacity(3);
+            let mut packer = row.packer();
+            packer.push(Datum::UInt64(partition + idx * partitions));
+            packer.push(Datum::UInt64(partition));
+            packer.push(Datum::Bytes(&value));
+            Some((0, typ, row))
+        }))
+    }
+}
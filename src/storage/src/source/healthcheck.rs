@@ -365,6 +365,7 @@ mod tests {
     use once_cell::sync::Lazy;
 
     use mz_ore::metrics::MetricsRegistry;
+    use mz_persist_client::rpc::NoopPubSubClient;
     use mz_persist_client::{PersistConfig, PersistLocation, ShardId};
 
     // Test suite
@@ -637,6 +638,7 @@ mod tests {
         Arc::new(Mutex::new(PersistClientCache::new(
             PersistConfig::new(&DUMMY_BUILD_INFO, SYSTEM_TIME.clone()),
             &MetricsRegistry::new(),
+            |_, _| Arc::new(NoopPubSubClient),
         )))
     }
 
@@ -659,6 +661,7 @@ mod tests {
             remap_shard: ShardId::new(),
             data_shard: ShardId::new(),
             status_shard: Some(status_shard_id),
+            arity: 0,
         };
 
         Healthchecker::new(
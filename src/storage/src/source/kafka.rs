@@ -20,6 +20,7 @@ use rdkafka::message::{BorrowedMessage, Headers};
 use rdkafka::statistics::Statistics;
 use rdkafka::topic_partition_list::Offset;
 use rdkafka::{ClientContext, Message, TopicPartitionList};
+use regex::Regex;
 use timely::scheduling::activate::SyncActivator;
 use tokio::runtime::Handle as TokioHandle;
 use tracing::{error, info, warn};
@@ -30,7 +31,7 @@ use mz_ore::thread::{JoinHandleExt, UnparkOnDropHandle};
 use mz_repr::{adt::jsonb::Jsonb, GlobalId};
 use mz_storage_client::types::connections::{ConnectionContext, StringOrSecret};
 use mz_storage_client::types::sources::encoding::SourceDataEncoding;
-use mz_storage_client::types::sources::{KafkaSourceConnection, MzOffset};
+use mz_storage_client::types::sources::{KafkaSourceConnection, KafkaTopics, MzOffset};
 
 use crate::source::commit::LogCommitter;
 use crate::source::types::{OffsetCommitter, SourceConnectionBuilder};
@@ -44,8 +45,10 @@ mod metrics;
 
 /// Contains all information necessary to ingest data from Kafka
 pub struct KafkaSourceReader {
-    /// Name of the topic on which this source is backed on
-    topic_name: String,
+    /// The topics on which this source is backed on
+    topics: KafkaTopics,
+    /// How partitions of the source's topics map to the source's partition ids
+    partition_mapping: PartitionMapping,
     /// Name of the source (will have format kafka-source-id)
     source_name: String,
     /// Source global ID
@@ -61,13 +64,13 @@ pub struct KafkaSourceReader {
     /// The most recently read offset for each partition known to this source
     /// reader. An offset of -1 indicates that no prior message has been read
     /// for the given partition.
-    last_offsets: HashMap<i32, i64>,
+    last_offsets: HashMap<(String, i32), i64>,
     /// The offset to start reading from for each partition.
-    start_offsets: HashMap<i32, i64>,
+    start_offsets: HashMap<(String, i32), i64>,
     /// Channel to receive Kafka statistics JSON blobs from the stats callback.
     stats_rx: crossbeam_channel::Receiver<Jsonb>,
     /// The last partition we received
-    partition_info: Arc<Mutex<Option<Vec<i32>>>>,
+    partition_info: Arc<Mutex<Option<Vec<(String, i32)>>>>,
     /// A handle to the spawned metadata thread
     // Drop order is important here, we want the thread to be unparked after the `partition_info`
     // Arc has been dropped, so that the unpacked thread notices it and exits immediately
@@ -80,7 +83,7 @@ pub struct KafkaSourceReader {
 
 pub struct KafkaOffsetCommiter {
     source_id: GlobalId,
-    topic_name: String,
+    partition_mapping: PartitionMapping,
     logger: LogCommitter,
     consumer: Arc<BaseConsumer<BrokerRewritingClientContext<GlueConsumerContext>>>,
 }
@@ -97,14 +100,16 @@ impl SourceConnectionBuilder for KafkaSourceConnection {
         worker_count: usize,
         consumer_activator: SyncActivator,
         restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _resume_tokens: HashMap<PartitionId, String>,
         _: SourceDataEncoding,
         metrics: crate::source::metrics::SourceBaseMetrics,
         connection_context: ConnectionContext,
     ) -> Result<(Self::Reader, Self::OffsetCommitter), anyhow::Error> {
+        let partition_mapping = PartitionMapping::new(&self);
         let KafkaSourceConnection {
             connection,
             connection_id,
-            topic,
+            topics,
             group_id_prefix,
             environment_id,
             ..
@@ -164,22 +169,23 @@ impl SourceConnectionBuilder for KafkaSourceConnection {
         let consumer = Arc::new(consumer);
 
         // Start offsets is a map from partition to the next offset to read
-        // from.
-        let mut start_offsets: HashMap<_, i64> = self
-            .start_offsets
-            .into_iter()
-            .filter(|(pid, _offset)| {
-                let pid = PartitionId::Kafka(*pid);
-                crate::source::responsible_for(&source_id, worker_id, worker_count, &pid)
-            })
-            .map(|(k, v)| (k, v))
-            .collect();
+        // from. Explicit start offsets can only be specified for sources that
+        // read from a single topic.
+        let mut start_offsets: HashMap<_, i64> = match &topics {
+            KafkaTopics::Topic(topic) => self
+                .start_offsets
+                .into_iter()
+                .map(|(pid, offset)| ((topic.clone(), pid), offset))
+                .filter(|((topic, pid), _offset)| {
+                    let pid = partition_mapping.to_pid(topic, *pid);
+                    crate::source::responsible_for(&source_id, worker_id, worker_count, &pid)
+                })
+                .collect(),
+            KafkaTopics::List(_) | KafkaTopics::Pattern(_) => HashMap::new(),
+        };
 
         for (pid, restored_offset) in restored_offsets {
-            let pid = match pid {
-                PartitionId::Kafka(id) => id,
-                _ => panic!("unexpected partition id type"),
-            };
+            let pid = partition_mapping.from_pid(pid);
             if let Some(restored_offset) = restored_offset {
                 let restored_offset = i64::try_from(restored_offset.offset)
                     .expect("restored kafka offsets must fit into i64");
@@ -193,10 +199,16 @@ impl SourceConnectionBuilder for KafkaSourceConnection {
 
         info!("worker {worker_id}/{worker_count}: Instantiating Kafka source reader at offsets {start_offsets:?}");
 
+        // Topic patterns are validated during purification.
+        let topic_pattern = match &topics {
+            KafkaTopics::Pattern(pattern) => Some(KafkaTopics::compile_pattern(pattern)?),
+            KafkaTopics::Topic(_) | KafkaTopics::List(_) => None,
+        };
+
         let partition_info = Arc::new(Mutex::new(None));
         let metadata_thread_handle = {
             let partition_info = Arc::downgrade(&partition_info);
-            let topic = topic.clone();
+            let topics = topics.clone();
             let consumer = Arc::clone(&consumer);
             let metadata_refresh_frequency = connection
                 .options
@@ -214,7 +226,12 @@ impl SourceConnectionBuilder for KafkaSourceConnection {
                 .name("kafka-metadata".to_string())
                 .spawn(move || {
                     while let Some(partition_info) = partition_info.upgrade() {
-                        match get_kafka_partitions(&consumer, &topic, Duration::from_secs(30)) {
+                        match get_kafka_partitions(
+                            &consumer,
+                            &topics,
+                            topic_pattern.as_ref(),
+                            Duration::from_secs(30),
+                        ) {
                             Ok(info) => {
                                 *partition_info.lock().unwrap() = Some(info);
                                 thread::park_timeout(metadata_refresh_frequency);
@@ -226,10 +243,11 @@ impl SourceConnectionBuilder for KafkaSourceConnection {
                 .unwrap()
                 .unpark_on_drop()
         };
-        let partition_ids = start_offsets.keys().copied().collect();
+        let partition_ids = start_offsets.keys().cloned().collect();
         Ok((
             KafkaSourceReader {
-                topic_name: topic.clone(),
+                topics,
+                partition_mapping: partition_mapping.clone(),
                 source_name,
                 id: source_id,
                 partition_consumers: VecDeque::new(),
@@ -242,16 +260,11 @@ impl SourceConnectionBuilder for KafkaSourceConnection {
                 partition_info,
                 include_headers: self.include_headers.is_some(),
                 _metadata_thread_handle: metadata_thread_handle,
-                partition_metrics: KafkaPartitionMetrics::new(
-                    metrics,
-                    partition_ids,
-                    topic.clone(),
-                    source_id,
-                ),
+                partition_metrics: KafkaPartitionMetrics::new(metrics, partition_ids, source_id),
             },
             KafkaOffsetCommiter {
                 source_id,
-                topic_name: topic,
+                partition_mapping,
                 logger: LogCommitter {
                     source_id,
                     worker_id,
@@ -283,11 +296,11 @@ impl SourceReader for KafkaSourceReader {
             // like. Shouldn't be a problem though, because we rarely hear about
             // new partitions.
             let mut unconsumed_partitions = Vec::new();
-            for pid in partitions {
-                let pid = PartitionId::Kafka(pid);
+            for (topic, partition) in partitions {
+                let pid = self.partition_mapping.to_pid(&topic, partition);
                 if crate::source::responsible_for(&self.id, self.worker_id, self.worker_count, &pid)
                 {
-                    self.ensure_partition(pid);
+                    self.ensure_partition(topic, partition);
                 } else {
                     unconsumed_partitions.push(pid);
                 }
@@ -311,10 +324,14 @@ impl SourceReader for KafkaSourceReader {
             match result {
                 Err(e) => error!(
                     "kafka error when polling consumer for source: {} topic: {} : {}",
-                    self.source_name, self.topic_name, e
+                    self.source_name, self.topics, e
                 ),
                 Ok(message) => {
-                    let source_message = construct_source_message(&message, self.include_headers)?;
+                    let source_message = construct_source_message(
+                        &message,
+                        &self.partition_mapping,
+                        self.include_headers,
+                    )?;
                     next_message = self.handle_message(source_message);
                 }
             }
@@ -358,10 +375,7 @@ impl OffsetCommitter for KafkaOffsetCommiter {
             // Note that we expect the above layers to pre-filter
             // by partition for us. This is part of the
             // `OffsetCommitter` contract.
-            let pid = match pid {
-                PartitionId::Kafka(id) => id,
-                _ => panic!("unexpected partition id type"),
-            };
+            let (topic, pid) = self.partition_mapping.from_pid(pid);
 
             // This matches the behavior of auto-commit, where we commit a
             // pseudo-_frontier_. Additionally, overflow will be caught
@@ -373,7 +387,7 @@ impl OffsetCommitter for KafkaOffsetCommiter {
                     .try_into()
                     .expect("offset to be vald i64"),
             );
-            tpl.add_partition_offset(&self.topic_name, pid, offset_to_commit)
+            tpl.add_partition_offset(&topic, pid, offset_to_commit)
                 .expect("offset known to be valid");
         }
 
@@ -390,20 +404,17 @@ impl OffsetCommitter for KafkaOffsetCommiter {
 }
 
 impl KafkaSourceReader {
-    /// Ensures that a partition queue for `pid` exists.
-    fn ensure_partition(&mut self, pid: PartitionId) {
-        let pid = match pid {
-            PartitionId::Kafka(p) => p,
-            _ => unreachable!(),
-        };
-        if self.last_offsets.contains_key(&pid) {
+    /// Ensures that a partition queue for partition `pid` of `topic` exists.
+    fn ensure_partition(&mut self, topic: String, pid: i32) {
+        let key = (topic, pid);
+        if self.last_offsets.contains_key(&key) {
             return;
         }
 
-        let start_offset = self.start_offsets.get(&pid).copied().unwrap_or(0);
-        self.create_partition_queue(pid, Offset::Offset(start_offset));
+        let start_offset = self.start_offsets.get(&key).copied().unwrap_or(0);
+        self.create_partition_queue(&key.0, key.1, Offset::Offset(start_offset));
 
-        let prev = self.last_offsets.insert(pid, start_offset - 1);
+        let prev = self.last_offsets.insert(key, start_offset - 1);
 
         assert!(prev.is_none());
     }
@@ -415,25 +426,25 @@ impl KafkaSourceReader {
         self.partition_consumers.len().try_into().unwrap()
     }
 
-    /// Creates a new partition queue for `partition_id`.
-    fn create_partition_queue(&mut self, partition_id: i32, initial_offset: Offset) {
+    /// Creates a new partition queue for partition `partition_id` of `topic`.
+    fn create_partition_queue(&mut self, topic: &str, partition_id: i32, initial_offset: Offset) {
         info!(
             "Activating Kafka queue for {} [{}] (source {}) on worker {}",
-            self.topic_name, partition_id, self.id, self.worker_id
+            topic, partition_id, self.id, self.worker_id
         );
 
         // Collect old partition assignments
         let tpl = self.consumer.assignment().unwrap();
         // Create list from assignments
         let mut partition_list = TopicPartitionList::new();
-        for partition in tpl.elements_for_topic(&self.topic_name) {
+        for partition in tpl.elements() {
             partition_list
                 .add_partition_offset(partition.topic(), partition.partition(), partition.offset())
                 .expect("offset known to be valid");
         }
         // Add new partition
         partition_list
-            .add_partition_offset(&self.topic_name, partition_id, initial_offset)
+            .add_partition_offset(topic, partition_id, initial_offset)
             .expect("offset known to be valid");
         self.consumer
             .assign(&partition_list)
@@ -445,7 +456,7 @@ impl KafkaSourceReader {
         for pc in &mut self.partition_consumers {
             pc.partition_queue = self
                 .consumer
-                .split_partition_queue(&self.topic_name, pc.pid)
+                .split_partition_queue(&pc.topic, pc.pid)
                 .expect("partition known to be valid");
             pc.partition_queue.set_nonempty_callback({
                 let context = Arc::clone(&context);
@@ -455,20 +466,18 @@ impl KafkaSourceReader {
 
         let mut partition_queue = self
             .consumer
-            .split_partition_queue(&self.topic_name, partition_id)
+            .split_partition_queue(topic, partition_id)
             .expect("partition known to be valid");
         partition_queue.set_nonempty_callback(move || context.inner().activate());
         self.partition_consumers.push_front(PartitionConsumer::new(
+            topic.to_string(),
             partition_id,
+            self.partition_mapping.clone(),
             partition_queue,
             self.include_headers,
         ));
         assert_eq!(
-            self.consumer
-                .assignment()
-                .unwrap()
-                .elements_for_topic(&self.topic_name)
-                .len(),
+            self.consumer.assignment().unwrap().count(),
             self.partition_consumers.len()
         );
     }
@@ -476,9 +485,9 @@ impl KafkaSourceReader {
     /// Fast-forward consumer to specified Kafka Offset. Prints a warning if failed to do so
     /// Assumption: if offset does not exist (for instance, because of compaction), will seek
     /// to the next available offset
-    fn fast_forward_consumer(&self, pid: i32, next_offset: i64) {
+    fn fast_forward_consumer(&self, topic: &str, pid: i32, next_offset: i64) {
         let res = self.consumer.seek(
-            &self.topic_name,
+            topic,
             pid,
             Offset::Offset(next_offset),
             Duration::from_secs(1),
@@ -486,22 +495,20 @@ impl KafkaSourceReader {
         match res {
             Ok(_) => {
                 let res = self.consumer.position().unwrap_or_default().to_topic_map();
-                let position = res
-                    .get(&(self.topic_name.clone(), pid))
-                    .and_then(|p| match p {
-                        Offset::Offset(o) => Some(o),
-                        _ => None,
-                    });
+                let position = res.get(&(topic.to_string(), pid)).and_then(|p| match p {
+                    Offset::Offset(o) => Some(o),
+                    _ => None,
+                });
                 if let Some(position) = position {
                     if *position != next_offset {
-                        warn!("Did not fast-forward consumer on partition PID: {} to the correct Kafka offset. Currently at offset: {} Expected offset: {}",
-                              pid, position, next_offset);
+                        warn!("Did not fast-forward consumer on topic {} partition PID: {} to the correct Kafka offset. Currently at offset: {} Expected offset: {}",
+                              topic, pid, position, next_offset);
                     } else {
-                        info!("Successfully fast-forwarded consumer on partition PID: {} to Kafka offset {}.", pid, position);
+                        info!("Successfully fast-forwarded consumer on topic {} partition PID: {} to Kafka offset {}.", topic, pid, position);
                     }
                 } else {
-                    warn!("Tried to fast-forward consumer on partition PID: {} to Kafka offset {}. Could not obtain new consumer position",
-                          pid, next_offset);
+                    warn!("Tried to fast-forward consumer on topic {} partition PID: {} to Kafka offset {}. Could not obtain new consumer position",
+                          topic, pid, next_offset);
                 }
             }
             Err(e) => error!(
@@ -516,15 +523,14 @@ impl KafkaSourceReader {
         while let Ok(stats) = self.stats_rx.try_recv() {
             match serde_json::from_str::<Statistics>(&stats.to_string()) {
                 Ok(statistics) => {
-                    let topic = statistics.topics.get(&self.topic_name);
-                    match topic {
-                        Some(topic) => {
-                            for (id, partition) in &topic.partitions {
-                                self.partition_metrics
-                                    .set_offset_max(*id, partition.hi_offset);
-                            }
+                    if statistics.topics.is_empty() {
+                        error!("No stats found for topics: {}", &self.topics);
+                    }
+                    for (name, topic) in &statistics.topics {
+                        for (id, partition) in &topic.partitions {
+                            self.partition_metrics
+                                .set_offset_max(name, *id, partition.hi_offset);
                         }
-                        None => error!("No stats found for topic: {}", &self.topic_name),
                     }
                 }
                 Err(e) => {
@@ -546,17 +552,17 @@ impl KafkaSourceReader {
 
         let message = match partition_queue.get_next_message()? {
             Err(e) => {
-                let pid = partition_queue.pid();
+                let key = (partition_queue.topic().to_string(), partition_queue.pid());
                 let last_offset = self
                     .last_offsets
-                    .get(&pid)
+                    .get(&key)
                     .expect("partition known to be installed");
 
                 error!(
                     "kafka error consuming from source: {} topic: {}: partition: {} last processed offset: {} : {}",
                     self.source_name,
-                    self.topic_name,
-                    pid,
+                    key.0,
+                    key.1,
                     last_offset,
                     e
                     );
@@ -576,10 +582,7 @@ impl KafkaSourceReader {
         &mut self,
         message: SourceMessage<Option<Vec<u8>>, Option<Vec<u8>>, ()>,
     ) -> NextMessage<Option<Vec<u8>>, Option<Vec<u8>>, ()> {
-        let partition = match message.partition {
-            PartitionId::Kafka(pid) => pid,
-            _ => unreachable!(),
-        };
+        let partition = self.partition_mapping.from_pid(message.partition.clone());
 
         // Offsets are guaranteed to be 1) monotonically increasing *unless* there is
         // a network issue or a new partition added, at which point the consumer may
@@ -611,13 +614,13 @@ impl KafkaSourceReader {
                              source {} (reading topic {}, partition {}) \
                              received offset {} expected offset {:?}",
                 self.source_name,
-                self.topic_name,
-                partition,
+                partition.0,
+                partition.1,
                 message.offset.offset,
                 last_offset + 1,
             );
             // Seek to the *next* offset that we have not yet processed
-            self.fast_forward_consumer(partition, last_offset + 1);
+            self.fast_forward_consumer(&partition.0, partition.1, last_offset + 1);
             // We explicitly should not consume the message as we have already processed it
             // However, we make sure to activate the source to make sure that we get a chance
             // to read from this consumer again (even if no new data arrives)
@@ -631,6 +634,7 @@ impl KafkaSourceReader {
 
 fn construct_source_message(
     msg: &BorrowedMessage<'_>,
+    partition_mapping: &PartitionMapping,
     include_headers: bool,
 ) -> Result<SourceMessage<Option<Vec<u8>>, Option<Vec<u8>>, ()>, anyhow::Error> {
     let headers = match msg.headers() {
@@ -644,7 +648,7 @@ fn construct_source_message(
     };
     Ok(SourceMessage {
         output: 0,
-        partition: PartitionId::Kafka(msg.partition()),
+        partition: partition_mapping.to_pid(msg.topic(), msg.partition()),
         offset: u64::try_from(msg.offset())
             .map_err(|_| {
                 anyhow::anyhow!(
@@ -663,8 +667,12 @@ fn construct_source_message(
 
 /// Wrapper around a partition containing the underlying consumer
 struct PartitionConsumer {
+    /// the topic with which this consumer is associated
+    topic: String,
     /// the partition id with which this consumer is associated
     pid: i32,
+    /// How partitions map to the source's partition ids
+    partition_mapping: PartitionMapping,
     /// The underlying Kafka partition queue
     partition_queue: PartitionQueue<BrokerRewritingClientContext<GlueConsumerContext>>,
     /// Whether or not to unpack and allocate headers and pass them through in the `SourceMessage`
@@ -674,12 +682,16 @@ struct PartitionConsumer {
 impl PartitionConsumer {
     /// Creates a new partition consumer from underlying Kafka consumer
     fn new(
+        topic: String,
         pid: i32,
+        partition_mapping: PartitionMapping,
         partition_queue: PartitionQueue<BrokerRewritingClientContext<GlueConsumerContext>>,
         include_headers: bool,
     ) -> Self {
         PartitionConsumer {
+            topic,
             pid,
+            partition_mapping,
             partition_queue,
             include_headers,
         }
@@ -699,8 +711,12 @@ impl PartitionConsumer {
     > {
         match self.partition_queue.poll(Duration::from_millis(0)) {
             Some(Ok(msg)) => {
-                let result = construct_source_message(&msg, self.include_headers)?;
-                assert_eq!(result.partition, PartitionId::Kafka(self.pid));
+                let result =
+                    construct_source_message(&msg, &self.partition_mapping, self.include_headers)?;
+                assert_eq!(
+                    result.partition,
+                    self.partition_mapping.to_pid(&self.topic, self.pid)
+                );
                 Ok(Ok(Some(result)))
             }
             Some(Err(err)) => Ok(Err(err)),
@@ -708,12 +724,51 @@ impl PartitionConsumer {
         }
     }
 
+    /// Return the topic for this PartitionConsumer
+    fn topic(&self) -> &str {
+        &self.topic
+    }
+
     /// Return the partition id for this PartitionConsumer
     fn pid(&self) -> i32 {
         self.pid
     }
 }
 
+/// Translates between the partitions of a source's Kafka topics and the
+/// [`PartitionId`]s that the source reports.
+#[derive(Clone)]
+struct PartitionMapping {
+    /// The topic of sources whose partition ids are not qualified with their
+    /// topic.
+    topic: Option<String>,
+}
+
+impl PartitionMapping {
+    fn new(connection: &KafkaSourceConnection) -> Self {
+        PartitionMapping {
+            topic: connection.single_topic().map(|topic| topic.to_string()),
+        }
+    }
+
+    /// Returns the partition id of partition `pid` of `topic`.
+    fn to_pid(&self, topic: &str, pid: i32) -> PartitionId {
+        match &self.topic {
+            Some(_) => PartitionId::Kafka(pid),
+            None => PartitionId::KafkaTopic(topic.to_string(), pid),
+        }
+    }
+
+    /// Returns the topic and partition identified by `pid`.
+    fn from_pid(&self, pid: PartitionId) -> (String, i32) {
+        match (&self.topic, pid) {
+            (Some(topic), PartitionId::Kafka(pid)) => (topic.clone(), pid),
+            (None, PartitionId::KafkaTopic(topic, pid)) => (topic, pid),
+            (_, pid) => panic!("unexpected partition id type: {:?}", pid),
+        }
+    }
+}
+
 /// An implementation of [`ConsumerContext`] that forwards statistics to the
 /// worker
 struct GlueConsumerContext {
@@ -754,21 +809,51 @@ impl GlueConsumerContext {
 
 impl ConsumerContext for GlueConsumerContext {}
 
-/// Return the list of partition ids associated with a specific topic
+/// Return the list of topics and partition ids associated with `topics`.
+///
+/// The topics of a `TOPIC PATTERN` are the existing topics that match
+/// `topic_pattern`, so new matching topics are discovered every time the
+/// metadata is fetched.
 fn get_kafka_partitions<C>(
     consumer: &BaseConsumer<C>,
-    topic: &str,
+    topics: &KafkaTopics,
+    topic_pattern: Option<&Regex>,
     timeout: Duration,
-) -> Result<Vec<i32>, anyhow::Error>
+) -> Result<Vec<(String, i32)>, anyhow::Error>
 where
     C: ConsumerContext,
 {
-    let metadata = consumer.fetch_metadata(Some(topic), timeout)?;
-    Ok(metadata.topics()[0]
-        .partitions()
-        .iter()
-        .map(|x| x.id())
-        .collect())
+    let topic_partitions = |metadata: &rdkafka::metadata::MetadataTopic| {
+        metadata
+            .partitions()
+            .iter()
+            .map(|x| (metadata.name().to_string(), x.id()))
+            .collect::<Vec<_>>()
+    };
+    match topics {
+        KafkaTopics::Topic(topic) => {
+            let metadata = consumer.fetch_metadata(Some(topic.as_str()), timeout)?;
+            Ok(topic_partitions(&metadata.topics()[0]))
+        }
+        KafkaTopics::List(topics) => {
+            let mut partitions = vec![];
+            for topic in topics {
+                let metadata = consumer.fetch_metadata(Some(topic.as_str()), timeout)?;
+                partitions.extend(topic_partitions(&metadata.topics()[0]));
+            }
+            Ok(partitions)
+        }
+        KafkaTopics::Pattern(_) => {
+            let topic_pattern = topic_pattern.expect("topic pattern known to be compiled");
+            let metadata = consumer.fetch_metadata(None, timeout)?;
+            Ok(metadata
+                .topics()
+                .iter()
+                .filter(|topic| topic.error().is_none() && topic_pattern.is_match(topic.name()))
+                .flat_map(topic_partitions)
+                .collect())
+        }
+    }
 }
 
 #[cfg(test)]
@@ -7,19 +7,19 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{HashMap, VecDeque};
 use std::time::Duration;
 use std::time::Instant;
 
 use anyhow::anyhow;
-use aws_sdk_kinesis::error::GetRecordsError;
+use aws_sdk_kinesis::error::{GetRecordsError, GetShardIteratorError};
 use aws_sdk_kinesis::output::GetRecordsOutput;
 use aws_sdk_kinesis::types::SdkError;
 use aws_sdk_kinesis::Client as KinesisClient;
 use prometheus::core::AtomicI64;
 use timely::scheduling::SyncActivator;
 use tokio::runtime::Handle as TokioHandle;
-use tracing::error;
+use tracing::{error, warn};
 
 use mz_cloud_resources::AwsExternalIdPrefix;
 use mz_expr::PartitionId;
@@ -46,36 +46,66 @@ use crate::source::{
 const KINESIS_SHARD_REFRESH_RATE: Duration = Duration::from_secs(60);
 
 /// Contains all information necessary to ingest data from Kinesis
+///
+/// Every shard of the stream is its own partition, whose offsets count the
+/// records read from that shard. Because Kinesis sequence numbers do not fit
+/// into an [`MzOffset`], the sequence number of the last record read from a
+/// shard is reported as the shard's resume token, which allows a restarted
+/// reader to continue each shard right after that record.
 pub struct KinesisSourceReader {
     tokio_handle: TokioHandle,
     /// Kinesis client used to obtain records
     kinesis_client: KinesisClient,
     /// The name of the stream
     stream_name: String,
-    /// The set of active shards
-    shard_set: HashMap<String, ShardMetrics>,
-    /// A queue representing the next shard to read from. This is necessary
+    /// The known shards of the stream, by shard id
+    shards: HashMap<String, ShardState>,
+    /// A queue of the shards that are ready to be read from. This is necessary
     /// to ensure that all shards are read from uniformly
-    shard_queue: VecDeque<(String, Option<String>)>,
+    shard_queue: VecDeque<String>,
     /// The time at which we last refreshed metadata
     /// TODO(natacha): this should be moved to timestamper
     last_checked_shards: Instant,
     /// Storage for messages that have not yet been timestamped
-    buffered_messages: VecDeque<SourceMessage<(), Option<Vec<u8>>, ()>>,
-    /// Count of processed message
-    processed_message_count: u64,
+    buffered_messages: VecDeque<SourceMessageType<(), Option<Vec<u8>>, ()>>,
+    /// The offsets and resume tokens of shards that were restored but have not
+    /// been discovered yet.
+    restored_shards: HashMap<String, (MzOffset, Option<String>)>,
     /// Metrics from which per-shard metrics get created.
     base_metrics: KinesisMetrics,
     // Kinesis sources support single-threaded ingestion only, so only one of
     // the `KinesisSourceReader`s will actually produce data.
     active_read_worker: bool,
-    // The non-active reader (see above `active_read_worker`) has to report back
-    // that is is not consuming from the one [`PartitionId:None`] partition.
-    // Before it can return a [`NextMessage::Finished`]. This is keeping track
-    // of that.
+    // The active reader has to report that it is not consuming from the
+    // partitions of restored shards that no longer exist, and from the
+    // [`PartitionId::None`] partition that sources created before per-shard
+    // partitions used. This is keeping track of that.
     reported_unconsumed_partitions: bool,
 }
 
+/// The progress of reading a single shard.
+struct ShardState {
+    /// The shards that this shard was split off of or merged from. A shard is
+    /// only read once its parents have been read to completion, so that records
+    /// with the same partition key are ingested in order across resharding.
+    parents: Vec<String>,
+    /// The shard iterator to continue reading from, if one has been obtained.
+    iterator: Option<String>,
+    /// The number of records read from this shard, which is also the offset of
+    /// the next record.
+    offset: MzOffset,
+    /// The sequence number of the last record read from this shard, if any.
+    last_sequence_number: Option<String>,
+    /// The number of records to skip when the shard has to be read from its
+    /// trim horizon because the sequence number to resume after is unknown.
+    records_to_skip: u64,
+    /// Whether the shard has been scheduled for reading.
+    scheduled: bool,
+    /// Whether the shard is closed and has been read to completion.
+    finished: bool,
+    metrics: ShardMetrics,
+}
+
 struct ShardMetrics {
     millis_behind_latest: DeleteOnDropGauge<'static, AtomicI64, Vec<String>>,
 }
@@ -92,30 +122,107 @@ impl ShardMetrics {
 
 impl KinesisSourceReader {
     async fn update_shard_information(&mut self) -> Result<(), anyhow::Error> {
-        let current_shards: HashSet<_> =
-            mz_kinesis_util::get_shard_ids(&self.kinesis_client, &self.stream_name)
-                .await?
+        let shards = mz_kinesis_util::list_shards(&self.kinesis_client, &self.stream_name).await?;
+        for shard in shards {
+            let shard_id = match shard.shard_id {
+                Some(shard_id) => shard_id,
+                None => continue,
+            };
+            if self.shards.contains_key(&shard_id) {
+                continue;
+            }
+            let (offset, last_sequence_number) = self
+                .restored_shards
+                .remove(&shard_id)
+                .unwrap_or((MzOffset::from(0), None));
+            // Without the sequence number of the last record that was read we
+            // can only re-read the shard from its trim horizon, which is exact
+            // only as long as none of its records have expired.
+            let records_to_skip = match &last_sequence_number {
+                None if offset.offset > 0 => {
+                    warn!(
+                        "kinesis shard {} of stream {} has no resume token for offset {}, \
+                         re-reading it from its trim horizon",
+                        shard_id, self.stream_name, offset.offset
+                    );
+                    offset.offset
+                }
+                _ => 0,
+            };
+            let parents = shard
+                .parent_shard_id
+                .into_iter()
+                .chain(shard.adjacent_parent_shard_id)
                 .collect();
-        let known_shards: HashSet<_> = self.shard_set.keys().cloned().collect();
-        let new_shards = current_shards
-            .difference(&known_shards)
-            .map(|shard_id| shard_id.to_owned());
-        for shard_id in new_shards {
-            self.shard_set.insert(
-                shard_id.to_string(),
-                ShardMetrics::new(&self.base_metrics, &self.stream_name, &shard_id),
+            let metrics = ShardMetrics::new(&self.base_metrics, &self.stream_name, &shard_id);
+            self.shards.insert(
+                shard_id,
+                ShardState {
+                    parents,
+                    iterator: None,
+                    offset,
+                    last_sequence_number,
+                    records_to_skip,
+                    scheduled: false,
+                    finished: false,
+                    metrics,
+                },
             );
-            self.shard_queue.push_back((
-                shard_id.to_string(),
+        }
+        self.schedule_ready_shards();
+        Ok(())
+    }
+
+    /// Schedules all shards whose parents have been read to completion. Parents
+    /// that are unknown have expired, so their records have been read or are
+    /// lost for good.
+    fn schedule_ready_shards(&mut self) {
+        let mut ready: Vec<_> = self
+            .shards
+            .iter()
+            .filter(|(_, shard)| !shard.scheduled && !shard.finished)
+            .filter(|(_, shard)| {
+                shard.parents.iter().all(|parent| {
+                    self.shards
+                        .get(parent)
+                        .map_or(true, |parent| parent.finished)
+                })
+            })
+            .map(|(shard_id, _)| shard_id.clone())
+            .collect();
+        ready.sort();
+        for shard_id in ready {
+            self.shards.get_mut(&shard_id).unwrap().scheduled = true;
+            self.shard_queue.push_back(shard_id);
+        }
+    }
+
+    /// Obtains an iterator for the given shard that continues after the last
+    /// record read from it.
+    async fn get_shard_iterator(
+        &self,
+        shard_id: &str,
+        last_sequence_number: Option<&str>,
+    ) -> Result<Option<String>, SdkError<GetShardIteratorError>> {
+        match last_sequence_number {
+            Some(sequence_number) => {
+                mz_kinesis_util::get_shard_iterator_after_sequence_number(
+                    &self.kinesis_client,
+                    &self.stream_name,
+                    shard_id,
+                    sequence_number,
+                )
+                .await
+            }
+            None => {
                 mz_kinesis_util::get_shard_iterator(
                     &self.kinesis_client,
                     &self.stream_name,
-                    &shard_id,
+                    shard_id,
                 )
-                .await?,
-            ));
+                .await
+            }
         }
-        Ok(())
     }
 
     /// Obtains the next record for this shard given a shard iterator
@@ -142,7 +249,8 @@ impl SourceConnectionBuilder for KinesisSourceConnection {
         worker_id: usize,
         worker_count: usize,
         _consumer_activator: SyncActivator,
-        _restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        mut resume_tokens: HashMap<PartitionId, String>,
         _encoding: SourceDataEncoding,
         metrics: crate::source::metrics::SourceBaseMetrics,
         connection_context: ConnectionContext,
@@ -150,36 +258,59 @@ impl SourceConnectionBuilder for KinesisSourceConnection {
         let active_read_worker =
             crate::source::responsible_for(&source_id, worker_id, worker_count, &PartitionId::None);
 
+        let mut restored_shards = HashMap::new();
+        for (pid, offset) in restored_offsets {
+            match (&pid, offset) {
+                (PartitionId::Kinesis(shard_id), Some(offset)) => {
+                    let resume_token = resume_tokens.remove(&pid);
+                    restored_shards.insert(shard_id.clone(), (offset, resume_token));
+                }
+                (PartitionId::None, Some(offset)) if offset.offset > 0 => {
+                    warn!(
+                        "kinesis source {} was created before tracking per-shard offsets, \
+                         re-reading all shards from their trim horizon",
+                        source_id
+                    );
+                }
+                _ => {}
+            }
+        }
+
         // TODO: This creates all the machinery, even for the non-active workers.
         // We could change that to only spin up Kinesis when needed.
         let state = TokioHandle::current().block_on(create_state(
-            &metrics.kinesis,
             self,
             connection_context.aws_external_id_prefix.as_ref(),
             source_id,
             &*connection_context.secrets_reader,
         ));
         match state {
-            Ok((kinesis_client, stream_name, shard_set, shard_queue)) => Ok((
-                KinesisSourceReader {
+            Ok((kinesis_client, stream_name)) => {
+                let mut reader = KinesisSourceReader {
                     tokio_handle: TokioHandle::current(),
                     kinesis_client,
-                    shard_queue,
+                    shards: HashMap::new(),
+                    shard_queue: VecDeque::new(),
                     last_checked_shards: Instant::now(),
                     buffered_messages: VecDeque::new(),
-                    shard_set,
+                    restored_shards,
                     stream_name,
-                    processed_message_count: 0,
                     base_metrics: metrics.kinesis,
                     active_read_worker,
                     reported_unconsumed_partitions: false,
-                },
-                LogCommitter {
-                    source_id,
-                    worker_id,
-                    worker_count,
-                },
-            )),
+                };
+                if active_read_worker {
+                    TokioHandle::current().block_on(reader.update_shard_information())?;
+                }
+                Ok((
+                    reader,
+                    LogCommitter {
+                        source_id,
+                        worker_id,
+                        worker_count,
+                    },
+                ))
+            }
             Err(e) => Err(anyhow!("{}", e)),
         }
     }
@@ -194,16 +325,23 @@ impl SourceReader for KinesisSourceReader {
         &mut self,
     ) -> Result<NextMessage<Self::Key, Self::Value, Self::Diff>, SourceReaderError> {
         if !self.active_read_worker {
-            if !self.reported_unconsumed_partitions {
-                self.reported_unconsumed_partitions = true;
-                return Ok(NextMessage::Ready(
-                    SourceMessageType::DropPartitionCapabilities(vec![PartitionId::None]),
-                ));
-            }
             return Ok(NextMessage::Finished);
         }
 
-        assert_eq!(self.shard_queue.len(), self.shard_set.len());
+        if !self.reported_unconsumed_partitions {
+            self.reported_unconsumed_partitions = true;
+            // Restored shards that were not discovered have expired, and will
+            // never produce more records.
+            let mut pids = vec![PartitionId::None];
+            pids.extend(
+                self.restored_shards
+                    .drain()
+                    .map(|(shard_id, _)| PartitionId::Kinesis(shard_id)),
+            );
+            return Ok(NextMessage::Ready(
+                SourceMessageType::DropPartitionCapabilities(pids),
+            ));
+        }
 
         //TODO move to timestamper
         if self.last_checked_shards.elapsed() >= KINESIS_SHARD_REFRESH_RATE {
@@ -219,116 +357,174 @@ impl SourceReader for KinesisSourceReader {
         }
 
         if let Some(message) = self.buffered_messages.pop_front() {
-            Ok(NextMessage::Ready(SourceMessageType::Finalized(message)))
-        } else {
-            // Rotate through all of a stream's shards, start with a new shard on each activation.
-            if let Some((shard_id, mut shard_iterator)) = self.shard_queue.pop_front() {
-                if let Some(iterator) = &shard_iterator {
-                    let get_records_output =
-                        match self.tokio_handle.block_on(self.get_records(iterator)) {
-                            Ok(output) => {
-                                shard_iterator = output.next_shard_iterator.clone();
-                                if let Some(millis) = output.millis_behind_latest {
-                                    self.shard_set
-                                        .get(&shard_id)
-                                        .unwrap()
-                                        .millis_behind_latest
-                                        .set(millis);
-                                }
-                                output
-                            }
-                            Err(SdkError::DispatchFailure(e)) => {
-                                // todo@jldlaughlin: Parse this to determine fatal/retriable?
-                                error!("{}", e);
-                                self.shard_queue.push_back((shard_id, shard_iterator));
-                                // Do not send error message as this would cause source to terminate
-                                return Ok(NextMessage::TransientDelay);
-                            }
-                            Err(SdkError::ServiceError { err, .. })
-                                if err.is_expired_iterator_exception() =>
-                            {
-                                // todo@jldlaughlin: Will need track source offsets to grab a new iterator.
-                                error!("{}", err);
-                                return Err(SourceReaderError {
-                                    inner: SourceErrorDetails::Other(err.to_string()),
-                                });
-                            }
-                            Err(SdkError::ServiceError { err, .. })
-                                if err.is_provisioned_throughput_exceeded_exception() =>
-                            {
-                                self.shard_queue.push_back((shard_id, shard_iterator));
-                                // Do not send error message as this would cause source to terminate
-                                return Ok(NextMessage::Pending);
-                            }
-                            Err(e) => {
-                                // Fatal service errors:
-                                //  - InvalidArgument
-                                //  - KMSAccessDenied, KMSDisabled, KMSInvalidState, KMSNotFound,
-                                //    KMSOptInRequired, KMSThrottling
-                                //  - ResourceNotFound
-                                //
-                                // Other fatal Rusoto errors:
-                                // - Credentials
-                                // - Validation
-                                // - ParseError
-                                // - Unknown (raw HTTP provided)
-                                // - Blocking
-                                error!("{}", e);
-                                return Err(SourceReaderError {
-                                    inner: SourceErrorDetails::Other(e.to_string()),
-                                });
-                            }
-                        };
-
-                    for record in get_records_output.records.unwrap_or_default() {
-                        let data = record
-                            .data
-                            .map(|blob| blob.into_inner())
-                            .unwrap_or_else(Vec::new);
-                        self.processed_message_count += 1;
-                        let source_message = SourceMessage {
-                            output: 0,
-                            partition: PartitionId::None,
-                            offset: MzOffset {
-                                //TODO: should MzOffset be modified to be a string?
-                                offset: self.processed_message_count,
-                            },
-                            upstream_time_millis: None,
-                            key: (),
-                            value: Some(data),
-                            headers: None,
-                            specific_diff: (),
-                        };
-                        self.buffered_messages.push_back(source_message);
+            return Ok(NextMessage::Ready(message));
+        }
+
+        // Rotate through all of a stream's shards, start with a new shard on each activation.
+        let shard_id = match self.shard_queue.pop_front() {
+            Some(shard_id) => shard_id,
+            None => return Ok(NextMessage::Pending),
+        };
+        let shard = &self.shards[&shard_id];
+
+        let iterator = match &shard.iterator {
+            Some(iterator) => iterator.clone(),
+            None => {
+                let last_sequence_number = shard.last_sequence_number.as_deref();
+                match self
+                    .tokio_handle
+                    .block_on(self.get_shard_iterator(&shard_id, last_sequence_number))
+                {
+                    Ok(Some(iterator)) => iterator,
+                    Ok(None) => {
+                        self.finish_shard(shard_id);
+                        return Ok(NextMessage::TransientDelay);
+                    }
+                    Err(SdkError::DispatchFailure(e)) => {
+                        error!("{}", e);
+                        self.shard_queue.push_back(shard_id);
+                        return Ok(NextMessage::TransientDelay);
+                    }
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(SourceReaderError {
+                            inner: SourceErrorDetails::Other(e.to_string()),
+                        });
                     }
-                    self.shard_queue.push_back((shard_id, shard_iterator));
                 }
             }
-            Ok(match self.buffered_messages.pop_front() {
-                Some(message) => NextMessage::Ready(SourceMessageType::Finalized(message)),
-                None => NextMessage::Pending,
-            })
+        };
+
+        let get_records_output = match self.tokio_handle.block_on(self.get_records(&iterator)) {
+            Ok(output) => {
+                if let Some(millis) = output.millis_behind_latest {
+                    self.shards[&shard_id]
+                        .metrics
+                        .millis_behind_latest
+                        .set(millis);
+                }
+                output
+            }
+            Err(SdkError::DispatchFailure(e)) => {
+                // todo@jldlaughlin: Parse this to determine fatal/retriable?
+                error!("{}", e);
+                self.shards.get_mut(&shard_id).unwrap().iterator = Some(iterator);
+                self.shard_queue.push_back(shard_id);
+                // Do not send error message as this would cause source to terminate
+                return Ok(NextMessage::TransientDelay);
+            }
+            Err(SdkError::ServiceError { err, .. }) if err.is_expired_iterator_exception() => {
+                // Obtain a new iterator that continues after the last record
+                // we read from this shard.
+                self.shards.get_mut(&shard_id).unwrap().iterator = None;
+                self.shard_queue.push_back(shard_id);
+                return Ok(NextMessage::TransientDelay);
+            }
+            Err(SdkError::ServiceError { err, .. })
+                if err.is_provisioned_throughput_exceeded_exception() =>
+            {
+                self.shards.get_mut(&shard_id).unwrap().iterator = Some(iterator);
+                self.shard_queue.push_back(shard_id);
+                // Do not send error message as this would cause source to terminate
+                return Ok(NextMessage::Pending);
+            }
+            Err(e) => {
+                // Fatal service errors:
+                //  - InvalidArgument
+                //  - KMSAccessDenied, KMSDisabled, KMSInvalidState, KMSNotFound,
+                //    KMSOptInRequired, KMSThrottling
+                //  - ResourceNotFound
+                //
+                // Other fatal Rusoto errors:
+                // - Credentials
+                // - Validation
+                // - ParseError
+                // - Unknown (raw HTTP provided)
+                // - Blocking
+                error!("{}", e);
+                return Err(SourceReaderError {
+                    inner: SourceErrorDetails::Other(e.to_string()),
+                });
+            }
+        };
+
+        let pid = PartitionId::Kinesis(shard_id.clone());
+        let shard = self.shards.get_mut(&shard_id).unwrap();
+        for record in get_records_output.records.unwrap_or_default() {
+            let sequence_number = record
+                .sequence_number
+                .ok_or_else(|| anyhow!("kinesis record without sequence number"))?;
+            if shard.records_to_skip > 0 {
+                shard.records_to_skip -= 1;
+                shard.last_sequence_number = Some(sequence_number);
+                continue;
+            }
+            let data = record
+                .data
+                .map(|blob| blob.into_inner())
+                .unwrap_or_else(Vec::new);
+            let offset = shard.offset;
+            shard.offset += MzOffset::from(1);
+            // The resume token must precede the message that advances the
+            // shard to its offset.
+            self.buffered_messages
+                .push_back(SourceMessageType::ResumeToken(
+                    pid.clone(),
+                    shard.offset,
+                    sequence_number.clone(),
+                ));
+            self.buffered_messages
+                .push_back(SourceMessageType::Finalized(SourceMessage {
+                    output: 0,
+                    partition: pid.clone(),
+                    offset,
+                    upstream_time_millis: None,
+                    key: (),
+                    value: Some(data),
+                    headers: None,
+                    specific_diff: (),
+                }));
+            shard.last_sequence_number = Some(sequence_number);
+        }
+
+        match get_records_output.next_shard_iterator {
+            Some(iterator) => {
+                shard.iterator = Some(iterator);
+                self.shard_queue.push_back(shard_id);
+            }
+            // The shard is closed and we have read all of its records.
+            None => self.finish_shard(shard_id),
         }
+
+        Ok(match self.buffered_messages.pop_front() {
+            Some(message) => NextMessage::Ready(message),
+            None => NextMessage::Pending,
+        })
+    }
+}
+
+impl KinesisSourceReader {
+    /// Marks a closed shard as read to completion, releasing its partition and
+    /// scheduling any of its children that are now ready to be read.
+    fn finish_shard(&mut self, shard_id: String) {
+        let shard = self.shards.get_mut(&shard_id).unwrap();
+        shard.finished = true;
+        shard.iterator = None;
+        self.buffered_messages
+            .push_back(SourceMessageType::DropPartitionCapabilities(vec![
+                PartitionId::Kinesis(shard_id),
+            ]));
+        self.schedule_ready_shards();
     }
 }
 
-/// Creates the necessary data-structures for shard management
-// todo: Better error handling here! Not all errors mean we're done/can't progress.
+/// Creates the Kinesis client for the stream of the given connection.
 async fn create_state(
-    base_metrics: &KinesisMetrics,
     c: KinesisSourceConnection,
     aws_external_id_prefix: Option<&AwsExternalIdPrefix>,
     source_id: GlobalId,
     secrets_reader: &dyn SecretsReader,
-) -> Result<
-    (
-        KinesisClient,
-        String,
-        HashMap<String, ShardMetrics>,
-        VecDeque<(String, Option<String>)>,
-    ),
-    anyhow::Error,
-> {
+) -> Result<(KinesisClient, String), anyhow::Error> {
     let config = c
         .aws
         .load(aws_external_id_prefix, Some(&source_id), secrets_reader)
@@ -336,24 +532,5 @@ async fn create_state(
 
     let kinesis_client = aws_sdk_kinesis::Client::new(&config);
 
-    let shard_set = mz_kinesis_util::get_shard_ids(&kinesis_client, &c.stream_name).await?;
-    let mut shard_queue: VecDeque<(String, Option<String>)> = VecDeque::new();
-    let mut shard_map = HashMap::new();
-    for shard_id in shard_set {
-        shard_queue.push_back((
-            shard_id.clone(),
-            mz_kinesis_util::get_shard_iterator(&kinesis_client, &c.stream_name, &shard_id).await?,
-        ));
-        shard_map.insert(
-            shard_id.clone(),
-            ShardMetrics::new(base_metrics, &c.stream_name, &shard_id),
-        );
-    }
-
-    Ok((
-        kinesis_client,
-        c.stream_name.clone(),
-        shard_map,
-        shard_queue,
-    ))
+    Ok((kinesis_client, c.stream_name.clone()))
 }
@@ -71,6 +71,12 @@ pub fn responsible_for(
     worker_count: usize,
     pid: &PartitionId,
 ) -> bool {
+    // Kinesis shards must be read in parent/child order across resharding events, so all
+    // shards of a stream are assigned to a single worker.
+    let pid = match pid {
+        PartitionId::Kinesis(_) => &PartitionId::None,
+        pid => pid,
+    };
     // Distribute partitions equally amongst workers.
     (usize::cast_from(pid.hashed()) % worker_count) == worker_id
 }
@@ -213,6 +213,7 @@ impl SourceConnectionBuilder for PostgresSourceConnection {
         worker_count: usize,
         consumer_activator: SyncActivator,
         start_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _resume_tokens: HashMap<PartitionId, String>,
         _encoding: SourceDataEncoding,
         metrics: SourceBaseMetrics,
         connection_context: ConnectionContext,
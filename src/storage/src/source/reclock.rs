@@ -52,29 +52,60 @@ pub struct ReclockFollower {
 /// A binding of None partition is encoded as a single datum containing the offset.
 ///
 /// A binding of a Kafka partition is encoded as the partition datum followed by the offset datum.
-fn pack_binding(pid: PartitionId, offset: MzOffset) -> SourceData {
-    let mut row = Row::with_capacity(2);
+///
+/// A binding of a topic-qualified Kafka partition is encoded as the topic datum, followed by the
+/// partition datum and the offset datum.
+///
+/// A binding of a Kinesis shard is encoded as the shard id datum followed by the offset datum and,
+/// if known, the resume token of the shard at that offset.
+fn pack_binding(pid: PartitionId, offset: MzOffset, resume_token: Option<&str>) -> SourceData {
+    let mut row = Row::with_capacity(3);
     let mut packer = row.packer();
-    match pid {
+    match &pid {
         PartitionId::None => {}
-        PartitionId::Kafka(pid) => packer.push(Datum::Int32(pid)),
+        PartitionId::Kafka(pid) => packer.push(Datum::Int32(*pid)),
+        PartitionId::Kinesis(shard_id) => packer.push(Datum::String(shard_id)),
+        PartitionId::KafkaTopic(topic, pid) => {
+            packer.push(Datum::String(topic));
+            packer.push(Datum::Int32(*pid));
+        }
     }
     packer.push(Datum::UInt64(offset.offset));
+    if let Some(resume_token) = resume_token {
+        packer.push(Datum::String(resume_token));
+    }
     SourceData(Ok(row))
 }
 
 /// Unpacks a binding from a Row
 /// See documentation of [pack_binding] for the encoded format
-fn unpack_binding(data: SourceData) -> (PartitionId, MzOffset) {
+fn unpack_binding(data: SourceData) -> (PartitionId, MzOffset, Option<String>) {
     let row = data.0.expect("invalid binding");
     let mut datums = row.iter();
-    let (pid, offset) = match (datums.next(), datums.next()) {
-        (Some(Datum::Int32(p)), Some(Datum::UInt64(offset))) => (PartitionId::Kafka(p), offset),
-        (Some(Datum::UInt64(offset)), None) => (PartitionId::None, offset),
+    let (pid, offset, resume_token) = match (datums.next(), datums.next(), datums.next()) {
+        (Some(Datum::Int32(p)), Some(Datum::UInt64(offset)), None) => {
+            (PartitionId::Kafka(p), offset, None)
+        }
+        (Some(Datum::String(topic)), Some(Datum::Int32(p)), Some(Datum::UInt64(offset))) => {
+            (PartitionId::KafkaTopic(topic.to_string(), p), offset, None)
+        }
+        (Some(Datum::String(shard_id)), Some(Datum::UInt64(offset)), resume_token) => {
+            let resume_token = match resume_token {
+                Some(Datum::String(token)) => Some(token.to_string()),
+                None => None,
+                _ => panic!("invalid binding"),
+            };
+            (
+                PartitionId::Kinesis(shard_id.to_string()),
+                offset,
+                resume_token,
+            )
+        }
+        (Some(Datum::UInt64(offset)), None, None) => (PartitionId::None, offset, None),
         _ => panic!("invalid binding"),
     };
 
-    (pid, MzOffset::from(offset))
+    (pid, MzOffset::from(offset), resume_token)
 }
 
 /// Drains the provided vector of updates containing insertions and retractions of full offset
@@ -389,6 +420,50 @@ impl ReclockFollowerInner {
     }
 }
 
+/// Reads the resume tokens that are recorded with the bindings of the remap shard as of `as_of`.
+///
+/// Each token is returned along with the offset it was recorded for, and can only be used to
+/// resume its partition at exactly that offset.
+pub async fn fetch_resume_tokens(
+    persist_clients: &Arc<Mutex<PersistClientCache>>,
+    metadata: &CollectionMetadata,
+    as_of: Antichain<Timestamp>,
+) -> anyhow::Result<HashMap<PartitionId, (MzOffset, String)>> {
+    let mut persist_clients = persist_clients.lock().await;
+    let persist_client = persist_clients
+        .open(metadata.persist_location.clone())
+        .await
+        .context("error creating persist client")?;
+    drop(persist_clients);
+
+    let mut read_handle = persist_client
+        .open_leased_reader::<SourceData, (), Timestamp, Diff>(metadata.remap_shard)
+        .await
+        .context("error opening persist shard")?;
+    let snapshot = read_handle.snapshot_and_fetch(as_of.clone()).await;
+    read_handle.expire().await;
+    let snapshot = snapshot.map_err(|since| {
+        anyhow::anyhow!("cannot read resume tokens as of {as_of:?}, since is {since:?}")
+    })?;
+
+    let mut bindings = snapshot
+        .into_iter()
+        .map(|((source_data, _), _, diff)| {
+            let source_data = source_data.expect("failed to decode binding");
+            (unpack_binding(source_data), diff)
+        })
+        .collect::<Vec<_>>();
+    consolidation::consolidate(&mut bindings);
+
+    let mut resume_tokens = HashMap::new();
+    for ((pid, offset, resume_token), diff) in bindings {
+        if let (Some(token), true) = (resume_token, diff > 0) {
+            resume_tokens.insert(pid, (offset, token));
+        }
+    }
+    Ok(resume_tokens)
+}
+
 /// The reclock operator reclocks a stream that is timestamped with some timestamp `SourceTime`
 /// into another time domain that is timestamped with some timestamp `DestTime`.
 ///
@@ -405,6 +480,13 @@ pub struct ReclockOperator {
     /// The upper frontier in terms of `SourceTime`. Any attempt to reclock messages beyond this
     /// frontier will lead to minting new bindings.
     source_upper: HashMap<PartitionId, MzOffset>,
+    /// The resume tokens recorded with the bindings in the remap shard, along with the offset
+    /// they were recorded for. A token is part of the current binding of its partition only if
+    /// its offset matches the one in `source_upper`.
+    resume_tokens: HashMap<PartitionId, (MzOffset, String)>,
+    /// Resume tokens reported by the source that will be recorded with the next binding minted
+    /// at the accompanying offset.
+    pending_resume_tokens: HashMap<PartitionId, (MzOffset, String)>,
 
     /// Write handle of the remap persist shard
     write_handle: WriteHandle<SourceData, (), Timestamp, Diff>,
@@ -489,6 +571,8 @@ impl ReclockOperator {
             since: since.clone(),
             upper: Antichain::from_elem(Timestamp::minimum()),
             source_upper: HashMap::new(),
+            resume_tokens: HashMap::new(),
+            pending_resume_tokens: HashMap::new(),
             write_handle,
             read_handle,
             listener,
@@ -586,7 +670,7 @@ impl ReclockOperator {
                 .expect("local since is not beyond read handle's since")
             {
                 let source_data = source_data.expect("failed to decode binding");
-                let binding = unpack_binding(source_data);
+                let binding = self.unpack_binding(source_data, diff);
                 pending_batch.push((binding, ts, diff));
             }
         }
@@ -611,7 +695,7 @@ impl ReclockOperator {
                     ListenEvent::Updates(updates) => {
                         for ((source_data, _), ts, diff) in updates {
                             let source_data = source_data.expect("failed to decode binding");
-                            let binding = unpack_binding(source_data);
+                            let binding = self.unpack_binding(source_data, diff);
                             pending_batch.push((binding, ts, diff));
                         }
                     }
@@ -691,8 +775,20 @@ impl ReclockOperator {
         loop {
             let upper = self.upper.clone();
             let new_upper = new_upper.clone();
-            let updates = integrate(&self.source_upper, updates)
-                .map(|((pid, offset), diff)| ((pack_binding(pid, offset), ()), next_ts, diff));
+            let updates = integrate(&self.source_upper, updates).map(|((pid, offset), diff)| {
+                // Retractions must exactly match the binding they retract, so they carry the
+                // token that was recorded with it.
+                let resume_tokens = match diff {
+                    -1 => &self.resume_tokens,
+                    _ => &self.pending_resume_tokens,
+                };
+                let resume_token = resume_tokens
+                    .get(&pid)
+                    .filter(|(token_offset, _)| *token_offset == offset)
+                    .map(|(_, token)| token.as_str());
+                let binding = pack_binding(pid, offset, resume_token);
+                ((binding, ()), next_ts, diff)
+            });
             match self
                 .write_handle
                 .compare_and_append(updates, upper, new_upper)
@@ -721,6 +817,33 @@ impl ReclockOperator {
         Ok(self.sync(&new_upper).await)
     }
 
+    /// Records a resume token that the source reported for resuming `pid` at `offset`. The token
+    /// is written together with the next binding that is minted for exactly that offset.
+    pub fn update_resume_token(&mut self, pid: PartitionId, offset: MzOffset, token: String) {
+        match self.pending_resume_tokens.get(&pid) {
+            Some((prev_offset, _)) if *prev_offset > offset => {}
+            _ => {
+                self.pending_resume_tokens.insert(pid, (offset, token));
+            }
+        }
+    }
+
+    /// Unpacks a binding read from the remap shard, remembering its resume token if it has one.
+    fn unpack_binding(&mut self, data: SourceData, diff: Diff) -> (PartitionId, MzOffset) {
+        let (pid, offset, resume_token) = unpack_binding(data);
+        if let Some(token) = resume_token {
+            if diff > 0 {
+                match self.resume_tokens.get(&pid) {
+                    Some((prev_offset, _)) if *prev_offset > offset => {}
+                    _ => {
+                        self.resume_tokens.insert(pid.clone(), (offset, token));
+                    }
+                }
+            }
+        }
+        (pid, offset)
+    }
+
     /// Produces a new timestamp suitable for minting bindings or the amount of time that the
     /// caller needs to wait for one to become available. On success, the returned timestamp is
     /// guaranteed to be beyond the current `upper` frontier and a multiple of `update_interval_ms`
@@ -871,6 +994,7 @@ mod tests {
     use mz_ore::now::{EpochMillis, SYSTEM_TIME};
 
     use mz_ore::metrics::MetricsRegistry;
+    use mz_persist_client::rpc::NoopPubSubClient;
     use mz_persist_client::{PersistConfig, PersistLocation, ShardId};
 
     // 15 minutes
@@ -885,6 +1009,7 @@ mod tests {
         Arc::new(Mutex::new(PersistClientCache::new(
             persistcfg,
             &MetricsRegistry::new(),
+            |_, _| Arc::new(NoopPubSubClient),
         )))
     }
 
@@ -940,6 +1065,7 @@ mod tests {
             remap_shard: shard,
             data_shard: ShardId::new(),
             status_shard: None,
+            arity: 0,
         };
 
         let (operator, initial_batch) = ReclockOperator::new(
@@ -1721,4 +1847,108 @@ mod tests {
             read_handle.since().to_owned()
         );
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_resume_tokens() {
+        let now_fn = TestNowFn::new();
+        let persist_cache = persist_cache(now_fn.now_fn());
+
+        let shard_a = PartitionId::Kinesis("shardId-000000000000".into());
+        let shard_b = PartitionId::Kinesis("shardId-000000000001".into());
+        let binding_shard = ShardId::new();
+        let metadata = CollectionMetadata {
+            persist_location: PersistLocation {
+                blob_uri: "mem://".to_owned(),
+                consensus_uri: "mem://".to_owned(),
+            },
+            remap_shard: binding_shard,
+            data_shard: ShardId::new(),
+            status_shard: None,
+            arity: 0,
+        };
+
+        let (mut operator, _follower) = make_test_operator(
+            binding_shard,
+            Antichain::from_elem(0.into()),
+            &persist_cache,
+            &now_fn,
+        )
+        .await;
+
+        // Tokens are recorded for the offsets they were reported for, and bindings minted at
+        // other offsets do not carry a token.
+        now_fn.advance(Duration::from_secs(1));
+        operator.update_resume_token(shard_a.clone(), MzOffset::from(2), "a2".into());
+        operator.update_resume_token(shard_b.clone(), MzOffset::from(1), "b1".into());
+        let source_upper = OffsetAntichain::from_iter([
+            (shard_a.clone(), MzOffset::from(2)),
+            (shard_b.clone(), MzOffset::from(3)),
+        ]);
+        let _ = operator.mint(&source_upper).await;
+
+        let tokens =
+            fetch_resume_tokens(&persist_cache, &metadata, Antichain::from_elem(1000.into()))
+                .await
+                .unwrap();
+        assert_eq!(
+            tokens,
+            HashMap::from([(shard_a.clone(), (MzOffset::from(2), "a2".to_string()))])
+        );
+
+        // Restart the operator, which has to recover the token of the current binding of the first
+        // shard to retract it, and advance the first shard.
+        let (mut operator, _follower) = make_test_operator(
+            binding_shard,
+            Antichain::from_elem(1000.into()),
+            &persist_cache,
+            &now_fn,
+        )
+        .await;
+        now_fn.advance(Duration::from_secs(1));
+        operator.update_resume_token(shard_a.clone(), MzOffset::from(5), "a5".into());
+        let source_upper = OffsetAntichain::from_iter([
+            (shard_a.clone(), MzOffset::from(5)),
+            (shard_b.clone(), MzOffset::from(3)),
+        ]);
+        let _ = operator.mint(&source_upper).await;
+
+        let tokens =
+            fetch_resume_tokens(&persist_cache, &metadata, Antichain::from_elem(2000.into()))
+                .await
+                .unwrap();
+        assert_eq!(
+            tokens,
+            HashMap::from([(shard_a.clone(), (MzOffset::from(5), "a5".to_string()))])
+        );
+
+        // Reading as of an earlier time returns the tokens of the bindings at that time.
+        let tokens =
+            fetch_resume_tokens(&persist_cache, &metadata, Antichain::from_elem(1000.into()))
+                .await
+                .unwrap();
+        assert_eq!(
+            tokens,
+            HashMap::from([(shard_a, (MzOffset::from(2), "a2".to_string()))])
+        );
+    }
+
+    #[test]
+    fn test_binding_roundtrip() {
+        let bindings = [
+            (PartitionId::None, None),
+            (PartitionId::Kafka(3), None),
+            (PartitionId::KafkaTopic("events.tenant_1".into(), 3), None),
+            (
+                PartitionId::Kinesis("shardId-000000000000".into()),
+                Some("token"),
+            ),
+        ];
+        for (pid, resume_token) in bindings {
+            let data = pack_binding(pid.clone(), MzOffset::from(42), resume_token);
+            assert_eq!(
+                unpack_binding(data),
+                (pid, MzOffset::from(42), resume_token.map(String::from))
+            );
+        }
+    }
 }
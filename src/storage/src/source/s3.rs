@@ -33,7 +33,7 @@ use std::default::Default;
 use std::ops::AddAssign;
 use std::sync::Arc;
 
-use async_compression::tokio::bufread::GzipDecoder;
+use async_compression::tokio::bufread::{BzDecoder, GzipDecoder, ZstdDecoder};
 use aws_sdk_s3::error::{GetObjectError, ListObjectsV2Error};
 use aws_sdk_s3::types::SdkError;
 use aws_sdk_s3::Client as S3Client;
@@ -715,8 +715,9 @@ async fn download_object(
         if let Some(s) = obj.content_encoding.as_deref() {
             match (s, compression) {
                 ("gzip", Compression::Gzip) => (),
+                ("zstd", Compression::Zstd) => (),
                 ("identity", Compression::None) => (),
-                ("identity" | "gzip", _) => {
+                ("identity" | "gzip" | "zstd", _) => {
                     debug!("object {} has mismatched Content-Encoding: {}", key, s)
                 }
                 _ => debug!("object {} has unrecognized Content-Encoding: {}", key, s),
@@ -747,6 +748,14 @@ async fn download_object(
             let decoder = GzipDecoder::new(reader);
             read_object_chunked(source_id, decoder, tx).await
         }
+        Compression::Zstd => {
+            let decoder = ZstdDecoder::new(reader);
+            read_object_chunked(source_id, decoder, tx).await
+        }
+        Compression::Bzip2 => {
+            let decoder = BzDecoder::new(reader);
+            read_object_chunked(source_id, decoder, tx).await
+        }
     };
 
     debug!(
@@ -822,6 +831,7 @@ impl SourceConnectionBuilder for S3SourceConnection {
         worker_count: usize,
         consumer_activator: SyncActivator,
         _restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _resume_tokens: HashMap<PartitionId, String>,
         _encoding: SourceDataEncoding,
         metrics: crate::source::metrics::SourceBaseMetrics,
         connection_context: ConnectionContext,
@@ -134,6 +134,8 @@ struct SourceMessageBatch<Key, Value, Diff> {
 #[derive(Clone, Serialize, Deserialize)]
 struct BatchUpperSummary {
     batch_upper: OffsetAntichain,
+    /// See `SourceReaderOperatorOutput`.
+    resume_tokens: HashMap<PartitionId, (MzOffset, String)>,
 }
 
 /// Creates a source dataflow operator graph from a connection that has a
@@ -249,6 +251,9 @@ struct SourceReaderOperatorOutput<S: SourceReader> {
     source_upper: OffsetAntichain,
     /// See `SourceMessageBatch`.
     batch_upper: OffsetAntichain,
+    /// The resume tokens reported by the source reader for the offsets in
+    /// `batch_upper`, along with those offsets.
+    resume_tokens: HashMap<PartitionId, (MzOffset, String)>,
 }
 
 fn build_source_reader_stream<S>(
@@ -299,6 +304,11 @@ where
         // by iterating over all messages in a batch when we emit it.
         let mut batch_upper = OffsetAntichain::new();
 
+        // Resume tokens for the offsets in `batch_upper`, and those reported
+        // for offsets that `batch_upper` has yet to reach.
+        let mut resume_tokens = HashMap::new();
+        let mut pending_resume_tokens = HashMap::<_, (MzOffset, String)>::new();
+
         // Send along an empty batch, so that the reclock operator knows
         // about the current frontier. Otherwise, if there are no new
         // messages after a restart, the reclock operator would be stuck and
@@ -309,6 +319,7 @@ where
             unconsumed_partitions: Vec::new(),
             source_upper: initial_source_upper,
             batch_upper: batch_upper.clone(),
+            resume_tokens: resume_tokens.clone(),
         });
 
         let source_stream = source_reader.into_stream(timestamp_interval).fuse();
@@ -376,6 +387,11 @@ where
                                     if let Some(prev_offset) = prev_offset {
                                         assert!(offset_frontier >= prev_offset, "offset regressed");
                                     }
+                                    if let Entry::Occupied(entry) = pending_resume_tokens.entry(pid.clone()) {
+                                        if entry.get().0 == offset_frontier {
+                                            resume_tokens.insert(pid.clone(), entry.remove());
+                                        }
+                                    }
                                     untimestamped_messages.entry(pid).or_default().push((message, offset));
                                 }
                                 SourceMessageType::SourceStatus(update) => {
@@ -383,6 +399,16 @@ where
                                         healthchecker.update_status(update).await;
                                     }
                                 }
+                                SourceMessageType::ResumeToken(pid, offset, token) => {
+                                    // Tokens are only forwarded once `batch_upper` reaches their
+                                    // offset, so that the remap operator sees them together
+                                    // with the frontier it mints bindings for.
+                                    if batch_upper.get(&pid) == Some(&offset) {
+                                        resume_tokens.insert(pid, (offset, token));
+                                    } else {
+                                        pending_resume_tokens.insert(pid, (offset, token));
+                                    }
+                                }
                             }
                         }
                         Some(Err(e)) => {
@@ -402,6 +428,7 @@ where
                                     unconsumed_partitions,
                                     source_upper: source_upper.clone(),
                                     batch_upper: batch_upper.clone(),
+                                    resume_tokens: resume_tokens.clone(),
                                 }
                             );
 
@@ -438,6 +465,7 @@ where
                             unconsumed_partitions: unconsumed_partitions.clone(),
                             source_upper: source_upper.clone(),
                             batch_upper: batch_upper.clone(),
+                            resume_tokens: resume_tokens.clone(),
                         }
                     );
                 }
@@ -497,11 +525,11 @@ where
         worker_count,
         timestamp_interval: _,
         encoding,
-        storage_metadata: _,
+        storage_metadata,
         resume_upper,
         base_metrics,
         now: now_fn,
-        persist_clients: _,
+        persist_clients,
     } = config;
 
     let (stream, capability) = async_source(
@@ -543,6 +571,31 @@ where
                 });
                 trace!("source_reader({id}) {worker_id}/{worker_count}: source_upper after thinning: {source_upper:?}");
 
+                // Only Kinesis sources report resume tokens, so we only read them back from the
+                // remap shard when restoring the offsets of Kinesis shards.
+                let mut resume_tokens = HashMap::new();
+                if source_upper
+                    .partitions()
+                    .any(|pid| matches!(pid, PartitionId::Kinesis(_)))
+                {
+                    // The offsets at the resume upper are those of the bindings as of the
+                    // timestamp right before it.
+                    let upper_ts = resume_upper.as_option().copied().unwrap();
+                    let as_of = Antichain::from_elem(upper_ts.saturating_sub(1));
+                    let tokens = crate::source::reclock::fetch_resume_tokens(
+                        &persist_clients,
+                        &storage_metadata,
+                        as_of,
+                    )
+                    .await
+                    .expect("Failed to read resume tokens");
+                    for (pid, (offset, token)) in tokens {
+                        if source_upper.get(&pid) == Some(&offset) {
+                            resume_tokens.insert(pid, token);
+                        }
+                    }
+                }
+
                 let (source_reader, offset_committer) = source_connection
                     .clone()
                     .into_reader(
@@ -552,6 +605,7 @@ where
                         worker_count,
                         sync_activator,
                         source_upper.as_vec(),
+                        resume_tokens,
                         encoding,
                         base_metrics,
                         connection_context.clone(),
@@ -667,6 +721,7 @@ where
                         unconsumed_partitions,
                         source_upper,
                         batch_upper,
+                        resume_tokens,
                     } = match update {
                         Some(update) => update,
                         None => {
@@ -692,6 +747,7 @@ where
                     // the remap operator.
                     let batch_upper_summary = BatchUpperSummary {
                         batch_upper: batch_upper.clone(),
+                        resume_tokens,
                     };
 
                     // Pull the upper to `max` for partitions that we are not
@@ -1113,6 +1169,9 @@ where
                             for (pid, offset) in batch_upper_summary.batch_upper.iter() {
                                 global_source_upper.maybe_insert(pid.clone(), *offset);
                             }
+                            for (pid, (offset, token)) in batch_upper_summary.resume_tokens {
+                                timestamper.update_resume_token(pid, offset, token);
+                            }
                         }
                     },
                     Event::Progress(frontier) => {
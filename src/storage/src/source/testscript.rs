@@ -7,6 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::HashMap;
 use std::time::Duration;
 
 use timely::scheduling::SyncActivator;
@@ -60,6 +61,7 @@ impl SourceConnectionBuilder for TestScriptSourceConnection {
         worker_count: usize,
         _consumer_activator: SyncActivator,
         _restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _resume_tokens: HashMap<PartitionId, String>,
         _encoding: SourceDataEncoding,
         _metrics: crate::source::metrics::SourceBaseMetrics,
         _connection_context: ConnectionContext,
@@ -47,6 +47,9 @@ pub trait SourceConnectionBuilder {
     /// Turn this connection into a new source reader.
     ///
     /// This function returns the source reader and its corresponding offset committed.
+    ///
+    /// `resume_tokens` contains the tokens previously reported via
+    /// [`SourceMessageType::ResumeToken`] for the offsets in `restored_offsets`, if any.
     fn into_reader(
         self,
         source_name: String,
@@ -55,6 +58,7 @@ pub trait SourceConnectionBuilder {
         worker_count: usize,
         consumer_activator: SyncActivator,
         restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        resume_tokens: HashMap<PartitionId, String>,
         encoding: SourceDataEncoding,
         metrics: crate::source::metrics::SourceBaseMetrics,
         connection_context: ConnectionContext,
@@ -195,6 +199,16 @@ pub enum SourceMessageType<Key, Value, Diff> {
     /// We need these to compute a "global" source upper, when determining
     /// completeness of a timestamp.
     DropPartitionCapabilities(Vec<PartitionId>),
+    /// Communicates an opaque token that allows a future [`SourceReader`] to
+    /// resume reading the given partition at exactly the given offset, for
+    /// sources whose upstream positions cannot be represented as an
+    /// [`MzOffset`]. The token is durably recorded together with the timestamp
+    /// binding for that offset and handed back to
+    /// [`SourceConnectionBuilder::into_reader`] on restart.
+    ///
+    /// Tokens must be emitted _before_ the messages that advance the partition
+    /// to the given offset.
+    ResumeToken(PartitionId, MzOffset, String),
 }
 
 /// Source-agnostic wrapper for messages. Each source must implement a
@@ -255,6 +255,20 @@ impl<'w, A: Allocate> Worker<'w, A> {
             StorageCommand::InitializationComplete => (),
             StorageCommand::CreateSources(ingestions) => {
                 for ingestion in ingestions {
+                    // Ingestions are altered by sending a new description for
+                    // an existing ID. Dataflows cannot be modified in place,
+                    // so we restart and rebuild all of them from the
+                    // controller's updated commands.
+                    if let Some(existing) = self.storage_state.ingestions.get(&ingestion.id) {
+                        if *existing != ingestion.description {
+                            halt!(
+                                "ingestion {} was altered; restarting to apply the new description",
+                                ingestion.id
+                            );
+                        }
+                        continue;
+                    }
+
                     // Remember the ingestion description to facilitate possible
                     // reconciliation later.
                     self.storage_state
@@ -20,6 +20,7 @@ use mz_build_info::DUMMY_BUILD_INFO;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::SYSTEM_TIME;
 use mz_ore::task::RuntimeExt;
+use mz_persist_client::rpc::NoopPubSubClient;
 use mz_repr::TimestampManipulation;
 use mz_repr::{Diff, GlobalId, Timestamp};
 use mz_storage::sink::SinkBaseMetrics;
@@ -132,8 +133,11 @@ where
                 blob_uri: "mem://".to_string(),
                 consensus_uri: "mem://".to_string(),
             };
-            let mut persist_cache =
-                mz_persist_client::cache::PersistClientCache::new(persistcfg, &metrics_registry);
+            let mut persist_cache = mz_persist_client::cache::PersistClientCache::new(
+                persistcfg,
+                &metrics_registry,
+                |_, _| Arc::new(NoopPubSubClient),
+            );
 
             // create a client for use with the `until` closure later.
             let persist_client = tokio_runtime
@@ -177,6 +181,7 @@ where
                 remap_shard: mz_persist_client::ShardId::new(),
                 data_shard: mz_persist_client::ShardId::new(),
                 status_shard: None,
+                arity: 0,
             };
             let data_shard = collection_metadata.data_shard.clone();
             let id = GlobalId::User(1);
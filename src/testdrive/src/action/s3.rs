@@ -12,6 +12,13 @@ use std::io::Write;
 use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, bail, Context};
+use arrow2::array::{Array, BinaryArray, BooleanArray, PrimitiveArray, Utf8Array};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema};
+use arrow2::io::parquet::write::{
+    CompressionOptions, Encoding, FileWriter, RowGroupIterator, Version, WriteOptions,
+};
+use async_compression::tokio::write::{BzEncoder, ZstdEncoder};
 use aws_sdk_s3::error::{CreateBucketError, CreateBucketErrorKind};
 use aws_sdk_s3::model::{
     BucketLocationConstraint, CreateBucketConfiguration, Delete, NotificationConfiguration,
@@ -21,9 +28,12 @@ use aws_sdk_s3::types::{ByteStream, SdkError};
 use aws_sdk_sqs::model::{DeleteMessageBatchRequestEntry, QueueAttributeName};
 use flate2::write::GzEncoder;
 use flate2::Compression as Flate2Compression;
+use serde_json::Value as JsonValue;
+use tokio::io::AsyncWriteExt;
 
 use crate::action::file::{build_compression, Compression};
 use crate::action::{ControlFlow, State};
+use crate::format::avro::{self, SchemaPiece, SchemaPieceOrNamed};
 use crate::format::bytes;
 use crate::parser::BuiltinCommand;
 
@@ -75,14 +85,30 @@ pub async fn run_put_object(
     let key = cmd.args.string("key")?;
     let compression = build_compression(&mut cmd)?;
     let trailing_newline = cmd.args.opt_bool("trailing-newline")?.unwrap_or(true);
-    let mut contents = vec![];
-    for line in cmd.input {
-        contents.extend(bytes::unescape(line.as_bytes())?);
-        contents.push(b'\n');
-    }
-    if !trailing_newline {
-        contents.pop();
-    }
+    let format = cmd.args.opt_string("format");
+    let schema = cmd.args.opt_string("schema");
+    let contents = match format.as_deref() {
+        None => {
+            let mut contents = vec![];
+            for line in cmd.input {
+                contents.extend(bytes::unescape(line.as_bytes())?);
+                contents.push(b'\n');
+            }
+            if !trailing_newline {
+                contents.pop();
+            }
+            contents
+        }
+        Some("avro-ocf") => {
+            let schema = schema.ok_or_else(|| anyhow!("format=avro-ocf requires a schema"))?;
+            encode_avro_ocf(&schema, &cmd.input)?
+        }
+        Some("parquet") => {
+            let schema = schema.ok_or_else(|| anyhow!("format=parquet requires a schema"))?;
+            encode_parquet(&schema, &cmd.input)?
+        }
+        Some(f) => bail!("unknown object format: {}", f),
+    };
     cmd.args.done()?;
 
     let bucket = format!("{}-{}", bucket_prefix, state.seed);
@@ -98,6 +124,30 @@ pub async fn run_put_object(
                 .context("writing to gzip encoder")?;
             encoder.finish().context("writing to gzip encoder")
         }
+        Compression::Zstd => {
+            let mut encoder = ZstdEncoder::new(Vec::new());
+            encoder
+                .write_all(buffer.as_ref())
+                .await
+                .context("writing to zstd encoder")?;
+            encoder
+                .shutdown()
+                .await
+                .context("writing to zstd encoder")?;
+            Ok(encoder.into_inner())
+        }
+        Compression::Bzip2 => {
+            let mut encoder = BzEncoder::new(Vec::new());
+            encoder
+                .write_all(buffer.as_ref())
+                .await
+                .context("writing to bzip2 encoder")?;
+            encoder
+                .shutdown()
+                .await
+                .context("writing to bzip2 encoder")?;
+            Ok(encoder.into_inner())
+        }
     }?;
 
     state
@@ -109,6 +159,8 @@ pub async fn run_put_object(
         .set_content_encoding(match compression {
             Compression::None => None,
             Compression::Gzip => Some("gzip".to_string()),
+            Compression::Zstd => Some("zstd".to_string()),
+            Compression::Bzip2 => None,
         })
         .key(&key)
         .send()
@@ -119,6 +171,140 @@ pub async fn run_put_object(
     Ok(ControlFlow::Continue)
 }
 
+/// Encodes each line of `input`, a JSON value, as a record in an Avro object
+/// container file with the given schema.
+fn encode_avro_ocf(schema: &str, input: &[String]) -> Result<Vec<u8>, anyhow::Error> {
+    let schema = avro::parse_schema(schema).context("parsing avro schema")?;
+    let mut writer = avro::Writer::new(schema.clone(), Vec::new());
+    for line in input {
+        let json = serde_json::from_str(line).context("parsing json")?;
+        let value = avro::from_json(&json, schema.top_node())?;
+        writer.append(value)?;
+    }
+    writer.flush()?;
+    Ok(writer.into_inner())
+}
+
+/// Encodes each line of `input`, a JSON object, as a row in a Parquet object
+/// whose columns are described by `schema`, an Avro record schema.
+///
+/// Only records of primitive types, optionally in a union with `null`, are
+/// supported.
+fn encode_parquet(schema: &str, input: &[String]) -> Result<Vec<u8>, anyhow::Error> {
+    let schema = avro::parse_schema(schema).context("parsing avro schema")?;
+    let fields = match schema.top_node().inner {
+        SchemaPiece::Record { fields, .. } => fields,
+        _ => bail!("parquet schema must be a record"),
+    };
+    let rows = input
+        .iter()
+        .map(|line| serde_json::from_str::<JsonValue>(line).context("parsing json"))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut arrow_fields = vec![];
+    let mut arrays: Vec<Box<dyn Array>> = vec![];
+    for field in fields {
+        let (piece, nullable) = match &field.schema {
+            SchemaPieceOrNamed::Piece(SchemaPiece::Union(union)) => match union.variants() {
+                [SchemaPieceOrNamed::Piece(SchemaPiece::Null), SchemaPieceOrNamed::Piece(p)]
+                | [SchemaPieceOrNamed::Piece(p), SchemaPieceOrNamed::Piece(SchemaPiece::Null)] => {
+                    (p, true)
+                }
+                _ => bail!("unsupported union type for field {}", field.name),
+            },
+            SchemaPieceOrNamed::Piece(p) => (p, false),
+            SchemaPieceOrNamed::Named(_) => bail!("unsupported type for field {}", field.name),
+        };
+        let values = rows
+            .iter()
+            .map(|row| row.get(&field.name).unwrap_or(&JsonValue::Null))
+            .collect::<Vec<_>>();
+        if !nullable && values.iter().any(|v| v.is_null()) {
+            bail!("missing value for non-nullable field {}", field.name);
+        }
+        let (data_type, array): (DataType, Box<dyn Array>) = match piece {
+            SchemaPiece::Boolean => (
+                DataType::Boolean,
+                Box::new(BooleanArray::from(
+                    values.iter().map(|v| v.as_bool()).collect::<Vec<_>>(),
+                )),
+            ),
+            SchemaPiece::Int => (
+                DataType::Int32,
+                Box::new(PrimitiveArray::<i32>::from(
+                    values
+                        .iter()
+                        .map(|v| v.as_i64().map(i32::try_from).transpose())
+                        .collect::<Result<Vec<_>, _>>()?,
+                )),
+            ),
+            SchemaPiece::Long => (
+                DataType::Int64,
+                Box::new(PrimitiveArray::<i64>::from(
+                    values.iter().map(|v| v.as_i64()).collect::<Vec<_>>(),
+                )),
+            ),
+            SchemaPiece::Float => (
+                DataType::Float32,
+                Box::new(PrimitiveArray::<f32>::from(
+                    values
+                        .iter()
+                        .map(|v| v.as_f64().map(|f| f as f32))
+                        .collect::<Vec<_>>(),
+                )),
+            ),
+            SchemaPiece::Double => (
+                DataType::Float64,
+                Box::new(PrimitiveArray::<f64>::from(
+                    values.iter().map(|v| v.as_f64()).collect::<Vec<_>>(),
+                )),
+            ),
+            SchemaPiece::String => (
+                DataType::Utf8,
+                Box::new(Utf8Array::<i32>::from(
+                    values.iter().map(|v| v.as_str()).collect::<Vec<_>>(),
+                )),
+            ),
+            SchemaPiece::Bytes => (
+                DataType::Binary,
+                Box::new(BinaryArray::<i32>::from(
+                    values
+                        .iter()
+                        .map(|v| v.as_str().map(|s| s.as_bytes()))
+                        .collect::<Vec<_>>(),
+                )),
+            ),
+            _ => bail!("unsupported type for field {}", field.name),
+        };
+        if array.null_count() != values.iter().filter(|v| v.is_null()).count() {
+            bail!("mistyped value for field {}", field.name);
+        }
+        arrow_fields.push(Field::new(&field.name, data_type, nullable));
+        arrays.push(array);
+    }
+
+    let schema = Schema::from(arrow_fields);
+    let options = WriteOptions {
+        write_statistics: true,
+        compression: CompressionOptions::Uncompressed,
+        version: Version::V2,
+    };
+    let encodings = schema
+        .fields
+        .iter()
+        .map(|_| vec![Encoding::Plain])
+        .collect();
+    let chunks = vec![Ok(Chunk::new(arrays))];
+    let row_groups = RowGroupIterator::try_new(chunks.into_iter(), &schema, options, encodings)?;
+    let mut buf = vec![];
+    let mut writer = FileWriter::try_new(&mut buf, schema, options)?;
+    for group in row_groups {
+        writer.write(group?)?;
+    }
+    writer.end(None)?;
+    Ok(buf)
+}
+
 pub async fn run_delete_object(
     mut cmd: BuiltinCommand,
     state: &mut State,
@@ -52,7 +52,11 @@ pub async fn run_sql(mut cmd: SqlCommand, state: &mut State) -> Result<ControlFl
         // Do not retry FETCH statements as subsequent executions are likely
         // to return an empty result. The original result would thus be lost.
         Fetch(_) => false,
-        Explain(stmt) if stmt.stage != ExplainStage::Timestamp => false,
+        // The output of `EXPLAIN TIMESTAMP` and `EXPLAIN ANALYZE` depends on
+        // the progress of running dataflows.
+        Explain(stmt) if !matches!(stmt.stage, ExplainStage::Timestamp | ExplainStage::Analyze) => {
+            false
+        }
         // DDL statements should always provide the expected result on the first try
         CreateConnection(_)
         | CreateCluster(_)
@@ -68,6 +72,7 @@ pub async fn run_sql(mut cmd: SqlCommand, state: &mut State) -> Result<ControlFl
         | CreateType(_)
         | CreateRole(_)
         | AlterObjectRename(_)
+        | AlterObjectSwap(_)
         | AlterIndex(_)
         | Discard(_)
         | DropDatabase(_)
@@ -21,13 +21,13 @@ use mz_expr::visit::Visit;
 use mz_expr::{CollectionPlan, Id, LocalId, MapFilterProject, MirRelationExpr, MirScalarExpr};
 use mz_ore::id_gen::IdGen;
 
-use crate::{monotonic::MonotonicFlag, IndexOracle, Optimizer, TransformError};
+use crate::{monotonic::MonotonicFlag, IndexOracle, Optimizer, StatisticsOracle, TransformError};
 
 /// Optimizes the implementation of each dataflow.
 ///
 /// Inlines views, performs a full optimization pass including physical
-/// planning using the supplied indexes, propagates filtering and projection
-/// information to dataflow sources and lifts monotonicity information.
+/// planning using the supplied indexes and statistics, propagates filtering and
+/// projection information to dataflow sources and lifts monotonicity information.
 #[tracing::instrument(
     target = "optimizer",
     level = "debug",
@@ -37,12 +37,13 @@ use crate::{monotonic::MonotonicFlag, IndexOracle, Optimizer, TransformError};
 pub fn optimize_dataflow(
     dataflow: &mut DataflowDesc,
     indexes: &dyn IndexOracle,
+    stats: &dyn StatisticsOracle,
 ) -> Result<(), TransformError> {
     // Inline views that are used in only one other view.
     inline_views(dataflow)?;
 
     // Logical optimization pass after view inlining
-    optimize_dataflow_relations(dataflow, indexes, &Optimizer::logical_optimizer())?;
+    optimize_dataflow_relations(dataflow, indexes, stats, &Optimizer::logical_optimizer())?;
 
     optimize_dataflow_filters(dataflow)?;
     // TODO: when the linear operator contract ensures that propagated
@@ -55,10 +56,10 @@ pub fn optimize_dataflow(
 
     // A smaller logical optimization pass after projections and filters are
     // pushed down across views.
-    optimize_dataflow_relations(dataflow, indexes, &Optimizer::logical_cleanup_pass())?;
+    optimize_dataflow_relations(dataflow, indexes, stats, &Optimizer::logical_cleanup_pass())?;
 
     // Physical optimization pass
-    optimize_dataflow_relations(dataflow, indexes, &Optimizer::physical_optimizer())?;
+    optimize_dataflow_relations(dataflow, indexes, stats, &Optimizer::physical_optimizer())?;
 
     optimize_dataflow_monotonic(dataflow)?;
 
@@ -180,6 +181,7 @@ fn inline_views(dataflow: &mut DataflowDesc) -> Result<(), TransformError> {
 fn optimize_dataflow_relations(
     dataflow: &mut DataflowDesc,
     indexes: &dyn IndexOracle,
+    stats: &dyn StatisticsOracle,
     optimizer: &Optimizer,
 ) -> Result<(), TransformError> {
     // Re-optimize each dataflow
@@ -190,7 +192,7 @@ fn optimize_dataflow_relations(
         // Re-name bindings to accommodate other analyses, specifically
         // `InlineLet` which probably wants a reworking in any case.
         // Re-run all optimizations on the composite views.
-        optimizer.transform(object.plan.as_inner_mut(), indexes)?;
+        optimizer.transform(object.plan.as_inner_mut(), indexes, stats)?;
     }
 
     mz_repr::explain_new::trace_plan(dataflow);
@@ -37,6 +37,7 @@
 //! Filter.transform(&mut expr, TransformArgs {
 //!   id_gen: &mut Default::default(),
 //!   indexes: &mz_transform::EmptyIndexOracle,
+//!   stats: &mz_transform::EmptyStatisticsOracle,
 //! });
 //!
 //! let correct = input.filter(vec![predicate0]);
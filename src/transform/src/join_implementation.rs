@@ -15,6 +15,9 @@
 //! This includes determining the type of join (e.g. differential linear, or delta queries),
 //! determining the orders of collections, lifting predicates if useful arrangements exist,
 //! and identifying opportunities to use indexes to replace filters.
+//!
+//! When statistics are available for all inputs of a join, they inform both the join orders
+//! and the choice between a differential and a delta join; see the `estimates` module.
 
 use std::collections::HashMap;
 
@@ -26,6 +29,7 @@ use mz_expr::{
 };
 use mz_ore::stack::{CheckedRecursion, RecursionGuard};
 
+use self::estimates::{Estimate, StatisticsMap};
 use self::index_map::IndexMap;
 use crate::predicate_pushdown::PredicatePushdown;
 use crate::{TransformArgs, TransformError};
@@ -64,7 +68,11 @@ impl crate::Transform for JoinImplementation {
         relation: &mut MirRelationExpr,
         args: TransformArgs,
     ) -> Result<(), TransformError> {
-        let result = self.action_recursive(relation, &mut IndexMap::new(args.indexes));
+        let result = self.action_recursive(
+            relation,
+            &mut IndexMap::new(args.indexes),
+            &mut StatisticsMap::new(args.stats),
+        );
         mz_repr::explain_new::trace_plan(&*relation);
         result
     }
@@ -73,15 +81,17 @@ impl crate::Transform for JoinImplementation {
 impl JoinImplementation {
     /// Pre-order visitor for each `MirRelationExpr` to find join operators.
     ///
-    /// This method accumulates state about let-bound arrangements, so that
-    /// join operators can more accurately assess their available arrangements.
+    /// This method accumulates state about let-bound arrangements and their
+    /// estimated contents, so that join operators can more accurately assess
+    /// their available arrangements.
     pub fn action_recursive(
         &self,
         relation: &mut MirRelationExpr,
         indexes: &mut IndexMap,
+        stats: &mut StatisticsMap,
     ) -> Result<(), TransformError> {
         if let MirRelationExpr::Let { id, value, body } = relation {
-            self.action_recursive(value, indexes)?;
+            self.action_recursive(value, indexes, stats)?;
             match &**value {
                 MirRelationExpr::ArrangeBy { keys, .. } => {
                     for key in keys {
@@ -96,13 +106,17 @@ impl JoinImplementation {
                 }
                 _ => {}
             }
-            self.action_recursive(body, indexes)?;
+            if let Some(estimate) = stats.estimate(value) {
+                stats.add_local(*id, estimate);
+            }
+            self.action_recursive(body, indexes, stats)?;
             indexes.remove_local(*id);
+            stats.remove_local(*id);
             Ok(())
         } else {
             let (mfp, mfp_input) = MapFilterProject::extract_non_errors_from_expr_ref_mut(relation);
-            mfp_input.try_visit_mut_children(|e| self.action_recursive(e, indexes))?;
-            self.action(mfp_input, mfp, indexes)?;
+            mfp_input.try_visit_mut_children(|e| self.action_recursive(e, indexes, stats))?;
+            self.action(mfp_input, mfp, indexes, stats)?;
             Ok(())
         }
     }
@@ -113,6 +127,7 @@ impl JoinImplementation {
         relation: &mut MirRelationExpr,
         mfp_above: MapFilterProject,
         indexes: &IndexMap,
+        stats: &StatisticsMap,
     ) -> Result<(), TransformError> {
         if let MirRelationExpr::Join {
             inputs,
@@ -285,6 +300,14 @@ impl JoinImplementation {
                     });
                 }
 
+                // Estimate the contents of each input, accounting for the same filters as the
+                // filter characteristics above. We only use estimates if we have them for all
+                // inputs, as otherwise we cannot compare the costs of different orders.
+                let estimates = (0..inputs.len())
+                    .map(|index| stats.estimate_join_input(&inputs[index], &push_downs[index]))
+                    .collect::<Option<Vec<Estimate>>>();
+                let num_inputs = inputs.len();
+
                 // Determine if we can perform delta queries with the existing arrangements.
                 // We could defer the execution if we are sure we know we want one input,
                 // but we could imagine wanting the best from each and then comparing the two.
@@ -294,6 +317,7 @@ impl JoinImplementation {
                     &available_arrangements,
                     &unique_keys,
                     &filters,
+                    estimates.as_deref(),
                 );
                 let differential_plan = differential::plan(
                     relation,
@@ -301,11 +325,34 @@ impl JoinImplementation {
                     &available_arrangements,
                     &unique_keys,
                     &filters,
+                    estimates.as_deref(),
                 );
 
-                *relation = delta_query_plan
-                    .or(differential_plan)
-                    .expect("Failed to produce a join plan");
+                *relation = match (delta_query_plan, differential_plan) {
+                    (
+                        Ok((delta_query_plan, Some(delta_cost))),
+                        Ok((differential_plan, Some(differential_cost))),
+                    ) => {
+                        // A delta query produces intermediate results along each of its paths,
+                        // but maintains none of them, whereas a differential join produces its
+                        // intermediate results once, but must also maintain them in arrangements.
+                        // We charge each arranged record once per path of the alternative delta
+                        // query, so that we only forgo a delta query if some of its paths produce
+                        // disproportionately large intermediate results.
+                        let differential_cost = differential_cost * num_inputs as f64;
+                        if delta_cost <= differential_cost {
+                            delta_query_plan
+                        } else {
+                            differential_plan
+                        }
+                    }
+                    (delta_query_plan, differential_plan) => {
+                        delta_query_plan
+                            .or(differential_plan)
+                            .expect("Failed to produce a join plan")
+                            .0
+                    }
+                };
             }
         }
         Ok(())
@@ -363,16 +410,283 @@ mod index_map {
     }
 }
 
+mod estimates {
+    use std::collections::HashMap;
+
+    use mz_expr::{
+        func, Id, JoinImplementation, JoinInputCharacteristics, LocalId, MapFilterProject,
+        MirRelationExpr, MirScalarExpr, UnaryFunc,
+    };
+    use mz_repr::stats::CollectionStatistics;
+
+    use crate::StatisticsOracle;
+
+    /// The fraction of records assumed to pass a predicate we know nothing about.
+    const DEFAULT_SELECTIVITY: f64 = 1.0 / 3.0;
+
+    /// The fraction of records assumed to hold distinct values of an expression
+    /// for which we have no statistics.
+    const DEFAULT_DISTINCT_FRACTION: f64 = 0.1;
+
+    /// Estimated contents of a collection, derived from collection statistics.
+    ///
+    /// Estimates are deliberately crude, and are only meant to tell apart plans
+    /// whose costs differ substantially.
+    #[derive(Clone, Debug)]
+    pub struct Estimate {
+        /// The estimated number of records.
+        rows: f64,
+        /// The estimated number of records before any filters were applied,
+        /// which bounds the number of distinct values of any key.
+        domain: f64,
+        /// The estimated number of distinct values of each column, if known.
+        distinct: Vec<Option<f64>>,
+    }
+
+    impl Estimate {
+        fn from_statistics(stats: &CollectionStatistics) -> Self {
+            let rows = stats.num_rows as f64;
+            Estimate {
+                rows,
+                domain: rows,
+                distinct: (0..stats.distinct_values.len())
+                    .map(|c| stats.distinct(c).map(|d| d as f64))
+                    .collect(),
+            }
+        }
+
+        /// The estimated number of distinct values of `expr`.
+        fn distinct(&self, expr: &MirScalarExpr) -> f64 {
+            let distinct = match expr {
+                MirScalarExpr::Literal(..) => Some(1.0),
+                MirScalarExpr::Column(c) => self.distinct.get(*c).copied().flatten(),
+                _ => None,
+            };
+            distinct
+                .unwrap_or(self.domain * DEFAULT_DISTINCT_FRACTION)
+                .max(1.0)
+        }
+
+        /// The estimated number of distinct values of `key`.
+        fn key_distinct(&self, key: &[MirScalarExpr]) -> f64 {
+            key.iter()
+                .map(|k| self.distinct(k))
+                .product::<f64>()
+                .min(self.domain)
+                .max(1.0)
+        }
+
+        /// The estimated fraction of records that satisfy `predicate`.
+        fn selectivity(&self, predicate: &MirScalarExpr) -> f64 {
+            if let Some(expr) = predicate.any_expr_eq_literal() {
+                1.0 / self.distinct(&expr)
+            } else if let MirScalarExpr::CallUnary {
+                func: UnaryFunc::Not(func::Not),
+                expr,
+            } = predicate
+            {
+                if let MirScalarExpr::CallUnary {
+                    func: UnaryFunc::IsNull(func::IsNull),
+                    ..
+                } = &**expr
+                {
+                    // Most `IS NOT NULL` predicates are introduced by the optimizer,
+                    // and we have no statistics about nulls anyway.
+                    1.0
+                } else {
+                    DEFAULT_SELECTIVITY
+                }
+            } else {
+                DEFAULT_SELECTIVITY
+            }
+        }
+
+        /// Estimates the result of applying a map, filter, and project to `self`.
+        fn apply(
+            mut self,
+            map: &[MirScalarExpr],
+            filter: &[MirScalarExpr],
+            project: &[usize],
+        ) -> Self {
+            self.distinct.extend(map.iter().map(|expr| match expr {
+                MirScalarExpr::Literal(..) => Some(1.0),
+                _ => None,
+            }));
+            let selectivity = filter
+                .iter()
+                .map(|predicate| self.selectivity(predicate))
+                .product::<f64>();
+            self.rows *= selectivity;
+            self.distinct = project
+                .iter()
+                .map(|c| self.distinct.get(*c).copied().flatten())
+                .collect();
+            self
+        }
+
+        /// The estimated number of records matching each record looked up by `key`.
+        ///
+        /// An empty `key` describes a cross join.
+        pub fn fanout(&self, key: &[MirScalarExpr], is_unique: bool) -> f64 {
+            let fanout = if key.is_empty() {
+                self.rows
+            } else {
+                self.rows / self.key_distinct(key)
+            };
+            if is_unique {
+                fanout.min(1.0)
+            } else {
+                fanout
+            }
+        }
+    }
+
+    /// The estimated number of intermediate records produced by joining inputs in `order`.
+    ///
+    /// The output of the final input is not included, as it is the same for all orders.
+    pub fn intermediate_cost(
+        estimates: &[Estimate],
+        order: &[(JoinInputCharacteristics, Vec<MirScalarExpr>, usize)],
+    ) -> f64 {
+        let mut size = match order.first() {
+            Some((_, _, start)) => estimates[*start].rows,
+            None => return 0.0,
+        };
+        let mut cost = 0.0;
+        for (characteristics, key, input) in order.iter().take(order.len() - 1).skip(1) {
+            size *= estimates[*input].fanout(key, characteristics.unique_key);
+            cost += size;
+        }
+        cost
+    }
+
+    /// Keeps track of local and global statistics available while descending
+    /// a `MirRelationExpr`.
+    #[derive(Debug)]
+    pub struct StatisticsMap<'a> {
+        local: HashMap<LocalId, Estimate>,
+        global: &'a dyn StatisticsOracle,
+    }
+
+    impl StatisticsMap<'_> {
+        /// Creates a new statistics map with knowledge of the provided global
+        /// statistics.
+        pub fn new(global: &dyn StatisticsOracle) -> StatisticsMap {
+            StatisticsMap {
+                local: HashMap::new(),
+                global,
+            }
+        }
+
+        /// Records the estimated contents of the specified local collection.
+        pub fn add_local(&mut self, id: LocalId, estimate: Estimate) {
+            self.local.insert(id, estimate);
+        }
+
+        /// Removes the estimated contents of the specified local collection.
+        pub fn remove_local(&mut self, id: LocalId) {
+            self.local.remove(&id);
+        }
+
+        fn get(&self, id: Id) -> Option<Estimate> {
+            match id {
+                Id::Global(id) => self.global.statistics(id).map(Estimate::from_statistics),
+                Id::Local(id) => self.local.get(&id).cloned(),
+            }
+        }
+
+        /// Estimates the contents of `expr`, if it is of a form we understand
+        /// and statistics are available for the collections it reads.
+        pub fn estimate(&self, expr: &MirRelationExpr) -> Option<Estimate> {
+            let (mfp, input) = MapFilterProject::extract_non_errors_from_expr(expr);
+            let (map, filter, project) = mfp.as_map_filter_project();
+            Some(
+                self.estimate_unwrapped(input)?
+                    .apply(&map, &filter, &project),
+            )
+        }
+
+        /// Estimates the contents of the join input `expr`, to which the predicates
+        /// in `push_downs` will be applied once it has been joined.
+        ///
+        /// The predicates may already be present in the topmost map, filter, and
+        /// project of `expr`, in which case they are only accounted for once.
+        pub fn estimate_join_input(
+            &self,
+            expr: &MirRelationExpr,
+            push_downs: &[MirScalarExpr],
+        ) -> Option<Estimate> {
+            let (mfp, input) = MapFilterProject::extract_non_errors_from_expr(expr);
+            let (map, mut filter, project) = mfp.as_map_filter_project();
+            for predicate in push_downs {
+                let mut predicate = predicate.clone();
+                predicate.permute(&project);
+                if !filter.contains(&predicate) {
+                    filter.push(predicate);
+                }
+            }
+            Some(
+                self.estimate_unwrapped(input)?
+                    .apply(&map, &filter, &project),
+            )
+        }
+
+        /// Estimates the contents of `expr`, ignoring any map, filter, and project
+        /// around it.
+        fn estimate_unwrapped(&self, expr: &MirRelationExpr) -> Option<Estimate> {
+            match expr {
+                MirRelationExpr::Get { id, .. } => self.get(*id),
+                MirRelationExpr::ArrangeBy { input, .. } => self.estimate(input),
+                MirRelationExpr::Join {
+                    inputs,
+                    implementation: JoinImplementation::IndexedFilter(_, key, values),
+                    ..
+                } => {
+                    // A semijoin of the indexed collection with a list of values of its key.
+                    let mut estimate = self.estimate(&inputs[0])?;
+                    let values = values.len() as f64;
+                    estimate.rows *= (values / estimate.key_distinct(key)).min(1.0);
+                    estimate.distinct.extend(key.iter().map(|_| Some(values)));
+                    Some(estimate)
+                }
+                MirRelationExpr::Reduce {
+                    input,
+                    group_key,
+                    aggregates,
+                    ..
+                } => {
+                    let input = self.estimate(input)?;
+                    let rows = input.key_distinct(group_key).min(input.rows);
+                    let distinct = group_key
+                        .iter()
+                        .map(|k| Some(input.distinct(k).min(rows)))
+                        .chain(aggregates.iter().map(|_| None))
+                        .collect();
+                    Some(Estimate {
+                        rows,
+                        domain: rows,
+                        distinct,
+                    })
+                }
+                _ => None,
+            }
+        }
+    }
+}
+
 mod delta_queries {
 
     use mz_expr::{
         FilterCharacteristics, JoinImplementation, JoinInputMapper, MirRelationExpr, MirScalarExpr,
     };
 
+    use crate::join_implementation::estimates::{intermediate_cost, Estimate};
     use crate::TransformError;
 
     /// Creates a delta query plan, and any predicates that need to be lifted.
     ///
+    /// If `estimates` are provided, also returns the estimated cost of the plan.
+    ///
     /// The method returns `Err` if it fails to find a sufficiently pleasing plan or
     /// if any errors occur during planning.
     pub fn plan(
@@ -381,7 +695,8 @@ mod delta_queries {
         available: &[Vec<Vec<MirScalarExpr>>],
         unique_keys: &[Vec<Vec<usize>>],
         filters: &[FilterCharacteristics],
-    ) -> Result<MirRelationExpr, TransformError> {
+        estimates: Option<&[Estimate]>,
+    ) -> Result<(MirRelationExpr, Option<f64>), TransformError> {
         let mut new_join = join.clone();
 
         if let MirRelationExpr::Join {
@@ -402,8 +717,14 @@ mod delta_queries {
             }
 
             // Determine a viable order for each relation, or return `Err` if none found.
-            let orders =
-                super::optimize_orders(equivalences, available, unique_keys, filters, input_mapper);
+            let orders = super::optimize_orders(
+                equivalences,
+                available,
+                unique_keys,
+                filters,
+                estimates,
+                input_mapper,
+            );
 
             // A viable delta query requires that, for every order,
             // there is an arrangement for every input except for
@@ -417,6 +738,14 @@ mod delta_queries {
                 )));
             }
 
+            // Each path produces its own intermediate results.
+            let cost = estimates.map(|estimates| {
+                orders
+                    .iter()
+                    .map(|order| intermediate_cost(estimates, order))
+                    .sum()
+            });
+
             // Convert the order information into specific (input, keys) information.
             let orders = orders
                 .into_iter()
@@ -437,7 +766,7 @@ mod delta_queries {
             super::install_lifted_mfp(&mut new_join, lifted_mfp)?;
 
             // Hooray done!
-            Ok(new_join)
+            Ok((new_join, cost))
         } else {
             Err(TransformError::Internal(String::from(
                 "delta_queries::plan call on non-join expression",
@@ -447,6 +776,7 @@ mod delta_queries {
 }
 
 mod differential {
+    use crate::join_implementation::estimates::{intermediate_cost, Estimate};
     use crate::join_implementation::{FilterCharacteristics, JoinInputCharacteristics};
     use itertools::Itertools;
     use mz_expr::{JoinImplementation, JoinInputMapper, MirRelationExpr, MirScalarExpr};
@@ -455,13 +785,16 @@ mod differential {
     use crate::TransformError;
 
     /// Creates a linear differential plan, and any predicates that need to be lifted.
+    ///
+    /// If `estimates` are provided, also returns the estimated cost of the plan.
     pub fn plan(
         join: &MirRelationExpr,
         input_mapper: &JoinInputMapper,
         available: &[Vec<Vec<MirScalarExpr>>],
         unique_keys: &[Vec<Vec<usize>>],
         filters: &[FilterCharacteristics],
-    ) -> Result<MirRelationExpr, TransformError> {
+        estimates: Option<&[Estimate]>,
+    ) -> Result<(MirRelationExpr, Option<f64>), TransformError> {
         let mut new_join = join.clone();
 
         if let MirRelationExpr::Join {
@@ -475,8 +808,27 @@ mod differential {
             // Important, we should choose something stable under re-ordering, to converge under fixed
             // point iteration; we choose to start with the first input optimizing our criteria, which
             // should remain stable even when promoted to the first position.
-            let mut orders =
-                super::optimize_orders(equivalences, available, unique_keys, filters, input_mapper);
+            let mut orders = super::optimize_orders(
+                equivalences,
+                available,
+                unique_keys,
+                filters,
+                estimates,
+                input_mapper,
+            );
+
+            // If we have estimates, we first restrict our attention to the orders with the
+            // smallest estimated intermediate results, and only then consider characteristics.
+            let cost = estimates.map(|estimates| {
+                let costs = orders
+                    .iter()
+                    .map(|order| intermediate_cost(estimates, order))
+                    .collect::<Vec<_>>();
+                let min_cost = costs.iter().copied().fold(f64::INFINITY, f64::min);
+                let mut costs = costs.into_iter();
+                orders.retain(|_| costs.next() == Some(min_cost));
+                min_cost
+            });
 
             // Inside each order, we take the `FilterCharacteristics` from each element, and OR it
             // to every other element to the right. This is because we are gonna be looking for the
@@ -566,7 +918,7 @@ mod differential {
             super::install_lifted_mfp(&mut new_join, lifted_mfp)?;
 
             // Hooray done!
-            Ok(new_join)
+            Ok((new_join, cost))
         } else {
             Err(TransformError::Internal(String::from(
                 "differential::plan call on non-join expression.",
@@ -699,9 +1051,17 @@ fn optimize_orders(
     available: &[Vec<Vec<MirScalarExpr>>],
     unique_keys: &[Vec<Vec<usize>>],
     filters: &[FilterCharacteristics],
+    estimates: Option<&[Estimate]>,
     input_mapper: &JoinInputMapper,
 ) -> Vec<Vec<(JoinInputCharacteristics, Vec<MirScalarExpr>, usize)>> {
-    let mut orderer = Orderer::new(equivalences, available, unique_keys, filters, input_mapper);
+    let mut orderer = Orderer::new(
+        equivalences,
+        available,
+        unique_keys,
+        filters,
+        estimates,
+        input_mapper,
+    );
     (0..available.len())
         .map(move |i| orderer.optimize_order_for(i))
         .collect::<Vec<_>>()
@@ -713,6 +1073,7 @@ struct Orderer<'a> {
     arrangements: &'a [Vec<Vec<MirScalarExpr>>],
     unique_keys: &'a [Vec<Vec<usize>>],
     filters: &'a [FilterCharacteristics],
+    estimates: Option<&'a [Estimate]>,
     input_mapper: &'a JoinInputMapper,
     reverse_equivalences: Vec<Vec<(usize, usize)>>,
     unique_arrangement: Vec<Vec<bool>>,
@@ -732,6 +1093,7 @@ impl<'a> Orderer<'a> {
         arrangements: &'a [Vec<Vec<MirScalarExpr>>],
         unique_keys: &'a [Vec<Vec<usize>>],
         filters: &'a [FilterCharacteristics],
+        estimates: Option<&'a [Estimate]>,
         input_mapper: &'a JoinInputMapper,
     ) -> Self {
         let inputs = arrangements.len();
@@ -767,6 +1129,7 @@ impl<'a> Orderer<'a> {
             arrangements,
             unique_keys,
             filters,
+            estimates,
             input_mapper,
             reverse_equivalences,
             unique_arrangement,
@@ -803,25 +1166,13 @@ impl<'a> Orderer<'a> {
             {
                 self.arrangement_active[input].push(pos);
                 self.priority_queue.push((
-                    JoinInputCharacteristics::new(
-                        is_unique,
-                        0,
-                        true,
-                        self.filters[input].clone(),
-                        input,
-                    ),
+                    self.characteristics(input, &[], is_unique, true),
                     vec![],
                     input,
                 ));
             } else {
                 self.priority_queue.push((
-                    JoinInputCharacteristics::new(
-                        is_unique,
-                        0,
-                        false,
-                        self.filters[input].clone(),
-                        input,
-                    ),
+                    self.characteristics(input, &[], is_unique, false),
                     vec![],
                     input,
                 ));
@@ -847,7 +1198,7 @@ impl<'a> Orderer<'a> {
         // calculate characteristics of an arrangement, if any on the starting input
         // by default, there is no arrangement on the starting input
         let mut start_tuple = (
-            JoinInputCharacteristics::new(false, 0, false, self.filters[start].clone(), start),
+            self.characteristics(start, &[], false, false),
             vec![],
             start,
         );
@@ -872,13 +1223,7 @@ impl<'a> Orderer<'a> {
                 {
                     let is_unique = self.unique_arrangement[start][pos];
                     start_tuple = (
-                        JoinInputCharacteristics::new(
-                            is_unique,
-                            candidate_start_key.len(),
-                            true,
-                            self.filters[start].clone(),
-                            start,
-                        ),
+                        self.characteristics(start, &candidate_start_key, is_unique, true),
                         candidate_start_key,
                         start,
                     );
@@ -890,6 +1235,30 @@ impl<'a> Orderer<'a> {
         std::mem::replace(&mut self.order, Vec::new())
     }
 
+    /// Describes the characteristics of joining in `input` by `key`.
+    ///
+    /// If estimates are available, these include the number of records we expect
+    /// to find in `input` for each record looked up by `key`.
+    fn characteristics(
+        &self,
+        input: usize,
+        key: &[MirScalarExpr],
+        is_unique: bool,
+        arranged: bool,
+    ) -> JoinInputCharacteristics {
+        let fanout = self
+            .estimates
+            .map(|estimates| estimates[input].fanout(key, is_unique).ceil() as usize);
+        JoinInputCharacteristics::new(
+            is_unique,
+            fanout,
+            key.len(),
+            arranged,
+            self.filters[input].clone(),
+            input,
+        )
+    }
+
     /// Introduces a specific input and keys to the order, along with its characteristics.
     ///
     /// This method places a next element in the order, and updates the associated state
@@ -946,13 +1315,7 @@ impl<'a> Orderer<'a> {
                                             // TODO: This could be pre-computed, as it is independent of the order.
                                             let is_unique = self.unique_arrangement[rel][pos];
                                             self.priority_queue.push((
-                                                JoinInputCharacteristics::new(
-                                                    is_unique,
-                                                    keys.len(),
-                                                    true,
-                                                    self.filters[rel].clone(),
-                                                    rel,
-                                                ),
+                                                self.characteristics(rel, keys, is_unique, true),
                                                 keys.clone(),
                                                 rel,
                                             ));
@@ -965,13 +1328,7 @@ impl<'a> Orderer<'a> {
                                     })
                                 });
                                 self.priority_queue.push((
-                                    JoinInputCharacteristics::new(
-                                        is_unique,
-                                        self.bound[rel].len(),
-                                        false,
-                                        self.filters[rel].clone(),
-                                        rel,
-                                    ),
+                                    self.characteristics(rel, &self.bound[rel], is_unique, false),
                                     self.bound[rel].clone(),
                                     rel,
                                 ));
@@ -29,6 +29,7 @@ use tracing::error;
 use mz_expr::visit::Visit;
 use mz_expr::{MirRelationExpr, MirScalarExpr};
 use mz_ore::id_gen::IdGen;
+use mz_repr::stats::CollectionStatistics;
 use mz_repr::GlobalId;
 
 pub mod attribute;
@@ -70,6 +71,8 @@ pub struct TransformArgs<'a> {
     pub id_gen: &'a mut IdGen,
     /// The indexes accessible.
     pub indexes: &'a dyn IndexOracle,
+    /// Statistics about the contents of accessible collections.
+    pub stats: &'a dyn StatisticsOracle,
 }
 
 /// Types capable of transforming relation expressions.
@@ -138,6 +141,28 @@ impl IndexOracle for EmptyIndexOracle {
     }
 }
 
+/// A trait for a type that can answer questions about the contents of
+/// collections.
+pub trait StatisticsOracle: fmt::Debug {
+    /// Returns approximate statistics about the identified collection, if any
+    /// have been collected.
+    ///
+    /// Statistics are only ever estimates, and may be arbitrarily stale. They
+    /// may inform the choice between equivalent plans, but must never affect
+    /// the correctness of a plan.
+    fn statistics(&self, id: GlobalId) -> Option<&CollectionStatistics>;
+}
+
+/// A [`StatisticsOracle`] that knows about no statistics.
+#[derive(Debug)]
+pub struct EmptyStatisticsOracle;
+
+impl StatisticsOracle for EmptyStatisticsOracle {
+    fn statistics(&self, _: GlobalId) -> Option<&CollectionStatistics> {
+        None
+    }
+}
+
 /// A sequence of transformations iterated some number of times.
 #[derive(Debug)]
 pub struct Fixpoint {
@@ -183,6 +208,7 @@ impl Transform for Fixpoint {
                             TransformArgs {
                                 id_gen: args.id_gen,
                                 indexes: args.indexes,
+                                stats: args.stats,
                             },
                         )?;
                     }
@@ -207,6 +233,7 @@ impl Transform for Fixpoint {
                 TransformArgs {
                     id_gen: args.id_gen,
                     indexes: args.indexes,
+                    stats: args.stats,
                 },
             )?;
         }
@@ -290,6 +317,7 @@ impl Transform for FuseAndCollapse {
                 TransformArgs {
                     id_gen: args.id_gen,
                     indexes: args.indexes,
+                    stats: args.stats,
                 },
             )?;
         }
@@ -464,7 +492,8 @@ impl Optimizer {
         &mut self,
         mut relation: MirRelationExpr,
     ) -> Result<mz_expr::OptimizedMirRelationExpr, TransformError> {
-        let transform_result = self.transform(&mut relation, &EmptyIndexOracle);
+        let transform_result =
+            self.transform(&mut relation, &EmptyIndexOracle, &EmptyStatisticsOracle);
         match transform_result {
             Ok(_) => {
                 mz_repr::explain_new::trace_plan(&relation);
@@ -489,6 +518,7 @@ impl Optimizer {
         &self,
         relation: &mut MirRelationExpr,
         indexes: &dyn IndexOracle,
+        stats: &dyn StatisticsOracle,
     ) -> Result<(), TransformError> {
         let mut id_gen = Default::default();
         for transform in self.transforms.iter() {
@@ -497,6 +527,7 @@ impl Optimizer {
                 TransformArgs {
                     id_gen: &mut id_gen,
                     indexes,
+                    stats,
                 },
             )?;
         }
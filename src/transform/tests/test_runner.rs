@@ -27,9 +27,10 @@ mod tests {
     };
     use mz_lowertest::{deserialize, tokenize};
     use mz_ore::str::separated;
+    use mz_repr::stats::CollectionStatistics;
     use mz_repr::GlobalId;
     use mz_transform::dataflow::{optimize_dataflow_demand_inner, optimize_dataflow_filters_inner};
-    use mz_transform::{EmptyIndexOracle, Optimizer, Transform, TransformArgs};
+    use mz_transform::{EmptyIndexOracle, Optimizer, StatisticsOracle, Transform, TransformArgs};
     use proc_macro2::TokenTree;
 
     // Global options
@@ -56,6 +57,16 @@ mod tests {
                 .collect::<Vec<_>>();
     }
 
+    /// Exposes the statistics recorded in a [TestCatalog] to the optimizer.
+    #[derive(Debug)]
+    struct TestStatisticsOracle<'a>(&'a TestCatalog);
+
+    impl StatisticsOracle for TestStatisticsOracle<'_> {
+        fn statistics(&self, id: GlobalId) -> Option<&CollectionStatistics> {
+            self.0.get_statistics(&id)
+        }
+    }
+
     #[derive(Debug, Copy, Clone, PartialEq, Eq)]
     enum FormatType<'a> {
         Explain(Option<&'a Vec<String>>),
@@ -130,6 +141,7 @@ mod tests {
                 TransformArgs {
                     id_gen: &mut id_gen,
                     indexes: &EmptyIndexOracle,
+                    stats: &TestStatisticsOracle(cat),
                 },
             )?;
         }
@@ -144,6 +156,7 @@ mod tests {
                         TransformArgs {
                             id_gen: &mut id_gen,
                             indexes: &EmptyIndexOracle,
+                            stats: &TestStatisticsOracle(cat),
                         },
                     )?;
                 }
@@ -168,6 +181,7 @@ mod tests {
                             TransformArgs {
                                 id_gen: &mut id_gen,
                                 indexes: &EmptyIndexOracle,
+                                stats: &TestStatisticsOracle(cat),
                             },
                         )?;
 
@@ -238,6 +252,9 @@ mod tests {
             })),
             "FlatMapToMap" => Ok(Box::new(mz_transform::fusion::flatmap_to_map::FlatMapToMap)),
             "JoinFusion" => Ok(Box::new(mz_transform::fusion::join::Join)),
+            "JoinImplementation" => Ok(Box::new(
+                mz_transform::join_implementation::JoinImplementation::default(),
+            )),
             "LiteralLifting" => Ok(Box::new(
                 mz_transform::literal_lifting::LiteralLifting::default(),
             )),
@@ -0,0 +1,55 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+from materialize.mzcompose import Composition
+from materialize.mzcompose.services import (
+    Localstack,
+    Materialized,
+    Storaged,
+    Testdrive,
+)
+
+SERVICES = [
+    Localstack(),
+    Materialized(),
+    Storaged(),
+    Testdrive(default_timeout="120s", no_reset=True, consistent_seed=True),
+]
+
+
+def workflow_default(c: Composition) -> None:
+    """Test that Kinesis sources resume each shard exactly where they left off."""
+
+    c.start_and_wait_for_tcp(services=["localstack", "materialized", "storaged"])
+    c.wait_for_materialized()
+
+    c.run("testdrive", "setup.td")
+    c.run("testdrive", "verify.td")
+
+    restart_storaged(c)
+
+    # Verify the same data is query-able, and that we can make forward progress
+    c.run("testdrive", "verify.td")
+    c.run("testdrive", "re-ingest-and-verify.td")
+
+    restart_storaged(c)
+
+    # Split the shards of the stream, and verify that records written to the
+    # closed parent shards and to the new child shards are all ingested once.
+    c.run("testdrive", "reshard-and-verify.td")
+
+    restart_storaged(c)
+
+    c.run("testdrive", "reshard-verify.td")
+
+
+def restart_storaged(c: Composition) -> None:
+    c.kill("storaged")
+    c.up("storaged")
+    c.sleep(10)
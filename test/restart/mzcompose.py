@@ -167,8 +167,28 @@ def workflow_storage_managed_collections(c: Composition) -> None:
         raise Exception("user shards empty or not equal after restart")
 
 
+def workflow_kafka_include_topic(c: Composition) -> None:
+    """Test that a single-topic Kafka source that includes the topic resumes
+    correctly after a restart."""
+    c.down(destroy_volumes=True)
+    c.start_and_wait_for_tcp(
+        services=["zookeeper", "kafka", "schema-registry", "materialized"]
+    )
+    c.wait_for_materialized()
+
+    with c.override(Testdrive(no_reset=True, consistent_seed=True)):
+        c.run("testdrive", "kafka-include-topic-before.td")
+
+        c.kill("materialized")
+        c.up("materialized")
+        c.wait_for_materialized()
+
+        c.run("testdrive", "kafka-include-topic-after.td")
+
+
 def workflow_default(c: Composition) -> None:
     workflow_github_8021(c)
     workflow_audit_log(c)
     workflow_timelines(c)
     workflow_stash(c)
+    workflow_kafka_include_topic(c)